@@ -0,0 +1,678 @@
+use crate::embed::share_image_button;
+use crate::models::data::{ComponentStateData, Data};
+use crate::models::error::Error;
+use futures::future::BoxFuture;
+use mongodb::bson::{doc, DateTime};
+use mongodb::{Client, Collection};
+use poise::serenity_prelude as serenity;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serenity::builder::CreateEmbed;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// Prefix of the custom IDs of the components answered by `handle_persistent_component`.
+pub const PERSISTENT_COMPONENT_PREFIX: &str = "persist";
+
+/// How long the components of a message keep working after it was sent (24 hours).
+const COMPONENT_STATE_TTL_SECS: i64 = 24 * 3600;
+
+/// Kind of the components switching between the views of an embed.
+const VIEWS_COMPONENT_KIND: &str = "views";
+
+/// Kind of the previous/next buttons browsing the pages of an embed.
+const PAGES_COMPONENT_KIND: &str = "pages";
+
+/// ⚙️ **Type**: Answers the components of a kind registered with `register_component_handler`.
+///
+/// The handler is given the serenity context, the data of the bot, the interaction, then the key, the action (see
+/// `component_id`) and the payload of the component state.
+pub type ComponentHandler = for<'a> fn(
+    &'a serenity::Context,
+    &'a Data,
+    &'a serenity::ComponentInteraction,
+    &'a str,
+    &'a str,
+    &'a str,
+) -> BoxFuture<'a, Result<(), Error>>;
+
+static COMPONENT_HANDLERS: OnceLock<RwLock<HashMap<&'static str, ComponentHandler>>> =
+    OnceLock::new();
+
+/// ⚙️ **Type**: Builds the embed of a view the first time it is opened, from the payload saved with it.
+///
+/// The loader handles its own errors, e.g. by showing a fallback field, since the view is displayed either way.
+pub type ViewLoader = for<'a> fn(&'a Data, &'a str) -> BoxFuture<'a, CreateEmbed>;
+
+static VIEW_LOADERS: OnceLock<RwLock<HashMap<&'static str, ViewLoader>>> = OnceLock::new();
+
+/// ⚙️ **Type**: Builds the components shown under a page besides the previous/next buttons, or under a view besides
+/// the ones switching views, from the payload saved with them and the index of the page or view displayed.
+pub type PageRowsBuilder = fn(&str, usize) -> Result<Vec<serenity::CreateActionRow>, Error>;
+
+static PAGE_ROWS_BUILDERS: OnceLock<RwLock<HashMap<&'static str, PageRowsBuilder>>> =
+    OnceLock::new();
+
+/// Views of an embed, as stored in a component state.
+#[derive(Serialize, Deserialize)]
+struct ViewsState {
+    /// The views as `(label, embed)` pairs, the embeds serialized as in the Discord API. Views not loaded yet have a
+    /// `null` embed.
+    views: Vec<(String, Value)>,
+    /// Whether the views are switched with a select menu rather than buttons.
+    menu: bool,
+    /// The views not loaded yet, by index, as `(loader name, payload)` pairs.
+    #[serde(default)]
+    loaders: HashMap<usize, (String, String)>,
+    /// Whether the "Share as image" button is shown under the views.
+    #[serde(default)]
+    share: bool,
+    /// The components shown under the views, as a `(builder name, payload)` pair.
+    #[serde(default)]
+    rows: Option<(String, String)>,
+}
+
+/// Pages of an embed, as stored in a component state.
+#[derive(Serialize, Deserialize)]
+struct PagesState {
+    /// The pages, serialized as in the Discord API.
+    pages: Vec<Value>,
+    /// The components shown under each page, as a `(builder name, payload)` pair.
+    #[serde(default)]
+    rows: Option<(String, String)>,
+}
+
+/// Suggestions offered with buttons, as stored in a component state (see `send_suggestions`).
+#[derive(Serialize, Deserialize)]
+pub struct SuggestionsState {
+    /// The suggestions, one button each.
+    pub suggestions: Vec<String>,
+    /// The context of the command, given back with the suggestion picked.
+    pub context: String,
+}
+
+/// ⚙️ **Struct**: A view of an embed built only when it is first opened, for content that is slow or costly to fetch.
+pub struct LazyView {
+    /// The label of the button or menu option of the view.
+    pub label: String,
+    /// The name the loader was registered with (see `register_view_loader`).
+    pub loader: &'static str,
+    /// The context given to the loader, e.g. serialized as JSON.
+    pub payload: String,
+}
+
+/// ⚙️ **Struct**: Components shown under each page or view of an embed, e.g. to act on the entries of the page.
+pub struct PageRows {
+    /// The name the builder was registered with (see `register_page_rows`).
+    pub builder: &'static str,
+    /// The context given to the builder, e.g. serialized as JSON.
+    pub payload: String,
+}
+
+/// ⚙️ **Function**: Registers the handler of the persistent components of a kind.
+///
+/// # Parameters:
+/// - `kind`: The kind given to `save_component_state`.
+/// - `handler`: The function answering the components of this kind.
+///
+/// # Example:
+/// ```rust
+/// register_component_handler(CHAMPION_SUGGESTION_KIND, handle_champion_suggestion);
+/// ```
+///
+/// # Notes:
+/// - Handlers are registered at startup (see `module::register_components`), so the feature modules answer their own
+///   components without this module depending on them.
+pub fn register_component_handler(kind: &'static str, handler: ComponentHandler) {
+    COMPONENT_HANDLERS
+        .get_or_init(|| RwLock::new(HashMap::new()))
+        .write()
+        .unwrap()
+        .insert(kind, handler);
+}
+
+/// ⚙️ **Function**: Registers the loader of the lazy views with the given name.
+///
+/// # Parameters:
+/// - `name`: The name used by the `LazyView`s built by this loader.
+/// - `loader`: The function building the embed of the view.
+///
+/// # Example:
+/// ```rust
+/// register_view_loader(PRO_BUILDS_VIEW_LOADER, load_pro_builds_view);
+/// ```
+///
+/// # Notes:
+/// - Loaders are registered at startup (see `module::register_components`), so a view saved before a restart can still
+///   be loaded.
+pub fn register_view_loader(name: &'static str, loader: ViewLoader) {
+    VIEW_LOADERS
+        .get_or_init(|| RwLock::new(HashMap::new()))
+        .write()
+        .unwrap()
+        .insert(name, loader);
+}
+
+/// ⚙️ **Function**: Registers the builder of the components shown under pages or views with the given name.
+///
+/// # Parameters:
+/// - `name`: The name used by the `PageRows` built by this builder.
+/// - `builder`: The function building the components of a page or view.
+///
+/// # Example:
+/// ```rust
+/// register_page_rows(FOLLOW_LIST_ROWS, follow_list_rows);
+/// ```
+///
+/// # Notes:
+/// - Builders are registered at startup (see `module::register_components`), so the pages and views saved before a
+///   restart can still be browsed.
+pub fn register_page_rows(name: &'static str, builder: PageRowsBuilder) {
+    PAGE_ROWS_BUILDERS
+        .get_or_init(|| RwLock::new(HashMap::new()))
+        .write()
+        .unwrap()
+        .insert(name, builder);
+}
+
+/// ⚙️ **Function**: Returns the `component_states` collection.
+pub fn component_states_collection(mongo_client: &Client) -> Collection<ComponentStateData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<ComponentStateData>("component_states")
+}
+
+/// ⚙️ **Function**: Builds the custom ID of a persistent component.
+///
+/// # Parameters:
+/// - `key`: The key of the component state of the message (see `save_component_state`).
+/// - `action`: What the component does, e.g. the index of a view.
+///
+/// # Returns:
+/// - `String`: The custom ID, e.g. `persist:views-1234:2`.
+pub fn component_id(key: &str, action: &str) -> String {
+    format!("{}:{}:{}", PERSISTENT_COMPONENT_PREFIX, key, action)
+}
+
+/// ⚙️ **Function**: Saves the context needed to answer the components of a message.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `key`: The key shared by the custom IDs of the components (see `component_id`).
+/// - `kind`: What the components do, used by `handle_persistent_component` to answer them.
+/// - `author_id`: The only user allowed to use the components, `None` for everyone.
+/// - `payload`: The context of the components, stored as JSON.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if the state cannot be serialized or saved.
+///
+/// # Notes:
+/// - The states expire after 24 hours. MongoDB removes the expired ones with the TTL index of `expires_at`.
+pub async fn save_component_state(
+    mongo_client: &Client,
+    key: &str,
+    kind: &str,
+    author_id: Option<u64>,
+    payload: &impl Serialize,
+) -> Result<(), Error> {
+    let expires_at = chrono::Utc::now().timestamp() + COMPONENT_STATE_TTL_SECS;
+    let state = ComponentStateData {
+        key: key.to_string(),
+        kind: kind.to_string(),
+        author_id,
+        payload: serde_json::to_string(payload)?,
+        expires_at: DateTime::from_millis(expires_at * 1000),
+    };
+    component_states_collection(mongo_client)
+        .replace_one(doc! { "key": key }, &state)
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Answers a persistent component with the context saved by `save_component_state`.
+///
+/// # Parameters:
+/// - `ctx`: The serenity context.
+/// - `data`: The data of the bot.
+/// - `press`: The component interaction, whose custom ID starts with `PERSISTENT_COMPONENT_PREFIX`.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if the state cannot be read or the response cannot be sent.
+///
+/// # Notes:
+/// - The state is read from MongoDB rather than from the running command, so the components keep working after the
+///   bot restarts.
+/// - Users other than the author of the command get an ephemeral message instead.
+pub async fn handle_persistent_component(
+    ctx: &serenity::Context,
+    data: &Data,
+    press: &serenity::ComponentInteraction,
+) -> Result<(), Error> {
+    let mut parts = press.data.custom_id.splitn(3, ':').skip(1);
+    let (Some(key), Some(action)) = (parts.next(), parts.next()) else {
+        return Ok(());
+    };
+    let state = component_states_collection(&data.mongo_client)
+        // Le TTL de MongoDB passe toutes les minutes : un état expiré peut encore être là
+        .find_one(doc! { "key": key, "expires_at": { "$gte": DateTime::now() } })
+        .await?;
+    let Some(state) = state else {
+        return respond_ephemeral(
+            ctx,
+            press,
+            "These buttons have expired: run the command again.",
+        )
+        .await;
+    };
+    if state
+        .author_id
+        .is_some_and(|author_id| author_id != press.user.id.get())
+    {
+        return respond_ephemeral(
+            ctx,
+            press,
+            "Only the member who ran the command can use these buttons.",
+        )
+        .await;
+    }
+    match state.kind.as_str() {
+        VIEWS_COMPONENT_KIND => switch_view(ctx, data, press, key, action, &state.payload).await,
+        PAGES_COMPONENT_KIND => switch_page(ctx, press, key, action, &state.payload).await,
+        kind => {
+            let handler = COMPONENT_HANDLERS
+                .get()
+                .and_then(|handlers| handlers.read().unwrap().get(kind).copied());
+            match handler {
+                Some(handler) => handler(ctx, data, press, key, action, &state.payload).await,
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+/// ⚙️ **Function**: Answers a component with a message only visible to the user who used it.
+pub async fn respond_ephemeral(
+    ctx: &serenity::Context,
+    press: &serenity::ComponentInteraction,
+    content: &str,
+) -> Result<(), Error> {
+    press
+        .create_response(
+            ctx,
+            serenity::CreateInteractionResponse::Message(
+                serenity::CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Builds the buttons of suggestions, one per suggestion.
+///
+/// # Parameters:
+/// - `key`: The key of the component state of the message.
+/// - `suggestions`: The suggestions (5 at most are shown).
+///
+/// # Returns:
+/// - `serenity::CreateActionRow`: The row of buttons, whose action (see `component_id`) is the index of the suggestion.
+pub fn suggestion_components(key: &str, suggestions: &[String]) -> serenity::CreateActionRow {
+    serenity::CreateActionRow::Buttons(
+        suggestions
+            .iter()
+            .take(5)
+            .enumerate()
+            .map(|(index, suggestion)| {
+                serenity::CreateButton::new(component_id(key, &index.to_string()))
+                    .label(suggestion)
+                    .style(serenity::ButtonStyle::Secondary)
+            })
+            .collect(),
+    )
+}
+
+/// ⚙️ **Function**: Reads the suggestion picked with a button built by `suggestion_components`.
+///
+/// # Parameters:
+/// - `action`: The action of the button, the index of the suggestion.
+/// - `payload`: The `SuggestionsState` of the message, as JSON.
+///
+/// # Returns:
+/// - `Result<Option<(String, String)>, Error>`: The suggestion picked and the context of the command, `None` if the
+///   action is not a suggestion.
+pub fn chosen_suggestion(action: &str, payload: &str) -> Result<Option<(String, String)>, Error> {
+    let state: SuggestionsState = serde_json::from_str(payload)?;
+    Ok(action
+        .parse::<usize>()
+        .ok()
+        .and_then(|index| state.suggestions.get(index).cloned())
+        .map(|suggestion| (suggestion, state.context)))
+}
+
+/// ⚙️ **Function**: Prepares the views of an embed switched with buttons or a select menu.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `key`: A key unique to the message, e.g. built from the ID of the interaction.
+/// - `author_id`: The only user allowed to switch views.
+/// - `views`: The views as `(label, embed)` pairs. The first view is displayed initially.
+/// - `lazy_views`: The views built when they are first opened, placed after `views`.
+/// - `menu`: Whether the views are switched with a select menu rather than buttons.
+/// - `share`: Whether the "Share as image" button is shown under the views.
+/// - `rows`: The components shown under each view besides the ones switching views, if any (see `PageRows`).
+///
+/// # Returns:
+/// - `Result<Vec<serenity::CreateActionRow>, Error>`: The components of the message, only the `rows` with a single
+///   view.
+///
+/// # Example:
+/// ```rust
+/// let components = save_views(&mongo_client, &format!("views-{}", ctx.id()), author_id, &views, &[], false, false, None).await?;
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub async fn save_views(
+    mongo_client: &Client,
+    key: &str,
+    author_id: u64,
+    views: &[(String, CreateEmbed)],
+    lazy_views: &[LazyView],
+    menu: bool,
+    share: bool,
+    rows: Option<&PageRows>,
+) -> Result<Vec<serenity::CreateActionRow>, Error> {
+    let rows = rows.map(|rows| (rows.builder.to_string(), rows.payload.clone()));
+    if views.len() + lazy_views.len() < 2 {
+        return page_rows(rows.as_ref(), 0);
+    }
+    let mut state = ViewsState {
+        views: views
+            .iter()
+            .map(|(label, embed)| Ok((label.clone(), serde_json::to_value(embed)?)))
+            .collect::<Result<_, serde_json::Error>>()?,
+        menu,
+        loaders: HashMap::new(),
+        share,
+        rows,
+    };
+    for lazy_view in lazy_views {
+        state.loaders.insert(
+            state.views.len(),
+            (lazy_view.loader.to_string(), lazy_view.payload.clone()),
+        );
+        state.views.push((lazy_view.label.clone(), Value::Null));
+    }
+    save_component_state(
+        mongo_client,
+        key,
+        VIEWS_COMPONENT_KIND,
+        Some(author_id),
+        &state,
+    )
+    .await?;
+    let labels: Vec<&str> = state
+        .views
+        .iter()
+        .map(|(label, _)| label.as_str())
+        .collect();
+    view_components(key, &labels, 0, &state)
+}
+
+/// ⚙️ **Function**: Builds the buttons or the select menu switching between views, the current one disabled or
+/// selected, then the other components shown under the views.
+fn view_components(
+    key: &str,
+    labels: &[&str],
+    current: usize,
+    state: &ViewsState,
+) -> Result<Vec<serenity::CreateActionRow>, Error> {
+    let mut rows = vec![switch_components(key, labels, current, state.menu)];
+    if state.share {
+        rows.push(share_image_button());
+    }
+    rows.extend(page_rows(state.rows.as_ref(), current)?);
+    Ok(rows)
+}
+
+/// ⚙️ **Function**: Builds the row of buttons or the select menu of `view_components`.
+fn switch_components(
+    key: &str,
+    labels: &[&str],
+    current: usize,
+    menu: bool,
+) -> serenity::CreateActionRow {
+    if menu {
+        let options = labels
+            .iter()
+            .enumerate()
+            .map(|(index, label)| {
+                serenity::CreateSelectMenuOption::new(*label, index.to_string())
+                    .default_selection(index == current)
+            })
+            .collect();
+        return serenity::CreateActionRow::SelectMenu(
+            serenity::CreateSelectMenu::new(
+                component_id(key, "menu"),
+                serenity::CreateSelectMenuKind::String { options },
+            )
+            .placeholder("Choose a section"),
+        );
+    }
+    serenity::CreateActionRow::Buttons(
+        labels
+            .iter()
+            .enumerate()
+            .map(|(index, label)| {
+                serenity::CreateButton::new(component_id(key, &index.to_string()))
+                    .label(*label)
+                    .style(serenity::ButtonStyle::Secondary)
+                    .disabled(index == current)
+            })
+            .collect(),
+    )
+}
+
+/// ⚙️ **Function**: Displays the view chosen with a button or the select menu of an embed.
+///
+/// # Notes:
+/// - A view not loaded yet is built by its loader while Discord shows the interaction as pending, then saved so it is
+///   only built once.
+async fn switch_view(
+    ctx: &serenity::Context,
+    data: &Data,
+    press: &serenity::ComponentInteraction,
+    key: &str,
+    action: &str,
+    payload: &str,
+) -> Result<(), Error> {
+    let mut state: ViewsState = serde_json::from_str(payload)?;
+    // Le menu porte l'index dans la valeur choisie, les boutons dans leur identifiant
+    let selected = match &press.data.kind {
+        serenity::ComponentInteractionDataKind::StringSelect { values } => {
+            values.first().cloned().unwrap_or_default()
+        }
+        _ => action.to_string(),
+    };
+    let Some(index) = selected
+        .parse::<usize>()
+        .ok()
+        .filter(|index| *index < state.views.len())
+    else {
+        return Ok(());
+    };
+    let Some((loader_name, loader_payload)) = state.loaders.remove(&index) else {
+        let embed: serenity::Embed = serde_json::from_value(state.views[index].1.clone())?;
+        let labels: Vec<&str> = state
+            .views
+            .iter()
+            .map(|(label, _)| label.as_str())
+            .collect();
+        press
+            .create_response(
+                ctx,
+                serenity::CreateInteractionResponse::UpdateMessage(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .embed(CreateEmbed::from(embed))
+                        .components(view_components(key, &labels, index, &state)?),
+                ),
+            )
+            .await?;
+        return Ok(());
+    };
+    let loader = VIEW_LOADERS
+        .get()
+        .and_then(|loaders| loaders.read().unwrap().get(loader_name.as_str()).copied());
+    let Some(loader) = loader else {
+        return respond_ephemeral(ctx, press, "This view is not available anymore.").await;
+    };
+    // Le chargement peut dépasser les 3 secondes laissées par Discord pour répondre
+    press
+        .create_response(ctx, serenity::CreateInteractionResponse::Acknowledge)
+        .await?;
+    let embed = loader(data, &loader_payload).await;
+    state.views[index].1 = serde_json::to_value(&embed)?;
+    if let Err(e) = component_states_collection(&data.mongo_client)
+        .update_one(
+            doc! { "key": key },
+            doc! { "$set": { "payload": serde_json::to_string(&state)? } },
+        )
+        .await
+    {
+        log::error!("Failed to save the loaded view of {}: {:?}", key, e);
+    }
+    let labels: Vec<&str> = state
+        .views
+        .iter()
+        .map(|(label, _)| label.as_str())
+        .collect();
+    press
+        .edit_response(
+            ctx,
+            serenity::EditInteractionResponse::new()
+                .embed(embed)
+                .components(view_components(key, &labels, index, &state)?),
+        )
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Prepares the pages of an embed browsed with previous/next buttons.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `key`: A key unique to the message, e.g. built from the ID of the interaction.
+/// - `author_id`: The only user allowed to browse the pages.
+/// - `pages`: The pages of the embed.
+/// - `current`: The index of the page displayed.
+/// - `rows`: The components shown under each page besides the previous/next buttons, if any.
+///
+/// # Returns:
+/// - `Result<Vec<serenity::CreateActionRow>, Error>`: The components of the page displayed, without previous/next
+///   buttons with a single page.
+///
+/// # Example:
+/// ```rust
+/// let components = save_pages(&mongo_client, &format!("pages-{}", ctx.id()), author_id, &pages, 0, None).await?;
+/// ```
+///
+/// # Notes:
+/// - Saving the pages again under the same key replaces them, e.g. once the entries they list have changed.
+pub async fn save_pages(
+    mongo_client: &Client,
+    key: &str,
+    author_id: u64,
+    pages: &[CreateEmbed],
+    current: usize,
+    rows: Option<&PageRows>,
+) -> Result<Vec<serenity::CreateActionRow>, Error> {
+    let rows = rows.map(|rows| (rows.builder.to_string(), rows.payload.clone()));
+    let mut components = page_rows(rows.as_ref(), current)?;
+    if pages.len() < 2 {
+        return Ok(components);
+    }
+    let state = PagesState {
+        pages: pages
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<_, serde_json::Error>>()?,
+        rows,
+    };
+    save_component_state(
+        mongo_client,
+        key,
+        PAGES_COMPONENT_KIND,
+        Some(author_id),
+        &state,
+    )
+    .await?;
+    components.push(page_components(key, current, pages.len()));
+    Ok(components)
+}
+
+/// ⚙️ **Function**: Builds the components shown under a page or view with the builder registered for them, if any.
+fn page_rows(
+    rows: Option<&(String, String)>,
+    current: usize,
+) -> Result<Vec<serenity::CreateActionRow>, Error> {
+    let Some((builder_name, payload)) = rows else {
+        return Ok(Vec::new());
+    };
+    let builder = PAGE_ROWS_BUILDERS
+        .get()
+        .and_then(|builders| builders.read().unwrap().get(builder_name.as_str()).copied());
+    match builder {
+        Some(builder) => builder(payload, current),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// ⚙️ **Function**: Builds the previous/next buttons of a page, with the page number between them.
+fn page_components(key: &str, current: usize, count: usize) -> serenity::CreateActionRow {
+    serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(component_id(key, &current.saturating_sub(1).to_string()))
+            .label("◀ Previous")
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(current == 0),
+        serenity::CreateButton::new(component_id(key, "current"))
+            .label(format!("Page {}/{}", current + 1, count))
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(true),
+        serenity::CreateButton::new(component_id(key, &(current + 1).min(count - 1).to_string()))
+            .label("Next ▶")
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(current + 1 >= count),
+    ])
+}
+
+/// ⚙️ **Function**: Displays the page chosen with the previous/next buttons of an embed.
+async fn switch_page(
+    ctx: &serenity::Context,
+    press: &serenity::ComponentInteraction,
+    key: &str,
+    action: &str,
+    payload: &str,
+) -> Result<(), Error> {
+    let state: PagesState = serde_json::from_str(payload)?;
+    let Some(index) = action
+        .parse::<usize>()
+        .ok()
+        .filter(|index| *index < state.pages.len())
+    else {
+        return Ok(());
+    };
+    let embed: serenity::Embed = serde_json::from_value(state.pages[index].clone())?;
+    let mut components = page_rows(state.rows.as_ref(), index)?;
+    components.push(page_components(key, index, state.pages.len()));
+    press
+        .create_response(
+            ctx,
+            serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new()
+                    .embed(CreateEmbed::from(embed))
+                    .components(components),
+            ),
+        )
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// ⚙️ **Struct**: Intervals and limits of the bot, read from the secrets at startup.
+///
+/// Every value has a default, so a missing or invalid secret never prevents the bot from starting.
+#[derive(Debug, Clone)]
+pub struct BotConfig {
+    /// Delay between two checks of the followed summoners (`FOLLOW_POLL_INTERVAL_SECS`, 120 seconds by default).
+    pub follow_poll_interval: Duration,
+    /// Number of followed players checked at the same time during a poll (`FOLLOW_POLL_CONCURRENCY`, 4 by default).
+    pub follow_poll_concurrency: usize,
+    /// Delay between two scrapes of the champion statistics and reloads of Data Dragon
+    /// (`CHAMPION_REFRESH_INTERVAL_HOURS`, 24 hours by default).
+    pub champion_refresh_interval: Duration,
+    /// Delay before the answers of the commands are deleted (`MESSAGE_DELETION_DELAY_SECS`, 60 seconds by default).
+    pub message_deletion_delay: Duration,
+    /// Longest follow, in hours, that can be asked with `/followgames`, `/followuser` and `/team follow`
+    /// (`MAX_FOLLOW_HOURS`, 48 by default).
+    pub max_follow_hours: u32,
+    /// Most summoners that can be followed at the same time in a guild, so that one server cannot use the whole Riot
+    /// API budget (`MAX_FOLLOWS_PER_GUILD`, 10 by default).
+    pub max_follows_per_guild: u64,
+    /// Whether the Riot API key has access to the tournament API (`TOURNAMENT_API`), needed by the `tournament_code`
+    /// option of `/scrim log`. Standard keys get a `403` from the tournament endpoints.
+    pub tournament_api: bool,
+    /// Sandbox mode for local development (`DRY_RUN`): the Discord gateway is not started and the messages of the
+    /// background tasks are logged instead of posted (see `sandbox`).
+    pub dry_run: bool,
+    /// Folder of recorded Riot API responses served instead of the Riot API (`RIOT_FIXTURES_DIR`, see `fixtures`).
+    pub riot_fixtures_dir: Option<PathBuf>,
+    /// How the fixtures folder is used (`RIOT_FIXTURES_MODE`, `replay` by default).
+    pub riot_fixtures_mode: FixtureMode,
+}
+
+/// ⚙️ **Enum**: How the Riot API responses of `RIOT_FIXTURES_DIR` are used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Requests with a fixture are served from it, the others go to the Riot API (`replay`).
+    #[default]
+    Replay,
+    /// Requests without a fixture fail, so that a run never depends on the Riot API (`replay-only`).
+    ReplayOnly,
+    /// Every request goes to the Riot API and its response is saved as a fixture (`record`).
+    Record,
+}
+
+impl Default for BotConfig {
+    fn default() -> Self {
+        BotConfig {
+            follow_poll_interval: Duration::from_secs(120),
+            follow_poll_concurrency: 4,
+            champion_refresh_interval: Duration::from_secs(24 * 3600),
+            message_deletion_delay: Duration::from_secs(60),
+            max_follow_hours: 48,
+            max_follows_per_guild: 10,
+            tournament_api: false,
+            dry_run: false,
+            riot_fixtures_dir: None,
+            riot_fixtures_mode: FixtureMode::Replay,
+        }
+    }
+}
+
+static CONFIG: OnceLock<BotConfig> = OnceLock::new();
+
+/// ⚙️ **Function**: Reads the configuration of the bot from the secrets.
+///
+/// # Parameters:
+/// - `get`: Returns the value of a secret, e.g. `|name| secret_store.get(name)`.
+///
+/// # Returns:
+/// - `BotConfig`: The configuration. Missing values, and values that are not positive numbers, keep their default.
+///   `DRY_RUN` and `TOURNAMENT_API` are enabled with `1`, `true` or `yes`.
+///
+/// # Example:
+/// ```rust
+/// let config = parse_bot_config(|name| secret_store.get(name));
+/// init_config(config);
+/// ```
+pub fn parse_bot_config(get: impl Fn(&str) -> Option<String>) -> BotConfig {
+    let number = |name: &str| -> Option<u64> {
+        let value = get(name)?;
+        match value.trim().parse::<u64>() {
+            Ok(number) if number > 0 => Some(number),
+            _ => {
+                log::warn!("Ignoring invalid {} value '{}'.", name, value);
+                None
+            }
+        }
+    };
+    let flag = |name: &str| -> bool {
+        get(name).is_some_and(|value| matches!(value.trim(), "1" | "true" | "yes"))
+    };
+    let default = BotConfig::default();
+    BotConfig {
+        follow_poll_interval: number("FOLLOW_POLL_INTERVAL_SECS")
+            .map(Duration::from_secs)
+            .unwrap_or(default.follow_poll_interval),
+        follow_poll_concurrency: number("FOLLOW_POLL_CONCURRENCY")
+            .and_then(|concurrency| usize::try_from(concurrency).ok())
+            .unwrap_or(default.follow_poll_concurrency),
+        champion_refresh_interval: number("CHAMPION_REFRESH_INTERVAL_HOURS")
+            .map(|hours| Duration::from_secs(hours * 3600))
+            .unwrap_or(default.champion_refresh_interval),
+        message_deletion_delay: number("MESSAGE_DELETION_DELAY_SECS")
+            .map(Duration::from_secs)
+            .unwrap_or(default.message_deletion_delay),
+        max_follow_hours: number("MAX_FOLLOW_HOURS")
+            .and_then(|hours| u32::try_from(hours).ok())
+            .unwrap_or(default.max_follow_hours),
+        max_follows_per_guild: number("MAX_FOLLOWS_PER_GUILD")
+            .unwrap_or(default.max_follows_per_guild),
+        tournament_api: flag("TOURNAMENT_API"),
+        dry_run: flag("DRY_RUN"),
+        riot_fixtures_dir: get("RIOT_FIXTURES_DIR")
+            .filter(|dir| !dir.trim().is_empty())
+            .map(PathBuf::from),
+        riot_fixtures_mode: match get("RIOT_FIXTURES_MODE").as_deref().map(str::trim) {
+            None | Some("replay") => FixtureMode::Replay,
+            Some("replay-only") => FixtureMode::ReplayOnly,
+            Some("record") => FixtureMode::Record,
+            Some(value) => {
+                log::warn!("Ignoring invalid RIOT_FIXTURES_MODE value '{}'.", value);
+                FixtureMode::Replay
+            }
+        },
+    }
+}
+
+/// ⚙️ **Function**: Sets the configuration used by the whole bot. Only the first call has an effect.
+pub fn init_config(config: BotConfig) {
+    log::info!("Configuration: {:?}", config);
+    if CONFIG.set(config).is_err() {
+        log::warn!("The configuration was already initialized.");
+    }
+}
+
+/// ⚙️ **Function**: Returns the configuration of the bot, the default one if `init_config` was not called.
+pub fn config() -> &'static BotConfig {
+    CONFIG.get_or_init(BotConfig::default)
+}
+
+/// ⚙️ **Function**: Returns the footer telling when the answer of a command is deleted.
+///
+/// # Returns:
+/// - `String`: e.g. `This message will be deleted in 60 seconds.`
+pub fn deletion_notice() -> String {
+    format!(
+        "This message will be deleted in {} seconds.",
+        config().message_deletion_delay.as_secs()
+    )
+}
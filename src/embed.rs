@@ -1,31 +1,62 @@
-use crate::models::error::Error;
-use crate::models::modal::LolStatsModal;
+use crate::components::{
+    save_component_state, save_pages, save_views, suggestion_components, LazyView, PageRows,
+    SuggestionsState,
+};
+use crate::config::{config, deletion_notice};
+use crate::error_reporting::record_error;
+use crate::models::error::{Error, ErrorDetails, RateLimitedError};
+use crate::models::riot_id::RiotId;
+use crate::module::lolstats::season::current_season;
+use crate::rate_limiter::{estimated_riot_wait, join_command_queue};
 use crate::{
-    models::data::{Data, EmojiId},
+    models::data::{Data, EmbedTheme, EmojiId},
     utils::get_emoji,
 };
 use mongodb::Collection;
 use poise::ReplyHandle;
 use poise::{
     serenity_prelude::{self as serenity},
-    CreateReply,
+    CreateReply, Modal,
 };
+use serde::Serialize;
 use serde_json::Value;
 use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+use std::time::Instant;
 use tokio::time::{sleep, Duration};
 
-/// ⚙️ **Function**: Creates a rich embed message displaying League of Legends player stats and match details.
+/// Longest time a command waits in the Riot API queue before running anyway.
+const MAX_QUEUE_WAIT: Duration = Duration::from_secs(120);
+
+/// Rough time taken by each command ahead in the queue, used for the estimated wait.
+const QUEUE_SLOT_ESTIMATE: Duration = Duration::from_secs(2);
+
+/// Longest time the queue position stays displayed before its estimated wait is refreshed.
+const QUEUE_STATUS_REFRESH: Duration = Duration::from_secs(5);
+
+/// How long the "Fix and resubmit" button of an invalid modal, then the modal itself, wait for the user.
+const MODAL_RETRY_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How many times a modal is submitted with invalid values before the command gives up.
+const MAX_MODAL_ATTEMPTS: usize = 3;
+
+/// Custom ID of the "Share as image" button, handled by `event_handler` whatever the message it is attached to.
+pub const SHARE_IMAGE_BUTTON_ID: &str = "share-image";
+
+/// ⚙️ **Function**: Creates the overview embed of `/lolstats`, displaying League of Legends player ranks and recent form.
 ///
 /// This function constructs a `CreateEmbed` message containing information about the player's Solo/Duo and Flex ranks,
-/// top champions, and detailed information about recent matches. The generated embed is used for displaying formatted
-/// stats in Discord messages.
+/// their season ranks and the record of their recent matches. The top champions and the details of the matches have
+/// their own views (see `create_embed_lolstats_champions` and `create_embed_lolstats_matches`).
 ///
 /// # Parameters:
-/// - `modal_data`: Contains the player's in-game name and tag, used to personalize the embed title.
+/// - `riot_id`: The Riot ID of the player, used to personalize the embed title.
+/// - `summoner_level`: The summoner level of the player, displayed next to their Riot ID.
+/// - `profile_icon`: The URL of the player's profile icon, used as the thumbnail (see `profile_icon_url`).
 /// - `solo_rank`: A JSON-like value containing the player's Solo/Duo rank information, including tier, division, LP, wins, losses, and winrate.
 /// - `flex_rank`: A JSON-like value containing the player's Flex rank information, similar to `solo_rank`.
-/// - `champions_info`: A formatted string representing the player's top champions, their levels, and mastery points.
-/// - `match_details`: A vector of JSON-like values representing detailed match information, including K/D/A, farm, game duration, and result.
+/// - `season_overview`: The peak rank of the current season and the end rank of the previous season, one line per queue.
+/// - `match_details`: A vector of JSON-like values representing detailed match information, used for the recent form.
+/// - `theme`: The guild's embed theme, providing the embed color and the optional brand line of the footer.
 ///
 /// # Returns:
 /// - `CreateEmbed`: The formatted embed message ready to be sent in a Discord channel.
@@ -36,28 +67,30 @@ use tokio::time::{sleep, Duration};
 ///
 /// # Example:
 /// ```rust
-/// let embed = create_embed(modal_data, solo_rank, flex_rank, champions_info, match_details);
+/// let embed = create_embed(&riot_id, 512, &profile_icon, solo_rank, flex_rank, season_overview, &match_details, &theme, collection_emoji);
 /// ctx.send(|m| m.set_embed(embed)).await?;
 /// ```
 ///
 /// The resulting embed will contain information such as:
 /// ```text
-/// 📊 Stats for Faker#1234
-/// 🔱 **Solo/Duo Rank**: Gold I (100 LP)
+/// 📊 Stats for Faker#1234 · Level 512
+/// 🔱 **Solo/Duo Rank**: Gold I (100 LP) · Top 28% of EUW
+/// Promos: W-L-_
 /// 🌀 **Flex Rank**: Silver IV (50 LP)
-/// 💥 **Top Champions**:
-/// Yasuo - Level: 7 - Points: 123456
-/// 📜 **Match Details**:
-/// Victory - **Yasuo**, 2 hours ago (Ranked Solo/Duo):
-/// K/D/A: **10/2/8** | **200 CS** | Duration: **30:45**
-/// ⏳ Played: **2 hours ago**
+/// 📅 **Season 2026**: Solo/Duo: peak **Gold I** - 100 LP · last season **Silver I** - 20 LP
+/// 📜 **Recent Form**: ✅✅❌✅❌ · 3W - 2L
 /// ```
+///
+/// When a teammate went AFK in a lost match, a `📈 Recent Winrate` field also shows the winrate without these losses.
 pub async fn create_embed(
-    modal_data: &LolStatsModal,
+    riot_id: &RiotId,
+    summoner_level: i64,
+    profile_icon: &str,
     solo_rank: Value,
     flex_rank: Value,
-    champions_info: String,
-    match_details: Vec<Value>,
+    season_overview: String,
+    match_details: &[Value],
+    theme: &EmbedTheme,
     collection_emoji: Collection<EmojiId>,
 ) -> Result<CreateEmbed, Error> {
     // Récupérer les émojis pour le rang solo et flex
@@ -90,6 +123,16 @@ pub async fn create_embed(
     } else {
         format!("**{}**", solo_emoji)
     };
+    let solo_rank_str = match solo_rank["ladder_percentile"].as_str().unwrap_or("") {
+        "" => solo_rank_str,
+        ladder_percentile => format!("{} · {}", solo_rank_str, ladder_percentile),
+    };
+    let solo_promos = solo_rank["promos"].as_str().unwrap_or("");
+    let solo_rank_str = if solo_promos.is_empty() {
+        solo_rank_str
+    } else {
+        format!("{}\n{}", solo_rank_str, solo_promos)
+    };
 
     // Construction de la chaîne du rang Flex
     let flex_rank_str = if flex_rank["lp"].as_i64().unwrap_or(0) > 0 {
@@ -113,55 +156,256 @@ pub async fn create_embed(
 
     // Construction de l'embed
     let embed = CreateEmbed::default()
-        .title(format!("📊 Stats for **{}#{}**", modal_data.game_name, modal_data.tag_line))
-        .color(0x00ff00)
-        .field("**Solo/Duo Rank**", solo_rank_str, false)
-        .field("🏆 **Wins**", format!("**{}**", solo_rank["wins"].as_i64().unwrap_or(-1)), true)
-        .field("❌ **Losses**", format!("**{}**", solo_rank["losses"].as_i64().unwrap_or(-1)), true)
+        .title(format!(
+            "📊 Stats for **{}** · Level {}",
+            riot_id, summoner_level
+        ))
+        .color(theme.info())
+        .field("**Solo/Duo Rank** (current split)", solo_rank_str, false)
+        .field(
+            "🏆 **Wins**",
+            format!("**{}**", solo_rank["wins"].as_i64().unwrap_or(-1)),
+            true,
+        )
+        .field(
+            "❌ **Losses**",
+            format!("**{}**", solo_rank["losses"].as_i64().unwrap_or(-1)),
+            true,
+        )
         .field(
             "📊 **Winrate**",
-            format!("**{:.2}%**", solo_rank["winrate"].as_f64().unwrap_or(-1.0)),
-            true
+            progress_bar(solo_rank["winrate"].as_f64().unwrap_or(0.0)),
+            true,
+        )
+        .field("**Flex Rank** (current split)", flex_rank_str, false)
+        .field(
+            "🏆 **Wins**",
+            format!("**{}**", flex_rank["wins"].as_i64().unwrap_or(-1)),
+            true,
+        )
+        .field(
+            "❌ **Losses**",
+            format!("**{}**", flex_rank["losses"].as_i64().unwrap_or(-1)),
+            true,
         )
-        .field("**Flex Rank**", flex_rank_str, false)
-        .field("🏆 **Wins**", format!("**{}**", flex_rank["wins"].as_i64().unwrap_or(-1)), true)
-        .field("❌ **Losses**", format!("**{}**", flex_rank["losses"].as_i64().unwrap_or(-1)), true)
         .field(
             "📊 **Winrate**",
-            format!("**{:.2}%**", flex_rank["winrate"].as_f64().unwrap_or(-1.0)),
-            true
+            progress_bar(flex_rank["winrate"].as_f64().unwrap_or(0.0)),
+            true,
         )
-        .field("💥 **Top Champions**", champions_info, false)
         .field(
-            "📜 **Match Details**",
-            if match_details.is_empty() {
-                "No match found on Normal and ranked game".to_string()
-            } else {
-                match_details
-                    .iter()
-                    .map(|match_detail| {
-                        format!(
-                            "{} - **{}**, {} ({}):\nK/D/A: **{}** | **{} CS** | Duration: **{}**\n⏳ Played: **{}**\n\n",
-                            match_detail.get("Result").unwrap().as_str().unwrap(),
-                            match_detail.get("champion_name").unwrap().as_str().unwrap(),
-                            match_detail.get("time_elapsed").unwrap().as_str().unwrap(),
-                            match_detail.get("game_type").unwrap().as_str().unwrap(),
-                            match_detail.get("K/D/A").unwrap().as_str().unwrap(),
-                            match_detail.get("Farm").unwrap().as_u64().unwrap(),
-                            match_detail.get("Duration").unwrap().as_str().unwrap(),
-                            match_detail.get("time_elapsed").unwrap().as_str().unwrap()
-                        )
-                    })
-                    .collect::<String>()
-            },
-            false
+            format!("📅 **Season {}**", current_season()),
+            season_overview,
+            false,
         )
-        .footer(CreateEmbedFooter::new("This message will be deleted in 60 seconds."))
-        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+        .field(
+            "📜 **Recent Form**",
+            format_recent_form(match_details),
+            false,
+        )
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail(profile_icon);
+    let embed = match format_recent_winrate(match_details) {
+        Some(recent_winrate) => embed.field("📈 **Recent Winrate**", recent_winrate, false),
+        None => embed,
+    };
 
     Ok(embed)
 }
 
+/// ⚙️ **Function**: Creates the matches view of `/lolstats`, with the details of each recent match.
+///
+/// # Parameters:
+/// - `riot_id`: The Riot ID of the player, used to personalize the embed title.
+/// - `match_details`: The recent matches produced by `extract_match_info`.
+/// - `profile_icon`: The URL of the player's profile icon, used as the thumbnail.
+/// - `theme`: The guild's embed theme.
+///
+/// # Returns:
+/// - `CreateEmbed`: The embed, with one paragraph per match in its description.
+///
+/// # Example:
+/// ```text
+/// 📜 Matches of **Faker#1234**
+/// Victory - **Yasuo**, <t:1625000000:R> (Ranked Solo/Duo):
+/// K/D/A: **10/2/8** (9.0 KDA) | **200 CS** (6.5/min) | **25.3k** damage
+/// Duration: **30:45** | ⏳ Played: **<t:1625000000:f>**
+/// ```
+pub fn create_embed_lolstats_matches(
+    riot_id: &RiotId,
+    match_details: &[Value],
+    profile_icon: &str,
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let description = if match_details.is_empty() {
+        "No match found on Normal and ranked game".to_string()
+    } else {
+        match_details
+            .iter()
+            .map(|match_detail| {
+                format!(
+                    "{} - **{}**, {} ({}):\nK/D/A: **{}** ({:.1} KDA) | **{} CS** ({:.1}/min) | **{:.1}k** damage\nDuration: **{}** | ⏳ Played: **{}**\n{}",
+                    match_detail["Result"].as_str().unwrap_or(""),
+                    match_detail["champion_name"].as_str().unwrap_or(""),
+                    match_detail["time_elapsed"].as_str().unwrap_or(""),
+                    match_detail["game_type"].as_str().unwrap_or(""),
+                    match_detail["K/D/A"].as_str().unwrap_or(""),
+                    match_detail["kda_ratio"].as_f64().unwrap_or(0.0),
+                    match_detail["Farm"].as_u64().unwrap_or(0),
+                    match_detail["cs_per_minute"].as_f64().unwrap_or(0.0),
+                    match_detail["damage"].as_u64().unwrap_or(0) as f64 / 1000.0,
+                    match_detail["Duration"].as_str().unwrap_or(""),
+                    match_detail["played_at"].as_str().unwrap_or(""),
+                    match_detail["leavers"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|leaver| leaver.as_str())
+                        .map(|leaver| format!("⚠️ {}\n", leaver))
+                        .collect::<String>()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    CreateEmbed::default()
+        .title(format!("📜 Matches of **{}**", riot_id))
+        .description(description)
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail(profile_icon)
+}
+
+/// ⚙️ **Function**: Creates the champions view of `/lolstats`, with the mastery of the top champions.
+///
+/// # Parameters:
+/// - `riot_id`: The Riot ID of the player, used to personalize the embed title.
+/// - `champions_info`: The top champions formatted by `extract_champions_info`.
+/// - `profile_icon`: The URL of the player's profile icon, used as the thumbnail.
+/// - `theme`: The guild's embed theme.
+///
+/// # Returns:
+/// - `CreateEmbed`: The embed, with the top champions in its description.
+pub fn create_embed_lolstats_champions(
+    riot_id: &RiotId,
+    champions_info: String,
+    profile_icon: &str,
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let description = if champions_info.is_empty() {
+        "No champion mastery yet.".to_string()
+    } else {
+        champions_info
+    };
+    CreateEmbed::default()
+        .title(format!("💥 Top champions of **{}**", riot_id))
+        .description(description)
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail(profile_icon)
+}
+
+/// ⚙️ **Function**: Formats the results of the recent matches, e.g. `✅✅❌✅❌ · 3W - 2L`.
+///
+/// # Parameters:
+/// - `match_details`: The recent matches produced by `extract_match_info`, the last one first.
+///
+/// # Returns:
+/// - `String`: One mark per match followed by the record, or a message when no match was found.
+fn format_recent_form(match_details: &[Value]) -> String {
+    if match_details.is_empty() {
+        return "No match found on Normal and ranked game".to_string();
+    }
+    let marks: String = match_details
+        .iter()
+        .map(|match_detail| {
+            if match_detail["Result"].as_str() == Some("Victory") {
+                "✅"
+            } else {
+                "❌"
+            }
+        })
+        .collect();
+    let wins = match_details
+        .iter()
+        .filter(|match_detail| match_detail["Result"].as_str() == Some("Victory"))
+        .count();
+    format!("{} · {}W - {}L", marks, wins, match_details.len() - wins)
+}
+
+/// ⚙️ **Function**: Formats the winrate of the recent matches, with and without the losses where a teammate went AFK.
+///
+/// # Parameters:
+/// - `match_details`: The recent matches produced by `extract_match_info`.
+///
+/// # Returns:
+/// - `Option<String>`: A line such as `3W - 2L (60%) · 75% without the 1 loss with an AFK ally`, or `None` when no
+///   loss had an AFK teammate (or when every match did).
+fn format_recent_winrate(match_details: &[Value]) -> Option<String> {
+    let wins = match_details
+        .iter()
+        .filter(|match_detail| match_detail["Result"].as_str() == Some("Victory"))
+        .count();
+    let losses = match_details.len() - wins;
+    let afk_losses = match_details
+        .iter()
+        .filter(|match_detail| {
+            match_detail["Result"].as_str() != Some("Victory")
+                && match_detail["ally_leaver"].as_bool().unwrap_or(false)
+        })
+        .count();
+    if afk_losses == 0 || afk_losses == match_details.len() {
+        return None;
+    }
+    Some(format!(
+        "{}W - {}L ({:.0}%) · {:.0}% without the {} loss{} with an AFK ally",
+        wins,
+        losses,
+        wins as f64 / match_details.len() as f64 * 100.0,
+        wins as f64 / (match_details.len() - afk_losses) as f64 * 100.0,
+        afk_losses,
+        if afk_losses > 1 { "es" } else { "" }
+    ))
+}
+
+/// ⚙️ **Function**: Renders a percentage as a Unicode block progress bar.
+///
+/// # Parameters:
+/// - `percent`: The percentage to render, between 0 and 100. Values outside this range are clamped for the bar.
+///
+/// # Returns:
+/// - `String`: A 5-segment bar followed by the percentage, e.g. `▰▰▰▱▱ 62%`.
+///
+/// # Example:
+/// ```rust
+/// assert_eq!(progress_bar(62.0), "▰▰▰▱▱ 62%");
+/// ```
+///
+/// # Notes:
+/// - Each segment stands for 20%; the number of filled segments is rounded to the nearest one.
+/// - The percentage keeps one decimal when it is not a whole number (e.g., `51.7%`).
+pub fn progress_bar(percent: f64) -> String {
+    const SEGMENTS: usize = 5;
+    let filled = ((percent.clamp(0.0, 100.0) / 100.0) * SEGMENTS as f64).round() as usize;
+    let value = if (percent * 10.0).round() % 10.0 == 0.0 {
+        format!("{:.0}%", percent)
+    } else {
+        format!("{:.1}%", percent)
+    };
+    format!(
+        "{}{} {}",
+        "▰".repeat(filled),
+        "▱".repeat(SEGMENTS - filled),
+        value
+    )
+}
+
 /// ⚙️ **Function**: Creates an embed displaying an error message for Discord interactions.
 ///
 /// This function constructs a Discord embed message that displays a given error message in a formatted way.
@@ -179,8 +423,9 @@ pub async fn create_embed(
 /// # ⚠️ Notes:
 /// - The embed's color is set to red (`0xff0000`) to visually signify an error.
 /// - The title of the embed is always set to "Error", and the provided `error_message` is used in the description.
-/// - The function is primarily used to provide user-friendly error messages in response to invalid inputs
-///   or issues in API calls.
+/// - The function is primarily used to provide user-friendly error messages in response to invalid inputs.
+///   Errors coming from a failed request should use `create_embed_coded_error`, which displays their own error code.
+/// - The embed always shows the `BOT-USAGE` code with its cause and suggested fix.
 ///
 /// # Example:
 /// ```rust
@@ -197,21 +442,96 @@ pub fn create_embed_error(error_message: &str) -> CreateReply {
     let embed: CreateEmbed = CreateEmbed::default()
         .title("Error")
         .description(error_message)
-        .color(0xff0000)
-        .footer(CreateEmbedFooter::new(
-            "This message will be deleted in 60 seconds.",
-        ))
-        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+        .color(0xff0000);
     CreateReply {
-        embeds: vec![embed],
+        embeds: vec![add_error_details_fields(embed, &ErrorDetails::usage())],
+        ..Default::default()
+    }
+}
+
+/// ⚙️ **Function**: Creates an error embed reply for a failed request, with its error code, likely cause and suggested fix.
+///
+/// The error is classified by `ErrorDetails::from_error` and kept in the recent errors log (see `/owner errors`).
+/// When the request failed because Riot is rate limiting the bot, the embed tells the user how long to wait instead of
+/// showing the raw error.
+///
+/// # Parameters:
+/// - `error_context`: A short description of the failed operation (e.g. `"Error fetching PUUID"`), or an empty string.
+/// - `error`: The error returned by the failed operation.
+///
+/// # Returns:
+/// - `CreateReply`: A Discord reply containing the constructed embed.
+///
+/// # Example:
+/// ```rust
+/// let reply = ctx.send(create_embed_coded_error("Error fetching PUUID", &e)).await?;
+/// ```
+///
+/// The resulting embed will look like this:
+/// ```text
+/// ❌ **Error**
+/// Error fetching PUUID: The player could not be found. ...
+/// Code: RIOT-404-SUMMONER
+/// Likely cause: No Riot account matches this game name and tag line.
+/// Suggested fix: Check the spelling of the Riot ID (Name#TAG) and the selected region.
+/// ```
+pub fn create_embed_coded_error(error_context: &str, error: &Error) -> CreateReply {
+    let details = ErrorDetails::from_error(&**error);
+    let message = if error_context.is_empty() {
+        error.to_string()
+    } else {
+        format!("{}: {}", error_context, error)
+    };
+    record_error(&details, &message);
+    let embed = match error.downcast_ref::<RateLimitedError>() {
+        Some(rate_limited) => CreateEmbed::default()
+            .title("⏳ Rate limited")
+            .description(rate_limited.to_string())
+            .color(0xFFA500),
+        None => CreateEmbed::default()
+            .title("Error")
+            .description(message)
+            .color(0xff0000),
+    };
+    CreateReply {
+        embeds: vec![add_error_details_fields(embed, &details)],
         ..Default::default()
     }
 }
 
+/// ⚙️ **Function**: Adds the correlation ID of an error to the embeds of an error reply.
+///
+/// The same ID is logged server-side with the full error, so a user reporting it lets the bot owners find what
+/// happened.
+///
+/// # Parameters:
+/// - `reply`: The error reply, e.g. built by `create_embed_coded_error`.
+/// - `correlation_id`: The ID of the error.
+///
+/// # Returns:
+/// - `CreateReply`: The reply with a "Reference" field on each embed.
+pub fn add_correlation_id(mut reply: CreateReply, correlation_id: &str) -> CreateReply {
+    reply.embeds = reply
+        .embeds
+        .into_iter()
+        .map(|embed| embed.field("Reference", format!("`{}`", correlation_id), true))
+        .collect();
+    reply
+}
+
+fn add_error_details_fields(embed: CreateEmbed, details: &ErrorDetails) -> CreateEmbed {
+    embed
+        .field("Code", format!("`{}`", details.code), true)
+        .field("Likely cause", details.cause, false)
+        .field("Suggested fix", details.hint, false)
+        .footer(CreateEmbedFooter::new(deletion_notice()))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
+
 /// ⚙️ **Function**: Creates a success embed reply for Discord messages.
 ///
 /// This function generates a Discord embed with the title "Sucess", a description provided by the `sucess_message` parameter,
-/// a green color to indicate success, and a footer notifying that the message will be deleted in 60 seconds. It returns a
+/// a green color to indicate success, and a footer notifying when the message will be deleted. It returns a
 /// `CreateReply` containing the embed, suitable for sending as a response to a Discord interaction or message.
 ///
 /// # Parameters:
@@ -221,7 +541,7 @@ pub fn create_embed_error(error_message: &str) -> CreateReply {
 /// - `CreateReply`: A Discord reply containing the constructed success embed.
 ///
 /// # ⚠️ Notes:
-/// - The embed's footer is in French: "This message will be deleted in 60 seconds." ("This message will be deleted in 60 seconds.").
+/// - The embed's footer is the deletion notice of the configuration (see `deletion_notice`).
 /// - The title "Sucess" appears to have a typo and might be intended as "Success".
 /// - The embed uses a green color (`0x00ff00`) to visually indicate a successful operation.
 ///
@@ -235,9 +555,7 @@ pub fn create_embed_sucess(sucess_message: &str) -> CreateReply {
         .title("Sucess")
         .description(sucess_message)
         .color(0x00ff00)
-        .footer(CreateEmbedFooter::new(
-            "This message will be deleted in 60 seconds.",
-        ))
+        .footer(CreateEmbedFooter::new(deletion_notice()))
         .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
     CreateReply {
         embeds: vec![embed],
@@ -247,7 +565,7 @@ pub fn create_embed_sucess(sucess_message: &str) -> CreateReply {
 
 /// ⚙️ **Function**: Schedules the deletion of a Discord message after a delay.
 ///
-/// This function delays the deletion of a Discord message by 60 seconds. After the delay, the function attempts
+/// This function delays the deletion of a Discord message by `MESSAGE_DELETION_DELAY_SECS` (60 seconds by default). After the delay, the function attempts
 /// to delete the message from the channel. It ensures that messages sent by the bot (e.g., error messages or
 /// status updates) are automatically removed after a certain time to keep the chat clean.
 ///
@@ -262,7 +580,7 @@ pub fn create_embed_sucess(sucess_message: &str) -> CreateReply {
 ///   or deleting it, returns an `Error`.
 ///
 /// # ⚠️ Notes:
-/// - The function uses `tokio::time::sleep` to pause execution for 60 seconds before attempting to delete the message.
+/// - The function uses `tokio::time::sleep` to pause execution for the configured delay before attempting to delete the message.
 /// - If the message cannot be fetched (e.g., due to permissions or being deleted manually), the deletion attempt will silently fail.
 /// - This function is typically used in scenarios where temporary messages (like errors or status updates)
 ///   need to be cleaned up automatically after a short period.
@@ -272,14 +590,512 @@ pub fn create_embed_sucess(sucess_message: &str) -> CreateReply {
 /// schedule_message_deletion(sent_message, ctx).await?;
 /// ```
 ///
-/// After 60 seconds, the message will be deleted from the Discord channel.
+/// After the delay, the message will be deleted from the Discord channel.
 pub async fn schedule_message_deletion(
     sent_message: ReplyHandle<'_>,
     ctx: poise::ApplicationContext<'_, Data, Error>,
 ) -> Result<(), Error> {
-    sleep(Duration::from_secs(60)).await;
+    sleep(config().message_deletion_delay).await;
     if let Ok(sent_msg) = sent_message.message().await {
         sent_msg.delete(&ctx.serenity_context().http).await?;
     }
     Ok(())
 }
+
+/// ⚙️ **Function**: Sends the "Fetching data…" placeholder of a command that calls the Riot API.
+///
+/// The interaction is deferred first (when it has not been answered yet, e.g. by a modal), so Discord's 3-second
+/// deadline is met even if the Riot API is slow. The placeholder is then edited into the final embed with
+/// `edit_and_schedule_deletion`.
+///
+/// When the Riot API rate limiter would delay the command, the command is put in the queue of waiting commands and
+/// the placeholder shows its position (e.g. "You're #3 in queue (~8s)") until its turn comes. The placeholder is
+/// edited when the position changes, or every 5 seconds to refresh the estimated wait.
+///
+/// # Parameters:
+/// - `ctx`: The application context of the command.
+///
+/// # Returns:
+/// - `Result<ReplyHandle<'_>, Error>`: The handle of the placeholder message, returned once the command can call the Riot API.
+///
+/// # Example:
+/// ```rust
+/// let fetching = send_fetching_message(ctx).await?;
+/// // ... Riot API calls ...
+/// edit_and_schedule_deletion(fetching, ctx, reply).await?;
+/// ```
+///
+/// # Notes:
+/// - A command never waits in the queue for more than 2 minutes; past that, its Riot API calls report the rate limit.
+pub async fn send_fetching_message(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+) -> Result<ReplyHandle<'_>, Error> {
+    ctx.defer().await?;
+    let fetching = ctx
+        .send(create_fetching_reply(
+            "⏳ Fetching data…",
+            "Please wait while Stat Summoner gathers the data from Riot.",
+        ))
+        .await?;
+    if let Some(ticket) = join_command_queue() {
+        let queued_at = Instant::now();
+        // Position affichée et heure de la dernière modification du message
+        let mut last_update: Option<(usize, Instant)> = None;
+        loop {
+            let position = ticket.position();
+            let wait = estimated_riot_wait();
+            if (position <= 1 && wait.is_zero()) || queued_at.elapsed() >= MAX_QUEUE_WAIT {
+                break;
+            }
+            // L'estimation change à chaque seconde : on ne modifie le message que si la position change
+            let outdated = last_update.is_none_or(|(last_position, edited_at)| {
+                last_position != position || edited_at.elapsed() >= QUEUE_STATUS_REFRESH
+            });
+            if outdated {
+                let eta = wait + QUEUE_SLOT_ESTIMATE * position.saturating_sub(1) as u32;
+                let reply = create_fetching_reply(
+                    "⏳ Waiting for Riot",
+                    &format!(
+                        "You're #{} in queue (~{}s)\nRiot is limiting our requests, your command will start automatically.",
+                        position,
+                        eta.as_secs().max(1)
+                    ),
+                );
+                fetching
+                    .edit(poise::Context::Application(ctx), reply)
+                    .await?;
+                last_update = Some((position, Instant::now()));
+            }
+            sleep(Duration::from_secs(1)).await;
+        }
+        let reply = create_fetching_reply(
+            "⏳ Fetching data…",
+            "Your turn has come, Stat Summoner is gathering the data from Riot.",
+        );
+        fetching
+            .edit(poise::Context::Application(ctx), reply)
+            .await?;
+    }
+    Ok(fetching)
+}
+
+fn create_fetching_reply(title: &str, description: &str) -> CreateReply {
+    let embed: CreateEmbed = CreateEmbed::default()
+        .title(title)
+        .description(description)
+        .color(0xA020F0)
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+    CreateReply {
+        embeds: vec![embed],
+        ..Default::default()
+    }
+}
+
+/// ⚙️ **Function**: Replaces a "Fetching data…" placeholder with the final reply and schedules its deletion.
+///
+/// # Parameters:
+/// - `fetching`: The handle returned by `send_fetching_message`.
+/// - `ctx`: The application context of the command.
+/// - `reply`: The final reply (result embed, error embed, ...).
+///
+/// # Returns:
+/// - `Result<(), Error>`: If successful, returns `Ok(())`. Returns an error if the message cannot be edited.
+///
+/// # Example:
+/// ```rust
+/// edit_and_schedule_deletion(fetching, ctx, create_embed_error("Player not found.")).await?;
+/// ```
+pub async fn edit_and_schedule_deletion(
+    fetching: ReplyHandle<'_>,
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    reply: CreateReply,
+) -> Result<(), Error> {
+    fetching
+        .edit(poise::Context::Application(ctx), reply)
+        .await?;
+    schedule_message_deletion(fetching, ctx).await
+}
+
+/// ⚙️ **Function**: Sends an embed with alternative views selectable through buttons, then deletes it after the deletion delay.
+///
+/// One button is displayed per view; the button of the view currently shown is disabled. Only the member who
+/// invoked the command can switch views.
+///
+/// # Parameters:
+/// - `ctx`: The application context of the command.
+/// - `views`: The views as `(button label, embed)` pairs. The first view is displayed initially.
+///
+/// # Returns:
+/// - `Result<(), Error>`: If successful, returns `Ok(())`. Returns an error if the views cannot be saved or the
+///   message cannot be sent or deleted.
+///
+/// # Example:
+/// ```rust
+/// send_embed_with_views(ctx, vec![("Overview".to_string(), overview), ("Situational".to_string(), situational)]).await?;
+/// ```
+///
+/// # Notes:
+/// - With a single view no button is added and the embed is sent as is.
+/// - The views are saved in MongoDB and the button presses are answered by `handle_persistent_component`, so they
+///   keep working if the bot restarts before the message is deleted.
+pub async fn send_embed_with_views(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    views: Vec<(String, CreateEmbed)>,
+) -> Result<(), Error> {
+    send_embed_with_lazy_views(ctx, views, Vec::new()).await
+}
+
+/// ⚙️ **Function**: Sends an embed with alternative views like `send_embed_with_views`, some of them built only when
+/// they are first opened.
+///
+/// # Parameters:
+/// - `ctx`: The application context of the command.
+/// - `views`: The views as `(button label, embed)` pairs. The first view is displayed initially.
+/// - `lazy_views`: The views built by their loader when they are first opened (see `register_view_loader`), with
+///   their buttons after the ones of `views`.
+///
+/// # Returns:
+/// - `Result<(), Error>`: If successful, returns `Ok(())`. Returns an error if the views cannot be saved or the
+///   message cannot be sent or deleted.
+///
+/// # Example:
+/// ```rust
+/// let pro_builds = LazyView { label: "Pro builds".to_string(), loader: PRO_BUILDS_VIEW_LOADER, payload };
+/// send_embed_with_lazy_views(ctx, vec![("Overview".to_string(), overview)], vec![pro_builds]).await?;
+/// ```
+pub async fn send_embed_with_lazy_views(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    views: Vec<(String, CreateEmbed)>,
+    lazy_views: Vec<LazyView>,
+) -> Result<(), Error> {
+    let components = save_views(
+        &ctx.data().mongo_client,
+        &format!("views-{}", ctx.id()),
+        ctx.author().id.get(),
+        &views,
+        &lazy_views,
+        false,
+        false,
+        None,
+    )
+    .await?;
+    let reply = CreateReply::default()
+        .embed(views[0].1.clone())
+        .components(components);
+    let sent_message = ctx.send(reply).await?;
+    schedule_message_deletion(sent_message, ctx).await
+}
+
+/// ⚙️ **Function**: Sends an embed split into pages browsed with previous/next buttons, then deletes it after the
+/// deletion delay.
+///
+/// # Parameters:
+/// - `ctx`: The application context of the command.
+/// - `pages`: The pages of the embed. The first page is displayed initially.
+/// - `rows`: The components shown under each page besides the previous/next buttons, if any (see `PageRows`).
+///
+/// # Returns:
+/// - `Result<(), Error>`: If successful, returns `Ok(())`. Returns an error if the pages cannot be saved or the
+///   message cannot be sent or deleted.
+///
+/// # Example:
+/// ```rust
+/// send_paginated_embed(ctx, pages, None).await?;
+/// ```
+///
+/// # Notes:
+/// - With a single page no button is added and the embed is sent as is.
+/// - Only the member who invoked the command can browse the pages. Like the views, the pages are saved in MongoDB
+///   under the key `pages-<interaction ID>` and answered by `handle_persistent_component`.
+pub async fn send_paginated_embed(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    pages: Vec<CreateEmbed>,
+    rows: Option<PageRows>,
+) -> Result<(), Error> {
+    let components = save_pages(
+        &ctx.data().mongo_client,
+        &format!("pages-{}", ctx.id()),
+        ctx.author().id.get(),
+        &pages,
+        0,
+        rows.as_ref(),
+    )
+    .await?;
+    let reply = CreateReply::default()
+        .embed(pages[0].clone())
+        .components(components);
+    let sent_message = ctx.send(reply).await?;
+    schedule_message_deletion(sent_message, ctx).await
+}
+
+/// ⚙️ **Function**: Replaces a "Fetching data…" placeholder with an embed with alternative views selectable through
+/// a select menu, then deletes it after the deletion delay.
+///
+/// # Parameters:
+/// - `fetching`: The handle returned by `send_fetching_message`.
+/// - `ctx`: The application context of the command.
+/// - `views`: The views as `(menu label, embed)` pairs. The first view is displayed initially.
+/// - `shareable`: Whether the "Share as image" button (see `share_image_button`) is shown under the menu, sharing the
+///   view displayed.
+///
+/// # Returns:
+/// - `Result<(), Error>`: If successful, returns `Ok(())`. Returns an error if the views cannot be saved or the
+///   message cannot be edited or deleted.
+///
+/// # Example:
+/// ```rust
+/// edit_embed_with_menu(fetching, ctx, vec![("Overview".to_string(), overview), ("Matches".to_string(), matches)], false).await?;
+/// ```
+///
+/// # Notes:
+/// - Views are switched in place like with `send_embed_with_views`, which suits messages with more views than fit
+///   comfortably in a row of buttons.
+pub async fn edit_embed_with_menu(
+    fetching: ReplyHandle<'_>,
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    views: Vec<(String, CreateEmbed)>,
+    shareable: bool,
+) -> Result<(), Error> {
+    let components = save_views(
+        &ctx.data().mongo_client,
+        &format!("views-{}", ctx.id()),
+        ctx.author().id.get(),
+        &views,
+        &[],
+        true,
+        shareable,
+        None,
+    )
+    .await?;
+    let reply = CreateReply::default()
+        .embed(views[0].1.clone())
+        .components(components);
+    edit_and_schedule_deletion(fetching, ctx, reply).await
+}
+
+/// ⚙️ **Function**: Opens the modal of a command and re-opens it, pre-filled, until its values are valid.
+///
+/// When `parse` refuses the submitted values, an ephemeral message explains why with a **Fix and resubmit**
+/// button. The button re-opens the modal with the previous values and the hint as its title, so the user only has to
+/// correct the invalid field instead of running the command again.
+///
+/// # Parameters:
+/// - `ctx`: The application context of the command. The modal must be its first response.
+/// - `parse`: Parses the submitted values, returning a short hint (45 characters at most) when they are invalid.
+///
+/// # Returns:
+/// - `Result<Option<T>, Error>`: The parsed values, or `None` when the command must stop. The user has then already
+///   been answered (or has dismissed the modal).
+///
+/// # Example:
+/// ```rust
+/// let Some(riot_id) = execute_modal_with_retry(ctx, |data: &LolStatsModal| {
+///     data.riot_id.parse::<RiotId>().map_err(|e| e.to_string())
+/// })
+/// .await?
+/// else {
+///     return Ok(());
+/// };
+/// ```
+///
+/// # Notes:
+/// - After 3 invalid submissions, the hint is sent as an error embed and the command stops.
+/// - The button and the re-opened modal wait 5 minutes for the user.
+/// - Unlike the persistent components, they are collected by the running command: its continuation (the values
+///   already validated, the reply to send) cannot be saved, so a restart ends the retry.
+pub async fn execute_modal_with_retry<M: Modal + Send, T>(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    parse: impl Fn(&M) -> Result<T, String>,
+) -> Result<Option<T>, Error> {
+    let mut modal_data = match M::execute(ctx).await {
+        Ok(Some(data)) => data,
+        Ok(None) => {
+            let reply = ctx
+                .send(create_embed_error("Modal data not found."))
+                .await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(None);
+        }
+        Err(_) => {
+            let reply = ctx
+                .send(create_embed_error("Failed to retrieve modal data."))
+                .await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(None);
+        }
+    };
+    let mut attempts = 1;
+    loop {
+        let hint = match parse(&modal_data) {
+            Ok(parsed) => return Ok(Some(parsed)),
+            Err(hint) => hint,
+        };
+        if attempts == MAX_MODAL_ATTEMPTS {
+            let reply = ctx.send(create_embed_error(&hint)).await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(None);
+        }
+
+        let button_id = format!("{}-modal-retry-{}", ctx.id(), attempts);
+        let mut reply = create_embed_error(&format!(
+            "{}\n\nPress **Fix and resubmit** to correct it, your other answers are kept.",
+            hint
+        ))
+        .ephemeral(true);
+        reply = reply.components(vec![serenity::CreateActionRow::Buttons(vec![
+            serenity::CreateButton::new(button_id.clone())
+                .label("Fix and resubmit")
+                .emoji('✏')
+                .style(serenity::ButtonStyle::Primary),
+        ])]);
+        let hint_message = ctx.send(reply).await?;
+        let press = serenity::ComponentInteractionCollector::new(ctx.serenity_context())
+            .author_id(ctx.author().id)
+            .filter(move |press| press.data.custom_id == button_id)
+            .timeout(MODAL_RETRY_TIMEOUT)
+            .await;
+        hint_message
+            .delete(poise::Context::Application(ctx))
+            .await?;
+        let Some(press) = press else {
+            return Ok(None);
+        };
+        let Some(resubmitted) = reopen_modal(ctx, press, modal_data, &hint).await? else {
+            return Ok(None);
+        };
+        modal_data = resubmitted;
+        attempts += 1;
+    }
+}
+
+/// ⚙️ **Function**: Offers the user a few suggestions with buttons, when their input matched nothing well.
+///
+/// # Parameters:
+/// - `ctx`: The application context of the command.
+/// - `question`: The text shown above the buttons, e.g. ``No champion is close enough to `jnx`. Did you mean:``.
+/// - `suggestions`: The suggestions, one button each (5 at most).
+/// - `kind`: The kind of the component state, whose handler (see `register_component_handler`) answers the buttons.
+/// - `context`: The context of the command given back to the handler with the suggestion picked.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if the suggestions cannot be saved or sent.
+///
+/// # Example:
+/// ```rust
+/// send_suggestions(ctx, &question, &["Jhin".into(), "Jinx".into()], CHAMPION_SUGGESTION_KIND, &context).await?;
+/// ```
+///
+/// # Notes:
+/// - The message is ephemeral and only its author can use the buttons.
+/// - The suggestions are saved like any persistent component, so they can still be picked after the bot restarts.
+pub async fn send_suggestions(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    question: &str,
+    suggestions: &[String],
+    kind: &str,
+    context: &impl Serialize,
+) -> Result<(), Error> {
+    let key = format!("suggestions-{}", ctx.id());
+    save_component_state(
+        &ctx.data().mongo_client,
+        &key,
+        kind,
+        Some(ctx.author().id.get()),
+        &SuggestionsState {
+            suggestions: suggestions.to_vec(),
+            context: serde_json::to_string(context)?,
+        },
+    )
+    .await?;
+    let reply = CreateReply::default()
+        .embed(CreateEmbed::default().description(question).color(0xFFA500))
+        .components(vec![suggestion_components(&key, suggestions)])
+        .ephemeral(true);
+    ctx.send(reply).await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Re-opens a modal from a button, pre-filled with the previous values and with the hint as its title.
+async fn reopen_modal<M: Modal>(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    press: serenity::ComponentInteraction,
+    defaults: M,
+    hint: &str,
+) -> Result<Option<M>, Error> {
+    // Discord limite le titre d'un modal à 45 caractères
+    let title: String = format!("⚠️ {}", hint).chars().take(45).collect();
+    let custom_id = press.id.to_string();
+    let response = retitle_modal(&M::create(Some(defaults), custom_id.clone()), &title)?;
+    press
+        .create_response(ctx.serenity_context(), response)
+        .await?;
+    let Some(submission) = serenity::ModalInteractionCollector::new(ctx.serenity_context())
+        .filter(move |submission| submission.data.custom_id == custom_id)
+        .timeout(MODAL_RETRY_TIMEOUT)
+        .await
+    else {
+        return Ok(None);
+    };
+    submission
+        .create_response(
+            ctx.serenity_context(),
+            serenity::CreateInteractionResponse::Acknowledge,
+        )
+        .await?;
+    Ok(Some(
+        M::parse(submission.data.clone()).map_err(serenity::Error::Other)?,
+    ))
+}
+
+/// ⚙️ **Function**: Rebuilds a modal created by `Modal::create` with another title, keeping its text inputs.
+fn retitle_modal(
+    response: &serenity::CreateInteractionResponse,
+    title: &str,
+) -> Result<serenity::CreateInteractionResponse, Error> {
+    // Le builder du modal n'expose pas ses champs : il est relu depuis sa forme JSON
+    let json = serde_json::to_value(response)?;
+    let custom_id = json["data"]["custom_id"].as_str().unwrap_or_default();
+    let mut rows = Vec::new();
+    for row in json["data"]["components"].as_array().into_iter().flatten() {
+        let input: serenity::InputText = serde_json::from_value(row["components"][0].clone())?;
+        let mut text = serenity::CreateInputText::new(
+            input.style.unwrap_or(serenity::InputTextStyle::Short),
+            input.label.unwrap_or_default(),
+            input.custom_id,
+        )
+        .required(input.required);
+        if let Some(value) = input.value {
+            text = text.value(value);
+        }
+        if let Some(placeholder) = input.placeholder {
+            text = text.placeholder(placeholder);
+        }
+        if let Some(min_length) = input.min_length {
+            text = text.min_length(min_length);
+        }
+        if let Some(max_length) = input.max_length {
+            text = text.max_length(max_length);
+        }
+        rows.push(serenity::CreateActionRow::InputText(text));
+    }
+    Ok(serenity::CreateInteractionResponse::Modal(
+        serenity::CreateModal::new(custom_id, title).components(rows),
+    ))
+}
+
+/// ⚙️ **Function**: Creates the "Share as image" button of an embed.
+///
+/// # Returns:
+/// - `serenity::CreateActionRow`: A row with the button. Pressing it sends the content of the first embed of the
+///   message as a PNG card (see `render_share_card`).
+///
+/// # Example:
+/// ```rust
+/// let reply = CreateReply::default().embed(embed).components(vec![share_image_button()]);
+/// ```
+pub fn share_image_button() -> serenity::CreateActionRow {
+    serenity::CreateActionRow::Buttons(vec![serenity::CreateButton::new(SHARE_IMAGE_BUTTON_ID)
+        .label("Share as image")
+        .emoji('🖼')
+        .style(serenity::ButtonStyle::Secondary)])
+}
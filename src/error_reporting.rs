@@ -0,0 +1,343 @@
+use crate::embed::{add_correlation_id, create_embed_coded_error, create_embed_error};
+use crate::models::data::Data;
+use crate::models::error::{Error, ErrorDetails};
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use regex::Regex;
+use sentry::protocol::{Event, Value};
+use sentry::{Breadcrumb, ClientInitGuard, ClientOptions};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Number of errors kept in memory for `/owner errors`.
+const RECENT_ERRORS_CAPACITY: usize = 200;
+
+/// An error displayed to a user, kept for `/owner errors`.
+#[derive(Debug, Clone)]
+pub struct RecentError {
+    pub code: String,
+    pub message: String,
+    pub occurred_at: i64,
+}
+
+static RECENT_ERRORS: OnceLock<Mutex<VecDeque<RecentError>>> = OnceLock::new();
+
+fn recent_errors_log() -> &'static Mutex<VecDeque<RecentError>> {
+    RECENT_ERRORS.get_or_init(|| Mutex::new(VecDeque::with_capacity(RECENT_ERRORS_CAPACITY)))
+}
+
+/// ⚙️ **Function**: Initializes the optional Sentry-compatible error reporting.
+///
+/// # Parameters:
+/// - `dsn`: The `SENTRY_DSN` secret. When absent, nothing is reported.
+/// - `sample_rate`: The optional `SENTRY_SAMPLE_RATE` secret, between `0.0` and `1.0` (defaults to `1.0`).
+///
+/// # Returns:
+/// - `Option<ClientInitGuard>`: The guard of the Sentry client. It must be kept alive for the whole lifetime of the bot,
+///   pending events are flushed when it is dropped.
+///
+/// # Example:
+/// ```rust
+/// let error_reporting_guard = init_error_reporting(
+///     secret_store.get("SENTRY_DSN"),
+///     secret_store.get("SENTRY_SAMPLE_RATE"),
+/// );
+/// ```
+///
+/// # Notes:
+/// - Panics are captured automatically once the client is initialized.
+/// - Events go through `scrub_event` before being sent, and default PII (IP addresses, user data) is never attached.
+pub fn init_error_reporting(
+    dsn: Option<String>,
+    sample_rate: Option<String>,
+) -> Option<ClientInitGuard> {
+    let dsn = dsn.filter(|dsn| !dsn.trim().is_empty())?;
+    let sample_rate = sample_rate
+        .and_then(|rate| rate.trim().parse::<f32>().ok())
+        .map_or(1.0, |rate| rate.clamp(0.0, 1.0));
+    let guard = sentry::init((
+        dsn,
+        ClientOptions {
+            release: sentry::release_name!(),
+            sample_rate,
+            send_default_pii: false,
+            before_send: Some(Arc::new(scrub_event)),
+            ..Default::default()
+        },
+    ));
+    log::info!("Error reporting enabled (sample rate: {}).", sample_rate);
+    Some(guard)
+}
+
+fn pii_patterns() -> &'static [(Regex, &'static str)] {
+    static PATTERNS: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        vec![
+            (
+                Regex::new(r"RGAPI-[0-9a-fA-F-]+").unwrap(),
+                "[riot-api-key]",
+            ),
+            (Regex::new(r"[A-Za-z0-9_-]{70,}").unwrap(), "[puuid]"),
+            (
+                Regex::new(r"[^\s#/]+#[A-Za-z0-9]{2,5}\b").unwrap(),
+                "[riot-id]",
+            ),
+            (Regex::new(r"[\w.+-]+@[\w-]+\.[\w.]+").unwrap(), "[email]"),
+        ]
+    })
+}
+
+fn scrub(text: &str) -> String {
+    pii_patterns()
+        .iter()
+        .fold(text.to_string(), |text, (pattern, replacement)| {
+            pattern.replace_all(&text, *replacement).into_owned()
+        })
+}
+
+/// Applies `scrub` to every string of a JSON value, such as the `extra` data of an event.
+fn scrub_value(value: &mut Value) {
+    match value {
+        Value::String(text) => *text = scrub(text),
+        Value::Array(values) => values.iter_mut().for_each(scrub_value),
+        Value::Object(values) => values.values_mut().for_each(scrub_value),
+        _ => {}
+    }
+}
+
+/// Removes personal data (Riot IDs, PUUIDs, API keys, emails, user and server info) from an event before it is sent.
+fn scrub_event(mut event: Event<'static>) -> Option<Event<'static>> {
+    event.user = None;
+    event.server_name = None;
+    event.request = None;
+    event.message = event.message.map(|message| scrub(&message));
+    // Les tags et données annexes portent le contexte des erreurs (commande, compte...) : même nettoyage
+    for tag in event.tags.values_mut() {
+        *tag = scrub(tag);
+    }
+    event.extra.values_mut().for_each(scrub_value);
+    for exception in event.exception.values.iter_mut() {
+        exception.value = exception.value.as_ref().map(|value| scrub(value));
+    }
+    for breadcrumb in event.breadcrumbs.values.iter_mut() {
+        breadcrumb.message = breadcrumb.message.as_ref().map(|message| scrub(message));
+        breadcrumb.data.values_mut().for_each(scrub_value);
+    }
+    Some(event)
+}
+
+/// ⚙️ **Function**: Reports an error with its context (command, guild, task, ...).
+///
+/// # Parameters:
+/// - `error`: The error to report.
+/// - `context`: Key/value pairs attached to the event as tags (e.g. `[("task", "check_and_update_db")]`).
+///
+/// # Example:
+/// ```rust
+/// report_error(&*e, &[("task", "fetch_champion_data")]);
+/// ```
+///
+/// # Notes:
+/// - Does nothing when error reporting is not configured.
+pub fn report_error<E: std::error::Error + ?Sized>(error: &E, context: &[(&str, &str)]) {
+    sentry::with_scope(
+        |scope| {
+            for (key, value) in context {
+                scope.set_tag(key, value);
+            }
+        },
+        || sentry::capture_error(error),
+    );
+}
+
+/// ⚙️ **Function**: Keeps a coded error in the recent errors log.
+///
+/// # Parameters:
+/// - `details`: The classified error.
+/// - `message`: The message displayed to the user. It is scrubbed like Sentry events are.
+///
+/// # Notes:
+/// - Only the last 200 errors are kept, and the log is reset when the bot restarts.
+pub fn record_error(details: &ErrorDetails, message: &str) {
+    let mut log = recent_errors_log().lock().unwrap();
+    if log.len() == RECENT_ERRORS_CAPACITY {
+        log.pop_front();
+    }
+    log.push_back(RecentError {
+        code: details.code.clone(),
+        message: scrub(message),
+        occurred_at: chrono::Utc::now().timestamp(),
+    });
+}
+
+/// ⚙️ **Function**: Returns the most recent coded errors, most recent first.
+///
+/// # Parameters:
+/// - `code`: Only keeps the errors whose code starts with this prefix (case insensitive), e.g. `RIOT` or `DB-TIMEOUT`.
+/// - `limit`: The maximum number of errors returned.
+///
+/// # Returns:
+/// - `Vec<RecentError>`: The matching errors.
+pub fn recent_errors(code: Option<&str>, limit: usize) -> Vec<RecentError> {
+    let prefix = code.map(|code| code.trim().to_uppercase());
+    recent_errors_log()
+        .lock()
+        .unwrap()
+        .iter()
+        .rev()
+        .filter(|error| match &prefix {
+            Some(prefix) => error.code.starts_with(prefix.as_str()),
+            None => true,
+        })
+        .take(limit)
+        .cloned()
+        .collect()
+}
+
+/// ⚙️ **Function**: Records a Riot API call, so the endpoints called before an error are attached to its report.
+///
+/// # Parameters:
+/// - `method`: The Riot API method name (e.g. `match-v5.getMatch`).
+/// - `status`: The HTTP status returned by Riot.
+pub fn record_riot_call(method: &str, status: u16) {
+    sentry::add_breadcrumb(Breadcrumb {
+        category: Some("riot_api".to_string()),
+        message: Some(format!("{} -> {}", method, status)),
+        ..Default::default()
+    });
+}
+
+/// ⚙️ **Function**: Framework error handler, the single place where command failures are reported and displayed.
+///
+/// Errors returned by a command and panics inside a command are given a correlation ID, logged and reported with it,
+/// and answered with a standardized error embed showing the error code and the ID. Cooldown hits, invalid options (e.g.
+/// a malformed `RiotId`) and command checks failing with an error are displayed with the bot's error embeds too. Other framework errors are displayed like
+/// Poise does.
+///
+/// # Parameters:
+/// - `error`: The error raised by the framework.
+///
+/// # Example:
+/// ```rust
+/// poise::FrameworkOptions {
+///     on_error: |error| Box::pin(on_framework_error(error)),
+///     ..Default::default()
+/// }
+/// ```
+///
+/// # Notes:
+/// - The user always gets an answer: when the interaction can no longer be answered (e.g. it expired), the error embed
+///   is posted in the channel instead.
+pub async fn on_framework_error(error: poise::FrameworkError<'_, Data, Error>) {
+    match error {
+        poise::FrameworkError::Command { error, ctx, .. } => {
+            let correlation_id = new_correlation_id();
+            let command = ctx.command().qualified_name.clone();
+            let guild = ctx
+                .guild_id()
+                .map_or(Cow::Borrowed("dm"), |id| Cow::Owned(id.get().to_string()));
+            let details = ErrorDetails::from_error(&*error);
+            log::error!(
+                "[{}] /{} failed with {}: {:?}",
+                correlation_id,
+                command,
+                details.code,
+                error
+            );
+            report_error(
+                &*error,
+                &[
+                    ("command", &command),
+                    ("guild", &guild),
+                    ("error_code", &details.code),
+                    ("correlation_id", &correlation_id),
+                ],
+            );
+            let reply = add_correlation_id(create_embed_coded_error("", &error), &correlation_id);
+            send_error_reply(ctx, reply).await;
+        }
+        poise::FrameworkError::CommandPanic { payload, ctx, .. } => {
+            let correlation_id = new_correlation_id();
+            log::error!(
+                "[{}] /{} panicked: {}",
+                correlation_id,
+                ctx.command().qualified_name,
+                payload.as_deref().unwrap_or("unknown panic payload")
+            );
+            sentry::with_scope(
+                |scope| scope.set_tag("correlation_id", &correlation_id),
+                || sentry::capture_message("Command panicked", sentry::Level::Error),
+            );
+            let error: Error = "The command stopped unexpectedly.".into();
+            let reply = add_correlation_id(create_embed_coded_error("", &error), &correlation_id);
+            send_error_reply(ctx, reply).await;
+        }
+        poise::FrameworkError::CooldownHit {
+            remaining_cooldown,
+            ctx,
+            ..
+        } => {
+            let reply = create_embed_error(&format!(
+                "You're going too fast! Please wait {} seconds before using `/{}` again.",
+                remaining_cooldown.as_secs().max(1),
+                ctx.command().qualified_name
+            ));
+            send_error_reply(ctx, reply).await;
+        }
+        poise::FrameworkError::ArgumentParse {
+            error, input, ctx, ..
+        } => {
+            let error_message = match input {
+                Some(input) => format!("`{}` is not a valid value. {}.", input, error),
+                None => format!("A value is not valid. {}.", error),
+            };
+            send_error_reply(ctx, create_embed_error(&error_message)).await;
+        }
+        poise::FrameworkError::CommandCheckFailed {
+            error: Some(error),
+            ctx,
+            ..
+        } => {
+            log::error!(
+                "Command check failed for /{}: {:?}",
+                ctx.command().qualified_name,
+                error
+            );
+            send_error_reply(
+                ctx,
+                create_embed_coded_error("Could not check this command", &error),
+            )
+            .await;
+        }
+        error => {
+            if let Err(e) = poise::builtins::on_error(error).await {
+                log::error!("Error while handling error: {}", e);
+            }
+        }
+    }
+}
+
+/// Generates the short ID shown to the user and logged with an error, e.g. `3FA94C1B`.
+fn new_correlation_id() -> String {
+    format!("{:08X}", rand::random::<u32>())
+}
+
+/// Sends an ephemeral error reply, falling back to a channel message when the interaction cannot be answered anymore.
+async fn send_error_reply(ctx: poise::Context<'_, Data, Error>, reply: CreateReply) {
+    let embeds = reply.embeds.clone();
+    let Err(e) = ctx.send(reply.ephemeral(true)).await else {
+        return;
+    };
+    log::warn!(
+        "Could not answer the interaction with the error, posting it in the channel: {}",
+        e
+    );
+    if let Err(e) = ctx
+        .channel_id()
+        .send_message(ctx.http(), serenity::CreateMessage::new().embeds(embeds))
+        .await
+    {
+        log::error!("Error while handling error: {}", e);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::config::{config, FixtureMode};
+use crate::models::error::{BotError, Error};
+use regex::Regex;
+use reqwest::{Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Riot API response saved on disk, served instead of the Riot API when `RIOT_FIXTURES_DIR` is set.
+#[derive(Serialize, Deserialize)]
+struct Fixture {
+    /// The HTTP status of the response, e.g. `404` for a player who is not in game.
+    status: u16,
+    /// The JSON body of the response.
+    body: Value,
+}
+
+/// Pattern of the PUUIDs (78 characters), as in the scrubbing of the error reports.
+fn puuid_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"[A-Za-z0-9_-]{70,}").unwrap())
+}
+
+/// ⚙️ **Function**: Replaces the PUUIDs of a text with pseudonyms.
+///
+/// # Parameters:
+/// - `text`: A Riot API URL or response body.
+///
+/// # Returns:
+/// - `String`: The text where each PUUID is replaced with `puuid-` followed by a hash of it.
+///
+/// # Notes:
+/// - The same PUUID always gets the same pseudonym, in the URLs as in the bodies: a replayed response gives
+///   pseudonyms, and the requests built from them find the fixtures recorded with the real PUUIDs.
+/// - The hash is FNV-1a, whose result does not change between runs or Rust versions.
+fn redact_puuids(text: &str) -> String {
+    puuid_pattern()
+        .replace_all(text, |captures: &regex::Captures| {
+            let hash = captures[0]
+                .bytes()
+                .fold(0xcbf29ce484222325u64, |hash, byte| {
+                    (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+                });
+            format!("puuid-{:016x}", hash)
+        })
+        .into_owned()
+}
+
+/// ⚙️ **Function**: Returns the path of the fixture of a Riot API request.
+///
+/// # Parameters:
+/// - `dir`: The fixtures folder (`RIOT_FIXTURES_DIR`).
+/// - `method`: The Riot API method name, used as a subfolder (e.g. `match-v5.getMatch`).
+/// - `url`: The full Riot API URL. Its host, path and query identify the fixture, PUUIDs redacted.
+///
+/// # Returns:
+/// - `PathBuf`: e.g. `fixtures/match-v5.getMatch/europe_api_riotgames_com_lol_match_v5_matches_EUW1_123.json`.
+fn fixture_path(dir: &Path, method: &str, url: &str) -> PathBuf {
+    let key: String = redact_puuids(url.trim_start_matches("https://"))
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    dir.join(method).join(format!("{}.json", key))
+}
+
+/// ⚙️ **Function**: Builds a `reqwest` response from a status and a body.
+fn build_response(status: u16, body: impl Into<reqwest::Body>) -> Option<Response> {
+    http::Response::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        .body(body)
+        .ok()
+        .map(Response::from)
+}
+
+/// ⚙️ **Function**: Serves a Riot API request from the fixtures folder.
+///
+/// # Parameters:
+/// - `method`: The Riot API method name (e.g. `match-v5.getMatch`).
+/// - `url`: The full Riot API URL.
+///
+/// # Returns:
+/// - `Result<Option<Response>, Error>`: The recorded response, or `None` when the Riot API must be called: no
+///   `RIOT_FIXTURES_DIR`, the `record` mode, or no fixture in the `replay` mode. In the `replay-only` mode, a missing
+///   fixture is a `BotError::MissingFixture`.
+///
+/// # Notes:
+/// - A fixture is a JSON file holding the `status` and the `body` of the response, e.g. `{"status": 200, "body": {...}}`.
+pub async fn replay_fixture(method: &str, url: &str) -> Result<Option<Response>, Error> {
+    let config = config();
+    let Some(dir) = config.riot_fixtures_dir.as_ref() else {
+        return Ok(None);
+    };
+    if config.riot_fixtures_mode == FixtureMode::Record {
+        return Ok(None);
+    }
+    let path = fixture_path(dir, method, url);
+    let content = match tokio::fs::read_to_string(&path).await {
+        Ok(content) => content,
+        Err(_) if config.riot_fixtures_mode == FixtureMode::ReplayOnly => {
+            return Err(Box::new(BotError::MissingFixture {
+                path: path.display().to_string(),
+            }));
+        }
+        Err(_) => {
+            log::info!("No fixture at {}, calling the Riot API.", path.display());
+            return Ok(None);
+        }
+    };
+    let fixture: Fixture = match serde_json::from_str(&content) {
+        Ok(fixture) => fixture,
+        Err(e) => {
+            log::warn!("Ignoring the invalid fixture {}: {}", path.display(), e);
+            return Ok(None);
+        }
+    };
+    Ok(build_response(fixture.status, fixture.body.to_string()))
+}
+
+/// ⚙️ **Function**: Saves a Riot API response as a fixture in the `record` mode.
+///
+/// # Parameters:
+/// - `method`: The Riot API method name (e.g. `match-v5.getMatch`).
+/// - `url`: The full Riot API URL.
+/// - `response`: The response of the Riot API.
+///
+/// # Returns:
+/// - `Result<Response, Error>`: The response, unchanged for the caller, or an error if its body cannot be read.
+///
+/// # Notes:
+/// - The PUUIDs of the body are replaced with pseudonyms (see `redact_puuids`), so fixtures can be shared.
+/// - Rate limits, rejected keys and server errors are not recorded: they say nothing about the request.
+/// - A fixture that cannot be written is logged and the response is still returned.
+pub async fn record_fixture(
+    method: &str,
+    url: &str,
+    response: Response,
+) -> Result<Response, Error> {
+    let config = config();
+    let (Some(dir), FixtureMode::Record) =
+        (config.riot_fixtures_dir.as_ref(), config.riot_fixtures_mode)
+    else {
+        return Ok(response);
+    };
+    let status = response.status();
+    if matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::FORBIDDEN
+    ) || status.is_server_error()
+    {
+        return Ok(response);
+    }
+    let bytes = response.bytes().await?;
+    let body = String::from_utf8_lossy(&bytes);
+    let fixture = Fixture {
+        status: status.as_u16(),
+        body: serde_json::from_str(&redact_puuids(&body))
+            .unwrap_or_else(|_| Value::String(redact_puuids(&body))),
+    };
+    let path = fixture_path(dir, method, url);
+    let content = serde_json::to_string_pretty(&fixture)?;
+    let written: std::io::Result<()> = async {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, content).await
+    }
+    .await;
+    match written {
+        Ok(()) => log::info!("Recorded the fixture {}.", path.display()),
+        Err(e) => log::warn!("Failed to record the fixture {}: {}", path.display(), e),
+    }
+    build_response(status.as_u16(), bytes)
+        .ok_or_else(|| format!("Failed to rebuild the response of {}", method).into())
+}
@@ -0,0 +1,338 @@
+use crate::components::{
+    component_id, handle_persistent_component, save_component_state, PERSISTENT_COMPONENT_PREFIX,
+};
+use crate::embed::{create_embed_coded_error, SHARE_IMAGE_BUTTON_ID};
+use crate::image::render_share_card;
+use crate::law::TERMS_VERSION;
+use crate::models::data::{Data, TosConsentData};
+use crate::models::error::Error;
+use crate::module::owner::utils::{count_guild_command, guild_command_check};
+use futures::future::BoxFuture;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serenity::builder::CreateEmbed;
+use std::time::Duration;
+
+/// Cooldown applied per user to every command that does not declare its own.
+pub const DEFAULT_COMMAND_COOLDOWN: Duration = Duration::from_secs(5);
+
+/// Kind of the component state of the Terms of Service prompt, answered by `handle_tos_component`.
+pub const TOS_COMPONENT_KIND: &str = "tos";
+
+/// ⚙️ **Function**: Returns the `tos_consents` collection.
+pub fn tos_consents_collection(mongo_client: &Client) -> Collection<TosConsentData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<TosConsentData>("tos_consents")
+}
+
+/// ⚙️ **Function**: Applies the default per-user cooldown to the commands which do not declare one.
+///
+/// # Parameters:
+/// - `commands`: The commands of the bot. Subcommands are updated too.
+///
+/// # Notes:
+/// - Cooldowns are enforced by Poise after the command checks, and a hit is displayed by `on_framework_error`.
+pub fn apply_default_cooldown(commands: &mut [poise::Command<Data, Error>]) {
+    for command in commands.iter_mut() {
+        {
+            let mut config = command.cooldown_config.write().unwrap();
+            if config.user.is_none() && config.member.is_none() {
+                config.user = Some(DEFAULT_COMMAND_COOLDOWN);
+            }
+        }
+        apply_default_cooldown(&mut command.subcommands);
+    }
+}
+
+/// ⚙️ **Function**: Global command check run before every command.
+///
+/// This asynchronous function is registered as the framework's `command_check`. It refuses the command when it has
+/// been disabled in the guild by the kill switch (see `guild_command_check`), then when the user has not accepted the
+/// current Terms of Service.
+///
+/// # Parameters:
+/// - `ctx`: The poise `Context` of the command about to be executed.
+///
+/// # Returns:
+/// - `Result<bool, Error>`: `true` if the command can run.
+///
+/// # Example:
+/// ```rust
+/// poise::FrameworkOptions {
+///     command_check: Some(|ctx| Box::pin(command_check(ctx))),
+///     ..Default::default()
+/// }
+/// ```
+///
+/// # ⚠️ Notes:
+/// - Every refusal is explained to the user by the check itself, so commands never have to.
+pub async fn command_check(ctx: poise::Context<'_, Data, Error>) -> Result<bool, Error> {
+    if !guild_command_check(ctx).await? {
+        return Ok(false);
+    }
+    tos_consent_check(ctx).await
+}
+
+/// ⚙️ **Function**: Command check gating the bot behind the acceptance of the Terms of Service.
+///
+/// When the user has not accepted the current version of the Terms of Service (`TERMS_VERSION`), the command is
+/// refused and an ephemeral summary of the terms is sent with an **Accept** button. Once accepted, the consent is
+/// stored in the `tos_consents` collection and the user can run the command again.
+///
+/// # Parameters:
+/// - `ctx`: The poise `Context` of the command about to be executed.
+///
+/// # Returns:
+/// - `Result<bool, Error>`: `true` if the user already accepted the current terms.
+///
+/// # ⚠️ Notes:
+/// - The command is refused even when the terms are accepted from the prompt: commands opening a modal must answer
+///   the original interaction, which the prompt already used.
+/// - The **Accept** button is a persistent component answered by `handle_tos_component`, so it keeps working after
+///   the bot restarts.
+/// - Bot owners are never gated.
+async fn tos_consent_check(ctx: poise::Context<'_, Data, Error>) -> Result<bool, Error> {
+    if ctx.framework().options().owners.contains(&ctx.author().id) {
+        return Ok(true);
+    }
+    let collection = tos_consents_collection(&ctx.data().mongo_client);
+    let discord_id = ctx.author().id.get();
+    if collection
+        .find_one(doc! { "discord_id": discord_id as i64, "version": TERMS_VERSION })
+        .await?
+        .is_some()
+    {
+        return Ok(true);
+    }
+
+    // Le bouton est persistant : l'acceptation reste possible après un redémarrage
+    let key = format!("tos-{}", ctx.id());
+    save_component_state(
+        &ctx.data().mongo_client,
+        &key,
+        TOS_COMPONENT_KIND,
+        Some(discord_id),
+        &ctx.command().qualified_name,
+    )
+    .await?;
+    let prompt = CreateEmbed::default()
+        .title(format!("📜 Terms of Service (v{})", TERMS_VERSION))
+        .description(
+            "Before using **Stat Summoner**, please accept its Terms of Service:\n\
+             - The bot only stores what you give it (Riot IDs, Discord IDs, channel IDs) to provide its features.\n\
+             - Statistics come from the public Riot Games API.\n\
+             - You can stop being followed at any time with `/optout`.\n\n\
+             The full terms and privacy policy are available in the bot documentation.",
+        )
+        .color(0xA020F0);
+    ctx.send(
+        CreateReply::default()
+            .embed(prompt)
+            .components(vec![serenity::CreateActionRow::Buttons(vec![
+                serenity::CreateButton::new(component_id(&key, "accept"))
+                    .label("Accept")
+                    .style(serenity::ButtonStyle::Success),
+            ])])
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(false)
+}
+
+/// ⚙️ **Function**: Records the consent given with the **Accept** button of the Terms of Service prompt.
+///
+/// # Parameters:
+/// - `ctx`: The serenity context.
+/// - `data`: The data of the bot.
+/// - `press`: The **Accept** button.
+/// - `payload`: The name of the command refused by `tos_consent_check`, as JSON.
+///
+/// # Returns:
+/// - `BoxFuture<'a, Result<(), Error>>`: An error if the consent cannot be saved or the response cannot be sent.
+///
+/// # Notes:
+/// - Registered under `TOS_COMPONENT_KIND` (see `module::register_components`).
+pub fn handle_tos_component<'a>(
+    ctx: &'a serenity::Context,
+    data: &'a Data,
+    press: &'a serenity::ComponentInteraction,
+    _key: &'a str,
+    _action: &'a str,
+    payload: &'a str,
+) -> BoxFuture<'a, Result<(), Error>> {
+    Box::pin(async move {
+        let command_name: String = serde_json::from_str(payload)?;
+        let discord_id = press.user.id.get();
+        tos_consents_collection(&data.mongo_client)
+            .replace_one(
+                doc! { "discord_id": discord_id as i64 },
+                TosConsentData {
+                    discord_id,
+                    version: TERMS_VERSION.to_string(),
+                    accepted_at: chrono::Utc::now().timestamp(),
+                },
+            )
+            .upsert(true)
+            .await?;
+        press
+            .create_response(
+                ctx,
+                serenity::CreateInteractionResponse::UpdateMessage(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .embed(
+                            CreateEmbed::default()
+                                .title("✅ Terms of Service accepted")
+                                .description(format!(
+                                    "Thank you! You can now run `/{}` again.",
+                                    command_name
+                                ))
+                                .color(0x00ff00),
+                        )
+                        .components(Vec::new()),
+                ),
+            )
+            .await?;
+        Ok(())
+    })
+}
+
+/// ⚙️ **Function**: Pre-command hook logging every command and counting its use in the guild.
+///
+/// # Parameters:
+/// - `ctx`: The poise `Context` of the command about to be executed.
+///
+/// # Example:
+/// ```rust
+/// poise::FrameworkOptions {
+///     pre_command: |ctx| Box::pin(pre_command(ctx)),
+///     ..Default::default()
+/// }
+/// ```
+pub async fn pre_command(ctx: poise::Context<'_, Data, Error>) {
+    log::info!(
+        "/{} invoked by {} in {}",
+        ctx.command().qualified_name,
+        ctx.author().id,
+        ctx.guild_id()
+            .map_or("DM".to_string(), |id| format!("guild {}", id))
+    );
+    count_guild_command(ctx).await;
+}
+
+/// ⚙️ **Function**: Post-command hook logging the commands which completed successfully, with their duration.
+///
+/// # Parameters:
+/// - `ctx`: The poise `Context` of the command which has just been executed.
+///
+/// # Notes:
+/// - Failed commands do not reach this hook: they are logged by `on_framework_error`.
+pub async fn post_command(ctx: poise::Context<'_, Data, Error>) {
+    let elapsed = chrono::Utc::now() - *ctx.created_at();
+    log::info!(
+        "/{} completed for {} in {} ms",
+        ctx.command().qualified_name,
+        ctx.author().id,
+        elapsed.num_milliseconds()
+    );
+}
+
+/// ⚙️ **Function**: Handles the Discord events which are not commands, such as the buttons of the bot's messages.
+///
+/// # Parameters:
+/// - `ctx`: The serenity context.
+/// - `event`: The event received from Discord.
+/// - `data`: The data of the bot.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if the response to an interaction cannot be sent.
+///
+/// # Example:
+/// ```rust
+/// poise::FrameworkOptions {
+///     event_handler: |ctx, event, _framework, data| Box::pin(event_handler(ctx, event, data)),
+///     ..Default::default()
+/// }
+/// ```
+///
+/// # Notes:
+/// - The buttons handled here work on any message of the bot, including the direct messages sent by the background
+///   jobs, since they are not tied to a running command.
+/// - The components whose custom ID starts with `PERSISTENT_COMPONENT_PREFIX` are answered from the state saved in
+///   MongoDB (see `handle_persistent_component`), so they keep working after the bot restarts.
+pub async fn event_handler(
+    ctx: &serenity::Context,
+    event: &serenity::FullEvent,
+    data: &Data,
+) -> Result<(), Error> {
+    if let serenity::FullEvent::InteractionCreate {
+        interaction: serenity::Interaction::Component(press),
+    } = event
+    {
+        if press.data.custom_id == SHARE_IMAGE_BUTTON_ID {
+            share_embed_as_image(ctx, press).await?;
+        } else if press
+            .data
+            .custom_id
+            .starts_with(&format!("{}:", PERSISTENT_COMPONENT_PREFIX))
+        {
+            handle_persistent_component(ctx, data, press).await?;
+        }
+    }
+    Ok(())
+}
+
+/// ⚙️ **Function**: Answers a "Share as image" button with the first embed of its message drawn as a PNG card.
+///
+/// # Parameters:
+/// - `ctx`: The serenity context.
+/// - `press`: The button interaction.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if the response cannot be sent.
+///
+/// # Notes:
+/// - The card is built from the embed itself, so it matches what the user sees even after a restart of the bot.
+/// - The card is sent as an ephemeral message: only the user who pressed the button sees it and can save it.
+/// - When the card cannot be drawn, an ephemeral error embed is sent instead, so the interaction is always answered.
+async fn share_embed_as_image(
+    ctx: &serenity::Context,
+    press: &serenity::ComponentInteraction,
+) -> Result<(), Error> {
+    let response = match press.message.embeds.first() {
+        Some(embed) => {
+            let mut lines: Vec<String> = embed
+                .description
+                .as_deref()
+                .unwrap_or("")
+                .lines()
+                .map(str::to_string)
+                .collect();
+            for field in &embed.fields {
+                lines.push(String::new());
+                lines.push(field.name.clone());
+                lines.extend(field.value.lines().map(str::to_string));
+            }
+            match render_share_card(embed.title.as_deref().unwrap_or("Stat Summoner"), &lines) {
+                Ok(png) => serenity::CreateInteractionResponseMessage::new()
+                    .add_file(serenity::CreateAttachment::bytes(png, "stat-summoner.png"))
+                    .ephemeral(true),
+                Err(e) => {
+                    log::error!("Failed to render the share card: {}", e);
+                    create_embed_coded_error("Error creating the image", &e)
+                        .ephemeral(true)
+                        .to_slash_initial_response(serenity::CreateInteractionResponseMessage::new())
+                }
+            }
+        }
+        None => serenity::CreateInteractionResponseMessage::new()
+            .content("This message has nothing to share anymore.")
+            .ephemeral(true),
+    };
+    press
+        .create_response(ctx, serenity::CreateInteractionResponse::Message(response))
+        .await?;
+    Ok(())
+}
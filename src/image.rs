@@ -0,0 +1,783 @@
+use crate::models::error::Error;
+use plotters::prelude::*;
+
+/// Size, in game units, of the Summoner's Rift map (both axes go from 0 to about 14 870).
+pub const SUMMONERS_RIFT_SIZE: f64 = 14870.0;
+
+/// Size, in pixels, of the charts attached to the embeds.
+pub const CHART_SIZE: (u32, u32) = (640, 320);
+
+/// An RGBA color.
+pub type Rgba = [u8; 4];
+
+/// A simple RGBA image drawn in memory and encoded as PNG to be attached to Discord messages.
+pub struct Canvas {
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<Rgba>,
+}
+
+impl Canvas {
+    /// Creates a canvas filled with a single color.
+    pub fn new(width: u32, height: u32, background: Rgba) -> Self {
+        Canvas {
+            width,
+            height,
+            pixels: vec![background; (width * height) as usize],
+        }
+    }
+
+    /// Creates a canvas from RGB pixels (3 bytes per pixel, row by row), such as the buffer of a plotters bitmap.
+    pub fn from_rgb(width: u32, height: u32, rgb: &[u8]) -> Self {
+        Canvas {
+            width,
+            height,
+            pixels: rgb
+                .chunks_exact(3)
+                .map(|pixel| [pixel[0], pixel[1], pixel[2], 255])
+                .collect(),
+        }
+    }
+
+    /// Blends a color over the pixel at `(x, y)`, using the alpha channel of the color. Pixels outside the canvas are ignored.
+    pub fn blend(&mut self, x: i64, y: i64, color: Rgba) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let pixel = &mut self.pixels[(y as u32 * self.width + x as u32) as usize];
+        let alpha = color[3] as u32;
+        for channel in 0..3 {
+            pixel[channel] = ((color[channel] as u32 * alpha
+                + pixel[channel] as u32 * (255 - alpha))
+                / 255) as u8;
+        }
+        pixel[3] = 255;
+    }
+
+    /// Fills a rectangle whose top-left corner is `(x, y)`.
+    pub fn fill_rect(&mut self, x: i64, y: i64, width: i64, height: i64, color: Rgba) {
+        for py in y..y + height {
+            for px in x..x + width {
+                self.blend(px, py, color);
+            }
+        }
+    }
+
+    /// Draws a line between two points, `thickness` pixels wide.
+    pub fn line(&mut self, from: (f64, f64), to: (f64, f64), thickness: i64, color: Rgba) {
+        let steps = (to.0 - from.0)
+            .abs()
+            .max((to.1 - from.1).abs())
+            .ceil()
+            .max(1.0) as i64;
+        let half = thickness / 2;
+        for step in 0..=steps {
+            let t = step as f64 / steps as f64;
+            let x = (from.0 + (to.0 - from.0) * t).round() as i64;
+            let y = (from.1 + (to.1 - from.1) * t).round() as i64;
+            self.fill_rect(x - half, y - half, thickness, thickness, color);
+        }
+    }
+
+    /// Encodes the canvas as a PNG file.
+    pub fn encode_png(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels.concat())?;
+        writer.finish()?;
+        Ok(bytes)
+    }
+}
+
+/// ⚙️ **Function**: Converts a position on Summoner's Rift to a pixel of a square minimap.
+///
+/// # Parameters:
+/// - `x`, `y`: The position in game units, as found in match-v5 timelines (the origin is the bottom-left corner).
+/// - `size`: The size of the minimap, in pixels.
+///
+/// # Returns:
+/// - `(f64, f64)`: The pixel, with the origin at the top-left corner.
+pub fn map_to_pixel(x: f64, y: f64, size: u32) -> (f64, f64) {
+    let scale = size as f64 / SUMMONERS_RIFT_SIZE;
+    (x * scale, size as f64 - y * scale)
+}
+
+/// ⚙️ **Function**: Draws a schematic minimap of Summoner's Rift: the jungle, the river, the three lanes and both bases.
+///
+/// # Parameters:
+/// - `size`: The size of the square minimap, in pixels.
+///
+/// # Returns:
+/// - `Canvas`: The minimap, ready to be drawn on.
+///
+/// # Example:
+/// ```rust
+/// let mut minimap = draw_minimap(512);
+/// minimap.fill_rect(250, 250, 12, 12, [255, 0, 0, 255]);
+/// let png = minimap.encode_png()?;
+/// ```
+///
+/// # Notes:
+/// - The minimap is drawn rather than loaded from an image so the bot does not depend on Riot's assets.
+pub fn draw_minimap(size: u32) -> Canvas {
+    let mut canvas = Canvas::new(size, size, [24, 48, 32, 255]);
+    let point = |x: f64, y: f64| map_to_pixel(x, y, size);
+    let lane_width = (size / 28).max(2) as i64;
+    let lane_color = [140, 120, 90, 255];
+
+    // Rivière (diagonale haut-gauche -> bas-droite)
+    canvas.line(
+        point(0.0, SUMMONERS_RIFT_SIZE),
+        point(SUMMONERS_RIFT_SIZE, 0.0),
+        lane_width * 2,
+        [40, 80, 130, 255],
+    );
+    // Top, mid et bot
+    let corner = SUMMONERS_RIFT_SIZE * 0.07;
+    let far = SUMMONERS_RIFT_SIZE - corner;
+    for (from, to) in [
+        ((corner, corner), (corner, far)),
+        ((corner, far), (far, far)),
+        ((corner, corner), (far, corner)),
+        ((far, corner), (far, far)),
+        ((corner, corner), (far, far)),
+    ] {
+        canvas.line(
+            point(from.0, from.1),
+            point(to.0, to.1),
+            lane_width,
+            lane_color,
+        );
+    }
+    // Bases bleue et rouge
+    let base = (size / 7) as i64;
+    canvas.fill_rect(0, size as i64 - base, base, base, [40, 90, 170, 255]);
+    canvas.fill_rect(size as i64 - base, 0, base, base, [170, 50, 50, 255]);
+    canvas
+}
+
+/// ⚙️ **Function**: Draws a heatmap of positions over a minimap of Summoner's Rift.
+///
+/// # Parameters:
+/// - `positions`: The positions to plot, in game units.
+/// - `size`: The size of the square image, in pixels.
+///
+/// # Returns:
+/// - `Result<Vec<u8>, Error>`: The heatmap, encoded as PNG.
+///
+/// # Example:
+/// ```rust
+/// let png = render_heatmap(&[(7400.0, 7400.0), (7600.0, 7200.0)], 512)?;
+/// let attachment = CreateAttachment::bytes(png, "heatmap.png");
+/// ```
+///
+/// # Notes:
+/// - Each position spreads over a radius of 1/16 of the map; the color goes from yellow to red where positions overlap.
+pub fn render_heatmap(positions: &[(f64, f64)], size: u32) -> Result<Vec<u8>, Error> {
+    let mut canvas = draw_minimap(size);
+    let radius = (size / 16).max(1) as i64;
+    let mut heat = vec![0.0f64; (size * size) as usize];
+    for &(x, y) in positions {
+        let (cx, cy) = map_to_pixel(x, y, size);
+        let (cx, cy) = (cx.round() as i64, cy.round() as i64);
+        for py in (cy - radius).max(0)..(cy + radius).min(size as i64) {
+            for px in (cx - radius).max(0)..(cx + radius).min(size as i64) {
+                let distance = (((px - cx).pow(2) + (py - cy).pow(2)) as f64).sqrt();
+                if distance < radius as f64 {
+                    heat[(py as u32 * size + px as u32) as usize] += 1.0 - distance / radius as f64;
+                }
+            }
+        }
+    }
+    let max_heat = heat.iter().cloned().fold(0.0, f64::max);
+    if max_heat > 0.0 {
+        for (index, value) in heat.iter().enumerate() {
+            if *value <= 0.0 {
+                continue;
+            }
+            let intensity = (value / max_heat).min(1.0);
+            let color = [
+                255,
+                (230.0 * (1.0 - intensity)) as u8,
+                0,
+                (90.0 + 140.0 * intensity) as u8,
+            ];
+            canvas.blend(
+                (index as u32 % size) as i64,
+                (index as u32 / size) as i64,
+                color,
+            );
+        }
+    }
+    canvas.encode_png()
+}
+
+/// ⚙️ **Function**: Draws the line chart of a difference over time, filled in green above zero and in red below.
+///
+/// # Parameters:
+/// - `points`: The `(minute, difference)` points of the chart, in chronological order.
+/// - `width`, `height`: The size of the image, in pixels.
+///
+/// # Returns:
+/// - `Result<Vec<u8>, Error>`: The chart, encoded as PNG, or an error if it cannot be drawn.
+///
+/// # Example:
+/// ```rust
+/// let png = render_difference_chart(&[(0.0, 0.0), (1.0, 150.0), (2.0, -80.0)], 640, 320)?;
+/// let attachment = CreateAttachment::bytes(png, "gold.png");
+/// ```
+///
+/// # Notes:
+/// - The chart has no text: a vertical line is drawn every 5 minutes and the values are meant to be described in the embed.
+/// - The vertical axis is symmetrical around zero and spans at least ±1 000.
+pub fn render_difference_chart(
+    points: &[(f64, f64)],
+    width: u32,
+    height: u32,
+) -> Result<Vec<u8>, Error> {
+    let mut rgb = vec![0u8; (width * height * 3) as usize];
+    {
+        let root = BitMapBackend::with_buffer(&mut rgb, (width, height)).into_drawing_area();
+        root.fill(&RGBColor(32, 34, 37))?;
+        let max_x = points.iter().map(|point| point.0).fold(1.0, f64::max);
+        let max_y = points
+            .iter()
+            .map(|point| point.1.abs())
+            .fold(1000.0, f64::max)
+            * 1.1;
+        let mut chart = ChartBuilder::on(&root)
+            .margin(12)
+            .build_cartesian_2d(0.0..max_x, -max_y..max_y)?;
+
+        // Repères toutes les 5 minutes et axe du zéro
+        for minute in (5..=max_x as u32).step_by(5) {
+            chart.draw_series(LineSeries::new(
+                [(minute as f64, -max_y), (minute as f64, max_y)],
+                &RGBColor(70, 72, 76),
+            ))?;
+        }
+        chart.draw_series(LineSeries::new(
+            [(0.0, 0.0), (max_x, 0.0)],
+            &RGBColor(150, 150, 150),
+        ))?;
+        chart.draw_series(AreaSeries::new(
+            points.iter().map(|&(x, y)| (x, y.max(0.0))),
+            0.0,
+            GREEN.mix(0.35),
+        ))?;
+        chart.draw_series(AreaSeries::new(
+            points.iter().map(|&(x, y)| (x, y.min(0.0))),
+            0.0,
+            RED.mix(0.35),
+        ))?;
+        chart.draw_series(LineSeries::new(
+            points.iter().copied(),
+            WHITE.stroke_width(2),
+        ))?;
+        root.present()?;
+    }
+    Canvas::from_rgb(width, height, &rgb).encode_png()
+}
+
+/// Glyphs of the pixel font used to write on the images: 7 rows of 5 pixels, the leftmost pixel in the highest bit.
+const FONT: [(char, [u8; 7]); 60] = [
+    (
+        'A',
+        [
+            0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001,
+        ],
+    ),
+    (
+        'B',
+        [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110,
+        ],
+    ),
+    (
+        'C',
+        [
+            0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        'D',
+        [
+            0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110,
+        ],
+    ),
+    (
+        'E',
+        [
+            0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111,
+        ],
+    ),
+    (
+        'F',
+        [
+            0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000,
+        ],
+    ),
+    (
+        'G',
+        [
+            0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111,
+        ],
+    ),
+    (
+        'H',
+        [
+            0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001,
+        ],
+    ),
+    (
+        'I',
+        [
+            0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110,
+        ],
+    ),
+    (
+        'J',
+        [
+            0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100,
+        ],
+    ),
+    (
+        'K',
+        [
+            0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001,
+        ],
+    ),
+    (
+        'L',
+        [
+            0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111,
+        ],
+    ),
+    (
+        'M',
+        [
+            0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001,
+        ],
+    ),
+    (
+        'N',
+        [
+            0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001,
+        ],
+    ),
+    (
+        'O',
+        [
+            0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        'P',
+        [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000,
+        ],
+    ),
+    (
+        'Q',
+        [
+            0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101,
+        ],
+    ),
+    (
+        'R',
+        [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001,
+        ],
+    ),
+    (
+        'S',
+        [
+            0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110,
+        ],
+    ),
+    (
+        'T',
+        [
+            0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100,
+        ],
+    ),
+    (
+        'U',
+        [
+            0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        'V',
+        [
+            0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100,
+        ],
+    ),
+    (
+        'W',
+        [
+            0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010,
+        ],
+    ),
+    (
+        'X',
+        [
+            0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001,
+        ],
+    ),
+    (
+        'Y',
+        [
+            0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100,
+        ],
+    ),
+    (
+        'Z',
+        [
+            0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111,
+        ],
+    ),
+    (
+        '0',
+        [
+            0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        '1',
+        [
+            0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110,
+        ],
+    ),
+    (
+        '2',
+        [
+            0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111,
+        ],
+    ),
+    (
+        '3',
+        [
+            0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        '4',
+        [
+            0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010,
+        ],
+    ),
+    (
+        '5',
+        [
+            0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        '6',
+        [
+            0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        '7',
+        [
+            0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000,
+        ],
+    ),
+    (
+        '8',
+        [
+            0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        '9',
+        [
+            0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100,
+        ],
+    ),
+    (' ', [0, 0, 0, 0, 0, 0, 0]),
+    ('.', [0, 0, 0, 0, 0, 0b01100, 0b01100]),
+    (',', [0, 0, 0, 0, 0b01100, 0b00100, 0b01000]),
+    (':', [0, 0b01100, 0b01100, 0, 0b01100, 0b01100, 0]),
+    (';', [0, 0b01100, 0b01100, 0, 0b01100, 0b00100, 0b01000]),
+    (
+        '!',
+        [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0, 0b00100],
+    ),
+    (
+        '?',
+        [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0, 0b00100],
+    ),
+    ('-', [0, 0, 0, 0b11111, 0, 0, 0]),
+    ('+', [0, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0]),
+    (
+        '/',
+        [
+            0b00001, 0b00010, 0b00010, 0b00100, 0b01000, 0b01000, 0b10000,
+        ],
+    ),
+    (
+        '%',
+        [
+            0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011,
+        ],
+    ),
+    (
+        '(',
+        [
+            0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010,
+        ],
+    ),
+    (
+        ')',
+        [
+            0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000,
+        ],
+    ),
+    (
+        '[',
+        [
+            0b01110, 0b01000, 0b01000, 0b01000, 0b01000, 0b01000, 0b01110,
+        ],
+    ),
+    (
+        ']',
+        [
+            0b01110, 0b00010, 0b00010, 0b00010, 0b00010, 0b00010, 0b01110,
+        ],
+    ),
+    (
+        '#',
+        [
+            0b01010, 0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b01010,
+        ],
+    ),
+    ('\'', [0b00100, 0b00100, 0b01000, 0, 0, 0, 0]),
+    ('"', [0b01010, 0b01010, 0, 0, 0, 0, 0]),
+    ('=', [0, 0, 0b11111, 0, 0b11111, 0, 0]),
+    ('~', [0, 0, 0b01000, 0b10101, 0b00010, 0, 0]),
+    ('_', [0, 0, 0, 0, 0, 0, 0b11111]),
+    (
+        '<',
+        [
+            0b00010, 0b00100, 0b01000, 0b10000, 0b01000, 0b00100, 0b00010,
+        ],
+    ),
+    (
+        '>',
+        [
+            0b01000, 0b00100, 0b00010, 0b00001, 0b00010, 0b00100, 0b01000,
+        ],
+    ),
+    (
+        '&',
+        [
+            0b01100, 0b10010, 0b10100, 0b01000, 0b10101, 0b10010, 0b01101,
+        ],
+    ),
+];
+
+/// Width, in font pixels, of a character and the space after it.
+const FONT_ADVANCE: i64 = 6;
+
+/// Size, in pixels, of the share cards.
+pub const SHARE_CARD_WIDTH: u32 = 800;
+
+/// Maximum number of lines of text on a share card.
+const SHARE_CARD_MAX_LINES: usize = 24;
+
+impl Canvas {
+    /// Writes a line of text with the pixel font, `scale` pixels per font pixel, from its top-left corner.
+    /// Letters are written in uppercase and unknown characters as `?`.
+    pub fn text(&mut self, x: i64, y: i64, text: &str, scale: i64, color: Rgba) {
+        for (index, character) in text.chars().enumerate() {
+            let character = character.to_ascii_uppercase();
+            let glyph = FONT
+                .iter()
+                .find(|(glyph_char, _)| *glyph_char == character)
+                .or_else(|| FONT.iter().find(|(glyph_char, _)| *glyph_char == '?'))
+                .map(|(_, rows)| rows);
+            let Some(rows) = glyph else {
+                continue;
+            };
+            let left = x + index as i64 * FONT_ADVANCE * scale;
+            for (row, bits) in rows.iter().enumerate() {
+                for column in 0..5 {
+                    if bits & (0b10000 >> column) != 0 {
+                        self.fill_rect(
+                            left + column * scale,
+                            y + row as i64 * scale,
+                            scale,
+                            scale,
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// ⚙️ **Function**: Converts the Markdown of an embed to text the pixel font can write.
+///
+/// # Parameters:
+/// - `markdown`: The text of the embed.
+///
+/// # Returns:
+/// - `String`: The text without formatting: custom emojis are replaced by their name, other emojis are removed and
+///   typographic characters are replaced by their ASCII equivalent.
+///
+/// # Example:
+/// ```rust
+/// assert_eq!(plain_text("**<:GOLD:123> II** · 45 LP 📈"), "GOLD II - 45 LP");
+/// ```
+pub fn plain_text(markdown: &str) -> String {
+    let mut text = String::new();
+    let mut rest = markdown;
+    // Emojis personnalisés : <:nom:id> ou <a:nom:id>
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let emoji = tail.find('>').and_then(|end| {
+            let inner = tail[1..end]
+                .strip_prefix("a:")
+                .or(tail[1..end].strip_prefix(':'))?;
+            Some((inner.split(':').next().unwrap_or("").to_string(), end))
+        });
+        match emoji {
+            Some((name, end)) => {
+                text.push_str(&name);
+                rest = &tail[end + 1..];
+            }
+            None => {
+                text.push('<');
+                rest = &tail[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    let text: String = text
+        .replace("**", "")
+        .replace("__", "")
+        .replace("~~", "")
+        .chars()
+        .filter_map(|character| match character {
+            '·' | '—' | '–' => Some('-'),
+            '×' => Some('x'),
+            '’' | '‘' => Some('\''),
+            '`' | '*' => None,
+            character if character.is_ascii() => Some(character),
+            _ => None,
+        })
+        .collect();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// ⚙️ **Function**: Cuts a text into lines of at most `width` characters, between words when possible.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word = word;
+        while word.len() > width {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            lines.push(word[..width].to_string());
+            word = &word[width..];
+        }
+        if !line.is_empty() && line.len() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// ⚙️ **Function**: Draws a branded card with the content of an embed, to be shared outside of Discord.
+///
+/// # Parameters:
+/// - `title`: The title of the card.
+/// - `lines`: The lines of text of the card, as Markdown (see `plain_text`). Empty lines are kept as spacing.
+///
+/// # Returns:
+/// - `Result<Vec<u8>, Error>`: The card, encoded as PNG, or an error if it cannot be encoded.
+///
+/// # Example:
+/// ```rust
+/// let png = render_share_card("Weekly recap", &["Last 7 days: **+85 LP**".to_string()])?;
+/// let attachment = CreateAttachment::bytes(png, "stat-summoner.png");
+/// ```
+///
+/// # Notes:
+/// - Long lines are wrapped and the card stops after 24 lines, ending with `...`.
+/// - The text is written with a pixel font drawn by the bot, so the cards do not depend on the fonts of the host.
+pub fn render_share_card(title: &str, lines: &[String]) -> Result<Vec<u8>, Error> {
+    let margin = 40;
+    let (title_scale, text_scale) = (4, 3);
+    let line_height = 10 * text_scale;
+    let columns = ((SHARE_CARD_WIDTH as i64 - 2 * margin) / (FONT_ADVANCE * text_scale)) as usize;
+    let title_columns =
+        ((SHARE_CARD_WIDTH as i64 - 2 * margin) / (FONT_ADVANCE * title_scale)) as usize;
+
+    let mut text_lines: Vec<String> = Vec::new();
+    for line in lines {
+        let line = plain_text(line);
+        if line.is_empty() {
+            text_lines.push(String::new());
+        } else {
+            text_lines.extend(wrap_text(&line, columns));
+        }
+    }
+    if text_lines.len() > SHARE_CARD_MAX_LINES {
+        text_lines.truncate(SHARE_CARD_MAX_LINES - 1);
+        text_lines.push("...".to_string());
+    }
+    let title = wrap_text(&plain_text(title), title_columns)
+        .into_iter()
+        .next()
+        .unwrap_or_default();
+
+    let header_height = 100;
+    let footer_height = 60;
+    let height = header_height + 20 + text_lines.len() as i64 * line_height + footer_height;
+    let mut canvas = Canvas::new(SHARE_CARD_WIDTH, height as u32, [32, 34, 37, 255]);
+    let purple = [160, 32, 240, 255];
+    canvas.fill_rect(0, 0, SHARE_CARD_WIDTH as i64, header_height, purple);
+    canvas.text(
+        margin,
+        (header_height - 7 * title_scale) / 2,
+        &title,
+        title_scale,
+        [255, 255, 255, 255],
+    );
+    for (index, line) in text_lines.iter().enumerate() {
+        canvas.text(
+            margin,
+            header_height + 20 + index as i64 * line_height,
+            line,
+            text_scale,
+            [220, 221, 222, 255],
+        );
+    }
+    canvas.fill_rect(0, height - 4, SHARE_CARD_WIDTH as i64, 4, purple);
+    canvas.text(
+        margin,
+        height - footer_height + 16,
+        "Stat Summoner",
+        2,
+        purple,
+    );
+    canvas.encode_png()
+}
@@ -0,0 +1,68 @@
+use crate::module::loop_module::utils::MASTERY_CACHE_SECS;
+use mongodb::bson::{doc, Document};
+use mongodb::options::IndexOptions;
+use mongodb::{Client, IndexModel};
+use std::time::Duration;
+
+/// ⚙️ **Function**: Creates the MongoDB indexes used by the bot, if they do not exist yet.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+///
+/// # Example:
+/// ```rust
+/// ensure_indexes(&mongo_client).await;
+/// ```
+///
+/// # Notes:
+/// - Creating an index that already exists does nothing, so this runs at every startup.
+/// - Errors are only logged: without an index, the queries are slower but still correct.
+/// - TTL indexes (`expire_after`) only apply to BSON dates: MongoDB deletes the documents once the date has passed.
+pub async fn ensure_indexes(mongo_client: &Client) {
+    let indexes: Vec<(&str, Document, Option<IndexOptions>)> = vec![
+        // Suivis : lus par PUUID à chaque passage de la boucle, et par serveur pour les commandes
+        (
+            "follower_summoner",
+            doc! { "puuid": 1, "guild_id": 1 },
+            None,
+        ),
+        ("follower_summoner", doc! { "guild_id": 1 }, None),
+        // États des composants : lus par clé à chaque clic, supprimés par MongoDB une fois expirés
+        ("component_states", doc! { "key": 1 }, None),
+        (
+            "component_states",
+            doc! { "expires_at": 1 },
+            Some(
+                IndexOptions::builder()
+                    .expire_after(Duration::from_secs(0))
+                    .build(),
+            ),
+        ),
+        // Maîtrises en cache : lues par joueur et champion, supprimées par MongoDB au bout de 7 jours
+        (
+            "champion_masteries",
+            doc! { "puuid": 1, "champion_id": 1 },
+            None,
+        ),
+        (
+            "champion_masteries",
+            doc! { "fetched_at": 1 },
+            Some(
+                IndexOptions::builder()
+                    .expire_after(Duration::from_secs(MASTERY_CACHE_SECS as u64))
+                    .build(),
+            ),
+        ),
+    ];
+    let database = mongo_client.database("stat-summoner");
+    for (collection, keys, options) in indexes {
+        let index = IndexModel::builder().keys(keys).options(options).build();
+        if let Err(e) = database
+            .collection::<Document>(collection)
+            .create_index(index)
+            .await
+        {
+            log::error!("Failed to create an index on {}: {}", collection, e);
+        }
+    }
+}
@@ -1,2 +1,7 @@
 pub mod privacy_policy;
 pub mod terms_of_service;
+
+/// Version of the Terms of Service users have to accept before using the bot (see `hooks::command_check`).
+///
+/// Bumping it asks every user to accept the terms again.
+pub const TERMS_VERSION: &str = "0.5.1";
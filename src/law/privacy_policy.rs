@@ -37,19 +37,27 @@
 //! - **No Selling of Data**: The Bot does not sell, trade, or rent users' personal identification information to others.
 //! - **Legal Requirements**: The Bot may disclose your information if required to do so by law or in response to valid requests by public authorities.
 //!
-//! ## 5. Children's Privacy
+//! ## 5. Opting Out
+//!
+//! Any player can demand that their League of Legends account is never looked up or followed through the Bot, in any Discord server:
+//!
+//! - Link your account with `/linkaccount`, then run `/optout` and follow the profile icon verification steps.
+//! - Once opted out, `/lolstats`, `/followgames` and `/followuser` refuse your account, and every existing follow of your account is removed.
+//! - The opt-out can be cancelled at any time with `/optout cancel: true`.
+//!
+//! ## 6. Children's Privacy
 //!
 //! The Bot is intended for use by individuals who are at least 16 years old. If you are under 16, please do not use the Bot or provide any personal information.
 //!
-//! ## 6. Changes to This Privacy Policy
+//! ## 7. Changes to This Privacy Policy
 //!
 //! The Bot's developer reserves the right to update or change this Privacy Policy at any time. Changes will be effective immediately upon posting the updated policy. Users are encouraged to periodically review this policy for any changes.
 //!
-//! ## 7. Your Acceptance of These Terms
+//! ## 8. Your Acceptance of These Terms
 //!
 //! By using the Bot, you signify your acceptance of this Privacy Policy. If you do not agree to this policy, please do not use the Bot. Continued use of the Bot following the posting of changes to this policy will be deemed your acceptance of those changes.
 //!
-//! ## 8. Contact Information
+//! ## 9. Contact Information
 //!
 //! If you have any questions or concerns about this Privacy Policy or the Bot's data practices, please contact:
 //!
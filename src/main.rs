@@ -1,29 +1,101 @@
+mod components;
+mod config;
 mod embed;
+mod error_reporting;
+mod fixtures;
+mod hooks;
+mod image;
+mod indexes;
 mod law;
 mod models;
 mod module;
+mod rate_limiter;
 mod riot_api;
+mod sandbox;
+mod static_data;
 mod utils;
 
+use config::{config, init_config, parse_bot_config};
+use error_reporting::{init_error_reporting, on_framework_error, report_error};
+use hooks::{command_check, event_handler, post_command, pre_command};
+use std::net::SocketAddr;
 use std::sync::Arc;
 
-use models::data::Data;
-use module::championsinfos::championsinfos::championsinfos;
-use module::followgames::followgames::followgames;
-use module::lolstats::lolstats::lolstats;
-use module::loop_module::loop_module::{check_and_update_db, fetch_champion_data};
-use module::randomchampions::randomchampions::randomchampions;
-use module::whoisfollowed::whoisfollowed::whoisfollowed;
+use models::champion_index::ChampionIndex;
+use models::data::{Data, HealthStatus, ShardConfig};
+use module::botstatus::utils::run_health_check;
+use module::challenge::utils::post_weekly_challenges;
+use module::clash::utils::sync_clash_events;
+use module::goal::utils::post_goal_recaps;
+use module::grind::utils::archive_linked_games;
+use module::lolstats::percentile::refresh_league_distributions;
+use module::loop_module::job_lock::{
+    acquire_job_lock, hold_job_lock_until, job_period, release_job_lock, spawn_job_lock_heartbeat,
+};
+use module::loop_module::loop_module::{
+    check_and_update_db, fetch_champion_data_exclusive, reload_champion_index,
+};
+use module::masterygain::utils::record_mastery_snapshots;
 use mongodb::bson::doc;
 use mongodb::{
     options::{ClientOptions, ServerApi, ServerApiVersion},
     Client,
 };
 use poise::serenity_prelude::{self as serenity};
+use rate_limiter::{init_key_pool, set_quota_alert_sender};
 use shuttle_runtime::SecretStore;
-use shuttle_serenity::ShuttleSerenity;
 use tokio::sync::RwLock;
 use tokio::time::{sleep, Duration};
+use utils::{parse_shard_config, retry_with_backoff, shard_range};
+
+/// Number of attempts at reaching MongoDB and Data Dragon when the bot starts.
+const STARTUP_ATTEMPTS: u32 = 4;
+
+/// ⚙️ **Struct**: Shuttle service running the Discord client with the shards assigned to this instance.
+///
+/// # Notes:
+/// - Without a `SHARD_COUNT` secret, the client uses the shard count recommended by Discord (autosharding).
+/// - With it, the instance only starts its own block of shards (see `shard_range`), so several instances can share the load.
+/// - In dry-run mode (`DRY_RUN`), no shard is started: only the background tasks run.
+pub struct StatSummonerService {
+    client: serenity::Client,
+    shard_config: ShardConfig,
+    _error_reporting_guard: Option<sentry::ClientInitGuard>,
+}
+
+#[shuttle_runtime::async_trait]
+impl shuttle_runtime::Service for StatSummonerService {
+    async fn bind(mut self, _addr: SocketAddr) -> Result<(), shuttle_runtime::Error> {
+        if config().dry_run {
+            // Seules les tâches de fond tournent : elles s'arrêtent avec le processus
+            std::future::pending::<()>().await;
+        }
+        match self.shard_config.total_shards {
+            Some(total_shards) => {
+                let range = shard_range(&self.shard_config, total_shards);
+                log::info!(
+                    "Starting shards {}..{} of {} (instance {}/{})",
+                    range.start,
+                    range.end,
+                    total_shards,
+                    self.shard_config.instance_index + 1,
+                    self.shard_config.instance_count
+                );
+                self.client.start_shard_range(range, total_shards).await
+            }
+            None => {
+                if self.shard_config.instance_count > 1 {
+                    log::warn!(
+                        "INSTANCE_COUNT is set without SHARD_COUNT: every instance will start all shards."
+                    );
+                }
+                self.client.start_autosharded().await
+            }
+        }
+        .map_err(shuttle_runtime::CustomError::new)?;
+        Ok(())
+    }
+}
 
 /// ⚙️ **Function**: Initializes and starts the Discord bot using the Shuttle runtime and Poise framework.
 ///
@@ -35,75 +107,156 @@ use tokio::time::{sleep, Duration};
 /// - `secret_store`: The Shuttle runtime secret store, which holds sensitive information such as the Discord token and Riot API key.
 ///
 /// # Returns:
-/// - `ShuttleSerenity`: An instance of the Serenity client wrapped in a result. It starts the bot client once all setup is complete.
+/// - `Result<StatSummonerService, shuttle_runtime::Error>`: The Serenity client wrapped in a shard-aware service. It starts the bot client once all setup is complete.
 ///
 /// # ⚠️ Notes:
 /// - The bot framework is built using the Poise framework, which is designed for building Discord bots easily.
-/// - The `lolstats` command is registered globally, meaning it will be available in all servers the bot is in.
+/// - The commands returned by `module::all_commands` are registered globally, meaning they will be available in all servers the bot is in.
 /// - The function uses non-privileged gateway intents, meaning it doesn't request sensitive Discord information such as message content or member lists.
+/// - The optional `SENTRY_DSN` and `SENTRY_SAMPLE_RATE` secrets enable error reporting of panics, command errors and background task errors.
+/// - The optional `SHARD_COUNT`, `INSTANCE_INDEX` and `INSTANCE_COUNT` secrets split the gateway shards and the follow loop between several instances.
+/// - MongoDB and Data Dragon are retried with a backoff at startup. Without Data Dragon, the bot starts without the
+///   champion lookups, `/botstatus` shows it, and the health check loads them once Data Dragon is back.
 ///
 /// # Example:
 /// This function is called automatically when the bot is deployed and run in the Shuttle environment.
 ///
 /// ```rust
 /// #[shuttle_runtime::main]
-/// async fn main(secret_store: SecretStore) -> ShuttleSerenity {
+/// async fn main(secret_store: SecretStore) -> Result<StatSummonerService, shuttle_runtime::Error> {
 ///     // Bot setup and startup code
 /// }
 /// ```
 ///
 /// The bot will start and listen to commands like `lolstats` once it is running.
 #[shuttle_runtime::main]
-async fn main(#[shuttle_runtime::Secrets] secret_store: SecretStore) -> ShuttleSerenity {
+async fn main(
+    #[shuttle_runtime::Secrets] secret_store: SecretStore,
+) -> Result<StatSummonerService, shuttle_runtime::Error> {
+    // Remontée d'erreurs optionnelle vers un DSN compatible Sentry
+    let error_reporting_guard = init_error_reporting(
+        secret_store.get("SENTRY_DSN"),
+        secret_store.get("SENTRY_SAMPLE_RATE"),
+    );
+
+    // Intervalles et limites configurables, avec des valeurs par défaut
+    init_config(parse_bot_config(|name| secret_store.get(name)));
+    // En mode bac à sable, le bot tourne sans token Discord et, avec des fixtures, sans clé Riot
+    let dry_run = config().dry_run;
+    if dry_run {
+        log::warn!(
+            "Dry-run mode: the Discord gateway is not started and messages are only logged."
+        );
+    }
+
     // Récupérer le token Discord, la clé Riot API et l'URI MongoDB depuis les secrets
     let discord_token = secret_store
         .get("DISCORD_TOKEN")
+        .or_else(|| dry_run.then(String::new))
         .ok_or_else(|| anyhow::anyhow!("'DISCORD_TOKEN' was not found"))?;
 
     let riot_api_key = secret_store
         .get("RIOT_API_KEY")
+        .or_else(|| dry_run.then(String::new))
         .ok_or_else(|| anyhow::anyhow!("'RIOT_API_KEY' was not found"))?;
 
+    // Clés Riot supplémentaires, séparées par des virgules
+    let mut riot_api_keys = vec![riot_api_key.clone()];
+    if let Some(extra_keys) = secret_store.get("RIOT_API_KEYS") {
+        riot_api_keys.extend(extra_keys.split(',').map(|key| key.to_string()));
+    }
+    init_key_pool(riot_api_keys);
+
     let mongodb_uri = secret_store
         .get("MONGODB_URI")
         .ok_or_else(|| anyhow::anyhow!("'MONGODB_URI' was not found"))?;
-    // Initialiser MongoDB
-    let mut client_options = ClientOptions::parse(&mongodb_uri)
+
+    let shard_config = parse_shard_config(
+        secret_store.get("SHARD_COUNT"),
+        secret_store.get("INSTANCE_INDEX"),
+        secret_store.get("INSTANCE_COUNT"),
+    );
+    let shard_config_clone = shard_config.clone();
+    // Identifiant unique de l'instance, utilisé pour les verrous des tâches planifiées
+    let instance_id = format!("{}-{:08x}", std::process::id(), rand::random::<u32>());
+    let instance_id_clone = instance_id.clone();
+    // Initialiser MongoDB : la résolution de l'URI (SRV) est réessayée, la connexion elle-même est paresseuse
+    let mut client_options =
+        retry_with_backoff("MongoDB URI resolution", STARTUP_ATTEMPTS, || async {
+            Ok(ClientOptions::parse(&mongodb_uri).await?)
+        })
         .await
-        .expect("Failed to parse MongoDB URI");
+        .map_err(|e| anyhow::anyhow!("Failed to parse MongoDB URI: {}", e))?;
     let server_api = ServerApi::builder().version(ServerApiVersion::V1).build();
     client_options.server_api = Some(server_api);
-    let mongo_client =
-        Client::with_options(client_options).expect("Failed to create MongoDB client");
+    let mongo_client = Client::with_options(client_options)
+        .map_err(|e| anyhow::anyhow!("Failed to create MongoDB client: {}", e))?;
+    module::register_components();
+    let mongo_client_for_indexes = mongo_client.clone();
+    tokio::spawn(async move {
+        indexes::ensure_indexes(&mongo_client_for_indexes).await;
+    });
     let mongo_client_clone = mongo_client.clone();
     let mongo_client_clone_2 = mongo_client.clone();
     let riot_api_key_clone = riot_api_key.clone();
-    let dd_json_value = riot_api::open_dd_json().await.unwrap();
-    let dd_json = Arc::new(RwLock::new(dd_json_value));
-    let dd_json_clone_for_loop = dd_json.clone();
+    // Sans Data Dragon, le bot démarre quand même : le bilan de santé recharge les champions plus tard
+    let champion_index = match retry_with_backoff(
+        "Data Dragon",
+        STARTUP_ATTEMPTS,
+        static_data::open_dd_json,
+    )
+    .await
+    {
+        Ok(dd_json) => ChampionIndex::from_dd_json(&dd_json),
+        Err(e) => {
+            log::error!("Starting without Data Dragon: {:?}", e);
+            report_error(&*e, &[("task", "startup_open_dd_json")]);
+            ChampionIndex::default()
+        }
+    };
+    let champions = Arc::new(RwLock::new(champion_index));
+    let champions_clone_for_health = champions.clone();
+    let champions_clone_for_loop = champions.clone();
+    let champions_clone_for_follows = champions.clone();
+    let health_status = Arc::new(RwLock::new(HealthStatus::default()));
+    let health_status_clone = health_status.clone();
+    let mongo_client_clone_3 = mongo_client.clone();
+    let riot_api_key_clone_2 = riot_api_key.clone();
+    let mongo_client_clone_4 = mongo_client.clone();
+    let riot_api_key_clone_3 = riot_api_key.clone();
+    let instance_id_clone_2 = instance_id.clone();
+    let mongo_client_clone_5 = mongo_client.clone();
+    let instance_id_clone_3 = instance_id.clone();
+    let champions_clone_for_challenges = champions.clone();
+    let riot_api_key_clone_4 = riot_api_key.clone();
+    // Salon où sont postés les changements d'état des dépendances
+    let ops_channel_id = secret_store
+        .get("OPS_CHANNEL_ID")
+        .and_then(|id| id.trim().parse::<u64>().ok());
 
     // Configurer le framework Poise avec les commandes
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
-            commands: vec![
-                lolstats(),
-                followgames(),
-                whoisfollowed(),
-                championsinfos(),
-                randomchampions(),
-            ],
+            commands: module::all_commands(),
+            command_check: Some(|ctx| Box::pin(command_check(ctx))),
+            pre_command: |ctx| Box::pin(pre_command(ctx)),
+            post_command: |ctx| Box::pin(post_command(ctx)),
+            on_error: |error| Box::pin(on_framework_error(error)),
+            event_handler: |ctx, event, _framework, data| Box::pin(event_handler(ctx, event, data)),
             ..Default::default()
         })
         .setup(move |_ctx, _ready, _framework| {
             let riot_api_key = riot_api_key.clone();
             let mongo_client = mongo_client.clone();
-            let dd_json = dd_json.clone();
+            let champions = champions.clone();
+            let health_status = health_status.clone();
             Box::pin(async move {
                 poise::builtins::register_globally(_ctx, &_framework.options().commands).await?;
                 Ok(Data {
                     riot_api_key,
                     mongo_client,
-                    dd_json,
+                    champions,
+                    health_status,
                 })
             })
         })
@@ -114,37 +267,291 @@ async fn main(#[shuttle_runtime::Secrets] secret_store: SecretStore) -> ShuttleS
             .await
             .map_err(shuttle_runtime::CustomError::new)?;
     let http = client.http.clone();
+    let http_for_alerts = client.http.clone();
+    let http_for_health = client.http.clone();
+    let http_for_challenges = client.http.clone();
+    let (quota_alert_sender, mut quota_alert_receiver) = tokio::sync::mpsc::unbounded_channel();
+    set_quota_alert_sender(quota_alert_sender);
     tokio::spawn(async move {
-        loop {
-            match check_and_update_db(&mongo_client_clone, &riot_api_key_clone, http.clone()).await
-            {
-                Ok(_) => (),
-                Err(e) => log::error!(
-                    "Erreur lors de la vérification de la base de données : {:?}",
-                    e
-                ),
+        // Prévenir le propriétaire du bot en DM quand un quota Riot dépasse 80%
+        while let Some(alert) = quota_alert_receiver.recv().await {
+            if config().dry_run {
+                log::info!("[dry run] Riot API quota alert: {}", alert);
+                continue;
+            }
+            let owner = match http_for_alerts.get_current_application_info().await {
+                Ok(info) => info.owner,
+                Err(e) => {
+                    log::error!("Error fetching the application owner: {:?}", e);
+                    None
+                }
+            };
+            if let Some(owner) = owner {
+                let embed = serenity::CreateEmbed::new()
+                    .title("⚠️ Riot API quota alert")
+                    .description(alert)
+                    .color(0xFF0000);
+                if let Err(e) = owner
+                    .direct_message(
+                        &http_for_alerts,
+                        serenity::CreateMessage::new().embed(embed),
+                    )
+                    .await
+                {
+                    log::error!("Error sending the quota alert to the owner: {:?}", e);
+                }
             }
-            sleep(Duration::from_secs(120)).await; // Attendre 2 minutes
         }
     });
     tokio::spawn(async move {
+        let job = format!("check_and_update_db:{}", shard_config_clone.instance_index);
         loop {
-            match fetch_champion_data(&mongo_client_clone_2).await {
-                Ok(_) => log::info!("Champion data updated successfully."),
-                Err(e) => log::error!("Error updating champion data: {:?}", e),
+            match acquire_job_lock(&mongo_client_clone, &job, &instance_id).await {
+                Ok(true) => {
+                    let heartbeat = spawn_job_lock_heartbeat(
+                        mongo_client_clone.clone(),
+                        job.clone(),
+                        instance_id.clone(),
+                    );
+                    match check_and_update_db(
+                        &mongo_client_clone,
+                        &riot_api_key_clone,
+                        http.clone(),
+                        &champions_clone_for_follows,
+                        &shard_config_clone,
+                    )
+                    .await
+                    {
+                        Ok(_) => (),
+                        Err(e) => {
+                            log::error!(
+                                "Erreur lors de la vérification de la base de données : {:?}",
+                                e
+                            );
+                            report_error(&*e, &[("task", "check_and_update_db")]);
+                        }
+                    }
+                    heartbeat.abort();
+                    if let Err(e) = release_job_lock(&mongo_client_clone, &job, &instance_id).await
+                    {
+                        log::error!("Error releasing the lease of job '{}': {:?}", job, e);
+                    }
+                }
+                Ok(false) => log::info!("Job '{}' is running on another instance, skipping.", job),
+                Err(e) => log::error!("Error acquiring the lease of job '{}': {:?}", job, e),
             }
-            match riot_api::open_dd_json().await {
-                Ok(new_dd_json) => {
-                    let mut dd_json_write = dd_json_clone_for_loop.write().await;
-                    *dd_json_write = new_dd_json;
-                    log::info!("DataDragon JSON updated successfully.");
+            sleep(config().follow_poll_interval).await;
+        }
+    });
+    tokio::spawn(async move {
+        loop {
+            // Le bail porte sur la période : une autre instance ou un redémarrage ne relance pas le job avant la suivante
+            let (job, period_end) =
+                job_period("fetch_champion_data", config().champion_refresh_interval);
+            match acquire_job_lock(&mongo_client_clone_2, &job, &instance_id_clone).await {
+                Ok(true) => {
+                    let heartbeat = spawn_job_lock_heartbeat(
+                        mongo_client_clone_2.clone(),
+                        job.clone(),
+                        instance_id_clone.clone(),
+                    );
+                    let succeeded = match fetch_champion_data_exclusive(
+                        &mongo_client_clone_2,
+                        &instance_id_clone,
+                    )
+                    .await
+                    {
+                        Ok(true) => {
+                            log::info!("Champion data updated successfully.");
+                            true
+                        }
+                        // Une actualisation manuelle (`/owner refreshdata`) est en cours : elle compte pour la période
+                        Ok(false) => {
+                            log::info!("Champion data is already being updated, skipping.");
+                            true
+                        }
+                        Err(e) => {
+                            log::error!("Error updating champion data: {:?}", e);
+                            report_error(&*e, &[("task", "fetch_champion_data")]);
+                            false
+                        }
+                    };
+                    heartbeat.abort();
+                    // En cas d'échec, le bail est libéré pour qu'une autre instance réessaie
+                    let lock_result = if succeeded {
+                        hold_job_lock_until(
+                            &mongo_client_clone_2,
+                            &job,
+                            &instance_id_clone,
+                            period_end,
+                        )
+                        .await
+                    } else {
+                        release_job_lock(&mongo_client_clone_2, &job, &instance_id_clone).await
+                    };
+                    if let Err(e) = lock_result {
+                        log::error!("Error updating the lease of job '{}': {:?}", job, e);
+                    }
                 }
+                Ok(false) => log::info!(
+                    "Job '{}' already ran or is running on another instance, skipping.",
+                    job
+                ),
+                Err(e) => log::error!("Error acquiring the lease of job '{}': {:?}", job, e),
+            }
+            // Les correspondances des champions sont gardées en mémoire, chaque instance doit donc les reconstruire
+            match reload_champion_index(&champions_clone_for_loop).await {
+                Ok(_) => log::info!("DataDragon JSON updated successfully."),
                 Err(e) => {
                     log::error!("Error updating DataDragon JSON : {:?}", e);
+                    report_error(&*e, &[("task", "open_dd_json")]);
                 }
             }
-            sleep(Duration::from_secs(60 * 60 * 24)).await; // Attendre 24 heures
+            sleep(config().champion_refresh_interval).await;
+        }
+    });
+    tokio::spawn(async move {
+        loop {
+            let (job, period_end) = job_period(
+                "refresh_league_distributions",
+                Duration::from_secs(60 * 60 * 6),
+            );
+            match acquire_job_lock(&mongo_client_clone_4, &job, &instance_id_clone_2).await {
+                Ok(true) => {
+                    let heartbeat = spawn_job_lock_heartbeat(
+                        mongo_client_clone_4.clone(),
+                        job.clone(),
+                        instance_id_clone_2.clone(),
+                    );
+                    // Une région par passage : chacune est échantillonnée une fois par semaine
+                    let succeeded = match refresh_league_distributions(
+                        &mongo_client_clone_4,
+                        &riot_api_key_clone_3,
+                    )
+                    .await
+                    {
+                        Ok(_) => true,
+                        Err(e) => {
+                            log::error!("Error updating the league distributions: {:?}", e);
+                            report_error(&*e, &[("task", "refresh_league_distributions")]);
+                            false
+                        }
+                    };
+                    heartbeat.abort();
+                    let lock_result = if succeeded {
+                        hold_job_lock_until(
+                            &mongo_client_clone_4,
+                            &job,
+                            &instance_id_clone_2,
+                            period_end,
+                        )
+                        .await
+                    } else {
+                        release_job_lock(&mongo_client_clone_4, &job, &instance_id_clone_2).await
+                    };
+                    if let Err(e) = lock_result {
+                        log::error!("Error updating the lease of job '{}': {:?}", job, e);
+                    }
+                }
+                Ok(false) => log::info!(
+                    "Job '{}' already ran or is running on another instance, skipping.",
+                    job
+                ),
+                Err(e) => log::error!("Error acquiring the lease of job '{}': {:?}", job, e),
+            }
+            sleep(Duration::from_secs(60 * 60 * 6)).await; // Attendre 6 heures
+        }
+    });
+    tokio::spawn(async move {
+        let job = "weekly_posts".to_string();
+        loop {
+            match acquire_job_lock(&mongo_client_clone_5, &job, &instance_id_clone_3).await {
+                Ok(true) => {
+                    let heartbeat = spawn_job_lock_heartbeat(
+                        mongo_client_clone_5.clone(),
+                        job.clone(),
+                        instance_id_clone_3.clone(),
+                    );
+                    let champions = champions_clone_for_challenges.read().await.clone();
+                    // Les récapitulatifs d'objectifs de rang partent avec le défi de la semaine
+                    if let Err(e) = post_goal_recaps(
+                        &mongo_client_clone_5,
+                        &http_for_challenges,
+                        &riot_api_key_clone_4,
+                    )
+                    .await
+                    {
+                        log::error!("Error sending the goal recaps: {:?}", e);
+                        report_error(&*e, &[("task", "post_goal_recaps")]);
+                    }
+                    // Relevé hebdomadaire de la maîtrise des comptes liés
+                    if let Err(e) =
+                        record_mastery_snapshots(&mongo_client_clone_5, &riot_api_key_clone_4).await
+                    {
+                        log::error!("Error recording the mastery snapshots: {:?}", e);
+                        report_error(&*e, &[("task", "record_mastery_snapshots")]);
+                    }
+                    // Les parties des comptes liés alimentent le classement de la semaine
+                    if let Err(e) =
+                        archive_linked_games(&mongo_client_clone_5, &riot_api_key_clone_4).await
+                    {
+                        log::error!("Error archiving the games of the linked accounts: {:?}", e);
+                        report_error(&*e, &[("task", "archive_linked_games")]);
+                    }
+                    if let Err(e) = post_weekly_challenges(
+                        &mongo_client_clone_5,
+                        &http_for_challenges,
+                        &champions,
+                    )
+                    .await
+                    {
+                        log::error!("Error posting the challenges of the week: {:?}", e);
+                        report_error(&*e, &[("task", "post_weekly_challenges")]);
+                    }
+                    if let Err(e) = sync_clash_events(
+                        &mongo_client_clone_5,
+                        &http_for_challenges,
+                        &riot_api_key_clone_4,
+                    )
+                    .await
+                    {
+                        log::error!("Error synchronizing the Clash events: {:?}", e);
+                        report_error(&*e, &[("task", "sync_clash_events")]);
+                    }
+                    heartbeat.abort();
+                    if let Err(e) =
+                        release_job_lock(&mongo_client_clone_5, &job, &instance_id_clone_3).await
+                    {
+                        log::error!("Error releasing the lease of job '{}': {:?}", job, e);
+                    }
+                }
+                Ok(false) => log::info!("Job '{}' is running on another instance, skipping.", job),
+                Err(e) => log::error!("Error acquiring the lease of job '{}': {:?}", job, e),
+            }
+            sleep(Duration::from_secs(60 * 60)).await; // Attendre 1 heure
+        }
+    });
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = run_health_check(
+                &mongo_client_clone_3,
+                &riot_api_key_clone_2,
+                health_status_clone.clone(),
+                http_for_health.clone(),
+                ops_channel_id,
+                &champions_clone_for_health,
+            )
+            .await
+            {
+                log::error!("Error running the health check: {:?}", e);
+                report_error(&*e, &[("task", "health_check")]);
+            }
+            sleep(Duration::from_secs(5 * 60)).await; // Attendre 5 minutes
         }
     });
-    Ok(client.into())
+    Ok(StatSummonerService {
+        client,
+        shard_config,
+        _error_reporting_guard: error_reporting_guard,
+    })
 }
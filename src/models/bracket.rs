@@ -0,0 +1,58 @@
+#[derive(Debug, Clone, Copy, PartialEq, poise::ChoiceParameter)]
+pub enum RankBracket {
+    #[name = "All ranks"]
+    All,
+    #[name = "Iron–Gold"]
+    IronGold,
+    #[name = "Platinum+"]
+    PlatinumPlus,
+    #[name = "Diamond+"]
+    DiamondPlus,
+}
+
+impl RankBracket {
+    /// Brackets scraped in addition to the all-ranks statistics.
+    pub const SEGMENTED: [RankBracket; 3] = [
+        RankBracket::IronGold,
+        RankBracket::PlatinumPlus,
+        RankBracket::DiamondPlus,
+    ];
+
+    /// Key stored in `BracketStatsData::bracket`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            RankBracket::All => "all",
+            RankBracket::IronGold => "iron_gold",
+            RankBracket::PlatinumPlus => "platinum_plus",
+            RankBracket::DiamondPlus => "diamond_plus",
+        }
+    }
+
+    /// Bracket whose `key` is the given one.
+    pub fn from_key(key: &str) -> Option<RankBracket> {
+        [RankBracket::All]
+            .into_iter()
+            .chain(RankBracket::SEGMENTED)
+            .find(|bracket| bracket.key() == key)
+    }
+
+    /// Human readable label used in embeds.
+    pub fn label(&self) -> &'static str {
+        match self {
+            RankBracket::All => "All ranks",
+            RankBracket::IronGold => "Iron–Gold",
+            RankBracket::PlatinumPlus => "Platinum+",
+            RankBracket::DiamondPlus => "Diamond+",
+        }
+    }
+
+    /// League of Graphs rank filters whose statistics make up the bracket.
+    pub fn league_of_graphs_filters(&self) -> &'static [&'static str] {
+        match self {
+            RankBracket::All => &[],
+            RankBracket::IronGold => &["iron", "bronze", "silver", "gold"],
+            RankBracket::PlatinumPlus => &["platinumplus"],
+            RankBracket::DiamondPlus => &["diamondplus"],
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Lookup tables between the numeric keys, the IDs and the names of the champions, e.g. `62`, `MonkeyKing` and
+/// `Wukong`.
+///
+/// Built once from the Data Dragon JSON each time it is refreshed, and shared through `Data::champions`, so that the
+/// commands do not scan the Data Dragon JSON for every champion they display.
+#[derive(Debug, Clone, Default)]
+pub struct ChampionIndex {
+    /// ID of each champion by numeric key, the `championId` of the Riot API.
+    ids_by_key: HashMap<i64, String>,
+    /// ID of each champion by lowercase name and by lowercase ID.
+    ids_by_name: HashMap<String, String>,
+    /// Name of each champion by ID.
+    names_by_id: HashMap<String, String>,
+}
+
+impl ChampionIndex {
+    /// ⚙️ **Function**: Builds the lookup tables from the Data Dragon champion data.
+    ///
+    /// # Parameters:
+    /// - `dd_json`: The Data Dragon JSON, as returned by `open_dd_json`.
+    ///
+    /// # Returns:
+    /// - `ChampionIndex`: The lookup tables, empty if `dd_json` has no `data` object.
+    ///
+    /// # Example:
+    /// ```rust
+    /// let champions = ChampionIndex::from_dd_json(&dd_json);
+    /// assert_eq!(champions.id_from_key(62), Some("MonkeyKing"));
+    /// ```
+    pub fn from_dd_json(dd_json: &Value) -> Self {
+        let mut index = Self::default();
+        let Some(champion_map) = dd_json["data"].as_object() else {
+            return index;
+        };
+        for champion in champion_map.values() {
+            let (Some(id), Some(name)) = (champion["id"].as_str(), champion["name"].as_str())
+            else {
+                continue;
+            };
+            if let Some(key) = champion["key"].as_str().and_then(|key| key.parse().ok()) {
+                index.ids_by_key.insert(key, id.to_string());
+            }
+            index.ids_by_name.insert(id.to_lowercase(), id.to_string());
+            index
+                .ids_by_name
+                .insert(name.to_lowercase(), id.to_string());
+            index.names_by_id.insert(id.to_string(), name.to_string());
+        }
+        index
+    }
+
+    /// ⚙️ **Function**: Returns the ID of a champion from its numeric key, e.g. `62` → `MonkeyKing`.
+    pub fn id_from_key(&self, key: i64) -> Option<&str> {
+        self.ids_by_key.get(&key).map(String::as_str)
+    }
+
+    /// ⚙️ **Function**: Returns the ID of a champion from its name or ID, ignoring case, e.g. `wukong` → `MonkeyKing`.
+    pub fn id_from_name(&self, name: &str) -> Option<&str> {
+        self.ids_by_name
+            .get(&name.trim().to_lowercase())
+            .map(String::as_str)
+    }
+
+    /// ⚙️ **Function**: Returns the name of a champion from its ID, e.g. `MonkeyKing` → `Wukong`.
+    pub fn name(&self, id: &str) -> Option<&str> {
+        self.names_by_id.get(id).map(String::as_str)
+    }
+
+    /// ⚙️ **Function**: Returns the name of a champion from its numeric key, e.g. `62` → `Wukong`.
+    pub fn name_from_key(&self, key: i64) -> Option<&str> {
+        self.id_from_key(key).and_then(|id| self.name(id))
+    }
+
+    /// ⚙️ **Function**: Returns the names of all the champions, in no particular order.
+    pub fn names(&self) -> Vec<String> {
+        self.names_by_id.values().cloned().collect()
+    }
+
+    /// ⚙️ **Function**: Returns whether no champion is known, e.g. when Data Dragon could not be reached at startup.
+    pub fn is_empty(&self) -> bool {
+        self.names_by_id.is_empty()
+    }
+}
@@ -10,3 +10,64 @@ pub const QUEUE_ID_MAP: [(i64, &str); 10] = [
     (850, "Co-op vs AI Intermediate"),
     (900, "URF"),
 ];
+
+/// Queue ID of Arena games, notified with the augments of the followed players instead of the lane matchups.
+pub const ARENA_QUEUE_ID: i64 = 1700;
+
+pub const CHAMPION_DATA_SOURCE: &str = "League of Graphs";
+
+/// Nicknames of champions commonly typed by players, as `(nickname, Data Dragon ID)` pairs.
+///
+/// The nicknames are normalized (lowercase letters and digits only, see `normalize_champion_alias`). Server-specific
+/// nicknames added with `/settings alias` take precedence over these.
+pub const CHAMPION_ALIASES: &[(&str, &str)] = &[
+    ("ali", "Alistar"),
+    ("asol", "AurelionSol"),
+    ("bel", "Belveth"),
+    ("blitz", "Blitzcrank"),
+    ("cait", "Caitlyn"),
+    ("cass", "Cassiopeia"),
+    ("cho", "Chogath"),
+    ("eve", "Evelynn"),
+    ("ez", "Ezreal"),
+    ("fiddle", "Fiddlesticks"),
+    ("gp", "Gangplank"),
+    ("heca", "Hecarim"),
+    ("heimer", "Heimerdinger"),
+    ("j4", "JarvanIV"),
+    ("jarvan", "JarvanIV"),
+    ("kass", "Kassadin"),
+    ("kata", "Katarina"),
+    ("kha", "Khazix"),
+    ("kog", "KogMaw"),
+    ("lb", "Leblanc"),
+    ("lee", "LeeSin"),
+    ("liss", "Lissandra"),
+    ("malph", "Malphite"),
+    ("malz", "Malzahar"),
+    ("mf", "MissFortune"),
+    ("morde", "Mordekaiser"),
+    ("morg", "Morgana"),
+    ("mundo", "DrMundo"),
+    ("naut", "Nautilus"),
+    ("nida", "Nidalee"),
+    ("ori", "Orianna"),
+    ("panth", "Pantheon"),
+    ("rek", "RekSai"),
+    ("sej", "Sejuani"),
+    ("sera", "Seraphine"),
+    ("shyv", "Shyvana"),
+    ("tahm", "TahmKench"),
+    ("tf", "TwistedFate"),
+    ("trist", "Tristana"),
+    ("trynd", "Tryndamere"),
+    ("vel", "Velkoz"),
+    ("vlad", "Vladimir"),
+    ("voli", "Volibear"),
+    ("wukong", "MonkeyKing"),
+    ("ww", "Warwick"),
+    ("xin", "XinZhao"),
+    ("yas", "Yasuo"),
+    ("yi", "MasterYi"),
+    ("zil", "Zilean"),
+];
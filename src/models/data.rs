@@ -1,15 +1,40 @@
+use crate::models::champion_index::ChampionIndex;
+use crate::models::riot_id::RiotId;
 use mongodb::Client;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 pub struct Data {
     pub riot_api_key: String,
     pub mongo_client: Client,
-    pub dd_json: Arc<RwLock<Value>>,
+    /// Lookups between the keys, IDs and names of the champions, rebuilt each time Data Dragon is refreshed.
+    pub champions: Arc<RwLock<ChampionIndex>>,
+    pub health_status: Arc<RwLock<HealthStatus>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+pub struct DependencyHealth {
+    pub name: String,
+    pub healthy: bool,
+    pub detail: String,
+    pub latency_ms: u128,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HealthStatus {
+    pub dependencies: Vec<DependencyHealth>,
+    pub checked_at: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShardConfig {
+    pub total_shards: Option<u32>,
+    pub instance_index: u32,
+    pub instance_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SummonerFollowedData {
     pub puuid: String,
     pub summoner_id: String,
@@ -20,6 +45,302 @@ pub struct SummonerFollowedData {
     pub time_end_follow: String,
     pub channel_id: u64,
     pub guild_id: String,
+    #[serde(default)]
+    pub embed_layout: Option<EmbedLayout>,
+    #[serde(default)]
+    pub notification_filter: NotificationFilter,
+    /// Queues whose games are notified, all queues by default.
+    #[serde(default)]
+    pub queue_filter: QueueFilter,
+}
+
+impl SummonerFollowedData {
+    /// Riot ID of the followed summoner, e.g. `Faker#KR1`.
+    pub fn riot_id(&self) -> RiotId {
+        RiotId::stored(&self.name, &self.tag)
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationFilter {
+    #[default]
+    #[name = "All games"]
+    All,
+    #[name = "Ranked games only"]
+    RankedOnly,
+    #[name = "Games longer than 15 minutes"]
+    LongGames,
+    #[name = "Wins only"]
+    WinsOnly,
+    #[name = "Losses only"]
+    LossesOnly,
+    #[name = "Notable games (pentakill, promotion, huge KDA)"]
+    Notable,
+}
+
+/// Queues whose games are notified for a follow, checked before the `NotificationFilter`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueFilter {
+    #[default]
+    #[name = "All queues"]
+    All,
+    #[name = "Ranked (Solo/Duo and Flex)"]
+    Ranked,
+    #[name = "Ranked Solo/Duo"]
+    SoloDuo,
+    #[name = "Ranked Flex"]
+    Flex,
+    #[name = "ARAM"]
+    Aram,
+}
+
+impl QueueFilter {
+    /// Whether the games of a queue are notified with this filter.
+    pub fn accepts(self, queue_id: i64) -> bool {
+        match self {
+            QueueFilter::All => true,
+            QueueFilter::Ranked => matches!(queue_id, 420 | 440),
+            QueueFilter::SoloDuo => queue_id == 420,
+            QueueFilter::Flex => queue_id == 440,
+            QueueFilter::Aram => queue_id == 450,
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbedLayout {
+    #[default]
+    #[name = "Detailed"]
+    Detailed,
+    #[name = "Compact (one line per role)"]
+    Compact,
+    #[name = "Followed player only"]
+    PlayerOnly,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GuildSettingsData {
+    pub guild_id: String,
+    #[serde(default)]
+    pub embed_layout: EmbedLayout,
+    #[serde(default)]
+    pub theme: EmbedTheme,
+    #[serde(default)]
+    pub challenge_channel_id: Option<u64>,
+    #[serde(default)]
+    pub challenge_week: Option<String>,
+    #[serde(default)]
+    pub gold_graph: bool,
+    /// Whether the match notifications show the bans of both teams (`/settings bans`).
+    #[serde(default)]
+    pub show_bans: bool,
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHours>,
+    #[serde(default)]
+    pub notification_batch: NotificationBatch,
+    #[serde(default)]
+    pub live_status: Option<LiveStatusChannel>,
+    #[serde(default)]
+    pub clash_region: Option<String>,
+    /// Champion nicknames added with `/settings alias`, as normalized nickname to Data Dragon ID.
+    #[serde(default)]
+    pub champion_aliases: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LiveStatusChannel {
+    pub channel_id: u64,
+    /// Whether the status is written in the channel name (voice channels) or in its topic (text channels).
+    pub voice: bool,
+    /// Name or topic of the channel before any status was shown, restored when no followed player is in game.
+    pub original_text: String,
+    #[serde(default)]
+    pub last_status: Option<String>,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationBatch {
+    #[default]
+    #[name = "Off (one message per game)"]
+    Off,
+    #[name = "Every 15 minutes"]
+    Every15Minutes,
+    #[name = "Every 30 minutes"]
+    Every30Minutes,
+    #[name = "Every hour"]
+    EveryHour,
+}
+
+impl NotificationBatch {
+    /// Interval between two batches, in minutes, or `None` when notifications are posted right away.
+    pub fn minutes(&self) -> Option<i64> {
+        match self {
+            NotificationBatch::Off => None,
+            NotificationBatch::Every15Minutes => Some(15),
+            NotificationBatch::Every30Minutes => Some(30),
+            NotificationBatch::EveryHour => Some(60),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct QuietHours {
+    pub start_hour: u32,
+    pub end_hour: u32,
+}
+
+impl QuietHours {
+    /// Whether an hour (UTC, 0 to 23) falls within the quiet hours. The end hour is excluded, and quiet hours may span midnight.
+    pub fn contains(&self, hour: u32) -> bool {
+        if self.start_hour <= self.end_hour {
+            (self.start_hour..self.end_hour).contains(&hour)
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct EmbedTheme {
+    pub victory_color: Option<u32>,
+    pub defeat_color: Option<u32>,
+    pub info_color: Option<u32>,
+    pub footer: Option<String>,
+}
+
+impl EmbedTheme {
+    /// Color of victories and successful results (green by default).
+    pub fn victory(&self) -> u32 {
+        self.victory_color.unwrap_or(0x00ff00)
+    }
+
+    /// Color of defeats (red by default).
+    pub fn defeat(&self) -> u32 {
+        self.defeat_color.unwrap_or(0xff0000)
+    }
+
+    /// Color of informational embeds such as player or champion statistics (green by default).
+    pub fn info(&self) -> u32 {
+        self.info_color.unwrap_or(0x00ff00)
+    }
+
+    /// Footer text, prefixed with the guild's brand line when one is configured.
+    pub fn footer_text(&self, text: &str) -> String {
+        match &self.footer {
+            Some(footer) if text.is_empty() => footer.clone(),
+            Some(footer) => format!("{}\n{}", footer, text),
+            None => text.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkedAccountData {
+    pub discord_id: u64,
+    pub puuid: String,
+    pub summoner_id: String,
+    pub name: String,
+    pub tag: String,
+    pub region: String,
+    #[serde(default)]
+    pub verification_icon_id: Option<i64>,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+impl LinkedAccountData {
+    /// Riot ID of the linked account, e.g. `Faker#KR1`.
+    pub fn riot_id(&self) -> RiotId {
+        RiotId::stored(&self.name, &self.tag)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TeamData {
+    pub guild_id: String,
+    pub name: String,
+    /// Discord IDs of the members; their primary linked account is used.
+    pub members: Vec<u64>,
+    pub created_by: u64,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScrimData {
+    pub guild_id: String,
+    pub team: String,
+    pub opponent: String,
+    pub win: bool,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub match_id: Option<String>,
+    #[serde(default)]
+    pub tournament_code: Option<String>,
+    pub played_at: i64,
+    pub logged_by: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, poise::ChoiceParameter)]
+pub enum ScrimResult {
+    Win,
+    Loss,
+}
+
+/// Page of `/matchdetails` to display.
+#[derive(Debug, Clone, Copy, PartialEq, Default, poise::ChoiceParameter)]
+pub enum MatchDetailsPage {
+    #[default]
+    #[name = "Lanes"]
+    Lanes,
+    #[name = "Pings"]
+    Pings,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OptedOutSummonerData {
+    pub puuid: String,
+    pub discord_id: u64,
+    pub opted_out_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TosConsentData {
+    pub discord_id: u64,
+    pub version: String,
+    pub accepted_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlacklistedSummonerData {
+    pub puuid: String,
+    pub name: String,
+    pub tag: String,
+    pub guild_id: Option<String>,
+    pub added_by: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GuildFlagsData {
+    pub guild_id: String,
+    #[serde(default)]
+    pub disabled_features: Vec<String>,
+    #[serde(default)]
+    pub commands_used: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,6 +360,27 @@ pub struct ChampionData {
     pub banrate: String,
     pub runes: RunesData,
     pub core_build: CoreBuildData,
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub patch: String,
+    #[serde(default)]
+    pub updated_at: i64,
+    #[serde(default)]
+    pub brackets: Vec<BracketStatsData>,
+    #[serde(default)]
+    pub situational_build: SituationalBuildData,
+    /// Whether the statistics were corrected with `/owner overridechampion`, in which case the scrape leaves them as is.
+    #[serde(default, rename = "override")]
+    pub overridden: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BracketStatsData {
+    pub bracket: String,
+    pub popularity: String,
+    pub winrate: String,
+    pub banrate: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -60,3 +402,230 @@ pub struct CoreBuildData {
     pub second: String,
     pub third: String,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProBuildData {
+    pub champion_id: String,
+    pub pro_name: String,
+    pub opponent: String,
+    pub win: bool,
+    pub items: Vec<String>,
+    pub runes: Vec<String>,
+    pub played_at: i64,
+    pub source: String,
+    pub fetched_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SituationalBuildData {
+    pub boots: Vec<String>,
+    pub items: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobLockData {
+    #[serde(rename = "_id")]
+    pub job: String,
+    pub owner: String,
+    pub lease_until: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FollowAuditEvent {
+    Created,
+    Updated,
+    Extended,
+    Expired,
+    Deleted,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditLogData {
+    pub event: FollowAuditEvent,
+    pub puuid: String,
+    pub name: String,
+    pub tag: String,
+    pub guild_id: String,
+    pub actor_id: Option<u64>,
+    pub time_end_follow: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RankSnapshotData {
+    pub puuid: String,
+    pub queue_type: String,
+    pub season: i32,
+    pub tier: String,
+    pub division: String,
+    pub league_points: i64,
+    pub recorded_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RankGoalData {
+    pub discord_id: u64,
+    pub puuid: String,
+    pub summoner_id: String,
+    pub region: String,
+    pub tier: String,
+    pub division: String,
+    pub set_at: i64,
+    #[serde(default)]
+    pub recap_week: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChampionMasteryCacheData {
+    pub puuid: String,
+    pub champion_id: i64,
+    pub points: i64,
+    /// A BSON date, so MongoDB removes the stale entries by itself (TTL index, see `ensure_indexes`).
+    pub fetched_at: mongodb::bson::DateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MatchRecordData {
+    pub match_id: String,
+    pub guild_id: String,
+    pub queue_id: i64,
+    pub played_at: i64,
+    /// Start of the game (UNIX seconds), `0` for the games archived before it was kept.
+    #[serde(default)]
+    pub started_at: i64,
+    pub team_id: i64,
+    pub win: bool,
+    pub followed_puuids: Vec<String>,
+    pub participants: Vec<MatchParticipantData>,
+    #[serde(default)]
+    pub bans: Vec<MatchBanData>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MatchBanData {
+    pub team_id: i64,
+    pub champion_id: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MatchParticipantData {
+    pub puuid: String,
+    pub team_id: i64,
+    pub champion_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LiveGameData {
+    pub puuid: String,
+    pub match_id: String,
+    pub seen_at: i64,
+    #[serde(default)]
+    pub started_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClashEventData {
+    pub guild_id: String,
+    pub tournament_id: i64,
+    /// ID of the tournament phase (one per Clash day).
+    pub schedule_id: i64,
+    pub event_id: u64,
+    pub start_time: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MasterySnapshotData {
+    pub puuid: String,
+    pub week: String,
+    pub taken_at: i64,
+    pub champions: Vec<ChampionPointsData>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChampionPointsData {
+    pub champion_id: i64,
+    pub points: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlayedGameData {
+    pub puuid: String,
+    pub match_id: String,
+    pub queue_id: i64,
+    pub played_at: i64,
+    /// Start of the game (UNIX seconds), `0` for the games archived before it was kept.
+    #[serde(default)]
+    pub started_at: i64,
+    pub duration: i64,
+    #[serde(default)]
+    pub champion_name: String,
+    /// Champions (IDs) banned by the enemy team.
+    #[serde(default)]
+    pub banned_against: Vec<i64>,
+    /// `None` for the games archived before the result was kept.
+    #[serde(default)]
+    pub win: Option<bool>,
+    /// Champions (names) of the enemy team.
+    #[serde(default)]
+    pub enemy_champions: Vec<String>,
+    /// Whether the game ended in a surrender, `None` for the games archived before it was kept.
+    #[serde(default)]
+    pub surrender: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueuedNotificationData {
+    pub guild_id: String,
+    pub match_id: String,
+    pub summoners: Vec<SummonerFollowedData>,
+    pub queued_at: i64,
+    /// Number of failed attempts to post the notification, dropped after `MAX_NOTIFICATION_ATTEMPTS`.
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LeagueDistributionData {
+    pub region: String,
+    pub queue_type: String,
+    pub divisions: Vec<DivisionCountData>,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DivisionCountData {
+    pub tier: String,
+    pub division: String,
+    pub players: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GuildBackupData {
+    pub version: u32,
+    pub guild_id: String,
+    pub exported_at: i64,
+    pub flags: Option<GuildFlagsData>,
+    #[serde(default)]
+    pub settings: Option<GuildSettingsData>,
+    #[serde(default)]
+    pub follows: Vec<SummonerFollowedData>,
+    #[serde(default)]
+    pub blacklist: Vec<BlacklistedSummonerData>,
+    #[serde(default)]
+    pub linked_accounts: Vec<LinkedAccountData>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ComponentStateData {
+    /// Key shared by the custom IDs of the components of a message.
+    pub key: String,
+    /// What the components do, e.g. `views`.
+    pub kind: String,
+    /// The only user allowed to use the components, `None` for everyone.
+    pub author_id: Option<u64>,
+    /// The context needed to answer the components, as JSON.
+    pub payload: String,
+    /// A BSON date, so MongoDB removes the expired states by itself (TTL index, see `ensure_indexes`).
+    pub expires_at: mongodb::bson::DateTime,
+}
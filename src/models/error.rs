@@ -1 +1,199 @@
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// Error returned when the Riot API rate limits every available key.
+#[derive(Debug)]
+pub struct RateLimitedError {
+    pub retry_after: std::time::Duration,
+}
+
+impl std::fmt::Display for RateLimitedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Riot is rate limiting us — try again in ~{}s.",
+            self.retry_after.as_secs().max(1)
+        )
+    }
+}
+
+impl std::error::Error for RateLimitedError {}
+
+/// Typed errors raised by the bot itself, each one mapped to an error code by `ErrorDetails::from_error`.
+#[derive(Debug)]
+pub enum BotError {
+    /// The Riot ID does not match any account.
+    SummonerNotFound,
+    /// The account exists but has no summoner profile in the requested region.
+    SummonerProfileNotFound,
+    /// Riot answered a request with an unexpected HTTP status.
+    RiotStatus { method: &'static str, status: u16 },
+    /// No Riot API key is configured or usable.
+    NoRiotApiKey,
+    /// A Riot API request has no fixture while `RIOT_FIXTURES_MODE` is `replay-only`.
+    MissingFixture { path: String },
+}
+
+impl std::fmt::Display for BotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BotError::SummonerNotFound => write!(
+                f,
+                "The player could not be found. Please verify that the region, game name, and tag line you provided are correct, and try again."
+            ),
+            BotError::SummonerProfileNotFound => write!(
+                f,
+                "Error retrieving summoner profile. Please verify that the region, game name, and tag line you provided are correct, and try again."
+            ),
+            BotError::RiotStatus { method, status } => {
+                write!(f, "Riot API answered {} with status {}", method, status)
+            }
+            BotError::NoRiotApiKey => write!(f, "No Riot API key is available."),
+            BotError::MissingFixture { path } => write!(f, "No Riot API fixture at {}", path),
+        }
+    }
+}
+
+impl std::error::Error for BotError {}
+
+/// Machine-readable description of an error, displayed in error embeds and kept in the recent errors log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorDetails {
+    /// Short code such as `RIOT-404-SUMMONER` or `DB-TIMEOUT`.
+    pub code: String,
+    /// The likely cause of the error.
+    pub cause: &'static str,
+    /// What the user (or the bot owner) can do about it.
+    pub hint: &'static str,
+}
+
+impl ErrorDetails {
+    fn new(code: impl Into<String>, cause: &'static str, hint: &'static str) -> Self {
+        ErrorDetails {
+            code: code.into(),
+            cause,
+            hint,
+        }
+    }
+
+    /// Details used for errors that are not raised by an `Error` value (invalid input, missing data...).
+    pub fn usage() -> Self {
+        ErrorDetails::new(
+            "BOT-USAGE",
+            "The command could not be completed with the values provided.",
+            "Check the values you entered and try again.",
+        )
+    }
+
+    /// ⚙️ **Function**: Classifies an error into its code, likely cause and suggested fix.
+    ///
+    /// # Parameters:
+    /// - `error`: The error to classify. Its `source()` chain is walked until a known error type is found.
+    ///
+    /// # Returns:
+    /// - `ErrorDetails`: The details of the first known error of the chain, or `BOT-INTERNAL` when none is known.
+    ///
+    /// # Example:
+    /// ```rust
+    /// let details = ErrorDetails::from_error(&*e);
+    /// assert_eq!(details.code, "RIOT-404-SUMMONER");
+    /// ```
+    pub fn from_error(error: &(dyn std::error::Error + 'static)) -> Self {
+        let mut current: Option<&(dyn std::error::Error + 'static)> = Some(error);
+        while let Some(error) = current {
+            if let Some(details) = Self::from_known_error(error) {
+                return details;
+            }
+            current = error.source();
+        }
+        ErrorDetails::new(
+            "BOT-INTERNAL",
+            "An unexpected error happened inside the bot.",
+            "Try again later. If it keeps happening, report the error code to the bot owner.",
+        )
+    }
+
+    fn from_known_error(error: &(dyn std::error::Error + 'static)) -> Option<Self> {
+        if let Some(error) = error.downcast_ref::<BotError>() {
+            return Some(match error {
+                BotError::SummonerNotFound => ErrorDetails::new(
+                    "RIOT-404-SUMMONER",
+                    "No Riot account matches this game name and tag line.",
+                    "Check the spelling of the Riot ID (Name#TAG) and the selected region.",
+                ),
+                BotError::SummonerProfileNotFound => ErrorDetails::new(
+                    "RIOT-404-PROFILE",
+                    "The account has no League of Legends profile in this region.",
+                    "Select the region the player actually plays on.",
+                ),
+                BotError::RiotStatus { status, .. } => ErrorDetails::new(
+                    format!("RIOT-{}", status),
+                    match status {
+                        401 | 403 => "The Riot API key was rejected.",
+                        404 => "Riot has no data for this request.",
+                        500..=599 => "The Riot API is having trouble.",
+                        _ => "The Riot API refused the request.",
+                    },
+                    match status {
+                        401 | 403 => "The bot owner must renew the Riot API key.",
+                        500..=599 => "Try again in a few minutes, or check /botstatus.",
+                        _ => "Try again later.",
+                    },
+                ),
+                BotError::NoRiotApiKey => ErrorDetails::new(
+                    "RIOT-NO-KEY",
+                    "No Riot API key is configured.",
+                    "The bot owner must configure a Riot API key.",
+                ),
+                BotError::MissingFixture { .. } => ErrorDetails::new(
+                    "BOT-NO-FIXTURE",
+                    "The request was not recorded in the Riot API fixtures.",
+                    "Record it with RIOT_FIXTURES_MODE = \"record\", or use the \"replay\" mode.",
+                ),
+            });
+        }
+        if error.downcast_ref::<RateLimitedError>().is_some() {
+            return Some(ErrorDetails::new(
+                "RIOT-429",
+                "Riot is rate limiting the bot.",
+                "Wait a few seconds and try again.",
+            ));
+        }
+        if let Some(error) = error.downcast_ref::<mongodb::error::Error>() {
+            return Some(match *error.kind {
+                mongodb::error::ErrorKind::ServerSelection { .. } => ErrorDetails::new(
+                    "DB-TIMEOUT",
+                    "The database did not answer in time.",
+                    "Try again in a few minutes.",
+                ),
+                _ => ErrorDetails::new(
+                    "DB-ERROR",
+                    "The database rejected the request.",
+                    "Try again later. If it keeps happening, report the error code to the bot owner.",
+                ),
+            });
+        }
+        if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+            return Some(if error.is_timeout() {
+                ErrorDetails::new(
+                    "NET-TIMEOUT",
+                    "An external service did not answer in time.",
+                    "Try again in a few minutes.",
+                )
+            } else {
+                ErrorDetails::new(
+                    "NET-ERROR",
+                    "An external service could not be reached or sent an invalid answer.",
+                    "Try again later.",
+                )
+            });
+        }
+        if error.downcast_ref::<serde_json::Error>().is_some() {
+            return Some(ErrorDetails::new(
+                "DATA-INVALID",
+                "Some data could not be read.",
+                "Try again later. If it keeps happening, report the error code to the bot owner.",
+            ));
+        }
+        None
+    }
+}
@@ -1,6 +1,10 @@
+pub mod bracket;
+pub mod champion_index;
 pub mod constants;
 pub mod data;
 pub mod error;
 pub mod modal;
+pub mod rank;
 pub mod region;
+pub mod riot_id;
 pub mod role;
@@ -3,29 +3,17 @@ use poise::Modal;
 #[derive(Debug, Modal)]
 #[name = "Enter your League of Legends Stats Info"]
 pub struct LolStatsModal {
-    #[name = "Game Name"]
-    #[placeholder = "Enter your game name (e.g., Faker)"]
-    pub game_name: String,
-
-    #[name = "Tag Line"]
-    #[placeholder = "Enter your tag line (e.g., 1234)"]
-    pub tag_line: String,
+    #[name = "Riot ID"]
+    #[placeholder = "Enter your Riot ID (e.g., Faker#KR1)"]
+    pub riot_id: String,
 }
 
 #[derive(Debug, Modal)]
 #[name = "Enter the summoner info"]
 pub struct FollowGamesModal {
-    #[name = "Game Name"]
-    #[placeholder = "Enter the game name (e.g., Faker)"]
-    pub game_name: String,
-
-    #[name = "Tag Line"]
-    #[placeholder = "Enter the tag line (e.g., 1234)"]
-    pub tag_line: String,
-
-    #[name = "Time Followed (in hours)"]
-    #[placeholder = "Enter the number of hours (e.g., 2)"]
-    pub time_followed: String,
+    #[name = "Riot ID"]
+    #[placeholder = "Enter the Riot ID (e.g., Faker#KR1)"]
+    pub riot_id: String,
 }
 
 #[derive(Debug, Modal)]
@@ -35,3 +23,11 @@ pub struct ChampionsInfosModal {
     #[placeholder = "Enter the champion name (e.g., Jinx)"]
     pub champion_name: String,
 }
+
+#[derive(Debug, Modal)]
+#[name = "Link your League of Legends account"]
+pub struct LinkAccountModal {
+    #[name = "Riot ID"]
+    #[placeholder = "Enter your Riot ID (e.g., Faker#KR1)"]
+    pub riot_id: String,
+}
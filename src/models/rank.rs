@@ -0,0 +1,56 @@
+#[derive(Debug, Clone, Copy, PartialEq, poise::ChoiceParameter)]
+pub enum Tier {
+    Iron,
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+    Emerald,
+    Diamond,
+    Master,
+    Grandmaster,
+    Challenger,
+}
+
+impl Tier {
+    /// Tier as returned by the Riot API (e.g. `PLATINUM`).
+    pub fn key(&self) -> &'static str {
+        match self {
+            Tier::Iron => "IRON",
+            Tier::Bronze => "BRONZE",
+            Tier::Silver => "SILVER",
+            Tier::Gold => "GOLD",
+            Tier::Platinum => "PLATINUM",
+            Tier::Emerald => "EMERALD",
+            Tier::Diamond => "DIAMOND",
+            Tier::Master => "MASTER",
+            Tier::Grandmaster => "GRANDMASTER",
+            Tier::Challenger => "CHALLENGER",
+        }
+    }
+
+    /// Whether the tier has no divisions (Master and above).
+    pub fn is_apex(&self) -> bool {
+        matches!(self, Tier::Master | Tier::Grandmaster | Tier::Challenger)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, poise::ChoiceParameter)]
+pub enum Division {
+    IV,
+    III,
+    II,
+    I,
+}
+
+impl Division {
+    /// Division as returned by the Riot API (e.g. `IV`).
+    pub fn key(&self) -> &'static str {
+        match self {
+            Division::IV => "IV",
+            Division::III => "III",
+            Division::II => "II",
+            Division::I => "I",
+        }
+    }
+}
@@ -12,3 +12,20 @@ pub enum Region {
     TR,
     JP,
 }
+
+impl Region {
+    /// Every region, in the order of the command choices.
+    pub const ALL: [Region; 11] = [
+        Region::EUW,
+        Region::NA,
+        Region::KR,
+        Region::EUNE,
+        Region::BR,
+        Region::LAN,
+        Region::LAS,
+        Region::OCE,
+        Region::RU,
+        Region::TR,
+        Region::JP,
+    ];
+}
@@ -0,0 +1,91 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Riot ID of a player, e.g. `Faker#KR1`.
+///
+/// Parsed from the `Name#TAG` form with `str::parse`, which also makes it usable as a slash command parameter: Discord
+/// shows a text option, and an invalid value is refused with the hint of `InvalidRiotId` before the command runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RiotId {
+    /// The game name, e.g. `Faker`.
+    pub name: String,
+    /// The tag line, without the `#`, e.g. `KR1`.
+    pub tag: String,
+}
+
+/// Reason why a text is not a valid Riot ID, short enough to be used as a modal title.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidRiotId(pub &'static str);
+
+impl fmt::Display for InvalidRiotId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidRiotId {}
+
+impl RiotId {
+    /// ⚙️ **Function**: Builds a Riot ID from a game name and a tag line, checking both.
+    ///
+    /// # Parameters:
+    /// - `name`: The game name, 3 to 16 characters.
+    /// - `tag`: The tag line, 3 to 5 letters or digits.
+    ///
+    /// # Returns:
+    /// - `Result<RiotId, InvalidRiotId>`: The trimmed Riot ID, or the reason why it is invalid.
+    ///
+    /// # Example:
+    /// ```rust
+    /// let riot_id = RiotId::new("Faker", "KR1")?;
+    /// assert_eq!(riot_id.to_string(), "Faker#KR1");
+    /// ```
+    pub fn new(name: &str, tag: &str) -> Result<Self, InvalidRiotId> {
+        let (name, tag) = (name.trim(), tag.trim());
+        if !(3..=16).contains(&name.chars().count()) {
+            return Err(InvalidRiotId("Game name: 3 to 16 characters"));
+        }
+        if !(3..=5).contains(&tag.chars().count()) || !tag.chars().all(char::is_alphanumeric) {
+            return Err(InvalidRiotId("Tag line: 3 to 5 letters or digits"));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            tag: tag.to_string(),
+        })
+    }
+
+    /// ⚙️ **Function**: Builds a Riot ID from stored values, without checking them.
+    ///
+    /// # Example:
+    /// ```rust
+    /// let riot_id = RiotId::stored(&account.name, &account.tag);
+    /// ```
+    pub fn stored(name: &str, tag: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            tag: tag.to_string(),
+        }
+    }
+
+    /// ⚙️ **Function**: Returns the game name encoded for a Riot API URL (spaces replaced with `%20`).
+    pub fn url_name(&self) -> String {
+        self.name.replace(' ', "%20")
+    }
+}
+
+impl FromStr for RiotId {
+    type Err = InvalidRiotId;
+
+    fn from_str(riot_id: &str) -> Result<Self, Self::Err> {
+        let Some((name, tag)) = riot_id.split_once('#') else {
+            return Err(InvalidRiotId("Riot ID: use Name#TAG, e.g. Faker#KR1"));
+        };
+        RiotId::new(name, tag)
+    }
+}
+
+impl fmt::Display for RiotId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.name, self.tag)
+    }
+}
@@ -1,4 +1,4 @@
-#[derive(Debug, poise::ChoiceParameter)]
+#[derive(Debug, Clone, poise::ChoiceParameter)]
 pub enum Role {
     TOPLANE,
     JUNGLE,
@@ -0,0 +1,121 @@
+use crate::config::deletion_notice;
+use crate::embed::{create_embed_coded_error, schedule_message_deletion};
+use crate::models::data::{Data, FollowAuditEvent};
+use crate::models::error::Error;
+use crate::module::auditlog::utils::get_recent_audit_entries;
+use crate::module::settings::utils::get_guild_theme;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use poise::CreateReply;
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum AuditEventChoice {
+    Created,
+    Updated,
+    Extended,
+    Expired,
+    Deleted,
+}
+
+impl From<AuditEventChoice> for FollowAuditEvent {
+    fn from(choice: AuditEventChoice) -> Self {
+        match choice {
+            AuditEventChoice::Created => FollowAuditEvent::Created,
+            AuditEventChoice::Updated => FollowAuditEvent::Updated,
+            AuditEventChoice::Extended => FollowAuditEvent::Extended,
+            AuditEventChoice::Expired => FollowAuditEvent::Expired,
+            AuditEventChoice::Deleted => FollowAuditEvent::Deleted,
+        }
+    }
+}
+
+/// Displays the most recent follow lifecycle events of the server (administrators only).
+///
+/// Each line shows the event, the summoner, the member who triggered it and when it happened.
+///
+/// # Parameters:
+/// - `event`: Optionally, only display one kind of event.
+/// - `limit`: The number of entries to display (1-25, 10 by default).
+///
+/// # Example:
+/// ```rust
+/// /auditlog event: Created limit: 20
+/// ```
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn auditlog(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Only display this kind of event"] event: Option<AuditEventChoice>,
+    #[description = "Number of entries to display (1-25)"]
+    #[min = 1]
+    #[max = 25]
+    limit: Option<u32>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let entries = match get_recent_audit_entries(
+        &ctx.data().mongo_client,
+        &guild_id,
+        event.map(FollowAuditEvent::from),
+        limit.unwrap_or(10) as i64,
+    )
+    .await
+    {
+        Ok(entries) => entries,
+        Err(e) => {
+            let reply = ctx
+                .send(create_embed_coded_error(
+                    "Error collecting informations from MongoDB",
+                    &e.into(),
+                ))
+                .await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(());
+        }
+    };
+
+    let description = if entries.is_empty() {
+        "No follow event has been recorded in this server yet.".to_string()
+    } else {
+        entries
+            .iter()
+            .map(|entry| {
+                let (emoji, label) = match entry.event {
+                    FollowAuditEvent::Created => ("🟢", "followed"),
+                    FollowAuditEvent::Updated => ("✏️", "updated"),
+                    FollowAuditEvent::Extended => ("⏫", "extended"),
+                    FollowAuditEvent::Expired => ("⌛", "expired"),
+                    FollowAuditEvent::Deleted => ("🗑️", "deleted"),
+                };
+                let actor = entry
+                    .actor_id
+                    .map_or("Stat Summoner".to_string(), |id| format!("<@{}>", id));
+                format!(
+                    "{} <t:{}:R> **{}#{}** {} by {}",
+                    emoji, entry.timestamp, entry.name, entry.tag, label, actor
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+    let theme = get_guild_theme(ctx).await;
+    let embed = CreateEmbed::new()
+        .title("Follow audit log")
+        .description(description)
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+    let reply = ctx
+        .send(CreateReply {
+            embeds: vec![embed],
+            ephemeral: Some(true),
+            ..Default::default()
+        })
+        .await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
@@ -0,0 +1,25 @@
+/// 🛠 **Module auditlog**: Contains the `/auditlog` command and the follow lifecycle audit trail.
+///
+/// Every follow creation, update, extension, expiration and deletion is stored in the `audit_log` collection with
+/// the member who triggered it (if any), the guild and the time of the event. Administrators can browse the most
+/// recent entries of their server to moderate who keeps adding follows.
+///
+/// # Files in this module:
+/// - `auditlog.rs`: The `/auditlog` command listing the latest events of the guild.
+/// - `utils.rs`: The helpers recording and reading audit entries.
+///
+/// # Example:
+/// The command must be registered in the bot's main framework setup:
+///
+/// ```rust
+/// use module::auditlog::auditlog::auditlog;
+///
+/// let framework = poise::Framework::builder()
+///     .options(poise::FrameworkOptions {
+///         commands: vec![auditlog()],
+///         ..Default::default()
+///     })
+///     .build();
+/// ```
+pub mod auditlog;
+pub mod utils;
@@ -0,0 +1,76 @@
+use crate::models::data::{AuditLogData, FollowAuditEvent, SummonerFollowedData};
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+
+fn audit_log_collection(mongo_client: &Client) -> Collection<AuditLogData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<AuditLogData>("audit_log")
+}
+
+/// ⚙️ **Function**: Records a follow lifecycle event in the `audit_log` collection.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `event`: The kind of event (`Created`, `Updated`, `Extended`, `Expired` or `Deleted`).
+/// - `followed_summoner`: The follow concerned by the event, after the change.
+/// - `actor_id`: The Discord ID of the member who triggered the event, or `None` for the bot itself (e.g. expirations).
+///
+/// # Example:
+/// ```rust
+/// record_follow_event(mongo_client, FollowAuditEvent::Created, &new_followed_summoner, Some(ctx.author().id.get())).await;
+/// ```
+///
+/// # Notes:
+/// - The audit log must never prevent a follow from being handled: a failed insertion is only logged.
+pub async fn record_follow_event(
+    mongo_client: &Client,
+    event: FollowAuditEvent,
+    followed_summoner: &SummonerFollowedData,
+    actor_id: Option<u64>,
+) {
+    let entry = AuditLogData {
+        event,
+        puuid: followed_summoner.puuid.clone(),
+        name: followed_summoner.name.clone(),
+        tag: followed_summoner.tag.clone(),
+        guild_id: followed_summoner.guild_id.clone(),
+        actor_id,
+        time_end_follow: followed_summoner.time_end_follow.clone(),
+        timestamp: Utc::now().timestamp(),
+    };
+    if let Err(e) = audit_log_collection(mongo_client).insert_one(entry).await {
+        log::error!("Error recording audit log entry: {:?}", e);
+    }
+}
+
+/// ⚙️ **Function**: Retrieves the most recent audit entries of a guild.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `event`: An optional event kind to filter on.
+/// - `limit`: The maximum number of entries to return.
+///
+/// # Returns:
+/// - `Result<Vec<AuditLogData>, mongodb::error::Error>`: The entries, most recent first.
+pub async fn get_recent_audit_entries(
+    mongo_client: &Client,
+    guild_id: &str,
+    event: Option<FollowAuditEvent>,
+    limit: i64,
+) -> Result<Vec<AuditLogData>, mongodb::error::Error> {
+    let mut filter = doc! { "guild_id": guild_id };
+    if let Some(event) = event {
+        filter.insert("event", mongodb::bson::to_bson(&event)?);
+    }
+    audit_log_collection(mongo_client)
+        .find(filter)
+        .sort(doc! { "timestamp": -1 })
+        .limit(limit)
+        .await?
+        .try_collect()
+        .await
+}
@@ -0,0 +1,141 @@
+use crate::config::config;
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, create_embed_sucess, schedule_message_deletion,
+};
+use crate::models::data::{Data, GuildBackupData};
+use crate::models::error::Error;
+use crate::module::backup::utils::{export_guild_data, import_guild_data};
+use poise::serenity_prelude::{self as serenity, CreateAttachment};
+use std::collections::HashSet;
+
+/// Largest backup file accepted by `/backup import`, in bytes.
+const MAX_BACKUP_SIZE: u32 = 8 * 1024 * 1024;
+
+/// Exports or imports the data of the server (administrators only).
+///
+/// # Example:
+/// ```rust
+/// /backup export
+/// /backup import file: stat-summoner-backup-123456789.json
+/// ```
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("export", "import"),
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn backup(_ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Exports the server settings, follows and blacklist to a JSON file.
+///
+/// The linked accounts of the followed summoners are included too. The file is only visible to the member who ran the command.
+#[poise::command(slash_command, guild_only)]
+pub async fn export(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let backup =
+        match export_guild_data(&ctx.data().mongo_client, ctx.serenity_context(), guild_id).await {
+            Ok(backup) => backup,
+            Err(e) => {
+                let reply = ctx
+                    .send(create_embed_coded_error(
+                        "Error exporting the server data",
+                        &e,
+                    ))
+                    .await?;
+                schedule_message_deletion(reply, ctx).await?;
+                return Ok(());
+            }
+        };
+    let json = serde_json::to_vec_pretty(&backup)?;
+    let sucess_message = format!(
+        "Backup of **{}** follow(s) and **{}** blacklist entry(ies). Keep this file safe: it contains the Riot IDs of the followed summoners.",
+        backup.follows.len(),
+        backup.blacklist.len()
+    );
+    let mut reply = create_embed_sucess(&sucess_message);
+    reply.ephemeral = Some(true);
+    reply = reply.attachment(CreateAttachment::bytes(
+        json,
+        format!("stat-summoner-backup-{}.json", guild_id),
+    ));
+    ctx.send(reply).await?;
+    Ok(())
+}
+
+/// Imports a backup made with `/backup export`, replacing the follows and blacklist of the server.
+///
+/// The guild settings and the linked accounts contained in the file are only restored when a bot owner imports it.
+/// Follows posting in a channel that is not in this server are moved to the channel of the command.
+#[poise::command(slash_command, guild_only)]
+pub async fn import(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Backup file made with /backup export"] file: serenity::Attachment,
+) -> Result<(), Error> {
+    if file.size > MAX_BACKUP_SIZE {
+        let error_message = "This file is too large to be a Stat Summoner backup.";
+        let reply = ctx.send(create_embed_error(&error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+    ctx.defer().await?;
+    let content = file.download().await?;
+    let backup: GuildBackupData = match serde_json::from_slice(&content) {
+        Ok(backup) => backup,
+        Err(e) => {
+            let error_message = format!("This file is not a valid Stat Summoner backup: {}", e);
+            let reply = ctx.send(create_embed_error(&error_message)).await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(());
+        }
+    };
+
+    let Some(guild) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let guild_id = guild.get().to_string();
+    let channel_ids: HashSet<u64> = match guild.channels(ctx).await {
+        Ok(channels) => channels.keys().map(|channel_id| channel_id.get()).collect(),
+        Err(e) => {
+            let reply = ctx
+                .send(create_embed_coded_error(
+                    "Error reading the channels of the server",
+                    &e.into(),
+                ))
+                .await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(());
+        }
+    };
+    let trusted = ctx.framework().options().owners.contains(&ctx.author().id);
+    let reply = match import_guild_data(
+        &ctx.data().mongo_client,
+        &guild_id,
+        backup,
+        trusted,
+        &channel_ids,
+        ctx.channel_id().get(),
+    )
+    .await
+    {
+        Ok(summary) if summary.skipped_follows > 0 => create_embed_sucess(&format!(
+            "Backup imported: **{}** follow(s) and **{}** blacklist entry(ies) restored. **{}** follow(s) were left out: expired, opted out, blacklisted or beyond the limit of {} follows per server.",
+            summary.follows,
+            summary.blacklist,
+            summary.skipped_follows,
+            config().max_follows_per_guild
+        )),
+        Ok(summary) => create_embed_sucess(&format!(
+            "Backup imported: **{}** follow(s) and **{}** blacklist entry(ies) restored.",
+            summary.follows, summary.blacklist
+        )),
+        Err(e) => create_embed_coded_error("Error importing the backup", &e),
+    };
+    let reply = ctx.send(reply).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
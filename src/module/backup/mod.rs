@@ -0,0 +1,25 @@
+/// 🛠 **Module backup**: Contains the `/backup` command exporting and importing the data of a guild.
+///
+/// A backup is a JSON file holding the guild settings, its follows, its blacklist and the linked accounts of the
+/// followed summoners. It can be re-imported in the same guild on another bot instance (migration) or after a data
+/// loss (disaster recovery).
+///
+/// # Files in this module:
+/// - `backup.rs`: The `/backup` command and its `export` and `import` subcommands.
+/// - `utils.rs`: The helpers building a backup from the database and restoring it.
+///
+/// # Example:
+/// The command must be registered in the bot's main framework setup:
+///
+/// ```rust
+/// use module::backup::backup::backup;
+///
+/// let framework = poise::Framework::builder()
+///     .options(poise::FrameworkOptions {
+///         commands: vec![backup()],
+///         ..Default::default()
+///     })
+///     .build();
+/// ```
+pub mod backup;
+pub mod utils;
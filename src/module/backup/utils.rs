@@ -0,0 +1,284 @@
+use crate::config::config;
+use crate::models::data::{
+    BlacklistedSummonerData, GuildBackupData, GuildFlagsData, GuildSettingsData, LinkedAccountData,
+    SummonerFollowedData,
+};
+use crate::models::error::Error;
+use crate::module::blacklist::utils::find_blacklist_entry;
+use crate::module::followgames::utils::clamp_follow_end;
+use crate::module::myaccounts::utils::is_guild_member;
+use crate::module::optout::utils::is_opted_out;
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::{Client, ClientSession};
+use poise::serenity_prelude::{CacheHttp, GuildId, UserId};
+use std::collections::HashSet;
+
+/// Version of the backup format, increased whenever its structure changes.
+pub const BACKUP_VERSION: u32 = 1;
+
+/// ⚙️ **Function**: Builds the backup of a guild from the database.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `cache_http`: The Discord cache and HTTP client, used to check the owners of the linked accounts.
+/// - `guild_id`: The ID of the guild to export.
+///
+/// # Returns:
+/// - `Result<GuildBackupData, Error>`: The guild flags, settings, follows, server blacklist and the linked accounts of the followed summoners.
+///
+/// # Example:
+/// ```rust
+/// let backup = export_guild_data(&mongo_client, ctx.serenity_context(), guild_id).await?;
+/// let json = serde_json::to_vec_pretty(&backup)?;
+/// ```
+///
+/// # Notes:
+/// - Global blacklist entries are not part of a guild backup.
+/// - Only the linked accounts of members of the guild are exported (see `is_guild_member`), so a backup never reveals
+///   the accounts of users outside the server.
+pub async fn export_guild_data(
+    mongo_client: &Client,
+    cache_http: impl CacheHttp,
+    guild_id: GuildId,
+) -> Result<GuildBackupData, Error> {
+    let guild_id_str = guild_id.get().to_string();
+    let guild_id_str = guild_id_str.as_str();
+    let database = mongo_client.database("stat-summoner");
+    let flags = database
+        .collection::<GuildFlagsData>("guild_flags")
+        .find_one(doc! { "guild_id": guild_id_str })
+        .await?;
+    let settings = database
+        .collection::<GuildSettingsData>("guild_settings")
+        .find_one(doc! { "guild_id": guild_id_str })
+        .await?;
+    let follows: Vec<SummonerFollowedData> = database
+        .collection::<SummonerFollowedData>("follower_summoner")
+        .find(doc! { "guild_id": guild_id_str })
+        .await?
+        .try_collect()
+        .await?;
+    let blacklist: Vec<BlacklistedSummonerData> = database
+        .collection::<BlacklistedSummonerData>("blacklisted_summoners")
+        .find(doc! { "guild_id": guild_id_str })
+        .await?
+        .try_collect()
+        .await?;
+    let puuids: Vec<&str> = follows.iter().map(|follow| follow.puuid.as_str()).collect();
+    let mut linked_accounts: Vec<LinkedAccountData> = Vec::new();
+    let mut cursor = database
+        .collection::<LinkedAccountData>("linked_accounts")
+        .find(doc! { "puuid": { "$in": puuids } })
+        .await?;
+    while let Some(linked_account) = cursor.try_next().await? {
+        if is_guild_member(
+            &cache_http,
+            guild_id,
+            UserId::new(linked_account.discord_id),
+        )
+        .await
+        {
+            linked_accounts.push(linked_account);
+        }
+    }
+
+    Ok(GuildBackupData {
+        version: BACKUP_VERSION,
+        guild_id: guild_id_str.to_string(),
+        exported_at: Utc::now().timestamp(),
+        flags,
+        settings,
+        follows,
+        blacklist,
+        linked_accounts,
+    })
+}
+
+/// ⚙️ **Struct**: What `import_guild_data` restored.
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    /// Follows restored.
+    pub follows: usize,
+    /// Server blacklist entries restored.
+    pub blacklist: usize,
+    /// Follows of the backup left out: expired, opted out, blacklisted or beyond `MAX_FOLLOWS_PER_GUILD`.
+    pub skipped_follows: usize,
+}
+
+/// ⚙️ **Function**: Restores a guild backup in the database.
+///
+/// The settings, follows and server blacklist of the guild are replaced by the ones of the backup. Every entry is rewritten
+/// with `guild_id`, so a backup can only affect the guild it is imported in.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild to restore.
+/// - `backup`: The parsed backup file.
+/// - `trusted`: `true` when the import is made by a bot owner. Only then are the guild flags (kill switches) and the
+///   linked accounts restored, as an administrator could otherwise forge them.
+/// - `channel_ids`: The IDs of the channels of the guild.
+/// - `fallback_channel_id`: The channel used by the follows whose channel is not in `channel_ids`, e.g. the channel
+///   where the backup is imported.
+///
+/// # Returns:
+/// - `Result<ImportSummary, Error>`: The number of follows and blacklist entries restored, and of follows left out.
+///
+/// # Example:
+/// ```rust
+/// let summary = import_guild_data(&mongo_client, "123456789", backup, false, &channel_ids, channel_id).await?;
+/// ```
+///
+/// # Notes:
+/// - A backup file can be edited, so the follows are checked like new ones: follows of summoners who opted out or are
+///   blacklisted (globally, in the guild or in the backup) are skipped, expired follows are dropped, the other ends
+///   are capped to `MAX_FOLLOW_HOURS` from now (permanent follows are kept), and at most `MAX_FOLLOWS_PER_GUILD` follows
+///   are restored.
+/// - The follows and the blacklist are replaced in a transaction, so a failure leaves the guild as it was.
+/// - Existing linked accounts are never overwritten.
+pub async fn import_guild_data(
+    mongo_client: &Client,
+    guild_id: &str,
+    backup: GuildBackupData,
+    trusted: bool,
+    channel_ids: &HashSet<u64>,
+    fallback_channel_id: u64,
+) -> Result<ImportSummary, Error> {
+    if backup.version > BACKUP_VERSION {
+        return Err(format!(
+            "This backup was made by a newer version of Stat Summoner (format {}).",
+            backup.version
+        )
+        .into());
+    }
+    let database = mongo_client.database("stat-summoner");
+    let mut summary = ImportSummary::default();
+
+    let blacklist: Vec<BlacklistedSummonerData> = backup
+        .blacklist
+        .into_iter()
+        .filter(|entry| entry.guild_id.is_some())
+        .map(|mut entry| {
+            entry.guild_id = Some(guild_id.to_string());
+            entry
+        })
+        .collect();
+    let collection_blacklist =
+        database.collection::<BlacklistedSummonerData>("blacklisted_summoners");
+
+    let now = Utc::now().timestamp();
+    let max_follows = config().max_follows_per_guild as usize;
+    let mut follows = Vec::new();
+    for mut follow in backup.follows {
+        let end = follow.time_end_follow.parse::<i64>().unwrap_or(0);
+        let blacklisted = blacklist.iter().any(|entry| entry.puuid == follow.puuid)
+            || find_blacklist_entry(&collection_blacklist, &follow.puuid, guild_id)
+                .await?
+                .is_some();
+        if end <= now
+            || follows.len() >= max_follows
+            || blacklisted
+            || is_opted_out(mongo_client, &follow.puuid).await?
+        {
+            summary.skipped_follows += 1;
+            continue;
+        }
+        follow.guild_id = guild_id.to_string();
+        follow.time_end_follow = clamp_follow_end(end).to_string();
+        if !channel_ids.contains(&follow.channel_id) {
+            follow.channel_id = fallback_channel_id;
+        }
+        follows.push(follow);
+    }
+
+    // Les suivis et la liste noire sont remplacés ensemble, ou pas du tout
+    let mut session = mongo_client.start_session().await?;
+    session.start_transaction().await?;
+    if let Err(e) =
+        replace_guild_entries(&mut session, mongo_client, guild_id, &follows, &blacklist).await
+    {
+        session.abort_transaction().await?;
+        return Err(e);
+    }
+    session.commit_transaction().await?;
+    summary.follows = follows.len();
+    summary.blacklist = blacklist.len();
+
+    if let Some(mut settings) = backup.settings {
+        settings.guild_id = guild_id.to_string();
+        database
+            .collection::<GuildSettingsData>("guild_settings")
+            .replace_one(doc! { "guild_id": guild_id }, settings)
+            .upsert(true)
+            .await?;
+    }
+
+    if trusted {
+        if let Some(mut flags) = backup.flags {
+            flags.guild_id = guild_id.to_string();
+            database
+                .collection::<GuildFlagsData>("guild_flags")
+                .replace_one(doc! { "guild_id": guild_id }, flags)
+                .upsert(true)
+                .await?;
+        }
+        let collection_linked = database.collection::<LinkedAccountData>("linked_accounts");
+        for linked_account in backup.linked_accounts {
+            let filter = doc! {
+                "discord_id": linked_account.discord_id as i64,
+                "puuid": &linked_account.puuid,
+            };
+            if collection_linked.find_one(filter).await?.is_none() {
+                collection_linked.insert_one(linked_account).await?;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// ⚙️ **Function**: Replaces the follows and the server blacklist of a guild, within the transaction of `session`.
+///
+/// # Parameters:
+/// - `session`: The MongoDB session, with a transaction started.
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `follows`: The new follows of the guild.
+/// - `blacklist`: The new server blacklist of the guild.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if a write fails. The caller then aborts the transaction.
+async fn replace_guild_entries(
+    session: &mut ClientSession,
+    mongo_client: &Client,
+    guild_id: &str,
+    follows: &[SummonerFollowedData],
+    blacklist: &[BlacklistedSummonerData],
+) -> Result<(), Error> {
+    let database = mongo_client.database("stat-summoner");
+    let collection_followed = database.collection::<SummonerFollowedData>("follower_summoner");
+    collection_followed
+        .delete_many(doc! { "guild_id": guild_id })
+        .session(&mut *session)
+        .await?;
+    if !follows.is_empty() {
+        collection_followed
+            .insert_many(follows)
+            .session(&mut *session)
+            .await?;
+    }
+    let collection_blacklist =
+        database.collection::<BlacklistedSummonerData>("blacklisted_summoners");
+    collection_blacklist
+        .delete_many(doc! { "guild_id": guild_id })
+        .session(&mut *session)
+        .await?;
+    if !blacklist.is_empty() {
+        collection_blacklist
+            .insert_many(blacklist)
+            .session(&mut *session)
+            .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,203 @@
+use crate::config::deletion_notice;
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, create_embed_sucess, edit_and_schedule_deletion,
+    schedule_message_deletion, send_fetching_message,
+};
+use crate::models::data::{BlacklistedSummonerData, Data};
+use crate::models::error::Error;
+use crate::models::riot_id::RiotId;
+use crate::module::blacklist::utils::{
+    add_blacklist_entry, list_blacklist_entries, remove_blacklist_entry,
+};
+use crate::module::settings::utils::get_guild_theme;
+use crate::riot_api::get_puuid;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use poise::CreateReply;
+
+/// Manages the summoners that cannot be followed in this server.
+///
+/// This slash command groups the `add`, `remove` and `list` subcommands. It is restricted to administrators.
+/// Bot owners can additionally use the `global` option to edit the blacklist shared by every server.
+///
+/// # Example:
+/// ```rust
+/// /blacklist add riot_id: Faker#KR1
+/// /blacklist remove riot_id: Faker#KR1 global: true
+/// /blacklist list
+/// ```
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("add", "remove", "list"),
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn blacklist(_ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Adds a summoner to the blacklist so `/followgames` and `/followuser` refuse to follow them.
+///
+/// # Parameters:
+/// - `riot_id`: The Riot ID of the summoner.
+/// - `global`: If `true`, the summoner is blacklisted in every server. Only bot owners can use this option.
+#[poise::command(slash_command, guild_only)]
+pub async fn add(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Riot ID of the summoner (e.g., Faker#KR1)"] riot_id: RiotId,
+    #[description = "Blacklist in every server (bot owners only)"] global: Option<bool>,
+) -> Result<(), Error> {
+    let Some(guild_id) = resolve_scope(ctx, global.unwrap_or(false)).await? else {
+        return Ok(());
+    };
+    let fetching = send_fetching_message(ctx).await?;
+    let puuid = match resolve_puuid(ctx, &riot_id).await {
+        Ok(puuid) => puuid,
+        Err(e) => {
+            edit_and_schedule_deletion(fetching, ctx, create_embed_coded_error("", &e)).await?;
+            return Ok(());
+        }
+    };
+
+    let entry = BlacklistedSummonerData {
+        puuid,
+        name: riot_id.name.clone(),
+        tag: riot_id.tag.clone(),
+        guild_id,
+        added_by: ctx.author().id.get(),
+    };
+    let collection = ctx
+        .data()
+        .mongo_client
+        .database("stat-summoner")
+        .collection::<BlacklistedSummonerData>("blacklisted_summoners");
+    let reply = match add_blacklist_entry(&collection, entry).await {
+        Ok(true) => create_embed_sucess(&format!(
+            "**{}** has been blacklisted and can no longer be followed.",
+            riot_id
+        )),
+        Ok(false) => create_embed_error(&format!("**{}** is already blacklisted.", riot_id)),
+        Err(e) => create_embed_coded_error("Error inserting blacklist entry to MongoDB", &e.into()),
+    };
+    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+    Ok(())
+}
+
+/// Removes a summoner from the blacklist.
+///
+/// # Parameters:
+/// - `riot_id`: The Riot ID of the summoner.
+/// - `global`: If `true`, the summoner is removed from the global blacklist. Only bot owners can use this option.
+#[poise::command(slash_command, guild_only)]
+pub async fn remove(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Riot ID of the summoner (e.g., Faker#KR1)"] riot_id: RiotId,
+    #[description = "Remove from the global blacklist (bot owners only)"] global: Option<bool>,
+) -> Result<(), Error> {
+    let Some(guild_id) = resolve_scope(ctx, global.unwrap_or(false)).await? else {
+        return Ok(());
+    };
+    let fetching = send_fetching_message(ctx).await?;
+    let puuid = match resolve_puuid(ctx, &riot_id).await {
+        Ok(puuid) => puuid,
+        Err(e) => {
+            edit_and_schedule_deletion(fetching, ctx, create_embed_coded_error("", &e)).await?;
+            return Ok(());
+        }
+    };
+
+    let collection = ctx
+        .data()
+        .mongo_client
+        .database("stat-summoner")
+        .collection::<BlacklistedSummonerData>("blacklisted_summoners");
+    let reply = match remove_blacklist_entry(&collection, &puuid, guild_id).await {
+        Ok(true) => create_embed_sucess(&format!(
+            "**{}** has been removed from the blacklist.",
+            riot_id
+        )),
+        Ok(false) => create_embed_error(&format!("**{}** is not blacklisted.", riot_id)),
+        Err(e) => {
+            create_embed_coded_error("Error removing blacklist entry from MongoDB", &e.into())
+        }
+    };
+    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+    Ok(())
+}
+
+/// Lists the summoners blacklisted in this server, including the global entries.
+#[poise::command(slash_command, guild_only)]
+pub async fn list(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let collection = ctx
+        .data()
+        .mongo_client
+        .database("stat-summoner")
+        .collection::<BlacklistedSummonerData>("blacklisted_summoners");
+    let entries = list_blacklist_entries(&collection, &guild_id).await?;
+
+    let description = if entries.is_empty() {
+        "No summoners are blacklisted.".to_string()
+    } else {
+        entries
+            .iter()
+            .map(|entry| {
+                let scope = if entry.guild_id.is_none() {
+                    " (global)"
+                } else {
+                    ""
+                };
+                format!("• **{}#{}**{}", entry.name, entry.tag, scope)
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+    let theme = get_guild_theme(ctx).await;
+    let embed = CreateEmbed::new()
+        .title("Blacklisted Summoners")
+        .description(description)
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+    let reply = ctx
+        .send(CreateReply {
+            embeds: vec![embed],
+            ..Default::default()
+        })
+        .await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Determines which blacklist a subcommand edits.
+///
+/// # Returns:
+/// - `Result<Option<Option<String>>, Error>`: `Some(Some(guild_id))` for the guild blacklist, `Some(None)` for the global one,
+///   or `None` if the author is not allowed to edit the global blacklist (an error message has already been sent).
+async fn resolve_scope(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    global: bool,
+) -> Result<Option<Option<String>>, Error> {
+    if !global {
+        let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+        return Ok(Some(Some(guild_id)));
+    }
+    if ctx.framework().options().owners.contains(&ctx.author().id) {
+        return Ok(Some(None));
+    }
+    let error_message = "Only the bot owners can edit the global blacklist.";
+    let reply = ctx.send(create_embed_error(&error_message)).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(None)
+}
+
+/// ⚙️ **Function**: Resolves a Riot ID to a PUUID.
+async fn resolve_puuid(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    riot_id: &RiotId,
+) -> Result<String, Error> {
+    let client = reqwest::Client::new();
+    get_puuid(&client, riot_id, &ctx.data().riot_api_key).await
+}
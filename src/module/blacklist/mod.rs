@@ -0,0 +1,28 @@
+/// 🛠 **Module blacklist**: Contains the commands used to manage the summoners that cannot be followed.
+///
+/// This module lets guild administrators maintain a blocklist of League of Legends accounts that `/followgames`
+/// and `/followuser` refuse to follow, to handle harassment or privacy complaints. Bot owners can also add
+/// entries to a global blocklist that applies to every guild.
+///
+/// # Files in this module:
+/// - `blacklist.rs`: The `/blacklist` command and its `add`, `remove` and `list` subcommands.
+/// - `utils.rs`: Utility functions to query and update the `blacklisted_summoners` collection.
+///
+/// # Example:
+/// To use the command in this module, ensure it is registered in the bot's main framework setup:
+///
+/// ```rust
+/// use module::blacklist::blacklist::blacklist;
+///
+/// #[shuttle_runtime::main]
+/// async fn main() {
+///     let framework = poise::Framework::builder()
+///         .options(poise::FrameworkOptions {
+///             commands: vec![blacklist()], // Register the blacklist command
+///             ..Default::default()
+///         })
+///         .build();
+/// }
+/// ```
+pub mod blacklist;
+pub mod utils;
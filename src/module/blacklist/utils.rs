@@ -0,0 +1,99 @@
+use crate::models::data::BlacklistedSummonerData;
+use futures::TryStreamExt;
+use mongodb::bson::{doc, Bson};
+use mongodb::Collection;
+
+/// ⚙️ **Function**: Checks whether a summoner is blacklisted in a guild or globally.
+///
+/// This asynchronous function looks for an entry matching the summoner's PUUID, either scoped to the given guild
+/// or global (entries without a `guild_id`).
+///
+/// # Parameters:
+/// - `collection`: The MongoDB collection containing the blacklisted summoners.
+/// - `puuid`: The PUUID of the summoner to check.
+/// - `guild_id`: The ID of the Discord guild in which the follow is requested.
+///
+/// # Returns:
+/// - `Result<Option<BlacklistedSummonerData>, mongodb::error::Error>`: The matching blacklist entry if the summoner is blacklisted, `None` otherwise.
+///
+/// # Example:
+/// ```rust
+/// if let Some(entry) = find_blacklist_entry(&collection, &puuid, &guild_id).await? {
+///     // Refuse the follow
+/// }
+/// ```
+///
+/// # Notes:
+/// - A global entry is returned with `guild_id` set to `None`, which lets the caller tailor the error message.
+pub async fn find_blacklist_entry(
+    collection: &Collection<BlacklistedSummonerData>,
+    puuid: &str,
+    guild_id: &str,
+) -> Result<Option<BlacklistedSummonerData>, mongodb::error::Error> {
+    collection
+        .find_one(doc! {
+            "puuid": puuid,
+            "$or": [ { "guild_id": guild_id }, { "guild_id": Bson::Null } ]
+        })
+        .await
+}
+
+/// ⚙️ **Function**: Adds a summoner to the blacklist of a guild, or to the global blacklist.
+///
+/// # Parameters:
+/// - `collection`: The MongoDB collection containing the blacklisted summoners.
+/// - `entry`: The `BlacklistedSummonerData` to store. A `guild_id` of `None` makes the entry global.
+///
+/// # Returns:
+/// - `Result<bool, mongodb::error::Error>`: `true` if the entry was added, `false` if the summoner was already blacklisted in this scope.
+pub async fn add_blacklist_entry(
+    collection: &Collection<BlacklistedSummonerData>,
+    entry: BlacklistedSummonerData,
+) -> Result<bool, mongodb::error::Error> {
+    let filter = doc! { "puuid": &entry.puuid, "guild_id": entry.guild_id.clone() };
+    if collection.find_one(filter).await?.is_some() {
+        return Ok(false);
+    }
+    collection.insert_one(entry).await?;
+    Ok(true)
+}
+
+/// ⚙️ **Function**: Removes a summoner from the blacklist of a guild, or from the global blacklist.
+///
+/// # Parameters:
+/// - `collection`: The MongoDB collection containing the blacklisted summoners.
+/// - `puuid`: The PUUID of the summoner to remove.
+/// - `guild_id`: The guild whose blacklist is edited, or `None` for the global blacklist.
+///
+/// # Returns:
+/// - `Result<bool, mongodb::error::Error>`: `true` if an entry was removed, `false` if the summoner was not blacklisted in this scope.
+pub async fn remove_blacklist_entry(
+    collection: &Collection<BlacklistedSummonerData>,
+    puuid: &str,
+    guild_id: Option<String>,
+) -> Result<bool, mongodb::error::Error> {
+    let result = collection
+        .delete_one(doc! { "puuid": puuid, "guild_id": guild_id })
+        .await?;
+    Ok(result.deleted_count > 0)
+}
+
+/// ⚙️ **Function**: Lists the blacklist entries that apply to a guild.
+///
+/// # Parameters:
+/// - `collection`: The MongoDB collection containing the blacklisted summoners.
+/// - `guild_id`: The ID of the Discord guild.
+///
+/// # Returns:
+/// - `Result<Vec<BlacklistedSummonerData>, mongodb::error::Error>`: The guild's entries followed by the global ones.
+pub async fn list_blacklist_entries(
+    collection: &Collection<BlacklistedSummonerData>,
+    guild_id: &str,
+) -> Result<Vec<BlacklistedSummonerData>, mongodb::error::Error> {
+    let cursor = collection
+        .find(doc! { "$or": [ { "guild_id": guild_id }, { "guild_id": Bson::Null } ] })
+        .await?;
+    let mut entries: Vec<BlacklistedSummonerData> = cursor.try_collect().await?;
+    entries.sort_by_key(|entry| entry.guild_id.is_none());
+    Ok(entries)
+}
@@ -0,0 +1,29 @@
+use crate::embed::schedule_message_deletion;
+use crate::models::data::Data;
+use crate::models::error::Error;
+use crate::module::botstatus::utils::create_embed_health;
+use crate::module::settings::utils::get_guild_theme;
+use poise::CreateReply;
+
+/// Displays the health of the services Stat Summoner depends on.
+///
+/// The status comes from the last health check (run every few minutes): Riot API platform status, MongoDB and Data Dragon,
+/// each with its latency or the error met.
+///
+/// # Example:
+/// ```rust
+/// /botstatus
+/// ```
+#[poise::command(slash_command)]
+pub async fn botstatus(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let health_status = ctx.data().health_status.read().await.clone();
+    let theme = get_guild_theme(ctx).await;
+    let reply = ctx
+        .send(CreateReply {
+            embeds: vec![create_embed_health(&health_status, &theme)],
+            ..Default::default()
+        })
+        .await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
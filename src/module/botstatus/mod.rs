@@ -0,0 +1,26 @@
+/// 🛠 **Module botstatus**: Contains the `/botstatus` command and the dependency health-check cycle.
+///
+/// This module periodically probes the services the bot depends on (the Riot API status endpoint, MongoDB and
+/// Data Dragon). The latest results are displayed by `/botstatus`, and a status embed is posted in the ops channel
+/// whenever a dependency degrades or recovers.
+///
+/// # Files in this module:
+/// - `botstatus.rs`: The `/botstatus` command displaying the latest health check.
+/// - `utils.rs`: The probes, the health-check cycle and the status embed.
+///
+/// # Example:
+/// The command must be registered in the bot's main framework setup, and the cycle spawned at startup:
+///
+/// ```rust
+/// use module::botstatus::botstatus::botstatus;
+/// use module::botstatus::utils::run_health_check;
+///
+/// let framework = poise::Framework::builder()
+///     .options(poise::FrameworkOptions {
+///         commands: vec![botstatus()],
+///         ..Default::default()
+///     })
+///     .build();
+/// ```
+pub mod botstatus;
+pub mod utils;
@@ -0,0 +1,258 @@
+use crate::config::deletion_notice;
+use crate::models::champion_index::ChampionIndex;
+use crate::models::data::{DependencyHealth, EmbedTheme, HealthStatus};
+use crate::models::error::Error;
+use crate::module::loop_module::loop_module::reload_champion_index;
+use crate::riot_api::{fetch_dd_version, get_platform_status};
+use crate::sandbox::send_message;
+use chrono::Utc;
+use mongodb::bson::doc;
+use mongodb::Client;
+use poise::serenity_prelude::{self as serenity, CreateEmbed, CreateEmbedFooter, CreateMessage};
+use serenity::http::Http;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::RwLock;
+
+/// ⚙️ **Function**: Probes every dependency of the bot once.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`, pinged with the `ping` command.
+/// - `riot_api_key`: The Riot API key used to query the `lol-status-v4` endpoint of EUW.
+/// - `champions`: The champion lookups of the bot, loaded from Data Dragon if they are still empty.
+///
+/// # Returns:
+/// - `Vec<DependencyHealth>`: The status of the Riot API, MongoDB and Data Dragon, in that order.
+///
+/// # Example:
+/// ```rust
+/// let dependencies = check_dependencies(&mongo_client, &riot_api_key, &champions).await;
+/// ```
+///
+/// # Notes:
+/// - The Riot API is considered degraded when the status endpoint fails or reports an ongoing incident.
+/// - Data Dragon is considered degraded while the champion lookups are empty, e.g. when it could not be reached at
+///   startup: the commands showing champions then work without their names.
+pub async fn check_dependencies(
+    mongo_client: &Client,
+    riot_api_key: &str,
+    champions: &RwLock<ChampionIndex>,
+) -> Vec<DependencyHealth> {
+    let client = reqwest::Client::new();
+
+    let start = Instant::now();
+    let riot = match get_platform_status(&client, "euw1", riot_api_key).await {
+        Ok(status) => {
+            let incidents = status["incidents"]
+                .as_array()
+                .map_or(0, |incidents| incidents.len());
+            DependencyHealth {
+                name: "Riot API".to_string(),
+                healthy: incidents == 0,
+                detail: if incidents == 0 {
+                    "Operational".to_string()
+                } else {
+                    format!("{} ongoing incident(s)", incidents)
+                },
+                latency_ms: start.elapsed().as_millis(),
+            }
+        }
+        Err(e) => unhealthy("Riot API", e, start),
+    };
+
+    let start = Instant::now();
+    let mongodb = match mongo_client
+        .database("stat-summoner")
+        .run_command(doc! { "ping": 1 })
+        .await
+    {
+        Ok(_) => healthy("MongoDB", "Operational", start),
+        Err(e) => unhealthy("MongoDB", e.into(), start),
+    };
+
+    let start = Instant::now();
+    let data_dragon = match fetch_dd_version(&client).await {
+        Ok(version) if champions.read().await.is_empty() => {
+            // Le démarrage s'est fait sans Data Dragon : on recharge les champions dès qu'il répond
+            match reload_champion_index(champions).await {
+                Ok(_) => {
+                    log::info!("Champion lookups loaded after a degraded startup.");
+                    healthy("Data Dragon", &format!("Version {}", version), start)
+                }
+                Err(e) => unhealthy(
+                    "Data Dragon",
+                    format!("Champion data not loaded: {}", e).into(),
+                    start,
+                ),
+            }
+        }
+        Ok(version) => healthy("Data Dragon", &format!("Version {}", version), start),
+        Err(e) if champions.read().await.is_empty() => unhealthy(
+            "Data Dragon",
+            format!("Champion data not loaded: {}", e).into(),
+            start,
+        ),
+        Err(e) => unhealthy("Data Dragon", e, start),
+    };
+
+    vec![riot, mongodb, data_dragon]
+}
+
+fn healthy(name: &str, detail: &str, start: Instant) -> DependencyHealth {
+    DependencyHealth {
+        name: name.to_string(),
+        healthy: true,
+        detail: detail.to_string(),
+        latency_ms: start.elapsed().as_millis(),
+    }
+}
+
+fn unhealthy(name: &str, error: Error, start: Instant) -> DependencyHealth {
+    DependencyHealth {
+        name: name.to_string(),
+        healthy: false,
+        detail: error.to_string(),
+        latency_ms: start.elapsed().as_millis(),
+    }
+}
+
+/// ⚙️ **Function**: Runs a health check, stores its result and reports the dependencies whose status changed.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `riot_api_key`: The Riot API key.
+/// - `health_status`: The shared status read by `/botstatus`.
+/// - `http`: The Discord HTTP client, used to post in the ops channel.
+/// - `ops_channel_id`: The ops channel, if the `OPS_CHANNEL_ID` secret is set.
+/// - `champions`: The champion lookups of the bot (see `check_dependencies`).
+///
+/// # Returns:
+/// - `Result<(), Error>`: An empty result, or an error if the status embed cannot be posted.
+///
+/// # Example:
+/// ```rust
+/// run_health_check(&mongo_client, &riot_api_key, health_status.clone(), http.clone(), ops_channel_id, &champions).await?;
+/// ```
+///
+/// # Notes:
+/// - Nothing is posted on the first check after startup unless a dependency is already degraded.
+pub async fn run_health_check(
+    mongo_client: &Client,
+    riot_api_key: &str,
+    health_status: Arc<RwLock<HealthStatus>>,
+    http: Arc<Http>,
+    ops_channel_id: Option<u64>,
+    champions: &RwLock<ChampionIndex>,
+) -> Result<(), Error> {
+    let dependencies = check_dependencies(mongo_client, riot_api_key, champions).await;
+    let new_status = HealthStatus {
+        dependencies,
+        checked_at: Utc::now().timestamp(),
+    };
+
+    let changed: Vec<&DependencyHealth> = {
+        let previous = health_status.read().await;
+        new_status
+            .dependencies
+            .iter()
+            .filter(|dependency| {
+                match previous
+                    .dependencies
+                    .iter()
+                    .find(|previous| previous.name == dependency.name)
+                {
+                    Some(previous) => previous.healthy != dependency.healthy,
+                    None => !dependency.healthy,
+                }
+            })
+            .collect()
+    };
+    for dependency in &changed {
+        if dependency.healthy {
+            log::info!("{} recovered: {}", dependency.name, dependency.detail);
+        } else {
+            log::warn!("{} degraded: {}", dependency.name, dependency.detail);
+        }
+    }
+
+    if let (false, Some(channel_id)) = (changed.is_empty(), ops_channel_id) {
+        send_message(
+            &http,
+            serenity::ChannelId::new(channel_id),
+            // Le salon de supervision n'appartient à aucun serveur suivi : thème par défaut
+            CreateMessage::new().embed(create_embed_health(&new_status, &EmbedTheme::default())),
+        )
+        .await?;
+    }
+
+    *health_status.write().await = new_status;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Creates the embed displaying the health of every dependency.
+///
+/// # Parameters:
+/// - `health_status`: The result of the last health check.
+/// - `theme`: The guild's embed theme, providing the colors and the brand line of the footer.
+///
+/// # Returns:
+/// - `CreateEmbed`: An embed with the victory color of the theme if every dependency is healthy, the defeat color
+///   otherwise.
+///
+/// # Example:
+/// ```rust
+/// let embed = create_embed_health(&health_status, &theme);
+/// ```
+///
+/// The embed will look like this:
+/// ```text
+/// 🩺 Bot status
+/// 🟢 Riot API — Operational (120 ms)
+/// 🔴 MongoDB — connection refused (5000 ms)
+/// 🟢 Data Dragon — Version 14.20.1 (80 ms)
+/// ```
+pub fn create_embed_health(health_status: &HealthStatus, theme: &EmbedTheme) -> CreateEmbed {
+    if health_status.dependencies.is_empty() {
+        return CreateEmbed::new()
+            .title("🩺 Bot status")
+            .description("No health check has been run yet. Please try again in a few minutes.")
+            .color(theme.info())
+            .footer(CreateEmbedFooter::new(
+                theme.footer_text(&deletion_notice()),
+            ))
+            .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+    }
+    let all_healthy = health_status
+        .dependencies
+        .iter()
+        .all(|dependency| dependency.healthy);
+    let description = health_status
+        .dependencies
+        .iter()
+        .map(|dependency| {
+            format!(
+                "{} **{}** — {} ({} ms)",
+                if dependency.healthy { "🟢" } else { "🔴" },
+                dependency.name,
+                dependency.detail,
+                dependency.latency_ms
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    CreateEmbed::new()
+        .title("🩺 Bot status")
+        .description(format!(
+            "{}\n\nLast check: <t:{}:R>",
+            description, health_status.checked_at
+        ))
+        .color(if all_healthy {
+            theme.victory()
+        } else {
+            theme.defeat()
+        })
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
@@ -0,0 +1,34 @@
+use crate::config::deletion_notice;
+use crate::embed::schedule_message_deletion;
+use crate::models::data::Data;
+use crate::models::error::Error;
+use crate::module::challenge::utils::{create_embed_challenge, generate_challenge};
+use crate::module::settings::utils::get_guild_theme;
+use poise::serenity_prelude::CreateEmbedFooter;
+use poise::CreateReply;
+
+/// Generates a random gameplay challenge to spice up your next games.
+///
+/// # Example:
+/// ```rust
+/// /challenge
+/// ```
+///
+/// # Notes:
+/// - Administrators can have a challenge of the week posted automatically with `/settings challenge`.
+#[poise::command(slash_command)]
+pub async fn challenge(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let challenge = generate_challenge(&*ctx.data().champions.read().await);
+    let theme = get_guild_theme(ctx).await;
+    let embed = create_embed_challenge("🎯 Your challenge", &challenge, &theme).footer(
+        CreateEmbedFooter::new(theme.footer_text(&deletion_notice())),
+    );
+    let reply = ctx
+        .send(CreateReply {
+            embeds: vec![embed],
+            ..Default::default()
+        })
+        .await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
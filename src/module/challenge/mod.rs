@@ -0,0 +1,18 @@
+/// 🛠 **Module challenge**: Contains the gameplay challenge generator.
+///
+/// This module builds random gameplay challenges (e.g. "Win a game with only AP items on an ADC") from a list of
+/// templates filled with random champions and roles. Each server can also have a challenge of the week posted in a
+/// channel of its choice by the scheduler.
+///
+/// # Files in this module:
+/// - `challenge.rs`: The `/challenge` command.
+/// - `utils.rs`: The challenge templates and generator, and the weekly job posting the featured challenges.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`, and the weekly job is started in `main.rs`:
+///
+/// ```rust
+/// post_weekly_challenges(&mongo_client, &http, &champions).await?;
+/// ```
+pub mod challenge;
+pub mod utils;
@@ -0,0 +1,165 @@
+use crate::models::champion_index::ChampionIndex;
+use crate::models::data::{EmbedTheme, GuildSettingsData};
+use crate::models::error::Error;
+use crate::module::grind::utils::{create_embed_grind, grind_leaderboard};
+use crate::module::settings::utils::{guild_settings_collection, is_in_quiet_hours};
+use crate::sandbox::send_message;
+use crate::utils::{current_week, current_week_start};
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::Client;
+use poise::serenity_prelude::{ChannelId, CreateEmbed, CreateMessage, GuildId, Http};
+use rand::seq::SliceRandom;
+
+/// Templates of the challenges. `{champion}` and `{role}` are replaced by a random champion and role.
+const CHALLENGE_TEMPLATES: [&str; 14] = [
+    "Win a game with only AP items on an ADC.",
+    "No-dash champion only: win a game with a champion that has no dash or blink.",
+    "Win a game as {champion} in the {role} role.",
+    "Play {champion} {role} and finish the game with more assists than deaths.",
+    "Win a game without buying boots.",
+    "Finish a game with a KDA above 3 on {champion}.",
+    "Get first blood in the {role} role.",
+    "Win a game in the {role} role with a champion you have never played.",
+    "Reach 8 CS per minute in the {role} role.",
+    "Win a game as {champion} with the first item you would never build on them.",
+    "Place 20 wards or more in a single game in the {role} role.",
+    "Win a game with the whole team on champions starting with the same letter as {champion}.",
+    "Take the first tower of the game as {champion}.",
+    "Win a game in the {role} role without dying before 15 minutes.",
+];
+
+/// Roles used to fill the `{role}` placeholder.
+const CHALLENGE_ROLES: [&str; 5] = ["Top", "Jungle", "Mid", "ADC", "Support"];
+
+/// ⚙️ **Function**: Generates a random gameplay challenge.
+///
+/// # Parameters:
+/// - `champions`: The champion lookups, used to pick the random champions.
+///
+/// # Returns:
+/// - `String`: A challenge such as "Win a game as Ahri in the Top role.".
+///
+/// # Example:
+/// ```rust
+/// let challenge = generate_challenge(&*ctx.data().champions.read().await);
+/// ```
+pub fn generate_challenge(champions: &ChampionIndex) -> String {
+    let mut rng = rand::thread_rng();
+    let champions = champions.names();
+    let template = CHALLENGE_TEMPLATES.choose(&mut rng).unwrap_or(&"");
+    let champion = champions
+        .choose(&mut rng)
+        .map_or("a champion you like", |champion| champion.as_str());
+    let role = CHALLENGE_ROLES.choose(&mut rng).unwrap_or(&"Mid");
+    template
+        .replace("{champion}", champion)
+        .replace("{role}", role)
+}
+
+/// ⚙️ **Function**: Creates the embed displaying a challenge.
+///
+/// # Parameters:
+/// - `title`: The title of the embed.
+/// - `challenge`: The challenge, as generated by `generate_challenge`.
+/// - `theme`: The embed theme of the guild.
+///
+/// # Returns:
+/// - `CreateEmbed`: The embed, without footer.
+pub fn create_embed_challenge(title: &str, challenge: &str, theme: &EmbedTheme) -> CreateEmbed {
+    CreateEmbed::default()
+        .title(title)
+        .description(format!("**{}**", challenge))
+        .color(theme.info())
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
+
+/// ⚙️ **Function**: Posts the challenge of the week in the guilds which configured a challenge channel.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `http`: The Discord HTTP client used to post the challenges.
+/// - `champions`: The champion lookups, used to pick the random champions.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if the guild settings cannot be read.
+///
+/// # Example:
+/// ```rust
+/// post_weekly_challenges(&mongo_client, &http, &*champions.read().await).await?;
+/// ```
+///
+/// # Notes:
+/// - The week of the last challenge posted is stored in the guild settings, so the job can run often and each guild
+///   still gets a single challenge per week.
+/// - The grind leaderboard of last week (see `grind_leaderboard`) is posted with the challenge when a member played.
+/// - A guild in its quiet hours (see `is_in_quiet_hours`) or whose channel cannot be reached is skipped and tried again on the next run.
+pub async fn post_weekly_challenges(
+    mongo_client: &Client,
+    http: &Http,
+    champions: &ChampionIndex,
+) -> Result<(), Error> {
+    let week = current_week();
+    let collection = guild_settings_collection(mongo_client);
+    let guilds: Vec<GuildSettingsData> = collection
+        .find(doc! {
+            "challenge_channel_id": { "$ne": null },
+            "challenge_week": { "$ne": &week },
+        })
+        .await?
+        .try_collect()
+        .await?;
+    for guild in guilds {
+        let Some(channel_id) = guild.challenge_channel_id else {
+            continue;
+        };
+        if is_in_quiet_hours(&guild) {
+            continue;
+        }
+        let challenge = generate_challenge(champions);
+        let embed = create_embed_challenge("🏆 Challenge of the week", &challenge, &guild.theme);
+        let mut message = CreateMessage::new().embed(embed);
+        // Classement des joueurs de la semaine précédente
+        let week_start = current_week_start();
+        if let Ok(guild_id) = guild.guild_id.parse::<u64>() {
+            match grind_leaderboard(
+                mongo_client,
+                http,
+                GuildId::new(guild_id),
+                week_start - 7 * 24 * 60 * 60,
+                week_start,
+            )
+            .await
+            {
+                Ok(leaderboard) if !leaderboard.is_empty() => {
+                    message = message.embed(create_embed_grind(
+                        "⏱️ Grind leaderboard of last week",
+                        &leaderboard,
+                        &guild.theme,
+                    ));
+                }
+                Ok(_) => {}
+                Err(e) => log::error!(
+                    "Error building the grind leaderboard of guild {}: {:?}",
+                    guild.guild_id,
+                    e
+                ),
+            }
+        }
+        if let Err(e) = send_message(http, ChannelId::new(channel_id), message).await {
+            log::error!(
+                "Error posting the challenge of the week in guild {}: {:?}",
+                guild.guild_id,
+                e
+            );
+            continue;
+        }
+        collection
+            .update_one(
+                doc! { "guild_id": &guild.guild_id },
+                doc! { "$set": { "challenge_week": &week } },
+            )
+            .await?;
+    }
+    Ok(())
+}
@@ -1,12 +1,44 @@
-use crate::embed::{create_embed_error, schedule_message_deletion};
-use crate::models::data::{ChampionData, Data, EmojiId};
+use crate::components::{chosen_suggestion, save_views, LazyView};
+use crate::config::config;
+use crate::embed::{
+    create_embed_error, schedule_message_deletion, send_embed_with_lazy_views, send_suggestions,
+};
+use crate::models::bracket::RankBracket;
+use crate::models::data::{ChampionData, Data, EmbedTheme, EmojiId};
 use crate::models::error::Error;
 use crate::models::modal::ChampionsInfosModal;
-use crate::module::championsinfos::utils::create_embed_champions_info;
-use crate::utils::{get_champion_id, get_champion_names};
-use mongodb::bson::doc;
+use crate::module::championsinfos::probuilds::{ProBuildsView, PRO_BUILDS_VIEW_LOADER};
+use crate::module::championsinfos::utils::{
+    create_embed_champions_info, create_embed_situational_build, get_bracket_stats,
+    get_champion_data, get_champion_patches, rank_champion_matches,
+};
+use crate::module::settings::utils::{get_guild_champion_aliases, get_guild_theme};
+use crate::utils::resolve_champion_alias;
+use futures::future::BoxFuture;
+use poise::serenity_prelude as serenity;
 use poise::{CreateReply, Modal};
-use strsim::normalized_levenshtein;
+use serde::{Deserialize, Serialize};
+use serenity::builder::CreateEmbed;
+
+/// Similarity (normalized Levenshtein) under which the closest champion is suggested instead of being displayed.
+const MIN_CHAMPION_MATCH_SCORE: f64 = 0.6;
+
+/// Number of champions suggested when the input is not close enough to any of them.
+const CHAMPION_SUGGESTIONS: usize = 3;
+
+/// Kind of the component state of the champion suggestions, answered by `handle_champion_suggestion`.
+pub const CHAMPION_SUGGESTION_KIND: &str = "champion-suggestion";
+
+/// The views of a champion: the embeds built right away, then the ones built when first opened.
+type ChampionViews = (Vec<(String, CreateEmbed)>, Vec<LazyView>);
+
+/// The options of `/championsinfos`, saved with the champion suggestions to answer them later.
+#[derive(Serialize, Deserialize)]
+struct ChampionSuggestionContext {
+    patch: Option<String>,
+    bracket: String,
+    theme: EmbedTheme,
+}
 
 /// Fetches and displays detailed information about a League of Legends champion based on user input.
 ///
@@ -17,14 +49,22 @@ use strsim::normalized_levenshtein;
 ///
 /// # Parameters:
 /// - `ctx`: The application context, providing access to Discord interaction methods, data dragon JSON, and the MongoDB client.
+/// - `patch`: Optional patch (e.g., `14.19`) whose statistics should be displayed instead of the latest ones.
+/// - `bracket`: Optional rank bracket (Iron–Gold, Platinum+, Diamond+) restricting the winrate, banrate and popularity.
 ///
 /// # Returns:
 /// - `Result<(), Error>`: If successful, returns `Ok(())`; otherwise, returns an error.
 ///
 /// # ⚠️ Notes:
 /// - The command opens a modal dialog to collect the champion's name from the user.
-/// - It uses fuzzy matching to find the best match for the champion name if the input is not exact.
+/// - Nicknames (`mf`, `asol`, `kata`, and the ones added with `/settings alias`) are resolved before fuzzy matching,
+///   which is used to find the best match for the champion name if the input is not exact.
+/// - When no champion is close enough to the input, the 3 closest ones are suggested with buttons instead of guessing.
 /// - The message displaying the champion's information is automatically deleted after 60 seconds to keep the chat clean.
+/// - A "Situational" button expands the embed with boots options and situational items, keeping the default view compact.
+/// - A "Pro builds" button shows the champion's recent professional-player games (cached for 6 hours). They are only
+///   fetched when the button is pressed.
+/// - Statistics are stored per patch, so previous patches remain available through the `patch` option.
 ///
 /// # Example:
 /// ```rust
@@ -64,7 +104,14 @@ use strsim::normalized_levenshtein;
 /// # Related Commands:
 /// - `lolstats`: Fetches and displays LoL player stats based on user input.
 #[poise::command(slash_command)]
-pub async fn championsinfos(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+pub async fn championsinfos(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Patch to display (e.g., 14.19), latest by default"]
+    #[autocomplete = "autocomplete_patch"]
+    patch: Option<String>,
+    #[description = "Rank bracket, all ranks by default"] bracket: Option<RankBracket>,
+) -> Result<(), Error> {
+    let bracket = bracket.unwrap_or(RankBracket::All);
     let modal_data: ChampionsInfosModal = match ChampionsInfosModal::execute(ctx).await {
         Ok(Some(data)) => data,
         Ok(None) => {
@@ -82,53 +129,298 @@ pub async fn championsinfos(ctx: poise::ApplicationContext<'_, Data, Error>) ->
     };
 
     let input_name = modal_data.champion_name.trim().to_lowercase();
-    let dd_json = &*ctx.data().dd_json.read().await;
-    let champion_names = get_champion_names(dd_json);
-    if champion_names.is_empty() {
+    let guild_aliases = get_guild_champion_aliases(ctx).await;
+    let (alias_champion, matches) = {
+        let champions = ctx.data().champions.read().await;
+        (
+            resolve_champion_alias(&champions, &guild_aliases, &input_name),
+            rank_champion_matches(&champions.names(), &input_name),
+        )
+    };
+    if matches.is_empty() {
         let error_message = "Impossible de récupérer la liste des champions.";
         let reply = ctx.send(create_embed_error(&error_message)).await?;
         schedule_message_deletion(reply, ctx).await?;
         return Ok(());
     }
 
-    let matched_champion = champion_names
-        .iter()
-        .max_by(|a, b| {
-            let score_a = normalized_levenshtein(&input_name, &a.to_lowercase());
-            let score_b = normalized_levenshtein(&input_name, &b.to_lowercase());
-            score_a
-                .partial_cmp(&score_b)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        })
-        .unwrap();
-    let matched_champion_id = get_champion_id(dd_json, matched_champion).unwrap();
+    let patch = patch.map(|patch| patch.trim().to_string());
+    let theme = get_guild_theme(ctx).await;
+    // Les surnoms (mf, asol...) passent avant la recherche approchée, qui les confond souvent
+    let matched_champion = match alias_champion {
+        Some(champion) => champion,
+        None if matches[0].1 >= MIN_CHAMPION_MATCH_SCORE => matches[0].0.clone(),
+        None => {
+            // Un nom trop éloigné de tous les champions : on propose au lieu de deviner
+            let question = format!(
+                "No champion is close enough to `{}`. Did you mean:",
+                modal_data.champion_name.trim()
+            );
+            let suggestions: Vec<String> = matches
+                .into_iter()
+                .take(CHAMPION_SUGGESTIONS)
+                .map(|(name, _)| name)
+                .collect();
+            let context = ChampionSuggestionContext {
+                patch,
+                bracket: bracket.key().to_string(),
+                theme,
+            };
+            send_suggestions(
+                ctx,
+                &question,
+                &suggestions,
+                CHAMPION_SUGGESTION_KIND,
+                &context,
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+    let Some(matched_champion_id) = ctx
+        .data()
+        .champions
+        .read()
+        .await
+        .id_from_name(&matched_champion)
+        .map(str::to_string)
+    else {
+        return Ok(());
+    };
+
+    match build_champion_views(
+        ctx.data(),
+        &matched_champion_id,
+        &matched_champion,
+        patch.as_deref(),
+        bracket,
+        &theme,
+    )
+    .await?
+    {
+        Ok((views, lazy_views)) => {
+            if let Err(e) = send_embed_with_lazy_views(ctx, views, lazy_views).await {
+                log::error!("Failed to send champion information: {}", e);
+            }
+        }
+        Err(error_message) => {
+            let reply = ctx.send(create_embed_error(&error_message)).await?;
+            schedule_message_deletion(reply, ctx).await?;
+        }
+    }
+
+    Ok(())
+}
 
-    let mongo_client: &mongodb::Client = &ctx.data().mongo_client;
-    let filter = doc! { "id_name": matched_champion_id};
+/// ⚙️ **Function**: Builds the views of the statistics of a champion displayed by `/championsinfos`.
+///
+/// # Parameters:
+/// - `data`: The data of the bot, used to access the MongoDB client.
+/// - `champion_id`: The ID of the champion (e.g., `MissFortune`).
+/// - `champion_name`: The name of the champion, used in the error messages.
+/// - `patch`: The patch to display, the latest by default.
+/// - `bracket`: The rank bracket of the statistics.
+/// - `theme`: The embed theme of the guild.
+///
+/// # Returns:
+/// - `Result<Result<ChampionViews, String>, Error>`: The views, or the message explaining to the user why there are
+///   none.
+async fn build_champion_views(
+    data: &Data,
+    champion_id: &str,
+    champion_name: &str,
+    patch: Option<&str>,
+    bracket: RankBracket,
+    theme: &EmbedTheme,
+) -> Result<Result<ChampionViews, String>, Error> {
+    let mongo_client: &mongodb::Client = &data.mongo_client;
     let collection_champions = mongo_client
         .database("stat-summoner")
         .collection::<ChampionData>("champions_data");
     let collection_emoji = mongo_client
         .database("stat-summoner")
         .collection::<EmojiId>("emojis_id");
-    match collection_champions.find_one(filter).await {
-        Ok(Some(champion_data)) => {
-            let embed = create_embed_champions_info(champion_data, &collection_emoji).await?;
-            let reply = CreateReply {
-                embeds: vec![embed],
-                ..Default::default()
-            };
-            let sent_message = ctx.send(reply).await?;
-            if let Err(e) = schedule_message_deletion(sent_message, ctx).await {
-                log::error!("Failed to schedule message deletion: {}", e);
-            }
+    let champion_data = match get_champion_data(&collection_champions, champion_id, patch).await {
+        Ok(Some(champion_data)) => champion_data,
+        Ok(None) => {
+            return Ok(Err(match patch {
+                Some(patch) => {
+                    format!("No data recorded for {} on patch {}.", champion_name, patch)
+                }
+                None => format!("No data recorded for {} yet.", champion_name),
+            }));
         }
-        Ok(None) => return Ok(()),
         Err(e) => {
-            log::error!("Erreur lors de la recherche de l'emoji: {:?}", e);
-            return Ok(());
+            log::error!("Erreur lors de la recherche du champion: {:?}", e);
+            return Ok(Err(format!(
+                "Failed to retrieve the statistics of {}.",
+                champion_name
+            )));
         }
+    };
+    if get_bracket_stats(&champion_data, bracket).is_none() {
+        return Ok(Err(format!(
+            "No {} statistics recorded for {} yet.",
+            bracket.label(),
+            champion_name
+        )));
     }
+    let embed =
+        create_embed_champions_info(champion_data.clone(), bracket, theme, &collection_emoji)
+            .await?;
+    let situational =
+        create_embed_situational_build(embed.clone(), &champion_data, &collection_emoji).await?;
+    // Les builds pros ne sont récupérés qu'à l'ouverture de la page
+    let pro_builds = LazyView {
+        label: "Pro builds".to_string(),
+        loader: PRO_BUILDS_VIEW_LOADER,
+        payload: serde_json::to_string(&ProBuildsView {
+            champion_id: champion_data.id_name.clone(),
+            champion_name: champion_data.name.clone(),
+            theme: theme.clone(),
+        })?,
+    };
+    let views = vec![
+        ("Overview".to_string(), embed),
+        ("Situational".to_string(), situational),
+    ];
+    Ok(Ok((views, vec![pro_builds])))
+}
 
-    Ok(())
+/// ⚙️ **Function**: Displays the champion picked among the suggestions of `/championsinfos`.
+///
+/// # Parameters:
+/// - `ctx`: The serenity context.
+/// - `data`: The data of the bot.
+/// - `press`: The button of the suggestion picked.
+/// - `action`: The index of the suggestion picked.
+/// - `payload`: The `SuggestionsState` of the suggestions, whose context is a `ChampionSuggestionContext`.
+///
+/// # Returns:
+/// - `BoxFuture<'a, Result<(), Error>>`: An error if the state is invalid or the response cannot be sent.
+///
+/// # Notes:
+/// - Registered under `CHAMPION_SUGGESTION_KIND` (see `module::register_components`), so the suggestions can still
+///   be picked after a restart.
+/// - The suggestions are replaced by the name of the champion picked, and its statistics are sent in a follow-up
+///   message deleted after the deletion delay, like the ones of the command.
+pub fn handle_champion_suggestion<'a>(
+    ctx: &'a serenity::Context,
+    data: &'a Data,
+    press: &'a serenity::ComponentInteraction,
+    _key: &'a str,
+    action: &'a str,
+    payload: &'a str,
+) -> BoxFuture<'a, Result<(), Error>> {
+    Box::pin(async move {
+        let Some((champion_name, context)) = chosen_suggestion(action, payload)? else {
+            return Ok(());
+        };
+        let context: ChampionSuggestionContext = serde_json::from_str(&context)?;
+        let bracket = RankBracket::from_key(&context.bracket).unwrap_or(RankBracket::All);
+        let Some(champion_id) = data
+            .champions
+            .read()
+            .await
+            .id_from_name(&champion_name)
+            .map(str::to_string)
+        else {
+            return Ok(());
+        };
+        // Répondre tout de suite : construire les embeds peut dépasser les 3 secondes de Discord
+        press
+            .create_response(
+                ctx,
+                serenity::CreateInteractionResponse::UpdateMessage(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .embed(
+                            CreateEmbed::default()
+                                .description(format!("✅ {}", champion_name))
+                                .color(0xFFA500),
+                        )
+                        .components(Vec::new()),
+                ),
+            )
+            .await?;
+        let (views, lazy_views) = match build_champion_views(
+            data,
+            &champion_id,
+            &champion_name,
+            context.patch.as_deref(),
+            bracket,
+            &context.theme,
+        )
+        .await?
+        {
+            Ok(views) => views,
+            Err(error_message) => {
+                let error = create_embed_error(&error_message);
+                press
+                    .create_followup(
+                        ctx,
+                        error.to_slash_followup_response(
+                            serenity::CreateInteractionResponseFollowup::new(),
+                        ),
+                    )
+                    .await?;
+                return Ok(());
+            }
+        };
+        let components = save_views(
+            &data.mongo_client,
+            &format!("views-{}", press.id),
+            press.user.id.get(),
+            &views,
+            &lazy_views,
+            false,
+            false,
+            None,
+        )
+        .await?;
+        let reply = CreateReply::default()
+            .embed(views[0].1.clone())
+            .components(components);
+        let message = press
+            .create_followup(
+                ctx,
+                reply
+                    .to_slash_followup_response(serenity::CreateInteractionResponseFollowup::new()),
+            )
+            .await?;
+        let ctx = ctx.clone();
+        let token = press.token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(config().message_deletion_delay).await;
+            if let Err(e) = ctx.http.delete_followup_message(&token, message.id).await {
+                log::error!("Failed to delete the champion information: {}", e);
+            }
+        });
+        Ok(())
+    })
+}
+
+/// ⚙️ **Function**: Suggests the patches for which champion statistics are stored.
+///
+/// # Parameters:
+/// - `ctx`: The application context, used to access the MongoDB client.
+/// - `partial`: The text typed so far by the user.
+///
+/// # Returns:
+/// - `Vec<String>`: Up to 25 stored patches starting with `partial`, newest first.
+async fn autocomplete_patch(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    partial: &str,
+) -> Vec<String> {
+    let collection = ctx
+        .data()
+        .mongo_client
+        .database("stat-summoner")
+        .collection::<ChampionData>("champions_data");
+    get_champion_patches(&collection)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|patch| patch.starts_with(partial.trim()))
+        .take(25)
+        .collect()
 }
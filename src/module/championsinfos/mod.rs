@@ -6,13 +6,14 @@
 ///
 /// # Files in this module:
 /// - `championsinfos.rs`: The command for fetching and displaying champion information.
+/// - `probuilds.rs`: The `ProBuildsProvider` trait, its probuilds.net implementation and the cache of recent pro-player builds.
 /// - `utils.rs`: Utility functions used by the `championsinfos` command, such as functions for data retrieval, fuzzy matching, and processing.
 ///
 /// # Example:
 /// To use the commands in this module, ensure they are registered in the bot's main framework setup:
 ///
 /// ```rust
-/// use commands::championsinfos::championsinfos;
+/// use module::championsinfos::championsinfos::championsinfos;
 ///
 /// #[shuttle_runtime::main]
 /// async fn main() {
@@ -29,6 +30,7 @@
 /// It utilizes fuzzy matching to handle variations or misspellings in the champion's name input by the user.
 /// The command presents the information in a well-structured Discord embed, enhancing user experience.
 ///
-/// As more commands or utilities related to champion information are added, they will be included here and imported into the main bot setup.
+/// As more commands or utilities related to champion information are added, they will be included here and added to `module::all_commands`.
 pub mod championsinfos;
+pub mod probuilds;
 pub mod utils;
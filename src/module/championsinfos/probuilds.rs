@@ -0,0 +1,320 @@
+use crate::config::deletion_notice;
+use crate::models::data::{Data, EmbedTheme, EmojiId, ProBuildData};
+use crate::models::error::Error;
+use crate::utils::{get_emoji, time_since_game_ended};
+use chrono::Utc;
+use futures::future::BoxFuture;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use select::document::Document;
+use select::predicate::{Attr, Class, Name, Predicate};
+use serde::{Deserialize, Serialize};
+
+/// Number of seconds before the cached pro builds of a champion are fetched again.
+const PRO_BUILDS_CACHE_SECS: i64 = 6 * 3600;
+
+/// Maximum number of pro builds kept per champion.
+const MAX_PRO_BUILDS: usize = 5;
+
+/// Name of the loader of the "Pro builds" view of `/championsinfos` (see `load_pro_builds_view`).
+pub const PRO_BUILDS_VIEW_LOADER: &str = "probuilds";
+
+/// Context of the "Pro builds" view, saved with the views of the message until it is opened.
+#[derive(Serialize, Deserialize)]
+pub struct ProBuildsView {
+    /// The Data Dragon id of the champion (e.g., `MonkeyKing`).
+    pub champion_id: String,
+    /// The name of the champion, for the title.
+    pub champion_name: String,
+    /// The embed theme of the guild.
+    pub theme: EmbedTheme,
+}
+
+/// ⚙️ **Trait**: A source of recent professional-player games for a champion.
+///
+/// Implementations return the games most recent first. The cache layer (`get_pro_builds`) is shared, so adding
+/// another source only requires implementing this trait.
+pub trait ProBuildsProvider {
+    /// Name of the source, displayed in the embed footer.
+    fn name(&self) -> &'static str;
+
+    /// Fetches the recent pro games of a champion, identified by its Data Dragon id (e.g., `MonkeyKing`).
+    async fn fetch_pro_builds(&self, champion_id: &str) -> Result<Vec<ProBuildData>, Error>;
+}
+
+/// ⚙️ **Struct**: Scrapes recent pro games from probuilds.net.
+pub struct ProbuildsNet;
+
+impl ProBuildsProvider for ProbuildsNet {
+    fn name(&self) -> &'static str {
+        "Probuilds"
+    }
+
+    async fn fetch_pro_builds(&self, champion_id: &str) -> Result<Vec<ProBuildData>, Error> {
+        let url = format!(
+            "https://www.probuilds.net/champions/details/{}",
+            champion_id
+        );
+        let body = reqwest::Client::new()
+            .get(&url)
+            .header("User-Agent", "Mozilla/5.0")
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        Ok(parse_probuilds_page(&body, champion_id, self.name()))
+    }
+}
+
+/// ⚙️ **Function**: Extracts the pro games listed on a probuilds.net champion page.
+///
+/// # Parameters:
+/// - `body`: The HTML of the champion page.
+/// - `champion_id`: The Data Dragon id of the champion, stored in each build.
+/// - `source`: The name of the provider, stored in each build.
+///
+/// # Returns:
+/// - `Vec<ProBuildData>`: Up to `MAX_PRO_BUILDS` games. Rows missing a player name are skipped, so a layout change yields no builds rather than wrong ones.
+fn parse_probuilds_page(body: &str, champion_id: &str, source: &str) -> Vec<ProBuildData> {
+    let document = Document::from(body);
+    let fetched_at = Utc::now().timestamp();
+    let image_names = |node: &select::node::Node, class: &str| -> Vec<String> {
+        node.find(Class(class).descendant(Name("img")))
+            .filter_map(|img| img.attr("alt"))
+            .map(|alt| alt.chars().filter(|c| c.is_alphanumeric()).collect())
+            .filter(|name: &String| !name.is_empty())
+            .collect()
+    };
+
+    document
+        .find(Class("pro-player"))
+        .filter_map(|row| {
+            let pro_name = row
+                .find(Class("player-name"))
+                .next()?
+                .text()
+                .trim()
+                .to_string();
+            if pro_name.is_empty() {
+                return None;
+            }
+            let opponent = image_names(&row, "opponent")
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let played_at = row
+                .find(Attr("data-timestamp", ()))
+                .next()
+                .and_then(|node| node.attr("data-timestamp"))
+                .and_then(|timestamp| timestamp.parse::<i64>().ok())
+                .unwrap_or(fetched_at * 1000);
+            Some(ProBuildData {
+                champion_id: champion_id.to_string(),
+                pro_name,
+                opponent,
+                win: row.is(Class("win")) || row.find(Class("win")).next().is_some(),
+                items: image_names(&row, "items"),
+                runes: image_names(&row, "runes"),
+                played_at,
+                source: source.to_string(),
+                fetched_at,
+            })
+        })
+        .take(MAX_PRO_BUILDS)
+        .collect()
+}
+
+/// ⚙️ **Function**: Returns the recent pro builds of a champion, fetching them from the provider when the cache is stale.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client, used to read and refresh the `pro_builds` collection.
+/// - `provider`: The source of pro games.
+/// - `champion_id`: The Data Dragon id of the champion.
+///
+/// # Returns:
+/// - `Result<Vec<ProBuildData>, Error>`: The pro builds, most recent first. May be empty.
+///
+/// # Example:
+/// ```rust
+/// let pro_builds = get_pro_builds(&mongo_client, &ProbuildsNet, "Jhin").await?;
+/// ```
+///
+/// # Notes:
+/// - Builds are cached for 6 hours per champion.
+/// - If the provider fails, the stale cache is returned and the error is logged, so the rest of `/championsinfos` keeps working.
+pub async fn get_pro_builds(
+    mongo_client: &Client,
+    provider: &impl ProBuildsProvider,
+    champion_id: &str,
+) -> Result<Vec<ProBuildData>, Error> {
+    let collection: Collection<ProBuildData> = mongo_client
+        .database("stat-summoner")
+        .collection("pro_builds");
+    let cached: Vec<ProBuildData> = collection
+        .find(doc! { "champion_id": champion_id })
+        .sort(doc! { "played_at": -1 })
+        .await?
+        .try_collect()
+        .await?;
+
+    let now = Utc::now().timestamp();
+    let is_fresh = cached
+        .iter()
+        .any(|build| now - build.fetched_at < PRO_BUILDS_CACHE_SECS);
+    if is_fresh {
+        return Ok(cached);
+    }
+
+    match provider.fetch_pro_builds(champion_id).await {
+        Ok(pro_builds) if !pro_builds.is_empty() => {
+            collection
+                .delete_many(doc! { "champion_id": champion_id })
+                .await?;
+            collection.insert_many(&pro_builds).await?;
+            Ok(pro_builds)
+        }
+        Ok(_) => Ok(cached),
+        Err(e) => {
+            log::warn!(
+                "Failed to fetch pro builds of {} from {}: {}",
+                champion_id,
+                provider.name(),
+                e
+            );
+            Ok(cached)
+        }
+    }
+}
+
+/// ⚙️ **Function**: Creates the "Pro builds" page of a champion.
+///
+/// # Parameters:
+/// - `champion_name`: The name of the champion, used for the title.
+/// - `pro_builds`: The pro builds to display, most recent first.
+/// - `theme`: The guild's embed theme.
+/// - `collection_emoji`: A reference to a MongoDB `Collection<EmojiId>` used to retrieve the rune and item emojis.
+///
+/// # Returns:
+/// - `Result<CreateEmbed, Error>`: One field per pro game, with the matchup, the result, the runes and the items.
+pub async fn create_embed_pro_builds(
+    champion_name: &str,
+    pro_builds: &[ProBuildData],
+    theme: &EmbedTheme,
+    collection_emoji: &Collection<EmojiId>,
+) -> Result<CreateEmbed, Error> {
+    let mut embed = CreateEmbed::default()
+        .title(format!("Pro builds of {}", champion_name))
+        .color(theme.info())
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+
+    if pro_builds.is_empty() {
+        embed = embed.description("No recent pro games found for this champion.");
+    }
+    for build in pro_builds {
+        let mut runes = Vec::new();
+        for rune in &build.runes {
+            runes.push(get_emoji(collection_emoji.clone(), "rune", rune).await?);
+        }
+        let mut items = Vec::new();
+        for item in &build.items {
+            items.push(get_emoji(collection_emoji.clone(), "item", item).await?);
+        }
+        let result = if build.win { "✅ Win" } else { "❌ Loss" };
+        let matchup = if build.opponent.is_empty() {
+            build.pro_name.clone()
+        } else {
+            format!("{} vs {}", build.pro_name, build.opponent)
+        };
+        // Les timestamps Discord ne sont pas affichés dans les noms de champs
+        embed = embed.field(
+            format!("{} — {}", matchup, result),
+            format!(
+                "**Played:** {}\n**Runes:** {}\n**Items:** {}",
+                time_since_game_ended(build.played_at.max(0) as u64),
+                runes.join(" "),
+                items.join(" ")
+            ),
+            false,
+        );
+    }
+
+    let source = pro_builds
+        .first()
+        .map(|build| build.source.as_str())
+        .unwrap_or("Probuilds");
+    Ok(
+        embed.footer(CreateEmbedFooter::new(theme.footer_text(&format!(
+            "Data from {}\n{}",
+            source,
+            deletion_notice()
+        )))),
+    )
+}
+
+/// ⚙️ **Function**: Builds the "Pro builds" view of `/championsinfos` when it is first opened.
+///
+/// # Parameters:
+/// - `data`: The data of the bot, used for the MongoDB client.
+/// - `payload`: The `ProBuildsView` of the message, as JSON.
+///
+/// # Returns:
+/// - `BoxFuture<CreateEmbed>`: The pro builds of the champion, or an embed with a fallback field when they cannot be
+///   loaded.
+///
+/// # Notes:
+/// - Registered under `PRO_BUILDS_VIEW_LOADER` (see `module::register_components`), so probuilds.net is only scraped
+///   when a user opens the view.
+pub fn load_pro_builds_view<'a>(data: &'a Data, payload: &'a str) -> BoxFuture<'a, CreateEmbed> {
+    Box::pin(async move {
+        let view: ProBuildsView = match serde_json::from_str(payload) {
+            Ok(view) => view,
+            Err(e) => {
+                log::error!("Invalid pro builds view: {:?}", e);
+                return pro_builds_fallback("this champion", &EmbedTheme::default());
+            }
+        };
+        let mongo_client = &data.mongo_client;
+        let collection_emoji = mongo_client
+            .database("stat-summoner")
+            .collection::<EmojiId>("emojis_id");
+        let embed = match get_pro_builds(mongo_client, &ProbuildsNet, &view.champion_id).await {
+            Ok(pro_builds) => {
+                create_embed_pro_builds(
+                    &view.champion_name,
+                    &pro_builds,
+                    &view.theme,
+                    &collection_emoji,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        embed.unwrap_or_else(|e| {
+            log::error!(
+                "Failed to load the pro builds of {}: {:?}",
+                view.champion_id,
+                e
+            );
+            pro_builds_fallback(&view.champion_name, &view.theme)
+        })
+    })
+}
+
+/// ⚙️ **Function**: Creates the "Pro builds" page shown when the pro builds cannot be loaded.
+fn pro_builds_fallback(champion_name: &str, theme: &EmbedTheme) -> CreateEmbed {
+    CreateEmbed::default()
+        .title(format!("Pro builds of {}", champion_name))
+        .color(theme.info())
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+        .field(
+            "Unavailable",
+            "The pro builds could not be loaded right now. Try again in a few minutes.",
+            false,
+        )
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+}
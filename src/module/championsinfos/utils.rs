@@ -1,9 +1,15 @@
-use crate::models::data::{ChampionData, EmojiId};
+use crate::config::deletion_notice;
+use crate::embed::progress_bar;
+use crate::models::bracket::RankBracket;
+use crate::models::data::{BracketStatsData, ChampionData, Data, EmbedTheme, EmojiId};
 use crate::models::error::Error;
-use crate::utils::get_emoji;
+use crate::module::settings::utils::get_guild_champion_aliases;
+use crate::static_data::champion_icon_url;
+use crate::utils::{champion_data_freshness, get_emoji, resolve_champion_alias};
+use mongodb::bson::doc;
 use mongodb::Collection;
 use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
-use serde_json::Value;
+use strsim::normalized_levenshtein;
 
 /// ⚙️ Constructs a Discord embed containing detailed information about a League of Legends champion.
 ///
@@ -13,6 +19,8 @@ use serde_json::Value;
 ///
 /// # Parameters:
 /// - `champion_data`: A `ChampionData` struct containing the champion's information, including roles, runes, items, and statistics.
+/// - `bracket`: The rank bracket whose winrate, banrate and popularity are displayed. Falls back to all ranks if the bracket was not scraped.
+/// - `theme`: The guild's embed theme, providing the embed color and the optional brand line of the footer.
 /// - `collection_emoji`: A reference to a MongoDB `Collection<EmojiId>` used to retrieve the appropriate emojis for runes and items.
 ///
 /// # Returns:
@@ -23,7 +31,7 @@ use serde_json::Value;
 /// - The function retrieves emojis asynchronously for each rune and item using the `get_emoji` function.
 /// - It formats numerical statistics (winrate, banrate, popularity) as percentages.
 /// - The embed includes a thumbnail image of the champion, fetched from the Data Dragon API.
-/// - The embed includes a footer indicating that the message will be deleted after 60 seconds.
+/// - The embed includes a footer stating where the statistics come from, how old they are and which patch they reflect, followed by the deletion notice.
 ///
 /// # Example:
 /// ```rust
@@ -68,6 +76,8 @@ use serde_json::Value;
 /// - It also depends on the MongoDB collection for retrieving emojis.
 pub async fn create_embed_champions_info(
     champion_data: ChampionData,
+    bracket: RankBracket,
+    theme: &EmbedTheme,
     collection_emoji: &Collection<EmojiId>,
 ) -> Result<CreateEmbed, Error> {
     let primary_rune_emoji = get_emoji(
@@ -156,9 +166,24 @@ pub async fn create_embed_champions_info(
     )
     .await?;
 
-    let popularity = champion_data.popularity.parse::<f64>().unwrap_or(0.0) * 100.0;
-    let winrate = champion_data.winrate.parse::<f64>().unwrap_or(0.0) * 100.0;
-    let banrate = champion_data.banrate.parse::<f64>().unwrap_or(0.0) * 100.0;
+    let (bracket, stats) = match get_bracket_stats(&champion_data, bracket) {
+        Some(stats) => (bracket, stats),
+        None => (
+            RankBracket::All,
+            get_bracket_stats(&champion_data, RankBracket::All).unwrap(),
+        ),
+    };
+    let popularity = stats.popularity.parse::<f64>().unwrap_or(0.0) * 100.0;
+    let winrate = stats.winrate.parse::<f64>().unwrap_or(0.0) * 100.0;
+    let banrate = stats.banrate.parse::<f64>().unwrap_or(0.0) * 100.0;
+    let title = match bracket {
+        RankBracket::All => format!("Informations about {}", champion_data.name),
+        _ => format!(
+            "Informations about {} ({})",
+            champion_data.name,
+            bracket.label()
+        ),
+    };
 
     let runes_description = format!(
         "**Primary Rune:** {}\n{} {} {}\n\n**Secondary Runes:** \n{} {}\n\n**Shards:** {} {} {}",
@@ -177,27 +202,248 @@ pub async fn create_embed_champions_info(
         "{} {} {}",
         core_item_1_emoji, core_item_2_emoji, core_item_3_emoji
     );
-    let version_json: Value = reqwest::get("https://ddragon.leagueoflegends.com/api/versions.json")
-        .await?
-        .json()
-        .await?;
-    let version = version_json[0].as_str().unwrap();
+    let thumbnail = champion_icon_url(&champion_data.id_name).await?;
     let embed = CreateEmbed::default()
-        .title(format!("Informations about {}", champion_data.name))
-        .color(0x00ff00)
+        .title(title)
+        .color(theme.info())
         .field("Role", champion_data.role.join(", "), false)
-        .field("Winrate", format!("{:.2}%", winrate), true)
-        .field("Banrate", format!("{:.2}%", banrate), true)
-        .field("Popularity", format!("{:.2}%", popularity), true)
+        .field("Winrate", progress_bar(winrate), true)
+        .field("Banrate", progress_bar(banrate), true)
+        .field("Popularity", progress_bar(popularity), true)
         .field("Runes", runes_description, false)
         .field("Build", core_build_description, false)
-        .footer(CreateEmbedFooter::new(
-            "This message will be deleted in 60 seconds.",
-        ))
-        .thumbnail(format!(
-            "https://ddragon.leagueoflegends.com/cdn/{}/img/champion/{}.png",
-            version, champion_data.id_name
-        ));
+        .footer(CreateEmbedFooter::new(theme.footer_text(&format!(
+            "{}{}\n{}",
+            champion_data_freshness(
+                &champion_data.source,
+                &champion_data.patch,
+                champion_data.updated_at
+            ),
+            if champion_data.overridden {
+                ", corrected by the bot owners"
+            } else {
+                ""
+            },
+            deletion_notice()
+        ))))
+        .thumbnail(thumbnail);
 
     Ok(embed)
 }
+
+/// ⚙️ **Function**: Adds the boots options and situational items of a champion to its information embed.
+///
+/// # Parameters:
+/// - `embed`: The embed built by `create_embed_champions_info`.
+/// - `champion_data`: The champion's stored data, including its situational build.
+/// - `collection_emoji`: A reference to a MongoDB `Collection<EmojiId>` used to retrieve the item emojis.
+///
+/// # Returns:
+/// - `Result<CreateEmbed, Error>`: The embed with an additional "Situational" field.
+///
+/// # Example:
+/// ```rust
+/// let situational = create_embed_situational_build(embed.clone(), &champion_data, &collection_emoji).await?;
+/// ```
+///
+/// # Notes:
+/// - Champions scraped before situational builds were stored show "No data yet." in place of the items.
+pub async fn create_embed_situational_build(
+    embed: CreateEmbed,
+    champion_data: &ChampionData,
+    collection_emoji: &Collection<EmojiId>,
+) -> Result<CreateEmbed, Error> {
+    let mut lines = Vec::new();
+    for (title, items) in [
+        ("Boots", &champion_data.situational_build.boots),
+        ("Situational items", &champion_data.situational_build.items),
+    ] {
+        let mut emojis = Vec::new();
+        for item in items {
+            emojis.push(get_emoji(collection_emoji.clone(), "item", item).await?);
+        }
+        let emojis = if emojis.is_empty() {
+            "No data yet.".to_string()
+        } else {
+            emojis.join(" ")
+        };
+        lines.push(format!("**{}:** {}", title, emojis));
+    }
+
+    Ok(embed.field("Situational", lines.join("\n\n"), false))
+}
+
+/// ⚙️ **Function**: Parses a patch number into a sortable `(major, minor)` pair.
+///
+/// # Parameters:
+/// - `patch`: A patch number such as `14.20`.
+///
+/// # Returns:
+/// - `(u32, u32)`: The major and minor components, `(0, 0)` for components that are missing or not numeric.
+fn patch_sort_key(patch: &str) -> (u32, u32) {
+    let mut parts = patch
+        .split('.')
+        .map(|part| part.parse::<u32>().unwrap_or(0));
+    (parts.next().unwrap_or(0), parts.next().unwrap_or(0))
+}
+
+/// ⚙️ **Function**: Lists the patches for which champion statistics are stored, newest first.
+///
+/// # Parameters:
+/// - `collection`: The `champions_data` MongoDB collection.
+///
+/// # Returns:
+/// - `Result<Vec<String>, Error>`: The stored patch numbers sorted from the most recent to the oldest.
+///
+/// # Notes:
+/// - Documents scraped before champion data was keyed by patch have no `patch` field and are ignored.
+/// - Patches are compared numerically so that `14.9` sorts before `14.10`.
+pub async fn get_champion_patches(
+    collection: &Collection<ChampionData>,
+) -> Result<Vec<String>, Error> {
+    let mut patches: Vec<String> = collection
+        .distinct("patch", doc! { "patch": { "$nin": ["", null] } })
+        .await?
+        .into_iter()
+        .filter_map(|patch| patch.as_str().map(|patch| patch.to_string()))
+        .collect();
+    patches.sort_by_key(|patch| std::cmp::Reverse(patch_sort_key(patch)));
+    Ok(patches)
+}
+
+/// ⚙️ **Function**: Returns the most recent patch for which champion statistics are stored.
+///
+/// # Parameters:
+/// - `collection`: The `champions_data` MongoDB collection.
+///
+/// # Returns:
+/// - `Result<Option<String>, Error>`: The latest patch, or `None` if no document is keyed by patch yet.
+pub async fn get_latest_champion_patch(
+    collection: &Collection<ChampionData>,
+) -> Result<Option<String>, Error> {
+    Ok(get_champion_patches(collection).await?.into_iter().next())
+}
+
+/// ⚙️ **Function**: Fetches a champion's statistics for a given patch, or for the latest one.
+///
+/// # Parameters:
+/// - `collection`: The `champions_data` MongoDB collection.
+/// - `id_name`: The Data Dragon identifier of the champion (e.g., `MonkeyKing`).
+/// - `patch`: The patch to look up. `None` selects the most recently scraped document.
+///
+/// # Returns:
+/// - `Result<Option<ChampionData>, Error>`: The champion's statistics, or `None` if nothing is stored for that patch.
+///
+/// # Example:
+/// ```rust
+/// let previous = get_champion_data(&collection, "Jhin", Some("14.19")).await?;
+/// ```
+pub async fn get_champion_data(
+    collection: &Collection<ChampionData>,
+    id_name: &str,
+    patch: Option<&str>,
+) -> Result<Option<ChampionData>, Error> {
+    let champion = match patch {
+        Some(patch) => {
+            collection
+                .find_one(doc! { "id_name": id_name, "patch": patch })
+                .await?
+        }
+        None => {
+            collection
+                .find_one(doc! { "id_name": id_name })
+                .sort(doc! { "updated_at": -1 })
+                .await?
+        }
+    };
+    Ok(champion)
+}
+
+/// ⚙️ **Function**: Returns a champion's popularity, winrate and banrate for a rank bracket.
+///
+/// # Parameters:
+/// - `champion_data`: The champion's stored statistics.
+/// - `bracket`: The rank bracket to read. `RankBracket::All` always succeeds.
+///
+/// # Returns:
+/// - `Option<BracketStatsData>`: The bracket's statistics, or `None` if that bracket was not scraped for this champion.
+pub fn get_bracket_stats(
+    champion_data: &ChampionData,
+    bracket: RankBracket,
+) -> Option<BracketStatsData> {
+    match bracket {
+        RankBracket::All => Some(BracketStatsData {
+            bracket: bracket.key().to_string(),
+            popularity: champion_data.popularity.clone(),
+            winrate: champion_data.winrate.clone(),
+            banrate: champion_data.banrate.clone(),
+        }),
+        _ => champion_data
+            .brackets
+            .iter()
+            .find(|stats| stats.bracket == bracket.key())
+            .cloned(),
+    }
+}
+
+/// ⚙️ **Function**: Ranks the champions by how close their name is to the text typed by the user.
+///
+/// # Parameters:
+/// - `champion_names`: The names of the champions.
+/// - `input`: The text typed by the user.
+///
+/// # Returns:
+/// - `Vec<(String, f64)>`: The champion names with their similarity to `input` (normalized Levenshtein, between 0 and
+///   1), closest first.
+///
+/// # Example:
+/// ```rust
+/// let matches = rank_champion_matches(&champion_names, "jnx");
+/// assert_eq!(matches[0].0, "Jinx");
+/// ```
+pub fn rank_champion_matches(champion_names: &[String], input: &str) -> Vec<(String, f64)> {
+    let input = input.trim().to_lowercase();
+    let mut matches: Vec<(String, f64)> = champion_names
+        .iter()
+        .map(|name| {
+            let score = normalized_levenshtein(&input, &name.to_lowercase());
+            (name.clone(), score)
+        })
+        .collect();
+    matches.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    matches
+}
+
+/// ⚙️ **Function**: Suggests the champions whose name contains the text typed so far.
+///
+/// # Parameters:
+/// - `ctx`: The application context, used to access the champion lookups and the nicknames of the server.
+/// - `partial`: The text typed so far by the user.
+///
+/// # Returns:
+/// - `Vec<String>`: Up to 25 champion names, sorted alphabetically, after the champion `partial` is a nickname of.
+///
+/// # Example:
+/// ```rust
+/// #[autocomplete = "autocomplete_champion"] champion: String,
+/// ```
+pub async fn autocomplete_champion(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    partial: &str,
+) -> Vec<String> {
+    let guild_aliases = get_guild_champion_aliases(ctx).await;
+    let champions = ctx.data().champions.read().await;
+    let mut names: Vec<String> = resolve_champion_alias(&champions, &guild_aliases, partial)
+        .into_iter()
+        .collect();
+    let partial = partial.trim().to_lowercase();
+    let mut matching: Vec<String> = champions
+        .names()
+        .into_iter()
+        .filter(|name| name.to_lowercase().contains(&partial) && !names.contains(name))
+        .collect();
+    matching.sort();
+    names.extend(matching);
+    names.truncate(25);
+    names
+}
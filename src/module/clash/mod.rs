@@ -0,0 +1,15 @@
+/// 🛠 **Module clash**: Contains the Discord scheduled events created for the Clash tournaments.
+///
+/// Guilds subscribe with `/settings clash`. The Clash schedule of their region is read from clash-v1 and every
+/// upcoming Clash day becomes a scheduled event of the guild, moved or removed when Riot changes the schedule.
+///
+/// # Files in this module:
+/// - `utils.rs`: The `clash_events` collection and the synchronization job.
+///
+/// # Example:
+/// The events are synchronized in `main.rs`, with the weekly posts:
+///
+/// ```rust
+/// sync_clash_events(&mongo_client, &http, &riot_api_key).await?;
+/// ```
+pub mod utils;
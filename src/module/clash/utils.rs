@@ -0,0 +1,252 @@
+use crate::config::config;
+use crate::models::data::{ClashEventData, GuildSettingsData};
+use crate::models::error::Error;
+use crate::models::region::Region;
+use crate::module::settings::utils::guild_settings_collection;
+use crate::riot_api::get_clash_tournaments;
+use crate::utils::region_from_string;
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+use poise::serenity_prelude::{
+    CreateScheduledEvent, EditScheduledEvent, GuildId, Http, ScheduledEventType, Timestamp,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Duration, in seconds, given to the scheduled event of a Clash day (the API only gives its start).
+const CLASH_EVENT_DURATION: i64 = 4 * 60 * 60;
+
+/// ⚙️ **Function**: Returns the `clash_events` collection, which links the Clash days to the scheduled events of each guild.
+pub fn clash_events_collection(mongo_client: &Client) -> Collection<ClashEventData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<ClashEventData>("clash_events")
+}
+
+/// ⚙️ **Function**: Creates, moves or removes the scheduled events of the upcoming Clash days in the subscribed guilds.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client.
+/// - `http`: The Discord HTTP client used to manage the scheduled events.
+/// - `riot_api_key`: The Riot API key.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if the database cannot be read or updated.
+///
+/// # Example:
+/// ```rust
+/// sync_clash_events(&mongo_client, &http, &riot_api_key).await?;
+/// ```
+///
+/// # Notes:
+/// - The schedule of each region is fetched once per run. A region that fails to load is skipped until the next run.
+/// - A Clash day whose start changed moves its event, and a cancelled one deletes it.
+/// - Events are created for the days that have not started yet; the bot needs the **Manage Events** permission.
+/// - The links of past Clash days are removed from the database, their events end on their own.
+/// - Nothing is done in dry-run mode, where the events could not be created.
+pub async fn sync_clash_events(
+    mongo_client: &Client,
+    http: &Http,
+    riot_api_key: &str,
+) -> Result<(), Error> {
+    if config().dry_run {
+        log::info!("[dry run] Skipping the synchronization of the Clash events.");
+        return Ok(());
+    }
+    let guilds: Vec<GuildSettingsData> = guild_settings_collection(mongo_client)
+        .find(doc! { "clash_region": { "$ne": null } })
+        .await?
+        .try_collect()
+        .await?;
+    let collection = clash_events_collection(mongo_client);
+    let client = reqwest::Client::new();
+    let now = Utc::now().timestamp();
+    let mut tournaments_by_region: HashMap<String, Vec<Value>> = HashMap::new();
+
+    for settings in guilds {
+        let Some(region) = settings.clash_region else {
+            continue;
+        };
+        if !tournaments_by_region.contains_key(&region) {
+            match get_clash_tournaments(&client, &region, riot_api_key).await {
+                Ok(tournaments) => {
+                    tournaments_by_region.insert(region.clone(), tournaments);
+                }
+                Err(e) => {
+                    log::error!(
+                        "Failed to fetch the Clash tournaments of {}: {:?}",
+                        region,
+                        e
+                    );
+                    continue;
+                }
+            }
+        }
+        let guild_id = GuildId::new(settings.guild_id.parse().unwrap_or(1));
+        for tournament in &tournaments_by_region[&region] {
+            let tournament_id = tournament["id"].as_i64().unwrap_or(0);
+            for phase in tournament["schedule"].as_array().into_iter().flatten() {
+                let schedule_id = phase["id"].as_i64().unwrap_or(0);
+                let start_time = phase["startTime"].as_i64().unwrap_or(0) / 1000;
+                let cancelled = phase["cancelled"].as_bool().unwrap_or(false);
+                let filter = doc! { "guild_id": &settings.guild_id, "schedule_id": schedule_id };
+                let existing = collection.find_one(filter.clone()).await?;
+                match existing {
+                    Some(event) if cancelled => {
+                        if let Err(e) = guild_id.delete_scheduled_event(http, event.event_id).await
+                        {
+                            log::warn!(
+                                "Failed to delete the Clash event {}: {}",
+                                event.event_id,
+                                e
+                            );
+                        }
+                        collection.delete_one(filter).await?;
+                    }
+                    Some(event) if event.start_time != start_time && start_time > now => {
+                        let edit = EditScheduledEvent::new()
+                            .start_time(unix_to_timestamp(start_time))
+                            .end_time(unix_to_timestamp(start_time + CLASH_EVENT_DURATION))
+                            .description(clash_event_description(phase));
+                        match guild_id
+                            .edit_scheduled_event(http, event.event_id, edit)
+                            .await
+                        {
+                            Ok(_) => {
+                                collection
+                                    .update_one(
+                                        filter,
+                                        doc! { "$set": { "start_time": start_time } },
+                                    )
+                                    .await?;
+                            }
+                            Err(e) => {
+                                log::warn!(
+                                    "Failed to move the Clash event {}: {}",
+                                    event.event_id,
+                                    e
+                                )
+                            }
+                        }
+                    }
+                    Some(_) => {}
+                    None if cancelled || start_time <= now => {}
+                    None => {
+                        let event = CreateScheduledEvent::new(
+                            ScheduledEventType::External,
+                            clash_event_name(tournament),
+                            unix_to_timestamp(start_time),
+                        )
+                        .end_time(unix_to_timestamp(start_time + CLASH_EVENT_DURATION))
+                        .location(scouting_link(&region))
+                        .description(clash_event_description(phase));
+                        match guild_id.create_scheduled_event(http, event).await {
+                            Ok(event) => {
+                                collection
+                                    .insert_one(ClashEventData {
+                                        guild_id: settings.guild_id.clone(),
+                                        tournament_id,
+                                        schedule_id,
+                                        event_id: event.id.get(),
+                                        start_time,
+                                    })
+                                    .await?;
+                            }
+                            Err(e) => log::warn!(
+                                "Failed to create a Clash event in guild {}: {}",
+                                settings.guild_id,
+                                e
+                            ),
+                        }
+                    }
+                }
+            }
+        }
+    }
+    collection
+        .delete_many(doc! { "start_time": { "$lt": now - CLASH_EVENT_DURATION } })
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Converts a Unix timestamp to a Discord timestamp.
+fn unix_to_timestamp(unix: i64) -> Timestamp {
+    Timestamp::from_unix_timestamp(unix).unwrap_or_else(|_| Timestamp::now())
+}
+
+/// ⚙️ **Function**: Builds the name of the scheduled event of a Clash tournament.
+///
+/// # Parameters:
+/// - `tournament`: The clash-v1 tournament.
+///
+/// # Returns:
+/// - `String`: The name of the event, e.g. `🏆 Clash: Bilgewater Cup (Day 2)`.
+fn clash_event_name(tournament: &Value) -> String {
+    let humanize = |key: &str| {
+        key.split('_')
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    };
+    let name = humanize(tournament["nameKey"].as_str().unwrap_or("clash"));
+    match tournament["nameKeySecondary"].as_str() {
+        Some(secondary) if !secondary.is_empty() => {
+            format!("🏆 Clash: {} ({})", name, humanize(secondary))
+        }
+        _ => format!("🏆 Clash: {}", name),
+    }
+}
+
+/// ⚙️ **Function**: Builds the description of the scheduled event of a Clash day.
+///
+/// # Parameters:
+/// - `phase`: The clash-v1 schedule phase of the day.
+///
+/// # Returns:
+/// - `String`: The registration and bracket times (UTC) and a reminder to scout the opponents.
+fn clash_event_description(phase: &Value) -> String {
+    let format_time = |millis: i64| {
+        chrono::DateTime::from_timestamp(millis / 1000, 0)
+            .map(|time| time.format("%A %d %B, %H:%M UTC").to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+    format!(
+        "Registration opens {}.\nBracket starts {}.\nScout your opponents with the link of the event before the games.",
+        format_time(phase["registrationTime"].as_i64().unwrap_or(0)),
+        format_time(phase["startTime"].as_i64().unwrap_or(0))
+    )
+}
+
+/// ⚙️ **Function**: Returns the multi-search page of OP.GG for a region, used to scout a Clash team.
+///
+/// # Parameters:
+/// - `region`: The server identifier (e.g., `euw1`).
+///
+/// # Returns:
+/// - `String`: The URL of the multi-search page of that region.
+fn scouting_link(region: &str) -> String {
+    let slug = match region_from_string(region) {
+        Some(Region::EUW) => "euw",
+        Some(Region::NA) => "na",
+        Some(Region::KR) => "kr",
+        Some(Region::EUNE) => "eune",
+        Some(Region::BR) => "br",
+        Some(Region::LAN) => "lan",
+        Some(Region::LAS) => "las",
+        Some(Region::OCE) => "oce",
+        Some(Region::RU) => "ru",
+        Some(Region::TR) => "tr",
+        Some(Region::JP) => "jp",
+        None => "euw",
+    };
+    format!("https://www.op.gg/multisearch/{}", slug)
+}
@@ -0,0 +1,97 @@
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, edit_and_schedule_deletion, send_fetching_message,
+};
+use crate::models::data::Data;
+use crate::models::error::Error;
+use crate::models::riot_id::RiotId;
+use crate::module::clutchstats::utils::{create_embed_clutchstats, summarize_clutch};
+use crate::module::optout::utils::is_opted_out;
+use crate::module::settings::utils::get_guild_theme;
+use crate::riot_api::{get_matchs_id, get_matchs_info, get_puuid};
+use futures::future::join_all;
+use poise::CreateReply;
+use reqwest::Client;
+use serde_json::Value;
+
+/// Shows a player's winrate in close games compared to stomps and other games.
+///
+/// # Example:
+/// ```rust
+/// /clutchstats riot_id: Faker#KR1
+/// /clutchstats riot_id: Faker#KR1 games: 10
+/// ```
+///
+/// # Notes:
+/// - A close game ends with a gold gap under 4k; a stomp with a gap of 10k or more, or by a surrender before 25 minutes.
+/// - Remakes are not counted.
+#[poise::command(slash_command)]
+pub async fn clutchstats(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Riot ID of the player (e.g., Faker#KR1)"] riot_id: RiotId,
+    #[description = "Number of recent games to analyze (20 by default)"]
+    #[min = 5]
+    #[max = 20]
+    games: Option<u32>,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let games = games.unwrap_or(20);
+    let client = Client::new();
+    let riot_api_key = &ctx.data().riot_api_key;
+
+    let puuid = match get_puuid(&client, &riot_id, riot_api_key).await {
+        Ok(puuid) => puuid,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error fetching PUUID", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    if is_opted_out(&ctx.data().mongo_client, &puuid).await? {
+        let error_message =
+            "This player has opted out of Stat Summoner: their statistics cannot be looked up.";
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+        return Ok(());
+    }
+
+    let match_ids = match get_matchs_id(&client, &puuid, riot_api_key, games).await {
+        Ok(match_ids) => match_ids,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error fetching match IDs", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    let matches: Vec<Value> = join_all(
+        match_ids
+            .iter()
+            .map(|match_id| get_matchs_info(&client, match_id, riot_api_key)),
+    )
+    .await
+    .into_iter()
+    .filter_map(|match_data| match match_data {
+        Ok(match_data) => Some(match_data),
+        Err(e) => {
+            log::error!("Error fetching a match for the clutch stats: {:?}", e);
+            None
+        }
+    })
+    .collect();
+    let summary = summarize_clutch(&puuid, &matches);
+    let riot_id = riot_id.to_string();
+    if summary.overall().games() == 0 {
+        let error_message = format!("No recent game found for {}.", riot_id);
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message)).await?;
+        return Ok(());
+    }
+
+    let reply = CreateReply {
+        embeds: vec![create_embed_clutchstats(
+            &riot_id,
+            &summary,
+            &get_guild_theme(ctx).await,
+        )],
+        ..Default::default()
+    };
+    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+/// 🛠 **Module clutchstats**: Contains the performance of a player in close games.
+///
+/// This module classifies a player's recent games as close games, stomps or regular games, from the final gold
+/// difference between the teams and the length of the game, and reports the player's record in each kind of game.
+///
+/// # Files in this module:
+/// - `clutchstats.rs`: The `/clutchstats` command.
+/// - `utils.rs`: The classification of the games, the records and their embed.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`:
+///
+/// ```rust
+/// let framework = poise::Framework::builder()
+///     .options(poise::FrameworkOptions {
+///         commands: module::all_commands(),
+///         ..Default::default()
+///     })
+///     .build();
+/// ```
+pub mod clutchstats;
+pub mod utils;
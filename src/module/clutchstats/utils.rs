@@ -0,0 +1,226 @@
+use crate::config::deletion_notice;
+use crate::embed::progress_bar;
+use crate::models::data::EmbedTheme;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use serde_json::Value;
+
+/// Largest final gold difference, between the two teams, of a close game.
+const CLOSE_GAME_MAX_GOLD_DIFF: i64 = 4000;
+
+/// Smallest final gold difference, between the two teams, of a stomp.
+const STOMP_MIN_GOLD_DIFF: i64 = 10000;
+
+/// Games ended by a surrender before this duration, in seconds, are stomps whatever the gold difference.
+const STOMP_MAX_SURRENDER_DURATION: i64 = 25 * 60;
+
+/// Games shorter than this duration, in seconds, are remakes and are not counted.
+const REMAKE_MAX_DURATION: i64 = 5 * 60;
+
+/// Kind of game, from how one-sided it was.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameKind {
+    Close,
+    Stomp,
+    Regular,
+}
+
+/// Wins and losses of a player in a kind of game.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Record {
+    pub wins: u32,
+    pub losses: u32,
+}
+
+impl Record {
+    /// Number of games of the record.
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses
+    }
+
+    /// Winrate of the record, in percent.
+    pub fn winrate(&self) -> f64 {
+        self.wins as f64 / self.games().max(1) as f64 * 100.0
+    }
+}
+
+/// Records of a player in close games, stomps and regular games.
+#[derive(Debug, Default)]
+pub struct ClutchSummary {
+    pub close: Record,
+    pub stomps: Record,
+    pub regular: Record,
+}
+
+impl ClutchSummary {
+    /// Record over every game, whatever its kind.
+    pub fn overall(&self) -> Record {
+        Record {
+            wins: self.close.wins + self.stomps.wins + self.regular.wins,
+            losses: self.close.losses + self.stomps.losses + self.regular.losses,
+        }
+    }
+}
+
+/// ⚙️ **Function**: Classifies a game as a close game, a stomp or a regular game.
+///
+/// # Parameters:
+/// - `info`: The `info` block of a match-v5 payload.
+///
+/// # Returns:
+/// - `Option<GameKind>`: The kind of game, or `None` for a remake.
+///
+/// # Example:
+/// ```rust
+/// if classify_game(&match_data["info"]) == Some(GameKind::Close) {
+///     println!("Nail-biter!");
+/// }
+/// ```
+///
+/// # Notes:
+/// - A stomp ends with a gold difference of 10k or more, or by a surrender before 25 minutes.
+/// - A close game ends with a gold difference under 4k.
+pub fn classify_game(info: &Value) -> Option<GameKind> {
+    let duration = info["gameDuration"].as_i64().unwrap_or(0);
+    if duration < REMAKE_MAX_DURATION {
+        return None;
+    }
+    let participants = info["participants"].as_array()?;
+    if participants
+        .iter()
+        .any(|p| p["gameEndedInEarlySurrender"].as_bool().unwrap_or(false))
+    {
+        return None;
+    }
+    let gold_diff: i64 = participants
+        .iter()
+        .map(|p| {
+            let gold = p["goldEarned"].as_i64().unwrap_or(0);
+            if p["teamId"].as_i64() == Some(100) {
+                gold
+            } else {
+                -gold
+            }
+        })
+        .sum::<i64>()
+        .abs();
+    let surrender = participants
+        .iter()
+        .any(|p| p["gameEndedInSurrender"].as_bool().unwrap_or(false));
+    if gold_diff >= STOMP_MIN_GOLD_DIFF || (surrender && duration < STOMP_MAX_SURRENDER_DURATION) {
+        Some(GameKind::Stomp)
+    } else if gold_diff < CLOSE_GAME_MAX_GOLD_DIFF {
+        Some(GameKind::Close)
+    } else {
+        Some(GameKind::Regular)
+    }
+}
+
+/// ⚙️ **Function**: Sums up a player's results by kind of game.
+///
+/// # Parameters:
+/// - `puuid`: The PUUID of the player.
+/// - `matches`: The match-v5 data of the player's games.
+///
+/// # Returns:
+/// - `ClutchSummary`: The player's record in close games, stomps and regular games.
+///
+/// # Example:
+/// ```rust
+/// let summary = summarize_clutch(&puuid, &matches);
+/// println!("{:.0}% in close games", summary.close.winrate());
+/// ```
+pub fn summarize_clutch(puuid: &str, matches: &[Value]) -> ClutchSummary {
+    let mut summary = ClutchSummary::default();
+    for match_data in matches {
+        let info = &match_data["info"];
+        let Some(kind) = classify_game(info) else {
+            continue;
+        };
+        let Some(participant) = info["participants"].as_array().and_then(|participants| {
+            participants
+                .iter()
+                .find(|participant| participant["puuid"].as_str() == Some(puuid))
+        }) else {
+            continue;
+        };
+        let record = match kind {
+            GameKind::Close => &mut summary.close,
+            GameKind::Stomp => &mut summary.stomps,
+            GameKind::Regular => &mut summary.regular,
+        };
+        if participant["win"].as_bool().unwrap_or(false) {
+            record.wins += 1;
+        } else {
+            record.losses += 1;
+        }
+    }
+    summary
+}
+
+/// ⚙️ **Function**: Creates the embed of a player's clutch stats.
+///
+/// # Parameters:
+/// - `riot_id`: The Riot ID of the player (e.g. `Faker#KR1`).
+/// - `summary`: The records built by `summarize_clutch`.
+/// - `theme`: The embed theme of the guild.
+///
+/// # Returns:
+/// - `CreateEmbed`: The embed, with the record and winrate of each kind of game and a verdict.
+///
+/// # Example:
+/// ```text
+/// 🧊 Clutch stats of Faker#KR1 (20 games)
+/// Close games: 5W - 1L ▰▰▰▰▱ 83%
+/// Stomps: 4W - 3L ▰▰▰▱▱ 57%
+/// Other games: 3W - 4L ▰▰▱▱▱ 43%
+/// Overall: 12W - 8L ▰▰▰▱▱ 60%
+/// Verdict: Ice in the veins: +23% in close games.
+/// ```
+pub fn create_embed_clutchstats(
+    riot_id: &str,
+    summary: &ClutchSummary,
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let overall = summary.overall();
+    let format_record = |record: &Record| {
+        if record.games() == 0 {
+            "No game".to_string()
+        } else {
+            format!(
+                "{}W - {}L {}",
+                record.wins,
+                record.losses,
+                progress_bar(record.winrate())
+            )
+        }
+    };
+    let verdict = if summary.close.games() < 3 {
+        "Not enough close games to judge yet.".to_string()
+    } else {
+        let delta = summary.close.winrate() - overall.winrate();
+        if delta >= 5.0 {
+            format!("Ice in the veins: {:+.0}% in close games.", delta)
+        } else if delta <= -5.0 {
+            format!("Shaky when it's tight: {:+.0}% in close games.", delta)
+        } else {
+            "As reliable in close games as in any other.".to_string()
+        }
+    };
+    CreateEmbed::default()
+        .title(format!(
+            "🧊 Clutch stats of {} ({} games)",
+            riot_id,
+            overall.games()
+        ))
+        .color(theme.info())
+        .field("🤏 Close games", format_record(&summary.close), false)
+        .field("💥 Stomps", format_record(&summary.stomps), false)
+        .field("⚖️ Other games", format_record(&summary.regular), false)
+        .field("📊 Overall", format_record(&overall), false)
+        .field("Verdict", verdict, false)
+        .footer(CreateEmbedFooter::new(theme.footer_text(&format!(
+            "Close: final gold gap under 4k · Stomp: 10k+ or surrender before 25 min\n{}",
+            deletion_notice()
+        ))))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
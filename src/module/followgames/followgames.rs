@@ -1,100 +1,97 @@
-use crate::embed::create_embed_error;
-use crate::embed::schedule_message_deletion;
-use crate::models::data::Data;
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, edit_and_schedule_deletion,
+    execute_modal_with_retry, schedule_message_deletion, send_fetching_message,
+};
 use crate::models::data::SummonerFollowedData;
+use crate::models::data::{Data, NotificationFilter, QueueFilter};
 use crate::models::error::Error;
 use crate::models::modal::FollowGamesModal;
 use crate::models::region::Region;
-use crate::module::followgames::utils::check_and_add_in_db;
+use crate::models::riot_id::RiotId;
+use crate::module::followgames::utils::{check_and_add_in_db, check_follow_hours, follow_end};
 use crate::riot_api::{get_matchs_id, get_puuid, get_summoner_id};
 use crate::utils::region_to_string;
-use chrono::{Duration, Utc};
-use poise::Modal;
 
 /// Starts following a player's games for a specified duration.
 ///
-/// This slash command allows a user to follow a player's games for a certain amount of time (between 1 and 48 hours).
+/// This slash command allows a user to follow a player's games for a certain amount of time (from 1 hour up to `MAX_FOLLOW_HOURS`, 48 by default),
+/// or permanently until the player is unfollowed.
 /// It retrieves the player's PUUID and Summoner ID from the Riot API, and stores their information in the database
 /// for tracking future games.
 ///
 /// # Parameters:
 /// - `ctx`: The `poise::ApplicationContext` provides the context in which the command is executed, including access to the Discord interaction and data.
 /// - `region`: A `Region` enum value selected by the user, indicating the player's region (e.g., NA, EUW, etc.).
+/// - `hours`: The follow duration, between 1 hour and `MAX_FOLLOW_HOURS` (48 by default).
+/// - `permanent`: Follows the player until they are unfollowed, instead of `hours`.
+/// - `filter`: The games to notify (see `NotificationFilter`), all games by default.
+/// - `queue`: The queues whose games are notified (see `QueueFilter`), all queues by default.
 ///
 /// # Returns:
 /// - `Result<(), Error>`: Returns an empty result if successful, or an error if the process fails.
 ///
 /// # Example:
-/// This command can be triggered in Discord using the `/followgames` command, and requires the user to input their game name, tagline, and duration for following:
+/// This command can be triggered in Discord using the `/followgames` command, and requires the user to input the Riot ID of the player:
 ///
 /// ```rust
-/// /followgames region: NA
+/// /followgames region: NA hours: 2
+/// /followgames region: NA permanent: True filter: Ranked games only
 /// ```
 ///
 /// # Flow:
-/// 1. The command opens a modal where the user inputs their game name, tag line, and duration to follow the games.
-/// 2. It validates the input and ensures that the follow duration is between 1 and 48 hours.
+/// 1. It checks that exactly one of `hours` and `permanent` is given, and that `hours` is within the limit.
+/// 2. The command opens a modal where the user inputs the Riot ID (`Name#TAG`). Invalid values re-open the modal
+///    pre-filled with the previous answers (see `execute_modal_with_retry`).
 /// 3. The Riot API is queried to retrieve the player's PUUID and Summoner ID.
 /// 4. The player's data is stored in the database, allowing the bot to follow their games for the specified duration.
 ///
 /// # Notes:
-/// - The command opens a modal using `FollowGamesModal::execute` to collect the player's game name and follow duration.
-/// - If the follow duration is invalid or the player is not found, an error message is sent to the Discord channel.
+/// - The command opens a modal using `execute_modal_with_retry` to collect the player's Riot ID.
+/// - If the player is not found, an error message is sent to the Discord channel.
 /// - The player's PUUID and Summoner ID are fetched from the Riot API and stored in the MongoDB database, enabling game tracking.
 #[poise::command(slash_command)]
 pub async fn followgames(
     ctx: poise::ApplicationContext<'_, Data, Error>,
     #[description = "Select your region"] region: Region,
+    #[description = "Number of hours to follow"]
+    #[min = 1]
+    hours: Option<u32>,
+    #[description = "Follow until unfollowed, instead of a number of hours"] permanent: Option<
+        bool,
+    >,
+    #[description = "Only notify some games (all games by default)"] filter: Option<
+        NotificationFilter,
+    >,
+    #[description = "Only notify the games of some queues (all queues by default)"] queue: Option<
+        QueueFilter,
+    >,
 ) -> Result<(), Error> {
-    let modal_data = match FollowGamesModal::execute(ctx).await {
-        Ok(Some(data)) => data,
-        Ok(None) => {
-            let error_message = "Modal data not found.";
-            let reply = ctx.send(create_embed_error(&error_message)).await?;
-            schedule_message_deletion(reply, ctx).await?;
-            return Ok(());
-        }
-        Err(_) => {
-            let error_message = "Failed to retrieve modal data.";
-            let reply = ctx.send(create_embed_error(&error_message)).await?;
-            schedule_message_deletion(reply, ctx).await?;
-            return Ok(());
-        }
-    };
-
-    let time_followed = match modal_data.time_followed.trim().parse::<u32>() {
-        Ok(value) => value,
-        Err(_) => {
-            let error_message = "Invalid time format. Please enter a valid number of hours.";
-            let reply = ctx.send(create_embed_error(&error_message)).await?;
-            schedule_message_deletion(reply, ctx).await?;
-            return Ok(());
-        }
-    };
-
-    if time_followed == 0 || time_followed > 48 {
-        let error_message = "Please enter a time between 1 and 48 hours.".to_string();
+    if let Err(error_message) = hours.map_or(Ok(()), check_follow_hours) {
         let reply = ctx.send(create_embed_error(&error_message)).await?;
         schedule_message_deletion(reply, ctx).await?;
         return Ok(());
     }
+    let Some(time_end_follow) = follow_end(hours, permanent.unwrap_or(false)) else {
+        let error_message = "Choose either a number of hours or a permanent follow.";
+        let reply = ctx.send(create_embed_error(error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    };
+    let Some(riot_id) = execute_modal_with_retry(ctx, |data: &FollowGamesModal| {
+        data.riot_id.parse::<RiotId>().map_err(|e| e.to_string())
+    })
+    .await?
+    else {
+        return Ok(());
+    };
 
+    let fetching = send_fetching_message(ctx).await?;
     let client = reqwest::Client::new();
-    let game_name_space = modal_data.game_name.replace(" ", "%20");
     let region_str = region_to_string(&region);
-    let puuid = match get_puuid(
-        &client,
-        &game_name_space,
-        &modal_data.tag_line,
-        &ctx.data().riot_api_key,
-    )
-    .await
-    {
+    let puuid = match get_puuid(&client, &riot_id, &ctx.data().riot_api_key).await {
         Ok(puuid) => puuid,
         Err(e) => {
-            let error_message = format!("{}", e);
-            let reply = ctx.send(create_embed_error(&error_message)).await?;
-            schedule_message_deletion(reply, ctx).await?;
+            edit_and_schedule_deletion(fetching, ctx, create_embed_coded_error("", &e)).await?;
             return Ok(());
         }
     };
@@ -103,35 +100,43 @@ pub async fn followgames(
         match get_summoner_id(&client, &region_str, &puuid, &ctx.data().riot_api_key).await {
             Ok(id) => id,
             Err(e) => {
-                let error_message = format!("{}", e);
-                let reply = ctx.send(create_embed_error(&error_message)).await?;
-                schedule_message_deletion(reply, ctx).await?;
+                edit_and_schedule_deletion(fetching, ctx, create_embed_coded_error("", &e)).await?;
                 return Ok(());
             }
         };
-    let match_id = get_matchs_id(&client, &puuid, &ctx.data().riot_api_key, 1)
-        .await
-        .unwrap()[0]
-        .to_string();
-    let time_end_follow = (Utc::now() + Duration::hours(time_followed as i64))
-        .timestamp()
-        .to_string();
+    let match_id = match get_matchs_id(&client, &puuid, &ctx.data().riot_api_key, 1).await {
+        Ok(ids) => ids.first().cloned().unwrap_or_default(),
+        Err(e) => {
+            edit_and_schedule_deletion(
+                fetching,
+                ctx,
+                create_embed_coded_error("Error fetching match IDs", &e),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
     eprint!("match_id: {:?}", match_id);
     let mongo_client = &ctx.data().mongo_client;
     let collection = mongo_client
         .database("stat-summoner")
         .collection::<SummonerFollowedData>("follower_summoner");
 
-    check_and_add_in_db(
-        collection,
-        ctx,
-        modal_data,
-        region_str,
+    let new_followed_summoner = SummonerFollowedData {
         puuid,
         summoner_id,
-        match_id,
-        time_end_follow,
-    )
-    .await?;
+        name: riot_id.name,
+        tag: riot_id.tag,
+        region: region_str,
+        last_match_id: match_id,
+        time_end_follow: time_end_follow.to_string(),
+        channel_id: ctx.channel_id().get(),
+        guild_id: ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string(),
+        embed_layout: None,
+        notification_filter: filter.unwrap_or_default(),
+        queue_filter: queue.unwrap_or_default(),
+    };
+
+    check_and_add_in_db(collection, ctx, fetching, new_followed_summoner).await?;
     Ok(())
 }
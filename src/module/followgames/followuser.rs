@@ -0,0 +1,119 @@
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, edit_and_schedule_deletion,
+    schedule_message_deletion, send_fetching_message,
+};
+use crate::models::data::{
+    Data, LinkedAccountData, NotificationFilter, QueueFilter, SummonerFollowedData,
+};
+use crate::models::error::Error;
+use crate::module::followgames::utils::{check_and_add_in_db, check_follow_hours};
+use crate::module::linkaccount::utils::get_linked_account;
+use crate::riot_api::get_matchs_id;
+use chrono::{Duration, Utc};
+use poise::serenity_prelude as serenity;
+
+/// Starts following the games of a Discord member who linked their League of Legends account.
+///
+/// This slash command is a variant of `/followgames` that takes a Discord member instead of a Riot ID.
+/// The member's account is resolved from the `linked_accounts` collection, so no modal is opened.
+///
+/// # Parameters:
+/// - `ctx`: The `poise::ApplicationContext` in which the command is executed.
+/// - `member`: The Discord user to follow. They must have linked their account with `/linkaccount`.
+/// - `hours`: The follow duration, between 1 hour and `MAX_FOLLOW_HOURS` (48 by default).
+/// - `filter`: The games to notify (see `NotificationFilter`), all games by default.
+/// - `queue`: The queues whose games are notified (see `QueueFilter`), all queues by default.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an empty result if successful, or an error if the process fails.
+///
+/// # Example:
+/// ```rust
+/// /followuser member: @Faker hours: 2
+/// ```
+///
+/// # Notes:
+/// - If the member has not linked an account, an error message explains how to do so.
+/// - The follow is stored exactly like a `/followgames` follow, using `check_and_add_in_db`.
+#[poise::command(slash_command)]
+pub async fn followuser(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Select the Discord member to follow"] member: serenity::User,
+    #[description = "Number of hours to follow"]
+    #[min = 1]
+    hours: u32,
+    #[description = "Only notify some games (all games by default)"] filter: Option<
+        NotificationFilter,
+    >,
+    #[description = "Only notify the games of some queues (all queues by default)"] queue: Option<
+        QueueFilter,
+    >,
+) -> Result<(), Error> {
+    if let Err(error_message) = check_follow_hours(hours) {
+        let reply = ctx.send(create_embed_error(&error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+    let fetching = send_fetching_message(ctx).await?;
+    let mongo_client = &ctx.data().mongo_client;
+    let collection_linked = mongo_client
+        .database("stat-summoner")
+        .collection::<LinkedAccountData>("linked_accounts");
+
+    let linked_account = match get_linked_account(&collection_linked, member.id.get()).await {
+        Ok(Some(account)) => account,
+        Ok(None) => {
+            let error_message = format!(
+                "{} has not linked a League of Legends account yet. They can do it with `/linkaccount`.",
+                member.name
+            );
+            edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message)).await?;
+            return Ok(());
+        }
+        Err(e) => {
+            let reply =
+                create_embed_coded_error("Error collecting informations from MongoDB", &e.into());
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+
+    let client = reqwest::Client::new();
+    let match_id =
+        match get_matchs_id(&client, &linked_account.puuid, &ctx.data().riot_api_key, 1).await {
+            Ok(ids) => ids.first().cloned().unwrap_or_default(),
+            Err(e) => {
+                edit_and_schedule_deletion(
+                    fetching,
+                    ctx,
+                    create_embed_coded_error("Error fetching match IDs", &e),
+                )
+                .await?;
+                return Ok(());
+            }
+        };
+    let time_end_follow = (Utc::now() + Duration::hours(hours as i64))
+        .timestamp()
+        .to_string();
+
+    let collection = mongo_client
+        .database("stat-summoner")
+        .collection::<SummonerFollowedData>("follower_summoner");
+    let new_followed_summoner = SummonerFollowedData {
+        puuid: linked_account.puuid,
+        summoner_id: linked_account.summoner_id,
+        name: linked_account.name,
+        tag: linked_account.tag,
+        region: linked_account.region,
+        last_match_id: match_id,
+        time_end_follow,
+        channel_id: ctx.channel_id().get(),
+        guild_id: ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string(),
+        embed_layout: None,
+        notification_filter: filter.unwrap_or_default(),
+        queue_filter: queue.unwrap_or_default(),
+    };
+
+    check_and_add_in_db(collection, ctx, fetching, new_followed_summoner).await?;
+    Ok(())
+}
@@ -1,17 +1,18 @@
-/// 🛠 **Module commands**: Contains all bot commands for the Discord bot.
+/// 🛠 **Module followgames**: Contains all bot commands for the Discord bot.
 ///
 /// This module organizes the different commands used by the bot. Each command is stored in its own file
-/// within the `commands` directory. These commands are registered and used through the bot's interaction
+/// within the `module/followgames` directory. These commands are registered and used through the bot's interaction
 /// with Discord via the Poise framework.
 ///
 /// # Files in this module:
 /// - `followgames.rs`: The command for following a player's games and tracking their match data for a specified period.
+/// - `followuser.rs`: A variant of `followgames` that follows a Discord member through their linked League of Legends account.
 ///
 /// # Example:
 /// To use commands in this module, ensure they are registered in the bot's main framework setup:
 ///
 /// ```rust
-/// use commands::followgames::followgames;
+/// use module::followgames::followgames::followgames;
 ///
 /// #[shuttle_runtime::main]
 /// async fn main() {
@@ -23,8 +24,9 @@
 ///         .build();
 /// }
 /// ```
-/// The `followgames` command allows users to track the games of a summoner in real time for a period between 1 and 48 hours.
+/// The `followgames` command allows users to track the games of a summoner in real time for a period between 1 hour and `MAX_FOLLOW_HOURS` (48 by default).
 ///
-/// As more commands are added, they will be included here and imported into the main bot setup.
+/// As more commands are added, they will be included here and added to `module::all_commands`.
 pub mod followgames;
+pub mod followuser;
 pub mod utils;
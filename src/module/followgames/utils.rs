@@ -1,138 +1,378 @@
-use crate::embed::schedule_message_deletion;
-use crate::embed::{create_embed_error, create_embed_sucess};
-use crate::models::data::{Data, SummonerFollowedData};
+use crate::config::config;
+use crate::embed::edit_and_schedule_deletion;
+use crate::embed::{create_embed_coded_error, create_embed_error, create_embed_sucess};
+use crate::models::data::{BlacklistedSummonerData, Data, FollowAuditEvent, SummonerFollowedData};
 use crate::models::error::Error;
-use crate::models::modal::FollowGamesModal;
-use mongodb::bson::doc;
+use crate::module::auditlog::utils::record_follow_event;
+use crate::module::blacklist::utils::find_blacklist_entry;
+use crate::module::optout::utils::is_opted_out;
+use crate::utils::discord_timestamp;
+use chrono::{Duration, Utc};
+use futures::TryStreamExt;
+use mongodb::bson::{self, doc};
+use poise::ReplyHandle;
+
+/// `time_end_follow` of a permanent follow: it never expires and lasts until the summoner is unfollowed.
+pub const PERMANENT_FOLLOW_END: i64 = i64::MAX;
 
 /// ⚙️ **Function**: Adds a summoner to the database for game follow-up if they are not already being followed.
 ///
 /// This asynchronous function checks if a summoner is already being followed by querying the MongoDB collection using their `puuid`.
-/// If they are not followed yet, it adds the summoner's data to the database and returns a success message. If the summoner is already followed, an error message is returned.
+/// If they are not followed yet in the current guild, it adds the summoner's data to the database and returns a success message.
+/// If the summoner is already followed in this guild, their follow end time is updated instead.
 ///
 /// # Parameters:
 /// - `collection`: A MongoDB collection (`mongodb::Collection<SummonerFollowedData>`) where the summoner's follow data is stored.
 /// - `ctx`: The `poise::ApplicationContext` provides the context for the Discord interaction, including the ability to send responses.
-/// - `modal_data`: A `FollowGamesModal` struct containing the user's input data from the modal (game name, tag line, etc.).
-/// - `region_str`: A string representing the summoner's region (e.g., "NA", "EUW").
-/// - `puuid`: A string containing the summoner's unique PUUID (player unique identifier from Riot's API).
-/// - `guild_id`: An integer representing the ID of the Discord guild.
-/// - `summoner_id`: A string containing the summoner's unique Summoner ID from Riot's API.
-/// - `match_id`: A string representing the summoner's latest match ID.
-/// - `time_end_follow`: A string representing the timestamp for when the follow period ends.
+/// - `fetching`: The "Fetching data…" placeholder of the command (see `send_fetching_message`), edited into the result message.
+/// - `new_followed_summoner`: A `SummonerFollowedData` struct containing the summoner's data (PUUID, summoner ID, Riot ID, region, latest match ID, follow end time, channel and guild).
 ///
 /// # Returns:
 /// - `Result<(), Error>`: Returns an empty result if the operation is successful, or an error if any part of the process fails.
 ///
 /// # Example:
-/// This function is used internally to add a summoner to the follow list after a successful interaction with the `/followgames` command:
+/// This function is used internally to add a summoner to the follow list after a successful interaction with the `/followgames` or `/followuser` commands:
 ///
 /// ```rust
-/// check_and_add_in_db(collection, ctx, modal_data, region_str, puuid, summoner_id, match_id, time_end_follow).await?;
+/// check_and_add_in_db(collection, ctx, fetching, new_followed_summoner).await?;
 /// ```
 ///
 /// # Notes:
-/// - If the user is already being followed, an error message is sent to the Discord channel using `create_embed_error`.
+/// - The follow is saved by `save_follow`, and its outcome is turned into a message.
+/// - If the summoner has opted out of Stat Summoner (see `is_opted_out`), the follow is refused with an error message.
+/// - If the summoner is blacklisted in the guild or globally (see `find_blacklist_entry`), the follow is refused with an error message.
+/// - If the guild already follows `MAX_FOLLOWS_PER_GUILD` summoners, new follows are refused with an error message.
+/// - If the user is already being followed in the guild, the follow end time and filters are updated and a success message is sent using `create_embed_sucess`.
 /// - If the user is successfully added to the database, a success message is sent using `create_embed_sucess`.
-/// - The function makes sure to handle errors from both MongoDB operations and Discord message sending by logging appropriate error messages.
+/// - The success messages tell when the follow ends (see `describe_follow_end`).
+/// - Database errors are shown with `create_embed_coded_error`.
 pub async fn check_and_add_in_db(
     collection: mongodb::Collection<SummonerFollowedData>,
     ctx: poise::ApplicationContext<'_, Data, Error>,
-    modal_data: FollowGamesModal,
-    region_str: String,
-    puuid: String,
-    summoner_id: String,
-    match_id: String,
-    time_end_follow: String,
+    fetching: ReplyHandle<'_>,
+    new_followed_summoner: SummonerFollowedData,
 ) -> Result<(), Error> {
-    match collection.find_one(doc! { "puuid": puuid.clone() }).await {
-        Ok(Some(_followed_summoner)) => {
-            let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
-            if _followed_summoner.guild_id == guild_id {
-                match collection
-                    .update_one(
-                        doc! { "puuid": puuid.clone(), "guild_id": guild_id },
-                        doc! { "$set": { "time_end_follow": time_end_follow.clone() } },
-                    )
-                    .await
-                {
-                    Ok(_) => {
-                        let success_message = "Success, tracking time has been updated.";
-                        let reply = ctx.send(create_embed_sucess(&success_message)).await?;
-                        schedule_message_deletion(reply, ctx).await?;
-                        return Ok(());
-                    }
-                    Err(_) => {
-                        let error_message = "Error, failed to update tracking time.";
-                        let reply = ctx.send(create_embed_error(&error_message)).await?;
-                        schedule_message_deletion(reply, ctx).await?;
-                        return Ok(());
-                    }
-                }
-            } else {
-                let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
-                let channel_id = ctx.channel_id().get();
-                let new_followed_summoner = SummonerFollowedData {
-                    puuid: puuid.clone(),
-                    summoner_id: summoner_id.clone(),
-                    name: modal_data.game_name.clone(),
-                    tag: modal_data.tag_line.clone(),
-                    region: region_str.to_string(),
-                    last_match_id: match_id.clone(),
-                    time_end_follow: time_end_follow.clone(),
-                    channel_id: channel_id,
-                    guild_id: guild_id,
-                };
-                match collection.insert_one(new_followed_summoner).await {
-                    Ok(_) => {
-                        let sucess_message = "User has been followed.";
-                        let reply = ctx.send(create_embed_sucess(&sucess_message)).await?;
-                        schedule_message_deletion(reply, ctx).await?;
-                        return Ok(());
-                    }
-                    Err(e) => {
-                        let error_message = format!("Error inserting user to MongoDB: {}", e);
-                        let reply = ctx.send(create_embed_error(&error_message)).await?;
-                        schedule_message_deletion(reply, ctx).await?;
-                        return Ok(());
-                    }
+    let reply = match save_follow(
+        &collection,
+        &ctx.data().mongo_client,
+        &new_followed_summoner,
+        ctx.author().id.get(),
+    )
+    .await
+    {
+        Ok(FollowOutcome::OptedOut) => create_embed_error(
+            "This summoner has opted out of Stat Summoner and cannot be followed.",
+        ),
+        Ok(FollowOutcome::Blacklisted { global: true }) => create_embed_error(
+            "This summoner cannot be followed: they have been blacklisted by the bot owners.",
+        ),
+        Ok(FollowOutcome::Blacklisted { global: false }) => create_embed_error(
+            "This summoner cannot be followed: they have been blacklisted by the administrators of this server.",
+        ),
+        Ok(FollowOutcome::GuildLimitReached { limit }) => create_embed_error(&format!(
+            "This server already follows {} summoners, the maximum. Unfollow a summoner with `/unfollow` or the buttons of `/whoisfollowed` before following another one.",
+            limit
+        )),
+        Ok(FollowOutcome::Updated) => create_embed_sucess(&format!(
+            "Success, tracking time has been updated: {}.",
+            describe_follow_end(&new_followed_summoner.time_end_follow)
+        )),
+        Ok(FollowOutcome::Created) => create_embed_sucess(&format!(
+            "User has been followed: {}.",
+            describe_follow_end(&new_followed_summoner.time_end_follow)
+        )),
+        Err(e) => create_embed_coded_error("Error saving the follow in MongoDB", &e),
+    };
+    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Describes when a follow ends, with Discord's timestamp markup.
+///
+/// # Parameters:
+/// - `time_end_follow`: The `time_end_follow` of the follow, a UNIX timestamp in seconds.
+///
+/// # Returns:
+/// - `String`: e.g. `until <t:1625000000:f> (<t:1625000000:R>)`, or `permanently` for a permanent follow.
+pub fn describe_follow_end(time_end_follow: &str) -> String {
+    match time_end_follow.parse::<i64>() {
+        Ok(PERMANENT_FOLLOW_END) => "permanently".to_string(),
+        Ok(end) => format!(
+            "until {} ({})",
+            discord_timestamp(end, 'f'),
+            discord_timestamp(end, 'R')
+        ),
+        Err(_) => "for an unknown time".to_string(),
+    }
+}
+
+/// Result of `save_follow`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FollowOutcome {
+    /// The summoner opted out of Stat Summoner, nothing was saved.
+    OptedOut,
+    /// The summoner is blacklisted by the bot owners (`global`) or by the guild, nothing was saved.
+    Blacklisted { global: bool },
+    /// The guild already follows `limit` summoners (`MAX_FOLLOWS_PER_GUILD`), the new follow was not saved.
+    GuildLimitReached { limit: u64 },
+    /// The summoner was already followed in the guild: the follow end time and filters were updated.
+    Updated,
+    /// The summoner is now followed in the guild.
+    Created,
+}
+
+/// ⚙️ **Function**: Saves a follow, or updates it if the summoner is already followed in the guild.
+///
+/// # Parameters:
+/// - `collection`: The MongoDB collection of the follows.
+/// - `mongo_client`: The MongoDB client, used for the opt-outs, the blacklist and the audit log.
+/// - `new_followed_summoner`: The follow to save.
+/// - `actor_id`: The ID of the Discord user creating the follow, recorded in the audit log.
+///
+/// # Returns:
+/// - `Result<FollowOutcome, Error>`: What was done, or an error if the database fails.
+///
+/// # Example:
+/// ```rust
+/// let outcome = save_follow(&collection, &mongo_client, &new_followed_summoner, ctx.author().id.get()).await?;
+/// ```
+///
+/// # Notes:
+/// - A summoner that is not followed yet is only added while the guild follows fewer than `MAX_FOLLOWS_PER_GUILD`
+///   summoners (expired follows do not count). Updating an existing follow is always allowed.
+/// - The active follows are counted again once the follow is inserted: if concurrent follows went over the limit, the
+///   new follow is deleted and `GuildLimitReached` is returned, so the limit is never exceeded.
+/// - Creations and follow time changes are recorded in the audit log (see `record_follow_event`).
+pub async fn save_follow(
+    collection: &mongodb::Collection<SummonerFollowedData>,
+    mongo_client: &mongodb::Client,
+    new_followed_summoner: &SummonerFollowedData,
+    actor_id: u64,
+) -> Result<FollowOutcome, Error> {
+    let puuid = &new_followed_summoner.puuid;
+    let guild_id = &new_followed_summoner.guild_id;
+    let time_end_follow = &new_followed_summoner.time_end_follow;
+
+    if is_opted_out(mongo_client, puuid).await? {
+        return Ok(FollowOutcome::OptedOut);
+    }
+    let collection_blacklist = mongo_client
+        .database("stat-summoner")
+        .collection::<BlacklistedSummonerData>("blacklisted_summoners");
+    if let Some(entry) = find_blacklist_entry(&collection_blacklist, puuid, guild_id).await? {
+        return Ok(FollowOutcome::Blacklisted {
+            global: entry.guild_id.is_none(),
+        });
+    }
+
+    let filter = doc! { "puuid": puuid, "guild_id": guild_id };
+    match collection.find_one(filter.clone()).await? {
+        Some(followed_summoner) => {
+            collection
+                .update_one(
+                    filter,
+                    doc! { "$set": {
+                        "time_end_follow": time_end_follow,
+                        "notification_filter": bson::to_bson(&new_followed_summoner.notification_filter)?,
+                        "queue_filter": bson::to_bson(&new_followed_summoner.queue_filter)?,
+                    } },
+                )
+                .await?;
+            let previous_end = followed_summoner.time_end_follow.parse::<i64>();
+            let event = match (previous_end, time_end_follow.parse::<i64>()) {
+                (Ok(previous_end), Ok(new_end)) if new_end > previous_end => {
+                    FollowAuditEvent::Extended
                 }
-            }
-        }
-        Ok(None) => {
-            let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
-            let channel_id = ctx.channel_id().get();
-            let new_followed_summoner = SummonerFollowedData {
-                puuid: puuid.clone(),
-                summoner_id: summoner_id.clone(),
-                name: modal_data.game_name.clone(),
-                tag: modal_data.tag_line.clone(),
-                region: region_str.to_string(),
-                last_match_id: match_id.clone(),
-                time_end_follow: time_end_follow.clone(),
-                channel_id: channel_id,
-                guild_id: guild_id,
+                _ => FollowAuditEvent::Updated,
             };
-            match collection.insert_one(new_followed_summoner).await {
-                Ok(_) => {
-                    let sucess_message = "User has been followed.";
-                    let reply = ctx.send(create_embed_sucess(&sucess_message)).await?;
-                    schedule_message_deletion(reply, ctx).await?;
-                    return Ok(());
-                }
-                Err(e) => {
-                    let error_message = format!("Error inserting user to MongoDB: {}", e);
-                    let reply = ctx.send(create_embed_error(&error_message)).await?;
-                    schedule_message_deletion(reply, ctx).await?;
-                    return Ok(());
-                }
-            }
+            record_follow_event(mongo_client, event, new_followed_summoner, Some(actor_id)).await;
+            Ok(FollowOutcome::Updated)
         }
-        Err(e) => {
-            let error_message = format!("Error collecting informations from MongoDB: {}", e);
-            let reply = ctx.send(create_embed_error(&error_message)).await?;
-            schedule_message_deletion(reply, ctx).await?;
-            return Ok(());
+        None => {
+            // Limite par serveur : seules les nouvelles suivies comptent
+            let limit = config().max_follows_per_guild;
+            if count_active_follows(collection, guild_id).await? >= limit {
+                return Ok(FollowOutcome::GuildLimitReached { limit });
+            }
+            // Deux suivis simultanés peuvent passer le contrôle : la suivie est recomptée après l'insertion et annulée
+            // si la limite est dépassée
+            let inserted_id = collection
+                .insert_one(new_followed_summoner)
+                .await?
+                .inserted_id;
+            if count_active_follows(collection, guild_id).await? > limit {
+                collection.delete_one(doc! { "_id": inserted_id }).await?;
+                return Ok(FollowOutcome::GuildLimitReached { limit });
+            }
+            record_follow_event(
+                mongo_client,
+                FollowAuditEvent::Created,
+                new_followed_summoner,
+                Some(actor_id),
+            )
+            .await;
+            Ok(FollowOutcome::Created)
         }
     }
 }
+
+/// ⚙️ **Function**: Counts the follows of a guild that have not ended yet.
+///
+/// # Parameters:
+/// - `collection`: The MongoDB collection of the follows.
+/// - `guild_id`: The ID of the Discord guild.
+///
+/// # Returns:
+/// - `Result<u64, Error>`: The number of active follows (permanent ones included), or an error if the database fails.
+///
+/// # Notes:
+/// - `time_end_follow` is stored as a string, so the ends are compared here rather than in the query.
+async fn count_active_follows(
+    collection: &mongodb::Collection<SummonerFollowedData>,
+    guild_id: &str,
+) -> Result<u64, Error> {
+    let now = Utc::now().timestamp();
+    let follows: Vec<SummonerFollowedData> = collection
+        .find(doc! { "guild_id": guild_id })
+        .await?
+        .try_collect()
+        .await?;
+    Ok(follows
+        .iter()
+        .filter(|follow| follow.time_end_follow.parse::<i64>().unwrap_or(0) > now)
+        .count() as u64)
+}
+
+/// ⚙️ **Function**: Extends the follow of a summoner in a guild by a number of hours.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the Discord guild.
+/// - `puuid`: The PUUID of the followed summoner.
+/// - `hours`: The number of hours to add to the follow.
+/// - `actor_id`: The ID of the Discord user extending the follow, recorded in the audit log.
+///
+/// # Returns:
+/// - `Result<Option<(i64, bool)>, Error>`: The new end of the follow and whether it was capped to `MAX_FOLLOW_HOURS`
+///   from now, or `None` if the summoner is not followed in the guild anymore or is followed permanently.
+///
+/// # Example:
+/// ```rust
+/// if let Some((end, capped)) = extend_follow(&mongo_client, &guild_id, &puuid, 6, press.user.id.get()).await? {
+///     // ...
+/// }
+/// ```
+///
+/// # Notes:
+/// - The hours are added to the current end of the follow, or to now if it has already ended, and the end is capped to
+///   `MAX_FOLLOW_HOURS` from now like with `/followgames` (see `clamp_follow_end`).
+/// - The end is never moved earlier: a follow saved beyond the current cap, e.g. before `MAX_FOLLOW_HOURS` was
+///   lowered, keeps its end and is reported as capped.
+/// - The extension is recorded in the audit log as `Extended`, unless the end did not change.
+pub async fn extend_follow(
+    mongo_client: &mongodb::Client,
+    guild_id: &str,
+    puuid: &str,
+    hours: i64,
+    actor_id: u64,
+) -> Result<Option<(i64, bool)>, Error> {
+    let collection = mongo_client
+        .database("stat-summoner")
+        .collection::<SummonerFollowedData>("follower_summoner");
+    let filter = doc! { "puuid": puuid, "guild_id": guild_id };
+    let Some(mut followed_summoner) = collection.find_one(filter.clone()).await? else {
+        return Ok(None);
+    };
+    let current_end = followed_summoner
+        .time_end_follow
+        .parse::<i64>()
+        .unwrap_or(0);
+    if current_end == PERMANENT_FOLLOW_END {
+        return Ok(None);
+    }
+    let requested_end = current_end.max(Utc::now().timestamp()) + hours * 3600;
+    let new_end = clamp_follow_end(requested_end).max(current_end);
+    let capped = new_end < requested_end;
+    if new_end == current_end {
+        return Ok(Some((new_end, capped)));
+    }
+    followed_summoner.time_end_follow = new_end.to_string();
+    collection
+        .update_one(
+            filter,
+            doc! { "$set": { "time_end_follow": &followed_summoner.time_end_follow } },
+        )
+        .await?;
+    record_follow_event(
+        mongo_client,
+        FollowAuditEvent::Extended,
+        &followed_summoner,
+        Some(actor_id),
+    )
+    .await;
+    Ok(Some((new_end, capped)))
+}
+
+/// ⚙️ **Function**: Checks a follow duration against the longest follow of the configuration (`MAX_FOLLOW_HOURS`).
+///
+/// # Parameters:
+/// - `hours`: The number of hours asked for.
+///
+/// # Returns:
+/// - `Result<(), String>`: The error message to display when the duration is too long.
+///
+/// # Notes:
+/// - The limit is checked here rather than with `#[max]`, which would be fixed when the commands are registered.
+pub fn check_follow_hours(hours: u32) -> Result<(), String> {
+    let max_follow_hours = config().max_follow_hours;
+    if hours > max_follow_hours {
+        return Err(format!(
+            "A follow can last at most {} hours. Use `permanent` with `/followgames` to follow a player longer.",
+            max_follow_hours
+        ));
+    }
+    Ok(())
+}
+
+/// ⚙️ **Function**: Caps the end of a follow to the longest follow of the configuration (`MAX_FOLLOW_HOURS`).
+///
+/// # Parameters:
+/// - `end`: The UNIX timestamp of the end of the follow.
+///
+/// # Returns:
+/// - `i64`: `end`, or `MAX_FOLLOW_HOURS` from now if it is later. Permanent follows (`PERMANENT_FOLLOW_END`) are kept.
+///
+/// # Example:
+/// ```rust
+/// let end = clamp_follow_end(end_from_backup);
+/// ```
+pub fn clamp_follow_end(end: i64) -> i64 {
+    if end == PERMANENT_FOLLOW_END {
+        return end;
+    }
+    let latest_end = (Utc::now() + Duration::hours(config().max_follow_hours as i64)).timestamp();
+    end.min(latest_end)
+}
+
+/// ⚙️ **Function**: Computes the end of a follow from the duration options of `/followgames`.
+///
+/// # Parameters:
+/// - `hours`: The number of hours to follow, checked with `check_follow_hours`.
+/// - `permanent`: Whether the follow lasts until the summoner is unfollowed.
+///
+/// # Returns:
+/// - `Option<i64>`: The UNIX timestamp of the end of the follow (`PERMANENT_FOLLOW_END` for a permanent one), or `None`
+///   when neither or both options were given.
+///
+/// # Example:
+/// ```rust
+/// let end = follow_end(Some(2), false); // In 2 hours
+/// assert_eq!(follow_end(None, true), Some(PERMANENT_FOLLOW_END));
+/// ```
+pub fn follow_end(hours: Option<u32>, permanent: bool) -> Option<i64> {
+    match (hours, permanent) {
+        (Some(hours), false) => Some((Utc::now() + Duration::hours(hours as i64)).timestamp()),
+        (None, true) => Some(PERMANENT_FOLLOW_END),
+        _ => None,
+    }
+}
@@ -0,0 +1,171 @@
+use crate::config::deletion_notice;
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, create_embed_sucess, edit_and_schedule_deletion,
+    schedule_message_deletion, send_fetching_message, share_image_button,
+};
+use crate::models::data::{Data, EmojiId, RankGoalData};
+use crate::models::error::Error;
+use crate::models::rank::{Division, Tier};
+use crate::module::goal::utils::{
+    describe_goal_progress, fetch_goal_rank, goal_label, rank_goals_collection,
+};
+use crate::module::linkaccount::utils::get_linked_account;
+use crate::module::myaccounts::utils::{account_label, linked_accounts_collection};
+use crate::module::settings::utils::get_guild_theme;
+use crate::utils::current_week;
+use mongodb::bson::doc;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use poise::CreateReply;
+
+/// Sets and follows a Solo/Duo rank goal for your primary linked account.
+///
+/// # Example:
+/// ```rust
+/// /goal set tier: Platinum division: IV
+/// /goal status
+/// /goal clear
+/// ```
+///
+/// # Notes:
+/// - A recap of the progress is sent in direct message every Monday.
+#[poise::command(slash_command, subcommands("set", "status", "clear"))]
+pub async fn goal(_ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Sets the Solo/Duo rank you want to reach with your primary linked account.
+#[poise::command(slash_command)]
+pub async fn set(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Tier to reach"] tier: Tier,
+    #[description = "Division to reach (ignored from Master)"] division: Option<Division>,
+) -> Result<(), Error> {
+    let mongo_client = &ctx.data().mongo_client;
+    let discord_id = ctx.author().id.get();
+    let account =
+        match get_linked_account(&linked_accounts_collection(mongo_client), discord_id).await {
+            Ok(Some(account)) => account,
+            Ok(None) => {
+                let error_message =
+                    "You need to link your League of Legends account with `/linkaccount` first.";
+                let reply = ctx.send(create_embed_error(error_message)).await?;
+                schedule_message_deletion(reply, ctx).await?;
+                return Ok(());
+            }
+            Err(e) => {
+                let reply = ctx
+                    .send(create_embed_coded_error(
+                        "Error collecting informations from MongoDB",
+                        &e.into(),
+                    ))
+                    .await?;
+                schedule_message_deletion(reply, ctx).await?;
+                return Ok(());
+            }
+        };
+    let division = if tier.is_apex() {
+        ""
+    } else {
+        division.unwrap_or(Division::IV).key()
+    };
+    let goal = RankGoalData {
+        discord_id,
+        puuid: account.puuid.clone(),
+        summoner_id: account.summoner_id.clone(),
+        region: account.region.clone(),
+        tier: tier.key().to_string(),
+        division: division.to_string(),
+        set_at: chrono::Utc::now().timestamp(),
+        recap_week: Some(current_week()),
+    };
+    let sucess_message = format!(
+        "Your goal is now **{}** on {}. Follow it with `/goal status`.",
+        goal_label(&goal),
+        account_label(&account)
+    );
+    if let Err(e) = rank_goals_collection(mongo_client)
+        .replace_one(doc! { "discord_id": discord_id as i64 }, goal)
+        .upsert(true)
+        .await
+    {
+        let reply = ctx
+            .send(create_embed_coded_error("Error saving the goal", &e.into()))
+            .await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+    let reply = ctx.send(create_embed_sucess(&sucess_message)).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Shows your progress towards your rank goal.
+#[poise::command(slash_command)]
+pub async fn status(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let mongo_client = &ctx.data().mongo_client;
+    let goal = match rank_goals_collection(mongo_client)
+        .find_one(doc! { "discord_id": ctx.author().id.get() as i64 })
+        .await
+    {
+        Ok(Some(goal)) => goal,
+        Ok(None) => {
+            let error_message = "You have no rank goal yet. Set one with `/goal set`.";
+            edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+            return Ok(());
+        }
+        Err(e) => {
+            let reply =
+                create_embed_coded_error("Error collecting informations from MongoDB", &e.into());
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    let solo_rank = match fetch_goal_rank(mongo_client, &goal, &ctx.data().riot_api_key).await {
+        Ok(solo_rank) => solo_rank,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error fetching rank information", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    let collection_emoji = mongo_client
+        .database("stat-summoner")
+        .collection::<EmojiId>("emojis_id");
+    let progress =
+        describe_goal_progress(mongo_client, &goal, solo_rank.as_ref(), &collection_emoji).await?;
+    let theme = get_guild_theme(ctx).await;
+    let embed = CreateEmbed::default()
+        .title(format!("🎯 Road to {}", goal_label(&goal)))
+        .description(progress)
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+    let reply = CreateReply {
+        embeds: vec![embed],
+        components: Some(vec![share_image_button()]),
+        ..Default::default()
+    };
+    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+    Ok(())
+}
+
+/// Removes your rank goal and stops the weekly recaps.
+#[poise::command(slash_command)]
+pub async fn clear(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let reply = match rank_goals_collection(&ctx.data().mongo_client)
+        .delete_one(doc! { "discord_id": ctx.author().id.get() as i64 })
+        .await
+    {
+        Ok(result) if result.deleted_count > 0 => {
+            create_embed_sucess("Your rank goal has been removed.")
+        }
+        Ok(_) => create_embed_error("You have no rank goal yet."),
+        Err(e) => create_embed_coded_error("Error removing the goal", &e.into()),
+    };
+    let reply = ctx.send(reply).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
@@ -0,0 +1,18 @@
+/// 🛠 **Module goal**: Contains the Solo/Duo rank goals of the linked users.
+///
+/// This module lets a user with a linked account set the rank they want to reach, and follows their progress with the
+/// rank snapshots recorded by the bot: LP remaining, LP gained over the last week and games needed at their current
+/// winrate. A recap is sent to each user in direct message once a week.
+///
+/// # Files in this module:
+/// - `goal.rs`: The `/goal` command and its `set`, `status` and `clear` subcommands.
+/// - `utils.rs`: The `rank_goals` collection helpers, the progress computation and the weekly recap job.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`, and the weekly recap is started in `main.rs`:
+///
+/// ```rust
+/// post_goal_recaps(&mongo_client, &http, &riot_api_key).await?;
+/// ```
+pub mod goal;
+pub mod utils;
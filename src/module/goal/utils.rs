@@ -0,0 +1,320 @@
+use crate::embed::share_image_button;
+use crate::models::data::{EmbedTheme, EmojiId, RankGoalData};
+use crate::models::error::Error;
+use crate::module::lolstats::season::{
+    get_rank_snapshot_at, rank_score, record_rank_snapshot, TIERS,
+};
+use crate::module::lolstats::utils::LP_PER_WIN_ESTIMATE;
+use crate::riot_api::get_rank_info;
+use crate::sandbox::send_direct_message;
+use crate::utils::{current_week, get_emoji};
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+use poise::serenity_prelude::{CreateEmbed, CreateMessage, Http, UserId};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Period over which the LP gained is reported.
+const PROGRESS_PERIOD_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// ⚙️ **Function**: Computes a rank score on a single LP ladder, for the progress towards a goal.
+///
+/// # Parameters:
+/// - `tier`: The tier (e.g. `GOLD`).
+/// - `division`: The division (e.g. `II`), ignored for the apex tiers.
+/// - `league_points`: The LP of the player.
+///
+/// # Returns:
+/// - `Option<i64>`: The score, or `None` when the tier is unknown (e.g. `Unranked`).
+///
+/// # Notes:
+/// - Unlike `rank_score`, Master, Grandmaster and Challenger share the same base: their LP follow each other on a
+///   single ladder, so the LP earned above Master keep counting.
+fn ladder_score(tier: &str, division: &str, league_points: i64) -> Option<i64> {
+    if is_apex_tier(tier) {
+        rank_score("MASTER", "", league_points)
+    } else {
+        rank_score(tier, division, league_points)
+    }
+}
+
+/// Whether the tier has no divisions (Master and above).
+fn is_apex_tier(tier: &str) -> bool {
+    tier.eq_ignore_ascii_case("MASTER")
+        || tier.eq_ignore_ascii_case("GRANDMASTER")
+        || tier.eq_ignore_ascii_case("CHALLENGER")
+}
+
+/// Position of the tier in `TIERS`, `None` when the tier is unknown.
+fn tier_index(tier: &str) -> Option<usize> {
+    TIERS.iter().position(|t| t.eq_ignore_ascii_case(tier))
+}
+
+/// ⚙️ **Function**: Returns the `rank_goals` collection.
+pub fn rank_goals_collection(mongo_client: &Client) -> Collection<RankGoalData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<RankGoalData>("rank_goals")
+}
+
+/// ⚙️ **Function**: Formats a rank with its tier emoji, e.g. `<Platinum emoji> IV`.
+///
+/// # Parameters:
+/// - `collection_emoji`: The MongoDB collection of the custom emojis.
+/// - `tier`: The tier as returned by the Riot API (e.g. `PLATINUM`).
+/// - `division`: The division (e.g. `IV`), empty for the apex tiers.
+///
+/// # Returns:
+/// - `String`: The formatted rank.
+pub async fn format_rank(
+    collection_emoji: &Collection<EmojiId>,
+    tier: &str,
+    division: &str,
+) -> String {
+    let tier_emoji = get_emoji(collection_emoji.clone(), "rank", tier)
+        .await
+        .unwrap_or(tier.to_string());
+    if division.is_empty() {
+        tier_emoji
+    } else {
+        format!("{} {}", tier_emoji, division)
+    }
+}
+
+/// ⚙️ **Function**: Returns the rank of a goal in plain text, e.g. `Platinum IV`, for embed titles.
+pub fn goal_label(goal: &RankGoalData) -> String {
+    let mut tier = goal.tier.to_lowercase();
+    if let Some(first) = tier.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+    if goal.division.is_empty() {
+        tier
+    } else {
+        format!("{} {}", tier, goal.division)
+    }
+}
+
+/// ⚙️ **Function**: Fetches the current Solo/Duo rank of the account of a goal and records it as a rank snapshot.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `goal`: The goal, holding the account to look up.
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<Option<HashMap<String, Value>>, Error>`: The Solo/Duo league entry, `None` if the account is unranked.
+pub async fn fetch_goal_rank(
+    mongo_client: &Client,
+    goal: &RankGoalData,
+    riot_api_key: &str,
+) -> Result<Option<HashMap<String, Value>>, Error> {
+    let client = reqwest::Client::new();
+    let solo_rank = get_rank_info(&client, &goal.region, &goal.summoner_id, riot_api_key)
+        .await?
+        .into_iter()
+        .find(|entry| entry.get("queueType").and_then(Value::as_str) == Some("RANKED_SOLO_5x5"));
+    if let Some(solo_rank) = &solo_rank {
+        if let Err(e) = record_rank_snapshot(mongo_client, &goal.puuid, solo_rank).await {
+            log::error!("Error recording the rank snapshot: {:?}", e);
+        }
+    }
+    Ok(solo_rank)
+}
+
+/// ⚙️ **Function**: Describes the progress of a player towards their rank goal.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`, used to read the rank snapshots.
+/// - `goal`: The goal of the player.
+/// - `solo_rank`: The current Solo/Duo league entry, as returned by `fetch_goal_rank`.
+/// - `collection_emoji`: The MongoDB collection of the custom emojis, used for the tier emojis.
+///
+/// # Returns:
+/// - `Result<String, Error>`: The current rank, the LP remaining, the LP gained over the last 7 days and the games
+///   needed at the current winrate.
+///
+/// # Example:
+/// ```text
+/// Current rank: **Gold I** - 60 LP
+/// Remaining: **140 LP**
+/// Last 7 days: **+45 LP**
+/// Games needed at your 54% winrate: **~18**
+/// ```
+///
+/// # Notes:
+/// - The LP gained are computed from the rank snapshots, so they only account for the ranks seen by the bot.
+/// - Games are estimated with 20 LP per win and per loss.
+/// - Above Master, the progress is measured in LP. The Grandmaster and Challenger cutoffs move with the ladder, so
+///   those goals are reached with the tier itself and no LP remaining is shown once the player is in Master.
+pub async fn describe_goal_progress(
+    mongo_client: &Client,
+    goal: &RankGoalData,
+    solo_rank: Option<&HashMap<String, Value>>,
+    collection_emoji: &Collection<EmojiId>,
+) -> Result<String, Error> {
+    let goal_score = ladder_score(&goal.tier, &goal.division, 0).unwrap_or(0);
+    let Some(solo_rank) = solo_rank else {
+        return Ok(
+            "Current rank: **Unranked**\nPlay your placement games to start tracking your goal."
+                .to_string(),
+        );
+    };
+    let field = |name: &str| solo_rank.get(name).and_then(Value::as_str).unwrap_or("");
+    let number = |name: &str| solo_rank.get(name).and_then(Value::as_i64).unwrap_or(0);
+    let league_points = number("leaguePoints");
+    let current_score = ladder_score(field("tier"), field("rank"), league_points).unwrap_or(0);
+
+    let mut lines = vec![format!(
+        "Current rank: **{}** - {} LP",
+        format_rank(collection_emoji, field("tier"), field("rank")).await,
+        league_points
+    )];
+    let remaining = goal_score - current_score;
+    // Au-delà de Master, seul le palier compte : les seuils de Grandmaster et Challenger varient avec le ladder
+    let reached = if is_apex_tier(&goal.tier) {
+        tier_index(field("tier")) >= tier_index(&goal.tier)
+    } else {
+        remaining <= 0
+    };
+    if reached {
+        lines.push("✅ **Goal reached!** Set a new one with `/goal set`.".to_string());
+    } else if remaining > 0 {
+        lines.push(format!("Remaining: **{} LP**", remaining));
+    } else {
+        lines.push(format!(
+            "Remaining: climb above the {} cutoff of your region",
+            goal_label(goal)
+        ));
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    if let Some(reference) = get_rank_snapshot_at(
+        mongo_client,
+        &goal.puuid,
+        "RANKED_SOLO_5x5",
+        now - PROGRESS_PERIOD_SECONDS,
+    )
+    .await?
+    {
+        if let Some(reference_score) = ladder_score(
+            &reference.tier,
+            &reference.division,
+            reference.league_points,
+        ) {
+            lines.push(format!(
+                "Last 7 days: **{:+} LP**",
+                current_score - reference_score
+            ));
+        }
+    }
+
+    if !reached && remaining > 0 {
+        let wins = number("wins");
+        let losses = number("losses");
+        if wins + losses > 0 {
+            let winrate = wins as f64 / (wins + losses) as f64;
+            let lp_per_game = (2.0 * winrate - 1.0) * LP_PER_WIN_ESTIMATE as f64;
+            if lp_per_game > 0.0 {
+                lines.push(format!(
+                    "Games needed at your {:.0}% winrate: **~{}**",
+                    winrate * 100.0,
+                    (remaining as f64 / lp_per_game).ceil() as i64
+                ));
+            } else {
+                lines.push(format!(
+                    "At your {:.0}% winrate, you need to win more than half of your games to climb.",
+                    winrate * 100.0
+                ));
+            }
+        }
+    }
+    Ok(lines.join("\n"))
+}
+
+/// ⚙️ **Function**: Sends the weekly goal recap to every user with a rank goal.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `http`: The Discord HTTP client used to send the direct messages.
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if the goals cannot be read.
+///
+/// # Example:
+/// ```rust
+/// post_goal_recaps(&mongo_client, &http, &riot_api_key).await?;
+/// ```
+///
+/// # Notes:
+/// - The week of the last recap is stored in each goal, so the job can run often and each user still gets a single
+///   recap per week.
+/// - Users whose rank cannot be fetched or who cannot receive direct messages are tried again on the next run.
+pub async fn post_goal_recaps(
+    mongo_client: &Client,
+    http: &Http,
+    riot_api_key: &str,
+) -> Result<(), Error> {
+    let week = current_week();
+    let collection = rank_goals_collection(mongo_client);
+    let collection_emoji = mongo_client
+        .database("stat-summoner")
+        .collection::<EmojiId>("emojis_id");
+    let goals: Vec<RankGoalData> = collection
+        .find(doc! { "recap_week": { "$ne": &week } })
+        .await?
+        .try_collect()
+        .await?;
+    for goal in goals {
+        let progress = match fetch_goal_rank(mongo_client, &goal, riot_api_key).await {
+            Ok(solo_rank) => match describe_goal_progress(
+                mongo_client,
+                &goal,
+                solo_rank.as_ref(),
+                &collection_emoji,
+            )
+            .await
+            {
+                Ok(progress) => progress,
+                Err(e) => {
+                    log::error!("Error describing the goal of {}: {:?}", goal.puuid, e);
+                    continue;
+                }
+            },
+            Err(e) => {
+                log::error!("Error fetching the rank of goal {}: {:?}", goal.puuid, e);
+                continue;
+            }
+        };
+        let embed = CreateEmbed::default()
+            .title(format!("📈 Weekly recap: road to {}", goal_label(&goal)))
+            .description(progress)
+            // Un message privé n'appartient à aucun serveur : thème par défaut
+            .color(EmbedTheme::default().info())
+            .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+        if let Err(e) = send_direct_message(
+            http,
+            UserId::new(goal.discord_id),
+            CreateMessage::new()
+                .embed(embed)
+                .components(vec![share_image_button()]),
+        )
+        .await
+        {
+            log::error!(
+                "Error sending the goal recap to {}: {:?}",
+                goal.discord_id,
+                e
+            );
+            continue;
+        }
+        collection
+            .update_one(
+                doc! { "discord_id": goal.discord_id as i64 },
+                doc! { "$set": { "recap_week": &week } },
+            )
+            .await?;
+    }
+    Ok(())
+}
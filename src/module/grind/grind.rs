@@ -0,0 +1,50 @@
+use crate::config::deletion_notice;
+use crate::embed::{create_embed_coded_error, edit_and_schedule_deletion, send_fetching_message};
+use crate::models::data::Data;
+use crate::models::error::Error;
+use crate::module::grind::utils::{create_embed_grind, grind_leaderboard};
+use crate::module::settings::utils::get_guild_theme;
+use crate::utils::current_week_start;
+use poise::serenity_prelude::CreateEmbedFooter;
+use poise::CreateReply;
+
+/// Ranks the members of this server by games played and hours in game this week.
+///
+/// # Example:
+/// ```rust
+/// /grind
+/// ```
+///
+/// # Notes:
+/// - Only members who linked their account with `/linkaccount` are ranked; weeks start on Monday (UTC).
+/// - Games are archived every hour, so the last ones can take a while to show up.
+#[poise::command(slash_command, guild_only)]
+pub async fn grind(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let fetching = send_fetching_message(ctx).await?;
+    let leaderboard = match grind_leaderboard(
+        &ctx.data().mongo_client,
+        ctx.serenity_context(),
+        guild_id,
+        current_week_start(),
+        chrono::Utc::now().timestamp() + 1,
+    )
+    .await
+    {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error building the grind leaderboard", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    let theme = get_guild_theme(ctx).await;
+    let embed = create_embed_grind("⏱️ Grind leaderboard of the week", &leaderboard, &theme)
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ));
+    edit_and_schedule_deletion(fetching, ctx, CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
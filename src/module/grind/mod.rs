@@ -0,0 +1,18 @@
+/// 🛠 **Module grind**: Contains the leaderboard of the guild members who played the most this week.
+///
+/// The games of every account linked with `/linkaccount` are archived in the `played_games` collection, along with the
+/// games of followed players. Members are ranked by games played and hours in game, and last week's leaderboard is
+/// posted with the challenge of the week.
+///
+/// # Files in this module:
+/// - `grind.rs`: The `/grind` command.
+/// - `utils.rs`: The `played_games` archive, the archiving job and the leaderboard embed.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`, and the games are archived in `main.rs`:
+///
+/// ```rust
+/// archive_linked_games(&mongo_client, &riot_api_key).await?;
+/// ```
+pub mod grind;
+pub mod utils;
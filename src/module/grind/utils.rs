@@ -0,0 +1,483 @@
+use crate::models::data::{EmbedTheme, LinkedAccountData, MatchRecordData, PlayedGameData};
+use crate::models::error::Error;
+use crate::module::linkaccount::utils::get_linked_accounts;
+use crate::module::myaccounts::utils::{
+    account_label, is_guild_member, linked_accounts_collection,
+};
+use crate::module::optout::utils::is_opted_out;
+use crate::riot_api::{get_matchs_id_since, get_matchs_info};
+use crate::utils::current_week_start;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+use poise::serenity_prelude::{CacheHttp, CreateEmbed, GuildId, UserId};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Maximum number of games archived per account and per run, to keep the number of Riot API calls bounded.
+const MAX_GAMES_PER_RUN: u32 = 20;
+
+/// Number of members shown in the leaderboard.
+const LEADERBOARD_SIZE: usize = 10;
+
+/// Games played by a member over a period.
+#[derive(Debug, Default)]
+pub struct GrindEntry {
+    pub discord_id: u64,
+    pub games: u32,
+    pub seconds: i64,
+}
+
+/// A game of a player with its result, read from the games archived by the bot (see `archived_games`).
+#[derive(Debug, Clone)]
+pub struct ArchivedGame {
+    pub match_id: String,
+    /// Start of the game (UNIX seconds), `0` when it was not kept.
+    pub started_at: i64,
+    pub win: bool,
+    /// Champions (names) of the enemy team, empty when they were not kept.
+    pub enemy_champions: Vec<String>,
+}
+
+/// ⚙️ **Function**: Returns the `played_games` collection.
+pub fn played_games_collection(mongo_client: &Client) -> Collection<PlayedGameData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<PlayedGameData>("played_games")
+}
+
+/// ⚙️ **Function**: Reads the games of a player archived by the bot, for linked accounts and for followed players.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `puuid`: The PUUID of the player.
+///
+/// # Returns:
+/// - `Result<Vec<ArchivedGame>, Error>`: One game per match, or an error if the archives cannot be read.
+///
+/// # Example:
+/// ```rust
+/// let games = archived_games(&mongo_client, &puuid).await?;
+/// let wins = games.iter().filter(|game| game.win).count();
+/// ```
+///
+/// # Notes:
+/// - A game archived both for a linked account (`played_games`) and as a followed game (`match_records`) is only
+///   returned once, the start and enemy champions missing from one archive being taken from the other.
+/// - The games of `played_games` archived before their result was kept are left out.
+pub async fn archived_games(
+    mongo_client: &Client,
+    puuid: &str,
+) -> Result<Vec<ArchivedGame>, Error> {
+    let mut games: Vec<ArchivedGame> = Vec::new();
+    let mut indexes: HashMap<String, usize> = HashMap::new();
+    let mut add = |game: ArchivedGame| match indexes.get(&game.match_id) {
+        Some(&index) => {
+            let known = &mut games[index];
+            if known.started_at <= 0 {
+                known.started_at = game.started_at;
+            }
+            if known.enemy_champions.is_empty() {
+                known.enemy_champions = game.enemy_champions;
+            }
+        }
+        None => {
+            indexes.insert(game.match_id.clone(), games.len());
+            games.push(game);
+        }
+    };
+
+    let played_games: Vec<PlayedGameData> = played_games_collection(mongo_client)
+        .find(doc! { "puuid": puuid })
+        .await?
+        .try_collect()
+        .await?;
+    for game in played_games {
+        if let Some(win) = game.win {
+            add(ArchivedGame {
+                match_id: game.match_id,
+                started_at: game.started_at,
+                win,
+                enemy_champions: game.enemy_champions,
+            });
+        }
+    }
+
+    let records: Vec<MatchRecordData> = mongo_client
+        .database("stat-summoner")
+        .collection::<MatchRecordData>("match_records")
+        .find(doc! { "participants.puuid": puuid })
+        .await?
+        .try_collect()
+        .await?;
+    for record in records {
+        let Some(player) = record
+            .participants
+            .iter()
+            .find(|participant| participant.puuid == puuid)
+        else {
+            continue;
+        };
+        // Le résultat enregistré est celui de l'équipe du premier joueur suivi
+        let win = (player.team_id == record.team_id) == record.win;
+        let enemy_champions = record
+            .participants
+            .iter()
+            .filter(|participant| participant.team_id != player.team_id)
+            .map(|participant| participant.champion_name.clone())
+            .filter(|champion| !champion.is_empty())
+            .collect();
+        add(ArchivedGame {
+            match_id: record.match_id,
+            started_at: record.started_at,
+            win,
+            enemy_champions,
+        });
+    }
+    Ok(games)
+}
+
+/// ⚙️ **Function**: Selects one of the linked accounts of a user, from the label picked in the autocomplete list.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `discord_id`: The Discord ID of the user.
+/// - `label`: The label of the account (see `account_label`), `None` for the primary account.
+///
+/// # Returns:
+/// - `Result<Option<LinkedAccountData>, Error>`: The account, `None` if it is not linked to the user (see
+///   `missing_account_message`), or an error if the accounts cannot be read.
+///
+/// # Example:
+/// ```rust
+/// let selected = select_linked_account(&mongo_client, ctx.author().id.get(), account.as_deref()).await?;
+/// ```
+pub async fn select_linked_account(
+    mongo_client: &Client,
+    discord_id: u64,
+    label: Option<&str>,
+) -> Result<Option<LinkedAccountData>, Error> {
+    let accounts =
+        get_linked_accounts(&linked_accounts_collection(mongo_client), discord_id).await?;
+    Ok(match label {
+        Some(label) => accounts
+            .into_iter()
+            .find(|account| account_label(account) == label.trim()),
+        None => accounts.into_iter().next(),
+    })
+}
+
+/// ⚙️ **Function**: Returns the message explaining why `select_linked_account` found no account.
+///
+/// # Parameters:
+/// - `label`: The label given to `select_linked_account`.
+///
+/// # Returns:
+/// - `&'static str`: The message to display to the user.
+pub fn missing_account_message(label: Option<&str>) -> &'static str {
+    match label {
+        Some(_) => "This account is not linked to you. Pick one from the list or link it with `/linkaccount`.",
+        None => "You need to link your League of Legends account with `/linkaccount` first.",
+    }
+}
+
+/// ⚙️ **Function**: Archives a match for each of the given players.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `match_id`: The ID of the match.
+/// - `match_info`: The match-v5 data of the match.
+/// - `puuids`: The players the match is archived for.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if a game cannot be saved.
+///
+/// # Notes:
+/// - A game is stored once per player, so archiving a match again has no effect.
+/// - The champion of the player and the bans of the enemy team are kept for `/guildmeta`, the result and the enemy
+///   champions for `/nemesis`, the start of the game for `/whentoplay` and the surrender for the surrender rates of
+///   `/lolstats` and `/session`.
+pub async fn archive_played_game(
+    mongo_client: &Client,
+    match_id: &str,
+    match_info: &Value,
+    puuids: &[&str],
+) -> Result<(), Error> {
+    let info = &match_info["info"];
+    let played_at = info["gameEndTimestamp"]
+        .as_i64()
+        .map(|timestamp| timestamp / 1000)
+        .unwrap_or_else(|| chrono::Utc::now().timestamp());
+    let collection = played_games_collection(mongo_client);
+    let bans = match_bans(match_info);
+    for puuid in puuids {
+        let participant = info["participants"].as_array().and_then(|participants| {
+            participants
+                .iter()
+                .find(|participant| participant["puuid"].as_str() == Some(*puuid))
+        });
+        let team_id = participant.and_then(|participant| participant["teamId"].as_i64());
+        let game = PlayedGameData {
+            puuid: puuid.to_string(),
+            match_id: match_id.to_string(),
+            queue_id: info["queueId"].as_i64().unwrap_or(-1),
+            played_at,
+            started_at: game_start(match_info),
+            duration: info["gameDuration"].as_i64().unwrap_or(0),
+            champion_name: participant
+                .and_then(|participant| participant["championName"].as_str())
+                .unwrap_or("")
+                .to_string(),
+            banned_against: bans
+                .iter()
+                .filter(|(ban_team_id, _)| Some(*ban_team_id) != team_id)
+                .map(|(_, champion_id)| *champion_id)
+                .collect(),
+            win: participant.and_then(|participant| participant["win"].as_bool()),
+            enemy_champions: info["participants"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|enemy| team_id.is_some() && enemy["teamId"].as_i64() != team_id)
+                .filter_map(|enemy| enemy["championName"].as_str())
+                .map(str::to_string)
+                .collect(),
+            surrender: Some(
+                info["participants"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .any(|p| p["gameEndedInSurrender"].as_bool().unwrap_or(false)),
+            ),
+        };
+        collection
+            .replace_one(doc! { "puuid": puuid, "match_id": match_id }, &game)
+            .upsert(true)
+            .await?;
+    }
+    Ok(())
+}
+
+/// ⚙️ **Function**: Returns the start of a match.
+///
+/// # Parameters:
+/// - `match_info`: The match-v5 data of the match.
+///
+/// # Returns:
+/// - `i64`: The UNIX timestamp (seconds) the game started at, its creation when the start is missing, or `0`.
+pub fn game_start(match_info: &Value) -> i64 {
+    let info = &match_info["info"];
+    info["gameStartTimestamp"]
+        .as_i64()
+        .or(info["gameCreation"].as_i64())
+        .map_or(0, |timestamp| timestamp / 1000)
+}
+
+/// ⚙️ **Function**: Lists the bans of a match.
+///
+/// # Parameters:
+/// - `match_info`: The match-v5 data of the match.
+///
+/// # Returns:
+/// - `Vec<(i64, i64)>`: The team ID and the champion ID of each ban. Skipped bans are left out.
+pub fn match_bans(match_info: &Value) -> Vec<(i64, i64)> {
+    let mut bans = Vec::new();
+    for team in match_info["info"]["teams"].as_array().into_iter().flatten() {
+        let team_id = team["teamId"].as_i64().unwrap_or(0);
+        for ban in team["bans"].as_array().into_iter().flatten() {
+            match ban["championId"].as_i64() {
+                Some(champion_id) if champion_id > 0 => bans.push((team_id, champion_id)),
+                _ => {}
+            }
+        }
+    }
+    bans
+}
+
+/// ⚙️ **Function**: Archives the games played this week by every linked account.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if the linked accounts or the archive cannot be read.
+///
+/// # Example:
+/// ```rust
+/// archive_linked_games(&mongo_client, &riot_api_key).await?;
+/// ```
+///
+/// # Notes:
+/// - Only the games played since the last archived one are fetched, at most 20 per account and per run.
+/// - Accounts whose games cannot be fetched are tried again on the next run.
+/// - Accounts that opted out of Stat Summoner (see `is_opted_out`) are not archived.
+pub async fn archive_linked_games(mongo_client: &Client, riot_api_key: &str) -> Result<(), Error> {
+    let client = reqwest::Client::new();
+    let collection = played_games_collection(mongo_client);
+    let accounts: Vec<LinkedAccountData> = linked_accounts_collection(mongo_client)
+        .find(doc! {})
+        .await?
+        .try_collect()
+        .await?;
+    for account in accounts {
+        if is_opted_out(mongo_client, &account.puuid).await? {
+            continue;
+        }
+        let last_played = collection
+            .find_one(doc! { "puuid": &account.puuid })
+            .sort(doc! { "played_at": -1 })
+            .await?
+            .map(|game| game.played_at)
+            .unwrap_or(0);
+        let since = last_played.max(current_week_start());
+        let match_ids = match get_matchs_id_since(
+            &client,
+            &account.puuid,
+            riot_api_key,
+            since,
+            MAX_GAMES_PER_RUN,
+        )
+        .await
+        {
+            Ok(match_ids) => match_ids,
+            Err(e) => {
+                log::error!("Error fetching the games of {}: {:?}", account.puuid, e);
+                continue;
+            }
+        };
+        for match_id in match_ids {
+            if collection
+                .find_one(doc! { "puuid": &account.puuid, "match_id": &match_id })
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            match get_matchs_info(&client, &match_id, riot_api_key).await {
+                Ok(match_info) => {
+                    archive_played_game(mongo_client, &match_id, &match_info, &[&account.puuid])
+                        .await?
+                }
+                Err(e) => log::error!("Error fetching the match {}: {:?}", match_id, e),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// ⚙️ **Function**: Ranks the members of a guild by games played over a period.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `cache_http`: The Discord cache and HTTP client, used to check which players are members of the guild.
+/// - `guild_id`: The guild to rank the members of.
+/// - `since`, `until`: The period, as UNIX timestamps (in seconds).
+///
+/// # Returns:
+/// - `Result<Vec<GrindEntry>, Error>`: The members with at least one game, most games first (ties broken by time in game).
+///
+/// # Notes:
+/// - The games of every account linked by a member are added up.
+/// - Memberships are checked with `is_guild_member`, which keeps its answers for an hour.
+pub async fn grind_leaderboard(
+    mongo_client: &Client,
+    cache_http: impl CacheHttp,
+    guild_id: GuildId,
+    since: i64,
+    until: i64,
+) -> Result<Vec<GrindEntry>, Error> {
+    let accounts: Vec<LinkedAccountData> = linked_accounts_collection(mongo_client)
+        .find(doc! {})
+        .await?
+        .try_collect()
+        .await?;
+    let owners: HashMap<&str, u64> = accounts
+        .iter()
+        .map(|account| (account.puuid.as_str(), account.discord_id))
+        .collect();
+    let puuids: Vec<&str> = owners.keys().copied().collect();
+    let games: Vec<PlayedGameData> = played_games_collection(mongo_client)
+        .find(doc! {
+            "puuid": { "$in": puuids },
+            "played_at": { "$gte": since, "$lt": until },
+        })
+        .await?
+        .try_collect()
+        .await?;
+
+    let mut entries: HashMap<u64, GrindEntry> = HashMap::new();
+    for game in &games {
+        let Some(&discord_id) = owners.get(game.puuid.as_str()) else {
+            continue;
+        };
+        let entry = entries.entry(discord_id).or_insert(GrindEntry {
+            discord_id,
+            ..Default::default()
+        });
+        entry.games += 1;
+        entry.seconds += game.duration;
+    }
+    let mut leaderboard = Vec::new();
+    for (discord_id, entry) in entries {
+        // Seuls les membres du serveur apparaissent dans le classement
+        if is_guild_member(&cache_http, guild_id, UserId::new(discord_id)).await {
+            leaderboard.push(entry);
+        }
+    }
+    leaderboard.sort_by(|a, b| b.games.cmp(&a.games).then(b.seconds.cmp(&a.seconds)));
+    Ok(leaderboard)
+}
+
+/// ⚙️ **Function**: Creates the embed of a grind leaderboard.
+///
+/// # Parameters:
+/// - `title`: The title of the embed.
+/// - `leaderboard`: The members, as ranked by `grind_leaderboard`.
+/// - `theme`: The embed theme of the guild.
+///
+/// # Returns:
+/// - `CreateEmbed`: The 10 members with the most games, with their time in game.
+///
+/// # Example:
+/// ```text
+/// 🥇 @Faker — 14 games (9.2h)
+/// 🥈 @Caps — 11 games (7.8h)
+/// ```
+pub fn create_embed_grind(
+    title: &str,
+    leaderboard: &[GrindEntry],
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let lines: Vec<String> = leaderboard
+        .iter()
+        .take(LEADERBOARD_SIZE)
+        .enumerate()
+        .map(|(rank, entry)| {
+            let medal = match rank {
+                0 => "🥇".to_string(),
+                1 => "🥈".to_string(),
+                2 => "🥉".to_string(),
+                _ => format!("{}.", rank + 1),
+            };
+            format!(
+                "{} <@{}> — **{}** game{} ({:.1}h)",
+                medal,
+                entry.discord_id,
+                entry.games,
+                if entry.games > 1 { "s" } else { "" },
+                entry.seconds as f64 / 3600.0
+            )
+        })
+        .collect();
+    let description = if lines.is_empty() {
+        "No game played by the linked members yet.".to_string()
+    } else {
+        lines.join("\n")
+    };
+    CreateEmbed::default()
+        .title(title)
+        .description(description)
+        .color(theme.info())
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
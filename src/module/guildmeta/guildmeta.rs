@@ -0,0 +1,40 @@
+use crate::embed::{create_embed_coded_error, edit_and_schedule_deletion, send_fetching_message};
+use crate::models::data::{Data, EmojiId};
+use crate::models::error::Error;
+use crate::module::guildmeta::utils::{compute_guild_meta, create_embed_guildmeta};
+use crate::module::settings::utils::get_guild_theme;
+use poise::CreateReply;
+
+/// Shows the champions most played on this server and most banned against its players.
+///
+/// # Example:
+/// ```rust
+/// /guildmeta
+/// ```
+///
+/// # Notes:
+/// - Reads the games archived for the followed summoners and the linked accounts of the members.
+#[poise::command(slash_command, guild_only)]
+pub async fn guildmeta(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let fetching = send_fetching_message(ctx).await?;
+    let mongo_client = &ctx.data().mongo_client;
+    let meta = match compute_guild_meta(mongo_client, ctx.serenity_context(), guild_id).await {
+        Ok(meta) => meta,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error collecting informations from MongoDB", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    let champions = ctx.data().champions.read().await;
+    let collection_emoji = mongo_client
+        .database("stat-summoner")
+        .collection::<EmojiId>("emojis_id");
+    let theme = get_guild_theme(ctx).await;
+    let embed = create_embed_guildmeta(&meta, &champions, &collection_emoji, &theme).await;
+    edit_and_schedule_deletion(fetching, ctx, CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
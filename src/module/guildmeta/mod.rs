@@ -0,0 +1,17 @@
+/// 🛠 **Module guildmeta**: Contains the champions most played by the players of a guild and most banned against them.
+///
+/// The picks and bans are read from the archived games: the `match_records` of the summoners followed in the guild and
+/// the `played_games` of the members who linked their account.
+///
+/// # Files in this module:
+/// - `guildmeta.rs`: The `/guildmeta` command.
+/// - `utils.rs`: The aggregation of the picks and bans and the embed.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`:
+///
+/// ```rust
+/// guildmeta::guildmeta::guildmeta(),
+/// ```
+pub mod guildmeta;
+pub mod utils;
@@ -0,0 +1,189 @@
+use crate::config::deletion_notice;
+use crate::models::champion_index::ChampionIndex;
+use crate::models::data::{EmbedTheme, EmojiId, MatchRecordData, PlayedGameData};
+use crate::models::error::Error;
+use crate::module::grind::utils::played_games_collection;
+use crate::module::myaccounts::utils::guild_linked_accounts;
+use crate::utils::get_emoji;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+use poise::serenity_prelude::{CacheHttp, CreateEmbed, CreateEmbedFooter, GuildId};
+use std::collections::{HashMap, HashSet};
+
+/// Number of champions shown in each list of the embed.
+const META_SIZE: usize = 10;
+
+/// Picks and bans of the games of a guild.
+#[derive(Debug, Default)]
+pub struct GuildMeta {
+    /// Number of games read.
+    pub games: usize,
+    /// Champions played (Data Dragon IDs, e.g. `MonkeyKing`) with their number of games, most played first.
+    pub picks: Vec<(String, u32)>,
+    /// Champions (IDs) banned by the opponents of the guild's players with their number of bans, most banned first.
+    pub bans_against: Vec<(i64, u32)>,
+}
+
+/// ⚙️ **Function**: Aggregates the picks of the players of a guild and the bans against them over the archived games.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `cache_http`: The Discord cache and HTTP client, used to check which linked accounts belong to members of the guild.
+/// - `guild_id`: The guild.
+///
+/// # Returns:
+/// - `Result<GuildMeta, Error>`: The picks and bans, or an error if the archives cannot be read.
+///
+/// # Notes:
+/// - A game archived both as a followed game and as a linked member's game is only counted once.
+/// - Games archived before the picks and bans were kept only count in the number of games.
+pub async fn compute_guild_meta(
+    mongo_client: &Client,
+    cache_http: impl CacheHttp,
+    guild_id: GuildId,
+) -> Result<GuildMeta, Error> {
+    let mut meta = GuildMeta::default();
+    let mut picks: HashMap<String, u32> = HashMap::new();
+    let mut bans_against: HashMap<i64, u32> = HashMap::new();
+    // (partie, joueur) pour les choix, partie pour les bannissements
+    let mut counted_picks: HashSet<(String, String)> = HashSet::new();
+    let mut counted_bans: HashSet<String> = HashSet::new();
+    let mut games: HashSet<String> = HashSet::new();
+
+    let records: Vec<MatchRecordData> = mongo_client
+        .database("stat-summoner")
+        .collection::<MatchRecordData>("match_records")
+        .find(doc! { "guild_id": guild_id.get().to_string() })
+        .await?
+        .try_collect()
+        .await?;
+    for record in &records {
+        games.insert(record.match_id.clone());
+        for participant in &record.participants {
+            if record.followed_puuids.contains(&participant.puuid)
+                && !participant.champion_name.is_empty()
+                && counted_picks.insert((record.match_id.clone(), participant.puuid.clone()))
+            {
+                *picks.entry(participant.champion_name.clone()).or_default() += 1;
+            }
+        }
+        if !record.bans.is_empty() && counted_bans.insert(record.match_id.clone()) {
+            for ban in record
+                .bans
+                .iter()
+                .filter(|ban| ban.team_id != record.team_id)
+            {
+                *bans_against.entry(ban.champion_id).or_default() += 1;
+            }
+        }
+    }
+
+    // Seuls les comptes des membres du serveur sont pris en compte
+    let accounts = guild_linked_accounts(mongo_client, cache_http, guild_id).await?;
+    let puuids: Vec<&str> = accounts
+        .iter()
+        .map(|account| account.puuid.as_str())
+        .collect();
+    let played_games: Vec<PlayedGameData> = played_games_collection(mongo_client)
+        .find(doc! { "puuid": { "$in": puuids } })
+        .await?
+        .try_collect()
+        .await?;
+    for game in &played_games {
+        games.insert(game.match_id.clone());
+        if !game.champion_name.is_empty()
+            && counted_picks.insert((game.match_id.clone(), game.puuid.clone()))
+        {
+            *picks.entry(game.champion_name.clone()).or_default() += 1;
+        }
+        if !game.banned_against.is_empty() && counted_bans.insert(game.match_id.clone()) {
+            for champion_id in &game.banned_against {
+                *bans_against.entry(*champion_id).or_default() += 1;
+            }
+        }
+    }
+
+    meta.games = games.len();
+    meta.picks = picks.into_iter().collect();
+    meta.picks
+        .sort_by(|(a_name, a), (b_name, b)| b.cmp(a).then(a_name.cmp(b_name)));
+    meta.bans_against = bans_against.into_iter().collect();
+    meta.bans_against
+        .sort_by(|(a_id, a), (b_id, b)| b.cmp(a).then(a_id.cmp(b_id)));
+    Ok(meta)
+}
+
+/// ⚙️ **Function**: Creates the embed of the picks and bans of a guild.
+///
+/// # Parameters:
+/// - `meta`: The picks and bans built by `compute_guild_meta`.
+/// - `champions`: The champion lookups, used to name the champions.
+/// - `collection_emoji`: The MongoDB collection of the custom emojis.
+/// - `theme`: The embed theme of the guild.
+///
+/// # Returns:
+/// - `CreateEmbed`: The 10 most played champions and the 10 most banned against the guild's players.
+///
+/// # Example:
+/// ```text
+/// 📈 Meta of the server (152 games)
+/// Most played: 1. Ahri — 14 games
+/// Most banned against us: 1. Zed — 22 bans
+/// ```
+pub async fn create_embed_guildmeta(
+    meta: &GuildMeta,
+    champions: &ChampionIndex,
+    collection_emoji: &Collection<EmojiId>,
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let mut picks = Vec::new();
+    for (rank, (id, games)) in meta.picks.iter().take(META_SIZE).enumerate() {
+        let name = champions.name(id).unwrap_or(id);
+        let emoji = get_emoji(collection_emoji.clone(), "champions", id)
+            .await
+            .unwrap_or_default();
+        picks.push(format!(
+            "{}. {} **{}** — {} game{}",
+            rank + 1,
+            emoji,
+            name,
+            games,
+            if *games > 1 { "s" } else { "" }
+        ));
+    }
+    let mut bans = Vec::new();
+    for (rank, (champion_id, count)) in meta.bans_against.iter().take(META_SIZE).enumerate() {
+        let id = champions
+            .id_from_key(*champion_id)
+            .unwrap_or("Unknown Champion");
+        let name = champions.name(id).unwrap_or(id);
+        let emoji = get_emoji(collection_emoji.clone(), "champions", id)
+            .await
+            .unwrap_or_default();
+        bans.push(format!(
+            "{}. {} **{}** — {} ban{}",
+            rank + 1,
+            emoji,
+            name,
+            count,
+            if *count > 1 { "s" } else { "" }
+        ));
+    }
+    let or_empty = |lines: Vec<String>| {
+        if lines.is_empty() {
+            "Not enough games archived yet.".to_string()
+        } else {
+            lines.join("\n")
+        }
+    };
+    CreateEmbed::default()
+        .title(format!("📈 Meta of the server ({} games)", meta.games))
+        .field("Most played", or_empty(picks), true)
+        .field("Most banned against us", or_empty(bans), true)
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
@@ -0,0 +1,96 @@
+use crate::embed::{
+    create_embed_coded_error, create_embed_sucess, edit_and_schedule_deletion,
+    execute_modal_with_retry, send_fetching_message,
+};
+use crate::models::data::{Data, LinkedAccountData};
+use crate::models::error::Error;
+use crate::models::modal::LinkAccountModal;
+use crate::models::region::Region;
+use crate::models::riot_id::RiotId;
+use crate::module::linkaccount::utils::save_linked_account;
+use crate::riot_api::{get_puuid, get_summoner_id};
+use crate::utils::region_to_string;
+
+/// Links the invoking Discord user to a League of Legends account.
+///
+/// This slash command opens a modal asking for the user's Riot ID (`Name#TAG`), resolves the account through the Riot API,
+/// and stores it in the `linked_accounts` collection. Other commands, such as `/followuser`, can then resolve the
+/// user's account without asking for their Riot ID again.
+///
+/// # Parameters:
+/// - `ctx`: The `poise::ApplicationContext` in which the command is executed.
+/// - `region`: A `Region` enum value selected by the user, indicating the account's region.
+/// - `primary`: Whether this account becomes the one used by default (e.g., by `/followuser` and `/mystats`).
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an empty result if successful, or an error if the process fails.
+///
+/// # Example:
+/// ```rust
+/// /linkaccount region: EUW
+/// ```
+///
+/// # Notes:
+/// - A user can link several accounts (e.g., one per region), the first linked account is the primary one.
+/// - Linking an account again updates it.
+/// - The confirmation message is automatically deleted after 60 seconds.
+#[poise::command(slash_command)]
+pub async fn linkaccount(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Select your region"] region: Region,
+    #[description = "Use this account by default"] primary: Option<bool>,
+) -> Result<(), Error> {
+    let Some(riot_id) = execute_modal_with_retry(ctx, |data: &LinkAccountModal| {
+        data.riot_id.parse::<RiotId>().map_err(|e| e.to_string())
+    })
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let fetching = send_fetching_message(ctx).await?;
+    let client = reqwest::Client::new();
+    let region_str = region_to_string(&region);
+    let puuid = match get_puuid(&client, &riot_id, &ctx.data().riot_api_key).await {
+        Ok(puuid) => puuid,
+        Err(e) => {
+            edit_and_schedule_deletion(fetching, ctx, create_embed_coded_error("", &e)).await?;
+            return Ok(());
+        }
+    };
+
+    let summoner_id =
+        match get_summoner_id(&client, &region_str, &puuid, &ctx.data().riot_api_key).await {
+            Ok(id) => id,
+            Err(e) => {
+                edit_and_schedule_deletion(fetching, ctx, create_embed_coded_error("", &e)).await?;
+                return Ok(());
+            }
+        };
+
+    let collection = ctx
+        .data()
+        .mongo_client
+        .database("stat-summoner")
+        .collection::<LinkedAccountData>("linked_accounts");
+    let linked_account = LinkedAccountData {
+        discord_id: ctx.author().id.get(),
+        puuid,
+        summoner_id,
+        name: riot_id.name.clone(),
+        tag: riot_id.tag.clone(),
+        region: region_str,
+        verification_icon_id: None,
+        primary: primary.unwrap_or(false),
+    };
+
+    let reply = match save_linked_account(&collection, linked_account).await {
+        Ok(_) => create_embed_sucess(&format!(
+            "Your Discord account is now linked to **{}**. See all your accounts with `/myaccounts`.",
+            riot_id
+        )),
+        Err(e) => create_embed_coded_error("Error linking your account", &e.into()),
+    };
+    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+    Ok(())
+}
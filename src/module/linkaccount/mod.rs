@@ -0,0 +1,29 @@
+/// 🛠 **Module linkaccount**: Contains the command used to link a Discord user to their League of Legends account.
+///
+/// This module lets a Discord user store their Riot ID and region once, so that other commands can resolve their
+/// League of Legends account directly from their Discord profile instead of asking for a game name and tag line.
+///
+/// # Files in this module:
+/// - `linkaccount.rs`: The command for linking the invoking Discord user to a League of Legends account.
+/// - `utils.rs`: Utility functions to store and retrieve linked accounts from MongoDB.
+///
+/// # Example:
+/// To use the command in this module, ensure it is registered in the bot's main framework setup:
+///
+/// ```rust
+/// use module::linkaccount::linkaccount::linkaccount;
+///
+/// #[shuttle_runtime::main]
+/// async fn main() {
+///     let framework = poise::Framework::builder()
+///         .options(poise::FrameworkOptions {
+///             commands: vec![linkaccount()], // Register the linkaccount command
+///             ..Default::default()
+///         })
+///         .build();
+/// }
+/// ```
+///
+/// Linked accounts are used by `/followuser` to follow a Discord member without opening a modal.
+pub mod linkaccount;
+pub mod utils;
@@ -0,0 +1,100 @@
+use crate::models::data::LinkedAccountData;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::Collection;
+
+/// ⚙️ **Function**: Stores a League of Legends account linked to a Discord user.
+///
+/// This asynchronous function upserts a `LinkedAccountData` document in the `linked_accounts` collection, using the
+/// Discord user ID and the PUUID as the unique key, so a Discord user can link one account per region (or more).
+///
+/// # Parameters:
+/// - `collection`: The MongoDB collection containing the linked accounts.
+/// - `linked_account`: The `LinkedAccountData` to store. When `primary` is `true`, the other accounts of the user stop
+///   being primary.
+///
+/// # Returns:
+/// - `Result<(), mongodb::error::Error>`: Returns `Ok(())` if the account was stored, or a MongoDB error otherwise.
+///
+/// # Example:
+/// ```rust
+/// save_linked_account(&collection, linked_account).await?;
+/// ```
+///
+/// # Notes:
+/// - The first account linked by a user is always primary.
+pub async fn save_linked_account(
+    collection: &Collection<LinkedAccountData>,
+    mut linked_account: LinkedAccountData,
+) -> Result<(), mongodb::error::Error> {
+    let discord_id = linked_account.discord_id as i64;
+    let other_accounts = collection
+        .count_documents(
+            doc! { "discord_id": discord_id, "puuid": { "$ne": &linked_account.puuid } },
+        )
+        .await?;
+    if other_accounts == 0 {
+        linked_account.primary = true;
+    }
+    if linked_account.primary {
+        collection
+            .update_many(
+                doc! { "discord_id": discord_id },
+                doc! { "$set": { "primary": false } },
+            )
+            .await?;
+    }
+    collection
+        .replace_one(
+            doc! { "discord_id": discord_id, "puuid": linked_account.puuid.clone() },
+            linked_account,
+        )
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Retrieves the primary League of Legends account linked to a Discord user.
+///
+/// # Parameters:
+/// - `collection`: The MongoDB collection containing the linked accounts.
+/// - `discord_id`: The ID of the Discord user.
+///
+/// # Returns:
+/// - `Result<Option<LinkedAccountData>, mongodb::error::Error>`: The primary linked account if the user has linked one, `None` otherwise.
+///
+/// # Example:
+/// ```rust
+/// if let Some(account) = get_linked_account(&collection, user.id.get()).await? {
+///     println!("{}#{}", account.name, account.tag);
+/// }
+/// ```
+pub async fn get_linked_account(
+    collection: &Collection<LinkedAccountData>,
+    discord_id: u64,
+) -> Result<Option<LinkedAccountData>, mongodb::error::Error> {
+    collection
+        .find_one(doc! { "discord_id": discord_id as i64 })
+        .sort(doc! { "primary": -1 })
+        .await
+}
+
+/// ⚙️ **Function**: Retrieves every League of Legends account linked to a Discord user, primary account first.
+///
+/// # Parameters:
+/// - `collection`: The MongoDB collection containing the linked accounts.
+/// - `discord_id`: The ID of the Discord user.
+///
+/// # Returns:
+/// - `Result<Vec<LinkedAccountData>, mongodb::error::Error>`: The linked accounts, empty if the user has not linked any.
+pub async fn get_linked_accounts(
+    collection: &Collection<LinkedAccountData>,
+    discord_id: u64,
+) -> Result<Vec<LinkedAccountData>, mongodb::error::Error> {
+    collection
+        .find(doc! { "discord_id": discord_id as i64 })
+        .sort(doc! { "primary": -1, "region": 1 })
+        .await?
+        .try_collect()
+        .await
+}
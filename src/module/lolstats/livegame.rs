@@ -0,0 +1,119 @@
+use crate::config::deletion_notice;
+use crate::models::champion_index::ChampionIndex;
+use crate::models::data::EmbedTheme;
+use crate::utils::{get_game_mode, seconds_to_time};
+use chrono::Utc;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use serde_json::Value;
+
+/// ⚙️ **Function**: Summarizes the game a player is currently playing, for the live-game badge of `/lolstats`.
+///
+/// # Parameters:
+/// - `game`: The game info returned by `get_active_game`.
+/// - `puuid`: The PUUID of the player.
+/// - `champions`: The champion lookups, used to find the champion names.
+///
+/// # Returns:
+/// - `String`: The queue, the champion played and the time spent in game.
+///
+/// # Example:
+/// ```rust
+/// let badge = live_game_summary(&game, &puuid, &champions);
+/// ```
+///
+/// The resulting string will look like this:
+/// ```text
+/// Ranked Solo/Duo as Ahri (12:34)
+/// ```
+pub fn live_game_summary(game: &Value, puuid: &str, champions: &ChampionIndex) -> String {
+    let queue_id = game["gameQueueConfigId"].as_i64().unwrap_or(0);
+    let game_mode = match get_game_mode(queue_id).as_str() {
+        "Unknown" => game["gameMode"]
+            .as_str()
+            .unwrap_or("Custom game")
+            .to_string(),
+        game_mode => game_mode.to_string(),
+    };
+    let champion = game["participants"]
+        .as_array()
+        .and_then(|participants| {
+            participants
+                .iter()
+                .find(|participant| participant["puuid"].as_str() == Some(puuid))
+        })
+        .map(|participant| champion_name(participant, champions))
+        .unwrap_or("Unknown Champion");
+    let (minutes, seconds) = seconds_to_time(game_length(game));
+    format!("{} as {} ({}:{})", game_mode, champion, minutes, seconds)
+}
+
+/// ⚙️ **Function**: Creates the live game embed of `/lolstats`, listing the champions and players of both teams.
+///
+/// # Parameters:
+/// - `game`: The game info returned by `get_active_game`.
+/// - `puuid`: The PUUID of the looked up player, highlighted in bold.
+/// - `champions`: The champion lookups, used to find the champion names.
+/// - `theme`: The guild's embed theme.
+///
+/// # Returns:
+/// - `CreateEmbed`: The embed, with one field per team.
+///
+/// # Example:
+/// ```rust
+/// let live_embed = create_embed_live_game(&game, &puuid, &champions, &theme);
+/// ```
+pub fn create_embed_live_game(
+    game: &Value,
+    puuid: &str,
+    champions: &ChampionIndex,
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let participants = game["participants"].as_array().cloned().unwrap_or_default();
+    let team = |team_id: i64| {
+        let lines: Vec<String> = participants
+            .iter()
+            .filter(|participant| participant["teamId"].as_i64() == Some(team_id))
+            .map(|participant| {
+                let line = format!(
+                    "{} — {}",
+                    champion_name(participant, champions),
+                    participant["riotId"].as_str().unwrap_or("Unknown player")
+                );
+                if participant["puuid"].as_str() == Some(puuid) {
+                    format!("**{}**", line)
+                } else {
+                    line
+                }
+            })
+            .collect();
+        if lines.is_empty() {
+            "No player".to_string()
+        } else {
+            lines.join("\n")
+        }
+    };
+    CreateEmbed::new()
+        .title("🔴 Live game")
+        .description(live_game_summary(game, puuid, champions))
+        .color(theme.info())
+        .field("🔵 Blue team", team(100), true)
+        .field("🔴 Red team", team(200), true)
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
+
+fn champion_name<'a>(participant: &Value, champions: &'a ChampionIndex) -> &'a str {
+    champions
+        .name_from_key(participant["championId"].as_i64().unwrap_or(0))
+        .unwrap_or("Unknown Champion")
+}
+
+/// Time spent in game, in seconds. `gameLength` is only updated every few minutes, so the start time is preferred.
+fn game_length(game: &Value) -> u64 {
+    match game["gameStartTime"].as_i64().filter(|start| *start > 0) {
+        Some(start) => ((Utc::now().timestamp_millis() - start) / 1000).max(0) as u64,
+        None => game["gameLength"].as_i64().unwrap_or(0).max(0) as u64,
+    }
+}
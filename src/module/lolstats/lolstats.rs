@@ -1,19 +1,29 @@
-use crate::embed::{create_embed_error, schedule_message_deletion};
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, edit_and_schedule_deletion, edit_embed_with_menu,
+    execute_modal_with_retry, send_fetching_message,
+};
 use crate::models::data::{Data, EmojiId};
 use crate::models::error::Error;
 use crate::models::modal::LolStatsModal;
 use crate::models::region::Region;
-use crate::module::lolstats::utils::create_and_send_embed_lolstats;
-use crate::riot_api::{get_champions, get_matchs_id, get_puuid, get_rank_info, get_summoner_id};
+use crate::models::riot_id::RiotId;
+use crate::module::lolstats::livegame::{create_embed_live_game, live_game_summary};
+use crate::module::lolstats::percentile::get_ladder_percentile;
+use crate::module::lolstats::season::{get_season_overview, record_rank_snapshot};
+use crate::module::lolstats::utils::create_embeds_lolstats;
+use crate::module::optout::utils::is_opted_out;
+use crate::module::settings::utils::get_guild_theme;
+use crate::riot_api::{
+    get_active_game, get_champions, get_matchs_id, get_puuid, get_rank_info, get_summoner,
+};
 use crate::utils::{determine_solo_flex, region_to_string};
 use futures::join;
-use poise::Modal;
 use reqwest::Client;
 use std::collections::HashMap;
 
 /// Fetches and displays LoL player stats based on user input.
 ///
-/// This Discord command allows a user to input their League of Legends in-game name and tag, then fetches
+/// This Discord command allows a user to input their League of Legends Riot ID (`Name#TAG`), then fetches
 /// the player's Solo/Duo and Flex rank, top champions, and recent match details from the Riot API.
 /// The results are displayed in a formatted embed and automatically deleted after 60 seconds.
 ///
@@ -25,92 +35,117 @@ use std::collections::HashMap;
 /// - `Result<(), Error>`: If successful, returns `Ok(())`, otherwise returns an error.
 ///
 /// # ⚠️ Notes:
-/// - The command opens a modal dialog to gather the player's in-game name and tag.
+/// - The command opens a modal dialog to gather the player's Riot ID.
+/// - A "Fetching data…" placeholder is shown while the Riot API is queried, then edited into the final embed.
+/// - Players registered in the opt-out registry (see `/optout`) cannot be looked up.
 /// - The message displaying the player's stats is automatically deleted after 60 seconds to keep the chat clean.
+/// - A select menu switches between the Overview, Matches and Champions sections, and Live when the player is in game.
 ///
 /// # Example:
 /// ```rust
 /// lolstats(ctx, Region::EUW).await?;
 /// ```
 ///
-/// This command displays information such as:
+/// The Overview section displays information such as:
 /// ```text
 /// 📊 Stats for Faker#1234
 /// 🔱 **Solo/Duo Rank**: Gold I (100 LP)
 /// 🌀 **Flex Rank**: Silver IV (50 LP)
-/// 💥 **Top Champions**:
-/// Yasuo - Level: 7 - Points: 123456
-/// 📜 **Match Details**:
-/// Victory - **Yasuo**, 2 hours ago (Ranked Solo/Duo):
-/// K/D/A: **10/2/8** | **200 CS** | Duration: **30:45**
-/// ⏳ Played: **2 hours ago**
+/// 📜 **Recent Form**: ✅✅❌✅❌ · 3W - 2L
 /// ```
 #[poise::command(slash_command)]
 pub async fn lolstats(
     ctx: poise::ApplicationContext<'_, Data, Error>,
     #[description = "Select your region"] region: Region,
 ) -> Result<(), Error> {
-    let modal_data: LolStatsModal = match LolStatsModal::execute(ctx).await {
-        Ok(Some(data)) => data,
-        Ok(None) => {
-            let error_message = "Modal data not found.";
-            let reply = ctx.send(create_embed_error(&error_message)).await?;
-            schedule_message_deletion(reply, ctx).await?;
-            return Ok(());
-        }
-        Err(_) => {
-            let error_message = "Failed to retrieve modal data.";
-            let reply = ctx.send(create_embed_error(&error_message)).await?;
-            schedule_message_deletion(reply, ctx).await?;
-            return Ok(());
-        }
+    let Some(riot_id) = execute_modal_with_retry(ctx, |data: &LolStatsModal| {
+        data.riot_id.parse::<RiotId>().map_err(|e| e.to_string())
+    })
+    .await?
+    else {
+        return Ok(());
     };
 
-    let client = Client::new();
-    let game_name_space = modal_data.game_name.replace(" ", "%20");
+    show_lolstats(ctx, riot_id, region).await
+}
 
+/// ⚙️ **Function**: Fetches and displays the stats of a player, like `/lolstats` does once its modal is submitted.
+///
+/// # Parameters:
+/// - `ctx`: The application context of the command.
+/// - `riot_id`: The Riot ID of the player.
+/// - `region`: The region of the player's account.
+///
+/// # Returns:
+/// - `Result<(), Error>`: If successful, returns `Ok(())`, otherwise returns an error.
+///
+/// # Example:
+/// ```rust
+/// show_lolstats(ctx, "Faker#KR1".parse()?, Region::EUW).await?;
+/// ```
+///
+/// # Notes:
+/// - Used by `/mystats` to display the stats of a linked account without opening the modal.
+pub async fn show_lolstats(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    riot_id: RiotId,
+    region: Region,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let client = Client::new();
     let region_str = region_to_string(&region);
 
-    let puuid = match get_puuid(
-        &client,
-        &game_name_space,
-        &modal_data.tag_line,
-        &ctx.data().riot_api_key,
-    )
-    .await
-    {
+    let puuid = match get_puuid(&client, &riot_id, &ctx.data().riot_api_key).await {
         Ok(puuid) => puuid,
         Err(e) => {
-            let error_message = format!("Error fetching PUUID: {}", e);
-            let reply = ctx.send(create_embed_error(&error_message)).await?;
-            schedule_message_deletion(reply, ctx).await?;
+            edit_and_schedule_deletion(
+                fetching,
+                ctx,
+                create_embed_coded_error("Error fetching PUUID", &e),
+            )
+            .await?;
             return Ok(());
         }
     };
 
-    let summoner_id =
-        match get_summoner_id(&client, &region_str, &puuid, &ctx.data().riot_api_key).await {
-            Ok(id) => id,
-            Err(e) => {
-                let error_message = format!("Error fetching summoner ID: {}", e);
-                let reply = ctx.send(create_embed_error(&error_message)).await?;
-                schedule_message_deletion(reply, ctx).await?;
-                return Ok(());
-            }
-        };
+    if is_opted_out(&ctx.data().mongo_client, &puuid).await? {
+        let error_message =
+            "This player has opted out of Stat Summoner: their statistics cannot be looked up.";
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message)).await?;
+        return Ok(());
+    }
+
+    let summoner = match get_summoner(&client, &region_str, &puuid, &ctx.data().riot_api_key).await
+    {
+        Ok(summoner) => summoner,
+        Err(e) => {
+            edit_and_schedule_deletion(
+                fetching,
+                ctx,
+                create_embed_coded_error("Error fetching summoner ID", &e),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+    let summoner_id = summoner["id"].as_str().unwrap_or("");
 
-    let (rank_info_res, champions_res, match_ids_res) = join!(
-        get_rank_info(&client, &region_str, &summoner_id, &ctx.data().riot_api_key),
+    let (rank_info_res, champions_res, match_ids_res, active_game_res) = join!(
+        get_rank_info(&client, &region_str, summoner_id, &ctx.data().riot_api_key),
         get_champions(&client, &puuid, &region_str, &ctx.data().riot_api_key),
-        get_matchs_id(&client, &puuid, &ctx.data().riot_api_key, 5)
+        get_matchs_id(&client, &puuid, &ctx.data().riot_api_key, 5),
+        get_active_game(&client, &region_str, &puuid, &ctx.data().riot_api_key)
     );
 
     let rank_info = match rank_info_res {
         Ok(info) => info,
         Err(e) => {
-            let error_message = format!("Error fetching rank info: {}", e);
-            let reply = ctx.send(create_embed_error(&error_message)).await?;
-            schedule_message_deletion(reply, ctx).await?;
+            edit_and_schedule_deletion(
+                fetching,
+                ctx,
+                create_embed_coded_error("Error fetching rank info", &e),
+            )
+            .await?;
             return Ok(());
         }
     };
@@ -118,9 +153,12 @@ pub async fn lolstats(
     let champions = match champions_res {
         Ok(champs) => champs,
         Err(e) => {
-            let error_message = format!("Error fetching champions: {}", e);
-            let reply = ctx.send(create_embed_error(&error_message)).await?;
-            schedule_message_deletion(reply, ctx).await?;
+            edit_and_schedule_deletion(
+                fetching,
+                ctx,
+                create_embed_coded_error("Error fetching champions", &e),
+            )
+            .await?;
             return Ok(());
         }
     };
@@ -128,9 +166,12 @@ pub async fn lolstats(
     let match_ids = match match_ids_res {
         Ok(ids) => ids,
         Err(e) => {
-            let error_message = format!("Error fetching match IDs: {}", e);
-            let reply = ctx.send(create_embed_error(&error_message)).await?;
-            schedule_message_deletion(reply, ctx).await?;
+            edit_and_schedule_deletion(
+                fetching,
+                ctx,
+                create_embed_coded_error("Error fetching match IDs", &e),
+            )
+            .await?;
             return Ok(()); // Retourne Ok(()) pour terminer proprement
         }
     };
@@ -155,24 +196,81 @@ pub async fn lolstats(
         serde_json::Value::String("".to_string()),
     );
 
-    let (solo_rank, flex_rank) = determine_solo_flex(&rank_info, &default_rank);
+    let (mut solo_rank, flex_rank) = determine_solo_flex(&rank_info, &default_rank);
     let mongo_client: &mongodb::Client = &ctx.data().mongo_client;
     let collection_emoji = mongo_client
         .database("stat-summoner")
         .collection::<EmojiId>("emojis_id");
-    let reply = create_and_send_embed_lolstats(
-        &modal_data,
-        summoner_id,
+    for rank_data in &rank_info {
+        if let Err(e) = record_rank_snapshot(mongo_client, &puuid, rank_data).await {
+            log::error!("Failed to record the rank snapshot: {}", e);
+        }
+    }
+    let ladder_percentile = get_ladder_percentile(
+        mongo_client,
+        &region,
+        solo_rank["tier"].as_str().unwrap_or(""),
+        solo_rank["rank"].as_str().unwrap_or(""),
+        solo_rank["leaguePoints"].as_i64().unwrap_or(0),
+    )
+    .await
+    .unwrap_or_else(|e| {
+        log::error!("Failed to compute the ladder percentile: {}", e);
+        None
+    });
+    if let Some(ladder_percentile) = ladder_percentile {
+        solo_rank.insert(
+            "ladderPercentile".to_string(),
+            serde_json::Value::String(ladder_percentile),
+        );
+    }
+    let season_overview = get_season_overview(mongo_client, &puuid, collection_emoji.clone())
+        .await
+        .unwrap_or_else(|e| {
+            log::error!("Failed to load the season overview: {}", e);
+            "Season ranks are unavailable right now.".to_string()
+        });
+    let mut views = create_embeds_lolstats(
+        &riot_id,
+        &summoner,
         &solo_rank,
         &flex_rank,
+        season_overview,
         champions,
         match_ids,
         &ctx,
         collection_emoji,
     )
     .await;
-    let sent_message = ctx.send(reply).await?;
-    if let Err(e) = schedule_message_deletion(sent_message, ctx).await {
+
+    // The live game is optional: a spectator error must not hide the stats
+    let active_game = active_game_res.unwrap_or_else(|e| {
+        log::error!("Failed to fetch the active game: {}", e);
+        None
+    });
+    if let Some(game) = active_game {
+        let theme = get_guild_theme(ctx).await;
+        let champions = ctx.data().champions.read().await;
+        let (label, overview) = views.remove(0);
+        views.insert(
+            0,
+            (
+                label,
+                overview.field(
+                    "🔴 **In game now**",
+                    live_game_summary(&game, &puuid, &champions),
+                    false,
+                ),
+            ),
+        );
+        views.push((
+            "Live".to_string(),
+            create_embed_live_game(&game, &puuid, &champions, &theme),
+        ));
+    }
+    // L'aperçu porte l'historique de rang de la saison, partageable en image
+    let result = edit_embed_with_menu(fetching, ctx, views, true).await;
+    if let Err(e) = result {
         log::error!("Failed to schedule message deletion: {}", e);
     }
     Ok(())
@@ -1,17 +1,21 @@
-/// 🛠 **Module commands**: Contains all bot commands for the Discord bot.
+/// 🛠 **Module lolstats**: Contains all bot commands for the Discord bot.
 ///
 /// This module organizes the different commands used by the bot. Each command is stored in its own file
-/// within the `commands` directory. These commands are registered and used through the bot's interaction
+/// within the `module/lolstats` directory. These commands are registered and used through the bot's interaction
 /// with Discord via the Poise framework.
 ///
 /// # Files in this module:
 /// - `lolstats.rs`: The command for fetching and displaying League of Legends player stats.
+/// - `livegame.rs`: The live-game badge and embed shown when the player is currently in game.
+/// - `percentile.rs`: The weekly sampling of the Solo/Duo ladder of each region, used to show the "top X%" of a player.
+/// - `season.rs`: The rank snapshots used to show the peak rank of the season and the end rank of the previous season.
+/// - `surrender.rs`: The surrender and FF@15 rates of a player, computed from their archived games.
 ///
 /// # Example:
 /// To use commands in this module, ensure they are registered in the bot's main framework setup:
 ///
 /// ```rust
-/// use commands::lolstats::lolstats;
+/// use module::lolstats::lolstats::lolstats;
 ///
 /// #[shuttle_runtime::main]
 /// async fn main() {
@@ -23,8 +27,11 @@
 ///         .build();
 /// }
 /// ```
-/// new command `followgames` will be added to the bot's command list soon.
 ///
-/// As more commands are added, they will be included here and imported into the main bot setup.
+/// As more commands are added, they will be included here and added to `module::all_commands`.
+pub mod livegame;
 pub mod lolstats;
+pub mod percentile;
+pub mod season;
+pub mod surrender;
 pub mod utils;
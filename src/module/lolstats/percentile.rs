@@ -0,0 +1,244 @@
+use crate::models::data::{DivisionCountData, LeagueDistributionData};
+use crate::models::error::{Error, RateLimitedError};
+use crate::models::region::Region;
+use crate::module::lolstats::season::{rank_score, DIVISIONS, TIERS};
+use crate::riot_api::get_league_exp_entries;
+use crate::utils::region_to_string;
+use chrono::Utc;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+use poise::ChoiceParameter;
+use tokio::time::sleep;
+
+/// Number of players in a full league-exp page.
+const LEAGUE_EXP_PAGE_SIZE: usize = 205;
+
+/// Last page looked at when counting the players of a division.
+const MAX_LEAGUE_EXP_PAGE: u32 = 4096;
+
+/// Age after which the distribution of a region is sampled again.
+const DISTRIBUTION_MAX_AGE_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Number of regions sampled per run, so the sampling of the 11 regions is spread over the week.
+const REGIONS_PER_RUN: usize = 1;
+
+/// Relative precision of the player counts: the search stops once the last page is known within 5% of the pages.
+const COUNT_PRECISION_DIVISOR: u32 = 20;
+
+/// Only the Solo/Duo ladder is sampled.
+const LADDER_QUEUE: &str = "RANKED_SOLO_5x5";
+
+fn league_distribution_collection(mongo_client: &Client) -> Collection<LeagueDistributionData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<LeagueDistributionData>("league_distribution")
+}
+
+/// ⚙️ **Function**: Samples the Solo/Duo ladder of the region whose cached distribution is the oldest, if it is older
+/// than a week.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client.
+/// - `riot_api_key`: The Riot API key.
+///
+/// # Returns:
+/// - `Result<(), Error>`: `Ok(())` once the region has been sampled, or when every region is up to date.
+///
+/// # Example:
+/// ```rust
+/// refresh_league_distributions(&mongo_client, &riot_api_key).await?;
+/// ```
+///
+/// # Notes:
+/// - Only `REGIONS_PER_RUN` regions are sampled per run: with a run every 6 hours, the regions are spread over the week
+///   instead of all being sampled on the same day.
+/// - The number of players of each division is found by looking for the last non-empty league-exp page (exponential
+///   then binary search). The search stops once the end is known within 5% of the pages, which is enough for a
+///   percentile and takes about 15 calls per division instead of reading every page.
+/// - Rate limited requests are retried once Riot allows it, so the job never fails because of the rate limits.
+pub async fn refresh_league_distributions(
+    mongo_client: &Client,
+    riot_api_key: &str,
+) -> Result<(), Error> {
+    let client = reqwest::Client::new();
+    let collection = league_distribution_collection(mongo_client);
+    let mut stale_regions = Vec::new();
+    for region in Region::ALL.iter() {
+        let region_str = region_to_string(region);
+        let updated_at = collection
+            .find_one(doc! { "region": &region_str, "queue_type": LADDER_QUEUE })
+            .await?
+            .map_or(0, |cached| cached.updated_at);
+        if Utc::now().timestamp() - updated_at >= DISTRIBUTION_MAX_AGE_SECS {
+            stale_regions.push((updated_at, region, region_str));
+        }
+    }
+    // Les régions jamais échantillonnées, puis les plus anciennes, passent en premier
+    stale_regions.sort_by_key(|(updated_at, _, _)| *updated_at);
+    for (_, region, region_str) in stale_regions.into_iter().take(REGIONS_PER_RUN) {
+        let mut divisions = Vec::new();
+        for tier in TIERS {
+            let tier_divisions: &[&str] = if matches!(tier, "MASTER" | "GRANDMASTER" | "CHALLENGER")
+            {
+                &["I"]
+            } else {
+                &DIVISIONS
+            };
+            for division in tier_divisions {
+                let players =
+                    count_division_players(&client, &region_str, tier, division, riot_api_key)
+                        .await?;
+                divisions.push(DivisionCountData {
+                    tier: tier.to_string(),
+                    division: division.to_string(),
+                    players,
+                });
+            }
+        }
+        collection
+            .replace_one(
+                doc! { "region": &region_str, "queue_type": LADDER_QUEUE },
+                LeagueDistributionData {
+                    region: region_str.clone(),
+                    queue_type: LADDER_QUEUE.to_string(),
+                    divisions,
+                    updated_at: Utc::now().timestamp(),
+                },
+            )
+            .upsert(true)
+            .await?;
+        log::info!("League distribution of {} updated.", region.name());
+    }
+    Ok(())
+}
+
+async fn count_division_players(
+    client: &reqwest::Client,
+    region_str: &str,
+    tier: &str,
+    division: &str,
+    riot_api_key: &str,
+) -> Result<i64, Error> {
+    // Le job n'est pas pressé : il attend la fin des limitations de Riot au lieu d'échouer
+    let page_len = |page: u32| async move {
+        loop {
+            match get_league_exp_entries(
+                client,
+                region_str,
+                LADDER_QUEUE,
+                tier,
+                division,
+                page,
+                riot_api_key,
+            )
+            .await
+            {
+                Ok(entries) => return Ok::<usize, Error>(entries.len()),
+                Err(e) => match e.downcast_ref::<RateLimitedError>() {
+                    Some(rate_limited) => sleep(rate_limited.retry_after).await,
+                    None => return Err(e),
+                },
+            }
+        }
+    };
+    let players = |page: u32, len: usize| ((page - 1) as usize * LEAGUE_EXP_PAGE_SIZE + len) as i64;
+
+    let first_len = page_len(1).await?;
+    if first_len < LEAGUE_EXP_PAGE_SIZE {
+        return Ok(first_len as i64);
+    }
+    // `low` is always a full page, `high` a page past the end of the division
+    let (mut low, mut high) = (1, 2);
+    loop {
+        let len = page_len(high).await?;
+        if len == LEAGUE_EXP_PAGE_SIZE {
+            low = high;
+            high *= 2;
+            if high > MAX_LEAGUE_EXP_PAGE {
+                return Ok(players(low + 1, 0));
+            }
+        } else if len > 0 {
+            return Ok(players(high, len));
+        } else {
+            break;
+        }
+    }
+    // Une précision de quelques pourcents suffit : on s'arrête au milieu de l'intervalle restant
+    while high - low > (low / COUNT_PRECISION_DIVISOR).max(1) {
+        let middle = (low + high) / 2;
+        let len = page_len(middle).await?;
+        if len == LEAGUE_EXP_PAGE_SIZE {
+            low = middle;
+        } else if len > 0 {
+            return Ok(players(middle, len));
+        } else {
+            high = middle;
+        }
+    }
+    if high - low > 1 {
+        return Ok(players((low + high) / 2, LEAGUE_EXP_PAGE_SIZE / 2));
+    }
+    Ok(players(low + 1, 0))
+}
+
+/// ⚙️ **Function**: Computes the approximate share of the Solo/Duo ladder of a region ranked above a player.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client.
+/// - `region`: The region of the player.
+/// - `tier`: The Solo/Duo tier of the player (e.g. `GOLD`).
+/// - `division`: The Solo/Duo division of the player (e.g. `II`).
+/// - `league_points`: The LP of the player.
+///
+/// # Returns:
+/// - `Result<Option<String>, mongodb::error::Error>`: A label such as `Top 12.3% of EUW`, or `None` when the player is
+///   unranked or the distribution of the region has not been sampled yet.
+///
+/// # Example:
+/// ```rust
+/// let label = get_ladder_percentile(&mongo_client, &Region::EUW, "GOLD", "II", 45).await?;
+/// ```
+///
+/// # Notes:
+/// - Players of the same division are assumed to be evenly spread between 0 and 100 LP.
+pub async fn get_ladder_percentile(
+    mongo_client: &Client,
+    region: &Region,
+    tier: &str,
+    division: &str,
+    league_points: i64,
+) -> Result<Option<String>, mongodb::error::Error> {
+    let Some(player_score) = rank_score(tier, division, 0) else {
+        return Ok(None);
+    };
+    let Some(distribution) = league_distribution_collection(mongo_client)
+        .find_one(doc! { "region": region_to_string(region), "queue_type": LADDER_QUEUE })
+        .await?
+    else {
+        return Ok(None);
+    };
+    let total: i64 = distribution.divisions.iter().map(|d| d.players).sum();
+    if total == 0 {
+        return Ok(None);
+    }
+    let mut above = 0.0;
+    for count in &distribution.divisions {
+        let score = rank_score(&count.tier, &count.division, 0).unwrap_or(0);
+        if score > player_score {
+            above += count.players as f64;
+        } else if score == player_score {
+            let share_above = if matches!(tier, "MASTER" | "GRANDMASTER" | "CHALLENGER") {
+                0.5
+            } else {
+                1.0 - (league_points.clamp(0, 100) as f64 / 100.0)
+            };
+            above += count.players as f64 * share_above;
+        }
+    }
+    let percent = (above / total as f64 * 100.0).max(0.1);
+    Ok(Some(if percent < 10.0 {
+        format!("Top {:.1}% of {}", percent, region.name())
+    } else {
+        format!("Top {:.0}% of {}", percent, region.name())
+    }))
+}
@@ -0,0 +1,251 @@
+use crate::models::data::{EmojiId, RankSnapshotData};
+use crate::utils::get_emoji;
+use chrono::{Datelike, Utc};
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Ranked tiers from the lowest to the highest, used to compare two ranks.
+pub const TIERS: [&str; 10] = [
+    "IRON",
+    "BRONZE",
+    "SILVER",
+    "GOLD",
+    "PLATINUM",
+    "EMERALD",
+    "DIAMOND",
+    "MASTER",
+    "GRANDMASTER",
+    "CHALLENGER",
+];
+
+/// Divisions of a tier from the lowest to the highest.
+pub const DIVISIONS: [&str; 4] = ["IV", "III", "II", "I"];
+
+fn rank_snapshots_collection(mongo_client: &Client) -> Collection<RankSnapshotData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<RankSnapshotData>("rank_snapshots")
+}
+
+/// ⚙️ **Function**: Returns the current ranked season.
+///
+/// # Returns:
+/// - `i32`: The season, which follows the calendar year (e.g. `2026`). Every split of a year belongs to the same season.
+pub fn current_season() -> i32 {
+    Utc::now().year()
+}
+
+/// ⚙️ **Function**: Computes a comparable score for a rank.
+///
+/// # Parameters:
+/// - `tier`: The tier (e.g. `GOLD`).
+/// - `division`: The division (e.g. `II`), empty for the apex tiers.
+/// - `league_points`: The LP of the player.
+///
+/// # Returns:
+/// - `Option<i64>`: The score, higher is better, or `None` when the tier is unknown (e.g. `Unranked`).
+///
+/// # Example:
+/// ```rust
+/// assert!(rank_score("GOLD", "I", 20) > rank_score("GOLD", "II", 90));
+/// ```
+pub fn rank_score(tier: &str, division: &str, league_points: i64) -> Option<i64> {
+    let tier_index = TIERS.iter().position(|t| t.eq_ignore_ascii_case(tier))? as i64;
+    let division_index = DIVISIONS.iter().position(|d| *d == division).unwrap_or(0) as i64;
+    Some(tier_index * 400 + division_index * 100 + league_points)
+}
+
+/// ⚙️ **Function**: Saves the current rank of a player for a queue, so the peak of the season can be tracked.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client.
+/// - `puuid`: The PUUID of the player.
+/// - `rank_data`: The league entry returned by the Riot API (`queueType`, `tier`, `rank`, `leaguePoints`).
+///
+/// # Returns:
+/// - `Result<(), mongodb::error::Error>`: `Ok(())` if the snapshot was saved or was not needed.
+///
+/// # Notes:
+/// - Unranked entries are ignored, and no snapshot is saved when the rank did not change since the last one.
+pub async fn record_rank_snapshot(
+    mongo_client: &Client,
+    puuid: &str,
+    rank_data: &HashMap<String, Value>,
+) -> Result<(), mongodb::error::Error> {
+    let field = |name: &str| {
+        rank_data
+            .get(name)
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string()
+    };
+    let queue_type = field("queueType");
+    let tier = field("tier");
+    let division = field("rank");
+    let league_points = rank_data
+        .get("leaguePoints")
+        .and_then(Value::as_i64)
+        .unwrap_or(0);
+    if queue_type.is_empty() || rank_score(&tier, &division, league_points).is_none() {
+        return Ok(());
+    }
+    let collection = rank_snapshots_collection(mongo_client);
+    let season = current_season();
+    let latest = collection
+        .find_one(doc! { "puuid": puuid, "queue_type": &queue_type, "season": season })
+        .sort(doc! { "recorded_at": -1 })
+        .await?;
+    if let Some(latest) = latest {
+        if latest.tier == tier
+            && latest.division == division
+            && latest.league_points == league_points
+        {
+            return Ok(());
+        }
+    }
+    collection
+        .insert_one(RankSnapshotData {
+            puuid: puuid.to_string(),
+            queue_type,
+            season,
+            tier,
+            division,
+            league_points,
+            recorded_at: Utc::now().timestamp(),
+        })
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Returns the rank a player had at a given time, from the recorded snapshots.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client.
+/// - `puuid`: The PUUID of the player.
+/// - `queue_type`: The ranked queue (e.g. `RANKED_SOLO_5x5`).
+/// - `timestamp`: The UNIX timestamp (in seconds).
+///
+/// # Returns:
+/// - `Result<Option<RankSnapshotData>, mongodb::error::Error>`: The last snapshot recorded before `timestamp`, or the
+///   first one recorded after it when the player was not tracked yet, `None` without any snapshot.
+///
+/// # Example:
+/// ```rust
+/// let week_ago = get_rank_snapshot_at(&mongo_client, &puuid, "RANKED_SOLO_5x5", now - 7 * 24 * 3600).await?;
+/// ```
+pub async fn get_rank_snapshot_at(
+    mongo_client: &Client,
+    puuid: &str,
+    queue_type: &str,
+    timestamp: i64,
+) -> Result<Option<RankSnapshotData>, mongodb::error::Error> {
+    let collection = rank_snapshots_collection(mongo_client);
+    let before = collection
+        .find_one(doc! {
+            "puuid": puuid,
+            "queue_type": queue_type,
+            "recorded_at": { "$lte": timestamp },
+        })
+        .sort(doc! { "recorded_at": -1 })
+        .await?;
+    if before.is_some() {
+        return Ok(before);
+    }
+    collection
+        .find_one(doc! {
+            "puuid": puuid,
+            "queue_type": queue_type,
+            "recorded_at": { "$gt": timestamp },
+        })
+        .sort(doc! { "recorded_at": 1 })
+        .await
+}
+
+/// ⚙️ **Function**: Formats the peak rank of the current season and the end rank of the previous season of a player.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client.
+/// - `puuid`: The PUUID of the player.
+/// - `collection_emoji`: The MongoDB collection of the custom emojis, used for the tier emojis.
+///
+/// # Returns:
+/// - `Result<String, mongodb::error::Error>`: One line per ranked queue with a snapshot, or a message saying no rank
+///   has been recorded yet.
+///
+/// # Example:
+/// ```rust
+/// let season_overview = get_season_overview(&mongo_client, &puuid, collection_emoji.clone()).await?;
+/// ```
+///
+/// The resulting string will look like this:
+/// ```text
+/// Solo/Duo: peak **Diamond II** - 45 LP · last season **Emerald I** - 80 LP
+/// Flex: peak **Gold IV** - 10 LP
+/// ```
+///
+/// # Notes:
+/// - Ranks are only known from the lookups made with the bot, so the peak is the best rank seen by Stat Summoner.
+pub async fn get_season_overview(
+    mongo_client: &Client,
+    puuid: &str,
+    collection_emoji: Collection<EmojiId>,
+) -> Result<String, mongodb::error::Error> {
+    let collection = rank_snapshots_collection(mongo_client);
+    let season = current_season();
+    let mut lines = Vec::new();
+    for (queue_type, label) in [("RANKED_SOLO_5x5", "Solo/Duo"), ("RANKED_FLEX_SR", "Flex")] {
+        let snapshots: Vec<RankSnapshotData> = collection
+            .find(doc! { "puuid": puuid, "queue_type": queue_type, "season": season })
+            .await?
+            .try_collect()
+            .await?;
+        let peak = snapshots.iter().max_by_key(|snapshot| {
+            rank_score(&snapshot.tier, &snapshot.division, snapshot.league_points)
+        });
+        let previous_season_end = collection
+            .find_one(doc! { "puuid": puuid, "queue_type": queue_type, "season": season - 1 })
+            .sort(doc! { "recorded_at": -1 })
+            .await?;
+
+        let mut parts = Vec::new();
+        if let Some(peak) = peak {
+            parts.push(format!(
+                "peak {}",
+                format_snapshot(peak, collection_emoji.clone()).await
+            ));
+        }
+        if let Some(previous) = &previous_season_end {
+            parts.push(format!(
+                "last season {}",
+                format_snapshot(previous, collection_emoji.clone()).await
+            ));
+        }
+        if !parts.is_empty() {
+            lines.push(format!("{}: {}", label, parts.join(" · ")));
+        }
+    }
+    if lines.is_empty() {
+        return Ok("No rank recorded this season yet.".to_string());
+    }
+    Ok(lines.join("\n"))
+}
+
+async fn format_snapshot(
+    snapshot: &RankSnapshotData,
+    collection_emoji: Collection<EmojiId>,
+) -> String {
+    let tier_emoji = get_emoji(collection_emoji, "rank", &snapshot.tier)
+        .await
+        .unwrap_or(snapshot.tier.clone());
+    if snapshot.division.is_empty() {
+        format!("**{}** - {} LP", tier_emoji, snapshot.league_points)
+    } else {
+        format!(
+            "**{} {}** - {} LP",
+            tier_emoji, snapshot.division, snapshot.league_points
+        )
+    }
+}
@@ -0,0 +1,130 @@
+use crate::module::grind::utils::played_games_collection;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::Client;
+
+/// Games shorter than this duration, in seconds, are remakes and are not counted.
+const REMAKE_MAX_DURATION: i64 = 300;
+
+/// Surrenders ending the game before this duration, in seconds, are FF@15: the vote opens at 15 minutes and needs
+/// every player of the team until 20 minutes.
+const FF15_MAX_DURATION: i64 = 20 * 60;
+
+/// Surrenders in the archived games of a player.
+#[derive(Debug, Default, PartialEq)]
+pub struct SurrenderStats {
+    /// Archived games whose surrender is known, remakes excluded.
+    pub games: u32,
+    /// Games lost by a surrender of the player's team.
+    pub team_surrenders: u32,
+    /// Surrenders of the player's team before 20 minutes.
+    pub team_ff15: u32,
+    /// Games won by a surrender of the enemy team.
+    pub enemy_surrenders: u32,
+    /// Surrenders of the enemy team before 20 minutes.
+    pub enemy_ff15: u32,
+}
+
+/// ⚙️ **Function**: Counts the surrenders in the archived games of a player.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `puuid`: The PUUID of the player.
+///
+/// # Returns:
+/// - `Result<SurrenderStats, mongodb::error::Error>`: The surrenders of the player's team and of the enemy team.
+///
+/// # Example:
+/// ```rust
+/// let stats = get_surrender_stats(&mongo_client, &puuid).await?;
+/// println!("{} surrenders in {} games", stats.team_surrenders, stats.games);
+/// ```
+///
+/// # Notes:
+/// - Games are archived when they are notified (see `archive_played_game`), so only the games of followed players
+///   are counted. Games archived before the surrender was kept, and games without a result, are left out.
+/// - A surrendered game is lost by the team that surrendered: the result of the player tells which team it was.
+pub async fn get_surrender_stats(
+    mongo_client: &Client,
+    puuid: &str,
+) -> Result<SurrenderStats, mongodb::error::Error> {
+    let games: Vec<_> = played_games_collection(mongo_client)
+        .find(doc! {
+            "puuid": puuid,
+            "surrender": { "$ne": null },
+            "win": { "$ne": null },
+            "duration": { "$gte": REMAKE_MAX_DURATION },
+        })
+        .await?
+        .try_collect()
+        .await?;
+    let mut stats = SurrenderStats::default();
+    for game in games {
+        let (Some(surrender), Some(win)) = (game.surrender, game.win) else {
+            continue;
+        };
+        stats.games += 1;
+        if !surrender {
+            continue;
+        }
+        let ff15 = game.duration < FF15_MAX_DURATION;
+        if win {
+            stats.enemy_surrenders += 1;
+            stats.enemy_ff15 += ff15 as u32;
+        } else {
+            stats.team_surrenders += 1;
+            stats.team_ff15 += ff15 as u32;
+        }
+    }
+    Ok(stats)
+}
+
+/// ⚙️ **Function**: Formats the surrender rates of a player.
+///
+/// # Parameters:
+/// - `stats`: The surrenders counted by `get_surrender_stats`.
+///
+/// # Returns:
+/// - `Option<String>`: The rates, or `None` when no archived game is known.
+///
+/// # Example:
+/// ```text
+/// Team: 15% (FF@15 5%) · Enemy: 10% (FF@15 0%)
+/// Over 20 archived games
+/// ```
+pub fn format_surrender_stats(stats: &SurrenderStats) -> Option<String> {
+    if stats.games == 0 {
+        return None;
+    }
+    let rate = |count: u32| count as f64 / stats.games as f64 * 100.0;
+    Some(format!(
+        "Team: {:.0}% (FF@15 {:.0}%) · Enemy: {:.0}% (FF@15 {:.0}%)\nOver {} archived game{}",
+        rate(stats.team_surrenders),
+        rate(stats.team_ff15),
+        rate(stats.enemy_surrenders),
+        rate(stats.enemy_ff15),
+        stats.games,
+        if stats.games > 1 { "s" } else { "" }
+    ))
+}
+
+/// ⚙️ **Function**: Loads and formats the surrender rates of a player.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `puuid`: The PUUID of the player.
+///
+/// # Returns:
+/// - `Option<String>`: The rates formatted by `format_surrender_stats`, or `None` when no archived game is known.
+///
+/// # Notes:
+/// - The rates are a side information: a database error is only logged.
+pub async fn get_surrender_summary(mongo_client: &Client, puuid: &str) -> Option<String> {
+    match get_surrender_stats(mongo_client, puuid).await {
+        Ok(stats) => format_surrender_stats(&stats),
+        Err(e) => {
+            log::error!("Failed to load the surrender rates: {}", e);
+            None
+        }
+    }
+}
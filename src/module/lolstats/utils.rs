@@ -1,90 +1,124 @@
-use crate::embed::create_embed;
+use crate::embed::{
+    create_embed, create_embed_lolstats_champions, create_embed_lolstats_matches, progress_bar,
+};
+use crate::models::champion_index::ChampionIndex;
 use crate::models::constants::QUEUE_ID_MAP;
 use crate::models::data::{Data, EmojiId};
 use crate::models::error::Error;
-use crate::models::modal::LolStatsModal;
+use crate::models::riot_id::RiotId;
+use crate::module::lolstats::surrender::get_surrender_summary;
+use crate::module::settings::utils::get_guild_theme;
 use crate::riot_api::get_matchs_info;
-use crate::utils::{get_emoji, is_valid_game_mode, seconds_to_time, time_since_game_ended};
+use crate::static_data::profile_icon_url;
+use crate::utils::{
+    describe_leaver, discord_timestamp, find_leavers, get_emoji, is_valid_game_mode,
+    seconds_to_time, time_since_game_ended,
+};
 use mongodb::Collection;
-use poise::CreateReply;
+use poise::serenity_prelude::CreateEmbed;
 use reqwest::Client;
-use serde_json::{Map, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
-/// ⚙️ **Function**: Fetches data and creates an embed displaying League of Legends player stats and match details.
+/// Maximum number of characters of an embed description.
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+
+/// Average LP gained per ranked win, used to estimate the wins needed to promote.
+pub const LP_PER_WIN_ESTIMATE: i64 = 20;
+
+/// ⚙️ **Function**: Fetches data and creates the views of `/lolstats`: overview, matches and champions.
 ///
 /// This function orchestrates the process of fetching rank, champion, and match data, and formats this information
-/// into an embed message. The embed is then prepared for sending in a Discord channel.
+/// into one embed per section. The sections are switched with the select menu of the message.
 ///
 /// # Parameters:
-/// - `modal_data`: A modal containing the player's in-game name and tag, used to personalize the embed title.
-/// - `summoner_id`: The unique ID of the summoner (player) whose data is being fetched. This is used to query relevant match and rank data.
+/// - `riot_id`: The Riot ID of the player, used to personalize the embed titles.
+/// - `summoner`: The summoner profile of the player (see `get_summoner`). Its ID is used to query the match data, its
+///   level and profile icon are shown in the header of the views.
 /// - `solo_rank`: A HashMap containing the player's Solo/Duo rank information, such as tier, LP, wins, losses, and winrate.
 /// - `flex_rank`: A HashMap containing the player's Flex rank information, structured similarly to `solo_rank`.
+/// - `season_overview`: The peak and previous season ranks of the player, formatted by `get_season_overview`.
 /// - `champions`: A vector of HashMaps, where each HashMap contains information about the player's top champions (e.g., champion level and mastery points).
 /// - `match_ids`: A vector of match IDs representing recent matches played by the user.
 /// - `ctx`: The application context, which includes methods for interacting with Discord and accessing API keys for fetching data.
 ///
 /// # Returns:
-/// - `CreateReply`: A formatted reply containing the embed message, ready to be sent to a Discord channel.
+/// - `Vec<(String, CreateEmbed)>`: The `Overview`, `Matches` and `Champions` views, as `(label, embed)` pairs.
 ///
 /// # ⚠️ Notes:
 /// - The function fetches champion data from Data Dragon and match data from the Riot API, ensuring that up-to-date information is displayed.
-/// - If no match details are found, the embed will indicate that no recent ranked or normal matches were played.
-/// - The function extracts and formats data for Solo/Duo and Flex ranks, as well as champion and match details.
+/// - If no match details are found, the views will indicate that no recent ranked or normal matches were played.
+/// - The overview shows the surrender rates of the player when some of their games were archived (see
+///   `get_surrender_summary`).
 ///
 /// # Example:
 /// ```rust
-/// let embed_reply = create_and_send_embed_lolstats(modal_data, summoner_id, &solo_rank, &flex_rank, champions, match_ids, &ctx).await;
-/// ctx.send(embed_reply).await?;
+/// let views = create_embeds_lolstats(&riot_id, &summoner, &solo_rank, &flex_rank, season_overview, champions, match_ids, &ctx, collection_emoji).await;
+/// edit_embed_with_menu(fetching, ctx, views).await?;
 /// ```
-///
-/// The resulting embed message will contain player stats like:
-/// ```text
-/// 📊 Stats for Faker#1234
-/// 🔱 **Solo/Duo Rank**: Gold I (100 LP)
-/// 🌀 **Flex Rank**: Silver IV (50 LP)
-/// 💥 **Top Champions**:
-/// Yasuo - Level: 7 - Points: 123456
-/// 📜 **Match Details**:
-/// Victory - **Yasuo**, 2 hours ago (Ranked Solo/Duo):
-/// K/D/A: **10/2/8** | **200 CS** | Duration: **30:45**
-/// ⏳ Played: **2 hours ago**
-/// ```
-pub async fn create_and_send_embed_lolstats(
-    modal_data: &LolStatsModal,
-    summoner_id: String,
+pub async fn create_embeds_lolstats(
+    riot_id: &RiotId,
+    summoner: &Value,
     solo_rank: &HashMap<String, Value>,
     flex_rank: &HashMap<String, Value>,
+    season_overview: String,
     champions: Vec<HashMap<String, Value>>,
     match_ids: Vec<String>,
     ctx: &poise::ApplicationContext<'_, Data, Error>,
     collection_emoji: Collection<EmojiId>,
-) -> CreateReply {
-    let dd_json = &*ctx.data().dd_json.read().await;
-    let champions_data = dd_json["data"].as_object().unwrap();
-
+) -> Vec<(String, CreateEmbed)> {
     let solo_rank = extract_rank_info(solo_rank);
     let flex_rank = extract_rank_info(flex_rank);
-    let champions_info =
-        extract_champions_info(champions, champions_data, collection_emoji.clone()).await;
+    let champions_info = extract_champions_info(
+        champions,
+        &*ctx.data().champions.read().await,
+        collection_emoji.clone(),
+    )
+    .await;
+    let summoner_id = summoner["id"].as_str().unwrap_or("").to_string();
     let match_details = extract_match_info(match_ids, ctx, summoner_id).await;
+    let theme = get_guild_theme(*ctx).await;
+    // Sans Data Dragon, le logo du bot remplace l'icône d'invocateur
+    let profile_icon = match profile_icon_url(summoner["profileIconId"].as_i64().unwrap_or(0)).await
+    {
+        Ok(profile_icon) => profile_icon,
+        Err(e) => {
+            log::error!("Failed to build the profile icon URL: {}", e);
+            "https://i.postimg.cc/9fKf2tYp/Logo.png".to_string()
+        }
+    };
 
-    let embed = create_embed(
-        modal_data,
+    let overview = create_embed(
+        riot_id,
+        summoner["summonerLevel"].as_i64().unwrap_or(0),
+        &profile_icon,
         solo_rank,
         flex_rank,
-        champions_info,
-        match_details,
+        season_overview,
+        &match_details,
+        &theme,
         collection_emoji.clone(),
     )
     .await
     .unwrap();
 
-    CreateReply {
-        embeds: vec![embed],
-        ..Default::default()
-    }
+    let puuid = summoner["puuid"].as_str().unwrap_or("");
+    let overview = match get_surrender_summary(&ctx.data().mongo_client, puuid).await {
+        Some(surrenders) => overview.field("🏳️ **Surrenders**", surrenders, false),
+        None => overview,
+    };
+
+    vec![
+        ("Overview".to_string(), overview),
+        (
+            "Matches".to_string(),
+            create_embed_lolstats_matches(riot_id, &match_details, &profile_icon, &theme),
+        ),
+        (
+            "Champions".to_string(),
+            create_embed_lolstats_champions(riot_id, champions_info, &profile_icon, &theme),
+        ),
+    ]
 }
 
 /// ⚙️ **Function**: Extracts and returns League of Legends rank information.
@@ -105,6 +139,8 @@ pub async fn create_and_send_embed_lolstats(
 ///     - `wins`: Number of wins, defaults to 0 if not present.
 ///     - `losses`: Number of losses, defaults to 0 if not present.
 ///     - `winrate`: The player's winrate, calculated as `wins / (wins + losses)`, defaults to 0 if no games are played.
+///     - `promos`: The promotion series or the estimated wins to promote (see `format_promotion`), empty if not applicable.
+///     - `ladder_percentile`: The `ladderPercentile` label set by `/lolstats` (e.g. `Top 12% of EUW`), empty if unknown.
 ///
 /// # ⚠️ Notes:
 /// - If the player is unranked or data is missing, the function will return default values such as `"Unranked"` for
@@ -125,7 +161,9 @@ pub async fn create_and_send_embed_lolstats(
 ///     "lp": 45,
 ///     "wins": 20,
 ///     "losses": 15,
-///     "winrate": 57.14
+///     "winrate": 57.14,
+///     "promos": "~3 wins to promote",
+///     "ladder_percentile": "Top 32% of EUW"
 /// }
 /// ```
 fn extract_rank_info(rank_data: &HashMap<String, Value>) -> Value {
@@ -148,16 +186,68 @@ fn extract_rank_info(rank_data: &HashMap<String, Value>) -> Value {
     } else {
         0.0
     };
+    let promos = format_promotion(rank_data, tier, lp);
+    let ladder_percentile = rank_data
+        .get("ladderPercentile")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
     return serde_json::json!({
         "tier": tier,
         "division": division,
         "lp": lp,
         "wins": wins,
         "losses": losses,
-        "winrate": winrate
+        "winrate": winrate,
+        "promos": promos,
+        "ladder_percentile": ladder_percentile
     });
 }
 
+/// ⚙️ **Function**: Formats the promotion series of a ranked entry, or estimates the wins needed to promote.
+///
+/// # Parameters:
+/// - `rank_data`: The league entry returned by the Riot API, which contains `miniSeries` while the player is in promos.
+/// - `tier`: The tier of the entry.
+/// - `lp`: The league points of the entry.
+///
+/// # Returns:
+/// - `String`: `Promos: W-L-_` during a promotion series, `~3 wins to promote` otherwise, or an empty string for the
+///   unranked and apex (Master and above) tiers.
+///
+/// # Notes:
+/// - The estimate assumes about 20 LP per win and adds `🔥` when Riot flags the player on a hot streak.
+fn format_promotion(rank_data: &HashMap<String, Value>, tier: &str, lp: i64) -> String {
+    if let Some(progress) = rank_data
+        .get("miniSeries")
+        .and_then(|series| series.get("progress"))
+        .and_then(Value::as_str)
+    {
+        let games: Vec<&str> = progress
+            .chars()
+            .map(|game| match game {
+                'W' => "W",
+                'L' => "L",
+                _ => "_",
+            })
+            .collect();
+        return format!("Promos: {}", games.join("-"));
+    }
+    if matches!(
+        tier.to_uppercase().as_str(),
+        "UNRANKED" | "MASTER" | "GRANDMASTER" | "CHALLENGER"
+    ) {
+        return String::new();
+    }
+    let wins_needed = ((100 - lp).max(1) + LP_PER_WIN_ESTIMATE - 1) / LP_PER_WIN_ESTIMATE;
+    let hot_streak = rank_data.get("hotStreak").and_then(Value::as_bool) == Some(true);
+    format!(
+        "~{} win{} to promote{}",
+        wins_needed,
+        if wins_needed > 1 { "s" } else { "" },
+        if hot_streak { " 🔥" } else { "" }
+    )
+}
+
 /// ⚙️ **Function**: Extracts and formats champion information for display.
 ///
 /// This function processes a list of champion details and matches each champion ID to the corresponding
@@ -167,8 +257,7 @@ fn extract_rank_info(rank_data: &HashMap<String, Value>) -> Value {
 /// # Parameters:
 /// - `champions`: A vector of HashMaps, where each HashMap contains information about a player's champion
 ///   (e.g., champion ID, level, mastery points). This is typically fetched from the Riot API.
-/// - `champions_data`: A HashMap containing the full list of champion data from Data Dragon, which is used
-///   to map champion IDs to their names.
+/// - `champion_index`: The champion lookups built from Data Dragon, used to map champion IDs to their names.
 ///
 /// # Returns:
 /// - `String`: A formatted string containing information about each champion:
@@ -176,54 +265,38 @@ fn extract_rank_info(rank_data: &HashMap<String, Value>) -> Value {
 ///     - Champion level
 ///     - Champion mastery points
 ///
-/// The returned string will display each champion on a new line, formatted like this:
+/// The returned string will display each champion with its mastery progress (see `format_mastery_progress`), formatted like this:
 /// ```text
 /// Yasuo - Level: 7 - Points: 123456
+/// ╰ ▰▰▰▱▱ 62% to Lv 8 · 🏅 1/2 marks
 /// Zed - Level: 6 - Points: 98765
-/// Lee Sin - Level: 5 - Points: 54321
+/// ╰ ▰▱▱▱▱ 18% to Lv 7
 /// ```
 ///
 /// # ⚠️ Notes:
-/// - If a champion's ID cannot be matched to a name in `champions_data`, the champion will be listed as "Unknown Champion".
+/// - If a champion's ID cannot be matched to a name in `champion_index`, the champion will be listed as "Unknown Champion".
 /// - This function assumes that every champion in the `champions` list has valid data for level and mastery points.
+/// - Champions that do not fit in the 4096 characters of an embed description are left out.
 ///
 /// # Example:
 /// ```rust
 /// let champions = some_function_fetching_champions();
-/// let champions_data = some_function_fetching_champion_data();
-/// let formatted_champions = extract_champions_info(champions, champions_data);
+/// let champion_index = ctx.data().champions.read().await;
+/// let formatted_champions = extract_champions_info(champions, &champion_index, collection_emoji).await;
 /// ```
 ///
-/// The resulting `formatted_champions` string will be:
-/// ```text
-/// Yasuo - Level: 7 - Points: 123456
-/// Zed - Level: 6 - Points: 98765
-/// Lee Sin - Level: 5 - Points: 54321
-/// ```
 async fn extract_champions_info(
     champions: Vec<HashMap<String, Value>>,
-    champions_data: &Map<String, Value>,
+    champion_index: &ChampionIndex,
     collection_emoji: Collection<EmojiId>,
 ) -> String {
     let mut champion_info_strings = Vec::new();
+    let mut field_length = 0;
 
     for champion in champions {
-        let champion_id = champion
-            .get("championId")
-            .unwrap()
-            .as_i64()
-            .unwrap()
-            .to_string();
-        let champion_name = champions_data
-            .values()
-            .find_map(|data| {
-                let champ = data.as_object().unwrap();
-                if champ.get("key").unwrap() == &Value::String(champion_id.clone()) {
-                    Some(champ.get("id").unwrap().as_str().unwrap())
-                } else {
-                    None
-                }
-            })
+        let champion_id = champion.get("championId").unwrap().as_i64().unwrap();
+        let champion_name = champion_index
+            .id_from_key(champion_id)
             .unwrap_or("Unknown Champion");
 
         let champion_level = champion.get("championLevel").unwrap().as_i64().unwrap();
@@ -231,14 +304,74 @@ async fn extract_champions_info(
         let champion_emoji = get_emoji(collection_emoji.clone(), "champions", champion_name)
             .await
             .unwrap_or(champion_name.to_string());
-        champion_info_strings.push(format!(
-            "{} - Level: {} - Points: {}",
-            champion_emoji, champion_level, champion_points
-        ));
+        let line = format!(
+            "{} - Level: {} - Points: {}\n╰ {}",
+            champion_emoji,
+            champion_level,
+            champion_points,
+            format_mastery_progress(&champion, champion_level)
+        );
+        field_length += line.chars().count() + 1;
+        if field_length > MAX_DESCRIPTION_LENGTH {
+            break;
+        }
+        champion_info_strings.push(line);
     }
     champion_info_strings.join("\n")
 }
 
+/// ⚙️ **Function**: Formats the progress of a champion mastery towards its next level and milestone.
+///
+/// # Parameters:
+/// - `champion`: The champion mastery entry returned by the Riot API.
+/// - `champion_level`: The current mastery level of the champion.
+///
+/// # Returns:
+/// - `String`: The progress bar towards the next level, followed by the marks, season milestone and chest when Riot sends them.
+///
+/// # Example:
+/// ```rust
+/// let progress = format_mastery_progress(&champion, 12);
+/// ```
+///
+/// The resulting string will look like this:
+/// ```text
+/// ▰▰▰▱▱ 62% to Lv 13 · 🏅 1/2 marks · Milestone 3 · 🎁
+/// ```
+///
+/// # Notes:
+/// - `championPointsUntilNextLevel` is `0` (or negative) when the level only requires marks, the bar is then full.
+/// - The chest is only shown when Riot still sends `chestGranted`.
+fn format_mastery_progress(champion: &HashMap<String, Value>, champion_level: i64) -> String {
+    let field = |name: &str| champion.get(name).and_then(Value::as_i64);
+    let since_last_level = field("championPointsSinceLastLevel").unwrap_or(0).max(0);
+    let until_next_level = field("championPointsUntilNextLevel").unwrap_or(0).max(0);
+    let percent = if until_next_level == 0 {
+        100.0
+    } else {
+        since_last_level as f64 / (since_last_level + until_next_level) as f64 * 100.0
+    };
+    let mut parts = vec![format!(
+        "{} to Lv {}",
+        progress_bar(percent),
+        champion_level + 1
+    )];
+    if let Some(required) = field("markRequiredForNextLevel").filter(|required| *required > 0) {
+        parts.push(format!(
+            "🏅 {}/{} marks",
+            field("tokensEarned").unwrap_or(0),
+            required
+        ));
+    }
+    if let Some(milestone) = field("championSeasonMilestone").filter(|milestone| *milestone > 0) {
+        parts.push(format!("Milestone {}", milestone));
+    }
+    if champion.get("chestGranted").and_then(Value::as_bool) == Some(true) {
+        parts.push("🎁".to_string());
+    }
+    parts.join(" · ")
+}
+
 /// ⚙️ **Function**: Extracts detailed information from recent League of Legends matches.
 ///
 /// This function processes a list of match IDs, fetching and extracting key match information
@@ -258,8 +391,11 @@ async fn extract_champions_info(
 ///     - `Farm`: The total number of minions and neutral monsters killed.
 ///     - `Result`: The outcome of the match (Victory or Defeat).
 ///     - `Duration`: The duration of the match in minutes and seconds.
-///     - `time_elapsed`: The time since the match ended, formatted as seconds, minutes, hours, or days ago.
+///     - `time_elapsed`: The time since the match ended, as a Discord relative timestamp (e.g. `<t:1625000000:R>`).
+///     - `played_at`: The date and time the match ended, as a Discord timestamp (e.g. `<t:1625000000:f>`).
 ///     - `game_type`: The type of game played (e.g., Ranked Solo/Duo, ARAM).
+///     - `leavers`: The participants who went AFK or left the game, described by `describe_leaver` (e.g., `Enemy Yasuo AFK at 8:00`).
+///     - `ally_leaver`: Whether one of the player's teammates (or the player) left the game.
 ///
 /// # ⚠️ Notes:
 /// - Only matches with a valid game mode (as determined by `is_valid_game_mode()`) are processed.
@@ -278,20 +414,32 @@ async fn extract_champions_info(
 ///   {
 ///     "champion_name": "Yasuo",
 ///     "K/D/A": "10/2/8",
+///     "kda_ratio": 9.0,
 ///     "Farm": 220,
+///     "cs_per_minute": 7.3,
+///     "damage": 25300,
 ///     "Result": "Victory",
 ///     "Duration": "30:12",
-///     "time_elapsed": "2 hours ago",
-///     "game_type": "Ranked Solo/Duo"
+///     "time_elapsed": "<t:1625000000:R>",
+///     "played_at": "<t:1625000000:f>",
+///     "game_type": "Ranked Solo/Duo",
+///     "leavers": [],
+///     "ally_leaver": false
 ///   },
 ///   {
 ///     "champion_name": "Zed",
 ///     "K/D/A": "7/5/10",
+///     "kda_ratio": 3.4,
 ///     "Farm": 180,
+///     "cs_per_minute": 6.3,
+///     "damage": 18750,
 ///     "Result": "Defeat",
 ///     "Duration": "28:45",
-///     "time_elapsed": "1 day ago",
-///     "game_type": "Ranked Flex"
+///     "time_elapsed": "<t:1624920000:R>",
+///     "played_at": "<t:1624920000:f>",
+///     "game_type": "Ranked Flex",
+///     "leavers": ["Ally Lux AFK at 12:41"],
+///     "ally_leaver": true
 ///   }
 /// ]
 /// ```
@@ -329,14 +477,29 @@ async fn extract_match_info(
                     .find(|(id, _)| *id == queue_id)
                     .unwrap()
                     .1;
+                let team_id = participant["teamId"].as_i64().unwrap_or(0);
+                let leavers = find_leavers(&info);
+                let ally_leaver = leavers
+                    .iter()
+                    .any(|leaver| leaver["teamId"].as_i64() == Some(team_id));
+                let leavers: Vec<String> = leavers
+                    .into_iter()
+                    .map(|leaver| describe_leaver(leaver, team_id))
+                    .collect();
                 match_details.push(serde_json::json!({
                     "champion_name": champion_name,
                     "K/D/A": format!("{}/{}/{}", kills, deaths, assists),
+                    "kda_ratio": (kills + assists) as f64 / deaths.max(1) as f64,
                     "Farm": total_farm,
+                    "cs_per_minute": total_farm as f64 * 60.0 / game_duration.max(1) as f64,
+                    "damage": participant["totalDamageDealtToChampions"].as_u64().unwrap_or(0),
                     "Result": game_result,
                     "Duration": format!("{}:{}", game_duration_minutes, game_duration_seconds),
                     "time_elapsed": time_since_game_ended,
-                    "game_type": game_type
+                    "played_at": discord_timestamp((game_end_timestamp / 1000) as i64, 'f'),
+                    "game_type": game_type,
+                    "leavers": leavers,
+                    "ally_leaver": ally_leaver
                 }));
             }
         }
@@ -0,0 +1,186 @@
+use crate::models::data::JobLockData;
+use crate::models::error::Error;
+use chrono::{DateTime, Utc};
+use mongodb::bson::doc;
+use mongodb::error::{ErrorKind, WriteFailure};
+use mongodb::{Client, Collection};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration};
+
+/// Duration of a lease, in seconds. A lease not renewed within this delay is considered abandoned.
+pub const JOB_LOCK_LEASE_SECS: i64 = 90;
+
+/// Delay between two heartbeats renewing a held lease.
+const JOB_LOCK_HEARTBEAT: Duration = Duration::from_secs(30);
+
+fn job_locks_collection(mongo_client: &Client) -> Collection<JobLockData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<JobLockData>("job_locks")
+}
+
+/// ⚙️ **Function**: Returns the lease name of a periodic job for the current period, with the end of that period.
+///
+/// # Parameters:
+/// - `job`: The name of the scheduled job (e.g. `"fetch_champion_data"`).
+/// - `period`: How often the job runs (e.g. 24 hours).
+///
+/// # Returns:
+/// - `(String, i64)`: The lease name, made of the job name and the start of the period, and the end of the period as
+///   a UNIX timestamp (in seconds).
+///
+/// # Example:
+/// ```rust
+/// let (job, period_end) = job_period("fetch_champion_data", Duration::from_secs(24 * 3600));
+/// // job == "fetch_champion_data:2026-10-16T00:00"
+/// ```
+///
+/// # Notes:
+/// - Periods are aligned on the UNIX epoch, so every instance computes the same name (daily periods start at midnight UTC).
+pub fn job_period(job: &str, period: Duration) -> (String, i64) {
+    let period_secs = (period.as_secs() as i64).max(1);
+    let start = Utc::now().timestamp() / period_secs * period_secs;
+    let label = DateTime::from_timestamp(start, 0)
+        .map(|start| start.format("%Y-%m-%dT%H:%M").to_string())
+        .unwrap_or_else(|| start.to_string());
+    (format!("{}:{}", job, label), start + period_secs)
+}
+
+/// ⚙️ **Function**: Tries to acquire the lease of a scheduled job for this instance.
+///
+/// The lease is a document of the `job_locks` collection keyed by the job name. It is taken if it does not exist,
+/// if it has expired, or if it is already held by this instance.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `job`: The name of the scheduled job (e.g. `"check_and_update_db"`).
+/// - `owner`: The unique identifier of this instance.
+///
+/// # Returns:
+/// - `Result<bool, Error>`: `true` if this instance now holds the lease, `false` if another instance does.
+///
+/// # Example:
+/// ```rust
+/// if acquire_job_lock(&mongo_client, "fetch_champion_data", &instance_id).await? {
+///     fetch_champion_data(&mongo_client).await?;
+/// }
+/// ```
+///
+/// # Notes:
+/// - Using the job name as `_id` makes the upsert fail with a duplicate key error when the lease is held by someone else, which is treated as "not acquired".
+pub async fn acquire_job_lock(
+    mongo_client: &Client,
+    job: &str,
+    owner: &str,
+) -> Result<bool, Error> {
+    let now = Utc::now().timestamp();
+    let filter = doc! {
+        "_id": job,
+        "$or": [
+            { "lease_until": { "$lt": now } },
+            { "owner": owner },
+        ],
+    };
+    let update = doc! {
+        "$set": {
+            "owner": owner,
+            "lease_until": now + JOB_LOCK_LEASE_SECS,
+        }
+    };
+    match job_locks_collection(mongo_client)
+        .update_one(filter, update)
+        .upsert(true)
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(e) => match *e.kind {
+            ErrorKind::Write(WriteFailure::WriteError(ref write_error))
+                if write_error.code == 11000 =>
+            {
+                Ok(false)
+            }
+            _ => Err(e.into()),
+        },
+    }
+}
+
+/// ⚙️ **Function**: Keeps a lease alive while its job is running.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB `Client`, moved into the heartbeat task.
+/// - `job`: The name of the scheduled job.
+/// - `owner`: The unique identifier of this instance.
+///
+/// # Returns:
+/// - `JoinHandle<()>`: The heartbeat task. It must be aborted once the job is finished.
+///
+/// # Notes:
+/// - The heartbeat stops by itself if the lease has been taken over by another instance.
+pub fn spawn_job_lock_heartbeat(
+    mongo_client: Client,
+    job: String,
+    owner: String,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            sleep(JOB_LOCK_HEARTBEAT).await;
+            match acquire_job_lock(&mongo_client, &job, &owner).await {
+                Ok(true) => (),
+                Ok(false) => {
+                    log::warn!("Lease of job '{}' was taken over by another instance.", job);
+                    break;
+                }
+                Err(e) => log::error!("Error renewing the lease of job '{}': {:?}", job, e),
+            }
+        }
+    })
+}
+
+/// ⚙️ **Function**: Releases a lease held by this instance so another instance can take the next run.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `job`: The name of the scheduled job.
+/// - `owner`: The unique identifier of this instance.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An empty result, or an error if the database request fails.
+pub async fn release_job_lock(mongo_client: &Client, job: &str, owner: &str) -> Result<(), Error> {
+    job_locks_collection(mongo_client)
+        .delete_one(doc! { "_id": job, "owner": owner })
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Keeps the lease of a periodic job once it has run, so the job does not run again in the same period.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `job`: The lease name, as returned by `job_period`.
+/// - `owner`: The unique identifier of this instance.
+/// - `until`: The end of the period, as returned by `job_period`.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An empty result, or an error if the database request fails.
+///
+/// # Notes:
+/// - Unlike `release_job_lock`, another instance (or this one after a restart) cannot take the lease before `until`.
+/// - The expired leases are deleted at the same time, so the leases of the past periods do not pile up.
+pub async fn hold_job_lock_until(
+    mongo_client: &Client,
+    job: &str,
+    owner: &str,
+    until: i64,
+) -> Result<(), Error> {
+    let collection = job_locks_collection(mongo_client);
+    collection
+        .update_one(
+            doc! { "_id": job, "owner": owner },
+            doc! { "$set": { "lease_until": until } },
+        )
+        .await?;
+    collection
+        .delete_many(doc! { "lease_until": { "$lt": Utc::now().timestamp() } })
+        .await?;
+    Ok(())
+}
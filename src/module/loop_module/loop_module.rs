@@ -1,17 +1,41 @@
-use crate::models::data::{ChampionData, CoreBuildData, EmojiId, RunesData, SummonerFollowedData};
+use crate::config::config;
+use crate::models::bracket::RankBracket;
+use crate::models::champion_index::ChampionIndex;
+use crate::models::constants::CHAMPION_DATA_SOURCE;
+use crate::models::data::{
+    ChampionData, CoreBuildData, EmojiId, RunesData, ShardConfig, SummonerFollowedData,
+};
 use crate::models::error::Error;
-use crate::module::loop_module::utils::{fetch_core_build, fetch_runes};
-use crate::module::loop_module::utils::{get_followed_summoners, process_followed_summoner};
-use crate::riot_api::open_dd_json;
+use crate::module::loop_module::job_lock::{
+    acquire_job_lock, release_job_lock, spawn_job_lock_heartbeat,
+};
+use crate::module::loop_module::utils::{
+    extract_page_core_build, extract_page_runes, extract_page_situational_build,
+    fetch_bracket_stats, fetch_builds_page,
+};
+use crate::module::loop_module::utils::{
+    flush_queued_notifications, is_notification_held, process_followed_player, queue_notification,
+    send_match_update_to_discord, stream_followed_players, update_live_status_channels,
+};
+use crate::static_data::open_dd_json;
+use crate::utils::{is_guild_handled, patch_from_version};
+use chrono::Utc;
 use futures::executor::block_on;
+use futures::StreamExt;
 use mongodb::bson::{self, doc};
 use mongodb::Client;
 use poise::serenity_prelude as serenity;
 use select::predicate::Predicate;
 use serenity::http::Http;
+use std::collections::HashMap;
+use std::pin::pin;
 use std::sync::Arc;
+use tokio::sync::RwLock;
 use tokio::task;
 
+/// Lease held while the champion statistics are scraped, by the daily job or `/owner refreshdata`.
+pub const CHAMPION_DATA_LOCK: &str = "fetch_champion_data:running";
+
 /// ⚙️ **Function**: Checks the database for followed summoners and updates their information from the Riot API.
 ///
 /// This asynchronous function queries the MongoDB collection to check if there are any followed summoners.
@@ -22,6 +46,8 @@ use tokio::task;
 /// - `mongo_client`: A reference to the MongoDB `Client`, used to query and update the database.
 /// - `riot_api_key`: A string slice representing the Riot API key, required to make authorized API calls.
 /// - `http`: An `Arc<Http>` reference to the HTTP client used for making requests to the Riot API.
+/// - `champions`: The champion lookups, used to show the bans in the notifications.
+/// - `shard_config`: The sharding configuration of this instance, used to only process the guilds it is responsible for.
 ///
 /// # Returns:
 /// - `Result<(), Error>`: Returns an empty result if successful, or an error if any part of the process fails.
@@ -30,16 +56,28 @@ use tokio::task;
 /// This function is used to periodically check and update summoner information.
 ///
 /// ```rust
-/// check_and_update_db(&mongo_client, riot_api_key, http.clone()).await?;
+/// check_and_update_db(&mongo_client, riot_api_key, http.clone(), &champions, &shard_config).await?;
 /// ```
 ///
 /// # Notes:
 /// - The function first checks if there are any documents in the `follower_summoner` collection. If the collection is empty, no further action is taken.
-/// - For each followed summoner, the function retrieves their latest match data using the Riot API and updates the database accordingly.
+/// - The follows are streamed grouped by PUUID (see `stream_followed_players`), so a player followed by
+///   several guilds has their match list fetched once (see `process_followed_player`), then the new match is fanned
+///   out to every guild following them.
+/// - Up to `FOLLOW_POLL_CONCURRENCY` players are checked at the same time, the Riot API rate limiter spacing out the
+///   requests.
+/// - An error while checking a player, or while posting a notification, is logged and only skips this player or this
+///   notification, so the new matches already found for the other players are still notified.
+/// - New matches are grouped per (guild, match ID) so that a game played by several followed summoners of the same guild is notified in a single embed.
+/// - Follows of guilds handled by another instance (see `is_guild_handled`) are skipped, so running several instances never double-notifies a game.
+/// - Notifications of guilds in their quiet hours or batching their notifications are queued (see `queue_notification`) and posted by `flush_queued_notifications`.
+/// - The live status channels of the guilds are updated last, from the live games tracked while polling (see `update_live_status_channels`).
 pub async fn check_and_update_db(
     mongo_client: &Client,
     riot_api_key: &str,
     http: Arc<Http>,
+    champions: &RwLock<ChampionIndex>,
+    shard_config: &ShardConfig,
 ) -> Result<(), Error> {
     let collection = mongo_client
         .database("stat-summoner")
@@ -50,22 +88,132 @@ pub async fn check_and_update_db(
     let count = collection.estimated_document_count().await?;
 
     if count > 0 {
-        let followed_summoners = get_followed_summoners(&collection).await?;
-        for followed_summoner in followed_summoners {
-            process_followed_summoner(
-                &collection,
-                &followed_summoner,
+        // Les joueurs sont traités au fil du curseur, plusieurs à la fois
+        let players = stream_followed_players(&collection)
+            .await?
+            .map(|follows| {
+                let follows = follows
+                    .into_iter()
+                    .filter(|followed_summoner| {
+                        is_guild_handled(shard_config, &followed_summoner.guild_id)
+                    })
+                    .collect();
+                process_followed_player(&collection, follows, riot_api_key)
+            })
+            .buffer_unordered(config().follow_poll_concurrency);
+        let mut players = pin!(players);
+        let mut pending_notifications: HashMap<(String, String), Vec<SummonerFollowedData>> =
+            HashMap::new();
+        while let Some(notifications) = players.next().await {
+            let notifications = match notifications {
+                Ok(notifications) => notifications,
+                Err(e) => {
+                    log::error!("Failed to check a followed player: {:?}", e);
+                    continue;
+                }
+            };
+            for (followed_summoner, match_id) in notifications {
+                pending_notifications
+                    .entry((followed_summoner.guild_id.clone(), match_id))
+                    .or_default()
+                    .push(followed_summoner);
+            }
+        }
+        for ((guild_id, match_id), summoners) in pending_notifications {
+            if is_notification_held(mongo_client, &guild_id).await {
+                // `last_match_id` est déjà à jour : si la file est indisponible, la notification est envoyée tout de suite
+                match queue_notification(mongo_client, &guild_id, &match_id, summoners.clone())
+                    .await
+                {
+                    Ok(()) => continue,
+                    Err(e) => log::error!(
+                        "Failed to queue the notification of match {} in guild {}, posting it now: {:?}",
+                        match_id,
+                        guild_id,
+                        e
+                    ),
+                }
+            }
+            if let Err(e) = send_match_update_to_discord(
+                &summoners,
+                &match_id,
                 riot_api_key,
                 http.clone(),
                 collection_emoji.clone(),
+                champions,
             )
-            .await?;
+            .await
+            {
+                log::error!(
+                    "Failed to post the notification of match {} in guild {}: {:?}",
+                    match_id,
+                    guild_id,
+                    e
+                );
+            }
         }
     }
+    flush_queued_notifications(
+        mongo_client,
+        riot_api_key,
+        http.clone(),
+        collection_emoji,
+        champions,
+        shard_config,
+    )
+    .await?;
+    update_live_status_channels(mongo_client, http, shard_config).await?;
 
     Ok(())
 }
 
+/// ⚙️ **Function**: Scrapes the champion statistics with `fetch_champion_data`, unless they are already being scraped.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `owner`: The unique identifier of the caller, holding the lease while scraping.
+///
+/// # Returns:
+/// - `Result<bool, Error>`: `true` once scraped, `false` if another instance or `/owner refreshdata` is already
+///   scraping, or an error if the lease cannot be read or the scrape fails.
+///
+/// # Example:
+/// ```rust
+/// if !fetch_champion_data_exclusive(&mongo_client, &instance_id).await? {
+///     log::info!("Champion statistics are already being scraped.");
+/// }
+/// ```
+///
+/// # Notes:
+/// - The lease (`CHAMPION_DATA_LOCK`) is shared by the daily job and `/owner refreshdata`, so the two never scrape
+///   at the same time.
+pub async fn fetch_champion_data_exclusive(
+    mongo_client: &Client,
+    owner: &str,
+) -> Result<bool, Error> {
+    if !acquire_job_lock(mongo_client, CHAMPION_DATA_LOCK, owner).await? {
+        return Ok(false);
+    }
+    let heartbeat = spawn_job_lock_heartbeat(
+        mongo_client.clone(),
+        CHAMPION_DATA_LOCK.to_string(),
+        owner.to_string(),
+    );
+    // L'erreur du scraping n'est pas `Send` : elle est convertie avant de libérer le bail
+    let result = fetch_champion_data(mongo_client)
+        .await
+        .map_err(|e| e.to_string());
+    heartbeat.abort();
+    if let Err(e) = release_job_lock(mongo_client, CHAMPION_DATA_LOCK, owner).await {
+        log::error!(
+            "Error releasing the lease of job '{}': {:?}",
+            CHAMPION_DATA_LOCK,
+            e
+        );
+    }
+    result.map(|_| true).map_err(Error::from)
+}
+
 /// ⚙️ **Function**: Fetches champion data from League of Graphs and updates MongoDB.
 ///
 /// This asynchronous function retrieves champion statistics, rune data, and core build information
@@ -89,12 +237,17 @@ pub async fn check_and_update_db(
 /// # Notes:
 /// - The function starts by sending an HTTP request to the League of Graphs page to fetch champion build data.
 /// - It parses the HTML content using the `select` crate, extracting details such as popularity, win rate, and ban rate for each champion.
-/// - For each champion, it also retrieves runes and core build information using the `fetch_runes` and `fetch_core_build` functions.
-/// - The MongoDB collection `champions_data` is then updated with the latest data for each champion. If the champion already exists, the data is updated; otherwise, a new entry is inserted.
+/// - For each champion, it also fetches its builds page once with `fetch_builds_page`, and reads the runes, core build and situational build from it with `extract_page_runes`, `extract_page_core_build` and `extract_page_situational_build`.
+/// - The MongoDB collection `champions_data` is then updated with the latest data for each champion. Documents are keyed by champion name and patch: the current patch's entry is updated if it exists, otherwise a new entry is inserted, so previous patches' numbers are kept.
 /// - The function makes use of `task::spawn_blocking` to handle blocking operations during HTML parsing.
+/// - Statistics restricted to the Iron–Gold, Platinum+ and Diamond+ brackets are fetched with `fetch_bracket_stats` and stored alongside the all-ranks ones. A bracket that fails to load is skipped.
+/// - Each document is stamped with its source, the current patch (from the Data Dragon version) and the scrape time, so embeds can show how fresh the numbers are.
+/// - Documents corrected with `/owner overridechampion` (`override: true`) are left untouched.
 pub async fn fetch_champion_data(mongo_client: &Client) -> Result<(), Box<dyn std::error::Error>> {
     let url = "https://www.leagueofgraphs.com/champions/builds";
-    let dd_json = open_dd_json().await.unwrap();
+    let dd_json = open_dd_json()
+        .await
+        .map_err(|e| e as Box<dyn std::error::Error>)?;
     let client = reqwest::Client::new();
     let res = client
         .get(url)
@@ -103,8 +256,11 @@ pub async fn fetch_champion_data(mongo_client: &Client) -> Result<(), Box<dyn st
         .await?;
 
     let body = res.text().await?;
+    let patch = patch_from_version(dd_json["version"].as_str().unwrap_or_default());
+    let champion_index = ChampionIndex::from_dd_json(&dd_json);
+    let scraped_at = Utc::now().timestamp();
 
-    let results: Vec<ChampionData> = task::spawn_blocking(move || {
+    let mut results: Vec<ChampionData> = task::spawn_blocking(move || {
         let document = select::document::Document::from(body.as_str());
         let mut results = Vec::new();
 
@@ -150,13 +306,8 @@ pub async fn fetch_champion_data(mongo_client: &Client) -> Result<(), Box<dyn st
                     .unwrap()
                     .to_string();
 
-                let id_name = dd_json["data"]
-                    .as_object()
-                    .and_then(|data| {
-                        data.values()
-                            .find(|champion| champion["name"].as_str().map_or(false, |n| n == name))
-                    })
-                    .and_then(|champion| champion["id"].as_str())
+                let id_name = champion_index
+                    .id_from_name(&name)
                     .unwrap_or(&name)
                     .to_string();
                 let default_runes = RunesData {
@@ -175,11 +326,23 @@ pub async fn fetch_champion_data(mongo_client: &Client) -> Result<(), Box<dyn st
                     second: String::new(),
                     third: String::new(),
                 };
-                let runes = block_on(fetch_runes(&id_name.to_lowercase())).unwrap_or(default_runes);
+                // Une seule requête par champion : runes et builds sont lus sur la même page
+                let builds_page = block_on(fetch_builds_page(&id_name.to_lowercase())).ok();
+                let runes = builds_page
+                    .as_ref()
+                    .and_then(|page| extract_page_runes(page).ok())
+                    .unwrap_or(default_runes);
 
-                let core_build = block_on(fetch_core_build(&id_name.to_lowercase()))
+                let core_build = builds_page
+                    .as_ref()
+                    .and_then(|page| extract_page_core_build(page).ok())
                     .unwrap_or(default_core_build);
 
+                let situational_build = builds_page
+                    .as_ref()
+                    .map(extract_page_situational_build)
+                    .unwrap_or_default();
+
                 results.push(ChampionData {
                     name: name,
                     id_name: id_name,
@@ -189,6 +352,12 @@ pub async fn fetch_champion_data(mongo_client: &Client) -> Result<(), Box<dyn st
                     banrate: banrate,
                     runes: runes,
                     core_build: core_build,
+                    source: CHAMPION_DATA_SOURCE.to_string(),
+                    patch: patch.clone(),
+                    updated_at: scraped_at,
+                    brackets: Vec::new(),
+                    situational_build: situational_build,
+                    overridden: false,
                 });
             }
         }
@@ -196,14 +365,31 @@ pub async fn fetch_champion_data(mongo_client: &Client) -> Result<(), Box<dyn st
     })
     .await?;
 
+    for bracket in RankBracket::SEGMENTED {
+        match fetch_bracket_stats(bracket).await {
+            Ok(mut stats) => {
+                for champion in results.iter_mut() {
+                    if let Some(bracket_stats) = stats.remove(&champion.name) {
+                        champion.brackets.push(bracket_stats);
+                    }
+                }
+            }
+            Err(e) => log::warn!("Failed to fetch {} statistics: {}", bracket.label(), e),
+        }
+    }
+
     let collection = mongo_client
         .database("stat-summoner")
         .collection::<ChampionData>("champions_data");
 
     for champion in results {
-        let filter = doc! { "name": &champion.name };
+        let filter = doc! { "name": &champion.name, "patch": &champion.patch };
 
-        if let Some(_) = collection.find_one(filter.clone()).await? {
+        if let Some(existing) = collection.find_one(filter.clone()).await? {
+            // Les statistiques corrigées à la main ne sont pas écrasées par le scraping
+            if existing.overridden {
+                continue;
+            }
             let update = doc! {
                 "$set": {
                     "role": champion.role,
@@ -212,7 +398,11 @@ pub async fn fetch_champion_data(mongo_client: &Client) -> Result<(), Box<dyn st
                     "banrate": champion.banrate,
                     "id_name": champion.id_name,
                     "runes": bson::to_document(&champion.runes).unwrap(),
-                    "core_build":  bson::to_document(&champion.core_build).unwrap()
+                    "core_build":  bson::to_document(&champion.core_build).unwrap(),
+                    "brackets": bson::to_bson(&champion.brackets).unwrap(),
+                    "situational_build": bson::to_document(&champion.situational_build).unwrap(),
+                    "source": champion.source,
+                    "updated_at": champion.updated_at
                 }
             };
             collection.update_one(filter, update).await?;
@@ -224,3 +414,22 @@ pub async fn fetch_champion_data(mongo_client: &Client) -> Result<(), Box<dyn st
     log::info!("Mise à jour des données MongoDB terminée.");
     Ok(())
 }
+
+/// ⚙️ **Function**: Downloads the latest Data Dragon champion data and rebuilds the champion lookups of this instance.
+///
+/// # Parameters:
+/// - `champions`: The champion lookups shared through `Data::champions`.
+///
+/// # Returns:
+/// - `Result<String, Error>`: The Data Dragon version now in use (e.g. `14.20.1`), or an error if Data Dragon cannot
+///   be reached. The previous lookups are then kept.
+///
+/// # Example:
+/// ```rust
+/// let version = reload_champion_index(&ctx.data().champions).await?;
+/// ```
+pub async fn reload_champion_index(champions: &RwLock<ChampionIndex>) -> Result<String, Error> {
+    let dd_json = open_dd_json().await?;
+    *champions.write().await = ChampionIndex::from_dd_json(&dd_json);
+    Ok(dd_json["version"].as_str().unwrap_or_default().to_string())
+}
@@ -1,2 +1,3 @@
+pub mod job_lock;
 pub mod loop_module;
 pub mod utils;
@@ -1,29 +1,93 @@
 use crate::{
     models::{
-        data::{CoreBuildData, EmojiId, RunesData, SummonerFollowedData},
+        bracket::RankBracket,
+        champion_index::ChampionIndex,
+        constants::ARENA_QUEUE_ID,
+        data::{
+            BracketStatsData, ChampionMasteryCacheData, CoreBuildData, EmbedLayout, EmbedTheme,
+            EmojiId, FollowAuditEvent, GuildSettingsData, LiveGameData, MatchBanData,
+            MatchParticipantData, MatchRecordData, NotificationFilter, QueueFilter,
+            QueuedNotificationData, RunesData, ShardConfig, SituationalBuildData,
+            SummonerFollowedData,
+        },
         error::Error,
     },
-    riot_api::{get_matchs_id, get_matchs_info},
+    module::{
+        auditlog::utils::record_follow_event,
+        grind::utils::{archive_played_game, game_start, match_bans},
+        lolstats::season::{get_rank_snapshot_at, rank_score, record_rank_snapshot},
+        matchdetails::utils::{ban_emojis, create_gold_graph, frame_stats_at, jungle_stats},
+        settings::utils::{
+            get_guild_settings, guild_settings_collection, is_in_quiet_hours, resolve_embed_layout,
+            save_live_status,
+        },
+    },
+    riot_api::{
+        get_active_game, get_champion_mastery_points, get_match_timeline, get_matchs_id,
+        get_matchs_info, get_rank_info,
+    },
+    sandbox::{edit_channel, send_message},
+    static_data::{arena_augment_names, champion_loading_url},
     utils::*,
 };
 use chrono::Utc;
-use futures::StreamExt;
+use futures::stream::{self, Stream};
+use futures::{StreamExt, TryStreamExt};
 use mongodb::{bson::doc, Collection};
-use poise::serenity_prelude::{self as serenity, CreateEmbed, CreateMessage, Http};
+use poise::serenity_prelude::{
+    self as serenity, CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, CreateMessage, Http,
+};
 use regex::Regex;
 use select::document::Document;
-use select::predicate::{Class, Name};
+use select::predicate::{Attr, Class, Name, Predicate};
 use serde_json::Value;
 use std::{collections::HashMap, sync::Arc};
+use tokio::sync::RwLock;
+
+/// Minimum duration, in seconds, of a game for its match notification to include the gold graph.
+const GOLD_GRAPH_MIN_DURATION: u64 = 20 * 60;
+
+/// Minute of the timeline frame compared in the laning report.
+const LANING_MINUTE: usize = 10;
+
+/// Number of seconds before the cached mastery points of a player on a champion are fetched again.
+pub const MASTERY_CACHE_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Mastery points under which an enemy laner is tagged as a possible first-time on their champion.
+const FIRST_TIME_MAX_POINTS: i64 = 5000;
+
+/// Number of seconds of archived matches searched for previous encounters with an enemy.
+const ENCOUNTER_WINDOW_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Minimum duration, in seconds, of a game notified with the "Games longer than 15 minutes" filter.
+const LONG_GAME_MIN_DURATION: i64 = 15 * 60;
+
+/// KDA from which a game is notable for the "Notable games" filter.
+const NOTABLE_KDA: f64 = 10.0;
+
+/// Number of failed attempts after which a queued notification is dropped.
+const MAX_NOTIFICATION_ATTEMPTS: u32 = 3;
+
+/// Minimum time, in seconds, between two edits of a live status channel (Discord allows two every 10 minutes).
+const LIVE_STATUS_MIN_INTERVAL_SECS: i64 = 5 * 60;
+
+/// Time, in seconds, after which a live game that was not seen by the spectator anymore is considered over.
+const LIVE_GAME_STALE_SECS: i64 = 5 * 60;
+
+/// Time, in seconds, after which an edit of a live status channel is given up, when Discord rate limits it.
+const LIVE_STATUS_EDIT_TIMEOUT_SECS: u64 = 10;
+
+/// Number of `playerAugment` fields of an Arena participant.
+const ARENA_AUGMENT_SLOTS: u32 = 6;
 
 /// ⚙️ **Function**: Extracts relevant match details for a given summoner from the match information.
 ///
-/// This function retrieves detailed information about a match, focusing on the summoner specified by their `summoner_id`.
+/// This function retrieves detailed information about a match, focusing on the followed summoners specified by their `summoner_ids`.
 /// It validates the game mode, identifies the summoner's performance, and compares their stats with the enemy team in each role (TOP, JUNGLE, MIDDLE, BOTTOM, UTILITY).
 ///
 /// # Parameters:
 /// - `match_info`: A reference to a `Value` (from the `serde_json` crate) containing the entire match data fetched from the Riot API.
-/// - `summoner_id`: A string slice representing the summoner's ID, used to locate their stats in the match data.
+/// - `summoner_ids`: A slice of summoner IDs of the followed summoners who played this match. The first one found in the match is used as the reference for the team and the match result.
 ///
 /// # Returns:
 /// - `Option<Value>`: Returns a JSON object containing the match result (Victory or Defeat) and detailed role-based stats comparisons, or `None` if the game mode is invalid or the data is not available.
@@ -32,7 +96,7 @@ use std::{collections::HashMap, sync::Arc};
 /// This function is typically used to extract and format match details for reporting to a Discord channel:
 ///
 /// ```rust
-/// let match_details = get_match_details(&match_info, summoner_id);
+/// let match_details = get_match_details(&match_info, &[summoner_id]);
 /// if let Some(details) = match_details {
 ///     // Process match details for further use
 /// }
@@ -43,22 +107,32 @@ use std::{collections::HashMap, sync::Arc};
 /// - It then searches for the summoner in the participants list and identifies their team and match result (Victory or Defeat).
 /// - The function separates the participants into two teams (the summoner's team and the enemy team) and compares stats for each role.
 /// - It generates JSON-formatted role matchups comparing stats between the summoner's team and their opponents for each role.
-pub fn get_match_details(match_info: &Value, summoner_id: &str) -> Option<Value> {
+/// - Both teams' kills, gold and objectives are summarized under `teamSummary` and `enemySummary` using `extract_team_summary`.
+/// - Each player's kill participation and damage share within their team are added to their stats by `add_team_shares`.
+/// - Matchups containing a followed summoner are flagged with `"followed": true` (ally side) or `"enemyFollowed": true` (enemy side) so the embed can highlight them.
+/// - Participants who went AFK or left are listed under `leavers` (e.g., `"Enemy Yasuo AFK at 8:00"`, see `find_leavers`), and `surrender` tells whether the game ended in a surrender.
+/// - In Arena games, the followed summoners are listed under `arena` with their `placement` and the IDs of their `augments`.
+/// - `gameEnd` is the UNIX timestamp (in seconds) of the end of the game.
+pub fn get_match_details(match_info: &Value, summoner_ids: &[&str]) -> Option<Value> {
     let queue_id = match_info["info"]["queueId"].as_i64().unwrap_or(-1);
     let (game_duration_minutes, game_duration_secondes) =
         seconds_to_time(match_info["info"]["gameDuration"].as_u64().unwrap_or(0));
     let game_duration_string = format!("{}:{}", game_duration_minutes, game_duration_secondes);
     // utilise QUEUE_ID_MAP qui est une constante dans models/constants.rs qui contient une liste de game modes faisant correspondre id -> game mode en str
-    let game_mode = get_game_mode(queue_id);
+    let game_mode = if queue_id == ARENA_QUEUE_ID {
+        "Arena".to_string()
+    } else {
+        get_game_mode(queue_id)
+    };
 
     let participants = match_info["info"]["participants"].as_array()?;
-    let participant = participants
-        .iter()
-        .find(|p| p["summonerId"].as_str().unwrap_or("") == summoner_id)?;
+    let is_followed = |p: &Value| summoner_ids.contains(&p["summonerId"].as_str().unwrap_or(""));
+    let participant = participants.iter().find(|p| is_followed(p))?;
 
     let team_id = participant["teamId"].as_i64().unwrap_or(0);
     let win = participant["win"].as_bool().unwrap_or(false);
     let game_result = if win { "Victory" } else { "Defeat" };
+    let champion_name = participant["championName"].as_str().unwrap_or("");
 
     let mut team_participants: HashMap<String, &Value> = HashMap::new();
     let mut enemy_participants: HashMap<String, &Value> = HashMap::new();
@@ -81,11 +155,15 @@ pub fn get_match_details(match_info: &Value, summoner_id: &str) -> Option<Value>
         if let (Some(team_p), Some(enemy_p)) =
             (team_participants.get(role), enemy_participants.get(role))
         {
-            let team_stats = extract_participant_stats(team_p);
-            let enemy_stats = extract_participant_stats(enemy_p);
+            let team_stats =
+                add_team_shares(extract_participant_stats(team_p), team_p, participants);
+            let enemy_stats =
+                add_team_shares(extract_participant_stats(enemy_p), enemy_p, participants);
 
             let matchup = serde_json::json!({
                 "role": role,
+                "followed": is_followed(team_p),
+                "enemyFollowed": is_followed(enemy_p),
                 "team": team_stats,
                 "enemy": enemy_stats
             });
@@ -94,49 +172,256 @@ pub fn get_match_details(match_info: &Value, summoner_id: &str) -> Option<Value>
         }
     }
 
+    let team_summary = extract_team_summary(match_info, team_id);
+    let enemy_summary = participants
+        .iter()
+        .map(|p| p["teamId"].as_i64().unwrap_or(0))
+        .find(|p_team_id| *p_team_id != team_id)
+        .map(|enemy_team_id| extract_team_summary(match_info, enemy_team_id))
+        .unwrap_or(Value::Null);
+    let leavers: Vec<String> = find_leavers(match_info)
+        .into_iter()
+        .map(|leaver| describe_leaver(leaver, team_id))
+        .collect();
+    let surrender = participant["gameEndedInSurrender"]
+        .as_bool()
+        .unwrap_or(false);
+    // En Arena, il n'y a pas de lanes : les augments des joueurs suivis remplacent les matchups
+    let arena: Vec<Value> = if queue_id == ARENA_QUEUE_ID {
+        participants
+            .iter()
+            .filter(|p| is_followed(p))
+            .map(|p| {
+                let mut stats = extract_participant_stats(p);
+                stats["placement"] = p["subteamPlacement"].as_u64().unwrap_or(0).into();
+                stats["augments"] = (1..=ARENA_AUGMENT_SLOTS)
+                    .filter_map(|slot| p[format!("playerAugment{}", slot)].as_i64())
+                    .filter(|id| *id != 0)
+                    .collect::<Vec<i64>>()
+                    .into();
+                stats
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
     Some(serde_json::json!({
         "gameMode": game_mode,
         "gameResult": game_result,
         "gameDuration": game_duration_string,
-        "matchups": matchups
+        "gameEnd": match_info["info"]["gameEndTimestamp"].as_i64().unwrap_or(0) / 1000,
+        "championName": champion_name,
+        "teamSummary": team_summary,
+        "enemySummary": enemy_summary,
+        "leavers": leavers,
+        "surrender": surrender,
+        "matchups": matchups,
+        "arena": arena
     }))
 }
 
-/// ⚙️ **Function**: Creates a detailed embed for a player's match performance in Discord.
+/// ⚙️ **Function**: Extracts a team-wide summary of kills, gold and objectives from the match information.
 ///
-/// This asynchronous function generates a `CreateEmbed` object that includes detailed statistics
-/// of a player's match, such as the game mode, result, duration, and a role-by-role comparison
-/// of the player's team versus the enemy team. The embed is enriched with emojis and formatted
-/// data to make it visually appealing for Discord.
+/// This function reads the `teams` block of a match-v5 payload to retrieve the objectives taken by the given team
+/// (towers, dragons, barons and rift heralds), and sums the gold earned by every participant of that team.
 ///
 /// # Parameters:
-/// - `info_json`: A reference to a `Value` (from the `serde_json` crate) containing the match data fetched from the Riot API.
-/// - `player_name`: A string slice representing the player's name, used for the embed's title.
-/// - `collection_emoji`: A MongoDB `Collection` containing emoji mappings, which are used to enhance the embed with role and champion-specific emojis.
+/// - `match_info`: A reference to a `Value` containing the entire match data fetched from the Riot API.
+/// - `team_id`: The identifier of the team to summarize (`100` for blue side, `200` for red side).
 ///
 /// # Returns:
-/// - `CreateEmbed`: Returns a `CreateEmbed` object containing the formatted match data, including role-based comparisons and game metadata, ready to be sent to a Discord channel.
+/// - `Value`: A JSON object containing the team's `kills`, `gold`, `towers`, `dragons`, `barons` and `heralds`.
 ///
 /// # Example:
-/// This function is typically used to send detailed match information to a Discord channel:
+/// ```rust
+/// let summary = extract_team_summary(&match_info, 100);
+/// println!("{} towers destroyed", summary["towers"]);
+/// ```
+///
+/// # Notes:
+/// - If the team cannot be found in the `teams` block (e.g., in modes without objectives), every objective defaults to `0`.
+fn extract_team_summary(match_info: &Value, team_id: i64) -> Value {
+    let empty = vec![];
+    let teams = match_info["info"]["teams"].as_array().unwrap_or(&empty);
+    let objectives = teams
+        .iter()
+        .find(|team| team["teamId"].as_i64().unwrap_or(0) == team_id)
+        .map(|team| &team["objectives"])
+        .unwrap_or(&Value::Null);
+    let gold: u64 = match_info["info"]["participants"]
+        .as_array()
+        .unwrap_or(&empty)
+        .iter()
+        .filter(|p| p["teamId"].as_i64().unwrap_or(0) == team_id)
+        .map(|p| p["goldEarned"].as_u64().unwrap_or(0))
+        .sum();
+
+    serde_json::json!({
+        "kills": objectives["champion"]["kills"].as_u64().unwrap_or(0),
+        "gold": gold,
+        "towers": objectives["tower"]["kills"].as_u64().unwrap_or(0),
+        "dragons": objectives["dragon"]["kills"].as_u64().unwrap_or(0),
+        "barons": objectives["baron"]["kills"].as_u64().unwrap_or(0),
+        "heralds": objectives["riftHerald"]["kills"].as_u64().unwrap_or(0)
+    })
+}
+
+/// ⚙️ **Function**: Compares the followed summoners to their lane opponents at 10 minutes.
+///
+/// # Parameters:
+/// - `match_info`: The match-v5 data of the match.
+/// - `timeline`: The match-v5 timeline of the match.
+/// - `summoner_ids`: The summoner IDs of the followed summoners who played the match.
+///
+/// # Returns:
+/// - `Vec<String>`: One line per followed summoner with a lane opponent, such as
+///   `**Faker** vs Chovy — CS 🟢 +12 · Gold 🟡 +140 · XP 🔴 -420`.
 ///
+/// # Example:
 /// ```rust
-/// let embed = create_embed_loop(&info_json, "PlayerName", collection_emoji).await;
-/// // Send the embed to a Discord channel using your bot's message-sending logic
+/// let laning = laning_report(&match_info, &timeline, &["summoner_id"]);
 /// ```
 ///
 /// # Notes:
-/// - The function begins by extracting key game metadata (game mode, result, and duration) from `info_json`.
-/// - Based on the match result, it selects appropriate emojis and colors for the embed.
-/// - The function then constructs the title and proceeds to iterate over the available role-based matchups, comparing the stats of the player's team with the enemy team for each role (TOP, JUNGLE, MIDDLE, BOTTOM, UTILITY).
-/// - Role and champion names are replaced by their corresponding emojis from the `collection_emoji`, retrieved using the `get_emoji` function.
-/// - The function formats team and enemy stats (kills, deaths, assists, CS, gold, vision score) for each role and adds them as fields in the embed.
-/// - It returns a fully constructed `CreateEmbed` ready to be sent in a Discord message.
-pub async fn create_embed_loop(
-    info_json: &Value,
+/// - The lane opponent is the enemy with the same `teamPosition`; players without a position (e.g., ARAM) are skipped.
+/// - 🟢 means clearly ahead, 🔴 clearly behind and 🟡 even (within 8 CS, 300 gold or 200 XP).
+fn laning_report(match_info: &Value, timeline: &Value, summoner_ids: &[&str]) -> Vec<String> {
+    let empty = vec![];
+    let participants = match_info["info"]["participants"]
+        .as_array()
+        .unwrap_or(&empty);
+    let indicator = |diff: i64, even: i64| {
+        let color = if diff >= even {
+            "🟢"
+        } else if diff <= -even {
+            "🔴"
+        } else {
+            "🟡"
+        };
+        format!("{} {:+}", color, diff)
+    };
+    participants
+        .iter()
+        .filter(|p| summoner_ids.contains(&p["summonerId"].as_str().unwrap_or("")))
+        .filter_map(|player| {
+            let position = player["teamPosition"].as_str().filter(|p| !p.is_empty())?;
+            let opponent = participants.iter().find(|p| {
+                p["teamPosition"].as_str() == Some(position) && p["teamId"] != player["teamId"]
+            })?;
+            let player_stats =
+                frame_stats_at(timeline, player["participantId"].as_i64()?, LANING_MINUTE)?;
+            let opponent_stats =
+                frame_stats_at(timeline, opponent["participantId"].as_i64()?, LANING_MINUTE)?;
+            let name = extract_participant_stats(player)["summonerName"]
+                .as_str()
+                .unwrap_or("Unknown")
+                .to_string();
+            let opponent_name = extract_participant_stats(opponent)["summonerName"]
+                .as_str()
+                .unwrap_or("Unknown")
+                .to_string();
+            Some(format!(
+                "**{}** vs {} — CS {} · Gold {} · XP {}",
+                name,
+                opponent_name,
+                indicator(player_stats.cs - opponent_stats.cs, 8),
+                indicator(player_stats.gold - opponent_stats.gold, 300),
+                indicator(player_stats.xp - opponent_stats.xp, 200)
+            ))
+        })
+        .collect()
+}
+
+/// ⚙️ **Function**: Describes the objective control, counter-jungling and ganks of the followed junglers.
+///
+/// # Parameters:
+/// - `match_info`: The match-v5 data of the match.
+/// - `timeline`: The match-v5 timeline of the match.
+/// - `summoner_ids`: The summoner IDs of the followed summoners who played the match.
+///
+/// # Returns:
+/// - `Vec<String>`: One line per followed jungler, such as
+///   `**Faker** — 🐉 2/3 · 🦀 1/1 · 🟣 1/1 | Invade CS: 14 | Ganks (pre-14): 5 (63% KP)`.
+///
+/// # Notes:
+/// - Objectives read "taken with the player / taken by the team"; epic monsters the team never took are omitted.
+fn jungle_report(match_info: &Value, timeline: &Value, summoner_ids: &[&str]) -> Vec<String> {
+    let empty = vec![];
+    match_info["info"]["participants"]
+        .as_array()
+        .unwrap_or(&empty)
+        .iter()
+        .filter(|p| {
+            summoner_ids.contains(&p["summonerId"].as_str().unwrap_or(""))
+                && p["teamPosition"].as_str() == Some("JUNGLE")
+        })
+        .filter_map(|player| {
+            let stats = jungle_stats(
+                timeline,
+                player["participantId"].as_i64()?,
+                player["teamId"].as_i64()?,
+            );
+            let player_stats = extract_participant_stats(player);
+            let objectives = stats
+                .objectives
+                .iter()
+                .filter(|(_, _, team_total)| *team_total > 0)
+                .map(|(emoji, taken, team_total)| format!("{} {}/{}", emoji, taken, team_total))
+                .collect::<Vec<_>>();
+            let objectives = if objectives.is_empty() {
+                "No epic monster".to_string()
+            } else {
+                objectives.join(" · ")
+            };
+            Some(format!(
+                "**{}** — {} | Invade CS: {} | Ganks (pre-14): {} ({:.0}% KP)",
+                player_stats["summonerName"].as_str().unwrap_or("Unknown"),
+                objectives,
+                player_stats["enemyJungleCs"].as_u64().unwrap_or(0),
+                stats.early_takedowns,
+                stats.early_takedowns as f64 / stats.early_team_kills.max(1) as f64 * 100.0
+            ))
+        })
+        .collect()
+}
+
+/// ⚙️ **Function**: Formats a team summary into a single line for the match notification embed.
+///
+/// # Parameters:
+/// - `label`: The label displayed before the summary (e.g., "🔵 **Your team**").
+/// - `summary`: A JSON object produced by `extract_team_summary`.
+///
+/// # Returns:
+/// - `String`: A line such as `🔵 **Your team**: **25** kills | Gold: 58,3k | 🗼 9 | 🐉 3 | 🟣 1 | 🦀 1`.
+fn format_team_summary(label: &str, summary: &Value) -> String {
+    format!(
+        "{}: **{}** kills | Gold: {} | 🗼 {} | 🐉 {} | 🟣 {} | 🦀 {}",
+        label,
+        summary["kills"].as_u64().unwrap_or(0),
+        format_gold_k(summary["gold"].as_u64().unwrap_or(0)),
+        summary["towers"].as_u64().unwrap_or(0),
+        summary["dragons"].as_u64().unwrap_or(0),
+        summary["barons"].as_u64().unwrap_or(0),
+        summary["heralds"].as_u64().unwrap_or(0)
+    )
+}
+
+/// ⚙️ **Function**: Builds the parts of a match notification shared by every layout.
+///
+/// # Parameters:
+/// - `info_json`: The match details produced by `get_match_details`.
+/// - `player_name`: The followed player's name (or several names joined together), used for the embed's title.
+/// - `theme`: The guild's embed theme, providing the victory/defeat colors and the optional brand footer.
+///
+/// # Returns:
+/// - `(CreateEmbed, Vec<&str>, HashMap<String, &Value>)`: The embed with its title, color, author, thumbnail, brand footer and AFK warnings, the roles
+///   in display order (followed summoners' lanes first), and the matchups indexed by role.
+fn create_embed_loop_base<'a>(
+    info_json: &'a Value,
     player_name: &str,
-    collection_emoji: Collection<EmojiId>,
-) -> CreateEmbed {
+    theme: &EmbedTheme,
+) -> (CreateEmbed, Vec<&'static str>, HashMap<String, &'a Value>) {
     let game_mode = info_json["gameMode"].as_str().unwrap_or("Unknown");
     let game_result = info_json["gameResult"].as_str().unwrap_or("Unknown");
     let game_duration = info_json["gameDuration"].as_str().unwrap_or("00:00");
@@ -150,10 +435,10 @@ pub async fn create_embed_loop(
     } else {
         "https://i.postimg.cc/XJBF0WwS/pngwing-com.png"
     };
-    let color: i32 = if game_result == "Victory" {
-        0x00ff00
+    let color = if game_result == "Victory" {
+        theme.victory()
     } else {
-        0xff0000
+        theme.defeat()
     };
 
     // Construct the embed title
@@ -162,8 +447,8 @@ pub async fn create_embed_loop(
         player_name, game_mode, game_result, game_result_emoji, game_duration
     );
 
-    let roles_order = ["TOP", "JUNGLE", "MIDDLE", "BOTTOM", "UTILITY"];
-    let mut matchups_by_role = std::collections::HashMap::new();
+    let mut roles_order = vec!["TOP", "JUNGLE", "MIDDLE", "BOTTOM", "UTILITY"];
+    let mut matchups_by_role = HashMap::new();
     if let Some(matchups) = info_json["matchups"].as_array() {
         for matchup in matchups {
             if let Some(role) = matchup["role"].as_str() {
@@ -171,95 +456,132 @@ pub async fn create_embed_loop(
             }
         }
     }
+    // Les rôles des joueurs suivis sont toujours affichés en premier
+    roles_order.sort_by_key(|role| {
+        !matchups_by_role.get(*role).is_some_and(|matchup| {
+            matchup["followed"].as_bool().unwrap_or(false)
+                || matchup["enemyFollowed"].as_bool().unwrap_or(false)
+        })
+    });
     let mut embed = CreateEmbed::new()
         .title(title)
         .color(color)
-        .thumbnail(game_result_thumbnail);
+        .author(CreateEmbedAuthor::new(game_result).icon_url(game_result_thumbnail));
+    let champion_name = info_json["championName"].as_str().unwrap_or("");
+    embed = if champion_name.is_empty() {
+        embed.thumbnail(game_result_thumbnail)
+    } else {
+        embed.thumbnail(champion_loading_url(champion_name))
+    };
+    if let Some(footer) = &theme.footer {
+        embed = embed.footer(CreateEmbedFooter::new(footer));
+    }
+    // Les AFK et leavers sont signalés sous le titre, quel que soit le layout
+    if let Some(leavers) = info_json["leavers"].as_array().filter(|l| !l.is_empty()) {
+        let mut lines: Vec<String> = leavers
+            .iter()
+            .filter_map(|leaver| leaver.as_str())
+            .map(|leaver| format!("⚠️ {}", leaver))
+            .collect();
+        if info_json["surrender"].as_bool().unwrap_or(false) {
+            lines.push("🏳️ The game ended in a surrender.".to_string());
+        }
+        embed = embed.description(lines.join("\n"));
+    }
+
+    (embed, roles_order, matchups_by_role)
+}
+
+/// ⚙️ **Function**: Creates a detailed embed for a player's match performance in Discord.
+///
+/// This asynchronous function generates a `CreateEmbed` object that includes detailed statistics
+/// of a player's match, such as the game mode, result, duration, and a role-by-role comparison
+/// of the player's team versus the enemy team. The embed is enriched with emojis and formatted
+/// data to make it visually appealing for Discord.
+///
+/// # Parameters:
+/// - `info_json`: A reference to a `Value` (from the `serde_json` crate) containing the match data fetched from the Riot API.
+/// - `player_name`: A string slice representing the followed player's name (or several names joined together when multiple followed summoners played the match), used for the embed's title.
+/// - `theme`: The guild's embed theme, providing the victory/defeat colors and the optional brand footer.
+/// - `collection_emoji`: A MongoDB `Collection` containing emoji mappings, which are used to enhance the embed with role and champion-specific emojis.
+///
+/// # Returns:
+/// - `CreateEmbed`: Returns a `CreateEmbed` object containing the formatted match data, including role-based comparisons and game metadata, ready to be sent to a Discord channel.
+///
+/// # Example:
+/// This function is typically used to send detailed match information to a Discord channel:
+///
+/// ```rust
+/// let embed = create_embed_loop(&info_json, "PlayerName", &theme, collection_emoji).await;
+/// // Send the embed to a Discord channel using your bot's message-sending logic
+/// ```
+///
+/// # Notes:
+/// - The function begins by extracting key game metadata (game mode, result, and duration) from `info_json`.
+/// - Based on the match result, it selects appropriate emojis and the guild's victory or defeat color for the embed. The victory/defeat image is used as the author icon.
+/// - The thumbnail is the loading-screen art of the followed player's champion from the Data Dragon CDN, falling back to the victory/defeat image if the champion is unknown.
+/// - The function then constructs the title and proceeds to iterate over the available role-based matchups, comparing the stats of the player's team with the enemy team for each role (TOP, JUNGLE, MIDDLE, BOTTOM, UTILITY).
+/// - A team summary (kills, gold, towers, dragons, barons, heralds) is added as the first field, before the per-lane breakdown.
+/// - The followed summoners' lanes are always listed first, and their rows are marked with ⭐ and rendered bold and underlined.
+/// - Role and champion names are replaced by their corresponding emojis from the `collection_emoji`, retrieved using the `get_emoji` function.
+/// - The function formats team and enemy stats (kills, deaths, assists, kill participation, damage share, CS, gold, vision score) for each role and adds them as fields in the embed. Junglers and supports get statistics suited to their role instead (see `select_role_stats`).
+/// - It returns a fully constructed `CreateEmbed` ready to be sent in a Discord message.
+pub async fn create_embed_loop(
+    info_json: &Value,
+    player_name: &str,
+    theme: &EmbedTheme,
+    collection_emoji: Collection<EmojiId>,
+) -> CreateEmbed {
+    let (mut embed, roles_order, matchups_by_role) =
+        create_embed_loop_base(info_json, player_name, theme);
+
+    embed = add_team_summary_field(embed, info_json);
 
     for role in &roles_order {
         if let Some(matchup) = matchups_by_role.get(&role.to_uppercase()) {
             let team_player = &matchup["team"];
             let enemy_player = &matchup["enemy"];
-            let role_label = match *role {
-                "TOP" => format!(
-                    "**{} TOP**\n",
-                    get_emoji(collection_emoji.clone(), "position", "TOP")
-                        .await
-                        .unwrap_or("🔼".to_string())
-                ),
-                "JUNGLE" => format!(
-                    "**{} JUNGLE**\n",
-                    get_emoji(collection_emoji.clone(), "position", "JUNGLE")
-                        .await
-                        .unwrap_or("🌲".to_string())
-                ),
-                "MIDDLE" => format!(
-                    "**{} MIDDLE**\n",
-                    get_emoji(collection_emoji.clone(), "position", "MIDDLE")
-                        .await
-                        .unwrap_or("🛣️".to_string())
-                ),
-                "BOTTOM" => format!(
-                    "**{} BOTTOM**\n",
-                    get_emoji(collection_emoji.clone(), "position", "BOTTOM")
-                        .await
-                        .unwrap_or("🔽".to_string())
-                ),
-                "UTILITY" => format!(
-                    "**{} SUPPORT**\n",
-                    get_emoji(collection_emoji.clone(), "position", "SUPPORT")
-                        .await
-                        .unwrap_or("🛡️".to_string())
-                ),
-                _ => "**UNKNOWN**\n".to_string(),
-            };
+            let is_followed = matchup["followed"].as_bool().unwrap_or(false);
+            let is_enemy_followed = matchup["enemyFollowed"].as_bool().unwrap_or(false);
+            let role_label = format!("**{}**\n", get_role_label(role, &collection_emoji).await);
 
             // Team player stats
+            let team_player_name = team_player["summonerName"].as_str().unwrap_or("Unknown");
+            let team_player_label = if is_followed {
+                format!("⭐ __**{}**__", team_player_name)
+            } else {
+                format!("**{}**", team_player_name)
+            };
             let team_stats = format!(
-                "{} **{}**\nK/D/A: **{}/{}/{}** | CS: **{}** | Gold: {} | Vision: {}",
-                get_emoji(
-                    collection_emoji.clone(),
-                    "champions",
-                    team_player["championName"].as_str().unwrap_or("Unknown")
-                )
-                .await
-                .unwrap_or(
-                    team_player["championName"]
-                        .as_str()
-                        .unwrap_or("Unknown")
-                        .to_string()
-                ),
-                team_player["summonerName"].as_str().unwrap_or("Unknown"),
+                "{} {}\nK/D/A: **{}/{}/{}** | KP: {:.0}% | DMG: {:.0}% | {}",
+                get_champion_emoji(team_player, &collection_emoji).await,
+                team_player_label,
                 team_player["kills"].as_u64().unwrap_or(0),
                 team_player["deaths"].as_u64().unwrap_or(0),
                 team_player["assists"].as_u64().unwrap_or(0),
-                team_player["totalFarm"].as_u64().unwrap_or(0),
-                format_gold_k(team_player["goldEarned"].as_u64().unwrap_or(0)),
-                team_player["visionScore"].as_u64().unwrap_or(0)
+                team_player["killParticipation"].as_f64().unwrap_or(0.0),
+                team_player["damageShare"].as_f64().unwrap_or(0.0),
+                format_role_stats(team_player)
             );
 
             // Enemy player stats
+            let enemy_player_name = enemy_player["summonerName"].as_str().unwrap_or("Unknown");
+            let enemy_player_label = if is_enemy_followed {
+                format!("⭐ __**{}**__", enemy_player_name)
+            } else {
+                format!("**{}**", enemy_player_name)
+            };
+            let enemy_player_label = append_player_hints(enemy_player_label, enemy_player);
             let enemy_stats = format!(
-                "{} **{}**\nK/D/A: **{}/{}/{}** | CS: **{}** | Gold: {} | Vision: {}",
-                get_emoji(
-                    collection_emoji.clone(),
-                    "champions",
-                    enemy_player["championName"].as_str().unwrap_or("Unknown")
-                )
-                .await
-                .unwrap_or(
-                    enemy_player["championName"]
-                        .as_str()
-                        .unwrap_or("Unknown")
-                        .to_string()
-                ),
-                enemy_player["summonerName"].as_str().unwrap_or("Unknown"),
+                "{} {}\nK/D/A: **{}/{}/{}** | KP: {:.0}% | DMG: {:.0}% | {}",
+                get_champion_emoji(enemy_player, &collection_emoji).await,
+                enemy_player_label,
                 enemy_player["kills"].as_u64().unwrap_or(0),
                 enemy_player["deaths"].as_u64().unwrap_or(0),
                 enemy_player["assists"].as_u64().unwrap_or(0),
-                enemy_player["totalFarm"].as_u64().unwrap_or(0),
-                format_gold_k(enemy_player["goldEarned"].as_u64().unwrap_or(0)),
-                enemy_player["visionScore"].as_u64().unwrap_or(0)
+                enemy_player["killParticipation"].as_f64().unwrap_or(0.0),
+                enemy_player["damageShare"].as_f64().unwrap_or(0.0),
+                format_role_stats(enemy_player)
             );
 
             // Combine team and enemy stats
@@ -273,181 +595,1088 @@ pub async fn create_embed_loop(
     embed
 }
 
-/// ⚙️ **Function**: Extracts key participant statistics from a match for a given player.
-///
-/// This function retrieves important statistics for a participant in a League of Legends match, such as their summoner name,
-/// champion name, kills, deaths, assists, total farm, gold earned, and vision score. The extracted stats are returned as a JSON object (`serde_json::Value`).
+/// ⚙️ **Function**: Adds the "TEAM SUMMARY" field (kills, gold and objectives of both teams) to a match notification.
 ///
 /// # Parameters:
-/// - `p`: A reference to a `serde_json::Value` object representing a participant in the match. This object contains all of the participant's stats and data.
+/// - `embed`: The embed to extend.
+/// - `info_json`: The match details produced by `get_match_details`.
 ///
 /// # Returns:
-/// - `Value`: Returns a JSON object containing the player's stats, including their summoner name, champion name, K/D/A (kills, deaths, assists),
-/// total farm (minions and neutral monsters killed), gold earned, gold per minute, and vision score.
-///
-/// # Example:
-/// This function is used to format and extract individual player stats from the match data:
+/// - `CreateEmbed`: The embed with the summary field, or unchanged if the match has no team summaries.
+fn add_team_summary_field(embed: CreateEmbed, info_json: &Value) -> CreateEmbed {
+    if info_json["teamSummary"].is_null() || info_json["enemySummary"].is_null() {
+        return embed;
+    }
+    let summary = format!(
+        "{}\n{}",
+        format_team_summary("🔵 **Your team**", &info_json["teamSummary"]),
+        format_team_summary("🔴 **Enemy team**", &info_json["enemySummary"])
+    );
+    embed.field("**📋 TEAM SUMMARY**", summary, false)
+}
+
+/// ⚙️ **Function**: Returns the emoji and display name of a role (e.g., `<:TOP:123> TOP`, `<:SUPPORT:456> SUPPORT`).
 ///
-/// ```rust
-/// let player_stats = extract_participant_stats(&participant);
-/// println!("{}", player_stats["summonerName"]);
-/// ```
+/// # Parameters:
+/// - `role`: The `teamPosition` of the role (`TOP`, `JUNGLE`, `MIDDLE`, `BOTTOM` or `UTILITY`).
+/// - `collection_emoji`: The MongoDB collection of emojis. A Unicode emoji is used if the lookup fails.
 ///
-/// # Notes:
-/// - The summoner's name is prioritized over their Riot ID game name, but if the summoner name is missing, the Riot ID is used as a fallback.
-/// - Total farm is calculated as the sum of minions killed and neutral monsters killed.
-/// - The stats returned include the summoner's name, champion, K/D/A, farm, gold, gold per minute, and vision score, which are useful for comparing performance across teams.
-fn extract_participant_stats(p: &Value) -> Value {
-    let riot_id_game_name = p["riotIdGameName"].as_str().unwrap_or("Unknown");
-    let summoner_name = if p["summonerName"].as_str().unwrap_or("Unknown").is_empty() {
-        riot_id_game_name
-    } else {
-        p["summonerName"].as_str().unwrap_or("Unknown")
+/// # Returns:
+/// - `String`: The emoji followed by the role name, or `UNKNOWN` for an unknown role.
+async fn get_role_label(role: &str, collection_emoji: &Collection<EmojiId>) -> String {
+    let (name, fallback) = match role {
+        "TOP" => ("TOP", "🔼"),
+        "JUNGLE" => ("JUNGLE", "🌲"),
+        "MIDDLE" => ("MIDDLE", "🛣️"),
+        "BOTTOM" => ("BOTTOM", "🔽"),
+        "UTILITY" => ("SUPPORT", "🛡️"),
+        _ => return "UNKNOWN".to_string(),
     };
-    let champion_name = p["championName"].as_str().unwrap_or("Unknown");
-    let kills = p["kills"].as_u64().unwrap_or(0);
-    let deaths = p["deaths"].as_u64().unwrap_or(0);
-    let assists = p["assists"].as_u64().unwrap_or(0);
-    let total_minions_killed = p["totalMinionsKilled"].as_u64().unwrap_or(0);
-    let neutral_minions_killed = p["neutralMinionsKilled"].as_u64().unwrap_or(0);
-    let total_farm = total_minions_killed + neutral_minions_killed;
-    let gold_earned = p["goldEarned"].as_u64().unwrap_or(0);
-    let vision_score = p["visionScore"].as_u64().unwrap_or(0);
+    let emoji = get_emoji(collection_emoji.clone(), "position", name)
+        .await
+        .unwrap_or(fallback.to_string());
+    format!("{} {}", emoji, name)
+}
 
-    serde_json::json!({
-        "summonerName": summoner_name,
-        "championName": champion_name,
-        "kills": kills,
-        "deaths": deaths,
-        "assists": assists,
-        "totalFarm": total_farm,
-        "goldEarned": gold_earned,
-        "visionScore": vision_score
-    })
+/// ⚙️ **Function**: Returns the emoji of the champion played by a participant.
+///
+/// # Parameters:
+/// - `player`: The participant stats produced by `extract_participant_stats`.
+/// - `collection_emoji`: The MongoDB collection of emojis.
+///
+/// # Returns:
+/// - `String`: The champion emoji, or the champion name if the lookup fails.
+async fn get_champion_emoji(player: &Value, collection_emoji: &Collection<EmojiId>) -> String {
+    let champion_name = player["championName"].as_str().unwrap_or("Unknown");
+    get_emoji(collection_emoji.clone(), "champions", champion_name)
+        .await
+        .unwrap_or(champion_name.to_string())
 }
 
-/// ⚙️ **Function**: Formats the amount of gold earned in a match into a more readable "k" notation when appropriate.
+/// ⚙️ **Function**: Formats the role-specific statistics of a participant's row.
 ///
-/// This function takes an amount of gold as input and formats it into a human-readable string. If the amount is less than 1000,
-/// it returns the gold value as a simple string. If the gold is 1000 or more, it formats the value in "k" notation (e.g., 1500 becomes "1.5k").
+/// # Parameters:
+/// - `player`: The participant stats produced by `extract_participant_stats`.
+///
+/// # Returns:
+/// - `String`: The statistics selected by `select_role_stats`, separated by `|`.
+fn format_role_stats(player: &Value) -> String {
+    player["roleStats"]
+        .as_array()
+        .map(|stats| {
+            stats
+                .iter()
+                .filter_map(|stat| stat.as_str())
+                .collect::<Vec<_>>()
+                .join(" | ")
+        })
+        .unwrap_or_default()
+}
+
+/// ⚙️ **Function**: Appends the hints added to a participant after their name: mastery and previous encounters.
 ///
 /// # Parameters:
-/// - `gold`: A `u64` value representing the amount of gold earned by a player in a match.
+/// - `label`: The formatted name of the participant.
+/// - `player`: The participant stats, with the optional `masteryHint` and `encounterHint`.
 ///
 /// # Returns:
-/// - `String`: A formatted string representing the gold amount. If the amount is less than 1000, it returns the value as is.
-/// For amounts equal to or greater than 1000, it returns a string in "k" notation (e.g., "1k", "1.5k") with a comma used as the decimal separator.
+/// - `String`: The label followed by the hints, e.g. `**Faker** (1.2M pts) · 🔁 faced twice this week, 1W-1L`.
+fn append_player_hints(label: String, player: &Value) -> String {
+    let mut label = label;
+    if let Some(hint) = player["masteryHint"].as_str() {
+        label = format!("{} {}", label, hint);
+    }
+    if let Some(hint) = player["encounterHint"].as_str() {
+        label = format!("{} · 🔁 {}", label, hint);
+    }
+    label
+}
+
+/// ⚙️ **Function**: Formats a participant as a single line: champion emoji, name and K/D/A.
 ///
-/// # Example:
-/// ```rust
-/// let formatted_gold = format_gold_k(1500);
-/// assert_eq!(formatted_gold, "1,5k");
-/// ```
+/// # Parameters:
+/// - `player`: The participant stats produced by `extract_participant_stats`.
+/// - `is_followed`: Whether the participant is a followed summoner, rendered with ⭐ in bold and underlined.
+/// - `collection_emoji`: The MongoDB collection of emojis.
 ///
-/// # Notes:
-/// - For gold values with no fractional part, the result will omit the decimal point (e.g., 1000 will be formatted as "1k" instead of "1.0k").
-/// - The function uses a comma to separate the decimal part, following European formatting conventions.
-fn format_gold_k(gold: u64) -> String {
-    if gold < 1000 {
-        gold.to_string()
+/// # Returns:
+/// - `String`: A line such as `<:Jhin:123> ⭐ __**Faker**__ 7/2/9`, with the hints of enemy laners (see `append_player_hints`).
+async fn format_participant_short(
+    player: &Value,
+    is_followed: bool,
+    collection_emoji: &Collection<EmojiId>,
+) -> String {
+    let name = player["summonerName"].as_str().unwrap_or("Unknown");
+    let label = if is_followed {
+        format!("⭐ __**{}**__", name)
     } else {
-        let gold_f64 = (gold as f64) / 1000.0;
-        if gold_f64.fract() == 0.0 {
-            format!("{}k", gold_f64 as u64)
-        } else {
-            let formatted = format!("{:.1}", gold_f64).replace('.', ",");
-            format!("{}k", formatted)
-        }
-    }
+        format!("**{}**", name)
+    };
+    let label = append_player_hints(label, player);
+    format!(
+        "{} {} {}/{}/{}",
+        get_champion_emoji(player, collection_emoji).await,
+        label,
+        player["kills"].as_u64().unwrap_or(0),
+        player["deaths"].as_u64().unwrap_or(0),
+        player["assists"].as_u64().unwrap_or(0)
+    )
 }
 
-/// ⚙️ **Function**: Retrieves all followed summoners from the database.
+/// ⚙️ **Function**: Creates a compact match notification with a single line per role.
 ///
-/// This asynchronous function queries the "follower_summoner" collection in the "stat-summoner" MongoDB database
-/// to retrieve all documents representing followed summoners. It collects each `SummonerFollowedData` into a vector.
+/// Each line shows the role, then both laners with their champion and K/D/A. The team summary is kept.
 ///
 /// # Parameters:
-/// - `collection`: A reference to the MongoDB collection containing `SummonerFollowedData` documents.
+/// - `info_json`: The match details produced by `get_match_details`.
+/// - `player_name`: The followed player's name (or several names joined together), used for the embed's title.
+/// - `theme`: The guild's embed theme.
+/// - `collection_emoji`: The MongoDB collection of emojis.
 ///
 /// # Returns:
-/// - `Result<Vec<SummonerFollowedData>, mongodb::error::Error>`: A vector of followed summoners if successful, or an error if the query fails.
-///
-/// # ⚠️ Notes:
-/// - Prints an error message in French if a document retrieval fails.
-/// - Ensure that the `SummonerFollowedData` struct aligns with the collection's document structure.
+/// - `CreateEmbed`: The compact notification.
 ///
 /// # Example:
 /// ```rust
-/// let summoners = get_followed_summoners(&collection).await?;
-/// println!("Retrieved {} summoners.", summoners.len());
+/// let embed = create_embed_loop_compact(&info_json, "Faker", &theme, collection_emoji).await;
 /// ```
-pub async fn get_followed_summoners(
-    collection: &Collection<SummonerFollowedData>,
-) -> Result<Vec<SummonerFollowedData>, mongodb::error::Error> {
-    let mut cursor = collection.find(doc! {}).await?;
-    let mut followed_summoners = Vec::new();
-
-    while let Some(result) = cursor.next().await {
-        match result {
-            Ok(followed_summoner) => {
-                followed_summoners.push(followed_summoner);
-            }
-            Err(e) => {
-                log::error!("Erreur lors de la récupération d'un document : {:?}", e);
-            }
+pub async fn create_embed_loop_compact(
+    info_json: &Value,
+    player_name: &str,
+    theme: &EmbedTheme,
+    collection_emoji: Collection<EmojiId>,
+) -> CreateEmbed {
+    let (embed, roles_order, matchups_by_role) =
+        create_embed_loop_base(info_json, player_name, theme);
+    let embed = add_team_summary_field(embed, info_json);
+
+    let mut lines = Vec::new();
+    for role in &roles_order {
+        if let Some(matchup) = matchups_by_role.get(*role) {
+            lines.push(format!(
+                "{} — {} vs {}",
+                get_role_label(role, &collection_emoji).await,
+                format_participant_short(
+                    &matchup["team"],
+                    matchup["followed"].as_bool().unwrap_or(false),
+                    &collection_emoji
+                )
+                .await,
+                format_participant_short(
+                    &matchup["enemy"],
+                    matchup["enemyFollowed"].as_bool().unwrap_or(false),
+                    &collection_emoji
+                )
+                .await
+            ));
         }
     }
-
-    Ok(followed_summoners)
+    if lines.is_empty() {
+        return embed;
+    }
+    embed.field("**⚔️ LANES**", lines.join("\n"), false)
 }
 
-/// ⚙️ **Function**: Processes a followed summoner by checking if their follow time has expired or if they have played a new match.
-///
-/// This asynchronous function handles the logic for a followed summoner. It checks if the follow time has expired and removes the summoner from the database if necessary. If the follow time is still valid, it checks for new matches and updates the summoner's information accordingly.
+/// ⚙️ **Function**: Creates a match notification restricted to the followed summoners' own stats.
+///
+/// # Parameters:
+/// - `info_json`: The match details produced by `get_match_details`.
+/// - `player_name`: The followed player's name (or several names joined together), used for the embed's title.
+/// - `theme`: The guild's embed theme.
+/// - `collection_emoji`: The MongoDB collection of emojis.
+///
+/// # Returns:
+/// - `CreateEmbed`: One field per followed summoner with K/D/A and the statistics of their role (see `select_role_stats`).
+///
+/// # Example:
+/// ```rust
+/// let embed = create_embed_loop_player_only(&info_json, "Faker", &theme, collection_emoji).await;
+/// ```
+///
+/// # Notes:
+/// - Followed summoners of both teams are displayed; the team summary and the other laners are omitted.
+pub async fn create_embed_loop_player_only(
+    info_json: &Value,
+    player_name: &str,
+    theme: &EmbedTheme,
+    collection_emoji: Collection<EmojiId>,
+) -> CreateEmbed {
+    let (mut embed, roles_order, matchups_by_role) =
+        create_embed_loop_base(info_json, player_name, theme);
+
+    for role in &roles_order {
+        let Some(matchup) = matchups_by_role.get(*role) else {
+            continue;
+        };
+        for (side, flag) in [("team", "followed"), ("enemy", "enemyFollowed")] {
+            if !matchup[flag].as_bool().unwrap_or(false) {
+                continue;
+            }
+            let player = &matchup[side];
+            let value = format!(
+                "{}\n{}",
+                format_participant_short(player, true, &collection_emoji).await,
+                format_role_stats(player)
+            );
+            embed = embed.field(
+                format!("**{}**", get_role_label(role, &collection_emoji).await),
+                value,
+                false,
+            );
+        }
+    }
+    embed
+}
+
+/// ⚙️ **Function**: Creates the notification of an Arena game, with the placement and augments of the followed summoners.
+///
+/// # Parameters:
+/// - `info_json`: The match details produced by `get_match_details`.
+/// - `player_name`: The followed player's name (or several names joined together), used for the embed's title.
+/// - `theme`: The guild's embed theme.
+/// - `collection_emoji`: The MongoDB collection of emojis.
+///
+/// # Returns:
+/// - `CreateEmbed`: One field per followed summoner, e.g.:
+/// ```text
+/// 🏟️ 2nd place
+/// <:Jhin:123> ⭐ __**Faker**__ 7/2/9
+/// 🎲 Jeweled Gauntlet · Master of Duality · Typhoon
+/// ```
+///
+/// # Notes:
+/// - The augment names come from Community Dragon (see `arena_augment_names`); their IDs are shown if it cannot be
+///   reached.
+pub async fn create_embed_loop_arena(
+    info_json: &Value,
+    player_name: &str,
+    theme: &EmbedTheme,
+    collection_emoji: Collection<EmojiId>,
+) -> CreateEmbed {
+    let (mut embed, _, _) = create_embed_loop_base(info_json, player_name, theme);
+    let client = reqwest::Client::new();
+    for player in info_json["arena"].as_array().into_iter().flatten() {
+        let ids: Vec<i64> = player["augments"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_i64)
+            .collect();
+        let augments = match arena_augment_names(&client, &ids).await {
+            Ok(names) => names,
+            Err(e) => {
+                log::error!("Error fetching the Arena augments: {:?}", e);
+                ids.iter().map(|id| format!("Augment #{}", id)).collect()
+            }
+        };
+        let augments = if augments.is_empty() {
+            "No augment".to_string()
+        } else {
+            augments.join(" · ")
+        };
+        embed = embed.field(
+            format!(
+                "**🏟️ {} place**",
+                format_placement(player["placement"].as_u64().unwrap_or(0))
+            ),
+            format!(
+                "{}\n🎲 {}",
+                format_participant_short(player, true, &collection_emoji).await,
+                augments
+            ),
+            false,
+        );
+    }
+    embed
+}
+
+/// ⚙️ **Function**: Formats an Arena placement as an ordinal number.
+///
+/// # Returns:
+/// - `String`: e.g. `1st`, `2nd`, `3rd`, `4th`, or `?` if the placement is unknown (`0`).
+fn format_placement(placement: u64) -> String {
+    match placement {
+        0 => "?".to_string(),
+        1 => "1st".to_string(),
+        2 => "2nd".to_string(),
+        3 => "3rd".to_string(),
+        _ => format!("{}th", placement),
+    }
+}
+
+/// ⚙️ **Function**: Creates a match notification with the given layout.
+///
+/// # Parameters:
+/// - `layout`: The layout selected by `resolve_embed_layout`.
+/// - `info_json`: The match details produced by `get_match_details`.
+/// - `player_name`: The followed player's name (or several names joined together), used for the embed's title.
+/// - `theme`: The guild's embed theme.
+/// - `collection_emoji`: The MongoDB collection of emojis.
+///
+/// # Returns:
+/// - `CreateEmbed`: The notification built by `create_embed_loop`, `create_embed_loop_compact` or `create_embed_loop_player_only`.
+///
+/// # Notes:
+/// - Arena games have no lanes, so they are always rendered by `create_embed_loop_arena`.
+pub async fn create_embed_for_layout(
+    layout: EmbedLayout,
+    info_json: &Value,
+    player_name: &str,
+    theme: &EmbedTheme,
+    collection_emoji: Collection<EmojiId>,
+) -> CreateEmbed {
+    if info_json["arena"]
+        .as_array()
+        .is_some_and(|players| !players.is_empty())
+    {
+        return create_embed_loop_arena(info_json, player_name, theme, collection_emoji).await;
+    }
+    match layout {
+        EmbedLayout::Detailed => {
+            create_embed_loop(info_json, player_name, theme, collection_emoji).await
+        }
+        EmbedLayout::Compact => {
+            create_embed_loop_compact(info_json, player_name, theme, collection_emoji).await
+        }
+        EmbedLayout::PlayerOnly => {
+            create_embed_loop_player_only(info_json, player_name, theme, collection_emoji).await
+        }
+    }
+}
+
+/// ⚙️ **Function**: Extracts key participant statistics from a match for a given player.
+///
+/// This function retrieves important statistics for a participant in a League of Legends match, such as their summoner name,
+/// champion name, kills, deaths, assists, total farm, gold earned, and vision score. The extracted stats are returned as a JSON object (`serde_json::Value`).
+///
+/// # Parameters:
+/// - `p`: A reference to a `serde_json::Value` object representing a participant in the match. This object contains all of the participant's stats and data.
+///
+/// # Returns:
+/// - `Value`: Returns a JSON object containing the player's stats, including their summoner name, champion name, K/D/A (kills, deaths, assists),
+/// total farm (minions and neutral monsters killed), gold earned, gold per minute, and vision score.
+///
+/// # Example:
+/// This function is used to format and extract individual player stats from the match data:
+///
+/// ```rust
+/// let player_stats = extract_participant_stats(&participant);
+/// println!("{}", player_stats["summonerName"]);
+/// ```
+///
+/// # Notes:
+/// - The summoner's name is prioritized over their Riot ID game name, but if the summoner name is missing, the Riot ID is used as a fallback.
+/// - Total farm is calculated as the sum of minions killed and neutral monsters killed.
+/// - The stats returned include the summoner's name, champion, K/D/A, farm, gold, gold per minute, and vision score, which are useful for comparing performance across teams.
+/// - `enemyJungleCs` is the number of monsters killed in the enemy jungle.
+/// - `roleStats` holds the statistics displayed after the K/D/A, chosen for the player's role by `select_role_stats`.
+fn extract_participant_stats(p: &Value) -> Value {
+    let riot_id_game_name = p["riotIdGameName"].as_str().unwrap_or("Unknown");
+    let summoner_name = if p["summonerName"].as_str().unwrap_or("Unknown").is_empty() {
+        riot_id_game_name
+    } else {
+        p["summonerName"].as_str().unwrap_or("Unknown")
+    };
+    let champion_name = p["championName"].as_str().unwrap_or("Unknown");
+    let kills = p["kills"].as_u64().unwrap_or(0);
+    let deaths = p["deaths"].as_u64().unwrap_or(0);
+    let assists = p["assists"].as_u64().unwrap_or(0);
+    let total_minions_killed = p["totalMinionsKilled"].as_u64().unwrap_or(0);
+    let neutral_minions_killed = p["neutralMinionsKilled"].as_u64().unwrap_or(0);
+    let total_farm = total_minions_killed + neutral_minions_killed;
+    let gold_earned = p["goldEarned"].as_u64().unwrap_or(0);
+    let vision_score = p["visionScore"].as_u64().unwrap_or(0);
+    let enemy_jungle_cs = p["totalEnemyJungleMinionsKilled"].as_u64().unwrap_or(0);
+
+    serde_json::json!({
+        "summonerName": summoner_name,
+        "puuid": p["puuid"].as_str().unwrap_or(""),
+        "championId": p["championId"].as_i64().unwrap_or(0),
+        "championName": champion_name,
+        "kills": kills,
+        "deaths": deaths,
+        "assists": assists,
+        "totalFarm": total_farm,
+        "goldEarned": gold_earned,
+        "visionScore": vision_score,
+        "enemyJungleCs": enemy_jungle_cs,
+        "roleStats": select_role_stats(p)
+    })
+}
+
+/// ⚙️ **Function**: Selects the statistics displayed for a participant depending on their role.
+///
+/// # Parameters:
+/// - `p`: A participant of the match-v5 payload.
+///
+/// # Returns:
+/// - `Vec<String>`: The formatted statistics:
+///     - `UTILITY`: vision score per minute, wards killed, control wards, heals and shields on teammates, and crowd control.
+///     - `JUNGLE`: CS, gold and monsters killed in the enemy jungle.
+///     - Other roles: CS, gold and vision score.
+///
+/// # Example:
+/// ```rust
+/// let stats = select_role_stats(&participant);
+/// assert_eq!(stats[0], "Vision/min: **2.1**");
+/// ```
+///
+/// # Notes:
+/// - The crowd control is `timeCCingOthers`, the crowd-control score shown at the end of a game.
+fn select_role_stats(p: &Value) -> Vec<String> {
+    let farm = format!(
+        "CS: **{}**",
+        p["totalMinionsKilled"].as_u64().unwrap_or(0)
+            + p["neutralMinionsKilled"].as_u64().unwrap_or(0)
+    );
+    let gold = format!(
+        "Gold: {}",
+        format_gold_k(p["goldEarned"].as_u64().unwrap_or(0))
+    );
+    match p["teamPosition"].as_str().unwrap_or("") {
+        "UTILITY" => {
+            let minutes = (p["timePlayed"].as_f64().unwrap_or(0.0) / 60.0).max(1.0);
+            let heal_shield = p["totalHealsOnTeammates"].as_u64().unwrap_or(0)
+                + p["totalDamageShieldedOnTeammates"].as_u64().unwrap_or(0);
+            vec![
+                format!(
+                    "Vision/min: **{:.1}**",
+                    p["visionScore"].as_f64().unwrap_or(0.0) / minutes
+                ),
+                format!("Wards killed: {}", p["wardsKilled"].as_u64().unwrap_or(0)),
+                format!(
+                    "Control wards: {}",
+                    p["detectorWardsPlaced"].as_u64().unwrap_or(0)
+                ),
+                format!("Heal/Shield: {}", format_gold_k(heal_shield)),
+                format!("CC: {}", p["timeCCingOthers"].as_u64().unwrap_or(0)),
+            ]
+        }
+        "JUNGLE" => vec![
+            farm,
+            gold,
+            format!(
+                "Invade CS: {}",
+                p["totalEnemyJungleMinionsKilled"].as_u64().unwrap_or(0)
+            ),
+        ],
+        _ => vec![
+            farm,
+            gold,
+            format!("Vision: {}", p["visionScore"].as_u64().unwrap_or(0)),
+        ],
+    }
+}
+
+/// ⚙️ **Function**: Adds the kill participation and damage share of a participant to their stats.
+///
+/// # Parameters:
+/// - `stats`: The participant stats produced by `extract_participant_stats`.
+/// - `p`: The participant in the match-v5 payload.
+/// - `participants`: Every participant of the match, used to compute the totals of the participant's team.
+///
+/// # Returns:
+/// - `Value`: The stats with `killParticipation` ((kills + assists) / team kills) and `damageShare` (damage to champions /
+///   team damage to champions), both as percentages.
+///
+/// # Example:
+/// ```rust
+/// let stats = add_team_shares(extract_participant_stats(&participant), &participant, participants);
+/// println!("KP: {:.0}%", stats["killParticipation"].as_f64().unwrap_or(0.0));
+/// ```
+fn add_team_shares(mut stats: Value, p: &Value, participants: &[Value]) -> Value {
+    let team = participants
+        .iter()
+        .filter(|other| other["teamId"] == p["teamId"]);
+    let (team_kills, team_damage) = team.fold((0, 0), |(kills, damage), other| {
+        (
+            kills + other["kills"].as_u64().unwrap_or(0),
+            damage + other["totalDamageDealtToChampions"].as_u64().unwrap_or(0),
+        )
+    });
+    let takedowns = p["kills"].as_u64().unwrap_or(0) + p["assists"].as_u64().unwrap_or(0);
+    let damage = p["totalDamageDealtToChampions"].as_u64().unwrap_or(0);
+    stats["killParticipation"] =
+        serde_json::json!(takedowns as f64 / team_kills.max(1) as f64 * 100.0);
+    stats["damageShare"] = serde_json::json!(damage as f64 / team_damage.max(1) as f64 * 100.0);
+    stats
+}
+
+/// ⚙️ **Function**: Tags each enemy laner of a match notification with their mastery on the champion they played.
+///
+/// # Parameters:
+/// - `info_json`: The match details produced by `get_match_details`. A `masteryHint` is added to the `enemy` of each matchup.
+/// - `mongo_client`: The MongoDB client, used for the `champion_masteries` cache.
+/// - `client`: The HTTP client used to query the Riot API.
+/// - `region_str`: The platform of the followed summoner (e.g., `euw1`), shared by every player of the match.
+/// - `riot_api_key`: The Riot API key.
+///
+/// # Example:
+/// ```rust
+/// add_mastery_hints(&mut info_json, &mongo_client, &client, "euw1", riot_api_key).await;
+/// ```
+///
+/// # Notes:
+/// - The hint reads `(first-time?)` under 5 000 points, or the points such as `(1.2M pts)` otherwise.
+/// - Followed enemies are not tagged, and a failed lookup simply leaves the laner without a hint.
+async fn add_mastery_hints(
+    info_json: &mut Value,
+    mongo_client: &mongodb::Client,
+    client: &reqwest::Client,
+    region_str: &str,
+    riot_api_key: &str,
+) {
+    let Some(matchups) = info_json["matchups"].as_array_mut() else {
+        return;
+    };
+    for matchup in matchups {
+        if matchup["enemyFollowed"].as_bool().unwrap_or(false) {
+            continue;
+        }
+        let enemy = &mut matchup["enemy"];
+        let puuid = enemy["puuid"].as_str().unwrap_or("").to_string();
+        let champion_id = enemy["championId"].as_i64().unwrap_or(0);
+        if puuid.is_empty() || champion_id == 0 {
+            continue;
+        }
+        let points = get_cached_mastery_points(
+            mongo_client,
+            client,
+            region_str,
+            &puuid,
+            champion_id,
+            riot_api_key,
+        )
+        .await;
+        if let Some(points) = points {
+            enemy["masteryHint"] = Value::String(format_mastery_hint(points));
+        }
+    }
+}
+
+/// ⚙️ **Function**: Returns the mastery points of a player on a champion, from the cache when it is recent enough.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client, used to read and refresh the `champion_masteries` collection.
+/// - `client`: The HTTP client used to query the Riot API.
+/// - `region_str`: The platform of the player (e.g., `euw1`).
+/// - `puuid`: The PUUID of the player.
+/// - `champion_id`: The numeric ID of the champion.
+/// - `riot_api_key`: The Riot API key.
+///
+/// # Returns:
+/// - `Option<i64>`: The mastery points, or `None` if they are neither cached nor available from the Riot API.
+///
+/// # Notes:
+/// - Points are cached for 7 days per player and champion, after which MongoDB removes them (see `ensure_indexes`).
+///   A stale value not removed yet is used when the Riot API fails.
+async fn get_cached_mastery_points(
+    mongo_client: &mongodb::Client,
+    client: &reqwest::Client,
+    region_str: &str,
+    puuid: &str,
+    champion_id: i64,
+    riot_api_key: &str,
+) -> Option<i64> {
+    let collection = mongo_client
+        .database("stat-summoner")
+        .collection::<ChampionMasteryCacheData>("champion_masteries");
+    let filter = doc! { "puuid": puuid, "champion_id": champion_id };
+    let cached = collection.find_one(filter.clone()).await.ok().flatten();
+    let now = mongodb::bson::DateTime::now();
+    if let Some(cached) = &cached {
+        if (now.timestamp_millis() - cached.fetched_at.timestamp_millis()) / 1000
+            < MASTERY_CACHE_SECS
+        {
+            return Some(cached.points);
+        }
+    }
+    match get_champion_mastery_points(client, region_str, puuid, champion_id, riot_api_key).await {
+        Ok(points) => {
+            let entry = ChampionMasteryCacheData {
+                puuid: puuid.to_string(),
+                champion_id,
+                points,
+                fetched_at: now,
+            };
+            if let Err(e) = collection.replace_one(filter, &entry).upsert(true).await {
+                log::error!("Failed to cache the mastery points of {}: {}", puuid, e);
+            }
+            Some(points)
+        }
+        Err(e) => {
+            log::warn!("Failed to fetch the mastery points of {}: {}", puuid, e);
+            cached.map(|cached| cached.points)
+        }
+    }
+}
+
+/// ⚙️ **Function**: Formats mastery points as a hint on how experienced a player is on their champion.
+///
+/// # Parameters:
+/// - `points`: The mastery points of the player on the champion.
+///
+/// # Returns:
+/// - `String`: `(first-time?)` under 5 000 points, otherwise the points such as `(350k pts)` or `(1.2M pts)`.
+fn format_mastery_hint(points: i64) -> String {
+    if points < FIRST_TIME_MAX_POINTS {
+        "(first-time?)".to_string()
+    } else if points < 1_000_000 {
+        format!("({}k pts)", points / 1000)
+    } else {
+        format!("({:.1}M pts)", points as f64 / 1_000_000.0)
+    }
+}
+
+/// ⚙️ **Function**: Builds the archive record of a match played by followed summoners of a guild.
+///
+/// # Parameters:
+/// - `match_info`: The match-v5 data of the match.
+/// - `match_id`: The ID of the match.
+/// - `followed_summoners`: The followed summoners of the guild who played the match.
+///
+/// # Returns:
+/// - `Option<MatchRecordData>`: The record, with the PUUID, team and champion of every participant and the bans of both teams,
+///   or `None` if no followed summoner is found in the match.
+fn build_match_record(
+    match_info: &Value,
+    match_id: &str,
+    followed_summoners: &[SummonerFollowedData],
+) -> Option<MatchRecordData> {
+    let info = &match_info["info"];
+    let participants = info["participants"].as_array()?;
+    let followed_puuids: Vec<String> = followed_summoners
+        .iter()
+        .map(|summoner| summoner.puuid.clone())
+        .collect();
+    let followed = participants.iter().find(|p| {
+        followed_puuids
+            .iter()
+            .any(|puuid| p["puuid"].as_str() == Some(puuid.as_str()))
+    })?;
+    let played_at = info["gameEndTimestamp"]
+        .as_i64()
+        .map(|timestamp| timestamp / 1000)
+        .unwrap_or_else(|| Utc::now().timestamp());
+    Some(MatchRecordData {
+        match_id: match_id.to_string(),
+        guild_id: followed_summoners.first()?.guild_id.clone(),
+        queue_id: info["queueId"].as_i64().unwrap_or(-1),
+        played_at,
+        started_at: game_start(match_info),
+        team_id: followed["teamId"].as_i64().unwrap_or(0),
+        win: followed["win"].as_bool().unwrap_or(false),
+        followed_puuids,
+        participants: participants
+            .iter()
+            .map(|p| MatchParticipantData {
+                puuid: p["puuid"].as_str().unwrap_or("").to_string(),
+                team_id: p["teamId"].as_i64().unwrap_or(0),
+                champion_name: p["championName"].as_str().unwrap_or("").to_string(),
+            })
+            .collect(),
+        bans: match_bans(match_info)
+            .into_iter()
+            .map(|(team_id, champion_id)| MatchBanData {
+                team_id,
+                champion_id,
+            })
+            .collect(),
+    })
+}
+
+/// ⚙️ **Function**: Saves the archive record of a match in the `match_records` collection.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client.
+/// - `record`: The record built by `build_match_record`.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Ok if the record was saved, or an error if the database operation fails.
+///
+/// # Notes:
+/// - A match is recorded once per guild: notifying it again replaces the previous record.
+async fn save_match_record(
+    mongo_client: &mongodb::Client,
+    record: &MatchRecordData,
+) -> Result<(), Error> {
+    let collection = mongo_client
+        .database("stat-summoner")
+        .collection::<MatchRecordData>("match_records");
+    collection
+        .replace_one(
+            doc! { "match_id": &record.match_id, "guild_id": &record.guild_id },
+            record,
+        )
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Tags the enemy laners of a match notification that the guild's followed summoners already faced.
+///
+/// # Parameters:
+/// - `info_json`: The match details produced by `get_match_details`. An `encounterHint` is added to the `enemy` of each matchup faced before.
+/// - `mongo_client`: The MongoDB client, used to read the `match_records` collection.
+/// - `record`: The record of the match being notified, built by `build_match_record`.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Ok once the hints are added, or an error if the archive cannot be read.
+///
+/// # Example:
+/// ```rust
+/// add_previous_encounters(&mut info_json, &mongo_client, &record).await?;
+/// // info_json["matchups"][0]["enemy"]["encounterHint"] == "faced twice this week, 1W-1L"
+/// ```
+///
+/// # Notes:
+/// - Only the guild's matches of the last 7 days where the player was on the other team are counted, the current match excluded.
+/// - The record (e.g. `1W-1L`) is given from the point of view of the guild's followed summoners.
+async fn add_previous_encounters(
+    info_json: &mut Value,
+    mongo_client: &mongodb::Client,
+    record: &MatchRecordData,
+) -> Result<(), Error> {
+    let Some(matchups) = info_json["matchups"].as_array_mut() else {
+        return Ok(());
+    };
+    let enemy_puuids: Vec<String> = matchups
+        .iter()
+        .filter(|matchup| !matchup["enemyFollowed"].as_bool().unwrap_or(false))
+        .filter_map(|matchup| matchup["enemy"]["puuid"].as_str())
+        .filter(|puuid| !puuid.is_empty())
+        .map(|puuid| puuid.to_string())
+        .collect();
+    if enemy_puuids.is_empty() {
+        return Ok(());
+    }
+    let collection = mongo_client
+        .database("stat-summoner")
+        .collection::<MatchRecordData>("match_records");
+    let previous_matches: Vec<MatchRecordData> = collection
+        .find(doc! {
+            "guild_id": &record.guild_id,
+            "match_id": { "$ne": &record.match_id },
+            "played_at": { "$gte": record.played_at - ENCOUNTER_WINDOW_SECS },
+            "participants.puuid": { "$in": &enemy_puuids },
+        })
+        .await?
+        .try_collect()
+        .await?;
+
+    for matchup in matchups {
+        let Some(puuid) = matchup["enemy"]["puuid"].as_str().map(str::to_string) else {
+            continue;
+        };
+        if !enemy_puuids.contains(&puuid) {
+            continue;
+        }
+        let (games, wins) = previous_matches
+            .iter()
+            .filter(|previous| {
+                previous
+                    .participants
+                    .iter()
+                    .any(|p| p.puuid == puuid && p.team_id != previous.team_id)
+            })
+            .fold((0, 0), |(games, wins), previous| {
+                (games + 1, wins + previous.win as u32)
+            });
+        if games > 0 {
+            matchup["enemy"]["encounterHint"] = Value::String(format_encounter_hint(games, wins));
+        }
+    }
+    Ok(())
+}
+
+/// ⚙️ **Function**: Formats the previous encounters with an enemy, e.g. `faced twice this week, 1W-1L`.
+///
+/// # Parameters:
+/// - `games`: The number of previous games against the enemy.
+/// - `wins`: The number of these games won by the guild's followed summoners.
+///
+/// # Returns:
+/// - `String`: The hint shown next to the enemy's name.
+fn format_encounter_hint(games: u32, wins: u32) -> String {
+    let times = match games {
+        1 => "once".to_string(),
+        2 => "twice".to_string(),
+        _ => format!("{} times", games),
+    };
+    format!("faced {} this week, {}W-{}L", times, wins, games - wins)
+}
+
+/// ⚙️ **Function**: Formats the amount of gold earned in a match into a more readable "k" notation when appropriate.
+///
+/// This function takes an amount of gold as input and formats it into a human-readable string. If the amount is less than 1000,
+/// it returns the gold value as a simple string. If the gold is 1000 or more, it formats the value in "k" notation (e.g., 1500 becomes "1.5k").
+///
+/// # Parameters:
+/// - `gold`: A `u64` value representing the amount of gold earned by a player in a match.
+///
+/// # Returns:
+/// - `String`: A formatted string representing the gold amount. If the amount is less than 1000, it returns the value as is.
+/// For amounts equal to or greater than 1000, it returns a string in "k" notation (e.g., "1k", "1.5k") with a comma used as the decimal separator.
+///
+/// # Example:
+/// ```rust
+/// let formatted_gold = format_gold_k(1500);
+/// assert_eq!(formatted_gold, "1,5k");
+/// ```
+///
+/// # Notes:
+/// - For gold values with no fractional part, the result will omit the decimal point (e.g., 1000 will be formatted as "1k" instead of "1.0k").
+/// - The function uses a comma to separate the decimal part, following European formatting conventions.
+fn format_gold_k(gold: u64) -> String {
+    if gold < 1000 {
+        gold.to_string()
+    } else {
+        let gold_f64 = (gold as f64) / 1000.0;
+        if gold_f64.fract() == 0.0 {
+            format!("{}k", gold_f64 as u64)
+        } else {
+            let formatted = format!("{:.1}", gold_f64).replace('.', ",");
+            format!("{}k", formatted)
+        }
+    }
+}
+
+/// ⚙️ **Function**: Streams the followed summoners from the database, grouped by player.
+///
+/// This asynchronous function reads the distinct PUUIDs of the "follower_summoner" collection in the "stat-summoner"
+/// MongoDB database, then yields the follows of each player one at a time, instead of loading the whole collection in
+/// memory.
+///
+/// # Parameters:
+/// - `collection`: A reference to the MongoDB collection containing `SummonerFollowedData` documents.
+///
+/// # Returns:
+/// - `Result<impl Stream<Item = Vec<SummonerFollowedData>>, mongodb::error::Error>`: A stream of the follows of each
+///   player (same PUUID, one per guild following them), or an error if the PUUIDs cannot be read.
+///
+/// # ⚠️ Notes:
+/// - The follows of a player are read with a short query on the `puuid` index (see `ensure_indexes`), so no cursor
+///   stays open while the Riot API is rate limited.
+/// - If the follows of a player cannot be read, the error is logged and only this player is skipped.
+/// - Ensure that the `SummonerFollowedData` struct aligns with the collection's document structure.
+///
+/// # Example:
+/// ```rust
+/// let mut players = stream_followed_players(&collection).await?;
+/// while let Some(follows) = players.next().await {
+///     println!("{} is followed by {} guilds.", follows[0].name, follows.len());
+/// }
+/// ```
+pub async fn stream_followed_players(
+    collection: &Collection<SummonerFollowedData>,
+) -> Result<impl Stream<Item = Vec<SummonerFollowedData>> + '_, mongodb::error::Error> {
+    let puuids = collection.distinct("puuid", doc! {}).await?;
+    Ok(stream::iter(puuids).filter_map(move |puuid| async move {
+        let follows: Result<Vec<SummonerFollowedData>, _> = async {
+            collection
+                .find(doc! { "puuid": &puuid })
+                .await?
+                .try_collect()
+                .await
+        }
+        .await;
+        match follows {
+            Ok(follows) if !follows.is_empty() => Some(follows),
+            Ok(_) => None,
+            Err(e) => {
+                log::error!(
+                    "Erreur lors de la récupération des suivis de {} : {:?}",
+                    puuid,
+                    e
+                );
+                None
+            }
+        }
+    }))
+}
+
+/// ⚙️ **Function**: Processes every follow of a player: removes the expired ones and finds the new match to notify.
+///
+/// A player can be followed by several guilds. Their match list and live game are fetched once for all the follows,
+/// then the new match is fanned out to each follow that has not seen it yet.
 ///
 /// # Parameters:
 /// - `collection`: A reference to a MongoDB `Collection<SummonerFollowedData>` that stores the followed summoners' data.
-/// - `followed_summoner`: A reference to a `SummonerFollowedData` struct containing the summoner's information, including their follow duration and last match details.
+/// - `follows`: The follows of a single player (same PUUID), in every guild handled by this instance.
 /// - `riot_api_key`: A string slice containing the Riot Games API key for authenticating the API request.
-/// - `http`: An `Arc<Http>` object used to send messages via the Discord API.
-/// - `collection_emojis`: A MongoDB `Collection` containing emoji mappings, used to enrich the Discord embeds with custom emojis for roles and champions.
 ///
 /// # Returns:
-/// - `Result<(), Error>`: Returns `Ok(())` if the summoner was successfully processed (either by removing them from the database or updating their match info), or an error if something went wrong.
+/// - `Result<Vec<(SummonerFollowedData, String)>, Error>`: The follows that must be notified, each with the ID of the
+///   new match, or an error if something went wrong.
 ///
 /// # Example:
-/// This function is typically called as part of a loop or scheduled task that checks the status of followed summoners:
-///
 /// ```rust
-/// let result = process_followed_summoner(collection, &followed_summoner, riot_api_key, http.clone(), collection_emojis).await;
-/// if result.is_err() {
-///     // Handle error (e.g., log failure or retry)
+/// for (followed_summoner, match_id) in process_followed_player(&collection, follows, riot_api_key).await? {
+///     // Queue the notification for this match
 /// }
 /// ```
 ///
 /// # Notes:
-/// - The function begins by checking if the follow time for the summoner has expired using the `is_follow_time_expired` function.
-/// - If the follow time has expired, the summoner is removed from the MongoDB collection by calling `delete_follower`.
-/// - If the summoner is still being followed, the function calls `update_follower_if_new_match` to check for new matches and potentially send an update to the associated Discord channel.
-/// - This function ensures that summoners are only followed for the specified duration and that Discord channels are updated with relevant match information during the follow period.
-pub async fn process_followed_summoner(
+/// - Expired follows are removed from the MongoDB collection by calling `delete_follower`, and the expiration is
+///   recorded in the audit log.
+/// - The latest match is fetched once, then `update_follower_if_new_match` compares it with each follow. The match
+///   details needed by the notification filters are fetched at most once as well.
+/// - The live game of the player is tracked with `track_live_game` only when one of the guilds following them has a
+///   live status channel. A spectator error is only logged so it never delays the notifications.
+/// - An error on one follow is logged and skips it, so the notifications of the follows already updated are kept.
+/// - Notifications are not sent here: the caller groups the new matches per guild so that a game played by several
+///   followed summoners is only posted once.
+pub async fn process_followed_player(
     collection: &Collection<SummonerFollowedData>,
-    followed_summoner: &SummonerFollowedData,
+    follows: Vec<SummonerFollowedData>,
     riot_api_key: &str,
-    http: Arc<Http>,
-    collection_emojis: Collection<EmojiId>,
-) -> Result<(), Error> {
-    if is_follow_time_expired(followed_summoner) {
-        delete_follower(collection, followed_summoner).await?;
-    } else {
-        update_follower_if_new_match(
+) -> Result<Vec<(SummonerFollowedData, String)>, Error> {
+    let mut active_follows = Vec::new();
+    for followed_summoner in follows {
+        if is_follow_time_expired(&followed_summoner) {
+            delete_follower(collection, &followed_summoner).await?;
+            record_follow_event(
+                collection.client(),
+                FollowAuditEvent::Expired,
+                &followed_summoner,
+                None,
+            )
+            .await;
+        } else {
+            active_follows.push(followed_summoner);
+        }
+    }
+    let Some(player) = active_follows.first() else {
+        return Ok(Vec::new());
+    };
+
+    let client = reqwest::Client::new();
+    let latest_match_id = get_latest_match_id(&client, &player.puuid, riot_api_key).await?;
+    // Le spectateur n'est interrogé que pour les serveurs qui affichent les joueurs en partie
+    if has_live_status_channel(collection.client(), &active_follows).await {
+        if let Err(e) = track_live_game(collection.client(), player, riot_api_key).await {
+            log::error!("Failed to track the live game of {}: {:?}", player.puuid, e);
+        }
+    }
+    // Un joueur sans aucune partie n'a pas encore de match à notifier
+    let Some(latest_match_id) = latest_match_id else {
+        return Ok(Vec::new());
+    };
+
+    let mut match_info = None;
+    let mut notifications = Vec::new();
+    for followed_summoner in active_follows {
+        // Une erreur sur un suivi ne doit pas perdre les notifications des suivis déjà mis à jour
+        match update_follower_if_new_match(
             collection,
-            followed_summoner,
+            &client,
+            &followed_summoner,
+            &latest_match_id,
+            &mut match_info,
             riot_api_key,
-            http,
-            collection_emojis,
         )
-        .await?;
+        .await
+        {
+            Ok(true) => notifications.push((followed_summoner, latest_match_id.clone())),
+            Ok(false) => {}
+            Err(e) => log::error!(
+                "Failed to check the new match of {} in guild {}: {:?}",
+                followed_summoner.puuid,
+                followed_summoner.guild_id,
+                e
+            ),
+        }
+    }
+    Ok(notifications)
+}
+
+/// ⚙️ **Function**: Checks whether one of the guilds of some follows has a live status channel.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client, used for the guild settings.
+/// - `follows`: The follows of a player.
+///
+/// # Returns:
+/// - `bool`: `true` if at least one of the guilds shows the followed players in game (see `update_live_status_channels`).
+///   A database error is logged and counts as `false`.
+async fn has_live_status_channel(
+    mongo_client: &mongodb::Client,
+    follows: &[SummonerFollowedData],
+) -> bool {
+    let guild_ids: Vec<&str> = follows
+        .iter()
+        .map(|followed_summoner| followed_summoner.guild_id.as_str())
+        .collect();
+    match guild_settings_collection(mongo_client)
+        .count_documents(doc! { "guild_id": { "$in": guild_ids }, "live_status": { "$ne": null } })
+        .await
+    {
+        Ok(count) => count > 0,
+        Err(e) => {
+            log::error!("Failed to read the live status channels: {}", e);
+            false
+        }
+    }
+}
+
+/// ⚙️ **Function**: Records the game a followed summoner is playing, for the live status channels.
+///
+/// The game seen by the spectator is kept in the `live_games` collection, and removed once the player is not in game
+/// anymore.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client, used for the `live_games` collection.
+/// - `followed_summoner`: The followed summoner to check.
+/// - `riot_api_key`: The Riot API key.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Ok once the live game is tracked, or an error if the Riot API or the database fails.
+///
+/// # Example:
+/// ```rust
+/// track_live_game(&mongo_client, &followed_summoner, riot_api_key).await?;
+/// ```
+///
+/// # Notes:
+/// - The state is kept per player, so a player followed on several servers is only tracked once.
+/// - The start of the game is kept as well, for the live status channels (see `update_live_status_channels`).
+async fn track_live_game(
+    mongo_client: &mongodb::Client,
+    followed_summoner: &SummonerFollowedData,
+    riot_api_key: &str,
+) -> Result<(), Error> {
+    let live_games = mongo_client
+        .database("stat-summoner")
+        .collection::<LiveGameData>("live_games");
+    let puuid = &followed_summoner.puuid;
+    let now = Utc::now().timestamp();
+    let client = reqwest::Client::new();
+    match get_active_game(&client, &followed_summoner.region, puuid, riot_api_key).await? {
+        Some(game) => {
+            let match_id = format!(
+                "{}_{}",
+                game["platformId"].as_str().unwrap_or(""),
+                game["gameId"].as_i64().unwrap_or(0)
+            );
+            // gameStartTime vaut 0 tant que la partie est en chargement
+            let started_at = match game["gameStartTime"].as_i64().unwrap_or(0) / 1000 {
+                0 => now,
+                started_at => started_at,
+            };
+            let live_game = LiveGameData {
+                puuid: puuid.clone(),
+                match_id,
+                seen_at: now,
+                started_at,
+            };
+            live_games
+                .replace_one(doc! { "puuid": puuid }, &live_game)
+                .upsert(true)
+                .await?;
+        }
+        None => {
+            live_games.delete_one(doc! { "puuid": puuid }).await?;
+        }
     }
     Ok(())
 }
@@ -520,182 +1749,988 @@ async fn delete_follower(
     Ok(())
 }
 
-/// ⚙️ **Function**: Updates a followed summoner's last match ID and sends a Discord update if a new match is detected.
+/// ⚙️ **Function**: Updates a followed summoner's last match ID if a new match is detected.
+///
+/// This asynchronous function compares the latest match of the player with the last match ID of a follow. If they
+/// differ, it updates the follow in the MongoDB collection and tells whether the new match must be notified.
+///
+/// # Parameters:
+/// - `collection`: A reference to a MongoDB `Collection<SummonerFollowedData>` that stores the followed summoners' data.
+/// - `client`: The HTTP client used to query the Riot API.
+/// - `followed_summoner`: A reference to a `SummonerFollowedData` struct containing the follow, including its last match ID and notification filter.
+/// - `latest_match_id`: The latest match of the player, fetched once for all their follows by `process_followed_player`.
+/// - `match_info`: The match-v5 data of the latest match, fetched on first use and shared between the follows of the player.
+/// - `riot_api_key`: A string slice containing the Riot Games API key for authenticating the API request.
+///
+/// # Returns:
+/// - `Result<bool, Error>`: `true` if a new match was detected and must be notified, `false` if the latest match is already known or filtered out, or an error if something went wrong.
+///
+/// # Example:
+/// ```rust
+/// let notify = update_follower_if_new_match(&collection, &client, &followed_summoner, &latest_match_id, &mut match_info, riot_api_key).await?;
+/// ```
+///
+/// # Notes:
+/// - If the latest match ID is different from the stored `last_match_id`, the function updates the MongoDB collection with it,
+///   once the match data needed by the filters was fetched: a Riot error leaves the match to be checked again at the next poll.
+/// - Matches of a queue excluded by the queue filter of the follow (see `QueueFilter`), or that do not pass its
+///   notification filter (see `matches_notification_filter`), are not notified.
+/// - The caller is responsible for sending the notification with `send_match_update_to_discord`.
+async fn update_follower_if_new_match(
+    collection: &Collection<SummonerFollowedData>,
+    client: &reqwest::Client,
+    followed_summoner: &SummonerFollowedData,
+    latest_match_id: &str,
+    match_info: &mut Option<Value>,
+    riot_api_key: &str,
+) -> Result<bool, Error> {
+    if followed_summoner.last_match_id == latest_match_id {
+        return Ok(false);
+    }
+    let notify = if followed_summoner.notification_filter == NotificationFilter::All
+        && followed_summoner.queue_filter == QueueFilter::All
+    {
+        true
+    } else {
+        let match_info = match match_info {
+            Some(match_info) => match_info,
+            None => {
+                match_info.insert(get_matchs_info(client, latest_match_id, riot_api_key).await?)
+            }
+        };
+        // Le filtre de file est vérifié avant le filtre de notification, qui peut appeler l'API Riot
+        let queue_id = match_info["info"]["queueId"].as_i64().unwrap_or(-1);
+        followed_summoner.queue_filter.accepts(queue_id)
+            && matches_notification_filter(
+                collection.client(),
+                client,
+                followed_summoner,
+                match_info,
+                riot_api_key,
+            )
+            .await
+    };
+    // Le match n'est marqué comme vu qu'une fois les filtres passés : une erreur Riot le laisse au prochain passage
+    collection
+        .update_one(
+            doc! {
+            "puuid": &followed_summoner.puuid,
+            "guild_id": &followed_summoner.guild_id
+            },
+            doc! { "$set": { "last_match_id": latest_match_id } },
+        )
+        .await?;
+    Ok(notify)
+}
+
+/// ⚙️ **Function**: Checks whether a match passes the notification filter chosen for a follow.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client, used for the rank snapshots of the `Notable` filter.
+/// - `client`: The HTTP client used to query the Riot API.
+/// - `followed_summoner`: The follow, with its `notification_filter`.
+/// - `match_info`: The match-v5 data of the new match.
+/// - `riot_api_key`: The Riot API key.
+///
+/// # Returns:
+/// - `bool`: `true` if the match must be notified.
+///
+/// # Notes:
+/// - A game is notable when the player got a pentakill, a KDA of 10 or more (with at least 10 kills and assists), or
+///   climbed to a higher division or tier in a ranked game (compared to their last recorded rank).
+/// - If the player cannot be found in the match, it is notified.
+async fn matches_notification_filter(
+    mongo_client: &mongodb::Client,
+    client: &reqwest::Client,
+    followed_summoner: &SummonerFollowedData,
+    match_info: &Value,
+    riot_api_key: &str,
+) -> bool {
+    let info = &match_info["info"];
+    let Some(participant) = info["participants"].as_array().and_then(|participants| {
+        participants
+            .iter()
+            .find(|p| p["puuid"].as_str() == Some(followed_summoner.puuid.as_str()))
+    }) else {
+        return true;
+    };
+    let queue_type = match info["queueId"].as_i64() {
+        Some(420) => Some("RANKED_SOLO_5x5"),
+        Some(440) => Some("RANKED_FLEX_SR"),
+        _ => None,
+    };
+    let win = participant["win"].as_bool().unwrap_or(false);
+    match followed_summoner.notification_filter {
+        NotificationFilter::All => true,
+        NotificationFilter::RankedOnly => queue_type.is_some(),
+        NotificationFilter::LongGames => {
+            info["gameDuration"].as_i64().unwrap_or(0) >= LONG_GAME_MIN_DURATION
+        }
+        NotificationFilter::WinsOnly => win,
+        NotificationFilter::LossesOnly => !win,
+        NotificationFilter::Notable => {
+            let stat = |name: &str| participant[name].as_i64().unwrap_or(0);
+            let takedowns = stat("kills") + stat("assists");
+            let kda = takedowns as f64 / stat("deaths").max(1) as f64;
+            if stat("pentaKills") > 0 || (kda >= NOTABLE_KDA && takedowns >= 10) {
+                return true;
+            }
+            match queue_type {
+                Some(queue_type) if win => {
+                    is_promotion(
+                        mongo_client,
+                        client,
+                        followed_summoner,
+                        queue_type,
+                        riot_api_key,
+                    )
+                    .await
+                }
+                _ => false,
+            }
+        }
+    }
+}
+
+/// ⚙️ **Function**: Checks whether a player climbed to a higher division or tier since their last recorded rank.
+///
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client, used for the rank snapshots.
+/// - `client`: The HTTP client used to query the Riot API.
+/// - `followed_summoner`: The followed summoner.
+/// - `queue_type`: The ranked queue of the game (e.g. `RANKED_SOLO_5x5`).
+/// - `riot_api_key`: The Riot API key.
+///
+/// # Returns:
+/// - `bool`: `true` if the current rank is higher than the last recorded one, ignoring LP. `false` when no rank was recorded yet.
+///
+/// # Notes:
+/// - The current rank is recorded as a new snapshot, so the next game is compared to it.
+async fn is_promotion(
+    mongo_client: &mongodb::Client,
+    client: &reqwest::Client,
+    followed_summoner: &SummonerFollowedData,
+    queue_type: &str,
+    riot_api_key: &str,
+) -> bool {
+    let puuid = &followed_summoner.puuid;
+    let previous =
+        match get_rank_snapshot_at(mongo_client, puuid, queue_type, Utc::now().timestamp()).await {
+            Ok(previous) => previous,
+            Err(e) => {
+                log::error!("Failed to read the rank snapshots of {}: {}", puuid, e);
+                None
+            }
+        };
+    let rank_info = match get_rank_info(
+        client,
+        &followed_summoner.region,
+        &followed_summoner.summoner_id,
+        riot_api_key,
+    )
+    .await
+    {
+        Ok(rank_info) => rank_info,
+        Err(e) => {
+            log::error!("Failed to fetch the rank of {}: {:?}", puuid, e);
+            return false;
+        }
+    };
+    let Some(entry) = rank_info
+        .iter()
+        .find(|entry| entry.get("queueType").and_then(Value::as_str) == Some(queue_type))
+    else {
+        return false;
+    };
+    if let Err(e) = record_rank_snapshot(mongo_client, puuid, entry).await {
+        log::error!("Failed to record the rank of {}: {}", puuid, e);
+    }
+    let field = |name: &str| entry.get(name).and_then(Value::as_str).unwrap_or("");
+    match (
+        previous.and_then(|previous| rank_score(&previous.tier, &previous.division, 0)),
+        rank_score(field("tier"), field("rank"), 0),
+    ) {
+        (Some(previous), Some(current)) => current > previous,
+        _ => false,
+    }
+}
+
+/// ⚙️ **Function**: Fetches the latest match ID for a given summoner using their PUUID.
+///
+/// This asynchronous function retrieves the most recent match ID for a summoner by making a request to the Riot API.
+/// It uses the summoner's `puuid` to query their match history and returns the match ID of the most recent game.
+///
+/// # Parameters:
+/// - `client`: A reference to the `reqwest::Client`, used to make HTTP requests to the Riot API.
+/// - `puuid`: A string slice representing the summoner's PUUID (a unique identifier for each player in Riot's system).
+/// - `riot_api_key`: A string slice representing the Riot API key, used for authorized requests.
+///
+/// # Returns:
+/// - `Result<Option<String>, Error>`: Returns the latest match ID as a string if successful, `None` if the summoner has not played any
+///   match yet, or an error if the request or retrieval fails.
+///
+/// # Example:
+/// This function is typically used to get the latest match ID for a summoner in order to check for new matches:
+///
+/// ```rust
+/// let Some(latest_match_id) = get_latest_match_id(&client, puuid, riot_api_key).await? else { return Ok(Vec::new()) };
+/// ```
+///
+/// # Notes:
+/// - The function calls `get_matchs_id` to retrieve the match history and then returns the first match in the list, which corresponds to the most recent match.
+/// - The `get_matchs_id` function is expected to return a vector of match IDs, from which the latest match (the first one) is extracted and returned.
+/// - The history is empty for a summoner who has not played yet (e.g. followed with `/followuser`): the player is then skipped.
+async fn get_latest_match_id(
+    client: &reqwest::Client,
+    puuid: &str,
+    riot_api_key: &str,
+) -> Result<Option<String>, Error> {
+    let matches = get_matchs_id(client, puuid, riot_api_key, 1).await?;
+    Ok(matches.into_iter().next())
+}
+
+/// ⚙️ **Function**: Sends a match update to a specific Discord channel for one or several followed summoners.
+///
+/// This asynchronous function fetches match information from the Riot API, formats the details into a single embed
+/// highlighting every followed summoner who played the match, and sends it as a message to the Discord channel of the first summoner.
+///
+/// # Parameters:
+/// - `followed_summoners`: A slice of `SummonerFollowedData` from the same guild who played the match. Their names are used in the embed title and the channel of the first one receives the message.
+/// - `match_id`: A string slice representing the match ID, used to fetch match details from the Riot API.
+/// - `riot_api_key`: A string slice containing the Riot Games API key for authenticating the API request.
+/// - `http`: An `Arc<Http>` object used to send messages via the Discord API.
+/// - `collection_emojis`: A MongoDB `Collection` containing emoji mappings, used to add custom emojis to the embed for roles and champions.
+/// - `champions`: The champion lookups, used to find the emojis of the banned champions.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns `Ok(())` if the match update was successfully sent to the Discord channel, or an error if something went wrong.
+///
+/// # Example:
+/// This function is typically called after detecting that a followed summoner has completed a match:
+///
+/// ```rust
+/// let result = send_match_update_to_discord(&followed_summoners, match_id, riot_api_key, http.clone(), collection_emojis, &champions).await;
+/// if result.is_err() {
+///     // Handle error (e.g., log failure or retry)
+/// }
+/// ```
+///
+/// # Notes:
+/// - The function creates an HTTP client using `reqwest` to fetch match information from the Riot API.
+/// - It retrieves detailed match data using the `get_matchs_info` and `get_match_details` functions.
+/// - The function constructs a `CreateEmbed` object using `create_embed_for_layout`, with the guild's theme and the layout of the follow or, failing that, of the guild (see `resolve_embed_layout`).
+/// - When several followed summoners of the same guild played the match, a single embed is sent instead of one per summoner.
+/// - The embed is sent as a message to the Discord channel of the first followed summoner.
+/// - The match details are built and the match is archived by `prepare_match_details`; unsupported game modes are not notified.
+/// - Games of 10 minutes or more get a "Laning" field comparing the followed summoners to their lane opponents at 10 minutes (see `laning_report`),
+///   and a "Jungle" field with the objective control and ganks of the followed junglers (see `jungle_report`).
+/// - When the guild enabled it (`/settings goldgraph`), games longer than 20 minutes also get the gold difference graph (see `create_gold_graph`).
+/// - When the guild enabled it (`/settings bans`), a "Bans" field lists the bans of both teams (see `ban_emojis`).
+/// - The Discord message is built using `CreateMessage` and sent asynchronously to the appropriate channel using the Discord API.
+pub async fn send_match_update_to_discord(
+    followed_summoners: &[SummonerFollowedData],
+    match_id: &str,
+    riot_api_key: &str,
+    http: Arc<Http>,
+    collection_emojis: Collection<EmojiId>,
+    champions: &RwLock<ChampionIndex>,
+) -> Result<(), Error> {
+    let Some(first_summoner) = followed_summoners.first() else {
+        return Ok(());
+    };
+    let client = reqwest::Client::new();
+    let info = get_matchs_info(&client, match_id, riot_api_key).await?;
+    let summoner_ids: Vec<&str> = followed_summoners
+        .iter()
+        .map(|summoner| summoner.summoner_id.as_str())
+        .collect();
+    let Some(info_json) = prepare_match_details(
+        followed_summoners,
+        match_id,
+        &info,
+        collection_emojis.client(),
+        &client,
+        riot_api_key,
+    )
+    .await
+    else {
+        return Ok(());
+    };
+    let player_names = followed_summoners
+        .iter()
+        .map(|summoner| summoner.name.as_str())
+        .collect::<Vec<&str>>()
+        .join(" & ");
+    let channel_id = serenity::model::id::ChannelId::new(first_summoner.channel_id);
+    let settings =
+        match get_guild_settings(collection_emojis.client(), &first_summoner.guild_id).await {
+            Ok(settings) => settings,
+            Err(e) => {
+                log::error!(
+                    "Failed to read the settings of guild {}: {}",
+                    first_summoner.guild_id,
+                    e
+                );
+                GuildSettingsData::default()
+            }
+        };
+    let layout = resolve_embed_layout(&settings, followed_summoners);
+    let mut embed = create_embed_for_layout(
+        layout,
+        &info_json,
+        &player_names,
+        &settings.theme,
+        collection_emojis.clone(),
+    )
+    .await;
+    let team_id = info["info"]["participants"]
+        .as_array()
+        .and_then(|participants| {
+            participants
+                .iter()
+                .find(|p| summoner_ids.contains(&p["summonerId"].as_str().unwrap_or("")))
+        })
+        .and_then(|p| p["teamId"].as_i64())
+        .unwrap_or(100);
+    // Ligne compacte des bans, si le serveur l'a activée
+    if settings.show_bans {
+        let bans = ban_emojis(&info, team_id, &*champions.read().await, collection_emojis).await;
+        if let Some((ally_bans, enemy_bans)) = bans {
+            embed = embed.field(
+                "**🚫 BANS**",
+                format!("{} | {}", ally_bans, enemy_bans),
+                false,
+            );
+        }
+    }
+    // La timeline sert au rapport de phase de lane et au graphique de l'or
+    let game_duration = info["info"]["gameDuration"].as_u64().unwrap_or(0);
+    let timeline = if game_duration >= LANING_MINUTE as u64 * 60 {
+        match get_match_timeline(&client, match_id, riot_api_key).await {
+            Ok(timeline) => Some(timeline),
+            Err(e) => {
+                log::error!("Error fetching the timeline of {}: {:?}", match_id, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    if let Some(timeline) = &timeline {
+        let laning = laning_report(&info, timeline, &summoner_ids);
+        if !laning.is_empty() {
+            embed = embed.field(
+                format!("**🛣️ LANING ({} MIN)**", LANING_MINUTE),
+                laning.join("\n"),
+                false,
+            );
+        }
+        let jungle = jungle_report(&info, timeline, &summoner_ids);
+        if !jungle.is_empty() {
+            embed = embed.field("**🌲 JUNGLE**", jungle.join("\n"), false);
+        }
+    }
+    let mut builder = CreateMessage::new().add_embed(embed);
+    // Graphique de l'or pour les parties de plus de 20 minutes, si le serveur l'a activé
+    if let Some(timeline) = timeline
+        .as_ref()
+        .filter(|_| settings.gold_graph && game_duration >= GOLD_GRAPH_MIN_DURATION)
+    {
+        match create_gold_graph(timeline, team_id, settings.theme.info()) {
+            Ok((gold_embed, attachment)) => {
+                builder = builder.add_embed(gold_embed).add_file(attachment);
+            }
+            Err(e) => log::error!("Error rendering the gold graph of {}: {:?}", match_id, e),
+        }
+    }
+    let _ = send_message(&http, channel_id, builder).await;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Builds the match details of a notification, with the hints on the enemy laners, and archives the match.
 ///
-/// This asynchronous function checks if a followed summoner has played a new match. If a new match is detected,
-/// it updates the summoner's last match ID in the MongoDB collection and sends a match update to the appropriate Discord channel.
+/// # Parameters:
+/// - `followed_summoners`: The followed summoners of the guild who played the match.
+/// - `match_id`: The ID of the match.
+/// - `info`: The match-v5 data of the match.
+/// - `mongo_client`: The MongoDB client.
+/// - `client`: The HTTP client used to query the Riot API.
+/// - `riot_api_key`: The Riot API key.
+///
+/// # Returns:
+/// - `Option<Value>`: The match details produced by `get_match_details`, or `None` if the game mode is not supported.
+///
+/// # Notes:
+/// - Enemy laners are tagged with their mastery on the champion they played (see `add_mastery_hints`).
+/// - The match is archived in `match_records` and in the `played_games` of each followed summoner, and enemies already
+///   faced by the guild's followed summoners this week are tagged (see `add_previous_encounters`).
+async fn prepare_match_details(
+    followed_summoners: &[SummonerFollowedData],
+    match_id: &str,
+    info: &Value,
+    mongo_client: &mongodb::Client,
+    client: &reqwest::Client,
+    riot_api_key: &str,
+) -> Option<Value> {
+    let first_summoner = followed_summoners.first()?;
+    let summoner_ids: Vec<&str> = followed_summoners
+        .iter()
+        .map(|summoner| summoner.summoner_id.as_str())
+        .collect();
+    let mut info_json = get_match_details(info, &summoner_ids)?;
+    add_mastery_hints(
+        &mut info_json,
+        mongo_client,
+        client,
+        &first_summoner.region,
+        riot_api_key,
+    )
+    .await;
+    if let Some(record) = build_match_record(info, match_id, followed_summoners) {
+        if let Err(e) = add_previous_encounters(&mut info_json, mongo_client, &record).await {
+            log::error!(
+                "Failed to read the previous encounters of {}: {}",
+                match_id,
+                e
+            );
+        }
+        if let Err(e) = save_match_record(mongo_client, &record).await {
+            log::error!("Failed to archive the match {}: {}", match_id, e);
+        }
+    }
+    let puuids: Vec<&str> = followed_summoners
+        .iter()
+        .map(|summoner| summoner.puuid.as_str())
+        .collect();
+    if let Err(e) = archive_played_game(mongo_client, match_id, info, &puuids).await {
+        log::error!("Failed to archive the games of {}: {}", match_id, e);
+    }
+    Some(info_json)
+}
+
+/// ⚙️ **Function**: Sends the queued matches of a guild as a single message per channel, one field per game.
 ///
 /// # Parameters:
-/// - `collection`: A reference to a MongoDB `Collection<SummonerFollowedData>` that stores the followed summoners' data.
-/// - `followed_summoner`: A reference to a `SummonerFollowedData` struct containing the summoner's information, including their PUUID, summoner ID, and last match ID.
-/// - `riot_api_key`: A string slice containing the Riot Games API key for authenticating the API request.
-/// - `http`: An `Arc<Http>` object used to send messages via the Discord API.
-/// - `collection_emojis`: A MongoDB `Collection` containing emoji mappings, used to enhance the Discord embed with custom emojis for roles and champions.
+/// - `notifications`: The queued notifications of the guild, oldest first.
+/// - `riot_api_key`: The Riot API key.
+/// - `http`: The Discord HTTP client used to post the messages.
+/// - `collection_emojis`: The MongoDB collection of emojis.
 ///
 /// # Returns:
-/// - `Result<(), Error>`: Returns `Ok(())` if the last match ID was successfully updated and the match update was sent to Discord, or an error if something went wrong.
+/// - `Result<(), Error>`: Always `Ok`, the matches that cannot be fetched are skipped.
 ///
 /// # Example:
-/// This function is typically called periodically to check if a followed summoner has played a new match:
-///
-/// ```rust
-/// let result = update_follower_if_new_match(collection, &followed_summoner, riot_api_key, http.clone(), collection_emojis).await;
-/// if result.is_err() {
-///     // Handle error (e.g., log failure or retry)
-/// }
+/// ```text
+/// 🕒 Latest games of followed players
+/// ✅ Faker — Victory
+/// Ranked Solo · 31:20 · <t:1625000000:R>
+/// <:Ahri:123> Ahri 12/2/8
 /// ```
 ///
 /// # Notes:
-/// - The function begins by creating an HTTP client using `reqwest` and fetching the latest match ID for the summoner using the `get_latest_match_id` function.
-/// - If the new match ID is different from the stored `last_match_id`, the function updates the MongoDB collection with the new match ID.
-/// - Once the database is updated, the function calls `send_match_update_to_discord` to send a match update to the Discord channel associated with the summoner.
-/// - This function ensures that the Discord server is notified whenever the summoner completes a new match, keeping followers updated in real time.
-async fn update_follower_if_new_match(
-    collection: &Collection<SummonerFollowedData>,
-    followed_summoner: &SummonerFollowedData,
+/// - The games are grouped by the channel of their follow, 25 games per embed at most.
+/// - Each game is archived like a regular notification (see `prepare_match_details`).
+/// - A match that cannot be fetched is logged and left out of the batch, so it never blocks the other games.
+pub async fn send_match_batch_to_discord(
+    notifications: &[QueuedNotificationData],
     riot_api_key: &str,
     http: Arc<Http>,
     collection_emojis: Collection<EmojiId>,
 ) -> Result<(), Error> {
-    let puuid = &followed_summoner.puuid;
-    let summoner_id = &followed_summoner.summoner_id;
-    let last_match_id = &followed_summoner.last_match_id;
-    let guild_id = &followed_summoner.guild_id;
+    let Some(guild_id) = notifications.first().map(|n| n.guild_id.as_str()) else {
+        return Ok(());
+    };
     let client = reqwest::Client::new();
-
-    let match_id_from_riot = get_latest_match_id(&client, puuid, riot_api_key).await?;
-
-    if last_match_id != &match_id_from_riot {
-        collection
-            .update_one(
-                doc! {
-                "puuid": puuid,
-                "guild_id": guild_id
-                },
-                doc! { "$set": { "last_match_id": &match_id_from_riot } },
-            )
-            .await?;
-        send_match_update_to_discord(
-            followed_summoner,
-            summoner_id,
-            &match_id_from_riot,
+    let mongo_client = collection_emojis.client().clone();
+    let theme = match get_guild_settings(&mongo_client, guild_id).await {
+        Ok(settings) => settings.theme,
+        Err(_) => EmbedTheme::default(),
+    };
+    let mut fields_by_channel: Vec<(u64, Vec<(String, String)>)> = Vec::new();
+    for notification in notifications {
+        let Some(channel_id) = notification.summoners.first().map(|s| s.channel_id) else {
+            continue;
+        };
+        // Un match introuvable est ignoré, sinon tout le lot serait réessayé indéfiniment
+        let info = match get_matchs_info(&client, &notification.match_id, riot_api_key).await {
+            Ok(info) => info,
+            Err(e) => {
+                log::error!(
+                    "Skipping match {} of the notification batch of guild {}: {:?}",
+                    notification.match_id,
+                    guild_id,
+                    e
+                );
+                continue;
+            }
+        };
+        let Some(info_json) = prepare_match_details(
+            &notification.summoners,
+            &notification.match_id,
+            &info,
+            &mongo_client,
+            &client,
             riot_api_key,
-            http,
-            collection_emojis,
         )
-        .await?;
+        .await
+        else {
+            continue;
+        };
+        let player_names = notification
+            .summoners
+            .iter()
+            .map(|summoner| summoner.name.as_str())
+            .collect::<Vec<&str>>()
+            .join(" & ");
+        let result = info_json["gameResult"].as_str().unwrap_or("");
+        let name = format!(
+            "{} {} — {}",
+            if result == "Victory" { "✅" } else { "❌" },
+            player_names,
+            result
+        );
+        let mut lines = vec![format!(
+            "{} · {} · {}",
+            info_json["gameMode"].as_str().unwrap_or("Unknown"),
+            info_json["gameDuration"].as_str().unwrap_or(""),
+            discord_timestamp(info_json["gameEnd"].as_i64().unwrap_or(0), 'R')
+        )];
+        for matchup in info_json["matchups"].as_array().into_iter().flatten() {
+            if matchup["followed"].as_bool().unwrap_or(false) {
+                let player = &matchup["team"];
+                lines.push(format!(
+                    "{} {} {}/{}/{}",
+                    get_champion_emoji(player, &collection_emojis).await,
+                    player["championName"].as_str().unwrap_or(""),
+                    player["kills"].as_u64().unwrap_or(0),
+                    player["deaths"].as_u64().unwrap_or(0),
+                    player["assists"].as_u64().unwrap_or(0)
+                ));
+            }
+        }
+        for player in info_json["arena"].as_array().into_iter().flatten() {
+            lines.push(format!(
+                "{} {} {}/{}/{} · {} place",
+                get_champion_emoji(player, &collection_emojis).await,
+                player["championName"].as_str().unwrap_or(""),
+                player["kills"].as_u64().unwrap_or(0),
+                player["deaths"].as_u64().unwrap_or(0),
+                player["assists"].as_u64().unwrap_or(0),
+                format_placement(player["placement"].as_u64().unwrap_or(0))
+            ));
+        }
+        match fields_by_channel
+            .iter_mut()
+            .find(|(id, _)| *id == channel_id)
+        {
+            Some((_, fields)) => fields.push((name, lines.join("\n"))),
+            None => fields_by_channel.push((channel_id, vec![(name, lines.join("\n"))])),
+        }
+    }
+    for (channel_id, fields) in fields_by_channel {
+        for chunk in fields.chunks(25) {
+            let embed = CreateEmbed::default()
+                .title("🕒 Latest games of followed players")
+                .color(theme.info())
+                .fields(
+                    chunk
+                        .iter()
+                        .map(|(name, value)| (name.clone(), value.clone(), false)),
+                )
+                .footer(CreateEmbedFooter::new(theme.footer_text("")))
+                .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+            let _ = send_message(
+                &http,
+                serenity::ChannelId::new(channel_id),
+                CreateMessage::new().add_embed(embed),
+            )
+            .await;
+        }
     }
     Ok(())
 }
 
-/// ⚙️ **Function**: Fetches the latest match ID for a given summoner using their PUUID.
+/// ⚙️ **Function**: Checks whether the notifications of a guild must be queued instead of posted right away.
 ///
-/// This asynchronous function retrieves the most recent match ID for a summoner by making a request to the Riot API.
-/// It uses the summoner's `puuid` to query their match history and returns the match ID of the most recent game.
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client.
+/// - `guild_id`: The ID of the guild.
+///
+/// # Returns:
+/// - `bool`: `true` if the guild is in its quiet hours or batches its notifications. A guild whose settings cannot be
+///   read gets its notifications right away.
+pub async fn is_notification_held(mongo_client: &mongodb::Client, guild_id: &str) -> bool {
+    match get_guild_settings(mongo_client, guild_id).await {
+        Ok(settings) => {
+            is_in_quiet_hours(&settings) || settings.notification_batch.minutes().is_some()
+        }
+        Err(e) => {
+            log::error!("Failed to read the settings of guild {}: {}", guild_id, e);
+            false
+        }
+    }
+}
+
+/// ⚙️ **Function**: Queues the notification of a match until the quiet hours of its guild end or its next batch is posted.
 ///
 /// # Parameters:
-/// - `client`: A reference to the `reqwest::Client`, used to make HTTP requests to the Riot API.
-/// - `puuid`: A string slice representing the summoner's PUUID (a unique identifier for each player in Riot's system).
-/// - `riot_api_key`: A string slice representing the Riot API key, used for authorized requests.
+/// - `mongo_client`: The MongoDB client.
+/// - `guild_id`: The ID of the guild.
+/// - `match_id`: The ID of the match to notify.
+/// - `summoners`: The followed summoners of the guild who played the match.
 ///
 /// # Returns:
-/// - `Result<String, Error>`: Returns the latest match ID as a string if successful, or an error if the request or retrieval fails.
+/// - `Result<(), Error>`: An error if the notification cannot be queued.
 ///
-/// # Example:
-/// This function is typically used to get the latest match ID for a summoner in order to check for new matches:
+/// # Notes:
+/// - The followed summoners are stored with the notification, so it is still posted if their follow expires meanwhile.
+pub async fn queue_notification(
+    mongo_client: &mongodb::Client,
+    guild_id: &str,
+    match_id: &str,
+    summoners: Vec<SummonerFollowedData>,
+) -> Result<(), Error> {
+    let notification = QueuedNotificationData {
+        guild_id: guild_id.to_string(),
+        match_id: match_id.to_string(),
+        summoners,
+        queued_at: Utc::now().timestamp(),
+        attempts: 0,
+    };
+    mongo_client
+        .database("stat-summoner")
+        .collection::<QueuedNotificationData>("queued_notifications")
+        .replace_one(
+            doc! { "guild_id": guild_id, "match_id": match_id },
+            &notification,
+        )
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Posts the queued notifications of the guilds whose quiet hours are over or whose batch is due.
 ///
-/// ```rust
-/// let latest_match_id = get_latest_match_id(&client, puuid, riot_api_key).await?;
-/// ```
+/// # Parameters:
+/// - `mongo_client`: The MongoDB client.
+/// - `riot_api_key`: The Riot API key.
+/// - `http`: The Discord HTTP client used to post the notifications.
+/// - `collection_emojis`: The MongoDB collection of emojis.
+/// - `champions`: The champion lookups, used for the bans of the notifications.
+/// - `shard_config`: The sharding configuration of this instance, used to only post the notifications of its guilds.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if the queue cannot be read.
 ///
 /// # Notes:
-/// - The function calls `get_matchs_id` to retrieve the match history and then returns the first match in the list, which corresponds to the most recent match.
-/// - The `get_matchs_id` function is expected to return a vector of match IDs, from which the latest match (the first one) is extracted and returned.
-async fn get_latest_match_id(
-    client: &reqwest::Client,
-    puuid: &str,
+/// - Without batching, notifications are posted one by one in the order they were queued.
+/// - With batching, the queue of a guild is posted at once with `send_match_batch_to_discord`, once its oldest
+///   notification has waited for the batch interval.
+/// - Notifications are removed from the queue once posted.
+/// - A notification that cannot be posted is logged and retried at the next flush, then dropped after
+///   `MAX_NOTIFICATION_ATTEMPTS` attempts (see `record_failed_notification`), so that it never blocks the queue of its
+///   guild or the other guilds.
+pub async fn flush_queued_notifications(
+    mongo_client: &mongodb::Client,
     riot_api_key: &str,
-) -> Result<String, Error> {
-    let matches = get_matchs_id(client, puuid, riot_api_key, 1).await?;
-    Ok(matches[0].clone())
+    http: Arc<Http>,
+    collection_emojis: Collection<EmojiId>,
+    champions: &RwLock<ChampionIndex>,
+    shard_config: &ShardConfig,
+) -> Result<(), Error> {
+    let collection = mongo_client
+        .database("stat-summoner")
+        .collection::<QueuedNotificationData>("queued_notifications");
+    let queued: Vec<QueuedNotificationData> = collection
+        .find(doc! {})
+        .sort(doc! { "queued_at": 1 })
+        .await?
+        .try_collect()
+        .await?;
+    let mut queued_by_guild: Vec<(String, Vec<QueuedNotificationData>)> = Vec::new();
+    for notification in queued {
+        if !is_guild_handled(shard_config, &notification.guild_id) {
+            continue;
+        }
+        match queued_by_guild
+            .iter_mut()
+            .find(|(guild_id, _)| *guild_id == notification.guild_id)
+        {
+            Some((_, notifications)) => notifications.push(notification),
+            None => queued_by_guild.push((notification.guild_id.clone(), vec![notification])),
+        }
+    }
+
+    let now = Utc::now().timestamp();
+    for (guild_id, notifications) in queued_by_guild {
+        let settings = match get_guild_settings(mongo_client, &guild_id).await {
+            Ok(settings) => settings,
+            Err(e) => {
+                log::error!("Failed to read the settings of guild {}: {}", guild_id, e);
+                GuildSettingsData::default()
+            }
+        };
+        if is_in_quiet_hours(&settings) {
+            continue;
+        }
+        match settings.notification_batch.minutes() {
+            Some(minutes) => {
+                if now - notifications[0].queued_at < minutes * 60 {
+                    continue;
+                }
+                if let Err(e) = send_match_batch_to_discord(
+                    &notifications,
+                    riot_api_key,
+                    http.clone(),
+                    collection_emojis.clone(),
+                )
+                .await
+                {
+                    log::error!(
+                        "Failed to post the notification batch of guild {}: {:?}",
+                        guild_id,
+                        e
+                    );
+                    for notification in &notifications {
+                        record_failed_notification(&collection, notification).await;
+                    }
+                    continue;
+                }
+                let match_ids: Vec<&str> = notifications
+                    .iter()
+                    .map(|notification| notification.match_id.as_str())
+                    .collect();
+                collection
+                    .delete_many(doc! { "guild_id": &guild_id, "match_id": { "$in": match_ids } })
+                    .await?;
+            }
+            None => {
+                for notification in &notifications {
+                    // Une notification en échec ne doit pas bloquer la file du serveur
+                    if let Err(e) = send_match_update_to_discord(
+                        &notification.summoners,
+                        &notification.match_id,
+                        riot_api_key,
+                        http.clone(),
+                        collection_emojis.clone(),
+                        champions,
+                    )
+                    .await
+                    {
+                        log::error!(
+                            "Failed to post the queued notification of match {} in guild {}: {:?}",
+                            notification.match_id,
+                            guild_id,
+                            e
+                        );
+                        record_failed_notification(&collection, notification).await;
+                        continue;
+                    }
+                    collection
+                        .delete_one(
+                            doc! { "guild_id": &guild_id, "match_id": &notification.match_id },
+                        )
+                        .await?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// ⚙️ **Function**: Records a failed attempt to post a queued notification, and drops it after too many attempts.
+///
+/// # Parameters:
+/// - `collection`: The MongoDB collection of the queued notifications.
+/// - `notification`: The notification that could not be posted.
+///
+/// # Notes:
+/// - The notification is deleted once it failed `MAX_NOTIFICATION_ATTEMPTS` times, e.g. when its channel was deleted
+///   or its match cannot be fetched anymore.
+/// - Database errors are only logged.
+async fn record_failed_notification(
+    collection: &Collection<QueuedNotificationData>,
+    notification: &QueuedNotificationData,
+) {
+    let filter = doc! { "guild_id": &notification.guild_id, "match_id": &notification.match_id };
+    let result = if notification.attempts + 1 >= MAX_NOTIFICATION_ATTEMPTS {
+        log::warn!(
+            "Dropping the notification of match {} in guild {} after {} attempts.",
+            notification.match_id,
+            notification.guild_id,
+            MAX_NOTIFICATION_ATTEMPTS
+        );
+        collection.delete_one(filter).await.map(|_| ())
+    } else {
+        collection
+            .update_one(filter, doc! { "$inc": { "attempts": 1 } })
+            .await
+            .map(|_| ())
+    };
+    if let Err(e) = result {
+        log::error!("Failed to update a queued notification: {}", e);
+    }
 }
 
-/// ⚙️ **Function**: Sends a match update to a specific Discord channel for a followed summoner.
+/// ⚙️ **Function**: Writes which followed players are in game in the live status channel of each guild.
 ///
-/// This asynchronous function fetches match information for a followed summoner from the Riot API,
-/// formats the details into an embed, and sends the embed as a message to the specified Discord channel.
+/// The status (e.g. `🔴 Faker in game (23:10)`) goes in the name of a voice channel or in the topic of a text channel,
+/// and the original name or topic is restored once no followed player of the guild is in game anymore.
 ///
 /// # Parameters:
-/// - `followed_summoner`: A reference to a `SummonerFollowedData` struct, which contains the summoner's name and the ID of the Discord channel to which the match update should be sent.
-/// - `summoner_id`: A string slice representing the summoner's ID, used to identify the player's stats in the match.
-/// - `match_id`: A string slice representing the match ID, used to fetch match details from the Riot API.
-/// - `riot_api_key`: A string slice containing the Riot Games API key for authenticating the API request.
-/// - `http`: An `Arc<Http>` object used to send messages via the Discord API.
-/// - `collection_emojis`: A MongoDB `Collection` containing emoji mappings, used to add custom emojis to the embed for roles and champions.
+/// - `mongo_client`: The MongoDB client, used for the guild settings, the follows and the `live_games` collection.
+/// - `http`: The Discord HTTP client used to edit the channels.
+/// - `shard_config`: The sharding configuration of this instance, used to only update the channels of its guilds.
 ///
 /// # Returns:
-/// - `Result<(), Error>`: Returns `Ok(())` if the match update was successfully sent to the Discord channel, or an error if something went wrong.
+/// - `Result<(), Error>`: An error if the database cannot be read or the status cannot be saved.
 ///
 /// # Example:
-/// This function is typically called after detecting that a followed summoner has completed a match:
-///
 /// ```rust
-/// let result = send_match_update_to_discord(&followed_summoner, summoner_id, match_id, riot_api_key, http.clone(), collection_emojis).await;
-/// if result.is_err() {
-///     // Handle error (e.g., log failure or retry)
-/// }
+/// update_live_status_channels(&mongo_client, http.clone(), &shard_config).await?;
 /// ```
 ///
 /// # Notes:
-/// - The function creates an HTTP client using `reqwest` to fetch match information from the Riot API.
-/// - It retrieves detailed match data using the `get_matchs_info` and `get_match_details` functions.
-/// - The function constructs a `CreateEmbed` object using the `create_embed_loop` function, which formats match statistics and adds emojis.
-/// - The embed is sent as a message to the Discord channel specified in the `followed_summoner` struct.
-/// - The Discord message is built using `CreateMessage` and sent asynchronously to the appropriate channel using the Discord API.
-async fn send_match_update_to_discord(
-    followed_summoner: &SummonerFollowedData,
-    summoner_id: &str,
-    match_id: &str,
-    riot_api_key: &str,
+/// - The live games are the ones tracked by `track_live_game`; a game not seen for 5 minutes is considered over.
+/// - Discord only allows two channel name or topic changes every 10 minutes, so a channel is edited at most once every
+///   5 minutes: the game time shown is the one of the last edit, and the status may be cleared up to 5 minutes late.
+/// - An edit that fails or gets rate limited is not retried before the next interval, and never blocks the polling loop.
+pub async fn update_live_status_channels(
+    mongo_client: &mongodb::Client,
     http: Arc<Http>,
-    collection_emojis: Collection<EmojiId>,
+    shard_config: &ShardConfig,
 ) -> Result<(), Error> {
-    let client = reqwest::Client::new();
-    let info = get_matchs_info(&client, match_id, riot_api_key).await?;
-    let info_json = get_match_details(&info, summoner_id).unwrap();
-    let channel_id = serenity::model::id::ChannelId::new(followed_summoner.channel_id);
-    let embed = create_embed_loop(&info_json, &followed_summoner.name, collection_emojis).await;
-    let builder = CreateMessage::new().add_embed(embed);
-    let _ = channel_id.send_message(&http, builder).await;
+    let database = mongo_client.database("stat-summoner");
+    let guilds: Vec<GuildSettingsData> = guild_settings_collection(mongo_client)
+        .find(doc! { "live_status": { "$ne": null } })
+        .await?
+        .try_collect()
+        .await?;
+    let now = Utc::now().timestamp();
+    for settings in guilds {
+        let Some(live_status) = settings.live_status else {
+            continue;
+        };
+        if !is_guild_handled(shard_config, &settings.guild_id)
+            || now - live_status.updated_at < LIVE_STATUS_MIN_INTERVAL_SECS
+        {
+            continue;
+        }
+        let followed_summoners: Vec<SummonerFollowedData> = database
+            .collection::<SummonerFollowedData>("follower_summoner")
+            .find(doc! { "guild_id": &settings.guild_id })
+            .await?
+            .try_collect()
+            .await?;
+        let puuids: Vec<&str> = followed_summoners
+            .iter()
+            .map(|followed_summoner| followed_summoner.puuid.as_str())
+            .collect();
+        let live_games: Vec<LiveGameData> = database
+            .collection::<LiveGameData>("live_games")
+            .find(doc! {
+                "puuid": { "$in": puuids },
+                "seen_at": { "$gte": now - LIVE_GAME_STALE_SECS },
+            })
+            .await?
+            .try_collect()
+            .await?;
+        let status = format_live_status(&followed_summoners, &live_games, now);
+        if status == live_status.last_status {
+            continue;
+        }
+
+        let text = status
+            .clone()
+            .unwrap_or_else(|| live_status.original_text.clone());
+        let edit = if live_status.voice {
+            serenity::EditChannel::new().name(text)
+        } else {
+            serenity::EditChannel::new().topic(text)
+        };
+        let channel_id = serenity::ChannelId::new(live_status.channel_id);
+        let saved_status = match tokio::time::timeout(
+            std::time::Duration::from_secs(LIVE_STATUS_EDIT_TIMEOUT_SECS),
+            edit_channel(&http, channel_id, edit),
+        )
+        .await
+        {
+            Ok(Ok(_)) => status,
+            Ok(Err(e)) => {
+                log::warn!("Failed to update the live status of {}: {}", channel_id, e);
+                live_status.last_status
+            }
+            // Serenity attend la fin de la limite de Discord : on abandonne et on réessaiera plus tard
+            Err(_) => {
+                log::warn!("Live status of {} rate limited, retrying later", channel_id);
+                live_status.last_status
+            }
+        };
+        save_live_status(mongo_client, &settings.guild_id, saved_status, now).await?;
+    }
     Ok(())
 }
 
-/// ⚙️ **Function**: Fetches rune data for a specific champion from League of Graphs.
+/// ⚙️ **Function**: Builds the live status of a guild from the live games of its followed players.
+///
+/// # Parameters:
+/// - `followed_summoners`: The follows of the guild.
+/// - `live_games`: The games in progress of these players.
+/// - `now`: The current Unix timestamp.
 ///
-/// This asynchronous function retrieves the rune build information for a given champion
-/// by making an HTTP request to League of Graphs. It then parses the HTML response to
-/// extract the rune tables and returns the runes in the `RunesData` structure.
+/// # Returns:
+/// - `Option<String>`: The status, e.g. `🔴 Faker, Caps in game (23:10)` with the time of the longest game, or `None`
+///   if no followed player is in game.
+fn format_live_status(
+    followed_summoners: &[SummonerFollowedData],
+    live_games: &[LiveGameData],
+    now: i64,
+) -> Option<String> {
+    let mut names: Vec<&str> = Vec::new();
+    for followed_summoner in followed_summoners {
+        let in_game = live_games
+            .iter()
+            .any(|live_game| live_game.puuid == followed_summoner.puuid);
+        if in_game && !names.contains(&followed_summoner.name.as_str()) {
+            names.push(&followed_summoner.name);
+        }
+    }
+    if names.is_empty() {
+        return None;
+    }
+    let started_at = live_games
+        .iter()
+        .map(|live_game| live_game.started_at)
+        .filter(|started_at| *started_at > 0)
+        .min();
+    let mut status = format!("🔴 {} in game", names.join(", "));
+    if let Some(started_at) = started_at {
+        let elapsed = (now - started_at).max(0);
+        status.push_str(&format!(" ({}:{:02})", elapsed / 60, elapsed % 60));
+    }
+    // Les noms de salons sont limités à 100 caractères
+    Some(status.chars().take(100).collect())
+}
+
+/// ⚙️ **Function**: Fetches and parses the League of Graphs builds page of a champion.
 ///
 /// # Parameters:
-/// - `champion_id`: A string slice representing the champion's identifier, used to build the URL for fetching the rune information.
+/// - `champion_id`: A string slice representing the champion's identifier, used to build the URL of the builds page.
 ///
 /// # Returns:
-/// - `Result<RunesData, Error>`: Returns a `RunesData` struct with the champion's rune information if successful, or an error if something goes wrong during the HTTP request or parsing.
+/// - `Result<Document, Error>`: The parsed page, or an error if the HTTP request fails.
 ///
 /// # Example:
-/// This function is typically called to retrieve the rune data for a specific champion:
-///
 /// ```rust
-/// let runes = fetch_runes("Rammus").await?;
-/// println!("{:?}", runes);
+/// let builds_page = fetch_builds_page("jinx").await?;
+/// let runes = extract_page_runes(&builds_page)?;
+/// let core_build = extract_page_core_build(&builds_page)?;
 /// ```
 ///
 /// # Notes:
-/// - The function makes an HTTP request to the League of Graphs page using the champion's ID to construct the URL.
-/// - It then parses the HTML to find the rune tables and extracts the relevant rune data.
-/// - The `extract_runes` function is used to process the HTML and return the rune information in the `RunesData` structure.
-/// - This function expects two rune tables (primary and secondary) to be present in the response, otherwise it will panic with an `unwrap()` error.
-pub async fn fetch_runes(champion_id: &str) -> Result<RunesData, Error> {
+/// - The runes, the core build and the situational build are all read from this page, so it is fetched once per
+///   champion and given to `extract_page_runes`, `extract_page_core_build` and `extract_page_situational_build`.
+pub async fn fetch_builds_page(champion_id: &str) -> Result<Document, Error> {
     let url = format!(
         "https://www.leagueofgraphs.com/champions/builds/{}",
         champion_id
@@ -707,56 +2742,69 @@ pub async fn fetch_runes(champion_id: &str) -> Result<RunesData, Error> {
         .send()
         .await?;
     let body = res.text().await?;
-    let document = Document::from(body.as_str());
+    Ok(Document::from(body.as_str()))
+}
 
+/// ⚙️ **Function**: Extracts the rune data of a champion from its League of Graphs builds page.
+///
+/// This function locates the rune tables of the builds page and returns the runes in the `RunesData` structure.
+///
+/// # Parameters:
+/// - `document`: The builds page of the champion, from `fetch_builds_page`.
+///
+/// # Returns:
+/// - `Result<RunesData, Error>`: Returns a `RunesData` struct with the champion's rune information if successful, or an error if the rune tables are missing.
+///
+/// # Example:
+/// This function is typically called to retrieve the rune data for a specific champion:
+///
+/// ```rust
+/// let runes = extract_page_runes(&fetch_builds_page("rammus").await?)?;
+/// println!("{:?}", runes);
+/// ```
+///
+/// # Notes:
+/// - The `extract_runes` function is used to process the HTML and return the rune information in the `RunesData` structure.
+/// - This function expects two rune tables (primary and secondary) to be present in the page, otherwise an error is returned.
+pub fn extract_page_runes(document: &Document) -> Result<RunesData, Error> {
     // Logique pour extraire les runes, en utilisant `RunesData` comme la structure finale
-    let first_rune_table = document.find(Class("perksTableOverview")).next().unwrap();
-    let secondary_rune_table = document.find(Class("perksTableOverview")).nth(1).unwrap();
+    let mut rune_tables = document.find(Class("perksTableOverview"));
+    let (Some(first_rune_table), Some(secondary_rune_table)) =
+        (rune_tables.next(), rune_tables.next())
+    else {
+        return Err(Box::from(
+            "Erreur: Impossible de trouver les tables de runes",
+        ));
+    };
 
     let runes = extract_runes(first_rune_table, secondary_rune_table);
     Ok(runes)
 }
 
-/// ⚙️ **Function**: Fetches core build data for a specific champion from League of Graphs.
+/// ⚙️ **Function**: Extracts the core build data of a champion from its League of Graphs builds page.
 ///
-/// This asynchronous function retrieves the core build item information for a given champion
-/// by making an HTTP request to League of Graphs. It then parses the HTML response to locate
-/// the core build section and extracts the items used in the build.
+/// This function locates the core build section of the builds page and extracts the items used in the build.
 ///
 /// # Parameters:
-/// - `champion_id`: A string slice representing the champion's identifier, used to build the URL for fetching the core build information.
+/// - `document`: The builds page of the champion, from `fetch_builds_page`.
 ///
 /// # Returns:
-/// - `Result<CoreBuildData, Error>`: Returns a `CoreBuildData` struct containing the champion's core build items if successful, or an error if something goes wrong during the HTTP request or parsing.
+/// - `Result<CoreBuildData, Error>`: Returns a `CoreBuildData` struct containing the champion's core build items if successful, or an error if the section cannot be found.
 ///
 /// # Example:
 /// This function is typically called to retrieve the core build data for a specific champion:
 ///
 /// ```rust
-/// let core_build = fetch_core_build("Jinx").await?;
+/// let core_build = extract_page_core_build(&fetch_builds_page("jinx").await?)?;
 /// println!("{:?}", core_build);
 /// ```
 ///
 /// # Notes:
-/// - The function makes an HTTP request to the League of Graphs page using the champion's ID to construct the URL.
-/// - It parses the HTML to find the core build section by searching for an `h3` element containing the text "Core Build".
+/// - It searches the core build section by looking for an `h3` element containing the text "Core Build".
 /// - Once the core build header is found, the function searches for its parent element and the `iconsRow` div where the build items are listed.
 /// - The function calls `extract_core_build` to process the icons and return the items in the `CoreBuildData` structure.
 /// - If the core build header or the `iconsRow` div is not found, an error is returned.
-pub async fn fetch_core_build(champion_id: &str) -> Result<CoreBuildData, Error> {
-    let url = format!(
-        "https://www.leagueofgraphs.com/champions/builds/{}",
-        champion_id
-    );
-    let client = reqwest::Client::new();
-    let res = client
-        .get(&url)
-        .header("User-Agent", "Mozilla/5.0")
-        .send()
-        .await?;
-    let body = res.text().await?;
-    let document = select::document::Document::from(body.as_str());
-
+pub fn extract_page_core_build(document: &Document) -> Result<CoreBuildData, Error> {
     if let Some(core_build_header) = document
         .find(Name("h3"))
         .find(|node| node.text().contains("Core Build"))
@@ -780,6 +2828,162 @@ pub async fn fetch_core_build(champion_id: &str) -> Result<CoreBuildData, Error>
     }
 }
 
+/// ⚙️ **Function**: Extracts the boots options and situational items of a champion from its League of Graphs builds page.
+///
+/// # Parameters:
+/// - `document`: The builds page of the champion, from `fetch_builds_page`.
+///
+/// # Returns:
+/// - `SituationalBuildData`: The boots and situational items, most popular first.
+///
+/// # Example:
+/// ```rust
+/// let situational_build = extract_page_situational_build(&fetch_builds_page("jinx").await?);
+/// println!("{:?}", situational_build.boots);
+/// ```
+///
+/// # Notes:
+/// - Sections are located like the core build: an `h3` header ("Boots", "Situational Items") whose parent holds the item icons.
+/// - A missing section yields an empty list rather than an error, since some champions (e.g., Cassiopeia) never buy boots.
+pub fn extract_page_situational_build(document: &Document) -> SituationalBuildData {
+    SituationalBuildData {
+        boots: extract_item_section(document, "Boots"),
+        items: extract_item_section(document, "Situational"),
+    }
+}
+
+/// ⚙️ **Function**: Extracts the item names listed under a section header of a League of Graphs builds page.
+///
+/// # Parameters:
+/// - `document`: The parsed builds page.
+/// - `header`: Text contained in the `h3` header of the section.
+///
+/// # Returns:
+/// - `Vec<String>`: Up to 6 distinct item names, cleaned with `clean_alt_text`, or an empty vector if the section is missing.
+fn extract_item_section(document: &Document, header: &str) -> Vec<String> {
+    let Some(section) = document
+        .find(Name("h3"))
+        .find(|node| node.text().contains(header))
+        .and_then(|node| node.parent())
+    else {
+        return Vec::new();
+    };
+
+    let mut items: Vec<String> = Vec::new();
+    for item in section
+        .find(Class("iconsRow").descendant(Name("img")))
+        .filter_map(|img| img.attr("alt"))
+        .map(clean_alt_text)
+    {
+        if !item.is_empty() && !items.contains(&item) {
+            items.push(item);
+        }
+    }
+    items.truncate(6);
+    items
+}
+
+/// ⚙️ **Function**: Fetches champion statistics restricted to a rank bracket from League of Graphs.
+///
+/// Each League of Graphs rank filter making up the bracket is scraped from the builds table, and the popularity,
+/// winrate and banrate of every champion are averaged over those filters.
+///
+/// # Parameters:
+/// - `bracket`: The rank bracket to fetch. `RankBracket::All` yields an empty map, since those statistics are scraped by `fetch_champion_data`.
+///
+/// # Returns:
+/// - `Result<HashMap<String, BracketStatsData>, Error>`: The statistics of the bracket, keyed by champion name.
+///
+/// # Example:
+/// ```rust
+/// let diamond_plus = fetch_bracket_stats(RankBracket::DiamondPlus).await?;
+/// ```
+///
+/// # Notes:
+/// - Rows that cannot be parsed are skipped instead of aborting the whole bracket.
+/// - The Iron–Gold bracket averages four tiers without weighting them by player count.
+pub async fn fetch_bracket_stats(
+    bracket: RankBracket,
+) -> Result<HashMap<String, BracketStatsData>, Error> {
+    let client = reqwest::Client::new();
+    let mut totals: HashMap<String, ([f64; 3], u32)> = HashMap::new();
+
+    for filter in bracket.league_of_graphs_filters() {
+        let url = format!("https://www.leagueofgraphs.com/champions/builds/{}", filter);
+        let body = client
+            .get(&url)
+            .header("User-Agent", "Mozilla/5.0")
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        for (name, rates) in parse_champion_rates(&body) {
+            let entry = totals.entry(name).or_insert(([0.0; 3], 0));
+            for (total, rate) in entry.0.iter_mut().zip(rates) {
+                *total += rate;
+            }
+            entry.1 += 1;
+        }
+    }
+
+    Ok(totals
+        .into_iter()
+        .map(|(name, (rates, count))| {
+            let count = f64::from(count);
+            let stats = BracketStatsData {
+                bracket: bracket.key().to_string(),
+                popularity: (rates[0] / count).to_string(),
+                winrate: (rates[1] / count).to_string(),
+                banrate: (rates[2] / count).to_string(),
+            };
+            (name, stats)
+        })
+        .collect())
+}
+
+/// ⚙️ **Function**: Extracts the popularity, winrate and banrate of every champion from a League of Graphs builds table.
+///
+/// # Parameters:
+/// - `body`: The HTML of a League of Graphs builds page.
+///
+/// # Returns:
+/// - `Vec<(String, [f64; 3])>`: The champion names with their popularity, winrate and banrate, as ratios between 0 and 1.
+fn parse_champion_rates(body: &str) -> Vec<(String, [f64; 3])> {
+    let document = Document::from(body);
+    let data_value = |cell: &select::node::Node| {
+        cell.find(Attr("data-value", ()))
+            .next()
+            .and_then(|node| node.attr("data-value"))
+            .and_then(|value| value.parse::<f64>().ok())
+    };
+
+    document
+        .find(Class("data_table").descendant(Name("tr")))
+        .filter_map(|node| {
+            let cells: Vec<_> = node.find(Name("td")).collect();
+            if cells.len() <= 5 {
+                return None;
+            }
+            let name = cells[1]
+                .find(Class("name"))
+                .next()?
+                .text()
+                .trim()
+                .to_string();
+            Some((
+                name,
+                [
+                    data_value(&cells[2])?,
+                    data_value(&cells[3])?,
+                    data_value(&cells[4])?,
+                ],
+            ))
+        })
+        .collect()
+}
+
 /// ⚙️ **Function**: Extracts rune data from two HTML tables.
 ///
 /// This function processes two HTML tables (representing primary and secondary runes) and extracts
@@ -919,7 +3123,7 @@ fn extract_core_build(icons_row: select::node::Node) -> CoreBuildData {
 /// - If the `alt` text contains parentheses `()`, a hyphen `-`, and a plus sign `+`, the function returns "HealthScale".
 /// - It uses regular expressions to remove unwanted characters such as parentheses, numbers, percentage symbols, commas, and others.
 /// - Spaces are also removed in the final output.
-fn clean_alt_text(alt: &str) -> String {
+pub fn clean_alt_text(alt: &str) -> String {
     if alt.contains('(') && alt.contains(')') && alt.contains('-') && alt.contains('+') {
         return "HealthScale".to_string();
     }
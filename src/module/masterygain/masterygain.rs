@@ -0,0 +1,122 @@
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, create_embed_sucess, edit_and_schedule_deletion,
+    send_fetching_message,
+};
+use crate::models::data::{Data, EmojiId};
+use crate::models::error::Error;
+use crate::module::linkaccount::utils::get_linked_accounts;
+use crate::module::masterygain::utils::{
+    compute_mastery_gains, create_embed_masterygain, fetch_mastery_points, get_reference_snapshot,
+    save_mastery_snapshot, GAIN_PERIOD_SECONDS,
+};
+use crate::module::myaccounts::utils::{
+    account_label, autocomplete_linked_account, linked_accounts_collection,
+};
+use crate::module::settings::utils::get_guild_theme;
+use poise::CreateReply;
+
+/// Shows the champions you gained the most mastery on over the last month.
+///
+/// # Parameters:
+/// - `account`: The linked account to look up, chosen from the autocomplete list. Defaults to the primary account.
+///
+/// # Example:
+/// ```rust
+/// /masterygain
+/// /masterygain account: Faker#KR1 (KR)
+/// ```
+///
+/// # Notes:
+/// - The mastery of linked accounts is recorded every week, so the first results show up a week after linking.
+/// - Until an account has been tracked for a month, the gains are counted from its first record.
+#[poise::command(slash_command)]
+pub async fn masterygain(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Linked account (defaults to your primary one)"]
+    #[autocomplete = "autocomplete_linked_account"]
+    account: Option<String>,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let mongo_client = &ctx.data().mongo_client;
+    let accounts = match get_linked_accounts(
+        &linked_accounts_collection(mongo_client),
+        ctx.author().id.get(),
+    )
+    .await
+    {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            let reply =
+                create_embed_coded_error("Error collecting informations from MongoDB", &e.into());
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    let selected = match &account {
+        Some(label) => accounts
+            .into_iter()
+            .find(|account| account_label(account) == label.trim()),
+        None => accounts.into_iter().next(),
+    };
+    let Some(selected) = selected else {
+        let error_message = match account {
+            Some(_) => "This account is not linked to you. Pick one from the list or link it with `/linkaccount`.",
+            None => "You need to link your League of Legends account with `/linkaccount` first.",
+        };
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+        return Ok(());
+    };
+
+    let current = match fetch_mastery_points(&selected, &ctx.data().riot_api_key).await {
+        Ok(current) => current,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error fetching champion mastery", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    let now = chrono::Utc::now().timestamp();
+    let reference = match get_reference_snapshot(
+        mongo_client,
+        &selected.puuid,
+        now - GAIN_PERIOD_SECONDS,
+    )
+    .await
+    {
+        Ok(reference) => reference,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error reading the mastery records", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    let Some(reference) = reference else {
+        // Premier relevé : le suivi commence maintenant
+        let reply = match save_mastery_snapshot(mongo_client, &selected.puuid, current).await {
+            Ok(_) => create_embed_sucess(
+                "Mastery tracking started: your mastery is now recorded every week. Come back in a few days to see your progress.",
+            ),
+            Err(e) => create_embed_coded_error("Error saving the mastery record", &e),
+        };
+        edit_and_schedule_deletion(fetching, ctx, reply).await?;
+        return Ok(());
+    };
+
+    let gains = compute_mastery_gains(&reference.champions, &current);
+    let days = ((now - reference.taken_at) / 86400).max(1);
+    let champions = ctx.data().champions.read().await;
+    let collection_emoji = mongo_client
+        .database("stat-summoner")
+        .collection::<EmojiId>("emojis_id");
+    let embed = create_embed_masterygain(
+        &account_label(&selected),
+        &gains,
+        days,
+        &champions,
+        &collection_emoji,
+        &get_guild_theme(ctx).await,
+    )
+    .await;
+    edit_and_schedule_deletion(fetching, ctx, CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
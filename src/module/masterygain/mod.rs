@@ -0,0 +1,17 @@
+/// 🛠 **Module masterygain**: Contains the mastery gained by linked players on each champion over the last month.
+///
+/// The mastery of every account linked with `/linkaccount` is recorded once a week. Comparing the current mastery to
+/// the record of a month ago shows which champions a player has been grinding lately.
+///
+/// # Files in this module:
+/// - `masterygain.rs`: The `/masterygain` command.
+/// - `utils.rs`: The `mastery_snapshots` collection helpers, the weekly snapshot job and the embed.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`, and the weekly snapshots are taken in `main.rs`:
+///
+/// ```rust
+/// record_mastery_snapshots(&mongo_client, &riot_api_key).await?;
+/// ```
+pub mod masterygain;
+pub mod utils;
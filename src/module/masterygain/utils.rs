@@ -0,0 +1,261 @@
+use crate::config::deletion_notice;
+use crate::models::champion_index::ChampionIndex;
+use crate::models::data::{
+    ChampionPointsData, EmbedTheme, EmojiId, LinkedAccountData, MasterySnapshotData,
+};
+use crate::models::error::Error;
+use crate::module::myaccounts::utils::linked_accounts_collection;
+use crate::module::optout::utils::is_opted_out;
+use crate::riot_api::get_champion_masteries;
+use crate::utils::{current_week, get_emoji};
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+
+/// Period over which the mastery gained is reported.
+pub const GAIN_PERIOD_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Number of champions listed in the embed.
+const MAX_CHAMPIONS_SHOWN: usize = 10;
+
+/// ⚙️ **Function**: Returns the `mastery_snapshots` collection.
+pub fn mastery_snapshots_collection(mongo_client: &Client) -> Collection<MasterySnapshotData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<MasterySnapshotData>("mastery_snapshots")
+}
+
+/// ⚙️ **Function**: Fetches the current mastery points of an account on every champion.
+///
+/// # Parameters:
+/// - `account`: The linked account to look up.
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<Vec<ChampionPointsData>, Error>`: The points of every champion the account played.
+pub async fn fetch_mastery_points(
+    account: &LinkedAccountData,
+    riot_api_key: &str,
+) -> Result<Vec<ChampionPointsData>, Error> {
+    let client = reqwest::Client::new();
+    let masteries =
+        get_champion_masteries(&client, &account.region, &account.puuid, riot_api_key).await?;
+    Ok(masteries
+        .iter()
+        .map(|mastery| ChampionPointsData {
+            champion_id: mastery["championId"].as_i64().unwrap_or(0),
+            points: mastery["championPoints"].as_i64().unwrap_or(0),
+        })
+        .collect())
+}
+
+/// ⚙️ **Function**: Records the mastery of an account for the current week.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `puuid`: The PUUID of the account.
+/// - `champions`: The mastery points of the account, as returned by `fetch_mastery_points`.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if the snapshot cannot be saved.
+///
+/// # Notes:
+/// - An account has a single snapshot per week: recording it again replaces the one of the week.
+pub async fn save_mastery_snapshot(
+    mongo_client: &Client,
+    puuid: &str,
+    champions: Vec<ChampionPointsData>,
+) -> Result<(), Error> {
+    let week = current_week();
+    let snapshot = MasterySnapshotData {
+        puuid: puuid.to_string(),
+        week: week.clone(),
+        taken_at: chrono::Utc::now().timestamp(),
+        champions,
+    };
+    mastery_snapshots_collection(mongo_client)
+        .replace_one(doc! { "puuid": puuid, "week": &week }, &snapshot)
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Takes the weekly mastery snapshot of every linked account.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if the linked accounts cannot be read.
+///
+/// # Example:
+/// ```rust
+/// record_mastery_snapshots(&mongo_client, &riot_api_key).await?;
+/// ```
+///
+/// # Notes:
+/// - Accounts that already have a snapshot this week are skipped, so the job can run often.
+/// - Opted-out accounts are not recorded, and accounts whose mastery cannot be fetched or saved are tried again on the
+///   next run.
+pub async fn record_mastery_snapshots(
+    mongo_client: &Client,
+    riot_api_key: &str,
+) -> Result<(), Error> {
+    let week = current_week();
+    let collection = mastery_snapshots_collection(mongo_client);
+    let accounts: Vec<LinkedAccountData> = linked_accounts_collection(mongo_client)
+        .find(doc! {})
+        .await?
+        .try_collect()
+        .await?;
+    for account in accounts {
+        let recorded = match collection
+            .find_one(doc! { "puuid": &account.puuid, "week": &week })
+            .await
+        {
+            Ok(snapshot) => snapshot.is_some(),
+            Err(e) => {
+                log::error!("Error reading the mastery of {}: {:?}", account.puuid, e);
+                continue;
+            }
+        };
+        let opted_out = match is_opted_out(mongo_client, &account.puuid).await {
+            Ok(opted_out) => opted_out,
+            Err(e) => {
+                log::error!("Error checking the opt-out of {}: {:?}", account.puuid, e);
+                continue;
+            }
+        };
+        if recorded || opted_out {
+            continue;
+        }
+        match fetch_mastery_points(&account, riot_api_key).await {
+            Ok(champions) => {
+                if let Err(e) = save_mastery_snapshot(mongo_client, &account.puuid, champions).await
+                {
+                    log::error!("Error saving the mastery of {}: {:?}", account.puuid, e);
+                }
+            }
+            Err(e) => log::error!("Error fetching the mastery of {}: {:?}", account.puuid, e),
+        }
+    }
+    Ok(())
+}
+
+/// ⚙️ **Function**: Returns the snapshot the mastery gained is computed from.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `puuid`: The PUUID of the account.
+/// - `since`: The start of the period, as a Unix timestamp.
+///
+/// # Returns:
+/// - `Result<Option<MasterySnapshotData>, Error>`: The last snapshot taken before `since`, or the oldest one when the
+///   account has been tracked for a shorter time, `None` if the account has no snapshot.
+pub async fn get_reference_snapshot(
+    mongo_client: &Client,
+    puuid: &str,
+    since: i64,
+) -> Result<Option<MasterySnapshotData>, Error> {
+    let collection = mastery_snapshots_collection(mongo_client);
+    let before = collection
+        .find_one(doc! { "puuid": puuid, "taken_at": { "$lte": since } })
+        .sort(doc! { "taken_at": -1 })
+        .await?;
+    if before.is_some() {
+        return Ok(before);
+    }
+    Ok(collection
+        .find_one(doc! { "puuid": puuid })
+        .sort(doc! { "taken_at": 1 })
+        .await?)
+}
+
+/// ⚙️ **Function**: Computes the mastery points gained on each champion between two snapshots.
+///
+/// # Parameters:
+/// - `reference`: The mastery points at the start of the period.
+/// - `current`: The current mastery points.
+///
+/// # Returns:
+/// - `Vec<(i64, i64)>`: The `(champion ID, points gained)` of every champion with points gained, most gained first.
+pub fn compute_mastery_gains(
+    reference: &[ChampionPointsData],
+    current: &[ChampionPointsData],
+) -> Vec<(i64, i64)> {
+    let mut gains: Vec<(i64, i64)> = current
+        .iter()
+        .map(|champion| {
+            let before = reference
+                .iter()
+                .find(|previous| previous.champion_id == champion.champion_id)
+                .map(|previous| previous.points)
+                .unwrap_or(0);
+            (champion.champion_id, champion.points - before)
+        })
+        .filter(|(_, gain)| *gain > 0)
+        .collect();
+    gains.sort_by(|(_, a), (_, b)| b.cmp(a));
+    gains
+}
+
+/// ⚙️ **Function**: Creates the embed of the mastery gained by an account.
+///
+/// # Parameters:
+/// - `label`: The label of the account (see `account_label`).
+/// - `gains`: The points gained per champion, as returned by `compute_mastery_gains`.
+/// - `days`: The number of days since the reference snapshot.
+/// - `champions`: The champion lookups, used for the champion names.
+/// - `collection_emoji`: The MongoDB collection of the custom emojis.
+/// - `theme`: The embed theme of the guild.
+///
+/// # Returns:
+/// - `CreateEmbed`: The 10 champions with the most points gained, with the total.
+///
+/// # Example:
+/// ```text
+/// 📚 Mastery gained by Faker#KR1 (KR)
+/// Last 30 days: +182400 pts
+/// 1. Ahri: +95200 pts
+/// 2. Sylas: +41800 pts
+/// ```
+pub async fn create_embed_masterygain(
+    label: &str,
+    gains: &[(i64, i64)],
+    days: i64,
+    champions: &ChampionIndex,
+    collection_emoji: &Collection<EmojiId>,
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let total: i64 = gains.iter().map(|(_, gain)| gain).sum();
+    let mut lines = vec![format!("Last {} days: **+{} pts**", days, total)];
+    for (rank, (champion_id, gain)) in gains.iter().take(MAX_CHAMPIONS_SHOWN).enumerate() {
+        let id = champions
+            .id_from_key(*champion_id)
+            .unwrap_or("Unknown Champion");
+        let name = champions.name(id).unwrap_or(id);
+        let emoji = get_emoji(collection_emoji.clone(), "champions", id)
+            .await
+            .unwrap_or_default();
+        lines.push(format!(
+            "{}. {} **{}**: +{} pts",
+            rank + 1,
+            emoji,
+            name,
+            gain
+        ));
+    }
+    if gains.is_empty() {
+        lines.push("No mastery gained over this period.".to_string());
+    }
+    CreateEmbed::default()
+        .title(format!("📚 Mastery gained by {}", label))
+        .description(lines.join("\n"))
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
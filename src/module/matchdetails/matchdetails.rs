@@ -0,0 +1,190 @@
+use crate::config::deletion_notice;
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, edit_and_schedule_deletion, send_fetching_message,
+};
+use crate::image::render_heatmap;
+use crate::models::data::{Data, EmbedLayout, EmojiId, MatchDetailsPage};
+use crate::models::error::Error;
+use crate::models::riot_id::RiotId;
+use crate::module::loop_module::utils::{create_embed_for_layout, get_match_details};
+use crate::module::matchdetails::utils::{
+    ban_emojis, create_embed_match_pings, create_gold_graph, ward_positions,
+};
+use crate::module::optout::utils::is_opted_out;
+use crate::module::settings::utils::get_guild_theme;
+use crate::riot_api::{get_match_timeline, get_matchs_id, get_matchs_info, get_puuid};
+use poise::serenity_prelude::{CreateAttachment, CreateEmbedFooter};
+use poise::CreateReply;
+use reqwest::Client;
+
+/// Size, in pixels, of the images attached to the match details.
+const IMAGE_SIZE: u32 = 512;
+
+/// Shows one of a player's recent games lane by lane, with their ward heatmap and the gold graph.
+///
+/// # Example:
+/// ```rust
+/// /matchdetails riot_id: Faker#KR1
+/// /matchdetails riot_id: Faker#KR1 game: 3
+/// /matchdetails riot_id: Faker#KR1 page: Pings
+/// ```
+///
+/// # Notes:
+/// - `game` counts back from the last game played (1 is the last one).
+/// - The `Pings` page lists the pings sent by every player of the game instead of the lanes.
+/// - The heatmap is only attached when the player placed wards during the game.
+/// - The gold graph shows the gold difference of the player's team over the game.
+#[poise::command(slash_command)]
+pub async fn matchdetails(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Riot ID of the player (e.g., Faker#KR1)"] riot_id: RiotId,
+    #[description = "Game to show, 1 being the last one (1 by default)"]
+    #[min = 1]
+    #[max = 20]
+    game: Option<u32>,
+    #[description = "Page to show (Lanes by default)"] page: Option<MatchDetailsPage>,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let game = game.unwrap_or(1);
+    let client = Client::new();
+    let riot_api_key = &ctx.data().riot_api_key;
+    let mongo_client = &ctx.data().mongo_client;
+
+    let puuid = match get_puuid(&client, &riot_id, riot_api_key).await {
+        Ok(puuid) => puuid,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error fetching PUUID", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    if is_opted_out(mongo_client, &puuid).await? {
+        let error_message =
+            "This player has opted out of Stat Summoner: their statistics cannot be looked up.";
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+        return Ok(());
+    }
+
+    let riot_id = riot_id.to_string();
+    let match_id = match get_matchs_id(&client, &puuid, riot_api_key, game).await {
+        Ok(match_ids) => match match_ids.get(game as usize - 1) {
+            Some(match_id) => match_id.clone(),
+            None => {
+                let error_message = format!("{} has played fewer than {} games.", riot_id, game);
+                edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message))
+                    .await?;
+                return Ok(());
+            }
+        },
+        Err(e) => {
+            let reply = create_embed_coded_error("Error fetching match IDs", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    let match_info = match get_matchs_info(&client, &match_id, riot_api_key).await {
+        Ok(match_info) => match_info,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error fetching match data", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    let Some(participant) =
+        match_info["info"]["participants"]
+            .as_array()
+            .and_then(|participants| {
+                participants
+                    .iter()
+                    .find(|participant| participant["puuid"].as_str() == Some(puuid.as_str()))
+            })
+    else {
+        let error_message = "This player could not be found in the game.";
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+        return Ok(());
+    };
+    let theme = get_guild_theme(ctx).await;
+    if page.unwrap_or_default() == MatchDetailsPage::Pings {
+        let embed = create_embed_match_pings(&match_info, &puuid, &riot_id, &theme).footer(
+            CreateEmbedFooter::new(theme.footer_text(&deletion_notice())),
+        );
+        edit_and_schedule_deletion(fetching, ctx, CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let summoner_id = participant["summonerId"].as_str().unwrap_or("");
+    let Some(info_json) = get_match_details(&match_info, &[summoner_id]) else {
+        let error_message = "This game mode is not supported by the match details.";
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+        return Ok(());
+    };
+
+    let collection_emoji = mongo_client
+        .database("stat-summoner")
+        .collection::<EmojiId>("emojis_id");
+    let mut embed = create_embed_for_layout(
+        EmbedLayout::Detailed,
+        &info_json,
+        &riot_id,
+        &theme,
+        collection_emoji.clone(),
+    )
+    .await
+    .footer(CreateEmbedFooter::new(
+        theme.footer_text(&deletion_notice()),
+    ));
+    let participant_id = participant["participantId"].as_i64().unwrap_or(0);
+    let team_id = participant["teamId"].as_i64().unwrap_or(100);
+    let bans = ban_emojis(
+        &match_info,
+        team_id,
+        &*ctx.data().champions.read().await,
+        collection_emoji,
+    )
+    .await;
+    if let Some((ally_bans, enemy_bans)) = bans {
+        embed = embed.field(
+            "**🚫 BANS**",
+            format!("Ally team: {}\nEnemy team: {}", ally_bans, enemy_bans),
+            false,
+        );
+    }
+    let mut reply = CreateReply::default();
+    let mut gold_embed = None;
+
+    // Les images sont optionnelles : une erreur de la timeline n'empêche pas d'afficher la partie
+    match get_match_timeline(&client, &match_id, riot_api_key).await {
+        Ok(timeline) => {
+            match create_gold_graph(&timeline, team_id, theme.info()) {
+                Ok((embed, attachment)) => {
+                    gold_embed = Some(embed);
+                    reply = reply.attachment(attachment);
+                }
+                Err(e) => log::error!("Error rendering the gold graph: {:?}", e),
+            }
+            let wards = ward_positions(&timeline, participant_id);
+            if !wards.is_empty() {
+                match render_heatmap(&wards, IMAGE_SIZE) {
+                    Ok(png) => {
+                        embed = embed
+                            .field(
+                                "**👁️ VISION**",
+                                format!("Wards placed by {}: **{}**", riot_id, wards.len()),
+                                false,
+                            )
+                            .image("attachment://vision.png");
+                        reply = reply.attachment(CreateAttachment::bytes(png, "vision.png"));
+                    }
+                    Err(e) => log::error!("Error rendering the vision heatmap: {:?}", e),
+                }
+            }
+        }
+        Err(e) => log::error!("Error fetching the timeline of {}: {:?}", match_id, e),
+    }
+
+    reply = reply.embed(embed);
+    if let Some(gold_embed) = gold_embed {
+        reply = reply.embed(gold_embed);
+    }
+    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+    Ok(())
+}
@@ -0,0 +1,23 @@
+/// 🛠 **Module matchdetails**: Contains the deep-dive into a single game of a player.
+///
+/// This module shows the role-by-role breakdown of one of a player's recent games, like the match notifications of
+/// followed summoners, along with images rendered from the match timeline.
+///
+/// # Files in this module:
+/// - `matchdetails.rs`: The `/matchdetails` command.
+/// - `utils.rs`: The extraction of the timeline data drawn in the images (ward placements, gold difference), the gold graph embed
+///   and the "Pings" page.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`:
+///
+/// ```rust
+/// let framework = poise::Framework::builder()
+///     .options(poise::FrameworkOptions {
+///         commands: module::all_commands(),
+///         ..Default::default()
+///     })
+///     .build();
+/// ```
+pub mod matchdetails;
+pub mod utils;
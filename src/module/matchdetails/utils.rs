@@ -0,0 +1,464 @@
+use crate::image::{render_difference_chart, CHART_SIZE};
+use crate::models::champion_index::ChampionIndex;
+use crate::models::data::{EmbedTheme, EmojiId};
+use crate::models::error::Error;
+use crate::module::grind::utils::match_bans;
+use crate::module::pingstats::utils::PING_KINDS;
+use crate::utils::get_emoji;
+use mongodb::Collection;
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed};
+use serde_json::Value;
+
+/// ⚙️ **Function**: Retrieves the approximate positions of the wards placed by a participant.
+///
+/// # Parameters:
+/// - `timeline`: The match-v5 timeline fetched by `get_match_timeline`.
+/// - `participant_id`: The `participantId` (1 to 10) of the player.
+///
+/// # Returns:
+/// - `Vec<(f64, f64)>`: One position, in game units, per ward placed by the player.
+///
+/// # Example:
+/// ```rust
+/// let wards = ward_positions(&timeline, 3);
+/// let png = render_heatmap(&wards, 512)?;
+/// ```
+///
+/// # Notes:
+/// - `WARD_PLACED` events have no coordinates: the position of the player in the frame closest to the event (one frame
+///   per minute) is used instead, so the heatmap shows the areas warded rather than the exact spots.
+/// - Wards of an undefined type (e.g., the ones of some champion abilities) are ignored.
+pub fn ward_positions(timeline: &Value, participant_id: i64) -> Vec<(f64, f64)> {
+    let empty = vec![];
+    let frames = timeline["info"]["frames"].as_array().unwrap_or(&empty);
+    let frame_interval = timeline["info"]["frameInterval"]
+        .as_u64()
+        .unwrap_or(60000)
+        .max(1);
+    frames
+        .iter()
+        .flat_map(|frame| frame["events"].as_array().unwrap_or(&empty))
+        .filter(|event| {
+            event["type"].as_str() == Some("WARD_PLACED")
+                && event["creatorId"].as_i64() == Some(participant_id)
+                && event["wardType"].as_str() != Some("UNDEFINED")
+        })
+        .filter_map(|event| {
+            let timestamp = event["timestamp"].as_u64()?;
+            let index = ((timestamp + frame_interval / 2) / frame_interval) as usize;
+            let frame = frames.get(index).or_else(|| frames.last())?;
+            let position = &frame["participantFrames"][participant_id.to_string()]["position"];
+            Some((position["x"].as_f64()?, position["y"].as_f64()?))
+        })
+        .collect()
+}
+
+/// ⚙️ **Function**: Computes the gold difference between a team and its opponent, minute by minute.
+///
+/// # Parameters:
+/// - `timeline`: The match-v5 timeline fetched by `get_match_timeline`.
+/// - `team_id`: The team the difference is computed for (`100` for blue side, `200` for red side).
+///
+/// # Returns:
+/// - `Vec<(f64, f64)>`: The `(minute, gold difference)` of every frame; positive when `team_id` is ahead.
+///
+/// # Example:
+/// ```rust
+/// let gold = gold_difference(&timeline, 100);
+/// let png = render_difference_chart(&gold, 640, 320)?;
+/// ```
+///
+/// # Notes:
+/// - Participants 1 to 5 play on the blue side and 6 to 10 on the red side.
+pub fn gold_difference(timeline: &Value, team_id: i64) -> Vec<(f64, f64)> {
+    let empty = vec![];
+    timeline["info"]["frames"]
+        .as_array()
+        .unwrap_or(&empty)
+        .iter()
+        .map(|frame| {
+            let blue_lead: i64 = (1..=10)
+                .map(|participant_id| {
+                    let gold = frame["participantFrames"][participant_id.to_string()]["totalGold"]
+                        .as_i64()
+                        .unwrap_or(0);
+                    if participant_id <= 5 {
+                        gold
+                    } else {
+                        -gold
+                    }
+                })
+                .sum();
+            let minute = frame["timestamp"].as_f64().unwrap_or(0.0) / 60000.0;
+            let lead = if team_id == 200 {
+                -blue_lead
+            } else {
+                blue_lead
+            };
+            (minute, lead as f64)
+        })
+        .collect()
+}
+
+/// ⚙️ **Function**: Describes the largest lead and deficit of a gold difference.
+///
+/// # Parameters:
+/// - `gold`: The gold difference computed by `gold_difference`.
+///
+/// # Returns:
+/// - `String`: A line such as `Largest lead: **+3.2k** at 18 min · Largest deficit: **-1.1k** at 9 min`.
+pub fn describe_gold_difference(gold: &[(f64, f64)]) -> String {
+    let format_gold = |value: f64| format!("{:+.1}k", value / 1000.0);
+    let lead = gold.iter().cloned().fold(
+        (0.0, 0.0),
+        |best, point| {
+            if point.1 > best.1 {
+                point
+            } else {
+                best
+            }
+        },
+    );
+    let deficit =
+        gold.iter().cloned().fold(
+            (0.0, 0.0),
+            |worst, point| {
+                if point.1 < worst.1 {
+                    point
+                } else {
+                    worst
+                }
+            },
+        );
+    let mut parts = Vec::new();
+    if lead.1 > 0.0 {
+        parts.push(format!(
+            "Largest lead: **{}** at {:.0} min",
+            format_gold(lead.1),
+            lead.0
+        ));
+    }
+    if deficit.1 < 0.0 {
+        parts.push(format!(
+            "Largest deficit: **{}** at {:.0} min",
+            format_gold(deficit.1),
+            deficit.0
+        ));
+    }
+    if parts.is_empty() {
+        "The gold stayed even the whole game.".to_string()
+    } else {
+        parts.join(" · ")
+    }
+}
+
+/// ⚙️ **Function**: Creates the gold difference graph of a match, as an embed and its image attachment.
+///
+/// # Parameters:
+/// - `timeline`: The match-v5 timeline fetched by `get_match_timeline`.
+/// - `team_id`: The team the difference is computed for.
+/// - `color`: The color of the embed.
+///
+/// # Returns:
+/// - `Result<(CreateEmbed, CreateAttachment), Error>`: The embed describing the largest lead and deficit, showing the
+///   `gold.png` attachment, or an error if the chart cannot be drawn.
+///
+/// # Example:
+/// ```rust
+/// let (embed, attachment) = create_gold_graph(&timeline, 100, theme.info())?;
+/// let builder = CreateMessage::new().add_embed(embed).add_file(attachment);
+/// ```
+pub fn create_gold_graph(
+    timeline: &Value,
+    team_id: i64,
+    color: u32,
+) -> Result<(CreateEmbed, CreateAttachment), Error> {
+    let gold = gold_difference(timeline, team_id);
+    let png = render_difference_chart(&gold, CHART_SIZE.0, CHART_SIZE.1)?;
+    let embed = CreateEmbed::default()
+        .title("💰 Team gold difference")
+        .description(describe_gold_difference(&gold))
+        .color(color)
+        .image("attachment://gold.png");
+    Ok((embed, CreateAttachment::bytes(png, "gold.png")))
+}
+
+/// CS, gold and experience of a participant at a given frame of the timeline.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameStats {
+    pub cs: i64,
+    pub gold: i64,
+    pub xp: i64,
+}
+
+/// ⚙️ **Function**: Retrieves the CS, gold and experience of a participant at a given minute of the game.
+///
+/// # Parameters:
+/// - `timeline`: The match-v5 timeline fetched by `get_match_timeline`.
+/// - `participant_id`: The `participantId` (1 to 10) of the player.
+/// - `minute`: The minute of the frame (e.g., `10` for the laning phase).
+///
+/// # Returns:
+/// - `Option<FrameStats>`: The stats of the player, or `None` if the game did not last that long.
+///
+/// # Example:
+/// ```rust
+/// let at_10 = frame_stats_at(&timeline, 3, 10);
+/// ```
+///
+/// # Notes:
+/// - The CS include the jungle camps (`minionsKilled` + `jungleMinionsKilled`).
+pub fn frame_stats_at(timeline: &Value, participant_id: i64, minute: usize) -> Option<FrameStats> {
+    let frame = timeline["info"]["frames"].as_array()?.get(minute)?;
+    let stats = &frame["participantFrames"][participant_id.to_string()];
+    if stats.is_null() {
+        return None;
+    }
+    Some(FrameStats {
+        cs: stats["minionsKilled"].as_i64().unwrap_or(0)
+            + stats["jungleMinionsKilled"].as_i64().unwrap_or(0),
+        gold: stats["totalGold"].as_i64().unwrap_or(0),
+        xp: stats["xp"].as_i64().unwrap_or(0),
+    })
+}
+
+/// Epic monsters tracked by the jungle report, with their emoji (the `monsterType` of `ELITE_MONSTER_KILL` events).
+const JUNGLE_OBJECTIVES: [(&str, &str); 4] = [
+    ("DRAGON", "🐉"),
+    ("HORDE", "🪲"),
+    ("RIFTHERALD", "🦀"),
+    ("BARON_NASHOR", "🟣"),
+];
+
+/// Minute before which kills count as ganks in the jungle report.
+const GANK_PHASE_MINUTES: u64 = 14;
+
+/// Jungle-specific statistics of a participant, computed from the match timeline.
+#[derive(Debug, Default)]
+pub struct JungleStats {
+    /// `(emoji, objectives taken with the player, objectives taken by the team)` for each epic monster of `JUNGLE_OBJECTIVES`.
+    pub objectives: Vec<(&'static str, u32, u32)>,
+    /// Kills and assists of the player before 14 minutes.
+    pub early_takedowns: u32,
+    /// Kills of the player's team before 14 minutes.
+    pub early_team_kills: u32,
+}
+
+/// ⚙️ **Function**: Computes the objective control and early kill participation of a jungler.
+///
+/// # Parameters:
+/// - `timeline`: The match-v5 timeline fetched by `get_match_timeline`.
+/// - `participant_id`: The `participantId` (1 to 10) of the player.
+/// - `team_id`: The team of the player (`100` or `200`).
+///
+/// # Returns:
+/// - `JungleStats`: The epic monsters taken with the player's help and their takedowns before 14 minutes.
+///
+/// # Example:
+/// ```rust
+/// let stats = jungle_stats(&timeline, 2, 100);
+/// println!("{} ganks", stats.early_takedowns);
+/// ```
+///
+/// # Notes:
+/// - The player takes part in an objective or a kill as the killer or one of the assisting participants.
+/// - Participants 1 to 5 play on the blue side (`100`) and 6 to 10 on the red side (`200`).
+pub fn jungle_stats(timeline: &Value, participant_id: i64, team_id: i64) -> JungleStats {
+    let empty = vec![];
+    let events = timeline["info"]["frames"]
+        .as_array()
+        .unwrap_or(&empty)
+        .iter()
+        .flat_map(|frame| frame["events"].as_array().unwrap_or(&empty));
+    let involves_player = |event: &Value| {
+        event["killerId"].as_i64() == Some(participant_id)
+            || event["assistingParticipantIds"]
+                .as_array()
+                .is_some_and(|ids| ids.iter().any(|id| id.as_i64() == Some(participant_id)))
+    };
+    let mut stats = JungleStats {
+        objectives: JUNGLE_OBJECTIVES
+            .iter()
+            .map(|(_, emoji)| (*emoji, 0, 0))
+            .collect(),
+        ..Default::default()
+    };
+    for event in events {
+        match event["type"].as_str() {
+            Some("ELITE_MONSTER_KILL") if event["killerTeamId"].as_i64() == Some(team_id) => {
+                let Some(index) = JUNGLE_OBJECTIVES
+                    .iter()
+                    .position(|(monster, _)| event["monsterType"].as_str() == Some(*monster))
+                else {
+                    continue;
+                };
+                stats.objectives[index].2 += 1;
+                if involves_player(event) {
+                    stats.objectives[index].1 += 1;
+                }
+            }
+            Some("CHAMPION_KILL")
+                if event["timestamp"].as_u64().unwrap_or(u64::MAX) < GANK_PHASE_MINUTES * 60000 =>
+            {
+                let killer_team = match event["killerId"].as_i64() {
+                    Some(1..=5) => 100,
+                    Some(6..=10) => 200,
+                    _ => continue,
+                };
+                if killer_team == team_id {
+                    stats.early_team_kills += 1;
+                    if involves_player(event) {
+                        stats.early_takedowns += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    stats
+}
+
+/// ⚙️ **Function**: Lists the bans of each team of a match with the champion emojis.
+///
+/// # Parameters:
+/// - `match_info`: The match-v5 data of the match.
+/// - `team_id`: The team of the player (`100` or `200`), whose bans are listed first.
+/// - `champions`: The champion lookups, used to turn the `championId` of the bans into the names of the emojis.
+/// - `collection_emoji`: The MongoDB collection of emojis.
+///
+/// # Returns:
+/// - `Option<(String, String)>`: The bans of the player's team and of the enemy team, e.g. `(":Yasuo: :Zed:", "None")`,
+///   or `None` when nobody banned (blind pick, ARAM...).
+///
+/// # Example:
+/// ```rust
+/// if let Some((ally_bans, enemy_bans)) = ban_emojis(&match_info, 100, &champions, collection_emoji).await {
+///     embed = embed.field("**🚫 BANS**", format!("Ally: {}\nEnemy: {}", ally_bans, enemy_bans), false);
+/// }
+/// ```
+///
+/// # Notes:
+/// - A champion without an emoji is shown by its Data Dragon ID, and by its key when the champion lookups are empty.
+pub async fn ban_emojis(
+    match_info: &Value,
+    team_id: i64,
+    champions: &ChampionIndex,
+    collection_emoji: Collection<EmojiId>,
+) -> Option<(String, String)> {
+    let bans = match_bans(match_info);
+    if bans.is_empty() {
+        return None;
+    }
+    let mut ally_bans = Vec::new();
+    let mut enemy_bans = Vec::new();
+    for (ban_team_id, champion_id) in bans {
+        let id = champions
+            .id_from_key(champion_id)
+            .map(str::to_string)
+            .unwrap_or_else(|| champion_id.to_string());
+        let emoji = get_emoji(collection_emoji.clone(), "champions", &id)
+            .await
+            .unwrap_or(id);
+        if ban_team_id == team_id {
+            ally_bans.push(emoji);
+        } else {
+            enemy_bans.push(emoji);
+        }
+    }
+    let join = |bans: Vec<String>| {
+        if bans.is_empty() {
+            "None".to_string()
+        } else {
+            bans.join(" ")
+        }
+    };
+    Some((join(ally_bans), join(enemy_bans)))
+}
+
+/// ⚙️ **Function**: Creates the "Pings" page of a game, with the pings sent by every player.
+///
+/// # Parameters:
+/// - `match_info`: The match-v5 data of the game.
+/// - `puuid`: The PUUID of the player looked up, whose team is listed first.
+/// - `riot_id`: The Riot ID of the player looked up, for the title.
+/// - `theme`: The guild's embed theme.
+///
+/// # Returns:
+/// - `CreateEmbed`: One field per team, with a line per player listing their pings by kind (see `PING_KINDS`), most
+///   pings first.
+///
+/// # Example:
+/// ```text
+/// 📣 Pings of Faker#KR1's game
+/// Ally team
+/// **Faker#KR1** (Ahri) — 14 pings: ❓ Enemy missing 6 · 🏃 On my way 5 · ⚠️ Danger 3
+/// ```
+///
+/// # Notes:
+/// - Games played before Riot added the ping counters to match-v5 show no ping.
+pub fn create_embed_match_pings(
+    match_info: &Value,
+    puuid: &str,
+    riot_id: &str,
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let participants = match_info["info"]["participants"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let team_id = participants
+        .iter()
+        .find(|participant| participant["puuid"].as_str() == Some(puuid))
+        .and_then(|participant| participant["teamId"].as_i64())
+        .unwrap_or(100);
+    let team_lines = |ally: bool| {
+        let mut players: Vec<(u64, String)> = participants
+            .iter()
+            .filter(|participant| (participant["teamId"].as_i64() == Some(team_id)) == ally)
+            .map(|participant| {
+                let mut counts: Vec<(u64, &str)> = PING_KINDS
+                    .iter()
+                    .map(|(field, label)| (participant[*field].as_u64().unwrap_or(0), *label))
+                    .filter(|(count, _)| *count > 0)
+                    .collect();
+                counts.sort_by(|(a, _), (b, _)| b.cmp(a));
+                let total: u64 = counts.iter().map(|(count, _)| count).sum();
+                let name = format!(
+                    "{}#{}",
+                    participant["riotIdGameName"].as_str().unwrap_or("?"),
+                    participant["riotIdTagline"].as_str().unwrap_or("?")
+                );
+                let champion = participant["championName"].as_str().unwrap_or("?");
+                let line = if counts.is_empty() {
+                    format!("**{}** ({}) — no ping", name, champion)
+                } else {
+                    format!(
+                        "**{}** ({}) — {} ping{}: {}",
+                        name,
+                        champion,
+                        total,
+                        if total > 1 { "s" } else { "" },
+                        counts
+                            .iter()
+                            .map(|(count, label)| format!("{} {}", label, count))
+                            .collect::<Vec<_>>()
+                            .join(" · ")
+                    )
+                };
+                (total, line)
+            })
+            .collect();
+        players.sort_by(|(a, _), (b, _)| b.cmp(a));
+        players
+            .into_iter()
+            .map(|(_, line)| line)
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    CreateEmbed::default()
+        .title(format!("📣 Pings of {}'s game", riot_id))
+        .field("Ally team", team_lines(true), false)
+        .field("Enemy team", team_lines(false), false)
+        .color(theme.info())
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
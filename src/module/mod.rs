@@ -1,6 +1,138 @@
+/// 🛠 **Module module**: Contains every feature module of the bot and the registry of its slash commands.
+///
+/// Each feature lives in its own directory (`<name>/mod.rs`, `<name>/<name>.rs`, `<name>/utils.rs`). Adding a command
+/// only requires declaring its module here and adding it to `all_commands`.
+pub mod auditlog;
+pub mod backup;
+pub mod blacklist;
+pub mod botstatus;
+pub mod challenge;
 pub mod championsinfos;
+pub mod clash;
+pub mod clutchstats;
 pub mod followgames;
+pub mod goal;
+pub mod grind;
+pub mod guildmeta;
+pub mod linkaccount;
 pub mod lolstats;
 pub mod loop_module;
+pub mod masterygain;
+pub mod matchdetails;
+pub mod myaccounts;
+pub mod nemesis;
+pub mod optout;
+pub mod owner;
+pub mod pingstats;
+pub mod queuetime;
 pub mod randomchampions;
+pub mod scrim;
+pub mod session;
+pub mod settings;
+pub mod team;
+pub mod tierlist;
+pub mod unfollow;
+pub mod whentoplay;
 pub mod whoisfollowed;
+
+use crate::components::{register_component_handler, register_page_rows, register_view_loader};
+use crate::hooks::{apply_default_cooldown, handle_tos_component, TOS_COMPONENT_KIND};
+use crate::models::data::Data;
+use crate::models::error::Error;
+
+/// ⚙️ **Function**: Returns every slash command of the bot, as registered in the Poise framework.
+///
+/// # Returns:
+/// - `Vec<poise::Command<Data, Error>>`: The top-level commands. Subcommands are declared by their parent command.
+///
+/// # Notes:
+/// - Commands without their own cooldown get the default per-user cooldown (see `apply_default_cooldown`).
+///
+/// # Example:
+/// ```rust
+/// let framework = poise::Framework::builder()
+///     .options(poise::FrameworkOptions {
+///         commands: module::all_commands(),
+///         ..Default::default()
+///     })
+///     .build();
+/// ```
+pub fn all_commands() -> Vec<poise::Command<Data, Error>> {
+    let mut commands = vec![
+        lolstats::lolstats::lolstats(),
+        followgames::followgames::followgames(),
+        unfollow::unfollow::unfollow(),
+        unfollow::unfollowall::unfollowall(),
+        whoisfollowed::whoisfollowed::whoisfollowed(),
+        championsinfos::championsinfos::championsinfos(),
+        tierlist::tierlist::tierlist(),
+        randomchampions::randomchampions::randomchampions(),
+        randomchampions::randomroles::randomroles(),
+        randomchampions::aramroll::aramroll(),
+        challenge::challenge::challenge(),
+        goal::goal::goal(),
+        grind::grind::grind(),
+        session::session::session(),
+        matchdetails::matchdetails::matchdetails(),
+        clutchstats::clutchstats::clutchstats(),
+        pingstats::pingstats::pingstats(),
+        linkaccount::linkaccount::linkaccount(),
+        followgames::followuser::followuser(),
+        blacklist::blacklist::blacklist(),
+        optout::optout::optout(),
+        owner::owner::owner(),
+        botstatus::botstatus::botstatus(),
+        auditlog::auditlog::auditlog(),
+        backup::backup::backup(),
+        settings::settings::settings(),
+        myaccounts::myaccounts::myaccounts(),
+        myaccounts::mystats::mystats(),
+        masterygain::masterygain::masterygain(),
+        nemesis::nemesis::nemesis(),
+        whentoplay::whentoplay::whentoplay(),
+        queuetime::queuetime::queuetime(),
+        team::team::team(),
+        scrim::scrim::scrim(),
+        guildmeta::guildmeta::guildmeta(),
+    ];
+    apply_default_cooldown(&mut commands);
+    commands
+}
+
+/// ⚙️ **Function**: Registers the loaders of the views built on demand, the builders of the components shown under
+/// pages and the handlers of the persistent components of the feature modules.
+///
+/// # Example:
+/// ```rust
+/// module::register_components();
+/// ```
+///
+/// # Notes:
+/// - Must run before the bot answers its first interaction, so saved components can be answered after a restart.
+pub fn register_components() {
+    register_view_loader(
+        championsinfos::probuilds::PRO_BUILDS_VIEW_LOADER,
+        championsinfos::probuilds::load_pro_builds_view,
+    );
+    register_component_handler(
+        championsinfos::championsinfos::CHAMPION_SUGGESTION_KIND,
+        championsinfos::championsinfos::handle_champion_suggestion,
+    );
+    register_component_handler(
+        randomchampions::randomchampions::RANDOM_CHAMPION_KIND,
+        randomchampions::randomchampions::handle_random_champion_reroll,
+    );
+    register_page_rows(
+        randomchampions::randomchampions::RANDOM_CHAMPION_ROWS,
+        randomchampions::randomchampions::random_champion_rows,
+    );
+    register_component_handler(TOS_COMPONENT_KIND, handle_tos_component);
+    register_component_handler(
+        whoisfollowed::utils::FOLLOW_LIST_COMPONENT_KIND,
+        whoisfollowed::utils::handle_follow_list_component,
+    );
+    register_page_rows(
+        whoisfollowed::utils::FOLLOW_LIST_ROWS,
+        whoisfollowed::utils::follow_list_rows,
+    );
+}
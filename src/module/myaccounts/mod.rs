@@ -0,0 +1,27 @@
+/// 🛠 **Module myaccounts**: Contains the commands summarizing the League of Legends accounts linked to a Discord user.
+///
+/// Players often have accounts on several regions. Every account linked with `/linkaccount` can be summarized at
+/// once, and the stats of any of them can be displayed without typing its Riot ID again.
+///
+/// # Files in this module:
+/// - `myaccounts.rs`: The `/myaccounts` command listing the rank of every linked account.
+/// - `mystats.rs`: The `/mystats` command displaying the `/lolstats` embed of a linked account.
+/// - `utils.rs`: The account label and the autocomplete of the account selector.
+///
+/// # Example:
+/// The commands must be registered in the bot's main framework setup:
+///
+/// ```rust
+/// use module::myaccounts::myaccounts::myaccounts;
+/// use module::myaccounts::mystats::mystats;
+///
+/// let framework = poise::Framework::builder()
+///     .options(poise::FrameworkOptions {
+///         commands: vec![myaccounts(), mystats()],
+///         ..Default::default()
+///     })
+///     .build();
+/// ```
+pub mod myaccounts;
+pub mod mystats;
+pub mod utils;
@@ -0,0 +1,165 @@
+use crate::config::deletion_notice;
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, edit_and_schedule_deletion, send_fetching_message,
+};
+use crate::models::data::{Data, EmojiId};
+use crate::models::error::Error;
+use crate::module::linkaccount::utils::get_linked_accounts;
+use crate::module::lolstats::season::rank_score;
+use crate::module::myaccounts::utils::{account_label, linked_accounts_collection};
+use crate::module::settings::utils::get_guild_theme;
+use crate::riot_api::get_rank_info;
+use crate::utils::get_emoji;
+use futures::future::join_all;
+use mongodb::Collection;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use poise::CreateReply;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Summarizes the rank of every League of Legends account linked to you.
+///
+/// # Example:
+/// ```rust
+/// /myaccounts
+/// ```
+///
+/// # Notes:
+/// - Accounts are linked with `/linkaccount`; the primary account is marked with ⭐.
+/// - The best Solo/Duo rank across every account is shown at the top.
+#[poise::command(slash_command)]
+pub async fn myaccounts(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let mongo_client = &ctx.data().mongo_client;
+    let collection = linked_accounts_collection(mongo_client);
+    let accounts = match get_linked_accounts(&collection, ctx.author().id.get()).await {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            let reply =
+                create_embed_coded_error("Error collecting informations from MongoDB", &e.into());
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    if accounts.is_empty() {
+        let error_message =
+            "You need to link your League of Legends account with `/linkaccount` first.";
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+        return Ok(());
+    }
+
+    let client = reqwest::Client::new();
+    let ranks = join_all(accounts.iter().map(|account| {
+        get_rank_info(
+            &client,
+            &account.region,
+            &account.summoner_id,
+            &ctx.data().riot_api_key,
+        )
+    }))
+    .await;
+    let collection_emoji = mongo_client
+        .database("stat-summoner")
+        .collection::<EmojiId>("emojis_id");
+    let theme = get_guild_theme(ctx).await;
+
+    let mut embed = CreateEmbed::new()
+        .title(format!("🗂️ Linked accounts of {}", ctx.author().name))
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+    let mut best_solo: Option<(i64, String)> = None;
+    for (account, rank_info) in accounts.iter().zip(ranks) {
+        let label = account_label(account);
+        let value = match rank_info {
+            Ok(rank_info) => {
+                let mut lines = Vec::new();
+                for (queue_type, queue_label) in
+                    [("RANKED_SOLO_5x5", "Solo/Duo"), ("RANKED_FLEX_SR", "Flex")]
+                {
+                    let entry = rank_info.iter().find(|entry| {
+                        entry.get("queueType").and_then(Value::as_str) == Some(queue_type)
+                    });
+                    let rank = match entry {
+                        Some(entry) => format_rank(entry, collection_emoji.clone()).await,
+                        None => "Unranked".to_string(),
+                    };
+                    if let (Some(entry), "RANKED_SOLO_5x5") = (entry, queue_type) {
+                        let score = rank_score(
+                            entry.get("tier").and_then(Value::as_str).unwrap_or(""),
+                            entry.get("rank").and_then(Value::as_str).unwrap_or(""),
+                            entry
+                                .get("leaguePoints")
+                                .and_then(Value::as_i64)
+                                .unwrap_or(0),
+                        );
+                        if let Some(score) = score {
+                            let is_best = match &best_solo {
+                                Some((best, _)) => score > *best,
+                                None => true,
+                            };
+                            if is_best {
+                                best_solo = Some((score, format!("{} on **{}**", rank, label)));
+                            }
+                        }
+                    }
+                    lines.push(format!("{}: {}", queue_label, rank));
+                }
+                lines.join("\n")
+            }
+            Err(e) => format!("Rank unavailable ({})", e),
+        };
+        let name = if account.primary {
+            format!("⭐ {}", label)
+        } else {
+            label
+        };
+        embed = embed.field(name, value, false);
+    }
+    embed = embed.description(match best_solo {
+        Some((_, best)) => format!("🏆 Best Solo/Duo rank: {}", best),
+        None => "🏆 No ranked Solo/Duo account yet.".to_string(),
+    });
+
+    let reply = CreateReply {
+        embeds: vec![embed],
+        ..Default::default()
+    };
+    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+    Ok(())
+}
+
+async fn format_rank(
+    entry: &HashMap<String, Value>,
+    collection_emoji: Collection<EmojiId>,
+) -> String {
+    let tier = entry
+        .get("tier")
+        .and_then(Value::as_str)
+        .unwrap_or("Unranked");
+    let division = entry.get("rank").and_then(Value::as_str).unwrap_or("");
+    let league_points = entry
+        .get("leaguePoints")
+        .and_then(Value::as_i64)
+        .unwrap_or(0);
+    let wins = entry.get("wins").and_then(Value::as_i64).unwrap_or(0);
+    let losses = entry.get("losses").and_then(Value::as_i64).unwrap_or(0);
+    let tier_emoji = get_emoji(collection_emoji, "rank", tier)
+        .await
+        .unwrap_or(tier.to_string());
+    let winrate = if wins + losses > 0 {
+        wins as f64 / (wins + losses) as f64 * 100.0
+    } else {
+        0.0
+    };
+    format!(
+        "**{} {}** - {} LP ({:.0}% WR, {} games)",
+        tier_emoji,
+        division,
+        league_points,
+        winrate,
+        wins + losses
+    )
+}
@@ -0,0 +1,67 @@
+use crate::embed::{create_embed_coded_error, create_embed_error, schedule_message_deletion};
+use crate::models::data::Data;
+use crate::models::error::Error;
+use crate::module::linkaccount::utils::get_linked_accounts;
+use crate::module::lolstats::lolstats::show_lolstats;
+use crate::module::myaccounts::utils::{
+    account_label, autocomplete_linked_account, linked_accounts_collection,
+};
+use crate::utils::region_from_string;
+
+/// Displays the stats of one of your linked accounts, without typing its Riot ID.
+///
+/// # Parameters:
+/// - `account`: The linked account to display, chosen from the autocomplete list. Defaults to the primary account.
+///
+/// # Example:
+/// ```rust
+/// /mystats account: Faker#KR1 (KR)
+/// ```
+///
+/// # Notes:
+/// - The embed is the same as the one of `/lolstats`.
+#[poise::command(slash_command)]
+pub async fn mystats(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Linked account (defaults to your primary one)"]
+    #[autocomplete = "autocomplete_linked_account"]
+    account: Option<String>,
+) -> Result<(), Error> {
+    let collection = linked_accounts_collection(&ctx.data().mongo_client);
+    let accounts = match get_linked_accounts(&collection, ctx.author().id.get()).await {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            let reply = ctx
+                .send(create_embed_coded_error(
+                    "Error collecting informations from MongoDB",
+                    &e.into(),
+                ))
+                .await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(());
+        }
+    };
+    let selected = match &account {
+        Some(label) => accounts
+            .into_iter()
+            .find(|account| account_label(account) == label.trim()),
+        None => accounts.into_iter().next(),
+    };
+    let Some(selected) = selected else {
+        let error_message = match account {
+            Some(_) => "This account is not linked to you. Pick one from the list or link it with `/linkaccount`.",
+            None => "You need to link your League of Legends account with `/linkaccount` first.",
+        };
+        let reply = ctx.send(create_embed_error(error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    };
+    let Some(region) = region_from_string(&selected.region) else {
+        let error_message =
+            "The region of this linked account is unknown. Link it again with `/linkaccount`.";
+        let reply = ctx.send(create_embed_error(error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    };
+    show_lolstats(ctx, selected.riot_id(), region).await
+}
@@ -0,0 +1,162 @@
+use crate::models::data::{Data, LinkedAccountData};
+use crate::models::error::Error;
+use crate::module::linkaccount::utils::get_linked_accounts;
+use crate::utils::region_from_string;
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+use poise::serenity_prelude::{self as serenity, CacheHttp, GuildId, UserId};
+use poise::ChoiceParameter;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Time, in seconds, during which the membership of a user in a guild is reused by `is_guild_member`.
+const MEMBERSHIP_CACHE_SECS: i64 = 3600;
+
+/// Memberships already checked, as (guild ID, user ID) to (is member, checked at).
+type MembershipCache = HashMap<(u64, u64), (bool, i64)>;
+
+static MEMBERSHIP_CACHE: OnceLock<Mutex<MembershipCache>> = OnceLock::new();
+
+/// ⚙️ **Function**: Returns the `linked_accounts` collection.
+pub fn linked_accounts_collection(mongo_client: &Client) -> Collection<LinkedAccountData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<LinkedAccountData>("linked_accounts")
+}
+
+/// ⚙️ **Function**: Checks whether a user is a member of a guild.
+///
+/// # Parameters:
+/// - `cache_http`: The Discord cache and HTTP client. With the gateway cache, known members are found without a request.
+/// - `guild_id`: The ID of the guild.
+/// - `user_id`: The ID of the user.
+///
+/// # Returns:
+/// - `bool`: `true` if the user is a member of the guild.
+///
+/// # Notes:
+/// - The answers are kept for `MEMBERSHIP_CACHE_SECS`, so leaderboards and statistics built from the linked accounts
+///   do not ask Discord for every account at each use.
+/// - Only a definite answer is kept: after another error (e.g. a rate limit), the user is considered absent this time
+///   and checked again next time.
+pub async fn is_guild_member(
+    cache_http: impl CacheHttp,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> bool {
+    let key = (guild_id.get(), user_id.get());
+    let now = Utc::now().timestamp();
+    let cache = MEMBERSHIP_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some((is_member, checked_at)) = cache.lock().unwrap().get(&key) {
+        if now - checked_at < MEMBERSHIP_CACHE_SECS {
+            return *is_member;
+        }
+    }
+    let is_member = match guild_id.member(cache_http, user_id).await {
+        Ok(_) => Some(true),
+        Err(serenity::Error::Http(serenity::HttpError::UnsuccessfulRequest(response)))
+            if response.status_code.as_u16() == 404 =>
+        {
+            Some(false)
+        }
+        Err(e) => {
+            log::warn!(
+                "Failed to check the membership of {} in guild {}: {:?}",
+                user_id,
+                guild_id,
+                e
+            );
+            None
+        }
+    };
+    if let Some(is_member) = is_member {
+        cache.lock().unwrap().insert(key, (is_member, now));
+    }
+    is_member.unwrap_or(false)
+}
+
+/// ⚙️ **Function**: Returns the linked accounts of the members of a guild.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `cache_http`: The Discord cache and HTTP client, used by `is_guild_member`.
+/// - `guild_id`: The ID of the guild.
+///
+/// # Returns:
+/// - `Result<Vec<LinkedAccountData>, Error>`: The accounts linked by users who are members of the guild.
+///
+/// # Example:
+/// ```rust
+/// let accounts = guild_linked_accounts(mongo_client, ctx.serenity_context(), guild_id).await?;
+/// ```
+///
+/// # Notes:
+/// - Each user is checked once, even if they linked several accounts.
+pub async fn guild_linked_accounts(
+    mongo_client: &Client,
+    cache_http: impl CacheHttp,
+    guild_id: GuildId,
+) -> Result<Vec<LinkedAccountData>, Error> {
+    let accounts: Vec<LinkedAccountData> = linked_accounts_collection(mongo_client)
+        .find(doc! {})
+        .await?
+        .try_collect()
+        .await?;
+    let mut members: HashMap<u64, bool> = HashMap::new();
+    let mut guild_accounts = Vec::new();
+    for account in accounts {
+        let is_member = match members.get(&account.discord_id) {
+            Some(is_member) => *is_member,
+            None => {
+                let is_member =
+                    is_guild_member(&cache_http, guild_id, UserId::new(account.discord_id)).await;
+                members.insert(account.discord_id, is_member);
+                is_member
+            }
+        };
+        if is_member {
+            guild_accounts.push(account);
+        }
+    }
+    Ok(guild_accounts)
+}
+
+/// ⚙️ **Function**: Formats the label of a linked account, as displayed by the account selector of `/mystats`.
+///
+/// # Parameters:
+/// - `account`: The linked account.
+///
+/// # Returns:
+/// - `String`: The Riot ID followed by the region, e.g. `Faker#KR1 (KR)`.
+pub fn account_label(account: &LinkedAccountData) -> String {
+    let region = region_from_string(&account.region)
+        .map(|region| region.name().to_string())
+        .unwrap_or(account.region.to_uppercase());
+    format!("{} ({})", account.riot_id(), region)
+}
+
+/// ⚙️ **Function**: Autocompletes the account selector of `/mystats` with the accounts linked by the user.
+///
+/// # Parameters:
+/// - `ctx`: The application context of the command.
+/// - `partial`: The text typed so far.
+///
+/// # Returns:
+/// - `Vec<String>`: The labels of the matching accounts (see `account_label`), primary account first.
+pub async fn autocomplete_linked_account(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    partial: &str,
+) -> Vec<String> {
+    let collection = linked_accounts_collection(&ctx.data().mongo_client);
+    let partial = partial.trim().to_lowercase();
+    get_linked_accounts(&collection, ctx.author().id.get())
+        .await
+        .unwrap_or_default()
+        .iter()
+        .map(account_label)
+        .filter(|label| label.to_lowercase().contains(&partial))
+        .take(25)
+        .collect()
+}
@@ -0,0 +1,17 @@
+/// 🛠 **Module nemesis**: Contains the enemy champions a player has the worst win rate against.
+///
+/// The matchups are read from the archived games of the player: the `played_games` of their linked accounts and the
+/// `match_records` of the games notified while they were followed.
+///
+/// # Files in this module:
+/// - `nemesis.rs`: The `/nemesis` command.
+/// - `utils.rs`: The win rate against each enemy champion and the embed.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`:
+///
+/// ```rust
+/// nemesis::nemesis::nemesis(),
+/// ```
+pub mod nemesis;
+pub mod utils;
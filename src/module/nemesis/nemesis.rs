@@ -0,0 +1,80 @@
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, edit_and_schedule_deletion, send_fetching_message,
+};
+use crate::models::data::{Data, EmojiId};
+use crate::models::error::Error;
+use crate::module::grind::utils::{missing_account_message, select_linked_account};
+use crate::module::myaccounts::utils::{account_label, autocomplete_linked_account};
+use crate::module::nemesis::utils::{compute_matchups, create_embed_nemesis, find_nemeses};
+use crate::module::settings::utils::get_guild_theme;
+use poise::CreateReply;
+
+/// Shows the enemy champions you have the worst win rate against.
+///
+/// # Parameters:
+/// - `account`: The linked account to look up, chosen from the autocomplete list. Defaults to the primary account.
+///
+/// # Example:
+/// ```rust
+/// /nemesis
+/// /nemesis account: Faker#KR1 (KR)
+/// ```
+///
+/// # Notes:
+/// - Only the champions faced at least 3 times are considered.
+/// - Reads the games archived by the bot, so games played before linking or following the account are not counted.
+#[poise::command(slash_command)]
+pub async fn nemesis(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Linked account (defaults to your primary one)"]
+    #[autocomplete = "autocomplete_linked_account"]
+    account: Option<String>,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let mongo_client = &ctx.data().mongo_client;
+    let selected = match select_linked_account(
+        mongo_client,
+        ctx.author().id.get(),
+        account.as_deref(),
+    )
+    .await
+    {
+        Ok(Some(selected)) => selected,
+        Ok(None) => {
+            let error_message = missing_account_message(account.as_deref());
+            edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+            return Ok(());
+        }
+        Err(e) => {
+            let reply = create_embed_coded_error("Error collecting informations from MongoDB", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+
+    let (games, matchups) = match compute_matchups(mongo_client, &selected.puuid).await {
+        Ok(matchups) => matchups,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error reading the archived games", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    let nemeses = find_nemeses(&matchups);
+    let champions = ctx.data().champions.read().await;
+    let collection_emoji = mongo_client
+        .database("stat-summoner")
+        .collection::<EmojiId>("emojis_id");
+    let theme = get_guild_theme(ctx).await;
+    let embed = create_embed_nemesis(
+        &account_label(&selected),
+        games,
+        &nemeses,
+        &champions,
+        &collection_emoji,
+        &theme,
+    )
+    .await;
+    edit_and_schedule_deletion(fetching, ctx, CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use crate::config::deletion_notice;
+use crate::models::champion_index::ChampionIndex;
+use crate::models::data::{EmbedTheme, EmojiId};
+use crate::models::error::Error;
+use crate::module::grind::utils::archived_games;
+use crate::utils::get_emoji;
+use mongodb::{Client, Collection};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use std::collections::HashMap;
+
+/// Minimum number of games against a champion for it to be a nemesis.
+pub const NEMESIS_MIN_GAMES: u32 = 3;
+
+/// Number of nemeses shown in the embed.
+const NEMESIS_SIZE: usize = 5;
+
+/// Games and wins of a player against an enemy champion.
+#[derive(Debug, Clone)]
+pub struct Matchup {
+    /// The name of the champion (Data Dragon ID, e.g. `MonkeyKing`).
+    pub champion: String,
+    pub games: u32,
+    pub wins: u32,
+}
+
+impl Matchup {
+    /// ⚙️ **Function**: Returns the win rate of the player against the champion, between 0 and 1.
+    pub fn winrate(&self) -> f64 {
+        self.wins as f64 / self.games as f64
+    }
+}
+
+/// ⚙️ **Function**: Counts the games and wins of a player against each enemy champion over the archived games.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `puuid`: The PUUID of the player.
+///
+/// # Returns:
+/// - `Result<(usize, Vec<Matchup>), Error>`: The number of games read and the matchups, or an error if the archives
+///   cannot be read.
+///
+/// # Notes:
+/// - The games are read with `archived_games`; the ones archived before their enemy champions were kept are left out.
+pub async fn compute_matchups(
+    mongo_client: &Client,
+    puuid: &str,
+) -> Result<(usize, Vec<Matchup>), Error> {
+    let mut matchups: HashMap<String, (u32, u32)> = HashMap::new();
+    let mut games = 0;
+    for game in archived_games(mongo_client, puuid).await? {
+        if game.enemy_champions.is_empty() {
+            continue;
+        }
+        games += 1;
+        for enemy in game.enemy_champions {
+            let (games, wins) = matchups.entry(enemy).or_default();
+            *games += 1;
+            if game.win {
+                *wins += 1;
+            }
+        }
+    }
+
+    let matchups = matchups
+        .into_iter()
+        .map(|(champion, (games, wins))| Matchup {
+            champion,
+            games,
+            wins,
+        })
+        .collect();
+    Ok((games, matchups))
+}
+
+/// ⚙️ **Function**: Selects the nemeses of a player: the enemy champions they lose to the most.
+///
+/// # Parameters:
+/// - `matchups`: The matchups built by `compute_matchups`.
+///
+/// # Returns:
+/// - `Vec<Matchup>`: The champions faced at least `NEMESIS_MIN_GAMES` times with a win rate under 50%, worst win rate
+///   first and most games first on ties.
+pub fn find_nemeses(matchups: &[Matchup]) -> Vec<Matchup> {
+    let mut nemeses: Vec<Matchup> = matchups
+        .iter()
+        .filter(|matchup| matchup.games >= NEMESIS_MIN_GAMES && matchup.wins * 2 < matchup.games)
+        .cloned()
+        .collect();
+    nemeses.sort_by(|a, b| {
+        a.winrate()
+            .total_cmp(&b.winrate())
+            .then(b.games.cmp(&a.games))
+            .then(a.champion.cmp(&b.champion))
+    });
+    nemeses
+}
+
+/// ⚙️ **Function**: Creates the embed of the nemeses of a player.
+///
+/// # Parameters:
+/// - `label`: The label of the account, e.g. `Faker#KR1 (KR)`.
+/// - `games`: The number of archived games read.
+/// - `nemeses`: The nemeses selected by `find_nemeses`.
+/// - `champions`: The champion lookups, used to name the champions.
+/// - `collection_emoji`: The MongoDB collection of the custom emojis.
+/// - `theme`: The embed theme of the guild.
+///
+/// # Returns:
+/// - `CreateEmbed`: The 5 worst matchups of the player.
+///
+/// # Example:
+/// ```text
+/// 😈 Nemeses of Faker#KR1 (KR)
+/// 1. Zed — 1W - 5L (17%)
+/// 2. Yasuo — 1W - 3L (25%)
+/// ```
+pub async fn create_embed_nemesis(
+    label: &str,
+    games: usize,
+    nemeses: &[Matchup],
+    champions: &ChampionIndex,
+    collection_emoji: &Collection<EmojiId>,
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let mut lines = Vec::new();
+    for (rank, nemesis) in nemeses.iter().take(NEMESIS_SIZE).enumerate() {
+        let name = champions
+            .name(&nemesis.champion)
+            .unwrap_or(&nemesis.champion);
+        let emoji = get_emoji(collection_emoji.clone(), "champions", &nemesis.champion)
+            .await
+            .unwrap_or_default();
+        lines.push(format!(
+            "{}. {} **{}** — {}W - {}L ({:.0}%)",
+            rank + 1,
+            emoji,
+            name,
+            nemesis.wins,
+            nemesis.games - nemesis.wins,
+            nemesis.winrate() * 100.0
+        ));
+    }
+    let description = if lines.is_empty() {
+        format!(
+            "No enemy champion faced at least {} times beats you more often than not ({} archived games).",
+            NEMESIS_MIN_GAMES, games
+        )
+    } else {
+        format!(
+            "The enemy champions you lose to the most over {} archived games:\n\n{}",
+            games,
+            lines.join("\n")
+        )
+    };
+    CreateEmbed::default()
+        .title(format!("😈 Nemeses of {}", label))
+        .description(description)
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
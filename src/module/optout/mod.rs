@@ -0,0 +1,29 @@
+/// 🛠 **Module optout**: Contains the command allowing a player to opt out of Stat Summoner.
+///
+/// This module implements the opt-out registry promised by the privacy policy: a player who proved ownership of
+/// their linked League of Legends account can demand that it is never looked up or followed by any guild.
+///
+/// # Files in this module:
+/// - `optout.rs`: The `/optout` command, including the profile icon verification flow.
+/// - `utils.rs`: Utility functions to query and update the `opted_out_summoners` collection.
+///
+/// # Example:
+/// To use the command in this module, ensure it is registered in the bot's main framework setup:
+///
+/// ```rust
+/// use module::optout::optout::optout;
+///
+/// #[shuttle_runtime::main]
+/// async fn main() {
+///     let framework = poise::Framework::builder()
+///         .options(poise::FrameworkOptions {
+///             commands: vec![optout()], // Register the optout command
+///             ..Default::default()
+///         })
+///         .build();
+/// }
+/// ```
+///
+/// The registry is enforced by `/lolstats`, `/followgames` and `/followuser` through `is_opted_out`.
+pub mod optout;
+pub mod utils;
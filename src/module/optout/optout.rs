@@ -0,0 +1,120 @@
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, create_embed_sucess, edit_and_schedule_deletion,
+    send_fetching_message,
+};
+use crate::models::data::{Data, LinkedAccountData};
+use crate::models::error::Error;
+use crate::module::linkaccount::utils::get_linked_account;
+use crate::module::optout::utils::{cancel_opt_out, register_opt_out};
+use crate::riot_api::get_summoner;
+use crate::static_data::profile_icon_url;
+use mongodb::bson::doc;
+use rand::Rng;
+
+/// Opts the invoking user's linked League of Legends account out of Stat Summoner.
+///
+/// Once opted out, the account can no longer be looked up with `/lolstats` nor followed with `/followgames` or `/followuser`
+/// in any guild, and every existing follow of the account is removed.
+///
+/// # Parameters:
+/// - `ctx`: The `poise::ApplicationContext` in which the command is executed.
+/// - `cancel`: If `true`, removes the account from the opt-out registry instead.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an empty result if successful, or an error if the process fails.
+///
+/// # Flow:
+/// 1. The user must have linked their account with `/linkaccount`.
+/// 2. On the first call, the bot asks the user to set a specific profile icon on their League of Legends account.
+/// 3. On the second call, the bot checks the profile icon through the Riot API. If it matches, the opt-out (or its cancellation) is applied.
+///
+/// # Notes:
+/// - The profile icon check proves that the Discord user owns the linked account, so nobody can opt out someone else.
+/// - The icons requested are part of the default icons owned by every account.
+#[poise::command(slash_command)]
+pub async fn optout(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Cancel a previous opt-out"] cancel: Option<bool>,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let mongo_client = &ctx.data().mongo_client;
+    let collection_linked = mongo_client
+        .database("stat-summoner")
+        .collection::<LinkedAccountData>("linked_accounts");
+    let discord_id = ctx.author().id.get();
+
+    let linked_account = match get_linked_account(&collection_linked, discord_id).await? {
+        Some(account) => account,
+        None => {
+            let error_message =
+                "You need to link your League of Legends account with `/linkaccount` first.";
+            edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message)).await?;
+            return Ok(());
+        }
+    };
+
+    let Some(expected_icon_id) = linked_account.verification_icon_id else {
+        let icon_id = rand::thread_rng().gen_range(0..=28);
+        collection_linked
+            .update_one(
+                doc! { "discord_id": discord_id as i64, "puuid": &linked_account.puuid },
+                doc! { "$set": { "verification_icon_id": icon_id } },
+            )
+            .await?;
+        let message = format!(
+            "To prove that you own **{}**, set your League of Legends profile icon to this one, then run `/optout` again:\n{}",
+            linked_account.riot_id(),
+            profile_icon_url(icon_id).await?
+        );
+        edit_and_schedule_deletion(fetching, ctx, create_embed_sucess(&message)).await?;
+        return Ok(());
+    };
+
+    let client = reqwest::Client::new();
+    let summoner = match get_summoner(
+        &client,
+        &linked_account.region,
+        &linked_account.puuid,
+        &ctx.data().riot_api_key,
+    )
+    .await
+    {
+        Ok(summoner) => summoner,
+        Err(e) => {
+            edit_and_schedule_deletion(fetching, ctx, create_embed_coded_error("", &e)).await?;
+            return Ok(());
+        }
+    };
+    if summoner["profileIconId"].as_i64() != Some(expected_icon_id) {
+        let error_message = format!(
+            "Your profile icon does not match yet. Set it to this icon and run `/optout` again:\n{}",
+            profile_icon_url(expected_icon_id).await?
+        );
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message)).await?;
+        return Ok(());
+    }
+
+    collection_linked
+        .update_one(
+            doc! { "discord_id": discord_id as i64 },
+            doc! { "$unset": { "verification_icon_id": "" } },
+        )
+        .await?;
+
+    let reply = if cancel.unwrap_or(false) {
+        if cancel_opt_out(mongo_client, &linked_account.puuid).await? {
+            create_embed_sucess(
+                "Your opt-out has been cancelled. Your account can be looked up and followed again.",
+            )
+        } else {
+            create_embed_error("Your account is not opted out.")
+        }
+    } else {
+        register_opt_out(mongo_client, &linked_account.puuid, discord_id).await?;
+        create_embed_sucess(
+            "Your account has been opted out. It can no longer be looked up or followed by any server.",
+        )
+    };
+    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+    Ok(())
+}
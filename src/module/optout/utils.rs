@@ -0,0 +1,107 @@
+use crate::models::data::{FollowAuditEvent, OptedOutSummonerData, SummonerFollowedData};
+use crate::module::auditlog::utils::record_follow_event;
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::Client;
+
+/// ⚙️ **Function**: Checks whether a player has opted out of Stat Summoner.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `puuid`: The PUUID of the player to check.
+///
+/// # Returns:
+/// - `Result<bool, mongodb::error::Error>`: `true` if the player is registered in the `opted_out_summoners` collection.
+///
+/// # Example:
+/// ```rust
+/// if is_opted_out(&ctx.data().mongo_client, &puuid).await? {
+///     // Refuse to look up or follow this player
+/// }
+/// ```
+pub async fn is_opted_out(
+    mongo_client: &Client,
+    puuid: &str,
+) -> Result<bool, mongodb::error::Error> {
+    let collection = mongo_client
+        .database("stat-summoner")
+        .collection::<OptedOutSummonerData>("opted_out_summoners");
+    Ok(collection
+        .find_one(doc! { "puuid": puuid })
+        .await?
+        .is_some())
+}
+
+/// ⚙️ **Function**: Registers a player in the opt-out registry and stops every follow on their account.
+///
+/// This asynchronous function inserts the player in the `opted_out_summoners` collection (if not already present)
+/// and deletes every follow of their PUUID, in every guild. Each deleted follow is recorded in the audit log.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `puuid`: The PUUID of the player opting out.
+/// - `discord_id`: The Discord ID of the user who requested the opt-out.
+///
+/// # Returns:
+/// - `Result<(), mongodb::error::Error>`: Returns `Ok(())` if the registry was updated, or a MongoDB error otherwise.
+pub async fn register_opt_out(
+    mongo_client: &Client,
+    puuid: &str,
+    discord_id: u64,
+) -> Result<(), mongodb::error::Error> {
+    let database = mongo_client.database("stat-summoner");
+    let collection = database.collection::<OptedOutSummonerData>("opted_out_summoners");
+    if collection
+        .find_one(doc! { "puuid": puuid })
+        .await?
+        .is_none()
+    {
+        collection
+            .insert_one(OptedOutSummonerData {
+                puuid: puuid.to_string(),
+                discord_id,
+                opted_out_at: Utc::now().timestamp(),
+            })
+            .await?;
+    }
+    let collection_followed = database.collection::<SummonerFollowedData>("follower_summoner");
+    let follows: Vec<SummonerFollowedData> = collection_followed
+        .find(doc! { "puuid": puuid })
+        .await?
+        .try_collect()
+        .await?;
+    collection_followed
+        .delete_many(doc! { "puuid": puuid })
+        .await?;
+    for follow in &follows {
+        record_follow_event(
+            mongo_client,
+            FollowAuditEvent::Deleted,
+            follow,
+            Some(discord_id),
+        )
+        .await;
+    }
+    Ok(())
+}
+
+/// ⚙️ **Function**: Removes a player from the opt-out registry.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `puuid`: The PUUID of the player opting back in.
+///
+/// # Returns:
+/// - `Result<bool, mongodb::error::Error>`: `true` if the player was registered and has been removed.
+pub async fn cancel_opt_out(
+    mongo_client: &Client,
+    puuid: &str,
+) -> Result<bool, mongodb::error::Error> {
+    let result = mongo_client
+        .database("stat-summoner")
+        .collection::<OptedOutSummonerData>("opted_out_summoners")
+        .delete_one(doc! { "puuid": puuid })
+        .await?;
+    Ok(result.deleted_count > 0)
+}
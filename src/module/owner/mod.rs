@@ -0,0 +1,26 @@
+/// 🛠 **Module owner**: Contains the owner-level administration commands and the guild kill switch.
+///
+/// This module lets the bot owners supervise every guild the bot is in: list the guilds with their follow counts
+/// and command usage, disable specific commands in a guild that abuses the shared Riot API quota, and check the
+/// consumption of that quota per Riot API method, and look up the recent errors by error code.
+///
+/// # Files in this module:
+/// - `owner.rs`: The `/owner` command and its `guilds`, `killswitch`, `apiusage` and `errors` subcommands.
+/// - `utils.rs`: The `guild_flags` collection helpers, including the kill switch check and the command counter used by
+///   the framework hooks (see `hooks.rs`).
+///
+/// # Example:
+/// The command is registered through `module::all_commands`, and the hooks in the bot's main framework setup:
+///
+/// ```rust
+/// let framework = poise::Framework::builder()
+///     .options(poise::FrameworkOptions {
+///         commands: module::all_commands(),
+///         command_check: Some(|ctx| Box::pin(command_check(ctx))),
+///         pre_command: |ctx| Box::pin(pre_command(ctx)),
+///         ..Default::default()
+///     })
+///     .build();
+/// ```
+pub mod owner;
+pub mod utils;
@@ -0,0 +1,473 @@
+use crate::config::deletion_notice;
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, create_embed_sucess, edit_and_schedule_deletion,
+    schedule_message_deletion, send_fetching_message,
+};
+use crate::error_reporting::recent_errors;
+use crate::models::data::{CoreBuildData, Data, RunesData};
+use crate::models::error::Error;
+use crate::module::championsinfos::utils::autocomplete_champion;
+use crate::module::loop_module::loop_module::{
+    fetch_champion_data_exclusive, reload_champion_index,
+};
+use crate::module::owner::utils::{
+    clear_champion_overrides, count_guild_follows, guild_flags_collection, override_champion_data,
+    parse_override_names,
+};
+use crate::rate_limiter::api_usage_snapshot;
+use mongodb::bson::{self, doc, Document};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use poise::CreateReply;
+
+/// Owner-level administration of every guild the bot is in.
+///
+/// This slash command groups the `guilds`, `killswitch`, `apiusage`, `errors`, `refreshdata`, `overridechampion` and
+/// `clearoverride` subcommands and can only be used by the bot owners.
+///
+/// # Example:
+/// ```rust
+/// /owner guilds
+/// /owner killswitch guild_id: 123456789 feature: followgames disabled: true
+/// /owner apiusage
+/// /owner errors code: RIOT-429
+/// /owner refreshdata
+/// /owner overridechampion champion: Jhin winrate: 51.2
+/// /owner clearoverride champion: Jhin
+/// ```
+#[poise::command(
+    slash_command,
+    owners_only,
+    subcommands(
+        "guilds",
+        "killswitch",
+        "apiusage",
+        "errors",
+        "refreshdata",
+        "overridechampion",
+        "clearoverride"
+    )
+)]
+pub async fn owner(_ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Lists every guild the bot is in, with its follow count, command usage and disabled features.
+#[poise::command(slash_command, owners_only)]
+pub async fn guilds(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let mongo_client = &ctx.data().mongo_client;
+    let collection = guild_flags_collection(mongo_client);
+    let cache = &ctx.serenity_context().cache;
+    let guild_ids = cache.guilds();
+
+    let mut lines = Vec::new();
+    for guild_id in &guild_ids {
+        let guild_id_str = guild_id.get().to_string();
+        let name = guild_id
+            .name(cache)
+            .unwrap_or_else(|| "Unknown".to_string());
+        let follows = count_guild_follows(mongo_client, &guild_id_str).await?;
+        let flags = collection
+            .find_one(doc! { "guild_id": &guild_id_str })
+            .await?;
+        let commands_used = flags.as_ref().map_or(0, |flags| flags.commands_used);
+        let disabled = flags
+            .map(|flags| flags.disabled_features.join(", "))
+            .filter(|disabled| !disabled.is_empty())
+            .map(|disabled| format!(" | ⛔ {}", disabled))
+            .unwrap_or_default();
+        lines.push(format!(
+            "**{}** (`{}`)\nFollows: **{}** | Commands: **{}**{}",
+            name, guild_id_str, follows, commands_used, disabled
+        ));
+    }
+
+    let mut description = String::new();
+    for line in lines {
+        if description.len() + line.len() + 2 > 4000 {
+            description.push('…');
+            break;
+        }
+        description.push_str(&line);
+        description.push_str("\n\n");
+    }
+    let embed = CreateEmbed::new()
+        .title(format!("Guilds ({})", guild_ids.len()))
+        .description(description)
+        .color(0xA020F0)
+        .footer(CreateEmbedFooter::new(deletion_notice()))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+    let reply = ctx
+        .send(CreateReply {
+            embeds: vec![embed],
+            ephemeral: Some(true),
+            ..Default::default()
+        })
+        .await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Enables or disables a command in a guild (kill switch).
+///
+/// # Parameters:
+/// - `guild_id`: The ID of the guild to restrict.
+/// - `feature`: The name of the command to toggle (e.g., `followgames`), or `all` for every command.
+/// - `disabled`: `true` to disable the command in the guild, `false` to enable it again.
+#[poise::command(slash_command, owners_only)]
+pub async fn killswitch(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "ID of the guild"] guild_id: String,
+    #[description = "Command name to toggle, or `all`"] feature: String,
+    #[description = "Disable (true) or enable (false) the command"] disabled: bool,
+) -> Result<(), Error> {
+    let feature = feature.trim().to_lowercase();
+    let is_known_feature = feature == "all"
+        || ctx
+            .framework()
+            .options()
+            .commands
+            .iter()
+            .any(|command| command.name == feature);
+    if !is_known_feature {
+        let error_message = format!("Unknown command `{}`.", feature);
+        let reply = ctx.send(create_embed_error(&error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+
+    let collection = guild_flags_collection(&ctx.data().mongo_client);
+    let update = if disabled {
+        doc! { "$addToSet": { "disabled_features": &feature } }
+    } else {
+        doc! { "$pull": { "disabled_features": &feature } }
+    };
+    collection
+        .update_one(doc! { "guild_id": guild_id.trim() }, update)
+        .upsert(true)
+        .await?;
+
+    let sucess_message = if disabled {
+        format!("`{}` has been disabled in guild `{}`.", feature, guild_id)
+    } else {
+        format!("`{}` has been enabled in guild `{}`.", feature, guild_id)
+    };
+    let reply = ctx.send(create_embed_sucess(&sucess_message)).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Shows the current Riot API consumption of every method and key since startup.
+///
+/// Counts come from the rate limit headers of the last Riot response, so a window shows the consumption at the time
+/// of the last call of that method.
+#[poise::command(slash_command, owners_only)]
+pub async fn apiusage(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let usages = api_usage_snapshot();
+    let mut description = String::new();
+    for usage in &usages {
+        let windows = usage
+            .windows
+            .iter()
+            .map(|window| {
+                let percent = if window.limit > 0 {
+                    window.count as f64 / window.limit as f64 * 100.0
+                } else {
+                    0.0
+                };
+                let warning = if percent >= 80.0 { " ⚠️" } else { "" };
+                format!(
+                    "{}/{} per {}s ({:.0}%){}",
+                    window.count, window.limit, window.window_secs, percent, warning
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(" | ");
+        let line = format!(
+            "**{}** — {} calls\n{}\n\n",
+            usage.method,
+            usage.total_calls,
+            if windows.is_empty() {
+                "No limit reported".to_string()
+            } else {
+                windows
+            }
+        );
+        if description.len() + line.len() > 4000 {
+            description.push('…');
+            break;
+        }
+        description.push_str(&line);
+    }
+    if description.is_empty() {
+        description = "No Riot API call has been made since startup.".to_string();
+    }
+    let embed = CreateEmbed::new()
+        .title("Riot API usage")
+        .description(description)
+        .color(0xA020F0)
+        .footer(CreateEmbedFooter::new(deletion_notice()))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+    let reply = ctx
+        .send(CreateReply {
+            embeds: vec![embed],
+            ephemeral: Some(true),
+            ..Default::default()
+        })
+        .await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Lists the most recent errors displayed to users, optionally filtered by error code.
+///
+/// # Parameters:
+/// - `code`: An error code or a code prefix (e.g., `RIOT-404-SUMMONER`, `DB`).
+#[poise::command(slash_command, owners_only)]
+pub async fn errors(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Error code or prefix (e.g. RIOT-429, DB)"] code: Option<String>,
+) -> Result<(), Error> {
+    let errors = recent_errors(code.as_deref(), 15);
+    let mut description = String::new();
+    for error in &errors {
+        let line = format!(
+            "<t:{}:R> `{}`\n{}\n\n",
+            error.occurred_at, error.code, error.message
+        );
+        if description.len() + line.len() > 4000 {
+            description.push('…');
+            break;
+        }
+        description.push_str(&line);
+    }
+    if description.is_empty() {
+        description = match &code {
+            Some(code) => format!("No recent error with the code `{}`.", code.to_uppercase()),
+            None => "No error has been displayed since startup.".to_string(),
+        };
+    }
+    let embed = CreateEmbed::new()
+        .title("Recent errors")
+        .description(description)
+        .color(0xA020F0)
+        .footer(CreateEmbedFooter::new(deletion_notice()))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+    let reply = ctx
+        .send(CreateReply {
+            embeds: vec![embed],
+            ephemeral: Some(true),
+            ..Default::default()
+        })
+        .await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Reloads Data Dragon and scrapes the champion statistics now, without waiting for the daily refresh.
+///
+/// Useful right after a patch is released: new champions become available to every command, and the champion
+/// statistics are stored under the new patch.
+///
+/// # Notes:
+/// - Only the champion lookups of the instance answering the command are rebuilt; the other instances rebuild theirs
+///   on their next daily refresh, as the reply says. The champion statistics are shared through MongoDB.
+/// - The statistics are scraped under the same lease as the daily refresh (see `fetch_champion_data_exclusive`), so
+///   the command does nothing if they are already being scraped.
+/// - Emojis are read from MongoDB each time they are displayed, so there is no emoji cache to reload.
+#[poise::command(slash_command, owners_only)]
+pub async fn refreshdata(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let data_dragon = match reload_champion_index(&ctx.data().champions).await {
+        Ok(version) => format!(
+            "✅ Data Dragon {} loaded on this instance ({} champions). The other instances reload it on their next daily refresh.",
+            version,
+            ctx.data().champions.read().await.names().len()
+        ),
+        Err(e) => format!("❌ Data Dragon could not be reloaded: {}", e),
+    };
+    let owner = format!("refreshdata-{}", ctx.id());
+    let champion_data = match fetch_champion_data_exclusive(&ctx.data().mongo_client, &owner).await
+    {
+        Ok(true) => "✅ Champion statistics scraped".to_string(),
+        Ok(false) => {
+            "⏳ Champion statistics are already being scraped, by the daily refresh or another instance. Try again in a few minutes.".to_string()
+        }
+        Err(e) => format!("❌ Champion statistics could not be scraped: {}", e),
+    };
+    let embed = CreateEmbed::new()
+        .title("Data refresh")
+        .description(format!("{}\n{}", data_dragon, champion_data))
+        .color(0xA020F0)
+        .footer(CreateEmbedFooter::new(deletion_notice()))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+    edit_and_schedule_deletion(fetching, ctx, CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Manually corrects the statistics of a champion when the scraped ones are wrong.
+///
+/// The corrected values are stored on the latest patch of the champion with an `override: true` flag, which the daily
+/// scrape leaves untouched until `/owner clearoverride`. The statistics of the next patch are scraped as usual.
+///
+/// # Parameters:
+/// - `champion`: The champion to correct.
+/// - `winrate`: The winrate, in percent.
+/// - `build`: The three core items, separated by commas (e.g. `Galeforce, Infinity Edge, Rapid Firecannon`).
+/// - `runes`: The keystone, the three primary runes, the two secondary runes and the three shards, separated by commas.
+///
+/// # Notes:
+/// - Item and rune names are cleaned like the scraped ones, so they match the emojis (`Infinity Edge` → `InfinityEdge`).
+#[poise::command(slash_command, owners_only)]
+pub async fn overridechampion(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Champion to correct"]
+    #[autocomplete = "autocomplete_champion"]
+    champion: String,
+    #[description = "Winrate in percent, e.g. 51.2"]
+    #[min = 0]
+    #[max = 100]
+    winrate: Option<f64>,
+    #[description = "The 3 core items, separated by commas"] build: Option<String>,
+    #[description = "Keystone, 3 primary runes, 2 secondary runes and 3 shards, separated by commas"]
+    runes: Option<String>,
+) -> Result<(), Error> {
+    let champion_id = ctx
+        .data()
+        .champions
+        .read()
+        .await
+        .id_from_name(&champion)
+        .map(str::to_string);
+    let Some(champion_id) = champion_id else {
+        let error_message = format!(
+            "`{}` is not a champion. Pick it from the suggestions.",
+            champion
+        );
+        let reply = ctx.send(create_embed_error(&error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    };
+
+    let mut update = Document::new();
+    if let Some(winrate) = winrate {
+        update.insert("winrate", (winrate / 100.0).to_string());
+    }
+    if let Some(build) = &build {
+        let items = parse_override_names(build);
+        if items.len() != 3 {
+            let reply = ctx
+                .send(create_embed_error(
+                    "The build must list exactly 3 items, separated by commas.",
+                ))
+                .await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(());
+        }
+        let core_build = CoreBuildData {
+            first: items[0].clone(),
+            second: items[1].clone(),
+            third: items[2].clone(),
+        };
+        update.insert("core_build", bson::to_document(&core_build)?);
+    }
+    if let Some(runes) = &runes {
+        let names = parse_override_names(runes);
+        if names.len() != 9 {
+            let error_message = "The runes must list exactly 9 names, separated by commas: the keystone, 3 primary runes, 2 secondary runes and 3 shards.";
+            let reply = ctx.send(create_embed_error(error_message)).await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(());
+        }
+        let runes = RunesData {
+            parent_primary_rune: names[0].clone(),
+            child_primary_rune_1: names[1].clone(),
+            child_primary_rune_2: names[2].clone(),
+            child_primary_rune_3: names[3].clone(),
+            child_secondary_rune_1: names[4].clone(),
+            child_secondary_rune_2: names[5].clone(),
+            tertiary_rune_1: names[6].clone(),
+            tertiary_rune_2: names[7].clone(),
+            tertiary_rune_3: names[8].clone(),
+        };
+        update.insert("runes", bson::to_document(&runes)?);
+    }
+    if update.is_empty() {
+        let reply = ctx
+            .send(create_embed_error(
+                "Give at least a winrate, a build or runes to correct.",
+            ))
+            .await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+
+    let reply = match override_champion_data(&ctx.data().mongo_client, &champion_id, update).await
+    {
+        Ok(Some(patch)) => create_embed_sucess(&format!(
+            "The statistics of **{}** on patch {} are now overridden. The daily scrape will not change them until `/owner clearoverride`.",
+            champion.trim(),
+            patch
+        )),
+        Ok(None) => create_embed_error(&format!(
+            "No statistics are stored for **{}** yet. Run `/owner refreshdata` first.",
+            champion.trim()
+        )),
+        Err(e) => create_embed_coded_error("Error updating the champion statistics", &e),
+    };
+    let reply = ctx.send(reply).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Removes the manual corrections of a champion, or of every champion, so the scrape updates them.
+///
+/// # Parameters:
+/// - `champion`: The champion whose corrections are removed, every champion when omitted.
+///
+/// # Notes:
+/// - The corrected values stay displayed until the next scrape, which can be run now with `/owner refreshdata`.
+#[poise::command(slash_command, owners_only)]
+pub async fn clearoverride(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Champion to clear, every champion by default"]
+    #[autocomplete = "autocomplete_champion"]
+    champion: Option<String>,
+) -> Result<(), Error> {
+    let champion_id = match &champion {
+        Some(champion) => {
+            let champion_id = ctx
+                .data()
+                .champions
+                .read()
+                .await
+                .id_from_name(champion)
+                .map(str::to_string);
+            let Some(champion_id) = champion_id else {
+                let error_message = format!(
+                    "`{}` is not a champion. Pick it from the suggestions.",
+                    champion
+                );
+                let reply = ctx.send(create_embed_error(&error_message)).await?;
+                schedule_message_deletion(reply, ctx).await?;
+                return Ok(());
+            };
+            Some(champion_id)
+        }
+        None => None,
+    };
+
+    let reply = match clear_champion_overrides(&ctx.data().mongo_client, champion_id.as_deref())
+        .await
+    {
+        Ok(0) => create_embed_error("There is no manual correction to clear."),
+        Ok(count) => create_embed_sucess(&format!(
+            "{} correction{} cleared. The scraped statistics come back with the next scrape, or now with `/owner refreshdata`.",
+            count,
+            if count > 1 { "s" } else { "" }
+        )),
+        Err(e) => create_embed_coded_error("Error updating the champion statistics", &e.into()),
+    };
+    let reply = ctx.send(reply).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
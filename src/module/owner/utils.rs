@@ -0,0 +1,186 @@
+use crate::embed::create_embed_error;
+use crate::models::data::{ChampionData, Data, GuildFlagsData, SummonerFollowedData};
+use crate::models::error::Error;
+use crate::module::championsinfos::utils::get_champion_data;
+use crate::module::loop_module::utils::clean_alt_text;
+use mongodb::bson::{doc, Document};
+use mongodb::{Client, Collection};
+
+/// ⚙️ **Function**: Returns the `guild_flags` collection.
+pub fn guild_flags_collection(mongo_client: &Client) -> Collection<GuildFlagsData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<GuildFlagsData>("guild_flags")
+}
+
+/// ⚙️ **Function**: Command check refusing commands disabled in the current guild by the kill switch.
+///
+/// This asynchronous function is called by the framework's `command_check` (see `hooks::command_check`). It looks up
+/// the guild in the `guild_flags` collection and refuses the command if its name (or `all`) is part of the guild's
+/// disabled features. Subcommands are refused with their parent command.
+///
+/// # Parameters:
+/// - `ctx`: The poise `Context` of the command about to be executed.
+///
+/// # Returns:
+/// - `Result<bool, Error>`: `true` if the command can run, `false` if it has been disabled in this guild.
+///
+/// # ⚠️ Notes:
+/// - Bot owners and direct messages are never restricted, so owners can always lift a kill switch.
+/// - When a command is refused, an error embed is sent to explain why, since poise only logs failed checks.
+pub async fn guild_command_check(ctx: poise::Context<'_, Data, Error>) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+    if ctx.framework().options().owners.contains(&ctx.author().id) {
+        return Ok(true);
+    }
+    let collection = guild_flags_collection(&ctx.data().mongo_client);
+    let Some(flags) = collection
+        .find_one(doc! { "guild_id": guild_id.get().to_string() })
+        .await?
+    else {
+        return Ok(true);
+    };
+    // Une fonctionnalité désactivée est une commande de premier niveau, avec ses sous-commandes
+    let command_name = ctx
+        .command()
+        .qualified_name
+        .split(' ')
+        .next()
+        .unwrap_or_default();
+    let disabled = flags
+        .disabled_features
+        .iter()
+        .any(|feature| feature == "all" || feature == command_name);
+    if disabled {
+        ctx.send(create_embed_error(
+            "This command has been disabled in this server by the bot owners.",
+        ))
+        .await?;
+    }
+    Ok(!disabled)
+}
+
+/// ⚙️ **Function**: Counts the commands used in each guild.
+///
+/// This asynchronous function is called by the framework's `pre_command` hook (see `hooks::pre_command`) and
+/// increments the `commands_used` counter of the guild in the `guild_flags` collection, creating the document if needed.
+///
+/// # Parameters:
+/// - `ctx`: The poise `Context` of the command about to be executed.
+///
+/// # ⚠️ Notes:
+/// - Errors are only logged: a failure to count a command must never prevent it from running.
+pub async fn count_guild_command(ctx: poise::Context<'_, Data, Error>) {
+    let Some(guild_id) = ctx.guild_id() else {
+        return;
+    };
+    let collection = guild_flags_collection(&ctx.data().mongo_client);
+    if let Err(e) = collection
+        .update_one(
+            doc! { "guild_id": guild_id.get().to_string() },
+            doc! { "$inc": { "commands_used": 1_i64 } },
+        )
+        .upsert(true)
+        .await
+    {
+        log::error!("Failed to count command usage: {:?}", e);
+    }
+}
+
+/// ⚙️ **Function**: Counts the summoners followed in a guild.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the Discord guild.
+///
+/// # Returns:
+/// - `Result<u64, mongodb::error::Error>`: The number of follows stored for the guild.
+pub async fn count_guild_follows(
+    mongo_client: &Client,
+    guild_id: &str,
+) -> Result<u64, mongodb::error::Error> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<SummonerFollowedData>("follower_summoner")
+        .count_documents(doc! { "guild_id": guild_id })
+        .await
+}
+
+/// ⚙️ **Function**: Splits a comma-separated list of item or rune names into the names used for the emojis.
+///
+/// # Parameters:
+/// - `names`: The names typed by the owner, e.g. `Sunfire Aegis, Thornmail, Spirit Visage`.
+///
+/// # Returns:
+/// - `Vec<String>`: The names cleaned like the scraped ones (see `clean_alt_text`), e.g. `SunfireAegis`.
+pub fn parse_override_names(names: &str) -> Vec<String> {
+    names
+        .split(',')
+        .map(clean_alt_text)
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// ⚙️ **Function**: Overrides the latest statistics of a champion.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `id_name`: The Data Dragon identifier of the champion (e.g., `MonkeyKing`).
+/// - `update`: The corrected fields, e.g. `{ "winrate": "0.512" }`.
+///
+/// # Returns:
+/// - `Result<Option<String>, Error>`: The patch of the overridden statistics, or `None` if none are stored for the
+///   champion.
+///
+/// # Notes:
+/// - The document is flagged with `override: true`, so that `fetch_champion_data` leaves it as is. The statistics of
+///   the next patch are scraped as usual.
+pub async fn override_champion_data(
+    mongo_client: &Client,
+    id_name: &str,
+    mut update: Document,
+) -> Result<Option<String>, Error> {
+    let collection = mongo_client
+        .database("stat-summoner")
+        .collection::<ChampionData>("champions_data");
+    let Some(champion) = get_champion_data(&collection, id_name, None).await? else {
+        return Ok(None);
+    };
+    update.insert("override", true);
+    collection
+        .update_one(
+            doc! { "id_name": id_name, "patch": &champion.patch },
+            doc! { "$set": update },
+        )
+        .await?;
+    Ok(Some(champion.patch))
+}
+
+/// ⚙️ **Function**: Removes the overrides of a champion, or of every champion.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `id_name`: The Data Dragon identifier of the champion, `None` for every champion.
+///
+/// # Returns:
+/// - `Result<u64, mongodb::error::Error>`: The number of documents no longer overridden.
+///
+/// # Notes:
+/// - The corrected values stay displayed until the next scrape replaces them.
+pub async fn clear_champion_overrides(
+    mongo_client: &Client,
+    id_name: Option<&str>,
+) -> Result<u64, mongodb::error::Error> {
+    let mut filter = doc! { "override": true };
+    if let Some(id_name) = id_name {
+        filter.insert("id_name", id_name);
+    }
+    let result = mongo_client
+        .database("stat-summoner")
+        .collection::<ChampionData>("champions_data")
+        .update_many(filter, doc! { "$set": { "override": false } })
+        .await?;
+    Ok(result.modified_count)
+}
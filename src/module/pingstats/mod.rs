@@ -0,0 +1,22 @@
+/// 🛠 **Module pingstats**: Contains the ping statistics of the summoners followed in a server.
+///
+/// This module reads the ping counters of match-v5 (`enemyMissingPings`, `baitPings`, `onMyWayPings`, ...) over the
+/// last games of every followed summoner, to find out who spams "?" the most.
+///
+/// # Files in this module:
+/// - `pingstats.rs`: The `/pingstats` command.
+/// - `utils.rs`: The ping counters, their aggregation per player and the leaderboard embed.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`:
+///
+/// ```rust
+/// let framework = poise::Framework::builder()
+///     .options(poise::FrameworkOptions {
+///         commands: module::all_commands(),
+///         ..Default::default()
+///     })
+///     .build();
+/// ```
+pub mod pingstats;
+pub mod utils;
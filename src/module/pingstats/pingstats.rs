@@ -0,0 +1,114 @@
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, edit_and_schedule_deletion, send_fetching_message,
+};
+use crate::models::data::{Data, SummonerFollowedData};
+use crate::models::error::Error;
+use crate::module::optout::utils::is_opted_out;
+use crate::module::pingstats::utils::{count_pings, create_embed_pingstats};
+use crate::module::settings::utils::get_guild_theme;
+use crate::riot_api::{get_matchs_id, get_matchs_info};
+use futures::future::join_all;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use poise::CreateReply;
+use reqwest::Client;
+use serde_json::Value;
+
+/// Maximum number of followed summoners looked up, to keep the number of Riot API calls bounded.
+const MAX_PINGSTATS_PLAYERS: usize = 10;
+
+/// Shows who spams "?" the most among the summoners followed on this server.
+///
+/// # Example:
+/// ```rust
+/// /pingstats
+/// /pingstats games: 10
+/// ```
+///
+/// # Notes:
+/// - Only the first 10 followed summoners are looked up.
+#[poise::command(slash_command, guild_only)]
+pub async fn pingstats(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Number of recent games per player (5 by default)"]
+    #[min = 1]
+    #[max = 10]
+    games: Option<u32>,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let games = games.unwrap_or(5);
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let client = Client::new();
+    let riot_api_key = &ctx.data().riot_api_key;
+    let mongo_client = &ctx.data().mongo_client;
+    let collection = mongo_client
+        .database("stat-summoner")
+        .collection::<SummonerFollowedData>("follower_summoner");
+
+    let mut followed: Vec<SummonerFollowedData> =
+        match collection.find(doc! { "guild_id": &guild_id }).await {
+            Ok(cursor) => match cursor.try_collect().await {
+                Ok(followed) => followed,
+                Err(e) => {
+                    let reply =
+                        create_embed_coded_error("Error fetching followed summoners", &e.into());
+                    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+                    return Ok(());
+                }
+            },
+            Err(e) => {
+                let reply =
+                    create_embed_coded_error("Error fetching followed summoners", &e.into());
+                edit_and_schedule_deletion(fetching, ctx, reply).await?;
+                return Ok(());
+            }
+        };
+    let mut seen = Vec::new();
+    followed.retain(|summoner| {
+        let first = !seen.contains(&summoner.puuid);
+        seen.push(summoner.puuid.clone());
+        first
+    });
+    followed.truncate(MAX_PINGSTATS_PLAYERS);
+    if followed.is_empty() {
+        let error_message = "No summoner is followed on this server. Use `/followgames` first.";
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+        return Ok(());
+    }
+
+    let mut players = Vec::new();
+    for summoner in &followed {
+        if is_opted_out(mongo_client, &summoner.puuid).await? {
+            continue;
+        }
+        let match_ids = match get_matchs_id(&client, &summoner.puuid, riot_api_key, games).await {
+            Ok(match_ids) => match_ids,
+            Err(e) => {
+                log::error!("Error fetching the matches of {}: {:?}", summoner.name, e);
+                continue;
+            }
+        };
+        let matches: Vec<Value> = join_all(
+            match_ids
+                .iter()
+                .map(|match_id| get_matchs_info(&client, match_id, riot_api_key)),
+        )
+        .await
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect();
+        let name = summoner.riot_id().to_string();
+        players.push(count_pings(&name, &summoner.puuid, &matches));
+    }
+
+    let reply = CreateReply {
+        embeds: vec![create_embed_pingstats(
+            players,
+            games,
+            &get_guild_theme(ctx).await,
+        )],
+        ..Default::default()
+    };
+    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+    Ok(())
+}
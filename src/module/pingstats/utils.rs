@@ -0,0 +1,144 @@
+use crate::config::deletion_notice;
+use crate::models::data::EmbedTheme;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use serde_json::Value;
+
+/// Ping counters of a match-v5 participant, with their label. The "?" ping (`enemyMissingPings`) comes first.
+pub const PING_KINDS: [(&str, &str); 8] = [
+    ("enemyMissingPings", "❓ Enemy missing"),
+    ("baitPings", "🎣 Bait"),
+    ("dangerPings", "⚠️ Danger"),
+    ("onMyWayPings", "🏃 On my way"),
+    ("assistMePings", "🆘 Assist me"),
+    ("getBackPings", "🔙 Get back"),
+    ("enemyVisionPings", "👁️ Enemy vision"),
+    ("needVisionPings", "🔦 Need vision"),
+];
+
+/// Pings sent by a followed summoner over their last games.
+#[derive(Debug)]
+pub struct PlayerPings {
+    pub name: String,
+    pub games: u32,
+    /// Number of pings of each kind, in the order of `PING_KINDS`.
+    pub counts: [u64; PING_KINDS.len()],
+}
+
+impl PlayerPings {
+    /// Average number of "?" pings per game.
+    pub fn missing_per_game(&self) -> f64 {
+        self.counts[0] as f64 / self.games.max(1) as f64
+    }
+}
+
+/// ⚙️ **Function**: Sums up the pings sent by a player over a list of matches.
+///
+/// # Parameters:
+/// - `name`: The display name of the player (e.g. `Faker#KR1`).
+/// - `puuid`: The PUUID of the player.
+/// - `matches`: The match-v5 data of the player's matches.
+///
+/// # Returns:
+/// - `PlayerPings`: The number of games found and the pings of each kind of `PING_KINDS`.
+///
+/// # Example:
+/// ```rust
+/// let pings = count_pings("Faker#KR1", &puuid, &matches);
+/// println!("{:.1} \"?\" per game", pings.missing_per_game());
+/// ```
+pub fn count_pings(name: &str, puuid: &str, matches: &[Value]) -> PlayerPings {
+    let mut pings = PlayerPings {
+        name: name.to_string(),
+        games: 0,
+        counts: [0; PING_KINDS.len()],
+    };
+    for match_data in matches {
+        let Some(participant) =
+            match_data["info"]["participants"]
+                .as_array()
+                .and_then(|participants| {
+                    participants
+                        .iter()
+                        .find(|participant| participant["puuid"].as_str() == Some(puuid))
+                })
+        else {
+            continue;
+        };
+        pings.games += 1;
+        for (count, (field, _)) in pings.counts.iter_mut().zip(PING_KINDS) {
+            *count += participant[field].as_u64().unwrap_or(0);
+        }
+    }
+    pings
+}
+
+/// ⚙️ **Function**: Creates the leaderboard embed of the pings of the followed summoners.
+///
+/// # Parameters:
+/// - `players`: The pings of every followed summoner, built by `count_pings`.
+/// - `games`: The number of recent games looked up per player.
+/// - `theme`: The embed theme of the guild.
+///
+/// # Returns:
+/// - `CreateEmbed`: The players ranked by "?" pings per game, with their favorite ping.
+///
+/// # Example:
+/// ```text
+/// ❓ Who spams "?" the most (last 5 games)
+/// 🥇 Faker#KR1 — 12.4 "?" per game (62) · favorite: ❓ Enemy missing
+/// 🥈 Caps#EUW — 3.0 "?" per game (15) · favorite: 🏃 On my way
+/// ```
+///
+/// # Notes:
+/// - Players without any game found are left out.
+pub fn create_embed_pingstats(
+    mut players: Vec<PlayerPings>,
+    games: u32,
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    players.retain(|player| player.games > 0);
+    players.sort_by(|a, b| b.missing_per_game().total_cmp(&a.missing_per_game()));
+    let lines = players
+        .iter()
+        .enumerate()
+        .map(|(index, player)| {
+            let medal = match index {
+                0 => "🥇".to_string(),
+                1 => "🥈".to_string(),
+                2 => "🥉".to_string(),
+                _ => format!("**{}.**", index + 1),
+            };
+            let favorite = player
+                .counts
+                .iter()
+                .zip(PING_KINDS)
+                .filter(|(count, _)| **count > 0)
+                .max_by_key(|(count, _)| **count)
+                .map(|(_, (_, label))| label)
+                .unwrap_or("none");
+            format!(
+                "{} **{}** — {:.1} \"?\" per game ({}) · favorite: {}",
+                medal,
+                player.name,
+                player.missing_per_game(),
+                player.counts[0],
+                favorite
+            )
+        })
+        .collect::<Vec<_>>();
+    CreateEmbed::default()
+        .title(format!(
+            "❓ Who spams \"?\" the most (last {} games)",
+            games
+        ))
+        .color(theme.info())
+        .description(if lines.is_empty() {
+            "No recent game found for the followed summoners.".to_string()
+        } else {
+            lines.join("\n")
+        })
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
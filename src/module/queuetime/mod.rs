@@ -0,0 +1,18 @@
+/// 🛠 **Module queuetime**: Contains the estimate of the queue times of a region.
+///
+/// The time between the end of a game and the start of the next game of the same player is read from the archived
+/// games, without any call to the Riot API: when a player queues again right away, it covers the queue, the champion
+/// select and the loading screen.
+///
+/// # Files in this module:
+/// - `queuetime.rs`: The `/queuetime` command.
+/// - `utils.rs`: The gaps between consecutive games and the embed.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`:
+///
+/// ```rust
+/// queuetime::queuetime::queuetime(),
+/// ```
+pub mod queuetime;
+pub mod utils;
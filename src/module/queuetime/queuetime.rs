@@ -0,0 +1,40 @@
+use crate::embed::{create_embed_coded_error, edit_and_schedule_deletion, send_fetching_message};
+use crate::models::data::Data;
+use crate::models::error::Error;
+use crate::models::region::Region;
+use crate::module::queuetime::utils::{create_embed_queuetime, estimate_queue_times};
+use crate::module::settings::utils::get_guild_theme;
+use crate::utils::region_to_string;
+use poise::{ChoiceParameter, CreateReply};
+
+/// Estimates the queue times of a region from the games of the players followed by the bot.
+///
+/// # Example:
+/// ```rust
+/// /queuetime region: EUW
+/// ```
+///
+/// # Notes:
+/// - Derived from the archived games of the last 7 days only: no Riot API request is made.
+#[poise::command(slash_command)]
+pub async fn queuetime(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Select the region"] region: Region,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let now = chrono::Utc::now().timestamp();
+    let estimates =
+        match estimate_queue_times(&ctx.data().mongo_client, &region_to_string(&region), now).await
+        {
+            Ok(estimates) => estimates,
+            Err(e) => {
+                let reply = create_embed_coded_error("Error reading the archived games", &e);
+                edit_and_schedule_deletion(fetching, ctx, reply).await?;
+                return Ok(());
+            }
+        };
+    let theme = get_guild_theme(ctx).await;
+    let embed = create_embed_queuetime(region.name(), &estimates, &theme);
+    edit_and_schedule_deletion(fetching, ctx, CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use crate::config::deletion_notice;
+use crate::models::data::{EmbedTheme, MatchRecordData, PlayedGameData};
+use crate::models::error::Error;
+use crate::module::grind::utils::played_games_collection;
+use crate::utils::get_game_mode;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::Client;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use std::collections::{HashMap, HashSet};
+
+/// Period of the archived games read for the estimate (7 days).
+const QUEUE_TIME_PERIOD_SECS: i64 = 7 * 86400;
+
+/// Longest gap between two games still counted as a requeue (15 minutes).
+const MAX_REQUEUE_GAP_SECS: i64 = 900;
+
+/// Minimum number of requeues for a queue to be estimated.
+const MIN_REQUEUES: usize = 3;
+
+/// Archived game of a player, as read for the estimate.
+struct ArchivedGame {
+    queue_id: i64,
+    started_at: i64,
+    ended_at: i64,
+}
+
+/// Estimated queue time of a queue.
+#[derive(Debug)]
+pub struct QueueEstimate {
+    pub queue_id: i64,
+    /// Median time between a game and the next one, in seconds.
+    pub median_secs: i64,
+    /// Number of requeues the estimate is based on.
+    pub samples: usize,
+}
+
+/// ⚙️ **Function**: Estimates the queue times of a region from the archived games of its players.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `platform`: The server of the region, as in the match IDs (e.g. `euw1`).
+/// - `now`: The current UNIX timestamp.
+///
+/// # Returns:
+/// - `Result<Vec<QueueEstimate>, Error>`: The estimate of each queue with enough requeues, most played queue first.
+///
+/// # Notes:
+/// - The gaps of the games of the last 7 days are read, and the gaps over 15 minutes are left out: the player did not
+///   queue again right away.
+/// - The gap is counted in the queue of the next game, and the median is used so that a few slow requeues do not skew
+///   the estimate.
+pub async fn estimate_queue_times(
+    mongo_client: &Client,
+    platform: &str,
+    now: i64,
+) -> Result<Vec<QueueEstimate>, Error> {
+    let since = now - QUEUE_TIME_PERIOD_SECS;
+    let match_prefix = format!("^{}_", platform.to_uppercase());
+    let mut counted: HashSet<(String, String)> = HashSet::new();
+    let mut games: HashMap<String, Vec<ArchivedGame>> = HashMap::new();
+
+    let played_games: Vec<PlayedGameData> = played_games_collection(mongo_client)
+        .find(doc! { "match_id": { "$regex": &match_prefix }, "started_at": { "$gte": since } })
+        .await?
+        .try_collect()
+        .await?;
+    for game in played_games {
+        if counted.insert((game.puuid.clone(), game.match_id.clone())) {
+            games.entry(game.puuid).or_default().push(ArchivedGame {
+                queue_id: game.queue_id,
+                started_at: game.started_at,
+                ended_at: game.played_at,
+            });
+        }
+    }
+
+    let records: Vec<MatchRecordData> = mongo_client
+        .database("stat-summoner")
+        .collection::<MatchRecordData>("match_records")
+        .find(doc! { "match_id": { "$regex": &match_prefix }, "started_at": { "$gte": since } })
+        .await?
+        .try_collect()
+        .await?;
+    for record in records {
+        for puuid in record.followed_puuids {
+            if counted.insert((puuid.clone(), record.match_id.clone())) {
+                games.entry(puuid).or_default().push(ArchivedGame {
+                    queue_id: record.queue_id,
+                    started_at: record.started_at,
+                    ended_at: record.played_at,
+                });
+            }
+        }
+    }
+
+    let mut gaps: HashMap<i64, Vec<i64>> = HashMap::new();
+    for player_games in games.values_mut() {
+        player_games.sort_by_key(|game| game.started_at);
+        for pair in player_games.windows(2) {
+            let gap = pair[1].started_at - pair[0].ended_at;
+            if (1..=MAX_REQUEUE_GAP_SECS).contains(&gap) {
+                gaps.entry(pair[1].queue_id).or_default().push(gap);
+            }
+        }
+    }
+    let mut estimates: Vec<QueueEstimate> = gaps
+        .into_iter()
+        .filter(|(_, gaps)| gaps.len() >= MIN_REQUEUES)
+        .map(|(queue_id, mut gaps)| {
+            gaps.sort_unstable();
+            QueueEstimate {
+                queue_id,
+                median_secs: gaps[gaps.len() / 2],
+                samples: gaps.len(),
+            }
+        })
+        .collect();
+    estimates.sort_by(|a, b| b.samples.cmp(&a.samples).then(a.queue_id.cmp(&b.queue_id)));
+    Ok(estimates)
+}
+
+/// ⚙️ **Function**: Creates the embed of the queue times of a region.
+///
+/// # Parameters:
+/// - `region`: The name of the region, e.g. `EUW`.
+/// - `estimates`: The estimates built by `estimate_queue_times`.
+/// - `theme`: The embed theme of the guild.
+///
+/// # Returns:
+/// - `CreateEmbed`: One line per queue, with the estimated time between two games and a color for the queue health.
+///
+/// # Example:
+/// ```text
+/// ⏳ Queue times on EUW
+/// 🟢 Ranked Solo/Duo: ~2m 40s (23 requeues)
+/// 🟡 ARAM: ~4m 05s (6 requeues)
+/// ```
+pub fn create_embed_queuetime(
+    region: &str,
+    estimates: &[QueueEstimate],
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let description = if estimates.is_empty() {
+        format!(
+            "Not enough games were archived on {} over the last 7 days to estimate the queue times.",
+            region
+        )
+    } else {
+        let lines = estimates
+            .iter()
+            .map(|estimate| {
+                let health = match estimate.median_secs {
+                    0..=179 => "🟢",
+                    180..=359 => "🟡",
+                    _ => "🔴",
+                };
+                format!(
+                    "{} **{}**: ~{}m {:02}s ({} requeues)",
+                    health,
+                    get_game_mode(estimate.queue_id),
+                    estimate.median_secs / 60,
+                    estimate.median_secs % 60,
+                    estimate.samples
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "Time between the end of a game and the start of the next one, champion select and loading included:\n\n{}",
+            lines
+        )
+    };
+    CreateEmbed::default()
+        .title(format!("⏳ Queue times on {}", region))
+        .description(description)
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
@@ -0,0 +1,130 @@
+use crate::embed::{create_embed_error, schedule_message_deletion, send_embed_with_views};
+use crate::models::bracket::RankBracket;
+use crate::models::data::{ChampionData, Data, EmojiId};
+use crate::models::error::Error;
+use crate::module::championsinfos::utils::{
+    autocomplete_champion, create_embed_champions_info, create_embed_situational_build,
+    get_champion_data,
+};
+use crate::module::settings::utils::{get_guild_champion_aliases, get_guild_theme};
+use crate::utils::resolve_champion_alias;
+use rand::seq::SliceRandom;
+
+/// Picks one champion among the ones offered in your ARAM lobby and shows its build.
+///
+/// # Parameters:
+/// - `ctx`: The command's context.
+/// - `champion1` to `champion15`: The champions offered in the lobby (your champion, the rerolls and the bench).
+///
+/// # Example:
+/// ```rust
+/// /aramroll champion1: Ahri champion2: Jinx champion3: Sion
+/// ```
+///
+/// # Notes:
+/// - Names are picked from the autocomplete list; nicknames such as `mf` are accepted, unknown names and duplicates
+///   are ignored.
+/// - The build shown is the one stored for the champion (Summoner's Rift statistics), no ARAM specific build is
+///   collected yet.
+#[poise::command(slash_command)]
+pub async fn aramroll(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Champion offered in the lobby"]
+    #[autocomplete = "autocomplete_champion"]
+    champion1: String,
+    #[autocomplete = "autocomplete_champion"] champion2: Option<String>,
+    #[autocomplete = "autocomplete_champion"] champion3: Option<String>,
+    #[autocomplete = "autocomplete_champion"] champion4: Option<String>,
+    #[autocomplete = "autocomplete_champion"] champion5: Option<String>,
+    #[autocomplete = "autocomplete_champion"] champion6: Option<String>,
+    #[autocomplete = "autocomplete_champion"] champion7: Option<String>,
+    #[autocomplete = "autocomplete_champion"] champion8: Option<String>,
+    #[autocomplete = "autocomplete_champion"] champion9: Option<String>,
+    #[autocomplete = "autocomplete_champion"] champion10: Option<String>,
+    #[autocomplete = "autocomplete_champion"] champion11: Option<String>,
+    #[autocomplete = "autocomplete_champion"] champion12: Option<String>,
+    #[autocomplete = "autocomplete_champion"] champion13: Option<String>,
+    #[autocomplete = "autocomplete_champion"] champion14: Option<String>,
+    #[autocomplete = "autocomplete_champion"] champion15: Option<String>,
+) -> Result<(), Error> {
+    let pool = [
+        Some(champion1),
+        champion2,
+        champion3,
+        champion4,
+        champion5,
+        champion6,
+        champion7,
+        champion8,
+        champion9,
+        champion10,
+        champion11,
+        champion12,
+        champion13,
+        champion14,
+        champion15,
+    ];
+    let guild_aliases = get_guild_champion_aliases(ctx).await;
+    let mut id_names: Vec<String> = Vec::new();
+    {
+        let champions = ctx.data().champions.read().await;
+        for name in pool.into_iter().flatten() {
+            let id_name = champions
+                .id_from_name(&name)
+                .map(str::to_string)
+                .or_else(|| {
+                    resolve_champion_alias(&champions, &guild_aliases, &name)
+                        .and_then(|name| champions.id_from_name(&name).map(str::to_string))
+                });
+            if let Some(id_name) = id_name {
+                if !id_names.contains(&id_name) {
+                    id_names.push(id_name);
+                }
+            }
+        }
+    }
+    let Some(picked) = id_names.choose(&mut rand::thread_rng()).cloned() else {
+        let error_message = "None of these champions exist. Pick them from the suggestions.";
+        let reply = ctx.send(create_embed_error(error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    };
+
+    let mongo_client = &ctx.data().mongo_client;
+    let collection_champions = mongo_client
+        .database("stat-summoner")
+        .collection::<ChampionData>("champions_data");
+    let collection_emoji = mongo_client
+        .database("stat-summoner")
+        .collection::<EmojiId>("emojis_id");
+    let Some(champion_data) = get_champion_data(&collection_champions, &picked, None).await? else {
+        let error_message = format!("No data recorded for {} yet.", picked);
+        let reply = ctx.send(create_embed_error(&error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    };
+    let theme = get_guild_theme(ctx).await;
+    let embed = create_embed_champions_info(
+        champion_data.clone(),
+        RankBracket::All,
+        &theme,
+        &collection_emoji,
+    )
+    .await?
+    .description(format!(
+        "🎲 Picked **{}** among {} champion{}.",
+        champion_data.name,
+        id_names.len(),
+        if id_names.len() > 1 { "s" } else { "" }
+    ));
+    let situational =
+        create_embed_situational_build(embed.clone(), &champion_data, &collection_emoji).await?;
+    let views = vec![
+        ("Overview".to_string(), embed),
+        ("Situational".to_string(), situational),
+    ];
+    if let Err(e) = send_embed_with_views(ctx, views).await {
+        log::error!("Failed to send champion information: {}", e);
+    }
+    Ok(())
+}
@@ -1,17 +1,21 @@
-/// 🛠 **Module commands**: Contains all bot commands for the Discord bot.
+/// 🛠 **Module randomchampions**: Contains all bot commands for the Discord bot.
 ///
 /// This module organizes the different commands used by the bot. Each command is stored in its own file
-/// within the `commands` directory. These commands are registered and used through the bot's interaction
+/// within the `module/randomchampions` directory. These commands are registered and used through the bot's interaction
 /// with Discord via the Poise framework.
 ///
 /// # Files in this module:
+/// - `aramroll.rs`: The `/aramroll` command, picking one champion among the ones offered in an ARAM lobby.
 /// - `randomchampions.rs`: The command for selecting a random League of Legends champion and displaying its information.
+/// - `randomroles.rs`: The `/randomroles` command, randomly assigning the five roles (and optionally a champion each) to
+///   the players of a voice channel.
+/// - `utils.rs`: The champion queries and random picks shared by the commands.
 ///
 /// # Example:
 /// To use the command in this module, ensure it is registered in the bot's main framework setup:
 ///
 /// ```rust
-/// use commands::randomchampions::randomchampions;
+/// use module::randomchampions::randomchampions::randomchampions;
 ///
 /// #[shuttle_runtime::main]
 /// async fn main() {
@@ -24,6 +28,8 @@
 /// }
 /// ```
 ///
-/// As more commands are added, they will be included here and imported into the main bot setup.
+/// As more commands are added, they will be included here and added to `module::all_commands`.
+pub mod aramroll;
 pub mod randomchampions;
+pub mod randomroles;
 pub mod utils;
@@ -1,10 +1,42 @@
-use crate::embed::schedule_message_deletion;
-use crate::models::data::{Data, EmojiId};
+use crate::components::{component_id, save_component_state, save_views, PageRows};
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, edit_and_schedule_deletion,
+    schedule_message_deletion, send_fetching_message,
+};
+use crate::models::bracket::RankBracket;
+use crate::models::data::{Data, EmbedTheme, EmojiId};
 use crate::models::error::Error;
 use crate::models::role::Role;
-use crate::module::championsinfos::utils::create_embed_champions_info;
-use crate::module::randomchampions::utils::{get_list_champions, get_random_champion};
-use poise::CreateReply;
+use crate::module::championsinfos::utils::{
+    create_embed_champions_info, create_embed_situational_build,
+};
+use crate::module::randomchampions::utils::{
+    filter_free_rotation, get_list_champions_for_role, get_random_champion,
+    match_role_with_database_roles,
+};
+use crate::module::settings::utils::get_guild_theme;
+use crate::riot_api::get_champion_rotation;
+use futures::future::BoxFuture;
+use poise::serenity_prelude as serenity;
+use poise::{CreateReply, ReplyHandle};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serenity::builder::CreateEmbed;
+
+/// Kind of the component state of the reroll button, answered by `handle_random_champion_reroll`.
+pub const RANDOM_CHAMPION_KIND: &str = "random-champion";
+
+/// Name of the builder of the reroll button shown under the views of `/randomchampions`.
+pub const RANDOM_CHAMPION_ROWS: &str = "random-champion";
+
+/// The options of `/randomchampions`, saved with the reroll button to pick another champion later.
+#[derive(Serialize, Deserialize)]
+struct RandomChampionContext {
+    /// The role of the champions, as stored in the database.
+    role: Option<String>,
+    free_rotation_only: bool,
+    theme: EmbedTheme,
+}
 
 /// Generates a random League of Legends champion embed and sends it as a Discord message.
 ///
@@ -15,15 +47,21 @@ use poise::CreateReply;
 /// # Parameters:
 /// - `ctx`: The command's context, providing access to the bot, the message, and other utilities.
 /// - `role`: An optional parameter specifying the role of the champion. If provided, the champion list will be filtered accordingly.
+/// - `free_rotation_only`: When `true`, only the champions of the current free rotation (champion-v3) can be picked.
 ///
 /// # Returns:
 /// - `Result<(), Error>`: Returns `Ok(())` if the command executes successfully, otherwise returns an `Error`.
 ///
 /// # ⚠️ Notes:
 /// - The function calls `get_list_champions` to retrieve a list of champions, optionally filtered by role.
+/// - With `free_rotation_only`, the list is narrowed with `filter_free_rotation`. The rotation is read from the EUW
+///   platform, the free rotation being the same on every region. The "Fetching data…" placeholder is sent before
+///   the Riot API is called, then replaced with the champion.
 /// - It uses `get_random_champion` to randomly select a champion from the filtered list.
 /// - `create_embed_champions_info` is called to construct a richly formatted embed with the champion's details.
 /// - After sending the embed, the message is scheduled for deletion after 60 seconds to keep the chat clean.
+/// - A "🎲 Reroll" button under the embed picks another champion with the same options (see
+///   `handle_random_champion_reroll`). It keeps working after a restart, until the message is deleted.
 ///
 /// # Example:
 /// ```rust
@@ -71,21 +109,218 @@ use poise::CreateReply;
 pub async fn randomchampions(
     ctx: poise::ApplicationContext<'_, Data, Error>,
     #[description = "Select a role (optional)"] role: Option<Role>,
+    #[description = "Only pick champions of the free rotation"] free_rotation_only: Option<bool>,
 ) -> Result<(), Error> {
-    let champions_list = get_list_champions(ctx, role).await?;
-    let mongo_client = &ctx.data().mongo_client;
-    let collection_emoji = mongo_client
-        .database("stat-summoner")
-        .collection::<EmojiId>("emojis_id");
-    let champion_data = get_random_champion(champions_list);
-    let embed = create_embed_champions_info(champion_data, &collection_emoji).await?;
-    let reply = CreateReply {
-        embeds: vec![embed],
-        ..Default::default()
+    let context = RandomChampionContext {
+        role: role.map(match_role_with_database_roles),
+        free_rotation_only: free_rotation_only.unwrap_or(false),
+        theme: get_guild_theme(ctx).await,
+    };
+    // La rotation gratuite est demandée à Riot : différer la réponse avant l'appel
+    let fetching = if context.free_rotation_only {
+        Some(send_fetching_message(ctx).await?)
+    } else {
+        None
+    };
+    let views = match build_random_champion_views(ctx.data(), &context).await? {
+        Ok(views) => views,
+        Err(reply) => return send_random_champion_reply(fetching, ctx, reply).await,
+    };
+    let key = format!("reroll-{}", ctx.id());
+    save_component_state(
+        &ctx.data().mongo_client,
+        &key,
+        RANDOM_CHAMPION_KIND,
+        Some(ctx.author().id.get()),
+        &context,
+    )
+    .await?;
+    let rows = PageRows {
+        builder: RANDOM_CHAMPION_ROWS,
+        payload: key,
     };
-    let sent_message = ctx.send(reply).await?;
-    if let Err(e) = schedule_message_deletion(sent_message, ctx).await {
-        log::error!("Failed to schedule message deletion: {}", e);
+    let components = save_views(
+        &ctx.data().mongo_client,
+        &format!("views-{}", ctx.id()),
+        ctx.author().id.get(),
+        &views,
+        &[],
+        false,
+        false,
+        Some(&rows),
+    )
+    .await?;
+    let reply = CreateReply::default()
+        .embed(views[0].1.clone())
+        .components(components);
+    if let Err(e) = send_random_champion_reply(fetching, ctx, reply).await {
+        log::error!("Failed to send champion information: {}", e);
     }
     Ok(())
 }
+
+/// ⚙️ **Function**: Sends the reply of `/randomchampions`, replacing the "Fetching data…" placeholder when the free
+/// rotation was fetched, then schedules its deletion.
+async fn send_random_champion_reply(
+    fetching: Option<ReplyHandle<'_>>,
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    reply: CreateReply,
+) -> Result<(), Error> {
+    match fetching {
+        Some(fetching) => edit_and_schedule_deletion(fetching, ctx, reply).await,
+        None => {
+            let sent_message = ctx.send(reply).await?;
+            schedule_message_deletion(sent_message, ctx).await
+        }
+    }
+}
+
+/// ⚙️ **Function**: Picks a random champion matching the options of `/randomchampions` and builds its views.
+///
+/// # Parameters:
+/// - `data`: The data of the bot.
+/// - `context`: The options of the command.
+///
+/// # Returns:
+/// - `Result<Result<Vec<(String, CreateEmbed)>, CreateReply>, Error>`: The views of the champion picked, or the error
+///   reply explaining to the user why there is none.
+async fn build_random_champion_views(
+    data: &Data,
+    context: &RandomChampionContext,
+) -> Result<Result<Vec<(String, CreateEmbed)>, CreateReply>, Error> {
+    let mut champions_list =
+        get_list_champions_for_role(&data.mongo_client, context.role.as_deref()).await?;
+    if context.free_rotation_only {
+        let free_champion_ids =
+            match get_champion_rotation(&Client::new(), "euw1", &data.riot_api_key).await {
+                Ok(ids) => ids,
+                Err(e) => {
+                    return Ok(Err(create_embed_coded_error(
+                        "Error fetching the free rotation",
+                        &e,
+                    )));
+                }
+            };
+        let champion_index = data.champions.read().await;
+        champions_list = filter_free_rotation(champions_list, &free_champion_ids, &champion_index);
+    }
+    if champions_list.is_empty() {
+        return Ok(Err(create_embed_error(
+            "No champion matches these filters. Try another role.",
+        )));
+    }
+    let collection_emoji = data
+        .mongo_client
+        .database("stat-summoner")
+        .collection::<EmojiId>("emojis_id");
+    let champion_data = get_random_champion(champions_list);
+    let embed = create_embed_champions_info(
+        champion_data.clone(),
+        RankBracket::All,
+        &context.theme,
+        &collection_emoji,
+    )
+    .await?;
+    let situational =
+        create_embed_situational_build(embed.clone(), &champion_data, &collection_emoji).await?;
+    Ok(Ok(vec![
+        ("Overview".to_string(), embed),
+        ("Situational".to_string(), situational),
+    ]))
+}
+
+/// ⚙️ **Function**: Builds the reroll button shown under the views of `/randomchampions`.
+///
+/// # Parameters:
+/// - `payload`: The key of the component state of the reroll button.
+/// - `_view`: The index of the view displayed, the button being the same under every view.
+///
+/// # Returns:
+/// - `Result<Vec<serenity::CreateActionRow>, Error>`: A row with the reroll button.
+///
+/// # Notes:
+/// - Registered under `RANDOM_CHAMPION_ROWS` (see `module::register_components`), so the button stays under the
+///   views when they are switched.
+pub fn random_champion_rows(
+    payload: &str,
+    _view: usize,
+) -> Result<Vec<serenity::CreateActionRow>, Error> {
+    Ok(vec![serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(component_id(payload, "reroll"))
+            .label("🎲 Reroll")
+            .style(serenity::ButtonStyle::Primary),
+    ])])
+}
+
+/// ⚙️ **Function**: Replaces the champion of `/randomchampions` with another one picked with the same options.
+///
+/// # Parameters:
+/// - `ctx`: The serenity context.
+/// - `data`: The data of the bot.
+/// - `press`: The reroll button.
+/// - `key`: The key of the component state of the reroll button.
+/// - `_action`: The action of the button, always `reroll`.
+/// - `payload`: The `RandomChampionContext` of the command.
+///
+/// # Returns:
+/// - `BoxFuture<'a, Result<(), Error>>`: An error if the state is invalid or the message cannot be updated.
+///
+/// # Notes:
+/// - Registered under `RANDOM_CHAMPION_KIND` (see `module::register_components`), so the champion can still be
+///   rerolled after a restart.
+/// - Only the member who ran the command can reroll. When no champion can be picked, they get an ephemeral message
+///   and the champion displayed is kept.
+pub fn handle_random_champion_reroll<'a>(
+    ctx: &'a serenity::Context,
+    data: &'a Data,
+    press: &'a serenity::ComponentInteraction,
+    key: &'a str,
+    _action: &'a str,
+    payload: &'a str,
+) -> BoxFuture<'a, Result<(), Error>> {
+    Box::pin(async move {
+        let context: RandomChampionContext = serde_json::from_str(payload)?;
+        // Répondre tout de suite : la rotation gratuite est demandée à Riot
+        press
+            .create_response(ctx, serenity::CreateInteractionResponse::Acknowledge)
+            .await?;
+        let views = match build_random_champion_views(data, &context).await? {
+            Ok(views) => views,
+            Err(reply) => {
+                press
+                    .create_followup(
+                        ctx,
+                        reply.ephemeral(true).to_slash_followup_response(
+                            serenity::CreateInteractionResponseFollowup::new(),
+                        ),
+                    )
+                    .await?;
+                return Ok(());
+            }
+        };
+        let rows = PageRows {
+            builder: RANDOM_CHAMPION_ROWS,
+            payload: key.to_string(),
+        };
+        let components = save_views(
+            &data.mongo_client,
+            &format!("views-{}", press.id),
+            press.user.id.get(),
+            &views,
+            &[],
+            false,
+            false,
+            Some(&rows),
+        )
+        .await?;
+        press
+            .edit_response(
+                ctx,
+                serenity::EditInteractionResponse::new()
+                    .embed(views[0].1.clone())
+                    .components(components),
+            )
+            .await?;
+        Ok(())
+    })
+}
@@ -0,0 +1,116 @@
+use crate::config::deletion_notice;
+use crate::embed::{create_embed_error, schedule_message_deletion};
+use crate::models::data::Data;
+use crate::models::error::Error;
+use crate::models::role::Role;
+use crate::module::randomchampions::utils::{
+    get_list_champions, match_role_with_database_roles, voice_channel_players,
+};
+use crate::module::settings::utils::get_guild_theme;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, Mentionable, User, UserId};
+use poise::CreateReply;
+use rand::seq::SliceRandom;
+
+/// Randomly assigns the five roles to the players of your voice channel or to mentioned players.
+///
+/// # Example:
+/// ```rust
+/// /randomroles roll_champions: True
+/// /randomroles player1: @Alice player2: @Bob player3: @Carol
+/// ```
+///
+/// # Notes:
+/// - Without mentions, the players are the members of the voice channel you are in (bots excluded).
+/// - With more than 5 players, 5 of them are drawn and the others sit out.
+/// - With `roll_champions`, each player also gets a random champion of their role, picked like `/randomchampions`
+///   does, without duplicates.
+#[poise::command(slash_command, guild_only)]
+pub async fn randomroles(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Also roll a champion for each player"] roll_champions: Option<bool>,
+    #[description = "First player (instead of your voice channel)"] player1: Option<User>,
+    #[description = "Second player"] player2: Option<User>,
+    #[description = "Third player"] player3: Option<User>,
+    #[description = "Fourth player"] player4: Option<User>,
+    #[description = "Fifth player"] player5: Option<User>,
+) -> Result<(), Error> {
+    let mut players: Vec<UserId> = Vec::new();
+    for player in [player1, player2, player3, player4, player5]
+        .into_iter()
+        .flatten()
+    {
+        if !players.contains(&player.id) {
+            players.push(player.id);
+        }
+    }
+    if players.is_empty() {
+        players = voice_channel_players(poise::Context::Application(ctx));
+    }
+    if players.is_empty() {
+        let error_message =
+            "Join a voice channel with your team, or mention the players to assign roles to.";
+        let reply = ctx.send(create_embed_error(error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+
+    players.shuffle(&mut rand::thread_rng());
+    let mut roles = vec![
+        Role::TOPLANE,
+        Role::JUNGLE,
+        Role::MIDLANE,
+        Role::ADC,
+        Role::SUPPORT,
+    ];
+    roles.shuffle(&mut rand::thread_rng());
+    let sitting_out = players.split_off(players.len().min(roles.len()));
+
+    let mut picked_champions: Vec<String> = Vec::new();
+    let mut lines = Vec::new();
+    for (player, role) in players.iter().zip(roles) {
+        let role_name = match_role_with_database_roles(role.clone());
+        let mut line = format!("**{}**: {}", role_name, player.mention());
+        if roll_champions.unwrap_or(false) {
+            let champion = get_list_champions(ctx, Some(role))
+                .await?
+                .into_iter()
+                .filter(|champion| !picked_champions.contains(&champion.name))
+                .collect::<Vec<_>>()
+                .choose(&mut rand::thread_rng())
+                .map(|champion| champion.name.clone());
+            if let Some(champion) = champion {
+                line.push_str(&format!(" ({})", champion));
+                picked_champions.push(champion);
+            }
+        }
+        lines.push(line);
+    }
+    if !sitting_out.is_empty() {
+        lines.push(format!(
+            "\n🪑 Sitting out: {}",
+            sitting_out
+                .iter()
+                .map(|player| player.mention().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    let theme = get_guild_theme(ctx).await;
+    let embed = CreateEmbed::default()
+        .title("🎲 Random roles")
+        .description(lines.join("\n"))
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+    let reply = ctx
+        .send(CreateReply {
+            embeds: vec![embed],
+            ..Default::default()
+        })
+        .await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
@@ -1,12 +1,15 @@
 use futures::TryStreamExt;
 use mongodb::bson::doc;
+use poise::serenity_prelude::UserId;
 use rand::Rng;
 
 use crate::models::{
+    champion_index::ChampionIndex,
     data::{ChampionData, Data},
     error::Error,
     role::Role,
 };
+use crate::module::championsinfos::utils::get_latest_champion_patch;
 
 /// ⚙️ Maps a `Role` enum value to its corresponding string representation as stored in the database.
 ///
@@ -35,7 +38,7 @@ use crate::models::{
 ///
 /// # See Also:
 /// - `get_champions_by_role`: Uses the string representation of a role to query the database for champions with that role.
-fn match_role_with_database_roles(role: Role) -> String {
+pub fn match_role_with_database_roles(role: Role) -> String {
     match role {
         Role::TOPLANE => "Top".to_string(),
         Role::JUNGLE => "Jungler".to_string(),
@@ -52,6 +55,7 @@ fn match_role_with_database_roles(role: Role) -> String {
 ///
 /// # Parameters:
 /// - `role`: A reference to a string representing the role to filter champions by (e.g., "Top", "Jungler").
+/// - `patch`: The patch to restrict the search to. `None` searches every stored document.
 /// - `collection`: A reference to a MongoDB collection of `ChampionData` representing the champion data stored in the database.
 ///
 /// # Returns:
@@ -61,7 +65,7 @@ fn match_role_with_database_roles(role: Role) -> String {
 /// # Example:
 /// ```rust
 /// let role = "AD Carry";
-/// let champions = get_champions_by_role(role, &collection).await?;
+/// let champions = get_champions_by_role(role, Some("14.20"), &collection).await?;
 /// for champion in champions {
 ///     println!("Champion: {}", champion.name);
 /// }
@@ -79,13 +83,17 @@ fn match_role_with_database_roles(role: Role) -> String {
 /// - Requires the `futures` crate for the `try_collect` method to handle the cursor results asynchronously.
 async fn get_champions_by_role(
     role: &str,
+    patch: Option<&str>,
     collection: &mongodb::Collection<ChampionData>,
 ) -> mongodb::error::Result<Vec<ChampionData>> {
-    let filter = doc! {
+    let mut filter = doc! {
         "role": {
             "$in": [role]
         }
     };
+    if let Some(patch) = patch {
+        filter.insert("patch", patch);
+    }
     let cursor = collection.find(filter).await?;
 
     cursor.try_collect().await
@@ -97,6 +105,7 @@ async fn get_champions_by_role(
 /// It is used to obtain a complete list of champions stored in the collection.
 ///
 /// # Parameters:
+/// - `patch`: The patch to restrict the search to. `None` searches every stored document.
 /// - `collection`: A reference to a MongoDB collection of `ChampionData` representing the champion data stored in the database.
 ///
 /// # Returns:
@@ -105,14 +114,14 @@ async fn get_champions_by_role(
 ///
 /// # Example:
 /// ```rust
-/// let champions = get_champions_with_no_role(&collection).await?;
+/// let champions = get_champions_with_no_role(Some("14.20"), &collection).await?;
 /// for champion in champions {
 ///     println!("Champion: {}", champion.name);
 /// }
 /// ```
 ///
 /// # ⚠️ Notes:
-/// - The function uses an empty filter (`{}`) to retrieve all documents in the `ChampionData` collection.
+/// - Without a patch, the function uses an empty filter (`{}`) to retrieve all documents in the `ChampionData` collection.
 /// - The result is collected into a vector using the `try_collect` method, which allows for asynchronous processing of the cursor results.
 ///
 /// # Related Functions:
@@ -122,9 +131,13 @@ async fn get_champions_by_role(
 /// - This function depends on a MongoDB collection that stores `ChampionData` documents.
 /// - Requires the `futures` crate for the `try_collect` method to handle the cursor results asynchronously.
 async fn get_champions_with_no_role(
+    patch: Option<&str>,
     collection: &mongodb::Collection<ChampionData>,
 ) -> mongodb::error::Result<Vec<ChampionData>> {
-    let filter = doc! {};
+    let mut filter = doc! {};
+    if let Some(patch) = patch {
+        filter.insert("patch", patch);
+    }
     let cursor = collection.find(filter).await?;
 
     cursor.try_collect().await
@@ -188,6 +201,7 @@ pub fn get_random_champion(champions: Vec<ChampionData>) -> ChampionData {
 /// # ⚠️ Notes:
 /// - If `role` is `None`, the function calls `get_champions_with_no_role` to retrieve all champions.
 /// - If `role` is provided, `match_role_with_database_roles` is used to convert the `Role` enum to a string, and `get_champions_by_role` is called to perform the filtered search.
+/// - Only the latest stored patch is searched, so each champion is returned once even though previous patches are kept.
 /// - This function interacts with MongoDB asynchronously and relies on other helper functions for specific queries.
 ///
 /// # Related Functions:
@@ -202,16 +216,101 @@ pub async fn get_list_champions(
     ctx: poise::ApplicationContext<'_, Data, Error>,
     role: Option<Role>,
 ) -> Result<Vec<ChampionData>, Error> {
-    let mongo_client = &ctx.data().mongo_client;
+    let role = role.map(match_role_with_database_roles);
+    get_list_champions_for_role(&ctx.data().mongo_client, role.as_deref()).await
+}
+
+/// ⚙️ Retrieves the champions of the latest stored patch, like `get_list_champions`, outside of a command.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `role`: The role of the champions as stored in the database (see `match_role_with_database_roles`). If `None`,
+///   all champions are retrieved.
+///
+/// # Returns:
+/// - `Result<Vec<ChampionData>, Error>`: The champions matching the role.
+///
+/// # Example:
+/// ```rust
+/// let champions_list = get_list_champions_for_role(&data.mongo_client, Some("Mid")).await?;
+/// ```
+pub async fn get_list_champions_for_role(
+    mongo_client: &mongodb::Client,
+    role: Option<&str>,
+) -> Result<Vec<ChampionData>, Error> {
     let collection = mongo_client
         .database("stat-summoner")
         .collection::<ChampionData>("champions_data");
-    if role.is_none() {
-        let champions = get_champions_with_no_role(&collection).await?;
-        return Ok(champions);
-    } else {
-        let role = match_role_with_database_roles(role.unwrap());
-        let champions = get_champions_by_role(&role, &collection).await?;
-        return Ok(champions);
+    let patch = get_latest_champion_patch(&collection).await?;
+    match role {
+        Some(role) => Ok(get_champions_by_role(role, patch.as_deref(), &collection).await?),
+        None => Ok(get_champions_with_no_role(patch.as_deref(), &collection).await?),
     }
 }
+
+/// ⚙️ Keeps only the champions of the free rotation.
+///
+/// # Parameters:
+/// - `champions`: The champions to filter.
+/// - `free_champion_ids`: The champion IDs of the free rotation, as returned by `get_champion_rotation`.
+/// - `champion_index`: The champion lookups, used to map the numeric IDs to the champions' `id_name`.
+///
+/// # Returns:
+/// - `Vec<ChampionData>`: The champions which are free to play this week.
+///
+/// # Example:
+/// ```rust
+/// let free_champions = filter_free_rotation(champions_list, &free_champion_ids, &champion_index);
+/// ```
+pub fn filter_free_rotation(
+    champions: Vec<ChampionData>,
+    free_champion_ids: &[i64],
+    champion_index: &ChampionIndex,
+) -> Vec<ChampionData> {
+    let free_id_names: Vec<&str> = free_champion_ids
+        .iter()
+        .filter_map(|key| champion_index.id_from_key(*key))
+        .collect();
+    champions
+        .into_iter()
+        .filter(|champion| free_id_names.contains(&champion.id_name.as_str()))
+        .collect()
+}
+
+/// ⚙️ Lists the players in the voice channel of the command's author.
+///
+/// # Parameters:
+/// - `ctx`: The command context. The voice states are read from the guild cache.
+///
+/// # Returns:
+/// - `Vec<UserId>`: The members in the same voice channel as the author (the author included, bots excluded), or an
+///   empty list when the author is not in a voice channel.
+///
+/// # Example:
+/// ```rust
+/// let players = voice_channel_players(poise::Context::Application(ctx));
+/// ```
+pub fn voice_channel_players(ctx: poise::Context<'_, Data, Error>) -> Vec<UserId> {
+    let Some(guild) = ctx.guild() else {
+        return Vec::new();
+    };
+    let Some(channel_id) = guild
+        .voice_states
+        .get(&ctx.author().id)
+        .and_then(|voice_state| voice_state.channel_id)
+    else {
+        return Vec::new();
+    };
+    guild
+        .voice_states
+        .values()
+        .filter(|voice_state| voice_state.channel_id == Some(channel_id))
+        .filter(|voice_state| {
+            !voice_state
+                .member
+                .as_ref()
+                .is_some_and(|member| member.user.bot)
+        })
+        .map(|voice_state| voice_state.user_id)
+        .collect()
+}
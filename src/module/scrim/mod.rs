@@ -0,0 +1,17 @@
+/// 🛠 **Module scrim**: Contains the scrim results logged by the captains of the teams of a guild.
+///
+/// Captains record the result of their scrims against other teams, optionally with the game played (by match ID or by
+/// tournament code), and the history of a team shows its last scrims and its win rate month by month.
+///
+/// # Files in this module:
+/// - `scrim.rs`: The `/scrim` command and its `log` and `history` subcommands.
+/// - `utils.rs`: The `scrims` collection, the result of a logged game and the history embed.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`:
+///
+/// ```rust
+/// scrim::scrim::scrim(),
+/// ```
+pub mod scrim;
+pub mod utils;
@@ -0,0 +1,237 @@
+use crate::config::config;
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, create_embed_sucess, edit_and_schedule_deletion,
+    send_fetching_message,
+};
+use crate::models::data::{Data, ScrimData, ScrimResult};
+use crate::models::error::Error;
+use crate::module::linkaccount::utils::get_linked_account;
+use crate::module::myaccounts::utils::linked_accounts_collection;
+use crate::module::scrim::utils::{
+    create_embed_scrim_history, find_tournament_game, get_scrims, scrims_collection,
+    team_result_in_match,
+};
+use crate::module::settings::utils::get_guild_theme;
+use crate::module::team::utils::{autocomplete_team, can_manage_team, find_team};
+use crate::riot_api::get_matchs_info;
+use chrono::Utc;
+use poise::CreateReply;
+
+/// Logs the scrims of the teams of this server and shows their history.
+///
+/// # Example:
+/// ```rust
+/// /scrim log team: Team Alpha opponent: Team Beta result: Win notes: Strong early game
+/// /scrim log team: Team Alpha opponent: Team Beta match_id: EUW1_1234567890
+/// /scrim history team: Team Alpha
+/// ```
+#[poise::command(slash_command, guild_only, subcommands("log", "history"))]
+pub async fn scrim(_ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Records the result of a scrim of a team (team captains only).
+///
+/// # Notes:
+/// - With `match_id` or `tournament_code`, the result is read from the game when a member of the team played it.
+/// - Tournament codes need a Riot API key with access to the tournament API (`TOURNAMENT_API`): without it, the
+///   lookup is refused before calling Riot.
+#[poise::command(slash_command, guild_only)]
+pub async fn log(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Team that played the scrim"]
+    #[autocomplete = "autocomplete_team"]
+    team: String,
+    #[description = "Name of the opposing team"]
+    #[max_length = 50]
+    opponent: String,
+    #[description = "Result of the scrim (read from the game when possible)"] result: Option<
+        ScrimResult,
+    >,
+    #[description = "Notes about the scrim"]
+    #[max_length = 200]
+    notes: Option<String>,
+    #[description = "ID of the game (e.g., EUW1_1234567890)"] match_id: Option<String>,
+    #[description = "Tournament code of the game"] tournament_code: Option<String>,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    if tournament_code.is_some() && !config().tournament_api {
+        let error_message = "Tournament lookups are unavailable: the Riot API key of the bot has no access to the tournament API. Use `match_id` to attach the game instead.";
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+        return Ok(());
+    }
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let mongo_client = &ctx.data().mongo_client;
+    let riot_api_key = &ctx.data().riot_api_key;
+    let team = match find_team(mongo_client, &guild_id, &team).await {
+        Ok(Some(team)) if can_manage_team(ctx, &team) => team,
+        Ok(Some(_)) => {
+            let error_message =
+                "Only the captain of this team and the server managers can log its scrims.";
+            edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+            return Ok(());
+        }
+        Ok(None) => {
+            let error_message = format!("There is no team named **{}** on this server.", team);
+            edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message)).await?;
+            return Ok(());
+        }
+        Err(e) => {
+            let reply =
+                create_embed_coded_error("Error collecting informations from MongoDB", &e.into());
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+
+    let client = reqwest::Client::new();
+    let tournament_code = tournament_code.map(|code| code.trim().to_string());
+    let game = match (&tournament_code, &match_id) {
+        (Some(tournament_code), _) => {
+            match find_tournament_game(&client, tournament_code, riot_api_key).await {
+                Ok(Some(game)) => Some(game),
+                Ok(None) => {
+                    let error_message = "No game was played with this tournament code yet.";
+                    edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message))
+                        .await?;
+                    return Ok(());
+                }
+                Err(e) => {
+                    let reply = create_embed_coded_error("Error fetching the tournament games", &e);
+                    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+                    return Ok(());
+                }
+            }
+        }
+        (None, Some(match_id)) => {
+            let match_id = match_id.trim().to_uppercase();
+            match get_matchs_info(&client, &match_id, riot_api_key).await {
+                Ok(match_info) if !match_info["info"].is_null() => Some((match_id, match_info)),
+                Ok(_) => {
+                    let error_message = format!("The game `{}` could not be found.", match_id);
+                    edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message))
+                        .await?;
+                    return Ok(());
+                }
+                Err(e) => {
+                    let reply = create_embed_coded_error("Error fetching match data", &e);
+                    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+                    return Ok(());
+                }
+            }
+        }
+        (None, None) => None,
+    };
+
+    // Le résultat de la partie prime sur celui saisi par le capitaine
+    let mut game_result = None;
+    if let Some((_, match_info)) = &game {
+        let collection_linked = linked_accounts_collection(mongo_client);
+        let mut puuids = Vec::new();
+        for member in &team.members {
+            if let Ok(Some(account)) = get_linked_account(&collection_linked, *member).await {
+                puuids.push(account.puuid);
+            }
+        }
+        game_result = team_result_in_match(match_info, &puuids);
+    }
+    let Some(win) = game_result.or(result.map(|result| result == ScrimResult::Win)) else {
+        let error_message =
+            "Give the `result` of the scrim, or a game played by a member of the team.";
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+        return Ok(());
+    };
+
+    let played_at = game
+        .as_ref()
+        .and_then(|(_, match_info)| match_info["info"]["gameCreation"].as_i64())
+        .map_or(Utc::now().timestamp(), |created_at| created_at / 1000);
+    let scrim = ScrimData {
+        guild_id,
+        team: team.name.clone(),
+        opponent: opponent.trim().to_string(),
+        win,
+        notes: notes
+            .map(|notes| notes.trim().to_string())
+            .filter(|notes| !notes.is_empty()),
+        match_id: game.map(|(match_id, _)| match_id),
+        tournament_code,
+        played_at,
+        logged_by: ctx.author().id.get(),
+    };
+    let reply = match scrims_collection(mongo_client).insert_one(&scrim).await {
+        Ok(_) => {
+            let mut sucess_message = format!(
+                "{} **{}** vs **{}** logged.",
+                if win { "✅ Win of" } else { "❌ Loss of" },
+                scrim.team,
+                scrim.opponent
+            );
+            if game_result.is_some_and(|game_result| {
+                result.is_some_and(|result| (result == ScrimResult::Win) != game_result)
+            }) {
+                sucess_message.push_str("\nThe result was read from the game.");
+            }
+            create_embed_sucess(&sucess_message)
+        }
+        Err(e) => create_embed_coded_error("Error saving the scrim", &e.into()),
+    };
+    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+    Ok(())
+}
+
+/// Shows the last scrims and the monthly win rate of a team, or of every team of this server.
+#[poise::command(slash_command, guild_only)]
+pub async fn history(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Team (every team by default)"]
+    #[autocomplete = "autocomplete_team"]
+    team: Option<String>,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let mongo_client = &ctx.data().mongo_client;
+    let team = match team {
+        Some(team) => match find_team(mongo_client, &guild_id, &team).await {
+            Ok(Some(team)) => Some(team),
+            Ok(None) => {
+                let error_message = format!("There is no team named **{}** on this server.", team);
+                edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message))
+                    .await?;
+                return Ok(());
+            }
+            Err(e) => {
+                let reply = create_embed_coded_error(
+                    "Error collecting informations from MongoDB",
+                    &e.into(),
+                );
+                edit_and_schedule_deletion(fetching, ctx, reply).await?;
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+    let team_name = team.as_ref().map(|team| team.name.as_str());
+    let scrims = match get_scrims(mongo_client, &guild_id, team_name).await {
+        Ok(scrims) => scrims,
+        Err(e) => {
+            let reply =
+                create_embed_coded_error("Error collecting informations from MongoDB", &e.into());
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    if scrims.is_empty() {
+        let error_message = "No scrim was logged yet. Log one with `/scrim log`.";
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+        return Ok(());
+    }
+    let title = match team_name {
+        Some(team_name) => format!("⚔️ Scrims of {}", team_name),
+        None => "⚔️ Scrims of the server".to_string(),
+    };
+    let theme = get_guild_theme(ctx).await;
+    let embed = create_embed_scrim_history(&title, &scrims, team_name.is_none(), &theme);
+    edit_and_schedule_deletion(fetching, ctx, CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
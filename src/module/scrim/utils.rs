@@ -0,0 +1,209 @@
+use crate::config::deletion_notice;
+use crate::models::data::{EmbedTheme, ScrimData};
+use crate::models::error::Error;
+use crate::riot_api::{get_matchs_id_by_tournament_code, get_matchs_info};
+use chrono::DateTime;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use serde_json::Value;
+
+/// Number of scrims listed in the history.
+pub const SCRIM_HISTORY_SIZE: usize = 10;
+
+/// Number of months shown in the monthly win rates of the history.
+const SCRIM_SUMMARY_MONTHS: usize = 6;
+
+/// ⚙️ **Function**: Returns the `scrims` collection.
+pub fn scrims_collection(mongo_client: &Client) -> Collection<ScrimData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<ScrimData>("scrims")
+}
+
+/// ⚙️ **Function**: Retrieves the scrims of a guild, most recent first.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `team`: Only the scrims of this team, or `None` for every team of the guild.
+///
+/// # Returns:
+/// - `Result<Vec<ScrimData>, mongodb::error::Error>`: The scrims, empty if none were logged.
+pub async fn get_scrims(
+    mongo_client: &Client,
+    guild_id: &str,
+    team: Option<&str>,
+) -> Result<Vec<ScrimData>, mongodb::error::Error> {
+    let mut filter = doc! { "guild_id": guild_id };
+    if let Some(team) = team {
+        filter.insert("team", team);
+    }
+    scrims_collection(mongo_client)
+        .find(filter)
+        .sort(doc! { "played_at": -1 })
+        .await?
+        .try_collect()
+        .await
+}
+
+/// ⚙️ **Function**: Finds the game played with a tournament code.
+///
+/// # Parameters:
+/// - `client`: The HTTP client used to query the Riot API.
+/// - `tournament_code`: The tournament code of the scrim.
+/// - `riot_api_key`: The Riot API key.
+///
+/// # Returns:
+/// - `Result<Option<(String, Value)>, Error>`: The ID and match-v5 data of the most recent game played with the code,
+///   `None` if no game was played with it yet.
+///
+/// # Notes:
+/// - Requires an API key with access to the tournament API (see `get_matchs_id_by_tournament_code`).
+pub async fn find_tournament_game(
+    client: &reqwest::Client,
+    tournament_code: &str,
+    riot_api_key: &str,
+) -> Result<Option<(String, Value)>, Error> {
+    let mut latest: Option<(String, Value)> = None;
+    for match_id in get_matchs_id_by_tournament_code(client, tournament_code, riot_api_key).await? {
+        let match_info = get_matchs_info(client, &match_id, riot_api_key).await?;
+        let created_at = match_info["info"]["gameCreation"].as_i64().unwrap_or(0);
+        let is_latest = latest.as_ref().is_none_or(|(_, latest)| {
+            created_at > latest["info"]["gameCreation"].as_i64().unwrap_or(0)
+        });
+        if is_latest {
+            latest = Some((match_id, match_info));
+        }
+    }
+    Ok(latest)
+}
+
+/// ⚙️ **Function**: Finds the result of a team in a game, from the players of the team who played it.
+///
+/// # Parameters:
+/// - `match_info`: The match-v5 data of the game.
+/// - `puuids`: The PUUIDs of the linked accounts of the members of the team.
+///
+/// # Returns:
+/// - `Option<bool>`: `true` for a win, `None` if no member of the team played the game.
+pub fn team_result_in_match(match_info: &Value, puuids: &[String]) -> Option<bool> {
+    match_info["info"]["participants"]
+        .as_array()?
+        .iter()
+        .find(|participant| {
+            participant["puuid"]
+                .as_str()
+                .is_some_and(|puuid| puuids.iter().any(|member| member == puuid))
+        })
+        .map(|participant| participant["win"].as_bool().unwrap_or(false))
+}
+
+/// ⚙️ **Function**: Computes the wins and losses of scrims month by month.
+///
+/// # Parameters:
+/// - `scrims`: The scrims, most recent first.
+///
+/// # Returns:
+/// - `Vec<(String, u32, u32)>`: The month (e.g. `October 2026`), wins and losses, most recent month first.
+pub fn monthly_summaries(scrims: &[ScrimData]) -> Vec<(String, u32, u32)> {
+    let mut summaries: Vec<(String, u32, u32)> = Vec::new();
+    for scrim in scrims {
+        let month = DateTime::from_timestamp(scrim.played_at, 0)
+            .map(|date| date.format("%B %Y").to_string())
+            .unwrap_or_default();
+        let index = match summaries.iter().position(|(label, _, _)| *label == month) {
+            Some(index) => index,
+            None => {
+                summaries.push((month, 0, 0));
+                summaries.len() - 1
+            }
+        };
+        if scrim.win {
+            summaries[index].1 += 1;
+        } else {
+            summaries[index].2 += 1;
+        }
+    }
+    summaries
+}
+
+/// ⚙️ **Function**: Creates the embed of the scrim history of a guild or of one of its teams.
+///
+/// # Parameters:
+/// - `title`: The title of the embed.
+/// - `scrims`: The scrims, most recent first.
+/// - `show_team`: Whether each line shows the team that played, when the history covers several teams.
+/// - `theme`: The embed theme of the guild.
+///
+/// # Returns:
+/// - `CreateEmbed`: The embed, with the last scrims and the win rate of the last 6 months.
+///
+/// # Example:
+/// ```text
+/// ⚔️ Scrims of Team Alpha
+/// ✅ 2026-10-14 vs Team Beta · EUW1_1234567890
+///    Won the early game, lost control of drakes
+///
+/// Monthly win rate
+/// October 2026: 5W - 2L (71%)
+/// ```
+pub fn create_embed_scrim_history(
+    title: &str,
+    scrims: &[ScrimData],
+    show_team: bool,
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let history = scrims
+        .iter()
+        .take(SCRIM_HISTORY_SIZE)
+        .map(|scrim| {
+            let date = DateTime::from_timestamp(scrim.played_at, 0)
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            let mut line = format!(
+                "{} {} {}vs **{}**",
+                if scrim.win { "✅" } else { "❌" },
+                date,
+                if show_team {
+                    format!("{} ", scrim.team)
+                } else {
+                    String::new()
+                },
+                scrim.opponent
+            );
+            if let Some(match_id) = &scrim.match_id {
+                line.push_str(&format!(" · `{}`", match_id));
+            }
+            if let Some(notes) = &scrim.notes {
+                line.push_str(&format!("\n> {}", notes));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let summaries = monthly_summaries(scrims)
+        .into_iter()
+        .take(SCRIM_SUMMARY_MONTHS)
+        .map(|(month, wins, losses)| {
+            format!(
+                "{}: {}W - {}L ({:.0}%)",
+                month,
+                wins,
+                losses,
+                wins as f64 / (wins + losses) as f64 * 100.0
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    CreateEmbed::default()
+        .title(title)
+        .description(history)
+        .field("Monthly win rate", summaries, false)
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
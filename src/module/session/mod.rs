@@ -0,0 +1,22 @@
+/// 🛠 **Module session**: Contains the summary of a player's recent gaming session.
+///
+/// This module gathers the games a player started in the last hours (match-v5) and sums them up: wins and losses,
+/// net LP, champions played and average KDA.
+///
+/// # Files in this module:
+/// - `session.rs`: The `/session` command.
+/// - `utils.rs`: The aggregation of the matches into a session summary and its embed.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`:
+///
+/// ```rust
+/// let framework = poise::Framework::builder()
+///     .options(poise::FrameworkOptions {
+///         commands: module::all_commands(),
+///         ..Default::default()
+///     })
+///     .build();
+/// ```
+pub mod session;
+pub mod utils;
@@ -0,0 +1,164 @@
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, edit_and_schedule_deletion, send_fetching_message,
+};
+use crate::models::data::Data;
+use crate::models::error::Error;
+use crate::models::region::Region;
+use crate::models::riot_id::RiotId;
+use crate::module::lolstats::season::{get_rank_snapshot_at, rank_score, record_rank_snapshot};
+use crate::module::lolstats::surrender::get_surrender_summary;
+use crate::module::optout::utils::is_opted_out;
+use crate::module::session::utils::{create_embed_session, summarize_session};
+use crate::module::settings::utils::get_guild_theme;
+use crate::riot_api::{
+    get_matchs_id_since, get_matchs_info, get_puuid, get_rank_info, get_summoner_id,
+};
+use crate::utils::region_to_string;
+use futures::future::join_all;
+use poise::CreateReply;
+use reqwest::Client;
+use serde_json::Value;
+
+/// Maximum number of games of a session, to keep the number of Riot API calls bounded.
+const MAX_SESSION_GAMES: u32 = 20;
+
+/// Summarizes the games a player played in the last hours: record, net LP, champions and KDA.
+///
+/// # Example:
+/// ```rust
+/// /session region: EUW riot_id: Faker#KR1
+/// /session region: EUW riot_id: Faker#KR1 hours: 6
+/// ```
+///
+/// # Notes:
+/// - The net LP comes from the ranks recorded by the bot when one was recorded before the session, otherwise it is
+///   estimated with 20 LP per Solo/Duo game.
+/// - At most the last 20 games are counted.
+/// - The surrender rates come from the games archived by the bot, not only from the session.
+#[poise::command(slash_command)]
+pub async fn session(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Select the region"] region: Region,
+    #[description = "Riot ID of the player (e.g., Faker#KR1)"] riot_id: RiotId,
+    #[description = "Length of the session in hours (12 by default)"]
+    #[min = 1]
+    #[max = 24]
+    hours: Option<u32>,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let hours = hours.unwrap_or(12);
+    let client = Client::new();
+    let riot_api_key = &ctx.data().riot_api_key;
+    let mongo_client = &ctx.data().mongo_client;
+    let region_str = region_to_string(&region);
+
+    let puuid = match get_puuid(&client, &riot_id, riot_api_key).await {
+        Ok(puuid) => puuid,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error fetching PUUID", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    if is_opted_out(mongo_client, &puuid).await? {
+        let error_message =
+            "This player has opted out of Stat Summoner: their statistics cannot be looked up.";
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+        return Ok(());
+    }
+
+    let session_start = chrono::Utc::now().timestamp() - hours as i64 * 3600;
+    let match_ids = match get_matchs_id_since(
+        &client,
+        &puuid,
+        riot_api_key,
+        session_start,
+        MAX_SESSION_GAMES,
+    )
+    .await
+    {
+        Ok(match_ids) => match_ids,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error fetching match IDs", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    let riot_id = riot_id.to_string();
+    if match_ids.is_empty() {
+        let error_message = format!("{} has not played in the last {}h.", riot_id, hours);
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message)).await?;
+        return Ok(());
+    }
+    let matches: Vec<Value> = join_all(
+        match_ids
+            .iter()
+            .map(|match_id| get_matchs_info(&client, match_id, riot_api_key)),
+    )
+    .await
+    .into_iter()
+    .filter_map(|match_data| match match_data {
+        Ok(match_data) => Some(match_data),
+        Err(e) => {
+            log::error!("Error fetching a match of the session: {:?}", e);
+            None
+        }
+    })
+    .collect();
+    let summary = summarize_session(&puuid, &matches);
+    if summary.games() == 0 {
+        let error_message = format!("{} has not played in the last {}h.", riot_id, hours);
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message)).await?;
+        return Ok(());
+    }
+
+    // Rank diffing: compare the current rank with the one recorded before the session
+    let mut tracked_lp = None;
+    if summary.ranked_wins + summary.ranked_losses > 0 {
+        let solo_rank = match get_summoner_id(&client, &region_str, &puuid, riot_api_key).await {
+            Ok(summoner_id) => get_rank_info(&client, &region_str, &summoner_id, riot_api_key)
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .find(|entry| {
+                    entry.get("queueType").and_then(Value::as_str) == Some("RANKED_SOLO_5x5")
+                }),
+            Err(_) => None,
+        };
+        if let Some(solo_rank) = solo_rank {
+            let reference =
+                get_rank_snapshot_at(mongo_client, &puuid, "RANKED_SOLO_5x5", session_start)
+                    .await?
+                    .filter(|snapshot| snapshot.recorded_at <= session_start);
+            record_rank_snapshot(mongo_client, &puuid, &solo_rank).await?;
+            let field = |name: &str| solo_rank.get(name).and_then(Value::as_str).unwrap_or("");
+            let current_score = rank_score(
+                field("tier"),
+                field("rank"),
+                solo_rank
+                    .get("leaguePoints")
+                    .and_then(Value::as_i64)
+                    .unwrap_or(0),
+            );
+            tracked_lp = reference.and_then(|reference| {
+                let reference_score = rank_score(
+                    &reference.tier,
+                    &reference.division,
+                    reference.league_points,
+                )?;
+                Some(current_score? - reference_score)
+            });
+        }
+    }
+
+    let surrenders = get_surrender_summary(mongo_client, &puuid).await;
+    let theme = get_guild_theme(ctx).await;
+    let reply = CreateReply {
+        embeds: vec![create_embed_session(
+            &riot_id, hours, &summary, tracked_lp, surrenders, &theme,
+        )],
+        ..Default::default()
+    };
+    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+    Ok(())
+}
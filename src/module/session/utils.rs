@@ -0,0 +1,201 @@
+use crate::config::deletion_notice;
+use crate::models::data::EmbedTheme;
+use crate::module::lolstats::utils::LP_PER_WIN_ESTIMATE;
+use crate::utils::find_leavers;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use serde_json::Value;
+
+/// Queue ID of Ranked Solo/Duo games.
+const RANKED_SOLO_QUEUE_ID: i64 = 420;
+
+/// Games played by a player during a session.
+#[derive(Debug, Default)]
+pub struct SessionSummary {
+    pub wins: u32,
+    pub losses: u32,
+    pub ranked_wins: i64,
+    pub ranked_losses: i64,
+    pub kills: i64,
+    pub deaths: i64,
+    pub assists: i64,
+    /// Losses where a teammate (or the player) went AFK or left the game.
+    pub afk_losses: u32,
+    /// Champions played with their number of games, most played first.
+    pub champions: Vec<(String, u32)>,
+}
+
+impl SessionSummary {
+    /// Number of games of the session.
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses
+    }
+
+    /// LP won or lost in Solo/Duo, estimated from the ranked results.
+    pub fn estimated_lp(&self) -> i64 {
+        (self.ranked_wins - self.ranked_losses) * LP_PER_WIN_ESTIMATE
+    }
+}
+
+/// ⚙️ **Function**: Sums up the matches of a session for a player.
+///
+/// # Parameters:
+/// - `puuid`: The PUUID of the player.
+/// - `matches`: The match-v5 data of the matches of the session.
+///
+/// # Returns:
+/// - `SessionSummary`: The results, KDA and champions of the player over these matches.
+///
+/// # Example:
+/// ```rust
+/// let summary = summarize_session(&puuid, &matches);
+/// println!("{}W - {}L", summary.wins, summary.losses);
+/// ```
+///
+/// # Notes:
+/// - Remakes (games shorter than 5 minutes) are not counted.
+/// - Losses with an AFK teammate (see `find_leavers`) are counted in `afk_losses` so they can be left out of the winrate.
+pub fn summarize_session(puuid: &str, matches: &[Value]) -> SessionSummary {
+    let mut summary = SessionSummary::default();
+    for match_data in matches {
+        let info = &match_data["info"];
+        if info["gameDuration"].as_i64().unwrap_or(0) < 300 {
+            continue;
+        }
+        let Some(participant) = info["participants"].as_array().and_then(|participants| {
+            participants
+                .iter()
+                .find(|participant| participant["puuid"].as_str() == Some(puuid))
+        }) else {
+            continue;
+        };
+        let win = participant["win"].as_bool().unwrap_or(false);
+        let ranked = info["queueId"].as_i64() == Some(RANKED_SOLO_QUEUE_ID);
+        match (win, ranked) {
+            (true, true) => summary.ranked_wins += 1,
+            (false, true) => summary.ranked_losses += 1,
+            _ => {}
+        }
+        if win {
+            summary.wins += 1;
+        } else {
+            summary.losses += 1;
+            let team_id = participant["teamId"].as_i64();
+            if find_leavers(match_data)
+                .iter()
+                .any(|leaver| leaver["teamId"].as_i64() == team_id)
+            {
+                summary.afk_losses += 1;
+            }
+        }
+        summary.kills += participant["kills"].as_i64().unwrap_or(0);
+        summary.deaths += participant["deaths"].as_i64().unwrap_or(0);
+        summary.assists += participant["assists"].as_i64().unwrap_or(0);
+        let champion = participant["championName"]
+            .as_str()
+            .unwrap_or("Unknown")
+            .to_string();
+        match summary
+            .champions
+            .iter_mut()
+            .find(|(name, _)| *name == champion)
+        {
+            Some((_, games)) => *games += 1,
+            None => summary.champions.push((champion, 1)),
+        }
+    }
+    summary.champions.sort_by(|(_, a), (_, b)| b.cmp(a));
+    summary
+}
+
+/// ⚙️ **Function**: Creates the embed of a session summary.
+///
+/// # Parameters:
+/// - `riot_id`: The Riot ID of the player (e.g. `Faker#KR1`).
+/// - `hours`: The length of the session, in hours.
+/// - `summary`: The session summary built by `summarize_session`.
+/// - `tracked_lp`: The LP change computed from the recorded ranks, when a rank was recorded before the session.
+/// - `surrenders`: The surrender rates of the player over their archived games (see `get_surrender_summary`), if any.
+/// - `theme`: The embed theme of the guild.
+///
+/// # Returns:
+/// - `CreateEmbed`: The embed, with the record, net LP, average KDA and champions played.
+///
+/// # Example:
+/// ```text
+/// 📅 Session of Faker#KR1 (last 12h)
+/// Record: 5W - 2L (71%)
+/// 83% without the 1 AFK loss
+/// Net LP: +35 LP
+/// Average KDA: 7.1 / 3.2 / 9.0 (5.03)
+/// Champions: Ahri ×3, Jinx ×2, Sion ×2
+/// Surrenders: Team: 15% (FF@15 5%) · Enemy: 10% (FF@15 0%)
+/// ```
+pub fn create_embed_session(
+    riot_id: &str,
+    hours: u32,
+    summary: &SessionSummary,
+    tracked_lp: Option<i64>,
+    surrenders: Option<String>,
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let games = summary.games() as f64;
+    let net_lp = match tracked_lp {
+        Some(lp) => format!("{:+} LP", lp),
+        None if summary.ranked_wins + summary.ranked_losses > 0 => {
+            format!("~{:+} LP (estimated)", summary.estimated_lp())
+        }
+        None => "No Solo/Duo game".to_string(),
+    };
+    let mut record = format!(
+        "{}W - {}L ({:.0}%)",
+        summary.wins,
+        summary.losses,
+        summary.wins as f64 / games * 100.0
+    );
+    if summary.afk_losses > 0 && summary.afk_losses < summary.games() {
+        record.push_str(&format!(
+            "\n{:.0}% without the {} AFK loss{}",
+            summary.wins as f64 / (summary.games() - summary.afk_losses) as f64 * 100.0,
+            summary.afk_losses,
+            if summary.afk_losses > 1 { "es" } else { "" }
+        ));
+    }
+    let kda_ratio = (summary.kills + summary.assists) as f64 / summary.deaths.max(1) as f64;
+    let champions = summary
+        .champions
+        .iter()
+        .map(|(name, games)| {
+            if *games > 1 {
+                format!("{} ×{}", name, games)
+            } else {
+                name.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let embed = CreateEmbed::default()
+        .title(format!("📅 Session of {} (last {}h)", riot_id, hours))
+        .color(theme.info())
+        .field("Record", record, true)
+        .field("Net LP", net_lp, true)
+        .field(
+            "Average KDA",
+            format!(
+                "{:.1} / {:.1} / {:.1} ({:.2})",
+                summary.kills as f64 / games,
+                summary.deaths as f64 / games,
+                summary.assists as f64 / games,
+                kda_ratio
+            ),
+            false,
+        )
+        .field("Champions", champions, false)
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+    match surrenders {
+        Some(surrenders) => embed.field("Surrenders", surrenders, false),
+        None => embed,
+    }
+}
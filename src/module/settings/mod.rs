@@ -0,0 +1,25 @@
+/// 🛠 **Module settings**: Contains the per-guild settings of the bot.
+///
+/// This module lets the administrators of a server adjust how Stat Summoner behaves in it, such as the layout
+/// of the match notifications sent for followed summoners (and whether they include the gold graph), the colors and
+/// brand line of its embeds or the channel of the challenge of the week.
+///
+/// # Files in this module:
+/// - `settings.rs`: The `/settings` command and its `layout`, `theme`, `challenge` and `goldgraph` subcommands.
+/// - `utils.rs`: The `guild_settings` collection helpers used by the command, the follow loop and the embed builders.
+///
+/// # Example:
+/// The command must be registered in the bot's main framework setup:
+///
+/// ```rust
+/// use module::settings::settings::settings;
+///
+/// let framework = poise::Framework::builder()
+///     .options(poise::FrameworkOptions {
+///         commands: vec![settings()],
+///         ..Default::default()
+///     })
+///     .build();
+/// ```
+pub mod settings;
+pub mod utils;
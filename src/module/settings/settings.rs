@@ -0,0 +1,552 @@
+use crate::config::deletion_notice;
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, create_embed_sucess, schedule_message_deletion,
+};
+use crate::models::data::{
+    Data, EmbedLayout, EmbedTheme, LiveStatusChannel, NotificationBatch, QuietHours,
+};
+use crate::models::error::Error;
+use crate::models::region::Region;
+use crate::models::riot_id::RiotId;
+use crate::module::championsinfos::utils::autocomplete_champion;
+use crate::module::settings::utils::{
+    get_guild_settings, parse_hex_color, set_challenge_channel, set_champion_alias,
+    set_clash_region, set_embed_layout, set_embed_theme, set_gold_graph, set_live_status_channel,
+    set_notification_batch, set_quiet_hours, set_show_bans,
+};
+use crate::utils::{normalize_champion_alias, region_to_string};
+use poise::serenity_prelude::{
+    ChannelId, ChannelType, CreateEmbed, CreateEmbedFooter, EditChannel, GuildChannel, Mentionable,
+};
+use poise::ChoiceParameter;
+use poise::CreateReply;
+
+/// Maximum number of champion nicknames a server can add.
+const MAX_CHAMPION_ALIASES: usize = 100;
+
+/// Configures Stat Summoner for this server (administrators only).
+///
+/// # Example:
+/// ```rust
+/// /settings layout mode: Compact
+/// /settings layout mode: Followed player only riot_id: Faker#KR1
+/// /settings theme victory: #1E90FF footer: Powered by Team Alpha
+/// /settings challenge channel: #league
+/// /settings goldgraph enabled: True
+/// /settings bans enabled: True
+/// /settings quiethours start: 1 end: 8
+/// /settings batch interval: Every 30 minutes
+/// /settings livestatus channel: #🔊 League
+/// /settings clash region: EUW
+/// /settings alias nickname: jgking champion: Jarvan IV
+/// ```
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands(
+        "layout",
+        "theme",
+        "challenge",
+        "goldgraph",
+        "bans",
+        "quiethours",
+        "batch",
+        "livestatus",
+        "clash",
+        "alias"
+    ),
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn settings(_ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Chooses how match notifications of followed summoners are displayed.
+///
+/// - `Detailed`: the team summary and every lane with full stats (default).
+/// - `Compact`: the team summary and a single line per role.
+/// - `Followed player only`: only the stats of the followed summoners.
+///
+/// Without `riot_id` the layout applies to the whole server; with it, only to that follow, overriding the server layout.
+#[poise::command(slash_command, guild_only)]
+pub async fn layout(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Layout of the match notifications"] mode: EmbedLayout,
+    #[description = "Only for this follow (e.g., Faker#KR1)"] riot_id: Option<RiotId>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    match set_embed_layout(&ctx.data().mongo_client, &guild_id, mode, riot_id.as_ref()).await {
+        Ok(true) => {
+            let target = match &riot_id {
+                Some(riot_id) => riot_id.to_string(),
+                None => "this server".to_string(),
+            };
+            let sucess_message = format!(
+                "Match notifications for {} now use the **{}** layout.",
+                target,
+                mode.name()
+            );
+            let reply = ctx.send(create_embed_sucess(&sucess_message)).await?;
+            schedule_message_deletion(reply, ctx).await?;
+        }
+        Ok(false) => {
+            let error_message = "This summoner is not followed on this server.";
+            let reply = ctx.send(create_embed_error(&error_message)).await?;
+            schedule_message_deletion(reply, ctx).await?;
+        }
+        Err(e) => {
+            let reply = ctx
+                .send(create_embed_coded_error("Error saving the layout", &e))
+                .await?;
+            schedule_message_deletion(reply, ctx).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Customizes the colors and branding of the embeds sent on this server.
+///
+/// Colors are hexadecimal (e.g., `#1E90FF`). Options left empty keep their current value; `reset: True` restores
+/// the default green/red palette and removes the brand line before applying the other options.
+#[poise::command(slash_command, guild_only)]
+pub async fn theme(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Color of victories (e.g., #00FF00)"] victory: Option<String>,
+    #[description = "Color of defeats (e.g., #FF0000)"] defeat: Option<String>,
+    #[description = "Color of statistics embeds (e.g., #00FF00)"] info: Option<String>,
+    #[description = "Brand line displayed in the footer"]
+    #[max_length = 100]
+    footer: Option<String>,
+    #[description = "Restore the default theme first"] reset: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let mongo_client = &ctx.data().mongo_client;
+    let mut theme = if reset.unwrap_or(false) {
+        EmbedTheme::default()
+    } else {
+        get_guild_settings(mongo_client, &guild_id).await?.theme
+    };
+
+    for (label, input, target) in [
+        ("victory", victory, &mut theme.victory_color),
+        ("defeat", defeat, &mut theme.defeat_color),
+        ("info", info, &mut theme.info_color),
+    ] {
+        let Some(input) = input else {
+            continue;
+        };
+        match parse_hex_color(&input) {
+            Some(color) => *target = Some(color),
+            None => {
+                let error_message = format!(
+                    "`{}` is not a valid {} color. Use a hexadecimal color such as `#1E90FF`.",
+                    input, label
+                );
+                let reply = ctx.send(create_embed_error(&error_message)).await?;
+                schedule_message_deletion(reply, ctx).await?;
+                return Ok(());
+            }
+        }
+    }
+    if let Some(footer) = footer {
+        let footer = footer.trim().to_string();
+        theme.footer = if footer.is_empty() {
+            None
+        } else {
+            Some(footer)
+        };
+    }
+
+    if let Err(e) = set_embed_theme(mongo_client, &guild_id, &theme).await {
+        let reply = ctx
+            .send(create_embed_coded_error("Error saving the theme", &e))
+            .await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+
+    let preview = |title: &str, color: u32| {
+        CreateEmbed::default()
+            .title(title)
+            .description(format!("`#{:06X}`", color))
+            .color(color)
+            .footer(CreateEmbedFooter::new(
+                theme.footer_text(&deletion_notice()),
+            ))
+    };
+    let reply = CreateReply::default()
+        .embed(preview("🏆 Victory", theme.victory()))
+        .embed(preview("❌ Defeat", theme.defeat()))
+        .embed(preview("📊 Statistics", theme.info()));
+    let reply = ctx.send(reply).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Chooses the channel where a challenge of the week is posted every Monday.
+///
+/// Without `channel`, the challenges of the week stop being posted.
+#[poise::command(slash_command, guild_only)]
+pub async fn challenge(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Channel of the challenge of the week"] channel: Option<GuildChannel>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let channel_id = channel.as_ref().map(|channel| channel.id.get());
+    if let Err(e) = set_challenge_channel(&ctx.data().mongo_client, &guild_id, channel_id).await {
+        let reply = ctx
+            .send(create_embed_coded_error(
+                "Error saving the challenge channel",
+                &e,
+            ))
+            .await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+    let sucess_message = match channel {
+        Some(channel) => format!(
+            "The challenge of the week will be posted in {}.",
+            channel.mention()
+        ),
+        None => "The challenge of the week will no longer be posted.".to_string(),
+    };
+    let reply = ctx.send(create_embed_sucess(&sucess_message)).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Adds the gold difference graph to the match notifications of games longer than 20 minutes.
+#[poise::command(slash_command, guild_only)]
+pub async fn goldgraph(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Attach the gold graph to match notifications"] enabled: bool,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    if let Err(e) = set_gold_graph(&ctx.data().mongo_client, &guild_id, enabled).await {
+        let reply = ctx
+            .send(create_embed_coded_error(
+                "Error saving the gold graph setting",
+                &e,
+            ))
+            .await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+    let sucess_message = if enabled {
+        "Match notifications of games longer than 20 minutes will include the gold graph."
+    } else {
+        "Match notifications will no longer include the gold graph."
+    };
+    let reply = ctx.send(create_embed_sucess(sucess_message)).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Adds the bans of both teams to the match notifications.
+#[poise::command(slash_command, guild_only)]
+pub async fn bans(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Show the bans in match notifications"] enabled: bool,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    if let Err(e) = set_show_bans(&ctx.data().mongo_client, &guild_id, enabled).await {
+        let reply = ctx
+            .send(create_embed_coded_error(
+                "Error saving the bans setting",
+                &e,
+            ))
+            .await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+    let sucess_message = if enabled {
+        "Match notifications will include the bans of both teams."
+    } else {
+        "Match notifications will no longer include the bans."
+    };
+    let reply = ctx.send(create_embed_sucess(sucess_message)).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Holds match notifications between two hours (UTC) and posts them once quiet hours end.
+///
+/// Leave both hours empty to disable quiet hours. The challenge of the week also waits for the end of quiet hours.
+#[poise::command(slash_command, guild_only)]
+pub async fn quiethours(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Start of quiet hours, in UTC (e.g., 1)"]
+    #[min = 0]
+    #[max = 23]
+    start: Option<u32>,
+    #[description = "End of quiet hours, in UTC (e.g., 8)"]
+    #[min = 0]
+    #[max = 23]
+    end: Option<u32>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let quiet_hours = match (start, end) {
+        (None, None) => None,
+        (Some(start_hour), Some(end_hour)) if start_hour != end_hour => Some(QuietHours {
+            start_hour,
+            end_hour,
+        }),
+        _ => {
+            let error_message =
+                "Set both `start` and `end` to two different hours, or leave both empty to disable quiet hours.";
+            let reply = ctx.send(create_embed_error(error_message)).await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(());
+        }
+    };
+    if let Err(e) = set_quiet_hours(&ctx.data().mongo_client, &guild_id, quiet_hours).await {
+        let reply = ctx
+            .send(create_embed_coded_error("Error saving the quiet hours", &e))
+            .await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+    let sucess_message = match quiet_hours {
+        Some(quiet_hours) => format!(
+            "Notifications will be held from {:02}:00 to {:02}:00 UTC and posted afterwards.",
+            quiet_hours.start_hour, quiet_hours.end_hour
+        ),
+        None => "Quiet hours are disabled.".to_string(),
+    };
+    let reply = ctx.send(create_embed_sucess(&sucess_message)).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Posts the games of followed summoners together every 15, 30 or 60 minutes instead of one by one.
+#[poise::command(slash_command, guild_only)]
+pub async fn batch(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "How often match notifications are posted"] interval: NotificationBatch,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    if let Err(e) = set_notification_batch(&ctx.data().mongo_client, &guild_id, interval).await {
+        let reply = ctx
+            .send(create_embed_coded_error(
+                "Error saving the notification batch setting",
+                &e,
+            ))
+            .await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+    let sucess_message = match interval {
+        NotificationBatch::Off => {
+            "Each game will be notified as soon as it is detected.".to_string()
+        }
+        interval => format!(
+            "Games will be posted together, **{}**.",
+            interval.name().to_lowercase()
+        ),
+    };
+    let reply = ctx.send(create_embed_sucess(&sucess_message)).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Shows which followed summoners are in game in a voice channel name or a text channel topic.
+///
+/// The original name or topic is restored when no followed summoner is in game. Without `channel`, the live status
+/// stops being shown.
+#[poise::command(slash_command, guild_only)]
+pub async fn livestatus(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Voice or text channel showing the live games"]
+    #[channel_types("Voice", "Text")]
+    channel: Option<GuildChannel>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let mongo_client = &ctx.data().mongo_client;
+    let previous = get_guild_settings(mongo_client, &guild_id)
+        .await?
+        .live_status;
+    let live_status = channel.as_ref().map(|channel| {
+        let voice = channel.kind == ChannelType::Voice;
+        LiveStatusChannel {
+            channel_id: channel.id.get(),
+            voice,
+            original_text: if voice {
+                channel.name.clone()
+            } else {
+                channel.topic.clone().unwrap_or_default()
+            },
+            last_status: None,
+            updated_at: 0,
+        }
+    });
+    if let Err(e) = set_live_status_channel(mongo_client, &guild_id, live_status).await {
+        let reply = ctx
+            .send(create_embed_coded_error(
+                "Error saving the live status channel",
+                &e,
+            ))
+            .await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+
+    // Le salon précédent affichait peut-être encore une partie en cours
+    if let Some(previous) = previous.filter(|previous| previous.last_status.is_some()) {
+        let edit = if previous.voice {
+            EditChannel::new().name(&previous.original_text)
+        } else {
+            EditChannel::new().topic(&previous.original_text)
+        };
+        if let Err(e) = ChannelId::new(previous.channel_id)
+            .edit(ctx.http(), edit)
+            .await
+        {
+            log::warn!(
+                "Failed to restore the live status channel {}: {}",
+                previous.channel_id,
+                e
+            );
+        }
+    }
+
+    let sucess_message = match channel {
+        Some(channel) if channel.kind == ChannelType::Voice => format!(
+            "The name of {} will show which followed summoners are in game.",
+            channel.mention()
+        ),
+        Some(channel) => format!(
+            "The topic of {} will show which followed summoners are in game.",
+            channel.mention()
+        ),
+        None => "The live games will no longer be shown.".to_string(),
+    };
+    let reply = ctx.send(create_embed_sucess(&sucess_message)).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Creates a scheduled event on this server for every upcoming Clash day of a region.
+///
+/// Events are moved or removed when Riot changes the schedule. Without `region`, no more events are created.
+#[poise::command(slash_command, guild_only)]
+pub async fn clash(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Region of the Clash tournaments"] region: Option<Region>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let region_str = region.as_ref().map(region_to_string);
+    if let Err(e) = set_clash_region(&ctx.data().mongo_client, &guild_id, region_str).await {
+        let reply = ctx
+            .send(create_embed_coded_error(
+                "Error saving the Clash region",
+                &e,
+            ))
+            .await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+    let sucess_message = match region {
+        Some(region) => format!(
+            "The upcoming Clash days of **{}** will be added to the events of this server within the hour. \
+             The bot needs the **Manage Events** permission.",
+            region.name()
+        ),
+        None => "No more Clash events will be created.".to_string(),
+    };
+    let reply = ctx.send(create_embed_sucess(&sucess_message)).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Adds a champion nickname for this server, used by `/championsinfos` and `/aramroll`.
+///
+/// Server nicknames take precedence over the bundled ones (`mf`, `asol`, `kata`...). Without `champion`, the
+/// nickname is removed.
+#[poise::command(slash_command, guild_only)]
+pub async fn alias(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Nickname typed by the members (e.g., jgking)"]
+    #[max_length = 32]
+    nickname: String,
+    #[description = "Champion the nickname stands for, leave empty to remove the nickname"]
+    #[autocomplete = "autocomplete_champion"]
+    champion: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let mongo_client = &ctx.data().mongo_client;
+    let alias = normalize_champion_alias(&nickname);
+    if alias.is_empty() {
+        let reply = ctx
+            .send(create_embed_error(
+                "The nickname must contain letters or digits.",
+            ))
+            .await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+
+    let champion_id = match &champion {
+        Some(champion) => {
+            let champion_id = ctx
+                .data()
+                .champions
+                .read()
+                .await
+                .id_from_name(champion)
+                .map(str::to_string);
+            let Some(champion_id) = champion_id else {
+                let error_message = format!(
+                    "`{}` is not a champion. Pick it from the suggestions.",
+                    champion
+                );
+                let reply = ctx.send(create_embed_error(&error_message)).await?;
+                schedule_message_deletion(reply, ctx).await?;
+                return Ok(());
+            };
+            let settings = match get_guild_settings(mongo_client, &guild_id).await {
+                Ok(settings) => settings,
+                Err(e) => {
+                    let reply = ctx
+                        .send(create_embed_coded_error(
+                            "Error reading the server settings",
+                            &e,
+                        ))
+                        .await?;
+                    schedule_message_deletion(reply, ctx).await?;
+                    return Ok(());
+                }
+            };
+            if !settings.champion_aliases.contains_key(&alias)
+                && settings.champion_aliases.len() >= MAX_CHAMPION_ALIASES
+            {
+                let error_message = format!(
+                    "This server already has {} nicknames. Remove one before adding another.",
+                    MAX_CHAMPION_ALIASES
+                );
+                let reply = ctx.send(create_embed_error(&error_message)).await?;
+                schedule_message_deletion(reply, ctx).await?;
+                return Ok(());
+            }
+            Some(champion_id)
+        }
+        None => None,
+    };
+
+    if let Err(e) =
+        set_champion_alias(mongo_client, &guild_id, &alias, champion_id.as_deref()).await
+    {
+        let reply = ctx
+            .send(create_embed_coded_error("Error saving the nickname", &e))
+            .await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+    let sucess_message = match champion {
+        Some(champion) => format!("`{}` now stands for **{}**.", alias, champion.trim()),
+        None => format!("The nickname `{}` was removed.", alias),
+    };
+    let reply = ctx.send(create_embed_sucess(&sucess_message)).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
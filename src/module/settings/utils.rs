@@ -0,0 +1,448 @@
+use crate::models::data::{
+    Data, EmbedLayout, EmbedTheme, GuildSettingsData, LiveStatusChannel, NotificationBatch,
+    QuietHours, SummonerFollowedData,
+};
+use crate::models::error::Error;
+use crate::models::riot_id::RiotId;
+use chrono::Timelike;
+use mongodb::bson::{self, doc};
+use mongodb::{Client, Collection};
+use std::collections::HashMap;
+
+/// ⚙️ **Function**: Returns the `guild_settings` collection.
+pub fn guild_settings_collection(mongo_client: &Client) -> Collection<GuildSettingsData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<GuildSettingsData>("guild_settings")
+}
+
+/// ⚙️ **Function**: Retrieves the settings of a guild.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+///
+/// # Returns:
+/// - `Result<GuildSettingsData, Error>`: The stored settings, or the default ones if the guild never changed them.
+///
+/// # Example:
+/// ```rust
+/// let settings = get_guild_settings(&mongo_client, "123456789").await?;
+/// ```
+pub async fn get_guild_settings(
+    mongo_client: &Client,
+    guild_id: &str,
+) -> Result<GuildSettingsData, Error> {
+    let settings = guild_settings_collection(mongo_client)
+        .find_one(doc! { "guild_id": guild_id })
+        .await?;
+    Ok(settings.unwrap_or_else(|| GuildSettingsData {
+        guild_id: guild_id.to_string(),
+        ..Default::default()
+    }))
+}
+
+/// ⚙️ **Function**: Sets the match notification layout of a guild, or of one of its follows.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `layout`: The layout to use.
+/// - `riot_id`: `Some(riot_id)` to only change the layout of that followed summoner, `None` for the whole guild.
+///
+/// # Returns:
+/// - `Result<bool, Error>`: `false` if `riot_id` does not match any follow of the guild, `true` otherwise.
+///
+/// # Example:
+/// ```rust
+/// set_embed_layout(&mongo_client, "123456789", EmbedLayout::Compact, None).await?;
+/// ```
+///
+/// # Notes:
+/// - The Riot ID is matched case-insensitively.
+pub async fn set_embed_layout(
+    mongo_client: &Client,
+    guild_id: &str,
+    layout: EmbedLayout,
+    riot_id: Option<&RiotId>,
+) -> Result<bool, Error> {
+    let layout = bson::to_bson(&layout)?;
+    match riot_id {
+        None => {
+            guild_settings_collection(mongo_client)
+                .update_one(
+                    doc! { "guild_id": guild_id },
+                    doc! { "$set": { "embed_layout": layout } },
+                )
+                .upsert(true)
+                .await?;
+            Ok(true)
+        }
+        Some(riot_id) => {
+            let result = mongo_client
+                .database("stat-summoner")
+                .collection::<SummonerFollowedData>("follower_summoner")
+                .update_one(
+                    doc! {
+                        "guild_id": guild_id,
+                        "name": { "$regex": format!("^{}$", regex::escape(&riot_id.name)), "$options": "i" },
+                        "tag": { "$regex": format!("^{}$", regex::escape(&riot_id.tag)), "$options": "i" },
+                    },
+                    doc! { "$set": { "embed_layout": layout } },
+                )
+                .await?;
+            Ok(result.matched_count > 0)
+        }
+    }
+}
+
+/// ⚙️ **Function**: Resolves the layout of a match notification.
+///
+/// The layout of the first followed summoner that has one takes precedence over the guild setting.
+///
+/// # Parameters:
+/// - `settings`: The settings of the guild the notification is sent to.
+/// - `followed_summoners`: The followed summoners of the same guild who played the match.
+///
+/// # Returns:
+/// - `EmbedLayout`: The layout to render.
+pub fn resolve_embed_layout(
+    settings: &GuildSettingsData,
+    followed_summoners: &[SummonerFollowedData],
+) -> EmbedLayout {
+    followed_summoners
+        .iter()
+        .find_map(|summoner| summoner.embed_layout)
+        .unwrap_or(settings.embed_layout)
+}
+
+/// ⚙️ **Function**: Retrieves the embed theme of the guild a command is used in.
+///
+/// # Parameters:
+/// - `ctx`: The application context of the command.
+///
+/// # Returns:
+/// - `EmbedTheme`: The guild's theme, or the default palette in direct messages or if the settings cannot be read.
+///
+/// # Example:
+/// ```rust
+/// let theme = get_guild_theme(ctx).await;
+/// let embed = CreateEmbed::default().color(theme.info());
+/// ```
+pub async fn get_guild_theme(ctx: poise::ApplicationContext<'_, Data, Error>) -> EmbedTheme {
+    let Some(guild_id) = ctx.guild_id() else {
+        return EmbedTheme::default();
+    };
+    match get_guild_settings(&ctx.data().mongo_client, &guild_id.get().to_string()).await {
+        Ok(settings) => settings.theme,
+        Err(e) => {
+            log::error!("Failed to read the settings of guild {}: {}", guild_id, e);
+            EmbedTheme::default()
+        }
+    }
+}
+
+/// ⚙️ **Function**: Retrieves the champion nicknames added by the guild a command is used in.
+///
+/// # Parameters:
+/// - `ctx`: The application context of the command.
+///
+/// # Returns:
+/// - `HashMap<String, String>`: The nicknames as normalized nickname to Data Dragon ID, empty in direct messages or if
+///   the settings cannot be read.
+pub async fn get_guild_champion_aliases(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+) -> HashMap<String, String> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return HashMap::new();
+    };
+    match get_guild_settings(&ctx.data().mongo_client, &guild_id.get().to_string()).await {
+        Ok(settings) => settings.champion_aliases,
+        Err(e) => {
+            log::error!("Failed to read the settings of guild {}: {}", guild_id, e);
+            HashMap::new()
+        }
+    }
+}
+
+/// ⚙️ **Function**: Saves the embed theme of a guild.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `theme`: The theme to save. `EmbedTheme::default()` restores the default palette.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an error if the settings cannot be saved.
+pub async fn set_embed_theme(
+    mongo_client: &Client,
+    guild_id: &str,
+    theme: &EmbedTheme,
+) -> Result<(), Error> {
+    guild_settings_collection(mongo_client)
+        .update_one(
+            doc! { "guild_id": guild_id },
+            doc! { "$set": { "theme": bson::to_bson(theme)? } },
+        )
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Sets the channel where the challenge of the week is posted for a guild.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `channel_id`: The channel of the challenges, or `None` to stop posting them.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an error if the settings cannot be saved.
+///
+/// # Notes:
+/// - Setting a channel resets the week of the last challenge, so the current week's challenge is posted on the next run.
+pub async fn set_challenge_channel(
+    mongo_client: &Client,
+    guild_id: &str,
+    channel_id: Option<u64>,
+) -> Result<(), Error> {
+    guild_settings_collection(mongo_client)
+        .update_one(
+            doc! { "guild_id": guild_id },
+            doc! { "$set": {
+                "challenge_channel_id": channel_id.map(|id| id as i64),
+                "challenge_week": bson::Bson::Null,
+            } },
+        )
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Enables or disables the gold difference graph of the match notifications of a guild.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `enabled`: Whether games longer than 20 minutes get a gold graph.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an error if the settings cannot be saved.
+pub async fn set_gold_graph(
+    mongo_client: &Client,
+    guild_id: &str,
+    enabled: bool,
+) -> Result<(), Error> {
+    guild_settings_collection(mongo_client)
+        .update_one(
+            doc! { "guild_id": guild_id },
+            doc! { "$set": { "gold_graph": enabled } },
+        )
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Enables or disables the bans line of the match notifications of a guild.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `enabled`: Whether the notifications show the bans of both teams.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an error if the settings cannot be saved.
+pub async fn set_show_bans(
+    mongo_client: &Client,
+    guild_id: &str,
+    enabled: bool,
+) -> Result<(), Error> {
+    guild_settings_collection(mongo_client)
+        .update_one(
+            doc! { "guild_id": guild_id },
+            doc! { "$set": { "show_bans": enabled } },
+        )
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Sets the quiet hours of a guild, during which notifications are held back.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `quiet_hours`: The quiet hours (UTC), or `None` to disable them.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an error if the settings cannot be saved.
+pub async fn set_quiet_hours(
+    mongo_client: &Client,
+    guild_id: &str,
+    quiet_hours: Option<QuietHours>,
+) -> Result<(), Error> {
+    guild_settings_collection(mongo_client)
+        .update_one(
+            doc! { "guild_id": guild_id },
+            doc! { "$set": { "quiet_hours": bson::to_bson(&quiet_hours)? } },
+        )
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Sets how often the match notifications of a guild are posted.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `batch`: The batch interval, or `NotificationBatch::Off` to post each game right away.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an error if the settings cannot be saved.
+pub async fn set_notification_batch(
+    mongo_client: &Client,
+    guild_id: &str,
+    batch: NotificationBatch,
+) -> Result<(), Error> {
+    guild_settings_collection(mongo_client)
+        .update_one(
+            doc! { "guild_id": guild_id },
+            doc! { "$set": { "notification_batch": bson::to_bson(&batch)? } },
+        )
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Sets the channel where a guild shows which followed players are in game.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `live_status`: The live status channel, or `None` to stop showing the live games.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an error if the settings cannot be saved.
+pub async fn set_live_status_channel(
+    mongo_client: &Client,
+    guild_id: &str,
+    live_status: Option<LiveStatusChannel>,
+) -> Result<(), Error> {
+    guild_settings_collection(mongo_client)
+        .update_one(
+            doc! { "guild_id": guild_id },
+            doc! { "$set": { "live_status": bson::to_bson(&live_status)? } },
+        )
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Subscribes a guild to the Clash schedule of a region, or unsubscribes it.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `region`: The server whose Clash tournaments become scheduled events (e.g., `euw1`), or `None` to stop.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an error if the settings cannot be saved.
+pub async fn set_clash_region(
+    mongo_client: &Client,
+    guild_id: &str,
+    region: Option<String>,
+) -> Result<(), Error> {
+    guild_settings_collection(mongo_client)
+        .update_one(
+            doc! { "guild_id": guild_id },
+            doc! { "$set": { "clash_region": region } },
+        )
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Adds a champion nickname to a guild, or removes it.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `alias`: The normalized nickname (see `normalize_champion_alias`).
+/// - `champion_id`: The Data Dragon ID of the champion, or `None` to remove the nickname.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an error if the settings cannot be saved.
+pub async fn set_champion_alias(
+    mongo_client: &Client,
+    guild_id: &str,
+    alias: &str,
+    champion_id: Option<&str>,
+) -> Result<(), Error> {
+    let field = format!("champion_aliases.{}", alias);
+    let update = match champion_id {
+        Some(champion_id) => doc! { "$set": { field: champion_id } },
+        None => doc! { "$unset": { field: "" } },
+    };
+    guild_settings_collection(mongo_client)
+        .update_one(doc! { "guild_id": guild_id }, update)
+        .upsert(true)
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Saves the status last written in the live status channel of a guild.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `last_status`: The status shown in the channel, or `None` if its original name or topic is shown.
+/// - `updated_at`: The Unix timestamp of the edit attempt.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an error if the settings cannot be saved.
+pub async fn save_live_status(
+    mongo_client: &Client,
+    guild_id: &str,
+    last_status: Option<String>,
+    updated_at: i64,
+) -> Result<(), Error> {
+    guild_settings_collection(mongo_client)
+        .update_one(
+            doc! { "guild_id": guild_id, "live_status": { "$ne": null } },
+            doc! { "$set": {
+                "live_status.last_status": last_status,
+                "live_status.updated_at": updated_at,
+            } },
+        )
+        .await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Checks whether a guild is currently in its quiet hours.
+///
+/// # Parameters:
+/// - `settings`: The settings of the guild.
+///
+/// # Returns:
+/// - `bool`: `true` if the guild configured quiet hours and the current UTC hour falls within them.
+pub fn is_in_quiet_hours(settings: &GuildSettingsData) -> bool {
+    settings
+        .quiet_hours
+        .is_some_and(|quiet_hours| quiet_hours.contains(chrono::Utc::now().hour()))
+}
+
+/// ⚙️ **Function**: Parses a hexadecimal color such as `#1E90FF` or `1e90ff`.
+///
+/// # Parameters:
+/// - `color`: The color entered by the user.
+///
+/// # Returns:
+/// - `Option<u32>`: The color as an RGB integer, or `None` if it is not a 6-digit hexadecimal color.
+pub fn parse_hex_color(color: &str) -> Option<u32> {
+    let color = color.trim().trim_start_matches('#');
+    if color.len() != 6 || !color.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(color, 16).ok()
+}
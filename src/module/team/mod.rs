@@ -0,0 +1,17 @@
+/// 🛠 **Module team**: Contains the rosters of linked accounts defined by a guild, such as its 5-stack.
+///
+/// A team is a named list of Discord members who linked their account with `/linkaccount`. Its statistics combine the
+/// ranks, recent games and champions of the members, and the whole team can be followed at once.
+///
+/// # Files in this module:
+/// - `team.rs`: The `/team` command and its subcommands.
+/// - `utils.rs`: The `teams` collection, the team autocompletion and the statistics embed.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`:
+///
+/// ```rust
+/// team::team::team(),
+/// ```
+pub mod team;
+pub mod utils;
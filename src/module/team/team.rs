@@ -0,0 +1,488 @@
+use crate::config::deletion_notice;
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, create_embed_sucess, edit_and_schedule_deletion,
+    schedule_message_deletion, send_fetching_message,
+};
+use crate::models::data::{Data, NotificationFilter, QueueFilter, SummonerFollowedData, TeamData};
+use crate::models::error::Error;
+use crate::module::followgames::utils::{check_follow_hours, save_follow, FollowOutcome};
+use crate::module::linkaccount::utils::get_linked_account;
+use crate::module::myaccounts::utils::linked_accounts_collection;
+use crate::module::settings::utils::get_guild_theme;
+use crate::module::team::utils::{
+    autocomplete_team, can_manage_team, create_embed_team_stats, fetch_member_summary, find_team,
+    get_guild_teams, teams_collection, TEAM_MAX_MEMBERS,
+};
+use crate::riot_api::get_matchs_id;
+use chrono::{Duration, Utc};
+use mongodb::bson::doc;
+use poise::serenity_prelude::{self as serenity, CreateEmbed, CreateEmbedFooter, Mentionable};
+use poise::CreateReply;
+
+/// Manages the teams of this server: named rosters of members who linked their account.
+///
+/// # Example:
+/// ```rust
+/// /team create name: Team Alpha
+/// /team add team: Team Alpha member: @Faker
+/// /team stats team: Team Alpha
+/// /team follow team: Team Alpha hours: 3
+/// ```
+///
+/// # Notes:
+/// - A team can be changed or deleted by the member who created it and by the members who can manage the server.
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("create", "add", "remove", "delete", "list", "stats", "follow")
+)]
+pub async fn team(_ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// ⚙️ **Function**: Finds the team named in a command and checks that its author can change it.
+///
+/// # Returns:
+/// - `Result<Option<TeamData>, Error>`: The team, or `None` once an error message has been sent.
+async fn find_managed_team(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    guild_id: &str,
+    name: &str,
+) -> Result<Option<TeamData>, Error> {
+    let error_message = match find_team(&ctx.data().mongo_client, guild_id, name).await {
+        Ok(Some(team)) if can_manage_team(ctx, &team) => return Ok(Some(team)),
+        Ok(Some(_)) => {
+            "Only the creator of this team and the server managers can change it.".to_string()
+        }
+        Ok(None) => format!("There is no team named **{}** on this server.", name.trim()),
+        Err(e) => {
+            let reply = ctx
+                .send(create_embed_coded_error(
+                    "Error collecting informations from MongoDB",
+                    &e.into(),
+                ))
+                .await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(None);
+        }
+    };
+    let reply = ctx.send(create_embed_error(&error_message)).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(None)
+}
+
+/// Creates a team on this server.
+#[poise::command(slash_command, guild_only)]
+pub async fn create(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Name of the team"]
+    #[max_length = 32]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let mongo_client = &ctx.data().mongo_client;
+    let name = name.trim().to_string();
+    let result = match find_team(mongo_client, &guild_id, &name).await {
+        Ok(Some(_)) => {
+            let error_message = format!("A team named **{}** already exists.", name);
+            let reply = ctx.send(create_embed_error(&error_message)).await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(());
+        }
+        Ok(None) => {
+            teams_collection(mongo_client)
+                .insert_one(TeamData {
+                    guild_id,
+                    name: name.clone(),
+                    members: Vec::new(),
+                    created_by: ctx.author().id.get(),
+                    created_at: Utc::now().timestamp(),
+                })
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    let reply = match result {
+        Ok(_) => create_embed_sucess(&format!(
+            "Team **{}** created. Add its members with `/team add`.",
+            name
+        )),
+        Err(e) => create_embed_coded_error("Error saving the team", &e.into()),
+    };
+    let reply = ctx.send(reply).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Adds a member who linked their account to a team.
+#[poise::command(slash_command, guild_only)]
+pub async fn add(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Name of the team"]
+    #[autocomplete = "autocomplete_team"]
+    team: String,
+    #[description = "Member to add"] member: serenity::User,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let Some(team) = find_managed_team(ctx, &guild_id, &team).await? else {
+        return Ok(());
+    };
+    let mongo_client = &ctx.data().mongo_client;
+    let error_message = if team.members.contains(&member.id.get()) {
+        Some(format!(
+            "{} is already in **{}**.",
+            member.mention(),
+            team.name
+        ))
+    } else if team.members.len() >= TEAM_MAX_MEMBERS {
+        Some(format!(
+            "A team cannot have more than {} members.",
+            TEAM_MAX_MEMBERS
+        ))
+    } else {
+        match get_linked_account(&linked_accounts_collection(mongo_client), member.id.get()).await
+        {
+            Ok(Some(_)) => None,
+            Ok(None) => Some(format!(
+                "{} has not linked a League of Legends account yet. They can do it with `/linkaccount`.",
+                member.mention()
+            )),
+            Err(e) => {
+                let reply = ctx
+                    .send(create_embed_coded_error(
+                        "Error collecting informations from MongoDB",
+                        &e.into(),
+                    ))
+                    .await?;
+                schedule_message_deletion(reply, ctx).await?;
+                return Ok(());
+            }
+        }
+    };
+    if let Some(error_message) = error_message {
+        let reply = ctx.send(create_embed_error(&error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+
+    let reply = match teams_collection(mongo_client)
+        .update_one(
+            doc! { "guild_id": &guild_id, "name": &team.name },
+            doc! { "$addToSet": { "members": member.id.get() as i64 } },
+        )
+        .await
+    {
+        Ok(_) => create_embed_sucess(&format!("{} joined **{}**.", member.mention(), team.name)),
+        Err(e) => create_embed_coded_error("Error saving the team", &e.into()),
+    };
+    let reply = ctx.send(reply).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Removes a member from a team.
+#[poise::command(slash_command, guild_only)]
+pub async fn remove(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Name of the team"]
+    #[autocomplete = "autocomplete_team"]
+    team: String,
+    #[description = "Member to remove"] member: serenity::User,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let Some(team) = find_managed_team(ctx, &guild_id, &team).await? else {
+        return Ok(());
+    };
+    if !team.members.contains(&member.id.get()) {
+        let error_message = format!("{} is not in **{}**.", member.mention(), team.name);
+        let reply = ctx.send(create_embed_error(&error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+    let reply = match teams_collection(&ctx.data().mongo_client)
+        .update_one(
+            doc! { "guild_id": &guild_id, "name": &team.name },
+            doc! { "$pull": { "members": member.id.get() as i64 } },
+        )
+        .await
+    {
+        Ok(_) => create_embed_sucess(&format!("{} left **{}**.", member.mention(), team.name)),
+        Err(e) => create_embed_coded_error("Error saving the team", &e.into()),
+    };
+    let reply = ctx.send(reply).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Deletes a team. The follows of its members are kept.
+#[poise::command(slash_command, guild_only)]
+pub async fn delete(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Name of the team"]
+    #[autocomplete = "autocomplete_team"]
+    team: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let Some(team) = find_managed_team(ctx, &guild_id, &team).await? else {
+        return Ok(());
+    };
+    let reply = match teams_collection(&ctx.data().mongo_client)
+        .delete_one(doc! { "guild_id": &guild_id, "name": &team.name })
+        .await
+    {
+        Ok(_) => create_embed_sucess(&format!("Team **{}** deleted.", team.name)),
+        Err(e) => create_embed_coded_error("Error deleting the team", &e.into()),
+    };
+    let reply = ctx.send(reply).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Lists the teams of this server and their members.
+#[poise::command(slash_command, guild_only)]
+pub async fn list(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let teams = match get_guild_teams(&ctx.data().mongo_client, &guild_id).await {
+        Ok(teams) => teams,
+        Err(e) => {
+            let reply = ctx
+                .send(create_embed_coded_error(
+                    "Error collecting informations from MongoDB",
+                    &e.into(),
+                ))
+                .await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(());
+        }
+    };
+    if teams.is_empty() {
+        let error_message = "This server has no team yet. Create one with `/team create`.";
+        let reply = ctx.send(create_embed_error(error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+    let theme = get_guild_theme(ctx).await;
+    let mut embed = CreateEmbed::default()
+        .title("👥 Teams")
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
+    for team in teams.iter().take(25) {
+        let members = if team.members.is_empty() {
+            "No member yet".to_string()
+        } else {
+            team.members
+                .iter()
+                .map(|member| serenity::UserId::new(*member).mention().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        embed = embed.field(&team.name, members, false);
+    }
+    let reply = ctx.send(CreateReply::default().embed(embed)).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
+
+/// Shows the combined rank, recent form and champion pools of a team.
+///
+/// # Notes:
+/// - The primary linked account of each member is used, and their last 5 games are read.
+#[poise::command(slash_command, guild_only)]
+pub async fn stats(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Name of the team"]
+    #[autocomplete = "autocomplete_team"]
+    team: String,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let mongo_client = &ctx.data().mongo_client;
+    let team = match find_team(mongo_client, &guild_id, &team).await {
+        Ok(Some(team)) if !team.members.is_empty() => team,
+        Ok(Some(team)) => {
+            let error_message = format!(
+                "**{}** has no member yet. Add them with `/team add`.",
+                team.name
+            );
+            edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message)).await?;
+            return Ok(());
+        }
+        Ok(None) => {
+            let error_message = format!("There is no team named **{}** on this server.", team);
+            edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message)).await?;
+            return Ok(());
+        }
+        Err(e) => {
+            let reply =
+                create_embed_coded_error("Error collecting informations from MongoDB", &e.into());
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+
+    let client = reqwest::Client::new();
+    let collection_linked = linked_accounts_collection(mongo_client);
+    let mut summaries = Vec::new();
+    let mut missing = 0;
+    for member in &team.members {
+        let Ok(Some(account)) = get_linked_account(&collection_linked, *member).await else {
+            missing += 1;
+            continue;
+        };
+        match fetch_member_summary(&client, &account, &ctx.data().riot_api_key).await {
+            Ok(summary) => summaries.push(summary),
+            Err(e) => {
+                log::error!("Failed to fetch the games of {}: {:?}", account.puuid, e);
+                missing += 1;
+            }
+        }
+    }
+    if summaries.is_empty() {
+        let error_message = "The accounts of the members of this team could not be read.";
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+        return Ok(());
+    }
+    let theme = get_guild_theme(ctx).await;
+    let embed = create_embed_team_stats(&team, &summaries, missing, &theme);
+    edit_and_schedule_deletion(fetching, ctx, CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Follows the games of every member of a team in this channel.
+///
+/// # Notes:
+/// - Each member is followed like with `/followuser`: members already followed get their follow time updated.
+#[poise::command(slash_command, guild_only)]
+pub async fn follow(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Name of the team"]
+    #[autocomplete = "autocomplete_team"]
+    team: String,
+    #[description = "Number of hours to follow"]
+    #[min = 1]
+    hours: u32,
+    #[description = "Only notify some games (all games by default)"] filter: Option<
+        NotificationFilter,
+    >,
+    #[description = "Only notify the games of some queues (all queues by default)"] queue: Option<
+        QueueFilter,
+    >,
+) -> Result<(), Error> {
+    if let Err(error_message) = check_follow_hours(hours) {
+        let reply = ctx.send(create_embed_error(&error_message)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        return Ok(());
+    }
+    let fetching = send_fetching_message(ctx).await?;
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let mongo_client = &ctx.data().mongo_client;
+    let team = match find_team(mongo_client, &guild_id, &team).await {
+        Ok(Some(team)) => team,
+        Ok(None) => {
+            let error_message = format!("There is no team named **{}** on this server.", team);
+            edit_and_schedule_deletion(fetching, ctx, create_embed_error(&error_message)).await?;
+            return Ok(());
+        }
+        Err(e) => {
+            let reply =
+                create_embed_coded_error("Error collecting informations from MongoDB", &e.into());
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+
+    let client = reqwest::Client::new();
+    let riot_api_key = &ctx.data().riot_api_key;
+    let collection_linked = linked_accounts_collection(mongo_client);
+    let collection = mongo_client
+        .database("stat-summoner")
+        .collection::<SummonerFollowedData>("follower_summoner");
+    let time_end_follow = (Utc::now() + Duration::hours(hours as i64))
+        .timestamp()
+        .to_string();
+    let mut lines = Vec::new();
+    for member in &team.members {
+        let mention = serenity::UserId::new(*member).mention();
+        let Ok(Some(account)) = get_linked_account(&collection_linked, *member).await else {
+            lines.push(format!("⚠️ {}: no linked account", mention));
+            continue;
+        };
+        let last_match_id = match get_matchs_id(&client, &account.puuid, riot_api_key, 1).await {
+            Ok(ids) => ids.first().cloned().unwrap_or_default(),
+            Err(e) => {
+                log::error!(
+                    "Failed to fetch the last game of {}: {:?}",
+                    account.puuid,
+                    e
+                );
+                lines.push(format!("⚠️ {}: the Riot API could not be reached", mention));
+                continue;
+            }
+        };
+        let new_followed_summoner = SummonerFollowedData {
+            puuid: account.puuid,
+            summoner_id: account.summoner_id,
+            name: account.name,
+            tag: account.tag,
+            region: account.region,
+            last_match_id,
+            time_end_follow: time_end_follow.clone(),
+            channel_id: ctx.channel_id().get(),
+            guild_id: guild_id.clone(),
+            embed_layout: None,
+            notification_filter: filter.unwrap_or_default(),
+            queue_filter: queue.unwrap_or_default(),
+        };
+        let riot_id = new_followed_summoner.riot_id();
+        let line = match save_follow(
+            &collection,
+            mongo_client,
+            &new_followed_summoner,
+            ctx.author().id.get(),
+        )
+        .await
+        {
+            Ok(FollowOutcome::Created) => format!("✅ {} ({}): followed", riot_id, mention),
+            Ok(FollowOutcome::Updated) => {
+                format!("🔄 {} ({}): follow time updated", riot_id, mention)
+            }
+            Ok(FollowOutcome::OptedOut) => {
+                format!("⛔ {} ({}): opted out of Stat Summoner", riot_id, mention)
+            }
+            Ok(FollowOutcome::Blacklisted { .. }) => {
+                format!("⛔ {} ({}): blacklisted", riot_id, mention)
+            }
+            Ok(FollowOutcome::GuildLimitReached { limit }) => format!(
+                "⛔ {} ({}): this server already follows {} summoners",
+                riot_id, mention, limit
+            ),
+            Err(e) => {
+                log::error!("Failed to follow {}: {:?}", riot_id, e);
+                format!(
+                    "⚠️ {} ({}): the follow could not be saved",
+                    riot_id, mention
+                )
+            }
+        };
+        lines.push(line);
+    }
+    let reply = if lines.is_empty() {
+        create_embed_error(&format!(
+            "**{}** has no member yet. Add them with `/team add`.",
+            team.name
+        ))
+    } else {
+        create_embed_sucess(&format!(
+            "Following **{}** for {} hour{}:\n{}",
+            team.name,
+            hours,
+            if hours > 1 { "s" } else { "" },
+            lines.join("\n")
+        ))
+    };
+    edit_and_schedule_deletion(fetching, ctx, reply).await?;
+    Ok(())
+}
@@ -0,0 +1,341 @@
+use crate::config::deletion_notice;
+use crate::models::data::{Data, EmbedTheme, LinkedAccountData, TeamData};
+use crate::models::error::Error;
+use crate::module::lolstats::season::{rank_score, DIVISIONS, TIERS};
+use crate::riot_api::{get_matchs_id, get_matchs_info, get_rank_info};
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use serde_json::Value;
+
+/// Maximum number of members of a team (a 5-stack and its substitutes).
+pub const TEAM_MAX_MEMBERS: usize = 10;
+
+/// Number of recent games of each member read for the team statistics.
+const TEAM_RECENT_GAMES: u32 = 5;
+
+/// Number of champions shown in the pool of each member.
+const CHAMPION_POOL_SIZE: usize = 3;
+
+/// ⚙️ **Function**: Returns the `teams` collection.
+pub fn teams_collection(mongo_client: &Client) -> Collection<TeamData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<TeamData>("teams")
+}
+
+/// ⚙️ **Function**: Retrieves the teams of a guild, sorted by name.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+///
+/// # Returns:
+/// - `Result<Vec<TeamData>, mongodb::error::Error>`: The teams of the guild, empty if it has none.
+pub async fn get_guild_teams(
+    mongo_client: &Client,
+    guild_id: &str,
+) -> Result<Vec<TeamData>, mongodb::error::Error> {
+    teams_collection(mongo_client)
+        .find(doc! { "guild_id": guild_id })
+        .sort(doc! { "name": 1 })
+        .await?
+        .try_collect()
+        .await
+}
+
+/// ⚙️ **Function**: Finds a team of a guild by its name, ignoring case.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the guild.
+/// - `name`: The name of the team.
+///
+/// # Returns:
+/// - `Result<Option<TeamData>, mongodb::error::Error>`: The team, `None` if the guild has no team with this name.
+///
+/// # Example:
+/// ```rust
+/// if let Some(team) = find_team(&mongo_client, &guild_id, "Team Alpha").await? {
+///     println!("{} members", team.members.len());
+/// }
+/// ```
+pub async fn find_team(
+    mongo_client: &Client,
+    guild_id: &str,
+    name: &str,
+) -> Result<Option<TeamData>, mongodb::error::Error> {
+    let name = name.trim();
+    Ok(get_guild_teams(mongo_client, guild_id)
+        .await?
+        .into_iter()
+        .find(|team| team.name.eq_ignore_ascii_case(name)))
+}
+
+/// ⚙️ **Function**: Autocompletes the name of a team of the guild.
+///
+/// # Parameters:
+/// - `ctx`: The application context of the command.
+/// - `partial`: The text typed so far.
+///
+/// # Returns:
+/// - `Vec<String>`: The names of the matching teams.
+pub async fn autocomplete_team(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    partial: &str,
+) -> Vec<String> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Vec::new();
+    };
+    let partial = partial.trim().to_lowercase();
+    get_guild_teams(&ctx.data().mongo_client, &guild_id.get().to_string())
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|team| team.name)
+        .filter(|name| name.to_lowercase().contains(&partial))
+        .take(25)
+        .collect()
+}
+
+/// ⚙️ **Function**: Checks whether the author of a command can change a team.
+///
+/// # Parameters:
+/// - `ctx`: The application context of the command.
+/// - `team`: The team to change.
+///
+/// # Returns:
+/// - `bool`: `true` for the creator of the team (its captain) and for the members who can manage the server.
+pub fn can_manage_team(ctx: poise::ApplicationContext<'_, Data, Error>, team: &TeamData) -> bool {
+    team.created_by == ctx.author().id.get()
+        || ctx
+            .interaction
+            .member
+            .as_ref()
+            .and_then(|member| member.permissions)
+            .is_some_and(|permissions| permissions.manage_guild())
+}
+
+/// Rank, recent games and champions of a team member.
+#[derive(Debug, Default)]
+pub struct MemberSummary {
+    pub riot_id: String,
+    /// Solo/Duo rank as `(tier, division, league points)`, `None` if the member is unranked.
+    pub rank: Option<(String, String, i64)>,
+    /// Results of the recent games, the last one first (`true` for a win).
+    pub results: Vec<bool>,
+    /// Champions played in the recent games with their number of games, most played first.
+    pub champions: Vec<(String, u32)>,
+}
+
+/// ⚙️ **Function**: Fetches the Solo/Duo rank, the recent games and the champions played of a team member.
+///
+/// # Parameters:
+/// - `client`: The HTTP client used to query the Riot API.
+/// - `account`: The linked account of the member.
+/// - `riot_api_key`: The Riot API key.
+///
+/// # Returns:
+/// - `Result<MemberSummary, Error>`: The summary of the member, or an error if the Riot API fails.
+///
+/// # Notes:
+/// - Only the last 5 games are read, whatever their queue, to keep the number of Riot API requests low.
+pub async fn fetch_member_summary(
+    client: &reqwest::Client,
+    account: &LinkedAccountData,
+    riot_api_key: &str,
+) -> Result<MemberSummary, Error> {
+    let mut summary = MemberSummary {
+        riot_id: account.riot_id().to_string(),
+        ..Default::default()
+    };
+    let rank_info =
+        get_rank_info(client, &account.region, &account.summoner_id, riot_api_key).await?;
+    summary.rank = rank_info
+        .iter()
+        .find(|entry| entry.get("queueType").and_then(Value::as_str) == Some("RANKED_SOLO_5x5"))
+        .map(|entry| {
+            let field = |name: &str| {
+                entry
+                    .get(name)
+                    .and_then(Value::as_str)
+                    .unwrap_or("")
+                    .to_string()
+            };
+            (
+                field("tier"),
+                field("rank"),
+                entry
+                    .get("leaguePoints")
+                    .and_then(Value::as_i64)
+                    .unwrap_or(0),
+            )
+        });
+
+    for match_id in get_matchs_id(client, &account.puuid, riot_api_key, TEAM_RECENT_GAMES).await? {
+        let match_info = get_matchs_info(client, &match_id, riot_api_key).await?;
+        let Some(participant) =
+            match_info["info"]["participants"]
+                .as_array()
+                .and_then(|participants| {
+                    participants.iter().find(|participant| {
+                        participant["puuid"].as_str() == Some(account.puuid.as_str())
+                    })
+                })
+        else {
+            continue;
+        };
+        summary
+            .results
+            .push(participant["win"].as_bool().unwrap_or(false));
+        let champion = participant["championName"]
+            .as_str()
+            .unwrap_or("Unknown")
+            .to_string();
+        match summary
+            .champions
+            .iter_mut()
+            .find(|(name, _)| *name == champion)
+        {
+            Some((_, games)) => *games += 1,
+            None => summary.champions.push((champion, 1)),
+        }
+    }
+    summary.champions.sort_by(|(_, a), (_, b)| b.cmp(a));
+    Ok(summary)
+}
+
+/// ⚙️ **Function**: Formats a rank score (see `rank_score`) as a rank, e.g. `Gold II`.
+///
+/// # Parameters:
+/// - `score`: The rank score.
+///
+/// # Returns:
+/// - `String`: The tier and division of the score. The division is left out for Master and above.
+fn format_rank_score(score: i64) -> String {
+    let tier = TIERS[(score / 400).clamp(0, TIERS.len() as i64 - 1) as usize];
+    let tier = format!("{}{}", &tier[..1], tier[1..].to_lowercase());
+    if score / 400 >= 7 {
+        return tier;
+    }
+    let division = DIVISIONS[((score % 400) / 100).clamp(0, 3) as usize];
+    format!("{} {}", tier, division)
+}
+
+/// ⚙️ **Function**: Creates the embed of the statistics of a team.
+///
+/// # Parameters:
+/// - `team`: The team.
+/// - `summaries`: The summaries of the members who linked an account, built by `fetch_member_summary`.
+/// - `missing`: The number of members whose account could not be read (no linked account or Riot API error).
+/// - `theme`: The embed theme of the guild.
+///
+/// # Returns:
+/// - `CreateEmbed`: The embed, with the average rank and the recent record of the team, then one line per member.
+///
+/// # Example:
+/// ```text
+/// 👥 Team Alpha
+/// Average rank: Gold I (4 ranked members)
+/// Recent form: 14W - 11L (56%)
+///
+/// Faker#KR1: Gold II 45 LP · ✅✅❌✅❌ · Ahri ×2, Sylas, Azir
+/// ```
+pub fn create_embed_team_stats(
+    team: &TeamData,
+    summaries: &[MemberSummary],
+    missing: usize,
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let scores: Vec<i64> = summaries
+        .iter()
+        .filter_map(|summary| {
+            let (tier, division, league_points) = summary.rank.as_ref()?;
+            rank_score(tier, division, *league_points)
+        })
+        .collect();
+    let average_rank = if scores.is_empty() {
+        "No ranked member".to_string()
+    } else {
+        format!(
+            "{} ({} ranked member{})",
+            format_rank_score(scores.iter().sum::<i64>() / scores.len() as i64),
+            scores.len(),
+            if scores.len() > 1 { "s" } else { "" }
+        )
+    };
+    let wins = summaries
+        .iter()
+        .flat_map(|summary| summary.results.iter())
+        .filter(|win| **win)
+        .count();
+    let games: usize = summaries.iter().map(|summary| summary.results.len()).sum();
+    let form = if games == 0 {
+        "No recent game".to_string()
+    } else {
+        format!(
+            "{}W - {}L ({:.0}%)",
+            wins,
+            games - wins,
+            wins as f64 / games as f64 * 100.0
+        )
+    };
+
+    let members = summaries
+        .iter()
+        .map(|summary| {
+            let rank = match &summary.rank {
+                Some((tier, division, league_points)) => {
+                    match rank_score(tier, division, *league_points) {
+                        Some(score) => format!("{} {} LP", format_rank_score(score), league_points),
+                        None => "Unranked".to_string(),
+                    }
+                }
+                None => "Unranked".to_string(),
+            };
+            let results: String = summary
+                .results
+                .iter()
+                .map(|win| if *win { "✅" } else { "❌" })
+                .collect();
+            let champions = summary
+                .champions
+                .iter()
+                .take(CHAMPION_POOL_SIZE)
+                .map(|(name, games)| {
+                    if *games > 1 {
+                        format!("{} ×{}", name, games)
+                    } else {
+                        name.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "**{}**: {} · {} · {}",
+                summary.riot_id, rank, results, champions
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut description = format!(
+        "Average rank: **{}**\nRecent form: **{}**\n\n{}",
+        average_rank, form, members
+    );
+    if missing > 0 {
+        description.push_str(&format!(
+            "\n\n{} member{} without a readable linked account.",
+            missing,
+            if missing > 1 { "s" } else { "" }
+        ));
+    }
+    CreateEmbed::default()
+        .title(format!("👥 {}", team.name))
+        .description(description)
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
@@ -0,0 +1,16 @@
+/// 🛠 **Module tierlist**: Contains the champions with the best winrate of the latest patch, by role and rank bracket.
+///
+/// The statistics are the ones scraped daily for `/championsinfos`, including their rank brackets.
+///
+/// # Files in this module:
+/// - `tierlist.rs`: The `/tierlist` command.
+/// - `utils.rs`: The ranking of the champions and the embed.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`:
+///
+/// ```rust
+/// tierlist::tierlist::tierlist(),
+/// ```
+pub mod tierlist;
+pub mod utils;
@@ -0,0 +1,68 @@
+use crate::embed::{create_embed_coded_error, edit_and_schedule_deletion, send_fetching_message};
+use crate::models::bracket::RankBracket;
+use crate::models::data::{ChampionData, Data, EmojiId};
+use crate::models::error::Error;
+use crate::models::role::Role;
+use crate::module::championsinfos::utils::get_latest_champion_patch;
+use crate::module::randomchampions::utils::{get_list_champions, match_role_with_database_roles};
+use crate::module::settings::utils::get_guild_theme;
+use crate::module::tierlist::utils::{create_embed_tierlist, rank_tierlist};
+use poise::CreateReply;
+
+/// Shows the champions with the best winrate of the latest patch.
+///
+/// # Parameters:
+/// - `role`: Optional role the champions are picked in.
+/// - `bracket`: Optional rank bracket (Iron–Gold, Platinum+, Diamond+) whose statistics are compared.
+///
+/// # Example:
+/// ```rust
+/// /tierlist
+/// /tierlist role: ADC bracket: Diamond+
+/// ```
+///
+/// # Notes:
+/// - The statistics are the ones of `/championsinfos`, scraped every day.
+/// - Champions played in less than 0.5% of the games are left out.
+#[poise::command(slash_command)]
+pub async fn tierlist(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Select a role (optional)"] role: Option<Role>,
+    #[description = "Rank bracket, all ranks by default"] bracket: Option<RankBracket>,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let bracket = bracket.unwrap_or(RankBracket::All);
+    let role_label = role.clone().map(match_role_with_database_roles);
+    let champions = match get_list_champions(ctx, role).await {
+        Ok(champions) => champions,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error collecting informations from MongoDB", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    let mongo_client = &ctx.data().mongo_client;
+    let patch = get_latest_champion_patch(
+        &mongo_client
+            .database("stat-summoner")
+            .collection::<ChampionData>("champions_data"),
+    )
+    .await
+    .unwrap_or_default();
+    let collection_emoji = mongo_client
+        .database("stat-summoner")
+        .collection::<EmojiId>("emojis_id");
+    let theme = get_guild_theme(ctx).await;
+    let entries = rank_tierlist(&champions, bracket);
+    let embed = create_embed_tierlist(
+        &entries,
+        role_label.as_deref(),
+        bracket,
+        patch.as_deref(),
+        &theme,
+        &collection_emoji,
+    )
+    .await;
+    edit_and_schedule_deletion(fetching, ctx, CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use crate::config::deletion_notice;
+use crate::models::bracket::RankBracket;
+use crate::models::data::{ChampionData, EmbedTheme, EmojiId};
+use crate::module::championsinfos::utils::get_bracket_stats;
+use crate::utils::get_emoji;
+use mongodb::Collection;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+
+/// Number of champions shown in the tier list.
+const TIERLIST_SIZE: usize = 15;
+
+/// Popularity (as a fraction of the games) under which a champion is left out, its winrate being too noisy.
+const MIN_POPULARITY: f64 = 0.005;
+
+/// A champion of the tier list, with its statistics in the chosen bracket.
+#[derive(Debug)]
+pub struct TierlistEntry {
+    /// The Data Dragon id of the champion (e.g., `MonkeyKing`).
+    pub id_name: String,
+    pub name: String,
+    /// Winrate, as a fraction.
+    pub winrate: f64,
+    /// Popularity, as a fraction.
+    pub popularity: f64,
+}
+
+/// ⚙️ **Function**: Ranks champions by winrate in a rank bracket.
+///
+/// # Parameters:
+/// - `champions`: The champions of the latest patch, already filtered by role.
+/// - `bracket`: The rank bracket whose statistics are compared.
+///
+/// # Returns:
+/// - `Vec<TierlistEntry>`: The champions with statistics in the bracket, best winrate first.
+///
+/// # Notes:
+/// - Champions played in less than 0.5% of the games are left out, so a few lucky games do not top the list.
+pub fn rank_tierlist(champions: &[ChampionData], bracket: RankBracket) -> Vec<TierlistEntry> {
+    let mut entries: Vec<TierlistEntry> = champions
+        .iter()
+        .filter_map(|champion| {
+            let stats = get_bracket_stats(champion, bracket)?;
+            Some(TierlistEntry {
+                id_name: champion.id_name.clone(),
+                name: champion.name.clone(),
+                winrate: stats.winrate.parse().ok()?,
+                popularity: stats.popularity.parse().ok()?,
+            })
+        })
+        .filter(|entry| entry.popularity >= MIN_POPULARITY)
+        .collect();
+    entries.sort_by(|a, b| b.winrate.total_cmp(&a.winrate));
+    entries
+}
+
+/// ⚙️ **Function**: Creates the embed of a tier list.
+///
+/// # Parameters:
+/// - `entries`: The champions, as ranked by `rank_tierlist`.
+/// - `role_label`: The role of the tier list, `None` for every role.
+/// - `bracket`: The rank bracket of the statistics.
+/// - `patch`: The patch of the statistics, if known.
+/// - `theme`: The guild's embed theme.
+/// - `collection_emoji`: The MongoDB collection of the custom emojis.
+///
+/// # Returns:
+/// - `CreateEmbed`: The 15 champions with the best winrate, with their popularity.
+///
+/// # Example:
+/// ```text
+/// 🏆 Tier list — ADC (Diamond+)
+/// 1. <Jhin emoji> **Jhin** — 53.2% WR · 12.4% pick
+/// ```
+pub async fn create_embed_tierlist(
+    entries: &[TierlistEntry],
+    role_label: Option<&str>,
+    bracket: RankBracket,
+    patch: Option<&str>,
+    theme: &EmbedTheme,
+    collection_emoji: &Collection<EmojiId>,
+) -> CreateEmbed {
+    let mut lines = Vec::new();
+    for (rank, entry) in entries.iter().take(TIERLIST_SIZE).enumerate() {
+        let emoji = get_emoji(collection_emoji.clone(), "champions", &entry.id_name)
+            .await
+            .unwrap_or_default();
+        lines.push(format!(
+            "{}. {} **{}** — {:.1}% WR · {:.1}% pick",
+            rank + 1,
+            emoji,
+            entry.name,
+            entry.winrate * 100.0,
+            entry.popularity * 100.0
+        ));
+    }
+    let description = if lines.is_empty() {
+        "No statistics recorded for these filters yet.".to_string()
+    } else {
+        lines.join("\n")
+    };
+    let title = match bracket {
+        RankBracket::All => format!("🏆 Tier list — {}", role_label.unwrap_or("All roles")),
+        _ => format!(
+            "🏆 Tier list — {} ({})",
+            role_label.unwrap_or("All roles"),
+            bracket.label()
+        ),
+    };
+    let footer = match patch {
+        Some(patch) => format!("Patch {}\n{}", patch, deletion_notice()),
+        None => deletion_notice(),
+    };
+    CreateEmbed::default()
+        .title(title)
+        .description(description)
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(theme.footer_text(&footer)))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
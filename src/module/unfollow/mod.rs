@@ -0,0 +1,29 @@
+/// 🛠 **Module unfollow**: Contains the command used to stop following a summoner before their follow expires.
+///
+/// This module removes the follows created by `/followgames`, `/followuser` and `/team follow` from the
+/// `follower_summoner` collection, so the bot stops posting the games of the summoner in the guild.
+///
+/// # Files in this module:
+/// - `unfollow.rs`: The `/unfollow` command, which lets the user pick one of the summoners followed in the guild.
+/// - `unfollowall.rs`: The `/unfollowall` command, which removes every follow of the guild (server managers only).
+/// - `utils.rs`: Utility functions to list and remove the follows of a guild.
+///
+/// # Example:
+/// To use the command in this module, ensure it is registered in the bot's main framework setup:
+///
+/// ```rust
+/// use module::unfollow::unfollow::unfollow;
+///
+/// #[shuttle_runtime::main]
+/// async fn main() {
+///     let framework = poise::Framework::builder()
+///         .options(poise::FrameworkOptions {
+///             commands: vec![unfollow()], // Register the unfollow command
+///             ..Default::default()
+///         })
+///         .build();
+/// }
+/// ```
+pub mod unfollow;
+pub mod unfollowall;
+pub mod utils;
@@ -0,0 +1,57 @@
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, create_embed_sucess, schedule_message_deletion,
+};
+use crate::models::data::Data;
+use crate::models::error::Error;
+use crate::module::unfollow::utils::{autocomplete_followed_summoner, remove_follow};
+
+/// Stops following a summoner in this server before their follow expires.
+///
+/// This slash command lists the summoners followed in the guild through the autocomplete of its `summoner` option,
+/// then removes the selected one from the `follower_summoner` collection. The bot stops posting their games here.
+///
+/// # Parameters:
+/// - `ctx`: The `poise::ApplicationContext` provides the context in which the command is executed.
+/// - `summoner`: The Riot ID of the followed summoner, chosen from the autocomplete list.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an empty result if successful, or an error if the reply cannot be sent.
+///
+/// # Example:
+/// ```rust
+/// /unfollow summoner: Faker#KR1
+/// ```
+///
+/// # Notes:
+/// - Only the follow of the current server is removed: other servers following the summoner keep their follow.
+/// - The removal is recorded in the audit log (see `/auditlog`).
+#[poise::command(slash_command, guild_only)]
+pub async fn unfollow(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Summoner to stop following"]
+    #[autocomplete = "autocomplete_followed_summoner"]
+    summoner: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let reply = match remove_follow(
+        &ctx.data().mongo_client,
+        &guild_id,
+        &summoner,
+        ctx.author().id.get(),
+    )
+    .await
+    {
+        Ok(Some(follow)) => create_embed_sucess(&format!(
+            "**{}#{}** is no longer followed in this server.",
+            follow.name, follow.tag
+        )),
+        Ok(None) => create_embed_error(&format!(
+            "**{}** is not followed in this server. Use `/whoisfollowed` to see the followed summoners.",
+            summoner
+        )),
+        Err(e) => create_embed_coded_error("Error removing the follow from MongoDB", &e.into()),
+    };
+    let reply = ctx.send(reply).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::embed::{create_embed_coded_error, create_embed_sucess, schedule_message_deletion};
+use crate::models::data::Data;
+use crate::models::error::Error;
+use crate::module::unfollow::utils::remove_guild_follows;
+
+/// Stops following every summoner of this server.
+///
+/// This slash command removes every follow of the guild from the `follower_summoner` collection. It is restricted to
+/// the members who can manage the server.
+///
+/// # Parameters:
+/// - `ctx`: The `poise::ApplicationContext` provides the context in which the command is executed.
+///
+/// # Returns:
+/// - `Result<(), Error>`: Returns an empty result if successful, or an error if the reply cannot be sent.
+///
+/// # Example:
+/// ```rust
+/// /unfollowall
+/// ```
+///
+/// # Notes:
+/// - Other servers following the same summoners keep their follows.
+/// - Each removal is recorded in the audit log (see `/auditlog`).
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn unfollowall(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
+    let reply = match remove_guild_follows(
+        &ctx.data().mongo_client,
+        &guild_id,
+        ctx.author().id.get(),
+    )
+    .await
+    {
+        Ok(0) => create_embed_sucess("No summoner is followed in this server."),
+        Ok(removed) => create_embed_sucess(&format!(
+            "{} summoner{} no longer followed in this server.",
+            removed,
+            if removed > 1 { "s are" } else { " is" }
+        )),
+        Err(e) => create_embed_coded_error("Error removing the follows from MongoDB", &e.into()),
+    };
+    let reply = ctx.send(reply).await?;
+    schedule_message_deletion(reply, ctx).await?;
+    Ok(())
+}
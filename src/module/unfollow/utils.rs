@@ -0,0 +1,168 @@
+use crate::models::data::{Data, FollowAuditEvent, SummonerFollowedData};
+use crate::models::error::Error;
+use crate::module::auditlog::utils::record_follow_event;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::{Client, Collection};
+
+/// ⚙️ **Function**: Returns the `follower_summoner` collection.
+fn follows_collection(mongo_client: &Client) -> Collection<SummonerFollowedData> {
+    mongo_client
+        .database("stat-summoner")
+        .collection::<SummonerFollowedData>("follower_summoner")
+}
+
+/// ⚙️ **Function**: Lists the summoners followed in a guild.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the Discord guild.
+///
+/// # Returns:
+/// - `Result<Vec<SummonerFollowedData>, mongodb::error::Error>`: The follows of the guild, sorted by Riot ID.
+pub async fn get_guild_follows(
+    mongo_client: &Client,
+    guild_id: &str,
+) -> Result<Vec<SummonerFollowedData>, mongodb::error::Error> {
+    follows_collection(mongo_client)
+        .find(doc! { "guild_id": guild_id })
+        .sort(doc! { "name": 1, "tag": 1 })
+        .await?
+        .try_collect()
+        .await
+}
+
+/// ⚙️ **Function**: Autocompletes the Riot ID of a summoner followed in the guild.
+///
+/// # Parameters:
+/// - `ctx`: The application context of the command.
+/// - `partial`: The text typed so far.
+///
+/// # Returns:
+/// - `Vec<String>`: The Riot IDs (`Name#TAG`) of the matching follows.
+pub async fn autocomplete_followed_summoner(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    partial: &str,
+) -> Vec<String> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Vec::new();
+    };
+    let partial = partial.trim().to_lowercase();
+    get_guild_follows(&ctx.data().mongo_client, &guild_id.get().to_string())
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|follow| format!("{}#{}", follow.name, follow.tag))
+        .filter(|riot_id| riot_id.to_lowercase().contains(&partial))
+        .take(25)
+        .collect()
+}
+
+/// ⚙️ **Function**: Stops following a summoner in a guild.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the Discord guild.
+/// - `riot_id`: The Riot ID of the summoner as chosen in the autocomplete list, e.g. `Faker#KR1`. Case is ignored.
+/// - `actor_id`: The ID of the Discord user removing the follow, recorded in the audit log.
+///
+/// # Returns:
+/// - `Result<Option<SummonerFollowedData>, mongodb::error::Error>`: The removed follow, or `None` if the summoner is not
+///   followed in the guild.
+///
+/// # Example:
+/// ```rust
+/// if let Some(follow) = remove_follow(&mongo_client, &guild_id, "Faker#KR1", ctx.author().id.get()).await? {
+///     println!("{} is no longer followed.", follow.name);
+/// }
+/// ```
+///
+/// # Notes:
+/// - The follow is removed like `save_follow` inserts it, by PUUID and guild, and the removal is recorded in the
+///   audit log as `Deleted` (see `record_follow_event`).
+pub async fn remove_follow(
+    mongo_client: &Client,
+    guild_id: &str,
+    riot_id: &str,
+    actor_id: u64,
+) -> Result<Option<SummonerFollowedData>, mongodb::error::Error> {
+    let riot_id = riot_id.trim();
+    let Some(follow) = get_guild_follows(mongo_client, guild_id)
+        .await?
+        .into_iter()
+        .find(|follow| format!("{}#{}", follow.name, follow.tag).eq_ignore_ascii_case(riot_id))
+    else {
+        return Ok(None);
+    };
+    remove_followed_puuid(mongo_client, guild_id, &follow.puuid, actor_id).await
+}
+
+/// ⚙️ **Function**: Stops following a summoner in a guild, identified by their PUUID.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the Discord guild.
+/// - `puuid`: The PUUID of the followed summoner.
+/// - `actor_id`: The ID of the Discord user removing the follow, recorded in the audit log.
+///
+/// # Returns:
+/// - `Result<Option<SummonerFollowedData>, mongodb::error::Error>`: The removed follow, or `None` if the summoner is not
+///   followed in the guild anymore.
+///
+/// # Notes:
+/// - Used by `remove_follow` and by the unfollow buttons of `/whoisfollowed`; the removal is recorded in the audit
+///   log as `Deleted`.
+pub async fn remove_followed_puuid(
+    mongo_client: &Client,
+    guild_id: &str,
+    puuid: &str,
+    actor_id: u64,
+) -> Result<Option<SummonerFollowedData>, mongodb::error::Error> {
+    let Some(follow) = follows_collection(mongo_client)
+        .find_one_and_delete(doc! { "puuid": puuid, "guild_id": guild_id })
+        .await?
+    else {
+        return Ok(None);
+    };
+    record_follow_event(
+        mongo_client,
+        FollowAuditEvent::Deleted,
+        &follow,
+        Some(actor_id),
+    )
+    .await;
+    Ok(Some(follow))
+}
+
+/// ⚙️ **Function**: Stops following every summoner of a guild.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `guild_id`: The ID of the Discord guild.
+/// - `actor_id`: The ID of the Discord user removing the follows, recorded in the audit log.
+///
+/// # Returns:
+/// - `Result<u64, mongodb::error::Error>`: The number of follows removed.
+///
+/// # Notes:
+/// - Each removed follow is recorded in the audit log as `Deleted`, like with `remove_follow`.
+pub async fn remove_guild_follows(
+    mongo_client: &Client,
+    guild_id: &str,
+    actor_id: u64,
+) -> Result<u64, mongodb::error::Error> {
+    let follows = get_guild_follows(mongo_client, guild_id).await?;
+    let result = follows_collection(mongo_client)
+        .delete_many(doc! { "guild_id": guild_id })
+        .await?;
+    for follow in &follows {
+        record_follow_event(
+            mongo_client,
+            FollowAuditEvent::Deleted,
+            follow,
+            Some(actor_id),
+        )
+        .await;
+    }
+    Ok(result.deleted_count)
+}
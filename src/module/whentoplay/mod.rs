@@ -0,0 +1,17 @@
+/// 🛠 **Module whentoplay**: Contains the win rate of a player by time of day and by day of the week.
+///
+/// The games are read from the archived games of the player (their linked accounts and the games notified while they
+/// were followed), using the time each game started at.
+///
+/// # Files in this module:
+/// - `whentoplay.rs`: The `/whentoplay` command.
+/// - `utils.rs`: The win rate of each period and the embed.
+///
+/// # Example:
+/// The command is registered through `module::all_commands`:
+///
+/// ```rust
+/// whentoplay::whentoplay::whentoplay(),
+/// ```
+pub mod utils;
+pub mod whentoplay;
@@ -0,0 +1,226 @@
+use crate::config::deletion_notice;
+use crate::models::data::EmbedTheme;
+use crate::models::error::Error;
+use crate::module::grind::utils::archived_games;
+use chrono::{DateTime, Datelike, Timelike};
+use mongodb::Client;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+
+/// Minimum number of games in a period for its win rate to be compared.
+pub const MIN_PERIOD_GAMES: u32 = 5;
+
+/// Win rate after midnight under which the player gets a warning.
+const LATE_NIGHT_WARNING_WINRATE: f64 = 0.45;
+
+/// Periods of the day, with the hour they start at (included) and end at (excluded).
+const PERIODS: [(&str, u32, u32); 4] = [
+    ("🌙 Night (0h - 6h)", 0, 6),
+    ("🌅 Morning (6h - 12h)", 6, 12),
+    ("☀️ Afternoon (12h - 18h)", 12, 18),
+    ("🌆 Evening (18h - 24h)", 18, 24),
+];
+
+/// Days of the week, Monday first.
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// Games and wins of a player over a period.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Record {
+    pub games: u32,
+    pub wins: u32,
+}
+
+impl Record {
+    /// ⚙️ **Function**: Returns the win rate of the record, between 0 and 1.
+    pub fn winrate(&self) -> f64 {
+        self.wins as f64 / self.games.max(1) as f64
+    }
+}
+
+/// Records of a player by hour of the day and by day of the week.
+#[derive(Debug, Default)]
+pub struct PlayTimes {
+    pub total: Record,
+    /// Indexed by the hour the games started at, in the time zone of the player.
+    pub hours: [Record; 24],
+    /// Indexed by the day the games started on, Monday first.
+    pub weekdays: [Record; 7],
+}
+
+impl PlayTimes {
+    /// ⚙️ **Function**: Returns the record between two hours of the day (start included, end excluded).
+    pub fn period(&self, start: u32, end: u32) -> Record {
+        self.hours[start as usize..end as usize]
+            .iter()
+            .fold(Record::default(), |total, hour| Record {
+                games: total.games + hour.games,
+                wins: total.wins + hour.wins,
+            })
+    }
+}
+
+/// ⚙️ **Function**: Splits the archived games of a player by hour of the day and by day of the week.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `puuid`: The PUUID of the player.
+/// - `utc_offset`: The offset of the time zone of the player from UTC, in hours.
+///
+/// # Returns:
+/// - `Result<PlayTimes, Error>`: The records of the player, or an error if the archives cannot be read.
+///
+/// # Notes:
+/// - The games are read with `archived_games`; the ones archived before their start was kept are left out.
+pub async fn compute_play_times(
+    mongo_client: &Client,
+    puuid: &str,
+    utc_offset: i64,
+) -> Result<PlayTimes, Error> {
+    let mut play_times = PlayTimes::default();
+    for game in archived_games(mongo_client, puuid).await? {
+        if game.started_at <= 0 {
+            continue;
+        }
+        let Some(date) = DateTime::from_timestamp(game.started_at + utc_offset * 3600, 0) else {
+            continue;
+        };
+        for record in [
+            &mut play_times.total,
+            &mut play_times.hours[date.hour() as usize],
+            &mut play_times.weekdays[date.weekday().num_days_from_monday() as usize],
+        ] {
+            record.games += 1;
+            if game.win {
+                record.wins += 1;
+            }
+        }
+    }
+    Ok(play_times)
+}
+
+/// ⚙️ **Function**: Formats a record, e.g. `12W - 8L (60%)`.
+fn format_record(record: &Record) -> String {
+    if record.games == 0 {
+        return "No game".to_string();
+    }
+    format!(
+        "{}W - {}L ({:.0}%)",
+        record.wins,
+        record.games - record.wins,
+        record.winrate() * 100.0
+    )
+}
+
+/// ⚙️ **Function**: Creates the embed of the win rate of a player by time of day and by day of the week.
+///
+/// # Parameters:
+/// - `label`: The label of the account, e.g. `Faker#KR1 (KR)`.
+/// - `play_times`: The records built by `compute_play_times`.
+/// - `utc_offset`: The offset of the time zone of the player from UTC, in hours.
+/// - `theme`: The embed theme of the guild.
+///
+/// # Returns:
+/// - `CreateEmbed`: The records of each period of the day and each day of the week, the best hour and day, and a
+///   warning when the player loses most of their games after midnight.
+///
+/// # Example:
+/// ```text
+/// 🕒 When should Faker#KR1 (KR) play? (UTC+2)
+/// Best time: 20h - 21h, 9W - 3L (75%)
+/// Best day: Saturday, 14W - 6L (70%)
+///
+/// 🌙 Your win rate after midnight is 31%. Maybe sleep on that next game?
+/// ```
+pub fn create_embed_whentoplay(
+    label: &str,
+    play_times: &PlayTimes,
+    utc_offset: i64,
+    theme: &EmbedTheme,
+) -> CreateEmbed {
+    let timezone = match utc_offset {
+        0 => "UTC".to_string(),
+        offset => format!("UTC{:+}", offset),
+    };
+    let best_hour = (0..24)
+        .filter(|hour| play_times.hours[*hour].games >= MIN_PERIOD_GAMES)
+        .max_by(|a, b| {
+            play_times.hours[*a]
+                .winrate()
+                .total_cmp(&play_times.hours[*b].winrate())
+        });
+    let best_day = (0..7)
+        .filter(|day| play_times.weekdays[*day].games >= MIN_PERIOD_GAMES)
+        .max_by(|a, b| {
+            play_times.weekdays[*a]
+                .winrate()
+                .total_cmp(&play_times.weekdays[*b].winrate())
+        });
+
+    let mut description = format!(
+        "Over **{}** archived games: {}\n",
+        play_times.total.games,
+        format_record(&play_times.total)
+    );
+    match best_hour {
+        Some(hour) => description.push_str(&format!(
+            "Best time: **{}h - {}h**, {}\n",
+            hour,
+            (hour + 1) % 24,
+            format_record(&play_times.hours[hour])
+        )),
+        None => description.push_str(&format!(
+            "Best time: not enough games yet ({} per hour needed)\n",
+            MIN_PERIOD_GAMES
+        )),
+    }
+    if let Some(day) = best_day {
+        description.push_str(&format!(
+            "Best day: **{}**, {}\n",
+            WEEKDAYS[day],
+            format_record(&play_times.weekdays[day])
+        ));
+    }
+    let night = play_times.period(0, 6);
+    if night.games >= MIN_PERIOD_GAMES && night.winrate() < LATE_NIGHT_WARNING_WINRATE {
+        description.push_str(&format!(
+            "\n🌙 Your win rate after midnight is **{:.0}%**. Maybe sleep on that next game?",
+            night.winrate() * 100.0
+        ));
+    }
+
+    let periods = PERIODS
+        .iter()
+        .map(|(name, start, end)| {
+            format!(
+                "{}: {}",
+                name,
+                format_record(&play_times.period(*start, *end))
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let weekdays = WEEKDAYS
+        .iter()
+        .zip(play_times.weekdays.iter())
+        .map(|(day, record)| format!("{}: {}", day, format_record(record)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    CreateEmbed::default()
+        .title(format!("🕒 When should {} play? ({})", label, timezone))
+        .description(description)
+        .field("Time of day", periods, true)
+        .field("Day of the week", weekdays, true)
+        .color(theme.info())
+        .footer(CreateEmbedFooter::new(
+            theme.footer_text(&deletion_notice()),
+        ))
+        .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png")
+}
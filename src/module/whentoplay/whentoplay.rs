@@ -0,0 +1,77 @@
+use crate::embed::{
+    create_embed_coded_error, create_embed_error, edit_and_schedule_deletion, send_fetching_message,
+};
+use crate::models::data::Data;
+use crate::models::error::Error;
+use crate::module::grind::utils::{missing_account_message, select_linked_account};
+use crate::module::myaccounts::utils::{account_label, autocomplete_linked_account};
+use crate::module::settings::utils::get_guild_theme;
+use crate::module::whentoplay::utils::{compute_play_times, create_embed_whentoplay};
+use poise::CreateReply;
+
+/// Shows when you win the most: your win rate by time of day and by day of the week.
+///
+/// # Parameters:
+/// - `account`: The linked account to look up, chosen from the autocomplete list. Defaults to the primary account.
+/// - `utc_offset`: The offset of your time zone from UTC, in hours. Defaults to UTC.
+///
+/// # Example:
+/// ```rust
+/// /whentoplay
+/// /whentoplay account: Faker#KR1 (KR) utc_offset: 9
+/// ```
+///
+/// # Notes:
+/// - Reads the games archived by the bot, so games played before linking or following the account are not counted.
+#[poise::command(slash_command)]
+pub async fn whentoplay(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Linked account (defaults to your primary one)"]
+    #[autocomplete = "autocomplete_linked_account"]
+    account: Option<String>,
+    #[description = "Offset of your time zone from UTC in hours (0 by default)"]
+    #[min = -12]
+    #[max = 14]
+    utc_offset: Option<i64>,
+) -> Result<(), Error> {
+    let fetching = send_fetching_message(ctx).await?;
+    let mongo_client = &ctx.data().mongo_client;
+    let selected = match select_linked_account(
+        mongo_client,
+        ctx.author().id.get(),
+        account.as_deref(),
+    )
+    .await
+    {
+        Ok(Some(selected)) => selected,
+        Ok(None) => {
+            let error_message = missing_account_message(account.as_deref());
+            edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+            return Ok(());
+        }
+        Err(e) => {
+            let reply = create_embed_coded_error("Error collecting informations from MongoDB", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+
+    let utc_offset = utc_offset.unwrap_or(0);
+    let play_times = match compute_play_times(mongo_client, &selected.puuid, utc_offset).await {
+        Ok(play_times) => play_times,
+        Err(e) => {
+            let reply = create_embed_coded_error("Error reading the archived games", &e);
+            edit_and_schedule_deletion(fetching, ctx, reply).await?;
+            return Ok(());
+        }
+    };
+    if play_times.total.games == 0 {
+        let error_message = "No archived game of this account yet: games are archived every hour once your account is linked.";
+        edit_and_schedule_deletion(fetching, ctx, create_embed_error(error_message)).await?;
+        return Ok(());
+    }
+    let theme = get_guild_theme(ctx).await;
+    let embed = create_embed_whentoplay(&account_label(&selected), &play_times, utc_offset, &theme);
+    edit_and_schedule_deletion(fetching, ctx, CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
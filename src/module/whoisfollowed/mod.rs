@@ -1,8 +1,8 @@
 pub mod utils;
-/// 🛠 **Module commands**: Contains all bot commands for the Discord bot.
+/// 🛠 **Module whoisfollowed**: Contains all bot commands for the Discord bot.
 ///
 /// This module organizes the different commands used by the bot. Each command is stored in its own file
-/// within the `commands` directory. These commands are registered and used through the bot's interaction
+/// within the `module/whoisfollowed` directory. These commands are registered and used through the bot's interaction
 /// with Discord via the Poise framework.
 ///
 /// # Files in this module:
@@ -12,7 +12,7 @@ pub mod utils;
 /// To use commands in this module, ensure they are registered in the bot's main framework setup:
 ///
 /// ```rust
-/// use commands::whoisfollowed::whoisfollowed;
+/// use module::whoisfollowed::whoisfollowed::whoisfollowed;
 ///
 /// #[shuttle_runtime::main]
 /// async fn main() {
@@ -26,5 +26,5 @@ pub mod utils;
 /// ```
 /// A new command `whoisfollowed` allows users to retrieve a list of summoners currently being followed in the guild, along with the remaining follow time.
 ///
-/// As more commands are added, they will be included here and imported into the main bot setup.
+/// As more commands are added, they will be included here and added to `module::all_commands`.
 pub mod whoisfollowed;
@@ -1,20 +1,64 @@
-use crate::models::data::SummonerFollowedData;
+use crate::components::{
+    component_id, respond_ephemeral, save_component_state, save_pages, PageRows,
+};
+use crate::config::{config, deletion_notice};
+use crate::models::data::{Data, EmbedTheme, SummonerFollowedData};
 use crate::models::error::Error;
-use chrono::{Duration, Utc};
+use crate::module::followgames::utils::{extend_follow, PERMANENT_FOLLOW_END};
+use crate::module::settings::utils::get_guild_settings;
+use crate::module::unfollow::utils::remove_followed_puuid;
+use crate::utils::discord_timestamp;
+use chrono::Utc;
+use futures::future::BoxFuture;
 use futures::StreamExt;
 use mongodb::bson::doc;
-use mongodb::Collection;
-use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
-use poise::CreateReply;
+use mongodb::{Client, Collection};
+use poise::serenity_prelude::{self as serenity, CreateEmbed, CreateEmbedFooter};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use serde_json::Value;
 
+/// Number of followed summoners listed on each page of `/whoisfollowed`.
+const FOLLOWS_PER_PAGE: usize = 10;
+
+/// Hours added to a follow by the extend buttons of `/whoisfollowed`.
+const EXTEND_FOLLOW_HOURS: [i64; 2] = [6, 24];
+
+/// Kind of the select menu and buttons unfollowing or extending the summoners of the `/whoisfollowed` list.
+pub const FOLLOW_LIST_COMPONENT_KIND: &str = "follows";
+
+/// Name of the builder of the select menu and buttons shown under each page of the `/whoisfollowed` list.
+pub const FOLLOW_LIST_ROWS: &str = "follow-list";
+
+/// `/whoisfollowed` list displayed in a message, as stored in its component state.
+#[derive(Serialize, Deserialize)]
+struct FollowListState {
+    guild_id: String,
+    /// Key of the pages of the message (see `save_pages`).
+    pages_key: String,
+    /// Only member allowed to browse the pages.
+    author_id: u64,
+    /// PUUIDs of the summoners of the list, in the order of the select menu.
+    puuids: Vec<String>,
+}
+
+/// Context of the select menu and buttons of the `/whoisfollowed` list, given to `follow_list_rows`.
+#[derive(Serialize, Deserialize)]
+struct FollowListRows {
+    /// Key of the `FollowListState` of the message.
+    key: String,
+    /// Name of each summoner of the list and whether they are followed permanently.
+    summoners: Vec<(String, bool)>,
+    /// Index of the summoner picked in the select menu.
+    selected: Option<usize>,
+}
+
 /// ⚙️ **Function**: Fetches the list of summoners followed in a specific Discord guild.
 ///
 /// This asynchronous function retrieves data about summoners followed within a particular Discord guild.
 /// It queries the provided MongoDB collection for records matching the specified `guild_id` and
-/// returns a list of summoners, along with the remaining follow duration for each.
-/// If the follow has ended, it will return "Follow ended" for that summoner.
+/// returns a list of summoners, along with the end of the follow for each.
+/// Follows that have already ended are left out of the list.
 ///
 /// # Parameters:
 /// - `collection`: The MongoDB collection containing follow data, where each document represents a summoner being followed.
@@ -23,14 +67,17 @@ use serde_json::Value;
 ///
 /// # Returns:
 /// - `Result<Value, Error>`: On success, it returns a `serde_json::Value` object containing a list of tracked summoners,
-///   each with their `name` and `time_remaining` (formatted as a human-readable string or "Follow ended" if the follow has expired).
+///   each with their `puuid`, `name`, `permanent` and `follow_end` (the end of the follow with Discord's timestamp markup).
 ///   In case of an error, it returns an `Error` object.
 ///
 /// # ⚠️ Notes:
-/// - The function calculates the remaining follow duration by comparing the current timestamp with the `time_end_follow`
-///   value from each summoner's record.
-/// - If a summoner's follow has expired, the time remaining is returned as "Follow ended".
-/// - The duration is formatted as a readable string for convenience.
+/// - Expired follows are skipped: they stay in the database until the next pass of the follow loop removes them, but
+///   they are not followed anymore.
+/// - Permanent follows (see `PERMANENT_FOLLOW_END`) end "never (permanent follow)".
+/// - The end of the follow is shown as an absolute date and a countdown with Discord's timestamp markup (see
+///   `discord_timestamp`), e.g. `<t:1625000000:f> (<t:1625000000:R>)`. Discord shows it in the timezone of each user and
+///   keeps the countdown up to date, so it does not go stale while the list is displayed.
+/// - The summoners are sorted by name, so the pages of `/whoisfollowed` keep the same order.
 ///
 /// # Example:
 /// ```rust
@@ -44,11 +91,11 @@ use serde_json::Value;
 /// ///   "tracked_summoners": [
 /// ///     {
 /// ///       "name": "Summoner1",
-/// ///       "time_remaining": "2 hours 15 minutes"
+/// ///       "follow_end": "<t:1625000000:f> (<t:1625000000:R>)"
 /// ///     },
 /// ///     {
 /// ///       "name": "Summoner2",
-/// ///       "time_remaining": "Follow ended"
+/// ///       "follow_end": "never (permanent follow)"
 /// ///     }
 /// ///   ]
 /// /// }
@@ -58,23 +105,33 @@ pub async fn get_data_followed_summoner(
     guild_id: String,
 ) -> Result<Value, Error> {
     let current_timestamp = Utc::now().timestamp();
-    let mut cursor = collection.find(doc! { "guild_id": guild_id }).await?;
+    let mut cursor = collection
+        .find(doc! { "guild_id": guild_id })
+        .sort(doc! { "name": 1, "tag": 1 })
+        .await?;
     let mut summoners = Vec::new();
     while let Some(followed_data) = cursor.next().await {
         if let Ok(data) = followed_data {
             let name = &data.name;
-            let time_end_follow = data.time_end_follow.parse::<i64>().unwrap();
-
-            let remaining_duration = time_end_follow - current_timestamp;
-            let time_remaining_str = if remaining_duration > 0 {
-                let duration = Duration::seconds(remaining_duration);
-                format_duration(duration)
+            let time_end_follow = data.time_end_follow.parse::<i64>().unwrap_or(0);
+            // Les suivis expirés restent en base jusqu'au prochain passage de la boucle
+            if time_end_follow <= current_timestamp {
+                continue;
+            }
+            let follow_end = if time_end_follow == PERMANENT_FOLLOW_END {
+                "never (permanent follow)".to_string()
             } else {
-                "Follow ended".to_string()
+                format!(
+                    "{} ({})",
+                    discord_timestamp(time_end_follow, 'f'),
+                    discord_timestamp(time_end_follow, 'R')
+                )
             };
             let summoner = json!({
+                "puuid": data.puuid,
                 "name": name,
-                "time_remaining": time_remaining_str
+                "permanent": time_end_follow == PERMANENT_FOLLOW_END,
+                "follow_end": follow_end
             });
             summoners.push(summoner);
         }
@@ -82,91 +139,27 @@ pub async fn get_data_followed_summoner(
     Ok(json!({ "tracked_summoners": summoners }))
 }
 
-/// ⚙️ **Function**: Formats a `Duration` into a human-readable string.
-///
-/// This function takes a `Duration` and returns a string representing the remaining time in a human-readable format.
-/// The function distinguishes between days, hours, and minutes, with specific rules for singular and plural terms.
-/// If the remaining time is less than a minute, it returns "less than a minute".
-///
-/// # Parameters:
-/// - `duration`: A `Duration` object representing the time span to format. The function will extract the number of
-///   days, hours, and minutes from this duration to create a user-friendly time description.
-///
-/// # Returns:
-/// - `String`: A human-readable string indicating how much time is left, formatted as:
-/// - "in 1 day", "in 1 day and X hours", "in X hours", "in X minutes", or "less than a minute".
-///   The string changes based on the length of the duration.
-///
-/// # ⚠️ Notes:
-/// - If the duration is greater than a day, the function formats the result as "in X days and Y hours",
-///   or "in X days" if there are no remaining hours.
-/// - If the duration is less than a day but more than an hour, the result is formatted as "in X hours".
-/// - For durations less than an hour but more than a minute, it returns "in X minutes".
-/// - If the duration is less than a minute, the function returns "less than a minute".
-///
-/// # Example:
-/// ```rust
-/// let duration = Duration::hours(5);
-/// let formatted = format_duration(duration);
-/// assert_eq!(formatted, "in 5 hours");
-///
-/// let short_duration = Duration::minutes(1);
-/// let formatted_short = format_duration(short_duration);
-/// assert_eq!(formatted_short, "in 1 minute");
-/// ```
-///
-/// The function will return the appropriate formatted string based on the duration passed in.
-
-fn format_duration(duration: Duration) -> String {
-    let days = duration.num_days();
-    let hours = duration.num_hours() % 24;
-    let minutes = duration.num_minutes() % 60;
-
-    if days > 0 {
-        if hours > 0 {
-            if hours == 1 {
-                return format!("in 1 day and 1 hour");
-            } else {
-                return format!("in 1 day and {} hours", hours);
-            }
-        } else {
-            return format!("in 1 day");
-        }
-    } else if hours > 0 {
-        if hours == 1 {
-            return format!("in 1 hour");
-        } else {
-            return format!("in {} hours", hours);
-        }
-    } else if minutes > 0 {
-        if minutes == 1 {
-            return format!("in 1 minute");
-        } else {
-            return format!("in {} minutes", minutes);
-        }
-    } else {
-        return "less than a minute".to_string();
-    }
-}
-
-/// ⚙️ **Function**: Creates an embed displaying the list of followed summoners.
+/// ⚙️ **Function**: Creates the pages of the embed displaying the list of followed summoners.
 ///
-/// This function constructs a Discord embed message that lists all summoners being followed in a guild.
-/// It includes the remaining time for each summoner's follow or a message if no summoners are currently being tracked.
-/// The embed has a default purple color and includes a footer stating that the message will be deleted after 60 seconds.
+/// This function constructs the pages of a Discord embed that lists all summoners being followed in a guild.
+/// It includes the end of each summoner's follow or a message if no summoners are currently being tracked.
+/// The embed uses the info color of the guild's theme and includes a footer stating that the message will be deleted after the configured delay.
 ///
 /// # Parameters:
 /// - `data`: A `serde_json::Value` object containing the list of tracked summoners.
-///   The `data` is expected to have a `tracked_summoners` field, which is an array of objects with each summoner's name and follow duration.
+///   The `data` is expected to have a `tracked_summoners` field, which is an array of objects with each summoner's name and follow end.
+/// - `theme`: The guild's embed theme, providing the embed color and the brand line prepended to the footer.
 ///
 /// # Returns:
-/// - `CreateReply`: A Discord reply object containing the constructed embed. This can be sent to a Discord channel.
-///   The embed includes fields with each summoner's name and the remaining follow time, or a message stating that no summoners are currently being followed.
+/// - `Vec<CreateEmbed>`: The pages of the embed, with `FOLLOWS_PER_PAGE` summoners each, to be sent with `send_paginated_embed`.
+///   Each page includes fields with each summoner's name and the end of their follow, or a message stating that no summoners are currently being followed.
 ///
 /// # ⚠️ Notes:
 /// - If no summoners are found in the `tracked_summoners` array, the embed will display "No summoners are currently being followed".
-/// - The embed's color is set to purple (`0xA020F0`), and a footer is included indicating that the message will be deleted after 60 seconds.
-/// - Each summoner's follow information is displayed in the format: `Follow ends in: X time`.
+/// - The embed's color is the info color of the theme (`theme.info()`), and a footer is included indicating that the message will be deleted after the configured delay.
+/// - Each summoner's follow information is displayed in the format: `Follow ends: <date> (<countdown>)`.
+/// - Discord limits an embed to 25 fields, so large follow lists are split into pages; the title of each page shows
+///   the total number of followed summoners.
 ///
 /// # Example:
 /// ```rust
@@ -174,50 +167,352 @@ fn format_duration(duration: Duration) -> String {
 ///     "tracked_summoners": [
 ///         {
 ///             "name": "Summoner1",
-///             "time_remaining": "2 hours 15 minutes"
+///             "follow_end": "<t:1625000000:f> (<t:1625000000:R>)"
 ///         },
 ///         {
 ///             "name": "Summoner2",
-///             "time_remaining": "Follow ended"
+///             "follow_end": "never (permanent follow)"
 ///         }
 ///     ]
 /// });
-/// let embed_reply = create_embed_followed_summoner(data);
-/// ctx.send(embed_reply).await?;
+/// let pages = create_embed_followed_summoner(data, &theme);
+/// send_paginated_embed(ctx, pages, None).await?;
 /// ```
 ///
-/// This example would produce an embed listing two summoners, with their remaining follow durations.
-pub fn create_embed_followed_summoner(data: Value) -> CreateReply {
+/// This example would produce a single page listing two summoners, with the end of their follows.
+pub fn create_embed_followed_summoner(data: Value, theme: &EmbedTheme) -> Vec<CreateEmbed> {
     let binding = vec![];
     let tracked_summoners = data["tracked_summoners"].as_array().unwrap_or(&binding);
-    let mut embed = CreateEmbed::new()
-        .title("Tracked Summoners")
-        .color(0xA020F0)
+    let embed = CreateEmbed::new()
+        .color(theme.info())
         .footer(CreateEmbedFooter::new(
-            "This message will be deleted in 60 seconds.",
+            theme.footer_text(&deletion_notice()),
         ))
         .thumbnail("https://i.postimg.cc/9fKf2tYp/Logo.png");
 
     if tracked_summoners.is_empty() {
-        embed = embed.field(
+        return vec![embed.title("Tracked Summoners").field(
             "",
             "No summoners are currently being followed".to_string(),
             false,
-        );
-        return CreateReply {
-            embeds: vec![embed],
-            ..Default::default()
-        };
+        )];
     }
-    for summoner in tracked_summoners {
-        let name = summoner["name"].as_str().unwrap_or("Unknown");
-        let time_remaining = summoner["time_remaining"].as_str().unwrap_or("Unknown");
+    let title = format!("Tracked Summoners ({})", tracked_summoners.len());
+    tracked_summoners
+        .chunks(FOLLOWS_PER_PAGE)
+        .map(|page| {
+            let mut embed = embed.clone().title(&title);
+            for summoner in page {
+                let name = summoner["name"].as_str().unwrap_or("Unknown");
+                let follow_end = summoner["follow_end"].as_str().unwrap_or("Unknown");
 
-        embed = embed.field(name, format!("Follow ends in: {}", time_remaining), false);
+                embed = embed.field(name, format!("Follow ends: {}", follow_end), false);
+            }
+            embed
+        })
+        .collect()
+}
+
+/// ⚙️ **Function**: Builds the pages of the `/whoisfollowed` list with the select menu and buttons shown under them,
+/// and saves the list for the select menu and buttons.
+///
+/// # Parameters:
+/// - `mongo_client`: A reference to the MongoDB `Client`.
+/// - `key`: A key unique to the message, e.g. built from the ID of the interaction.
+/// - `guild_id`: The ID of the Discord guild.
+/// - `pages_key`: The key of the pages of the message (see `send_paginated_embed`).
+/// - `author_id`: The member who ran the command, the only one allowed to browse the pages.
+/// - `selected`: The PUUID of the summoner picked in the select menu, if any.
+///
+/// # Returns:
+/// - `Result<(Vec<CreateEmbed>, Option<PageRows>), Error>`: The pages of the list, and the components shown under each
+///   page (built by `follow_list_rows`), `None` if no summoner is followed.
+///
+/// # Example:
+/// ```rust
+/// let (pages, rows) = prepare_follow_list(
+///     &mongo_client,
+///     &format!("follows-{}", ctx.id()),
+///     &guild_id,
+///     &format!("pages-{}", ctx.id()),
+///     ctx.author().id.get(),
+///     None,
+/// )
+/// .await?;
+/// send_paginated_embed(ctx, pages, rows).await?;
+/// ```
+///
+/// # Notes:
+/// - The list is read from MongoDB each time, so the list displayed after an unfollow is up to date.
+/// - The select menu and buttons are answered by `handle_follow_list_component`, even after the bot restarts.
+pub async fn prepare_follow_list(
+    mongo_client: &Client,
+    key: &str,
+    guild_id: &str,
+    pages_key: &str,
+    author_id: u64,
+    selected: Option<&str>,
+) -> Result<(Vec<CreateEmbed>, Option<PageRows>), Error> {
+    let collection = mongo_client
+        .database("stat-summoner")
+        .collection::<SummonerFollowedData>("follower_summoner");
+    let data = get_data_followed_summoner(collection, guild_id.to_string()).await?;
+    let theme = match get_guild_settings(mongo_client, guild_id).await {
+        Ok(settings) => settings.theme,
+        Err(e) => {
+            log::error!("Failed to read the settings of guild {}: {}", guild_id, e);
+            EmbedTheme::default()
+        }
+    };
+    let summoners: Vec<Value> = data["tracked_summoners"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let pages = create_embed_followed_summoner(data, &theme);
+    if summoners.is_empty() {
+        return Ok((pages, None));
     }
+    let state = FollowListState {
+        guild_id: guild_id.to_string(),
+        pages_key: pages_key.to_string(),
+        author_id,
+        puuids: summoners
+            .iter()
+            .map(|summoner| summoner["puuid"].as_str().unwrap_or("").to_string())
+            .collect(),
+    };
+    save_component_state(mongo_client, key, FOLLOW_LIST_COMPONENT_KIND, None, &state).await?;
+    let rows = FollowListRows {
+        key: key.to_string(),
+        summoners: summoners
+            .iter()
+            .map(|summoner| {
+                (
+                    summoner["name"].as_str().unwrap_or("Unknown").to_string(),
+                    summoner["permanent"].as_bool().unwrap_or(false),
+                )
+            })
+            .collect(),
+        // Le joueur choisi est retrouvé par PUUID : la liste a pu changer depuis l'affichage
+        selected: selected.and_then(|puuid| state.puuids.iter().position(|p| p == puuid)),
+    };
+    Ok((
+        pages,
+        Some(PageRows {
+            builder: FOLLOW_LIST_ROWS,
+            payload: serde_json::to_string(&rows)?,
+        }),
+    ))
+}
+
+/// ⚙️ **Function**: Builds the select menu of the summoners of a page of the `/whoisfollowed` list, and the buttons
+/// unfollowing or extending the summoner picked.
+///
+/// # Parameters:
+/// - `payload`: The `FollowListRows` of the list, as JSON.
+/// - `page`: The index of the page displayed.
+///
+/// # Returns:
+/// - `Result<Vec<serenity::CreateActionRow>, Error>`: The select menu, then a row with the unfollow and extend buttons,
+///   disabled until a summoner of the page is picked.
+///
+/// # Notes:
+/// - Registered under `FOLLOW_LIST_ROWS` (see `module::register_components`), so the rows are built again when the
+///   page changes.
+pub fn follow_list_rows(
+    payload: &str,
+    page: usize,
+) -> Result<Vec<serenity::CreateActionRow>, Error> {
+    let rows: FollowListRows = serde_json::from_str(payload)?;
+    let first = page * FOLLOWS_PER_PAGE;
+    let options = rows
+        .summoners
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(FOLLOWS_PER_PAGE)
+        .map(|(index, (name, _))| {
+            serenity::CreateSelectMenuOption::new(name, index.to_string())
+                .default_selection(rows.selected == Some(index))
+        })
+        .collect();
+    let select_menu = serenity::CreateSelectMenu::new(
+        component_id(&rows.key, "select"),
+        serenity::CreateSelectMenuKind::String { options },
+    )
+    .placeholder("Pick a summoner to unfollow or extend");
 
-    CreateReply {
-        embeds: vec![embed],
-        ..Default::default()
+    // Les boutons ne s'activent que pour un joueur de la page affichée
+    let selected = rows
+        .selected
+        .filter(|index| (first..first + FOLLOWS_PER_PAGE).contains(index));
+    let mut buttons = vec![serenity::CreateButton::new(component_id(
+        &rows.key,
+        &format!("unfollow-{}", selected.unwrap_or(0)),
+    ))
+    .label("Unfollow")
+    .emoji('✖')
+    .style(serenity::ButtonStyle::Danger)
+    .disabled(selected.is_none())];
+    // Les suivis permanents n'ont pas de fin à repousser
+    let permanent = selected
+        .and_then(|index| rows.summoners.get(index))
+        .is_none_or(|(_, permanent)| *permanent);
+    for hours in EXTEND_FOLLOW_HOURS {
+        buttons.push(
+            serenity::CreateButton::new(component_id(
+                &rows.key,
+                &format!("extend{}-{}", hours, selected.unwrap_or(0)),
+            ))
+            .label(format!("+{}h", hours))
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(permanent),
+        );
     }
+    Ok(vec![
+        serenity::CreateActionRow::SelectMenu(select_menu),
+        serenity::CreateActionRow::Buttons(buttons),
+    ])
+}
+
+/// ⚙️ **Function**: Answers the select menu and the unfollow and extend buttons of the `/whoisfollowed` list.
+///
+/// # Parameters:
+/// - `ctx`: The serenity context.
+/// - `data`: The data of the bot.
+/// - `press`: The component interaction.
+/// - `key`: The key of the component state of the message.
+/// - `action`: `select` to pick a summoner, `unfollow-<index>` to unfollow the summoner picked, or
+///   `extend<hours>-<index>` to extend their follow.
+/// - `payload`: The `FollowListState` saved by `prepare_follow_list`.
+///
+/// # Returns:
+/// - `BoxFuture<'a, Result<(), Error>>`: An error if the follow cannot be changed or the message cannot be updated.
+///
+/// # Notes:
+/// - Registered under `FOLLOW_LIST_COMPONENT_KIND` (see `module::register_components`), so the list can still be
+///   changed after a restart.
+/// - Anyone can extend a follow (see `extend_follow`), like anyone can follow a summoner with `/followgames`, but only
+///   the members who can manage the server can unfollow a summoner. The changes are recorded in the audit log.
+/// - When an extension is capped to `MAX_FOLLOW_HOURS` from now, the member gets an ephemeral message with the end of
+///   the follow.
+/// - The message is updated with the page of the summoner picked once the follow is changed.
+pub fn handle_follow_list_component<'a>(
+    ctx: &'a serenity::Context,
+    data: &'a Data,
+    press: &'a serenity::ComponentInteraction,
+    key: &'a str,
+    action: &'a str,
+    payload: &'a str,
+) -> BoxFuture<'a, Result<(), Error>> {
+    Box::pin(async move {
+        let state: FollowListState = serde_json::from_str(payload)?;
+        // Le menu porte l'index dans la valeur choisie, les boutons dans leur identifiant
+        let (action, index) = match &press.data.kind {
+            serenity::ComponentInteractionDataKind::StringSelect { values } => {
+                ("select", values.first().cloned().unwrap_or_default())
+            }
+            _ => match action.split_once('-') {
+                Some((action, index)) => (action, index.to_string()),
+                None => return Ok(()),
+            },
+        };
+        let Some((index, puuid)) = index
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| Some((index, state.puuids.get(index)?)))
+        else {
+            return Ok(());
+        };
+        let mut capped_end = None;
+        let selected = if action == "unfollow" {
+            let can_manage = press
+                .member
+                .as_ref()
+                .and_then(|member| member.permissions)
+                .is_some_and(|permissions| permissions.manage_guild());
+            if !can_manage {
+                return respond_ephemeral(
+                    ctx,
+                    press,
+                    "Only the members who can manage the server can unfollow a summoner.",
+                )
+                .await;
+            }
+            remove_followed_puuid(
+                &data.mongo_client,
+                &state.guild_id,
+                puuid,
+                press.user.id.get(),
+            )
+            .await?;
+            None
+        } else if let Some(hours) = action.strip_prefix("extend") {
+            let Some(hours) = hours
+                .parse::<i64>()
+                .ok()
+                .filter(|hours| EXTEND_FOLLOW_HOURS.contains(hours))
+            else {
+                return Ok(());
+            };
+            let extension = extend_follow(
+                &data.mongo_client,
+                &state.guild_id,
+                puuid,
+                hours,
+                press.user.id.get(),
+            )
+            .await?;
+            if let Some((end, true)) = extension {
+                capped_end = Some(end);
+            }
+            Some(puuid.as_str())
+        } else {
+            Some(puuid.as_str())
+        };
+        let (pages, rows) = prepare_follow_list(
+            &data.mongo_client,
+            key,
+            &state.guild_id,
+            &state.pages_key,
+            state.author_id,
+            selected,
+        )
+        .await?;
+        let page = (index / FOLLOWS_PER_PAGE).min(pages.len() - 1);
+        let components = save_pages(
+            &data.mongo_client,
+            &state.pages_key,
+            state.author_id,
+            &pages,
+            page,
+            rows.as_ref(),
+        )
+        .await?;
+        press
+            .create_response(
+                ctx,
+                serenity::CreateInteractionResponse::UpdateMessage(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .embed(pages[page].clone())
+                        .components(components),
+                ),
+            )
+            .await?;
+        if let Some(end) = capped_end {
+            press
+                .create_followup(
+                    ctx,
+                    serenity::CreateInteractionResponseFollowup::new()
+                        .content(format!(
+                            "A follow cannot last more than {} hours from now: it now ends {}.",
+                            config().max_follow_hours,
+                            discord_timestamp(end, 'R')
+                        ))
+                        .ephemeral(true),
+                )
+                .await?;
+        }
+        Ok(())
+    })
 }
@@ -1,15 +1,12 @@
-use crate::embed::schedule_message_deletion;
-use crate::models::data::{Data, SummonerFollowedData};
+use crate::embed::send_paginated_embed;
+use crate::models::data::Data;
 use crate::models::error::Error;
-use crate::module::whoisfollowed::utils::{
-    create_embed_followed_summoner, get_data_followed_summoner,
-};
-use mongodb::bson::doc;
+use crate::module::whoisfollowed::utils::prepare_follow_list;
 
 /// Retrieves and displays the list of summoners followed in the current Discord guild.
 ///
 /// This slash command fetches the summoners being followed within the Discord guild where the command is invoked.
-/// It queries the MongoDB collection for follow data and creates an embed message that lists all tracked summoners, along with the time remaining for each follow.
+/// It queries the MongoDB collection for follow data and creates an embed message that lists all tracked summoners, along with the end of each follow.
 /// The message is set to automatically delete after 60 seconds.
 ///
 /// # Parameters:
@@ -21,8 +18,9 @@ use mongodb::bson::doc;
 ///
 /// # ⚠️ Notes:
 /// - The function retrieves the guild's ID and queries the `follower_summoner` collection for summoners being tracked in that guild.
-/// - It uses the `get_data_followed_summoner` function to gather the data and the `create_embed_followed_summoner` function to construct the embed message.
-/// - The message is automatically deleted after 60 seconds using the `schedule_message_deletion` function.
+/// - It uses the `prepare_follow_list` function to gather the data and construct the pages of the embed, with the select menu shown under them.
+/// - Large follow lists are browsed with previous/next buttons (see `send_paginated_embed`), and the summoner picked in the select menu can be unfollowed by the members who can manage the server, or extended (see `handle_follow_list_component`).
+/// - The message is automatically deleted after 60 seconds.
 /// - The command can only be used in a Discord server (guild), not in direct messages.
 ///
 /// # Example:
@@ -32,33 +30,37 @@ use mongodb::bson::doc;
 ///     ctx: poise::ApplicationContext<'_, Data, Error>,
 /// ) -> Result<(), Error> {
 ///     let mongo_client = &ctx.data().mongo_client;
-///     let collection = mongo_client
-///         .database("stat-summoner")
-///         .collection::<SummonerFollowedData>("follower_summoner");
-///
 ///     let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
 ///
-///     let followed_data = get_data_followed_summoner(collection, guild_id).await?;
+///     let (pages, rows) = prepare_follow_list(
+///         mongo_client,
+///         &format!("follows-{}", ctx.id()),
+///         &guild_id,
+///         &format!("pages-{}", ctx.id()),
+///         ctx.author().id.get(),
+///         None,
+///     )
+///     .await?;
 ///
-///     let reply = ctx.send(create_embed_followed_summoner(followed_data)).await?;
-///     schedule_message_deletion(reply, ctx).await?;
+///     send_paginated_embed(ctx, pages, rows).await?;
 ///     return Ok(());
 /// }
 /// ```
 ///
-/// This command will create an embed showing all followed summoners in the guild where the command is run, along with their remaining follow time.
+/// This command will create an embed showing all followed summoners in the guild where the command is run, along with the end of their follow.
 #[poise::command(slash_command)]
 pub async fn whoisfollowed(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
     let mongo_client = &ctx.data().mongo_client;
-    let collection = mongo_client
-        .database("stat-summoner")
-        .collection::<SummonerFollowedData>("follower_summoner");
-
     let guild_id = ctx.guild_id().map(|id| id.get()).unwrap_or(0).to_string();
-    let followed_data = get_data_followed_summoner(collection, guild_id).await?;
-    let reply = ctx
-        .send(create_embed_followed_summoner(followed_data))
-        .await?;
-    schedule_message_deletion(reply, ctx).await?;
+    let (pages, rows) = prepare_follow_list(
+        mongo_client,
+        &format!("follows-{}", ctx.id()),
+        &guild_id,
+        &format!("pages-{}", ctx.id()),
+        ctx.author().id.get(),
+        None,
+    )
+    .await?;
+    send_paginated_embed(ctx, pages, rows).await?;
     return Ok(());
 }
@@ -0,0 +1,575 @@
+use crate::error_reporting::record_riot_call;
+use crate::fixtures::{record_fixture, replay_fixture};
+use crate::models::error::{BotError, Error, RateLimitedError};
+use reqwest::{Client, Response, StatusCode};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::time::sleep;
+
+/// Application limits of a Riot development key, used until Riot sends the real ones in the `X-App-Rate-Limit` header.
+const DEFAULT_APP_LIMITS: [(usize, u64); 2] = [(20, 1), (100, 120)];
+
+/// Longest delay a request will wait for a free slot before giving up.
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(10);
+
+/// Cooldown applied to a key rejected because of the key itself (`401`, or `403` on a method it already served).
+const FORBIDDEN_KEY_COOLDOWN: Duration = Duration::from_secs(60 * 60);
+
+/// Longest `Retry-After` for which a rate limited request is retried once automatically.
+const AUTO_RETRY_MAX_WAIT: Duration = Duration::from_secs(3);
+
+/// Share of a quota above which an alert is sent to the bot owner.
+const QUOTA_ALERT_THRESHOLD: f64 = 0.8;
+
+struct RateWindow {
+    limit: usize,
+    duration: Duration,
+    calls: VecDeque<Instant>,
+}
+
+impl RateWindow {
+    fn new(limit: usize, duration: Duration) -> Self {
+        RateWindow {
+            limit,
+            duration,
+            calls: VecDeque::new(),
+        }
+    }
+
+    /// Time to wait before a call fits in the window, `Duration::ZERO` if it fits now.
+    fn wait_time(&mut self, now: Instant) -> Duration {
+        while let Some(first) = self.calls.front() {
+            if now.duration_since(*first) >= self.duration {
+                self.calls.pop_front();
+            } else {
+                break;
+            }
+        }
+        if self.calls.len() < self.limit {
+            Duration::ZERO
+        } else {
+            self.duration - now.duration_since(self.calls[0])
+        }
+    }
+}
+
+struct KeyState {
+    key: String,
+    windows: Vec<RateWindow>,
+    blocked_until: Option<Instant>,
+    /// Methods this key got a successful response from, i.e. endpoints the key is known to cover.
+    served_methods: HashSet<String>,
+}
+
+impl KeyState {
+    fn new(key: String) -> Self {
+        KeyState {
+            key,
+            windows: DEFAULT_APP_LIMITS
+                .iter()
+                .map(|(limit, secs)| RateWindow::new(*limit, Duration::from_secs(*secs)))
+                .collect(),
+            blocked_until: None,
+            served_methods: HashSet::new(),
+        }
+    }
+
+    fn wait_time(&mut self, now: Instant) -> Duration {
+        let blocked = self
+            .blocked_until
+            .map(|until| until.saturating_duration_since(now))
+            .unwrap_or(Duration::ZERO);
+        self.windows
+            .iter_mut()
+            .map(|window| window.wait_time(now))
+            .fold(blocked, Duration::max)
+    }
+}
+
+enum Reservation {
+    Ready(String),
+    Wait(Duration),
+    Exhausted,
+}
+
+/// ⚙️ **Struct**: Pool of Riot API keys sharing the request load.
+///
+/// Each key keeps its own sliding windows of calls so that the quota of every key is respected. A key answered with
+/// `429`, `401`, or `403` on a method it already served, is put aside until its cooldown expires and the request is
+/// retried with the next available key.
+pub struct KeyPool {
+    keys: Mutex<Vec<KeyState>>,
+}
+
+static KEY_POOL: OnceLock<KeyPool> = OnceLock::new();
+
+fn key_pool() -> &'static KeyPool {
+    KEY_POOL.get_or_init(|| KeyPool {
+        keys: Mutex::new(Vec::new()),
+    })
+}
+
+/// ⚙️ **Function**: Registers the Riot API keys available to the bot.
+///
+/// # Parameters:
+/// - `keys`: Every Riot API key configured in the secrets. Empty and duplicated keys are ignored.
+///
+/// # Example:
+/// ```rust
+/// init_key_pool(vec![riot_api_key.clone(), "RGAPI-second-key".to_string()]);
+/// ```
+///
+/// # Notes:
+/// - Keys that are not registered here are added to the pool the first time they are used.
+pub fn init_key_pool(keys: Vec<String>) {
+    let mut states = key_pool().keys.lock().unwrap();
+    for key in keys {
+        let key = key.trim().to_string();
+        if !key.is_empty() && !states.iter().any(|state| state.key == key) {
+            states.push(KeyState::new(key));
+        }
+    }
+    log::info!(
+        "Riot API key pool initialized with {} key(s).",
+        states.len()
+    );
+}
+
+impl KeyPool {
+    fn reserve(&self, preferred: &str, excluded: &[String]) -> Reservation {
+        let mut states = self.keys.lock().unwrap();
+        if !states.iter().any(|state| state.key == preferred) {
+            states.push(KeyState::new(preferred.to_string()));
+        }
+        let now = Instant::now();
+        let preferred_index = states
+            .iter()
+            .position(|state| state.key == preferred)
+            .unwrap_or(0);
+        let len = states.len();
+        let mut shortest_wait: Option<Duration> = None;
+        for offset in 0..len {
+            let state = &mut states[(preferred_index + offset) % len];
+            if excluded.contains(&state.key) {
+                continue;
+            }
+            let wait = state.wait_time(now);
+            if wait.is_zero() {
+                for window in state.windows.iter_mut() {
+                    window.calls.push_back(now);
+                }
+                return Reservation::Ready(state.key.clone());
+            }
+            shortest_wait = Some(shortest_wait.map_or(wait, |current| current.min(wait)));
+        }
+        match shortest_wait {
+            Some(wait) => Reservation::Wait(wait),
+            None => Reservation::Exhausted,
+        }
+    }
+
+    /// Time before any key of the pool can send a request, `Duration::ZERO` if one can send it now.
+    fn shortest_wait(&self) -> Duration {
+        let mut states = self.keys.lock().unwrap();
+        let now = Instant::now();
+        states
+            .iter_mut()
+            .map(|state| state.wait_time(now))
+            .min()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    fn block(&self, key: &str, cooldown: Duration) {
+        let mut states = self.keys.lock().unwrap();
+        if let Some(state) = states.iter_mut().find(|state| state.key == key) {
+            state.blocked_until = Some(Instant::now() + cooldown);
+        }
+    }
+
+    fn record_served(&self, key: &str, method: &str) {
+        let mut states = self.keys.lock().unwrap();
+        if let Some(state) = states.iter_mut().find(|state| state.key == key) {
+            if !state.served_methods.contains(method) {
+                state.served_methods.insert(method.to_string());
+            }
+        }
+    }
+
+    fn has_served(&self, key: &str, method: &str) -> bool {
+        let states = self.keys.lock().unwrap();
+        states
+            .iter()
+            .any(|state| state.key == key && state.served_methods.contains(method))
+    }
+
+    fn update_limits(&self, key: &str, header: &str) {
+        let windows: Vec<RateWindow> = header
+            .split(',')
+            .filter_map(|limit| {
+                let (count, secs) = limit.trim().split_once(':')?;
+                Some(RateWindow::new(
+                    count.parse().ok()?,
+                    Duration::from_secs(secs.parse().ok()?),
+                ))
+            })
+            .collect();
+        if windows.is_empty() {
+            return;
+        }
+        let mut states = self.keys.lock().unwrap();
+        if let Some(state) = states.iter_mut().find(|state| state.key == key) {
+            let unchanged = state.windows.len() == windows.len()
+                && state
+                    .windows
+                    .iter()
+                    .zip(windows.iter())
+                    .all(|(old, new)| old.limit == new.limit && old.duration == new.duration);
+            if !unchanged {
+                state.windows = windows;
+            }
+        }
+    }
+}
+
+/// Consumption of one rate limit window, as reported by Riot.
+#[derive(Debug, Clone)]
+pub struct RateUsage {
+    pub count: u64,
+    pub limit: u64,
+    pub window_secs: u64,
+}
+
+/// Consumption of a Riot API method (or of the application limit of a key).
+#[derive(Debug, Clone)]
+pub struct MethodUsage {
+    pub method: String,
+    pub total_calls: u64,
+    pub windows: Vec<RateUsage>,
+}
+
+#[derive(Default)]
+struct UsageTracker {
+    methods: HashMap<String, MethodUsage>,
+    last_alerts: HashMap<String, Instant>,
+    alert_sender: Option<UnboundedSender<String>>,
+}
+
+static USAGE_TRACKER: OnceLock<Mutex<UsageTracker>> = OnceLock::new();
+
+fn usage_tracker() -> &'static Mutex<UsageTracker> {
+    USAGE_TRACKER.get_or_init(|| Mutex::new(UsageTracker::default()))
+}
+
+static COMMAND_QUEUE: OnceLock<Mutex<VecDeque<u64>>> = OnceLock::new();
+static NEXT_QUEUE_TICKET: AtomicU64 = AtomicU64::new(0);
+
+fn command_queue() -> &'static Mutex<VecDeque<u64>> {
+    COMMAND_QUEUE.get_or_init(|| Mutex::new(VecDeque::new()))
+}
+
+/// ⚙️ **Struct**: Place of a command in the queue of commands waiting for the Riot API rate limiter.
+///
+/// The place is released when the ticket is dropped, which lets the next command of the queue go.
+pub struct QueueTicket {
+    id: u64,
+}
+
+impl QueueTicket {
+    /// Position of the command in the queue, `1` being the next command to run.
+    pub fn position(&self) -> usize {
+        command_queue()
+            .lock()
+            .unwrap()
+            .iter()
+            .position(|id| *id == self.id)
+            .map_or(0, |index| index + 1)
+    }
+}
+
+impl Drop for QueueTicket {
+    fn drop(&mut self) {
+        command_queue().lock().unwrap().retain(|id| *id != self.id);
+    }
+}
+
+/// ⚙️ **Function**: Puts a command in the queue when the Riot API rate limiter would delay it.
+///
+/// # Returns:
+/// - `Option<QueueTicket>`: The place of the command in the queue, or `None` when a key is free and no other command
+///   is already waiting, in which case the command can run right away.
+///
+/// # Example:
+/// ```rust
+/// if let Some(ticket) = join_command_queue() {
+///     while ticket.position() > 1 || !estimated_riot_wait().is_zero() {
+///         sleep(Duration::from_millis(500)).await;
+///     }
+/// }
+/// ```
+pub fn join_command_queue() -> Option<QueueTicket> {
+    let mut queue = command_queue().lock().unwrap();
+    if queue.is_empty() && key_pool().shortest_wait().is_zero() {
+        return None;
+    }
+    let id = NEXT_QUEUE_TICKET.fetch_add(1, Ordering::Relaxed);
+    queue.push_back(id);
+    Some(QueueTicket { id })
+}
+
+/// ⚙️ **Function**: Returns the time before the rate limiter lets the next Riot API request go.
+///
+/// # Returns:
+/// - `Duration`: `Duration::ZERO` if a key can send a request now.
+pub fn estimated_riot_wait() -> Duration {
+    key_pool().shortest_wait()
+}
+
+/// ⚙️ **Function**: Sets the channel receiving the quota alerts.
+///
+/// # Parameters:
+/// - `sender`: The sending half of the channel. Each alert is a ready-to-display message.
+///
+/// # Example:
+/// ```rust
+/// let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+/// set_quota_alert_sender(sender);
+/// ```
+pub fn set_quota_alert_sender(sender: UnboundedSender<String>) {
+    usage_tracker().lock().unwrap().alert_sender = Some(sender);
+}
+
+/// ⚙️ **Function**: Returns the current consumption of every Riot API method called since startup.
+///
+/// # Returns:
+/// - `Vec<MethodUsage>`: The usage of each method and of the application limit of each key, sorted by name.
+pub fn api_usage_snapshot() -> Vec<MethodUsage> {
+    let tracker = usage_tracker().lock().unwrap();
+    let mut usages: Vec<MethodUsage> = tracker.methods.values().cloned().collect();
+    usages.sort_by(|a, b| a.method.cmp(&b.method));
+    usages
+}
+
+/// Matches the `limit:seconds` entries of a `X-*-Rate-Limit` header with the `count:seconds` entries of its `-Count` header.
+fn parse_rate_usage(limit_header: &str, count_header: &str) -> Vec<RateUsage> {
+    let parse = |header: &str| -> Vec<(u64, u64)> {
+        header
+            .split(',')
+            .filter_map(|entry| {
+                let (value, secs) = entry.trim().split_once(':')?;
+                Some((value.parse().ok()?, secs.parse().ok()?))
+            })
+            .collect()
+    };
+    let counts = parse(count_header);
+    parse(limit_header)
+        .into_iter()
+        .map(|(limit, window_secs)| RateUsage {
+            count: counts
+                .iter()
+                .find(|(_, secs)| *secs == window_secs)
+                .map_or(0, |(count, _)| *count),
+            limit,
+            window_secs,
+        })
+        .collect()
+}
+
+fn record_usage(scope: &str, windows: Vec<RateUsage>) {
+    let mut tracker = usage_tracker().lock().unwrap();
+    let usage = tracker
+        .methods
+        .entry(scope.to_string())
+        .or_insert_with(|| MethodUsage {
+            method: scope.to_string(),
+            total_calls: 0,
+            windows: Vec::new(),
+        });
+    usage.total_calls += 1;
+    if !windows.is_empty() {
+        usage.windows = windows;
+    }
+    let Some(window) = usage
+        .windows
+        .iter()
+        .find(|window| {
+            window.limit > 0 && window.count as f64 >= window.limit as f64 * QUOTA_ALERT_THRESHOLD
+        })
+        .cloned()
+    else {
+        return;
+    };
+
+    // Une seule alerte par fenêtre et par méthode
+    let now = Instant::now();
+    let recently_alerted = tracker
+        .last_alerts
+        .get(scope)
+        .is_some_and(|last| now.duration_since(*last) < Duration::from_secs(window.window_secs));
+    if recently_alerted {
+        return;
+    }
+    tracker.last_alerts.insert(scope.to_string(), now);
+    let message = format!(
+        "`{}` used **{}/{}** calls in its {}s window ({:.0}%).",
+        scope,
+        window.count,
+        window.limit,
+        window.window_secs,
+        window.count as f64 / window.limit as f64 * 100.0
+    );
+    log::warn!("Riot API quota alert: {}", message);
+    if let Some(sender) = &tracker.alert_sender {
+        let _ = sender.send(message);
+    }
+}
+
+fn header_str<'a>(response: &'a Response, name: &str) -> &'a str {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("")
+}
+
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get("Retry-After")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+/// ⚙️ **Function**: Sends a GET request to the Riot API through the rate limiter and the key pool.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+/// - `method`: The Riot API method name (e.g. `match-v5.getMatch`), used for the usage accounting.
+/// - `url`: The full Riot API URL, without any `api_key` query parameter.
+/// - `riot_api_key`: The preferred API key. Other keys of the pool are used when this one is out of quota.
+///
+/// # Returns:
+/// - `Result<Response, Error>`: The Riot API response, or a `RateLimitedError` if every key is rate limited.
+///
+/// # Example:
+/// ```rust
+/// let response = riot_get(&client, "summoner-v4.getByPUUID", &summoner_url, riot_api_key).await?;
+/// let summoner_json: Value = response.json().await?;
+/// ```
+///
+/// # Notes:
+/// - The key is sent in the `X-Riot-Token` header, so it never appears in logged URLs.
+/// - A `429` blocks the key for the `Retry-After` delay, then the next key is tried.
+/// - A `401`, or a `403` on a method the key already got a successful response from, means the key itself is expired or
+///   revoked: it is blocked for one hour and the next key is tried.
+/// - Other `403`s are refusals of the endpoint (e.g. tournament endpoints with a standard key): the response is returned
+///   to the caller as is and the key stays in the pool.
+/// - When every key is rate limited, the request is retried once if the `Retry-After` delay is short (3 seconds at most),
+///   otherwise a `RateLimitedError` holding the delay is returned so the command can show a friendly message.
+/// - When every key has been rejected, the last Riot response is returned as is.
+/// - The limits of each key are updated from the `X-App-Rate-Limit` header sent by Riot.
+/// - The `X-Method-Rate-Limit(-Count)` and `X-App-Rate-Limit(-Count)` headers feed `api_usage_snapshot`, and an alert is sent when a quota goes above 80%.
+/// - With `RIOT_FIXTURES_DIR`, the responses are replayed from fixtures or recorded to them, depending on
+///   `RIOT_FIXTURES_MODE` (see `replay_fixture` and `record_fixture`).
+pub async fn riot_get(
+    client: &Client,
+    method: &str,
+    url: &str,
+    riot_api_key: &str,
+) -> Result<Response, Error> {
+    if let Some(response) = replay_fixture(method, url).await? {
+        return Ok(response);
+    }
+    let response = match send_with_key_pool(client, method, url, riot_api_key).await {
+        Err(e) => match e.downcast_ref::<RateLimitedError>() {
+            Some(rate_limited) if rate_limited.retry_after <= AUTO_RETRY_MAX_WAIT => {
+                sleep(rate_limited.retry_after).await;
+                send_with_key_pool(client, method, url, riot_api_key).await
+            }
+            _ => Err(e),
+        },
+        result => result,
+    }?;
+    record_fixture(method, url, response).await
+}
+
+async fn send_with_key_pool(
+    client: &Client,
+    method: &str,
+    url: &str,
+    riot_api_key: &str,
+) -> Result<Response, Error> {
+    let pool = key_pool();
+    let mut tried: Vec<String> = Vec::new();
+    let mut last_rejected: Option<Response> = None;
+    let mut shortest_retry_after: Option<Duration> = None;
+    loop {
+        let key = match pool.reserve(riot_api_key, &tried) {
+            Reservation::Ready(key) => key,
+            Reservation::Wait(wait) if wait <= MAX_RATE_LIMIT_WAIT => {
+                sleep(wait).await;
+                continue;
+            }
+            Reservation::Wait(wait) => {
+                return Err(Box::new(RateLimitedError {
+                    retry_after: shortest_retry_after.map_or(wait, |retry| retry.min(wait)),
+                }))
+            }
+            Reservation::Exhausted => {
+                return match (shortest_retry_after, last_rejected) {
+                    (Some(retry_after), _) => Err(Box::new(RateLimitedError { retry_after })),
+                    (None, Some(response)) => Ok(response),
+                    (None, None) => Err(Box::new(BotError::NoRiotApiKey)),
+                }
+            }
+        };
+
+        let response = client.get(url).header("X-Riot-Token", &key).send().await?;
+        record_riot_call(method, response.status().as_u16());
+        let app_limits = header_str(&response, "X-App-Rate-Limit");
+        if !app_limits.is_empty() {
+            pool.update_limits(&key, app_limits);
+        }
+        record_usage(
+            method,
+            parse_rate_usage(
+                header_str(&response, "X-Method-Rate-Limit"),
+                header_str(&response, "X-Method-Rate-Limit-Count"),
+            ),
+        );
+        record_usage(
+            &format!("application (key …{})", &key[key.len().saturating_sub(4)..]),
+            parse_rate_usage(app_limits, header_str(&response, "X-App-Rate-Limit-Count")),
+        );
+
+        let cooldown = match response.status() {
+            StatusCode::TOO_MANY_REQUESTS => {
+                let retry_after = retry_after(&response).unwrap_or(Duration::from_secs(1));
+                shortest_retry_after = Some(
+                    shortest_retry_after.map_or(retry_after, |shortest| shortest.min(retry_after)),
+                );
+                retry_after
+            }
+            StatusCode::UNAUTHORIZED => FORBIDDEN_KEY_COOLDOWN,
+            // Un 403 sur une méthode que la clé a déjà servie vient de la clé, sinon de l'endpoint
+            StatusCode::FORBIDDEN if pool.has_served(&key, method) => FORBIDDEN_KEY_COOLDOWN,
+            status => {
+                if status.is_success() {
+                    pool.record_served(&key, method);
+                }
+                return Ok(response);
+            }
+        };
+        log::warn!(
+            "Riot API key ending with '{}' rejected with {}, rotating.",
+            &key[key.len().saturating_sub(4)..],
+            response.status()
+        );
+        pool.block(&key, cooldown);
+        tried.push(key);
+        last_rejected = Some(response);
+    }
+}
@@ -1,7 +1,13 @@
-use crate::models::error::Error;
+use crate::models::error::{BotError, Error};
+use crate::models::riot_id::RiotId;
+use crate::rate_limiter::riot_get;
 use reqwest::Client;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Base URL of Data Dragon, the static data and images of League of Legends.
+const DD_BASE_URL: &str = "https://ddragon.leagueoflegends.com";
 
 /// ⚙️ **Function**: Fetches the player's PUUID (Player Unique Identifier) from the Riot API.
 ///
@@ -10,8 +16,7 @@ use std::collections::HashMap;
 ///
 /// # Parameters:
 /// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
-/// - `game_name_space`: The player's in-game name (spaces should be replaced with `%20`).
-/// - `tag_line`: The player's tag line, typically a four-digit number associated with their Riot account.
+/// - `riot_id`: The player's Riot ID (game name and tag line).
 /// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
 ///
 /// # Returns:
@@ -19,11 +24,12 @@ use std::collections::HashMap;
 ///
 /// # ⚠️ Notes:
 /// - If the player does not exist or the information provided is incorrect, the function will return an error message.
+/// - Only a `404` is reported as a missing player: any other error status is returned as `BotError::RiotStatus`.
 /// - The PUUID is a critical identifier that is used in subsequent requests to fetch match and player data.
 ///
 /// # Example:
 /// ```rust
-/// let puuid = get_puuid(&client, "Faker", "1234", riot_api_key).await?;
+/// let puuid = get_puuid(&client, &"Faker#1234".parse()?, riot_api_key).await?;
 /// ```
 ///
 /// The resulting `puuid` will be a unique string identifier, such as:
@@ -32,16 +38,25 @@ use std::collections::HashMap;
 /// ```
 pub async fn get_puuid(
     client: &Client,
-    game_name_space: &str,
-    tag_line: &str,
+    riot_id: &RiotId,
     riot_api_key: &str,
 ) -> Result<String, Error> {
     let puuid_url = format!(
-        "https://europe.api.riotgames.com/riot/account/v1/accounts/by-riot-id/{}/{}?api_key={}",
-        game_name_space, tag_line, riot_api_key
+        "https://europe.api.riotgames.com/riot/account/v1/accounts/by-riot-id/{}/{}",
+        riot_id.url_name(),
+        riot_id.tag
     );
 
-    let response = client.get(&puuid_url).send().await?;
+    let response = riot_get(client, "account-v1.getByRiotId", &puuid_url, riot_api_key).await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(Box::new(BotError::SummonerNotFound));
+    }
+    if !response.status().is_success() {
+        return Err(Box::new(BotError::RiotStatus {
+            method: "account-v1.getByRiotId",
+            status: response.status().as_u16(),
+        }));
+    }
     let puuid_json: Value = response.json().await?;
     let puuid = puuid_json
         .get("puuid")
@@ -50,7 +65,7 @@ pub async fn get_puuid(
         .to_string();
 
     if puuid.is_empty() {
-        Err("The player could not be found. Please verify that the region, game name, and tag line you provided are correct, and try again.".into())
+        Err(Box::new(BotError::SummonerNotFound))
     } else {
         Ok(puuid)
     }
@@ -91,15 +106,59 @@ pub async fn get_matchs_id(
     nb_match: u32,
 ) -> Result<Vec<String>, Error> {
     let matchs_url = format!(
-            "https://europe.api.riotgames.com/lol/match/v5/matches/by-puuid/{}/ids?&count={}&api_key={}",
-            puuid, nb_match.to_string(),  riot_api_key
-        );
+        "https://europe.api.riotgames.com/lol/match/v5/matches/by-puuid/{}/ids?&count={}",
+        puuid,
+        nb_match.to_string()
+    );
 
-    let response = client.get(&matchs_url).send().await?;
+    let response = riot_get(
+        client,
+        "match-v5.getMatchIdsByPUUID",
+        &matchs_url,
+        riot_api_key,
+    )
+    .await?;
     let matchs_id: Vec<String> = response.json().await?;
     Ok(matchs_id)
 }
 
+/// ⚙️ **Function**: Retrieves the IDs of the matches a player started after a given time.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+/// - `puuid`: The player's unique PUUID (Player Unique Identifier).
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+/// - `start_time`: The UNIX timestamp (in seconds) from which matches are returned.
+/// - `nb_match`: The maximum number of match IDs to retrieve (at most 100).
+///
+/// # Returns:
+/// - `Result<Vec<String>, Error>`: The match IDs, most recent first.
+///
+/// # Example:
+/// ```rust
+/// let match_ids = get_matchs_id_since(&client, &puuid, riot_api_key, now - 12 * 3600, 20).await?;
+/// ```
+pub async fn get_matchs_id_since(
+    client: &Client,
+    puuid: &str,
+    riot_api_key: &str,
+    start_time: i64,
+    nb_match: u32,
+) -> Result<Vec<String>, Error> {
+    let matchs_url = format!(
+        "https://europe.api.riotgames.com/lol/match/v5/matches/by-puuid/{}/ids?startTime={}&count={}",
+        puuid, start_time, nb_match
+    );
+    let response = riot_get(
+        client,
+        "match-v5.getMatchIdsByPUUID",
+        &matchs_url,
+        riot_api_key,
+    )
+    .await?;
+    Ok(response.json().await?)
+}
+
 /// ⚙️ **Function**: Fetches the summoner ID for a player using their PUUID.
 ///
 /// This function sends a request to the Riot API to retrieve the summoner ID of a player, which is used for further
@@ -117,6 +176,7 @@ pub async fn get_matchs_id(
 ///
 /// # ⚠️ Notes:
 /// - If the summoner ID cannot be retrieved (e.g., due to incorrect region or PUUID), the function logs an error and returns an appropriate message.
+/// - Only a `404` is reported as a missing profile: any other error status is returned as `BotError::RiotStatus`.
 /// - The summoner ID is required for many other API requests, such as retrieving ranked data and match history.
 ///
 /// # Example:
@@ -135,11 +195,26 @@ pub async fn get_summoner_id(
     riot_api_key: &str,
 ) -> Result<String, Error> {
     let summoner_url = format!(
-        "https://{}.api.riotgames.com/lol/summoner/v4/summoners/by-puuid/{}?api_key={}",
-        region_str, puuid, riot_api_key
+        "https://{}.api.riotgames.com/lol/summoner/v4/summoners/by-puuid/{}",
+        region_str, puuid
     );
 
-    let response = client.get(&summoner_url).send().await?;
+    let response = riot_get(
+        client,
+        "summoner-v4.getByPUUID",
+        &summoner_url,
+        riot_api_key,
+    )
+    .await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(Box::new(BotError::SummonerProfileNotFound));
+    }
+    if !response.status().is_success() {
+        return Err(Box::new(BotError::RiotStatus {
+            method: "summoner-v4.getByPUUID",
+            status: response.status().as_u16(),
+        }));
+    }
     let summoner_json: Value = response.json().await?;
     let summoner_id = summoner_json
         .get("id")
@@ -147,12 +222,69 @@ pub async fn get_summoner_id(
         .unwrap_or("")
         .to_string();
     if summoner_id.is_empty() {
-        Err("Error retrieving summoner ID. Please verify that the region, game name, and tag line you provided are correct, and try again.".into())
+        Err(Box::new(BotError::SummonerProfileNotFound))
     } else {
         Ok(summoner_id)
     }
 }
 
+/// ⚙️ **Function**: Fetches the summoner profile of a player using their PUUID.
+///
+/// This function sends a request to the Riot API to retrieve the full summoner profile of a player, including
+/// their summoner ID, profile icon ID and summoner level.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+/// - `region_str`: A string representing the region (e.g., `euw1`, `na1`, `kr`) where the player's account is located.
+/// - `puuid`: The player's unique PUUID (Player Unique Identifier).
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<Value, Error>`: The summoner profile as a JSON object, or an error if the player cannot be found or the request fails.
+///
+/// # Notes:
+/// - Only a `404` is reported as a missing profile: any other error status is returned as `BotError::RiotStatus`.
+///
+/// # Example:
+/// ```rust
+/// let summoner = get_summoner(&client, "euw1", "abcd1234-efgh5678-ijkl91011-mnop1213", riot_api_key).await?;
+/// let icon_id = summoner["profileIconId"].as_i64();
+/// ```
+pub async fn get_summoner(
+    client: &Client,
+    region_str: &str,
+    puuid: &str,
+    riot_api_key: &str,
+) -> Result<Value, Error> {
+    let summoner_url = format!(
+        "https://{}.api.riotgames.com/lol/summoner/v4/summoners/by-puuid/{}",
+        region_str, puuid
+    );
+
+    let response = riot_get(
+        client,
+        "summoner-v4.getByPUUID",
+        &summoner_url,
+        riot_api_key,
+    )
+    .await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(Box::new(BotError::SummonerProfileNotFound));
+    }
+    if !response.status().is_success() {
+        return Err(Box::new(BotError::RiotStatus {
+            method: "summoner-v4.getByPUUID",
+            status: response.status().as_u16(),
+        }));
+    }
+    let summoner_json: Value = response.json().await?;
+    if summoner_json.get("id").is_none() {
+        Err(Box::new(BotError::SummonerProfileNotFound))
+    } else {
+        Ok(summoner_json)
+    }
+}
+
 /// ⚙️ **Function**: Fetches ranked information for a player using their summoner ID.
 ///
 /// This function sends a request to the Riot API to retrieve ranked information for a player, including their rank,
@@ -204,10 +336,286 @@ pub async fn get_rank_info(
     riot_api_key: &str,
 ) -> Result<Vec<HashMap<String, Value>>, Error> {
     let rank_url = format!(
-        "https://{}.api.riotgames.com/lol/league/v4/entries/by-summoner/{}?api_key={}",
-        region_str, summoner_id, riot_api_key
+        "https://{}.api.riotgames.com/lol/league/v4/entries/by-summoner/{}",
+        region_str, summoner_id
+    );
+    let response = riot_get(
+        client,
+        "league-v4.getLeagueEntriesForSummoner",
+        &rank_url,
+        riot_api_key,
+    )
+    .await?;
+    Ok(response.json().await?)
+}
+
+/// ⚙️ **Function**: Retrieves one page of the players of a ranked division.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+/// - `region_str`: A string representing the region (e.g., `euw1`, `na1`, `kr`).
+/// - `queue_type`: The ranked queue (e.g., `RANKED_SOLO_5x5`).
+/// - `tier`: The tier (e.g., `GOLD`, `MASTER`).
+/// - `division`: The division (e.g., `II`). Apex tiers only have the division `I`.
+/// - `page`: The page to fetch, starting at `1`. Riot returns up to 205 players per page.
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<Vec<Value>, Error>`: The league entries of the page, empty past the last page.
+///
+/// # Example:
+/// ```rust
+/// let entries = get_league_exp_entries(&client, "euw1", "RANKED_SOLO_5x5", "GOLD", "II", 1, riot_api_key).await?;
+/// ```
+pub async fn get_league_exp_entries(
+    client: &Client,
+    region_str: &str,
+    queue_type: &str,
+    tier: &str,
+    division: &str,
+    page: u32,
+    riot_api_key: &str,
+) -> Result<Vec<Value>, Error> {
+    let entries_url = format!(
+        "https://{}.api.riotgames.com/lol/league-exp/v4/entries/{}/{}/{}?page={}",
+        region_str, queue_type, tier, division, page
+    );
+    let response = riot_get(
+        client,
+        "league-exp-v4.getLeagueEntries",
+        &entries_url,
+        riot_api_key,
+    )
+    .await?;
+    if !response.status().is_success() {
+        return Err(Box::new(BotError::RiotStatus {
+            method: "league-exp-v4.getLeagueEntries",
+            status: response.status().as_u16(),
+        }));
+    }
+    Ok(response.json().await?)
+}
+
+/// ⚙️ **Function**: Retrieves the game a player is currently playing, if any.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+/// - `region_str`: A string representing the region (e.g., `euw1`, `na1`, `kr`) where the player's account is located.
+/// - `puuid`: The player's unique PUUID (Player Unique Identifier).
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<Option<Value>, Error>`: The spectator-v5 game info (`gameQueueConfigId`, `gameStartTime`, `gameLength`,
+///   `participants`...), `None` if the player is not in game, or an error if the request fails.
+///
+/// # Example:
+/// ```rust
+/// if let Some(game) = get_active_game(&client, "euw1", &puuid, riot_api_key).await? {
+///     println!("In game for {}s", game["gameLength"]);
+/// }
+/// ```
+pub async fn get_active_game(
+    client: &Client,
+    region_str: &str,
+    puuid: &str,
+    riot_api_key: &str,
+) -> Result<Option<Value>, Error> {
+    let active_game_url = format!(
+        "https://{}.api.riotgames.com/lol/spectator/v5/active-games/by-summoner/{}",
+        region_str, puuid
+    );
+    let response = riot_get(
+        client,
+        "spectator-v5.getCurrentGameInfoByPuuid",
+        &active_game_url,
+        riot_api_key,
+    )
+    .await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(Box::new(BotError::RiotStatus {
+            method: "spectator-v5.getCurrentGameInfoByPuuid",
+            status: response.status().as_u16(),
+        }));
+    }
+    Ok(Some(response.json().await?))
+}
+
+/// ⚙️ **Function**: Retrieves the active and upcoming Clash tournaments of a region.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+/// - `region_str`: A string representing the region (e.g., `euw1`, `na1`, `kr`).
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<Vec<Value>, Error>`: The clash-v1 tournaments (`id`, `nameKey`, `nameKeySecondary` and `schedule`, whose
+///   phases have a `registrationTime` and a `startTime` in milliseconds and a `cancelled` flag).
+///
+/// # Example:
+/// ```rust
+/// let tournaments = get_clash_tournaments(&client, "euw1", riot_api_key).await?;
+/// ```
+pub async fn get_clash_tournaments(
+    client: &Client,
+    region_str: &str,
+    riot_api_key: &str,
+) -> Result<Vec<Value>, Error> {
+    let tournaments_url = format!(
+        "https://{}.api.riotgames.com/lol/clash/v1/tournaments",
+        region_str
+    );
+    let response = riot_get(
+        client,
+        "clash-v1.getTournaments",
+        &tournaments_url,
+        riot_api_key,
+    )
+    .await?;
+    if !response.status().is_success() {
+        return Err(Box::new(BotError::RiotStatus {
+            method: "clash-v1.getTournaments",
+            status: response.status().as_u16(),
+        }));
+    }
+    Ok(response.json().await?)
+}
+
+/// ⚙️ **Function**: Retrieves the champions of the current free rotation.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+/// - `region_str`: A string representing the region (e.g., `euw1`, `na1`, `kr`).
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<Vec<i64>, Error>`: The champion IDs (the `key` of the Data Dragon champions) of the free rotation.
+///
+/// # Example:
+/// ```rust
+/// let free_champion_ids = get_champion_rotation(&client, "euw1", riot_api_key).await?;
+/// ```
+///
+/// # ⚠️ Notes:
+/// - The rotation for new players (`freeChampionIdsForNewPlayers`) is not returned.
+pub async fn get_champion_rotation(
+    client: &Client,
+    region_str: &str,
+    riot_api_key: &str,
+) -> Result<Vec<i64>, Error> {
+    let rotation_url = format!(
+        "https://{}.api.riotgames.com/lol/platform/v3/champion-rotations",
+        region_str
     );
-    let response = client.get(&rank_url).send().await?;
+    let response = riot_get(
+        client,
+        "champion-v3.getChampionInfo",
+        &rotation_url,
+        riot_api_key,
+    )
+    .await?;
+    if !response.status().is_success() {
+        return Err(Box::new(BotError::RiotStatus {
+            method: "champion-v3.getChampionInfo",
+            status: response.status().as_u16(),
+        }));
+    }
+    let rotation: Value = response.json().await?;
+    Ok(rotation["freeChampionIds"]
+        .as_array()
+        .map(|ids| ids.iter().filter_map(Value::as_i64).collect())
+        .unwrap_or_default())
+}
+
+/// ⚙️ **Function**: Fetches the mastery points of a player on a champion.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+/// - `region_str`: The platform of the player (e.g., `euw1`).
+/// - `puuid`: The PUUID of the player.
+/// - `champion_id`: The numeric ID of the champion (`championId` in match-v5).
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<i64, Error>`: The mastery points, `0` if the player never played the champion, or an error if the Riot API
+///   does not answer with a success status.
+///
+/// # Example:
+/// ```rust
+/// let points = get_champion_mastery_points(&client, "euw1", &puuid, 157, riot_api_key).await?;
+/// ```
+pub async fn get_champion_mastery_points(
+    client: &Client,
+    region_str: &str,
+    puuid: &str,
+    champion_id: i64,
+    riot_api_key: &str,
+) -> Result<i64, Error> {
+    let mastery_url = format!(
+        "https://{}.api.riotgames.com/lol/champion-mastery/v4/champion-masteries/by-puuid/{}/by-champion/{}",
+        region_str, puuid, champion_id
+    );
+    let response = riot_get(
+        client,
+        "champion-mastery-v4.getChampionMasteryByPUUID",
+        &mastery_url,
+        riot_api_key,
+    )
+    .await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(0);
+    }
+    if !response.status().is_success() {
+        return Err(Box::new(BotError::RiotStatus {
+            method: "champion-mastery-v4.getChampionMasteryByPUUID",
+            status: response.status().as_u16(),
+        }));
+    }
+    let mastery: Value = response.json().await?;
+    Ok(mastery["championPoints"].as_i64().unwrap_or(0))
+}
+
+/// ⚙️ **Function**: Retrieves the mastery of a player on every champion they played.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+/// - `region_str`: The platform of the player (e.g., `euw1`).
+/// - `puuid`: The PUUID of the player.
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<Vec<Value>, Error>`: The champion-mastery-v4 entries (`championId`, `championLevel`, `championPoints`...),
+///   or an error if the Riot API does not answer with a success status.
+///
+/// # Example:
+/// ```rust
+/// let masteries = get_champion_masteries(&client, "euw1", &puuid, riot_api_key).await?;
+/// ```
+pub async fn get_champion_masteries(
+    client: &Client,
+    region_str: &str,
+    puuid: &str,
+    riot_api_key: &str,
+) -> Result<Vec<Value>, Error> {
+    let masteries_url = format!(
+        "https://{}.api.riotgames.com/lol/champion-mastery/v4/champion-masteries/by-puuid/{}",
+        region_str, puuid
+    );
+    let response = riot_get(
+        client,
+        "champion-mastery-v4.getAllChampionMasteriesByPUUID",
+        &masteries_url,
+        riot_api_key,
+    )
+    .await?;
+    if !response.status().is_success() {
+        return Err(Box::new(BotError::RiotStatus {
+            method: "champion-mastery-v4.getAllChampionMasteriesByPUUID",
+            status: response.status().as_u16(),
+        }));
+    }
     Ok(response.json().await?)
 }
 
@@ -227,7 +635,7 @@ pub async fn get_rank_info(
 ///
 /// # ⚠️ Notes:
 /// - The function returns the top 10 champions based on mastery points, but this count can be adjusted in the API URL.
-/// - The information includes each champion's ID, mastery level, and mastery points.
+/// - The information includes each champion's ID, mastery level, mastery points, progress towards the next level, marks and season milestone.
 /// - The function requires a valid `puuid` and `region` for the request to succeed.
 ///
 /// # Example:
@@ -257,62 +665,88 @@ pub async fn get_champions(
     riot_api_key: &str,
 ) -> Result<Vec<HashMap<String, Value>>, Error> {
     let champions_url = format!(
-            "https://{}.api.riotgames.com/lol/champion-mastery/v4/champion-masteries/by-puuid/{}/top?count=10&api_key={}",
-            region, puuid, riot_api_key
+            "https://{}.api.riotgames.com/lol/champion-mastery/v4/champion-masteries/by-puuid/{}/top?count=10",
+            region, puuid
         );
-    let response = client.get(&champions_url).send().await?;
+    let response = riot_get(
+        client,
+        "champion-mastery-v4.getTopChampionMasteriesByPUUID",
+        &champions_url,
+        riot_api_key,
+    )
+    .await?;
     Ok(response.json().await?)
 }
 
-/// ⚙️ **Function**: Fetches the latest champion data from Data Dragon (Riot's official static data service).
+/// Latest Data Dragon version, detected by `fetch_dd_version`. Empty until Data Dragon has answered once.
+static DD_VERSION: RwLock<String> = RwLock::new(String::new());
+
+/// ⚙️ **Function**: Fetches the latest Data Dragon version and remembers it for the CDN URLs.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
 ///
-/// This function sends a request to Data Dragon to retrieve the latest static data about League of Legends champions,
-/// such as champion names, IDs, and related information. The data is returned as a JSON object and can be used to map
-/// champion IDs to their names and other static details.
+/// # Returns:
+/// - `Result<String, Error>`: The latest version, e.g. `14.20.1`, or an error if Data Dragon cannot be reached.
+///
+/// # Notes:
+/// - It is called by `open_dd_json` (through `DataDragon`) at startup and on each champion refresh, and by the health check, so the version
+///   used by `dd_version` follows the new patches without a restart.
+pub async fn fetch_dd_version(client: &Client) -> Result<String, Error> {
+    let versions: Value = client
+        .get(format!("{}/api/versions.json", DD_BASE_URL))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let version = versions[0]
+        .as_str()
+        .ok_or("Data Dragon returned no version")?
+        .to_string();
+    if let Ok(mut latest) = DD_VERSION.write() {
+        latest.clone_from(&version);
+    }
+    Ok(version)
+}
+
+/// ⚙️ **Function**: Returns the latest Data Dragon version, fetching it if it is not known yet.
 ///
 /// # Returns:
-/// - `Result<Value, Error>`: A JSON object containing champion data if the request is successful, or an error if the request fails.
+/// - `Result<String, Error>`: The latest version, or an error if it is unknown and Data Dragon cannot be reached.
+pub async fn dd_version() -> Result<String, Error> {
+    let latest = DD_VERSION
+        .read()
+        .map(|latest| latest.clone())
+        .unwrap_or_default();
+    if latest.is_empty() {
+        return fetch_dd_version(&Client::new()).await;
+    }
+    Ok(latest)
+}
+
+/// ⚙️ **Function**: Builds the URL of a Data Dragon CDN file.
 ///
-/// # ⚠️ Notes:
-/// - The request fetches champion data in French (`fr_FR`), but the language can be changed by modifying the URL.
-/// - Data Dragon provides static, versioned data, which means this data may not always be up to date with the latest game patches unless the URL version is updated.
+/// Every Data Dragon CDN URL of the bot goes through this function.
+///
+/// # Parameters:
+/// - `version`: The Data Dragon version (see `dd_version`), or an empty string for the unversioned art such as the
+///   loading screens and splash arts.
+/// - `path`: The path of the file below the version, e.g. `img/champion/Ahri.png`.
+///
+/// # Returns:
+/// - `String`: e.g. `https://ddragon.leagueoflegends.com/cdn/14.20.1/img/champion/Ahri.png`.
 ///
 /// # Example:
 /// ```rust
-/// let dd_json = open_dd_json().await?;
+/// let thumbnail = dd_cdn_url(&dd_version().await?, &format!("img/champion/{}.png", id_name));
+/// let loading = dd_cdn_url("", &format!("img/champion/loading/{}_0.jpg", id_name));
 /// ```
-///
-/// The resulting `dd_json` will contain champion data like:
-/// ```json
-/// {
-///   "type": "champion",
-///   "format": "standAloneComplex",
-///   "data": {
-///     "Aatrox": {
-///       "id": "Aatrox",
-///       "key": "266",
-///       "name": "Aatrox",
-///       ...
-///     },
-///     ...
-///   }
-/// }
-/// ```
-pub async fn open_dd_json() -> Result<Value, Error> {
-    let version_json: Value = reqwest::get("https://ddragon.leagueoflegends.com/api/versions.json")
-        .await?
-        .json()
-        .await?;
-    let version = version_json[0].as_str().unwrap();
-    let dd_json = reqwest::get(format!(
-        "https://ddragon.leagueoflegends.com/cdn/{}/data/en_US/champion.json",
-        version
-    ))
-    .await?
-    .json()
-    .await?;
-    log::info!("Data dragon version: {}", version);
-    Ok(dd_json)
+pub fn dd_cdn_url(version: &str, path: &str) -> String {
+    if version.is_empty() {
+        format!("{}/cdn/{}", DD_BASE_URL, path)
+    } else {
+        format!("{}/cdn/{}/{}", DD_BASE_URL, version, path)
+    }
 }
 
 /// ⚙️ **Function**: Fetches detailed information about a specific match using the match ID.
@@ -367,11 +801,130 @@ pub async fn get_matchs_info(
     riot_api_key: &str,
 ) -> Result<Value, Error> {
     let matchs_info_url = format!(
-        "https://europe.api.riotgames.com/lol/match/v5/matches/{}?api_key={}",
-        match_id, riot_api_key
+        "https://europe.api.riotgames.com/lol/match/v5/matches/{}",
+        match_id
     );
     eprint!("Fetching match data from {}...\n", matchs_info_url);
-    let response = client.get(&matchs_info_url).send().await?;
+    let response = riot_get(client, "match-v5.getMatch", &matchs_info_url, riot_api_key).await?;
     let matchs_info: Value = response.json().await?;
     Ok(matchs_info)
 }
+
+/// ⚙️ **Function**: Retrieves the IDs of the matches played with a tournament code.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+/// - `tournament_code`: The tournament code of the custom games.
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<Vec<String>, Error>`: The match IDs, or an error if the Riot API does not answer with a success status.
+///
+/// # Example:
+/// ```rust
+/// let match_ids = get_matchs_id_by_tournament_code(&client, "EUW04a2-...", riot_api_key).await?;
+/// ```
+///
+/// # ⚠️ Notes:
+/// - The endpoint requires an API key with access to the tournament API; other keys get a `403`.
+pub async fn get_matchs_id_by_tournament_code(
+    client: &Client,
+    tournament_code: &str,
+    riot_api_key: &str,
+) -> Result<Vec<String>, Error> {
+    let matchs_url = format!(
+        "https://europe.api.riotgames.com/lol/match/v5/matches/by-tournament-code/{}/ids",
+        tournament_code
+    );
+    let response = riot_get(
+        client,
+        "match-v5.getMatchIdsByTournamentCode",
+        &matchs_url,
+        riot_api_key,
+    )
+    .await?;
+    if !response.status().is_success() {
+        return Err(Box::new(BotError::RiotStatus {
+            method: "match-v5.getMatchIdsByTournamentCode",
+            status: response.status().as_u16(),
+        }));
+    }
+    let matchs_id: Vec<String> = response.json().await?;
+    Ok(matchs_id)
+}
+
+/// ⚙️ **Function**: Fetches the timeline of a match (one frame per minute and every in-game event).
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+/// - `match_id`: The ID of the match (e.g., `EUW1_1234567890`).
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<Value, Error>`: The match-v5 timeline, whose `info.frames` hold the `participantFrames` (position, gold, XP)
+///   and the `events` of each minute, or an error if the Riot API does not answer with a success status.
+///
+/// # Example:
+/// ```rust
+/// let timeline = get_match_timeline(&client, "EUW1_1234567890", riot_api_key).await?;
+/// let frames = timeline["info"]["frames"].as_array().map_or(0, |frames| frames.len());
+/// ```
+pub async fn get_match_timeline(
+    client: &Client,
+    match_id: &str,
+    riot_api_key: &str,
+) -> Result<Value, Error> {
+    let timeline_url = format!(
+        "https://europe.api.riotgames.com/lol/match/v5/matches/{}/timeline",
+        match_id
+    );
+    let response = riot_get(client, "match-v5.getTimeline", &timeline_url, riot_api_key).await?;
+    if !response.status().is_success() {
+        return Err(Box::new(BotError::RiotStatus {
+            method: "match-v5.getTimeline",
+            status: response.status().as_u16(),
+        }));
+    }
+    let timeline: Value = response.json().await?;
+    Ok(timeline)
+}
+
+/// ⚙️ **Function**: Fetches the status of a League of Legends platform (incidents and maintenances).
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+/// - `region_str`: A string representing the platform (e.g., `euw1`, `na1`, `kr`).
+/// - `riot_api_key`: The API key used to authenticate the request with the Riot API.
+///
+/// # Returns:
+/// - `Result<Value, Error>`: The platform data (`incidents`, `maintenances`, ...), or an error if the Riot API does not answer with a success status.
+///
+/// # Example:
+/// ```rust
+/// let status = get_platform_status(&client, "euw1", riot_api_key).await?;
+/// let incidents = status["incidents"].as_array().map_or(0, |incidents| incidents.len());
+/// ```
+pub async fn get_platform_status(
+    client: &Client,
+    region_str: &str,
+    riot_api_key: &str,
+) -> Result<Value, Error> {
+    let status_url = format!(
+        "https://{}.api.riotgames.com/lol/status/v4/platform-data",
+        region_str
+    );
+    let response = riot_get(
+        client,
+        "lol-status-v4.getPlatformData",
+        &status_url,
+        riot_api_key,
+    )
+    .await?;
+    if !response.status().is_success() {
+        return Err(Box::new(BotError::RiotStatus {
+            method: "lol-status-v4.getPlatformData",
+            status: response.status().as_u16(),
+        }));
+    }
+    Ok(response.json().await?)
+}
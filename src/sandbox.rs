@@ -0,0 +1,92 @@
+use crate::config::config;
+use poise::serenity_prelude::{
+    self as serenity, ChannelId, CreateMessage, EditChannel, Http, UserId,
+};
+use serde::Serialize;
+
+/// ⚙️ **Function**: Logs what the bot would have sent to Discord in dry-run mode.
+///
+/// # Parameters:
+/// - `target`: Where the message would have been sent, e.g. `channel 1234`.
+/// - `payload`: The builder of the message, logged as JSON as it would have been sent to the Discord API.
+fn log_dry_run(target: &str, payload: &impl Serialize) {
+    match serde_json::to_string_pretty(payload) {
+        Ok(json) => log::info!("[dry run] {}:\n{}", target, json),
+        Err(e) => log::info!("[dry run] {} (not serializable: {})", target, e),
+    }
+}
+
+/// ⚙️ **Function**: Posts a message in a channel, or logs it in dry-run mode.
+///
+/// # Parameters:
+/// - `http`: The HTTP client of the bot.
+/// - `channel_id`: The channel to post in.
+/// - `message`: The message to post.
+///
+/// # Returns:
+/// - `serenity::Result<()>`: The error of Discord if the message could not be posted.
+///
+/// # Example:
+/// ```rust
+/// send_message(&http, channel_id, CreateMessage::new().add_embed(embed)).await?;
+/// ```
+///
+/// # Notes:
+/// - The background tasks post through this function rather than `ChannelId::send_message`, so that the whole follow
+///   pipeline can run locally without a bot token (see `DRY_RUN`). Attachments are left out of the log.
+pub async fn send_message(
+    http: &Http,
+    channel_id: ChannelId,
+    message: CreateMessage,
+) -> serenity::Result<()> {
+    if config().dry_run {
+        log_dry_run(&format!("Message to channel {}", channel_id), &message);
+        return Ok(());
+    }
+    channel_id.send_message(http, message).await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Sends a direct message to a user, or logs it in dry-run mode.
+///
+/// # Parameters:
+/// - `http`: The HTTP client of the bot.
+/// - `user_id`: The user to send the message to.
+/// - `message`: The message to send.
+///
+/// # Returns:
+/// - `serenity::Result<()>`: The error of Discord if the message could not be sent, e.g. closed direct messages.
+pub async fn send_direct_message(
+    http: &Http,
+    user_id: UserId,
+    message: CreateMessage,
+) -> serenity::Result<()> {
+    if config().dry_run {
+        log_dry_run(&format!("Direct message to user {}", user_id), &message);
+        return Ok(());
+    }
+    user_id.direct_message(http, message).await?;
+    Ok(())
+}
+
+/// ⚙️ **Function**: Edits the name or topic of a channel, or logs the edit in dry-run mode.
+///
+/// # Parameters:
+/// - `http`: The HTTP client of the bot.
+/// - `channel_id`: The channel to edit.
+/// - `edit`: The changes to apply.
+///
+/// # Returns:
+/// - `serenity::Result<()>`: The error of Discord if the channel could not be edited.
+pub async fn edit_channel(
+    http: &Http,
+    channel_id: ChannelId,
+    edit: EditChannel<'_>,
+) -> serenity::Result<()> {
+    if config().dry_run {
+        log_dry_run(&format!("Edit of channel {}", channel_id), &edit);
+        return Ok(());
+    }
+    channel_id.edit(http, edit).await?;
+    Ok(())
+}
@@ -0,0 +1,431 @@
+use crate::models::error::Error;
+use crate::riot_api::{dd_cdn_url, dd_version, fetch_dd_version};
+use reqwest::Client;
+use serde_json::{json, Map, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+/// Base URL of Community Dragon, for the game data Data Dragon does not provide (e.g. the Arena augments).
+const CDRAGON_BASE_URL: &str = "https://raw.communitydragon.org";
+
+/// Base URL of the Community Dragon CDN, serving the champion art by champion ID (e.g. `MonkeyKing`).
+const CDRAGON_CDN_URL: &str = "https://cdn.communitydragon.org";
+
+/// Path of the game data of the client on Community Dragon, below the patch (e.g. `latest`).
+const CDRAGON_GAME_DATA_PATH: &str = "plugins/rcp-be-lol-game-data/global/default/v1";
+
+/// Whether Data Dragon lags behind the live patch, as detected by `open_dd_json`. The profile icons and champion art
+/// then come from Community Dragon too.
+static DD_LAGGING: AtomicBool = AtomicBool::new(false);
+
+/// Names of the Arena augments by ID, loaded from Community Dragon by `fetch_arena_augments`.
+static ARENA_AUGMENTS: RwLock<Vec<(i64, String)>> = RwLock::new(Vec::new());
+
+/// Names of the queues by ID, loaded from Community Dragon by `fetch_queue_names`.
+static QUEUE_NAMES: RwLock<Vec<(i64, String)>> = RwLock::new(Vec::new());
+
+/// ⚙️ **Trait**: A source of the static game data of the bot: versions, champions, profile icons and champion art.
+///
+/// `open_dd_json` picks the source of the champions: Data Dragon, or Community Dragon when Data Dragon lags behind
+/// the live patch or cannot be reached.
+pub trait StaticDataProvider {
+    /// Name of the source, displayed in the logs.
+    fn name(&self) -> &'static str;
+
+    /// Fetches the latest version of the data, e.g. `14.20.1`.
+    async fn latest_version(&self, client: &Client) -> Result<String, Error>;
+
+    /// Fetches the champions of a version, in the format of the Data Dragon `champion.json`.
+    async fn champions(&self, client: &Client, version: &str) -> Result<Value, Error>;
+
+    /// Builds the URL of a profile icon, e.g. the `profileIconId` of summoner-v4.
+    fn profile_icon_url(&self, version: &str, icon_id: i64) -> String;
+
+    /// Builds the URL of the square icon of a champion, from its Data Dragon ID (e.g. `MonkeyKing`).
+    fn champion_icon_url(&self, version: &str, id_name: &str) -> String;
+
+    /// Builds the URL of the loading screen art of the base skin of a champion, from its Data Dragon ID.
+    fn champion_loading_url(&self, id_name: &str) -> String;
+}
+
+/// ⚙️ **Struct**: Data Dragon, Riot's official static data service.
+pub struct DataDragon;
+
+impl StaticDataProvider for DataDragon {
+    fn name(&self) -> &'static str {
+        "Data Dragon"
+    }
+
+    async fn latest_version(&self, client: &Client) -> Result<String, Error> {
+        fetch_dd_version(client).await
+    }
+
+    async fn champions(&self, client: &Client, version: &str) -> Result<Value, Error> {
+        Ok(client
+            .get(dd_cdn_url(version, "data/en_US/champion.json"))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    fn profile_icon_url(&self, version: &str, icon_id: i64) -> String {
+        dd_cdn_url(version, &format!("img/profileicon/{}.png", icon_id))
+    }
+
+    fn champion_icon_url(&self, version: &str, id_name: &str) -> String {
+        dd_cdn_url(version, &format!("img/champion/{}.png", id_name))
+    }
+
+    fn champion_loading_url(&self, id_name: &str) -> String {
+        dd_cdn_url("", &format!("img/champion/loading/{}_0.jpg", id_name))
+    }
+}
+
+/// ⚙️ **Struct**: Community Dragon, the game files of the live client, usually updated hours before Data Dragon.
+pub struct CommunityDragon;
+
+impl CommunityDragon {
+    /// ⚙️ **Function**: Builds the URL of a file of the latest client game data, e.g. `queues.json`.
+    fn game_data_url(path: &str) -> String {
+        format!(
+            "{}/latest/{}/{}",
+            CDRAGON_BASE_URL, CDRAGON_GAME_DATA_PATH, path
+        )
+    }
+}
+
+impl StaticDataProvider for CommunityDragon {
+    fn name(&self) -> &'static str {
+        "Community Dragon"
+    }
+
+    async fn latest_version(&self, client: &Client) -> Result<String, Error> {
+        let metadata: Value = client
+            .get(format!("{}/latest/content-metadata.json", CDRAGON_BASE_URL))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(metadata["version"]
+            .as_str()
+            .ok_or("Community Dragon returned no version")?
+            .to_string())
+    }
+
+    async fn champions(&self, client: &Client, version: &str) -> Result<Value, Error> {
+        let summary: Value = client
+            .get(Self::game_data_url("champion-summary.json"))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        // Même format que le champion.json de Data Dragon : l'alias de Community Dragon est l'ID de Data Dragon
+        let mut data = Map::new();
+        for champion in summary
+            .as_array()
+            .ok_or("Community Dragon returned no champion")?
+        {
+            let (Some(key), Some(id), Some(name)) = (
+                champion["id"].as_i64().filter(|key| *key > 0),
+                champion["alias"].as_str(),
+                champion["name"].as_str(),
+            ) else {
+                continue;
+            };
+            data.insert(
+                id.to_string(),
+                json!({ "id": id, "key": key.to_string(), "name": name }),
+            );
+        }
+        Ok(json!({ "type": "champion", "version": version, "data": data }))
+    }
+
+    fn profile_icon_url(&self, _version: &str, icon_id: i64) -> String {
+        Self::game_data_url(&format!("profile-icons/{}.jpg", icon_id))
+    }
+
+    fn champion_icon_url(&self, _version: &str, id_name: &str) -> String {
+        format!("{}/latest/champion/{}/square", CDRAGON_CDN_URL, id_name)
+    }
+
+    fn champion_loading_url(&self, id_name: &str) -> String {
+        format!("{}/latest/champion/{}/portrait", CDRAGON_CDN_URL, id_name)
+    }
+}
+
+/// ⚙️ **Function**: Tells whether the patch of a version is more recent than the patch of another one.
+///
+/// # Returns:
+/// - `bool`: e.g. `true` for `14.21.620.1234` against `14.20.1`, `false` for the same patch or unreadable versions.
+fn is_newer_patch(version: &str, than: &str) -> bool {
+    let patch = |version: &str| -> Option<(u32, u32)> {
+        let mut parts = version.split('.');
+        Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+    };
+    match (patch(version), patch(than)) {
+        (Some(version), Some(than)) => version > than,
+        _ => false,
+    }
+}
+
+/// ⚙️ **Function**: Fetches the latest champion data, from Data Dragon or from Community Dragon.
+///
+/// The champions are fetched from Data Dragon, Riot's official static data service, unless Data Dragon lags behind
+/// the live patch or cannot be reached: they are then fetched from Community Dragon, so new champions are known as
+/// soon as the patch is live. The data is returned in the format of the Data Dragon `champion.json` either way.
+///
+/// # Returns:
+/// - `Result<Value, Error>`: A JSON object containing champion data if the request is successful, or an error if
+///   neither source can be reached.
+///
+/// # ⚠️ Notes:
+/// - The champions from Community Dragon only have their `id`, `key` and `name`, which is all `ChampionIndex` needs.
+/// - The source in use is remembered, so `profile_icon_url`, `champion_icon_url` and `champion_loading_url` also take
+///   the newer art from Community Dragon while Data Dragon lags behind.
+/// - The queue names of Community Dragon are refreshed at the same time (see `fetch_queue_names`).
+///
+/// # Example:
+/// ```rust
+/// let dd_json = open_dd_json().await?;
+/// ```
+///
+/// The resulting `dd_json` will contain champion data like:
+/// ```json
+/// {
+///   "type": "champion",
+///   "version": "14.20.1",
+///   "data": {
+///     "Aatrox": {
+///       "id": "Aatrox",
+///       "key": "266",
+///       "name": "Aatrox",
+///       ...
+///     },
+///     ...
+///   }
+/// }
+/// ```
+pub async fn open_dd_json() -> Result<Value, Error> {
+    let client = Client::new();
+    if let Err(e) = fetch_queue_names(&client).await {
+        log::error!("Error fetching the queues from Community Dragon: {:?}", e);
+    }
+    let dd_version = DataDragon.latest_version(&client).await;
+    let cdragon_version = CommunityDragon.latest_version(&client).await;
+    if let Ok(cdragon_version) = &cdragon_version {
+        let lagging = match &dd_version {
+            Ok(dd_version) => is_newer_patch(cdragon_version, dd_version),
+            Err(e) => {
+                log::error!("Data Dragon unreachable, trying Community Dragon: {:?}", e);
+                true
+            }
+        };
+        if lagging {
+            match CommunityDragon.champions(&client, cdragon_version).await {
+                Ok(champions) => {
+                    log::warn!(
+                        "Data Dragon lags behind, champions loaded from {} {}",
+                        CommunityDragon.name(),
+                        cdragon_version
+                    );
+                    DD_LAGGING.store(true, Ordering::Relaxed);
+                    return Ok(champions);
+                }
+                Err(e) => log::error!(
+                    "Error fetching the champions from Community Dragon: {:?}",
+                    e
+                ),
+            }
+        }
+    }
+    let version = dd_version?;
+    let dd_json = DataDragon.champions(&client, &version).await?;
+    DD_LAGGING.store(false, Ordering::Relaxed);
+    log::info!("{} version: {}", DataDragon.name(), version);
+    Ok(dd_json)
+}
+
+/// ⚙️ **Function**: Returns the URL of a profile icon on the latest patch.
+///
+/// # Parameters:
+/// - `icon_id`: The ID of the profile icon, e.g. the `profileIconId` of summoner-v4.
+///
+/// # Returns:
+/// - `Result<String, Error>`: e.g. `https://ddragon.leagueoflegends.com/cdn/14.20.1/img/profileicon/28.png`, or an
+///   error if the version is unknown and Data Dragon cannot be reached.
+///
+/// # Notes:
+/// - While Data Dragon lags behind the live patch (see `open_dd_json`), the icon comes from Community Dragon, which
+///   already has the icons of the new patch.
+pub async fn profile_icon_url(icon_id: i64) -> Result<String, Error> {
+    if DD_LAGGING.load(Ordering::Relaxed) {
+        return Ok(CommunityDragon.profile_icon_url("", icon_id));
+    }
+    Ok(DataDragon.profile_icon_url(&dd_version().await?, icon_id))
+}
+
+/// ⚙️ **Function**: Returns the URL of the square icon of a champion on the latest patch.
+///
+/// # Parameters:
+/// - `id_name`: The Data Dragon ID of the champion, e.g. `MonkeyKing`.
+///
+/// # Returns:
+/// - `Result<String, Error>`: e.g. `https://ddragon.leagueoflegends.com/cdn/14.20.1/img/champion/MonkeyKing.png`, or an
+///   error if the version is unknown and Data Dragon cannot be reached.
+///
+/// # Notes:
+/// - Like `profile_icon_url`, the icon comes from Community Dragon while Data Dragon lags behind the live patch, so new
+///   champions have an icon.
+pub async fn champion_icon_url(id_name: &str) -> Result<String, Error> {
+    if DD_LAGGING.load(Ordering::Relaxed) {
+        return Ok(CommunityDragon.champion_icon_url("", id_name));
+    }
+    Ok(DataDragon.champion_icon_url(&dd_version().await?, id_name))
+}
+
+/// ⚙️ **Function**: Returns the URL of the loading screen art of the base skin of a champion.
+///
+/// # Parameters:
+/// - `id_name`: The Data Dragon ID of the champion, e.g. the `championName` of match-v5.
+///
+/// # Returns:
+/// - `String`: e.g. `https://ddragon.leagueoflegends.com/cdn/img/champion/loading/MonkeyKing_0.jpg`.
+///
+/// # Notes:
+/// - Like `profile_icon_url`, the art comes from Community Dragon while Data Dragon lags behind the live patch.
+pub fn champion_loading_url(id_name: &str) -> String {
+    if DD_LAGGING.load(Ordering::Relaxed) {
+        return CommunityDragon.champion_loading_url(id_name);
+    }
+    DataDragon.champion_loading_url(id_name)
+}
+
+/// ⚙️ **Function**: Fetches the names of the Arena augments from Community Dragon and remembers them.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+///
+/// # Returns:
+/// - `Result<Vec<(i64, String)>, Error>`: The `(ID, name)` of every augment of the latest patch, or an error if
+///   Community Dragon cannot be reached.
+pub async fn fetch_arena_augments(client: &Client) -> Result<Vec<(i64, String)>, Error> {
+    let arena: Value = client
+        .get(format!(
+            "{}/latest/cdragon/arena/en_us.json",
+            CDRAGON_BASE_URL
+        ))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let augments: Vec<(i64, String)> = arena["augments"]
+        .as_array()
+        .ok_or("Community Dragon returned no augment")?
+        .iter()
+        .filter_map(|augment| {
+            Some((
+                augment["id"].as_i64()?,
+                augment["name"].as_str()?.to_string(),
+            ))
+        })
+        .collect();
+    if let Ok(mut known) = ARENA_AUGMENTS.write() {
+        known.clone_from(&augments);
+    }
+    Ok(augments)
+}
+
+/// ⚙️ **Function**: Resolves the names of Arena augments from their IDs.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+/// - `ids`: The augment IDs, e.g. the `playerAugment1..4` fields of a match-v5 participant.
+///
+/// # Returns:
+/// - `Result<Vec<String>, Error>`: The names of the augments, in the same order as `ids`, or an error if they are not
+///   known yet and Community Dragon cannot be reached.
+///
+/// # Example:
+/// ```rust
+/// let names = arena_augment_names(&client, &[1, 205]).await?;
+/// ```
+///
+/// # Notes:
+/// - The augments are fetched once, then again when an unknown ID shows up (new patch). An ID still unknown after
+///   that is shown as `Augment #<id>`.
+pub async fn arena_augment_names(client: &Client, ids: &[i64]) -> Result<Vec<String>, Error> {
+    let mut augments = ARENA_AUGMENTS
+        .read()
+        .map(|known| known.clone())
+        .unwrap_or_default();
+    if ids
+        .iter()
+        .any(|id| !augments.iter().any(|(known_id, _)| known_id == id))
+    {
+        augments = fetch_arena_augments(client).await?;
+    }
+    Ok(ids
+        .iter()
+        .map(|id| {
+            augments
+                .iter()
+                .find(|(known_id, _)| known_id == id)
+                .map(|(_, name)| name.clone())
+                .unwrap_or_else(|| format!("Augment #{}", id))
+        })
+        .collect())
+}
+
+/// ⚙️ **Function**: Fetches the names of the queues from Community Dragon and remembers them for `queue_name`.
+///
+/// # Parameters:
+/// - `client`: An instance of the `reqwest::Client` used to send HTTP requests.
+///
+/// # Returns:
+/// - `Result<(), Error>`: An error if Community Dragon cannot be reached. The queues already known are then kept.
+pub async fn fetch_queue_names(client: &Client) -> Result<(), Error> {
+    let queues: Value = client
+        .get(CommunityDragon::game_data_url("queues.json"))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    // Le fichier est indexé par ID de file, ou est une liste de files selon les versions
+    let names: Vec<(i64, String)> = match &queues {
+        Value::Object(queues) => queues
+            .iter()
+            .filter_map(|(id, queue)| Some((id.parse().ok()?, queue["name"].as_str()?.to_string())))
+            .collect(),
+        Value::Array(queues) => queues
+            .iter()
+            .filter_map(|queue| Some((queue["id"].as_i64()?, queue["name"].as_str()?.to_string())))
+            .collect(),
+        _ => return Err("Community Dragon returned no queue".into()),
+    };
+    if let Ok(mut known) = QUEUE_NAMES.write() {
+        *known = names
+            .into_iter()
+            .filter(|(_, name)| !name.is_empty())
+            .collect();
+    }
+    Ok(())
+}
+
+/// ⚙️ **Function**: Returns the name of a queue from the names loaded by `fetch_queue_names`.
+///
+/// # Returns:
+/// - `Option<String>`: e.g. `Arena` for `1700`, or `None` if the queue is unknown or the names were not loaded.
+pub fn queue_name(queue_id: i64) -> Option<String> {
+    QUEUE_NAMES.read().ok().and_then(|names| {
+        names
+            .iter()
+            .find(|(id, _)| *id == queue_id)
+            .map(|(_, name)| name.clone())
+    })
+}
@@ -1,12 +1,15 @@
-use crate::models::constants::QUEUE_ID_MAP;
-use crate::models::data::EmojiId;
+use crate::models::champion_index::ChampionIndex;
+use crate::models::constants::{CHAMPION_ALIASES, CHAMPION_DATA_SOURCE, QUEUE_ID_MAP};
+use crate::models::data::{EmojiId, ShardConfig};
 use crate::models::region::Region;
-use chrono::{NaiveDateTime, Utc};
+use crate::static_data::queue_name;
+use chrono::{Datelike, Utc};
 use mongodb::bson::doc;
 use mongodb::Collection;
 use serde::de::value::Error;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::ops::Range;
 
 /// ⚙️ **Function**: Checks if a given queue ID corresponds to a valid game mode.
 ///
@@ -39,52 +42,123 @@ pub fn is_valid_game_mode(queue_id: i64) -> bool {
     QUEUE_ID_MAP.iter().any(|&(id, _)| id == queue_id)
 }
 
-/// ⚙️ **Function**: Calculates the time elapsed since a game ended and returns it as a human-readable string.
+/// ⚙️ **Function**: Formats a UNIX timestamp with Discord's timestamp markup.
 ///
-/// This function computes the duration between the game's end timestamp and the current time. It returns a string
-/// representing how much time has passed, formatted in seconds, minutes, hours, days, months, or years, depending on the duration.
+/// Discord renders the markup in the timezone and language of each user, and keeps relative times up to date.
 ///
 /// # Parameters:
-/// - `game_end_timestamp`: A UNIX timestamp (in milliseconds) representing when the game ended.
+/// - `unix_seconds`: A UNIX timestamp, in seconds.
+/// - `style`: The Discord timestamp style, e.g. `R` for a relative time ("2 hours ago", "in 5 hours"), `f` for a date
+///   and time, `t` for a time only.
 ///
 /// # Returns:
-/// - `String`: A human-readable string representing how long ago the game ended (e.g., "5 minutes ago", "2 hours ago").
+/// - `String`: The markup, e.g. `<t:1625000000:R>`.
+///
+/// # Example:
+/// ```rust
+/// assert_eq!(discord_timestamp(1625000000, 'R'), "<t:1625000000:R>");
+/// ```
 ///
 /// # ⚠️ Notes:
-/// - The function converts the timestamp from milliseconds to seconds before performing the calculation.
-/// - If the duration is less than 60 seconds, the result will be in seconds. If it's less than 24 hours, the result will be in minutes or hours, and so on.
+/// - The markup is rendered in messages, embed descriptions and field values, but not in embed titles, field names,
+///   authors or footers, where it is shown as is.
+pub fn discord_timestamp(unix_seconds: i64, style: char) -> String {
+    format!("<t:{}:{}>", unix_seconds, style)
+}
+
+/// ⚙️ **Function**: Tells how long ago a game ended, with Discord's relative timestamp markup.
+///
+/// # Parameters:
+/// - `game_end_timestamp`: A UNIX timestamp (in milliseconds) representing when the game ended.
+///
+/// # Returns:
+/// - `String`: The markup of a relative time (see `discord_timestamp`), shown by Discord as e.g. "2 hours ago".
 ///
 /// # Example:
 /// ```rust
 /// let time_elapsed = time_since_game_ended(1625000000000);
-/// println!("{}", time_elapsed);  // Output: "5 hours ago"
+/// println!("{}", time_elapsed);  // Output: "<t:1625000000:R>"
 /// ```
+pub fn time_since_game_ended(game_end_timestamp: u64) -> String {
+    discord_timestamp((game_end_timestamp / 1000) as i64, 'R')
+}
+
+/// ⚙️ **Function**: Reduces a Data Dragon version to its patch number.
 ///
-/// The resulting string will vary depending on the duration since the game ended:
-/// ```text
-/// "2 minutes ago"
-/// "5 days ago"
-/// "1 year ago"
+/// Data Dragon versions look like `14.20.1`; players refer to the patch as `14.20`, so only the
+/// major and minor components are kept.
+///
+/// # Parameters:
+/// - `version`: The Data Dragon version string.
+///
+/// # Returns:
+/// - `String`: The patch number, or an empty string if the version is empty.
+///
+/// # Example:
+/// ```rust
+/// assert_eq!(patch_from_version("14.20.1"), "14.20");
 /// ```
-pub fn time_since_game_ended(game_end_timestamp: u64) -> String {
-    #[allow(deprecated)]
-    let game_end_time = NaiveDateTime::from_timestamp_opt((game_end_timestamp / 1000) as i64, 0)
-        .expect("Invalid timestamp");
-    let now = Utc::now().naive_utc();
-    let duration = now.signed_duration_since(game_end_time);
+pub fn patch_from_version(version: &str) -> String {
+    version.split('.').take(2).collect::<Vec<_>>().join(".")
+}
 
-    if duration.num_seconds() < 60 {
-        format!("{} seconds ago", duration.num_seconds())
-    } else if duration.num_minutes() < 60 {
-        format!("{} minutes ago", duration.num_minutes())
-    } else if duration.num_hours() < 24 {
-        format!("{} hours ago", duration.num_hours())
-    } else if duration.num_days() < 30 {
-        format!("{} days ago", duration.num_days())
-    } else if duration.num_days() < 365 {
-        format!("{} months ago", duration.num_days() / 30)
+/// ⚙️ **Function**: Returns the current ISO week, used by the weekly jobs to run once per week.
+///
+/// # Returns:
+/// - `String`: The week, e.g. `2024-W41`. Weeks start on Monday (UTC).
+pub fn current_week() -> String {
+    let week = Utc::now().iso_week();
+    format!("{}-W{:02}", week.year(), week.week())
+}
+
+/// ⚙️ **Function**: Returns the start of the current week, Monday at 00:00 UTC.
+///
+/// # Returns:
+/// - `i64`: The UNIX timestamp (in seconds) of the start of the week returned by `current_week`.
+pub fn current_week_start() -> i64 {
+    let today = Utc::now().date_naive();
+    let monday = today - chrono::Duration::days(today.weekday().num_days_from_monday() as i64);
+    monday
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc()
+        .timestamp()
+}
+
+/// ⚙️ **Function**: Builds the freshness line shown under champion statistics.
+///
+/// # Parameters:
+/// - `source`: The website the statistics were scraped from.
+/// - `patch`: The patch the statistics were scraped on.
+/// - `updated_at`: UNIX timestamp (in seconds) of the scrape.
+///
+/// # Returns:
+/// - `String`: A line such as "Data from League of Graphs, updated 6h ago (patch 14.20)".
+///
+/// # Notes:
+/// - Documents scraped before these fields existed have no timestamp; the line then reads "update time unknown".
+pub fn champion_data_freshness(source: &str, patch: &str, updated_at: i64) -> String {
+    let source = if source.is_empty() {
+        CHAMPION_DATA_SOURCE
     } else {
-        format!("{} years ago", duration.num_days() / 365)
+        source
+    };
+    let age = if updated_at > 0 {
+        let elapsed = (Utc::now().timestamp() - updated_at).max(0);
+        if elapsed < 3600 {
+            format!("updated {}m ago", elapsed / 60)
+        } else if elapsed < 86400 {
+            format!("updated {}h ago", elapsed / 3600)
+        } else {
+            format!("updated {}d ago", elapsed / 86400)
+        }
+    } else {
+        "update time unknown".to_string()
+    };
+    if patch.is_empty() {
+        format!("Data from {}, {}", source, age)
+    } else {
+        format!("Data from {}, {} (patch {})", source, age, patch)
     }
 }
 
@@ -195,6 +269,24 @@ pub fn region_to_string(region: &Region) -> String {
     .to_string()
 }
 
+/// ⚙️ **Function**: Finds the region matching a Riot server identifier, the reverse of `region_to_string`.
+///
+/// # Parameters:
+/// - `server`: The server identifier (e.g., `euw1`, `na1`, `kr`).
+///
+/// # Returns:
+/// - `Option<Region>`: The matching region, `None` if the identifier is unknown.
+///
+/// # Example:
+/// ```rust
+/// assert!(matches!(region_from_string("na1"), Some(Region::NA)));
+/// ```
+pub fn region_from_string(server: &str) -> Option<Region> {
+    Region::ALL
+        .into_iter()
+        .find(|region| region_to_string(region) == server)
+}
+
 /// ⚙️ **Function**: Converts a duration in seconds into a tuple representing minutes and seconds.
 ///
 /// This function takes a duration in seconds and converts it into a more human-readable format, returning
@@ -230,6 +322,71 @@ pub fn seconds_to_time(seconds: u64) -> (String, String) {
     }
     (game_duration_minutes.to_string(), game_duration_seconds_str)
 }
+/// Minimum time, in seconds, a participant must have missed to be considered an AFK or a leaver.
+const LEAVER_MIN_MISSED_SECONDS: u64 = 180;
+
+/// ⚙️ **Function**: Finds the participants of a match who went AFK or left before the end of the game.
+///
+/// # Parameters:
+/// - `match_info`: The match-v5 data of the match.
+///
+/// # Returns:
+/// - `Vec<&Value>`: The participants whose `timePlayed` is at least 3 minutes shorter than the `gameDuration`.
+///
+/// # Example:
+/// ```rust
+/// let leavers = find_leavers(&match_info);
+/// println!("{} player(s) left the game", leavers.len());
+/// ```
+///
+/// # Notes:
+/// - Remakes (`gameEndedInEarlySurrender`) have no leaver: the game does not count.
+/// - `timePlayed` is the time the participant stayed connected, so it approximates when they left.
+pub fn find_leavers(match_info: &Value) -> Vec<&Value> {
+    let game_duration = match_info["info"]["gameDuration"].as_u64().unwrap_or(0);
+    let Some(participants) = match_info["info"]["participants"].as_array() else {
+        return vec![];
+    };
+    if participants
+        .iter()
+        .any(|p| p["gameEndedInEarlySurrender"].as_bool().unwrap_or(false))
+    {
+        return vec![];
+    }
+    participants
+        .iter()
+        .filter(|p| {
+            p["timePlayed"]
+                .as_u64()
+                .is_some_and(|time_played| time_played + LEAVER_MIN_MISSED_SECONDS <= game_duration)
+        })
+        .collect()
+}
+
+/// ⚙️ **Function**: Describes a leaver found by `find_leavers` from the point of view of a team.
+///
+/// # Parameters:
+/// - `leaver`: The participant who left the game.
+/// - `team_id`: The team of the player the match is displayed for.
+///
+/// # Returns:
+/// - `String`: A line such as `Enemy Yasuo AFK at 8:00` or `Ally Lux AFK at 12:41`.
+pub fn describe_leaver(leaver: &Value, team_id: i64) -> String {
+    let side = if leaver["teamId"].as_i64() == Some(team_id) {
+        "Ally"
+    } else {
+        "Enemy"
+    };
+    let (minutes, seconds) = seconds_to_time(leaver["timePlayed"].as_u64().unwrap_or(0));
+    format!(
+        "{} {} AFK at {}:{}",
+        side,
+        leaver["championName"].as_str().unwrap_or("Unknown"),
+        minutes,
+        seconds
+    )
+}
+
 /// ⚙️ **Function**: Retrieves a custom emoji string based on role and name from a MongoDB collection.
 ///
 /// This asynchronous function searches a MongoDB collection for a custom emoji corresponding to a specific role and name.
@@ -285,7 +442,7 @@ pub async fn get_emoji(
 /// - `queue_id`: An `i64` representing the queue ID for which the game mode is being queried.
 ///
 /// # Returns:
-/// - `&'static str`: Returns a string slice representing the game mode name corresponding to the queue ID, or "Unknown" if the queue ID is not found.
+/// - `String`: Returns the game mode name corresponding to the queue ID, or "Unknown" if the queue ID is not found.
 ///
 /// # Example:
 /// This function can be used to retrieve the game mode based on the queue ID returned from match data:
@@ -299,46 +456,208 @@ pub async fn get_emoji(
 /// # Notes:
 /// - The function iterates over the `QUEUE_ID_MAP`, a predefined list of tuples mapping queue IDs to game modes.
 /// - If the queue ID is found in the map, the corresponding game mode is returned immediately.
+/// - Queues missing from the map (e.g. new or rotating game modes) are named from the Community Dragon queue data
+///   (see `queue_name`).
 /// - If the queue ID is not found, the function defaults to returning "Unknown".
-pub fn get_game_mode(queue_id: i64) -> &'static str {
+pub fn get_game_mode(queue_id: i64) -> String {
     for &(id, mode) in QUEUE_ID_MAP.iter() {
         if id == queue_id {
-            return mode;
+            return mode.to_string();
         }
     }
-    "Unknown"
+    queue_name(queue_id).unwrap_or_else(|| "Unknown".to_string())
 }
 
-pub fn get_champion_names(dd_json: &Value) -> Vec<String> {
-    // Obtenir le champ "data" qui contient les champions
-    let data = &dd_json["data"];
+/// ⚙️ **Function**: Normalizes a champion nickname so that `Miss Fortune`, `miss-fortune` and `MissFortune` match.
+///
+/// # Returns:
+/// - `String`: The lowercase letters and digits of `name`.
+pub fn normalize_champion_alias(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
 
-    // Vérifier que "data" est un objet
-    if let Some(champion_map) = data.as_object() {
-        // Itérer sur les valeurs (données des champions)
-        champion_map
-            .values()
-            .filter_map(|champion| champion["name"].as_str().map(|s| s.to_string()))
-            .collect()
-    } else {
-        vec![]
+/// ⚙️ **Function**: Resolves a champion nickname, e.g. `mf` or `asol`, to the name of the champion.
+///
+/// # Parameters:
+/// - `champions`: The champion lookups built from Data Dragon.
+/// - `guild_aliases`: The nicknames added by the server, as normalized nickname to Data Dragon ID.
+/// - `input`: The text typed by the user.
+///
+/// # Returns:
+/// - `Option<String>`: The name of the champion, or `None` if `input` is not a known nickname.
+///
+/// # Example:
+/// ```rust
+/// let name = resolve_champion_alias(&champions, &HashMap::new(), "mf");
+/// assert_eq!(name.as_deref(), Some("Miss Fortune"));
+/// ```
+///
+/// # Notes:
+/// - The nicknames of the server are checked first, then the bundled ones (`CHAMPION_ALIASES`), then the Data Dragon
+///   IDs, which differ from the names for a few champions (`MonkeyKing` for Wukong).
+pub fn resolve_champion_alias(
+    champions: &ChampionIndex,
+    guild_aliases: &HashMap<String, String>,
+    input: &str,
+) -> Option<String> {
+    let alias = normalize_champion_alias(input);
+    if alias.is_empty() {
+        return None;
     }
+    let champion_id = guild_aliases
+        .get(&alias)
+        .map(String::as_str)
+        .or_else(|| {
+            CHAMPION_ALIASES
+                .iter()
+                .find(|(nickname, _)| *nickname == alias)
+                .map(|(_, champion_id)| *champion_id)
+        })
+        .or_else(|| champions.id_from_name(&alias))?;
+    champions.name(champion_id).map(str::to_string)
 }
 
-pub fn get_champion_id(dd_json: &Value, name: &str) -> Option<String> {
-    let data = &dd_json["data"];
-    if let Some(champion_map) = data.as_object() {
-        for (_, champion_value) in champion_map {
-            // Obtenir le nom du champion
-            if let Some(champion_name) = champion_value["name"].as_str() {
-                if champion_name.eq_ignore_ascii_case(name) {
-                    if let Some(champion_id) = champion_value["id"].as_str() {
-                        return Some(champion_id.to_string());
-                    }
-                }
+/// ⚙️ **Function**: Runs a startup step until it succeeds, waiting twice as long after each failure.
+///
+/// # Parameters:
+/// - `name`: The name of the step, used in the logs (e.g., `Data Dragon`).
+/// - `attempts`: The maximum number of attempts.
+/// - `step`: The step to run.
+///
+/// # Returns:
+/// - `Result<T, Error>`: The result of the first successful attempt, or the error of the last one.
+///
+/// # Example:
+/// ```rust
+/// let dd_json = retry_with_backoff("Data Dragon", 4, open_dd_json).await?;
+/// ```
+///
+/// # Notes:
+/// - The first retry waits 1 second, then 2, 4, 8... seconds.
+pub async fn retry_with_backoff<T, F, Fut>(
+    name: &str,
+    attempts: u32,
+    mut step: F,
+) -> Result<T, crate::models::error::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, crate::models::error::Error>>,
+{
+    let mut delay = std::time::Duration::from_secs(1);
+    let mut attempt = 1;
+    loop {
+        match step().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < attempts => {
+                log::warn!(
+                    "{} failed (attempt {}/{}): {}. Retrying in {}s.",
+                    name,
+                    attempt,
+                    attempts,
+                    e,
+                    delay.as_secs()
+                );
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
             }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// ⚙️ **Function**: Reads the sharding configuration of this instance from the secret store values.
+///
+/// # Parameters:
+/// - `shard_count`: The optional `SHARD_COUNT` secret. When absent, Discord's recommended shard count is used.
+/// - `instance_index`: The optional `INSTANCE_INDEX` secret (zero-based), defaults to `0`.
+/// - `instance_count`: The optional `INSTANCE_COUNT` secret, defaults to `1`.
+///
+/// # Returns:
+/// - `ShardConfig`: The parsed configuration. Invalid values fall back to a single instance running every shard.
+///
+/// # Example:
+/// ```rust
+/// let shard_config = parse_shard_config(
+///     secret_store.get("SHARD_COUNT"),
+///     secret_store.get("INSTANCE_INDEX"),
+///     secret_store.get("INSTANCE_COUNT"),
+/// );
+/// ```
+pub fn parse_shard_config(
+    shard_count: Option<String>,
+    instance_index: Option<String>,
+    instance_count: Option<String>,
+) -> ShardConfig {
+    let total_shards = shard_count
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .filter(|count| *count > 0);
+    let instance_count = instance_count
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .filter(|count| *count > 0)
+        .unwrap_or(1);
+    let instance_index = instance_index
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .filter(|index| *index < instance_count)
+        .unwrap_or(0);
+    ShardConfig {
+        total_shards,
+        instance_index,
+        instance_count,
+    }
+}
+
+/// ⚙️ **Function**: Computes the range of shards started by this instance.
+///
+/// The shards are split in contiguous blocks of `total_shards / instance_count` shards rounded up, so the last
+/// instance gets fewer shards when they do not divide evenly (or none with more instances than shards).
+///
+/// # Parameters:
+/// - `shard_config`: The sharding configuration of this instance.
+/// - `total_shards`: The total number of shards of the bot.
+///
+/// # Returns:
+/// - `Range<u32>`: The shard IDs handled by this instance.
+///
+/// # Example:
+/// ```rust
+/// // 10 shards, instance 1 of 3 -> shards 4..8
+/// let range = shard_range(&shard_config, 10);
+/// ```
+pub fn shard_range(shard_config: &ShardConfig, total_shards: u32) -> Range<u32> {
+    let per_instance = total_shards.div_ceil(shard_config.instance_count);
+    let start = (shard_config.instance_index * per_instance).min(total_shards);
+    let end = (start + per_instance).min(total_shards);
+    start..end
+}
+
+/// ⚙️ **Function**: Checks whether a guild belongs to this instance and must be processed by the follow loop.
+///
+/// # Parameters:
+/// - `shard_config`: The sharding configuration of this instance.
+/// - `guild_id`: The guild ID as stored in the database.
+///
+/// # Returns:
+/// - `bool`: `true` if this instance is responsible for the guild.
+///
+/// # Notes:
+/// - With a fixed `SHARD_COUNT`, the guild is handled by the instance running its shard (`(guild_id >> 22) % shard_count`, as Discord does).
+/// - Without it, guilds are partitioned by the same hash over the number of instances so two instances never process the same follow.
+pub fn is_guild_handled(shard_config: &ShardConfig, guild_id: &str) -> bool {
+    if shard_config.instance_count <= 1 {
+        return true;
+    }
+    let guild_hash = guild_id.parse::<u64>().unwrap_or(0) >> 22;
+    match shard_config.total_shards {
+        Some(total_shards) => {
+            let shard_id = (guild_hash % total_shards as u64) as u32;
+            shard_range(shard_config, total_shards).contains(&shard_id)
+        }
+        None => {
+            guild_hash % shard_config.instance_count as u64 == shard_config.instance_index as u64
         }
     }
-    // Si aucun champion correspondant n'est trouvé, retourner None
-    None
 }